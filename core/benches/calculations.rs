@@ -20,6 +20,7 @@ fn benchmark_full_calculation(c: &mut Criterion) {
         post_tax_deductions: dec!(0),
         traditional_401k: dec!(10000),
         roth_401k: dec!(0),
+        ..Default::default()
     };
 
     c.bench_function("full_calculation_ca_100k", |b| {
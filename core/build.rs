@@ -2,4 +2,49 @@
 fn main() {
     // UniFFI binding generation now uses proc-macro approach
     // The UDL file is kept for reference but not used in build
+
+    #[cfg(feature = "grpc")]
+    grpc::generate();
+}
+
+/// gRPC stubs for `takehome.v1.TakeHome`
+///
+/// The messages are hand-written prost types in `src/grpc.rs`, so only the
+/// service is generated here and no `protoc` is needed. Keep the methods in
+/// sync with `proto/takehome.proto`.
+#[cfg(feature = "grpc")]
+mod grpc {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    fn method(name: &str, route: &str, input: &str, output: &str) -> Method {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("super::{input}"))
+            .output_type(format!("super::{output}"))
+            .codec_path("tonic::codec::ProstCodec")
+            .build()
+    }
+
+    pub fn generate() {
+        println!("cargo:rerun-if-changed=build.rs");
+        let service = Service::builder()
+            .name("TakeHome")
+            .package("takehome.v1")
+            .method(method("calculate", "Calculate", "TaxInput", "TaxResult"))
+            .method(method(
+                "compare",
+                "Compare",
+                "CompareRequest",
+                "ScenarioComparison",
+            ))
+            .method(method(
+                "list_states",
+                "ListStates",
+                "ListStatesRequest",
+                "ListStatesResponse",
+            ))
+            .build();
+        Builder::new().compile(&[service]);
+    }
 }
@@ -0,0 +1,463 @@
+//! Benefit phase-out cliff detection
+//!
+//! Take-home pay never falls as wages rise, but resources including
+//! income-tested benefits can: the Saver's Credit drops by tiers, Medicare
+//! IRMAA surcharges start in steps, and without the enhanced premium tax
+//! credit the ACA subsidy ends at 400% of poverty. The detector evaluates a
+//! range of gross incomes and reports where earning more leaves a household
+//! with less. The EITC and IRMAA follow the engine's year; the Saver's
+//! Credit uses 2024 parameters.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::credits::earned_income::EarnedIncomeCreditCalculator;
+use crate::calculators::irmaa::IrmaaCalculator;
+use crate::calculators::premium_tax_credit::{PremiumTaxCreditCalculator, PremiumTaxCreditInput};
+use crate::data::TaxDataProvider;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+use crate::models::rate::Rate;
+use crate::models::tax::FilingStatus;
+
+/// Default distance between evaluated incomes
+pub const DEFAULT_SCAN_STEP: Decimal = dec!(1000);
+
+/// Saver's Credit contributions counted per person
+const SAVERS_CREDIT_CONTRIBUTION_LIMIT: Decimal = dec!(2000);
+
+/// Saver's Credit AGI ceilings for the 50%, 20%, and 10% rates
+const SAVERS_CREDIT_JOINT: [Decimal; 3] = [dec!(46000), dec!(50000), dec!(76500)];
+const SAVERS_CREDIT_HEAD_OF_HOUSEHOLD: [Decimal; 3] = [dec!(34500), dec!(37500), dec!(57375)];
+const SAVERS_CREDIT_OTHER: [Decimal; 3] = [dec!(23000), dec!(25000), dec!(38250)];
+const SAVERS_CREDIT_RATES: [Decimal; 3] = [dec!(0.50), dec!(0.20), dec!(0.10)];
+
+/// Benefits and surcharges that depend on income
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Benefit {
+    EarnedIncomeCredit,
+    SaversCredit,
+    PremiumTaxCredit,
+    /// Medicare income-related monthly adjustment amount
+    Irmaa,
+}
+
+impl Benefit {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Benefit::EarnedIncomeCredit => "Earned Income Credit",
+            Benefit::SaversCredit => "Saver's Credit",
+            Benefit::PremiumTaxCredit => "ACA Premium Tax Credit",
+            Benefit::Irmaa => "Medicare IRMAA",
+        }
+    }
+}
+
+/// What the household could receive, beyond the wage calculation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenefitProfile {
+    /// Children qualifying for the EITC
+    pub qualifying_children: u32,
+    /// Tax household size, for the premium tax credit
+    pub household_size: u32,
+    /// Annual benchmark silver premium; `None` without marketplace coverage
+    pub benchmark_premium: Option<Decimal>,
+    /// People on Medicare, each owing IRMAA surcharges
+    pub medicare_enrollees: u32,
+    /// IRA contributions counted toward the Saver's Credit with the 401(k)
+    pub ira_contributions: Decimal,
+}
+
+/// Annual benefit amounts at one income
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenefitValues {
+    pub earned_income_credit: Decimal,
+    pub savers_credit: Decimal,
+    pub premium_tax_credit: Decimal,
+    /// Surcharge paid, a cost rather than a benefit
+    pub irmaa_surcharge: Decimal,
+}
+
+impl BenefitValues {
+    /// Benefits less surcharges
+    pub fn net(&self) -> Decimal {
+        self.earned_income_credit + self.savers_credit + self.premium_tax_credit
+            - self.irmaa_surcharge
+    }
+
+    /// Benefits that are worse here than in `lower`
+    fn reduced_from(&self, lower: &BenefitValues) -> Vec<Benefit> {
+        [
+            (
+                Benefit::EarnedIncomeCredit,
+                self.earned_income_credit < lower.earned_income_credit,
+            ),
+            (
+                Benefit::SaversCredit,
+                self.savers_credit < lower.savers_credit,
+            ),
+            (
+                Benefit::PremiumTaxCredit,
+                self.premium_tax_credit < lower.premium_tax_credit,
+            ),
+            (Benefit::Irmaa, self.irmaa_surcharge > lower.irmaa_surcharge),
+        ]
+        .into_iter()
+        .filter_map(|(benefit, reduced)| reduced.then_some(benefit))
+        .collect()
+    }
+}
+
+/// Resources at one gross income
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcePoint {
+    pub gross_income: Decimal,
+    pub take_home: Decimal,
+    pub benefits: BenefitValues,
+    /// Take-home pay plus benefits, less surcharges
+    pub net_resources: Decimal,
+}
+
+/// An income range over which earning more reduces net resources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenefitCliff {
+    pub gross_income_from: Decimal,
+    pub gross_income_to: Decimal,
+    pub net_resources_lost: Decimal,
+    /// Benefits lost or surcharges added across the range
+    pub causes: Vec<Benefit>,
+}
+
+impl BenefitCliff {
+    /// Share of the raise lost, above 100% on a cliff
+    pub fn effective_marginal_rate(&self) -> Rate {
+        let raise = self.gross_income_to - self.gross_income_from;
+        Rate::of(raise + self.net_resources_lost, raise)
+    }
+}
+
+/// Resources across an income range and the cliffs within it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliffScan {
+    pub points: Vec<ResourcePoint>,
+    pub cliffs: Vec<BenefitCliff>,
+}
+
+impl CliffScan {
+    /// Cliff costing the most
+    pub fn worst(&self) -> Option<&BenefitCliff> {
+        self.cliffs.iter().max_by_key(|c| c.net_resources_lost)
+    }
+}
+
+/// Scans gross incomes for benefit cliffs
+pub struct BenefitCliffDetector<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> BenefitCliffDetector<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Evaluate `input` at gross incomes from `from` to `to` in `step`
+    /// increments, keeping everything but the wages fixed
+    pub fn scan(
+        &self,
+        input: &TaxCalculationInput,
+        profile: &BenefitProfile,
+        from: Decimal,
+        to: Decimal,
+        step: Decimal,
+    ) -> CliffScan {
+        let step = if step > Decimal::ZERO {
+            step
+        } else {
+            DEFAULT_SCAN_STEP
+        };
+        let mut points = Vec::new();
+        let mut gross = from.max(Decimal::ZERO);
+        while gross <= to {
+            points.push(self.evaluate(input, profile, gross));
+            gross += step;
+        }
+
+        let mut cliffs: Vec<BenefitCliff> = Vec::new();
+        for pair in points.windows(2) {
+            let (lower, higher) = (&pair[0], &pair[1]);
+            let lost = lower.net_resources - higher.net_resources;
+            if lost <= Decimal::ZERO {
+                continue;
+            }
+            let causes = higher.benefits.reduced_from(&lower.benefits);
+            match cliffs.last_mut() {
+                Some(cliff) if cliff.gross_income_to == lower.gross_income => {
+                    cliff.gross_income_to = higher.gross_income;
+                    cliff.net_resources_lost += lost;
+                    for cause in causes {
+                        if !cliff.causes.contains(&cause) {
+                            cliff.causes.push(cause);
+                        }
+                    }
+                },
+                _ => cliffs.push(BenefitCliff {
+                    gross_income_from: lower.gross_income,
+                    gross_income_to: higher.gross_income,
+                    net_resources_lost: lost,
+                    causes,
+                }),
+            }
+        }
+
+        CliffScan { points, cliffs }
+    }
+
+    /// Take-home pay and benefits at one gross income
+    pub fn evaluate(
+        &self,
+        input: &TaxCalculationInput,
+        profile: &BenefitProfile,
+        gross_income: Decimal,
+    ) -> ResourcePoint {
+        let input = TaxCalculationInput {
+            gross_income,
+            ..input.clone()
+        };
+        let result = self.engine.calculate(&input);
+        let benefits = self.benefits(&input, &result, profile);
+        // The profile's children set the EITC here, so leave out the
+        // engine's own
+        let take_home = result.income.net - result.tax_breakdown.credits.earned_income_credit;
+        ResourcePoint {
+            gross_income,
+            take_home,
+            net_resources: take_home + benefits.net(),
+            benefits,
+        }
+    }
+
+    fn benefits(
+        &self,
+        input: &TaxCalculationInput,
+        result: &TaxCalculationResult,
+        profile: &BenefitProfile,
+    ) -> BenefitValues {
+        let agi = result.adjusted_gross_income;
+        let premium_tax_credit = profile
+            .benchmark_premium
+            .map(|benchmark_premium| {
+                let untaxed_social_security = input.retirement_income.social_security_benefits
+                    - result.taxable_social_security;
+                PremiumTaxCreditCalculator::new(self.engine.data_provider())
+                    .calculate(
+                        &PremiumTaxCreditInput {
+                            magi: agi + untaxed_social_security,
+                            household_size: profile.household_size.max(1),
+                            state: input.state,
+                            benchmark_premium,
+                            enrolled_premium: None,
+                        },
+                        self.engine.year(),
+                    )
+                    .credit
+            })
+            .unwrap_or(Decimal::ZERO);
+
+        BenefitValues {
+            earned_income_credit: EarnedIncomeCreditCalculator::credit(
+                input.gross_income,
+                agi,
+                input.filing_status,
+                profile.qualifying_children,
+                self.engine.year(),
+            ),
+            savers_credit: savers_credit(
+                agi,
+                input.filing_status,
+                input.traditional_401k + input.roth_401k + profile.ira_contributions,
+                result.tax_breakdown.federal.tax,
+            ),
+            premium_tax_credit: premium_tax_credit.round_dp(2),
+            irmaa_surcharge: irmaa_surcharge(
+                self.engine.data_provider(),
+                agi,
+                input.filing_status,
+                profile.medicare_enrollees,
+                self.engine.year(),
+            ),
+        }
+    }
+}
+
+/// Saver's Credit on retirement contributions, limited to income tax owed
+pub fn savers_credit(
+    agi: Decimal,
+    filing_status: FilingStatus,
+    contributions: Decimal,
+    income_tax: Decimal,
+) -> Decimal {
+    let (ceilings, people) = match filing_status {
+        FilingStatus::MarriedFilingJointly => (SAVERS_CREDIT_JOINT, 2),
+        FilingStatus::HeadOfHousehold => (SAVERS_CREDIT_HEAD_OF_HOUSEHOLD, 1),
+        _ => (SAVERS_CREDIT_OTHER, 1),
+    };
+    let Some(rate) = ceilings
+        .iter()
+        .zip(SAVERS_CREDIT_RATES)
+        .find(|(ceiling, _)| agi <= **ceiling)
+        .map(|(_, rate)| rate)
+    else {
+        return Decimal::ZERO;
+    };
+    let eligible = contributions
+        .max(Decimal::ZERO)
+        .min(SAVERS_CREDIT_CONTRIBUTION_LIMIT * Decimal::from(people));
+    (eligible * rate)
+        .min(income_tax.max(Decimal::ZERO))
+        .round_dp(2)
+}
+
+/// Annual IRMAA surcharges for every Medicare enrollee in the household,
+/// set by MAGI earned in `income_year`
+pub fn irmaa_surcharge(
+    data_provider: &dyn TaxDataProvider,
+    magi: Decimal,
+    filing_status: FilingStatus,
+    enrollees: u32,
+    income_year: u32,
+) -> Decimal {
+    IrmaaCalculator::new(data_provider)
+        .estimate(magi, filing_status, income_year)
+        .annual_surcharge
+        * Decimal::from(enrollees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_benefit_formulas() {
+        let single = FilingStatus::Single;
+        let eitc =
+            |earned, status| EarnedIncomeCreditCalculator::credit(earned, earned, status, 1, 2024);
+        assert_eq!(eitc(dec!(20000), single), dec!(4213));
+        assert_eq!(eitc(dec!(30000), single), dec!(3049.66));
+        assert_eq!(
+            eitc(dec!(30000), FilingStatus::MarriedFilingSeparately),
+            dec!(0)
+        );
+
+        assert_eq!(
+            savers_credit(dec!(22000), single, dec!(3000), dec!(5000)),
+            dec!(1000)
+        );
+        assert_eq!(
+            savers_credit(dec!(24000), single, dec!(3000), dec!(5000)),
+            dec!(400)
+        );
+        assert_eq!(
+            savers_credit(dec!(22000), single, dec!(3000), dec!(300)),
+            dec!(300)
+        );
+        assert_eq!(
+            savers_credit(dec!(40000), single, dec!(3000), dec!(5000)),
+            dec!(0)
+        );
+
+        // 2022 income sets the 2024 surcharges
+        let data = setup();
+        let irmaa = |magi, status, enrollees| irmaa_surcharge(&data, magi, status, enrollees, 2022);
+        assert_eq!(irmaa(dec!(103000), single, 1), dec!(0));
+        assert_eq!(irmaa(dec!(103001), single, 1), dec!(993.60));
+        assert_eq!(
+            irmaa(dec!(300000), FilingStatus::MarriedFilingJointly, 2),
+            dec!(4992)
+        );
+
+        // Separate filers jump straight to $458.50 a month, then $500.30
+        let separate = FilingStatus::MarriedFilingSeparately;
+        assert_eq!(irmaa(dec!(103000), separate, 1), dec!(0));
+        assert_eq!(irmaa(dec!(150000), separate, 1), dec!(5502));
+        assert_eq!(irmaa(dec!(397000), separate, 1), dec!(6003.60));
+
+        // Later income uses later schedules
+        assert_eq!(
+            irmaa_surcharge(&data, dec!(104000), single, 1, 2022),
+            dec!(993.60)
+        );
+        assert_eq!(
+            irmaa_surcharge(&data, dec!(104000), single, 1, 2023),
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn test_savers_credit_cliff() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::Texas,
+            traditional_401k: dec!(4000),
+            ..Default::default()
+        };
+
+        let scan = BenefitCliffDetector::new(&engine).scan(
+            &input,
+            &BenefitProfile::default(),
+            dec!(48000),
+            dec!(53000),
+            DEFAULT_SCAN_STEP,
+        );
+
+        // AGI passes $46,000 between $50,000 and $51,000 of wages: the
+        // credit falls from 50% to 20% of $4,000, more than the raise nets
+        assert_eq!(scan.points.len(), 6);
+        assert_eq!(scan.cliffs.len(), 1);
+        let cliff = &scan.cliffs[0];
+        assert_eq!(cliff.gross_income_from, dec!(50000));
+        assert_eq!(cliff.gross_income_to, dec!(51000));
+        assert_eq!(cliff.causes, vec![Benefit::SaversCredit]);
+        assert!(cliff.effective_marginal_rate().as_fraction() > Decimal::ONE);
+    }
+
+    #[test]
+    fn test_irmaa_cliff_and_smooth_phase_outs() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let detector = BenefitCliffDetector::new(&engine);
+        let input = TaxCalculationInput {
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let medicare = BenefitProfile {
+            medicare_enrollees: 1,
+            ..Default::default()
+        };
+
+        // 2024 income sets 2026 premiums, on the latest ($106,000) tiers
+        let scan = detector.scan(&input, &medicare, dec!(103000), dec!(109000), dec!(1000));
+        let worst = scan.worst().unwrap();
+        assert_eq!(worst.gross_income_from, dec!(106000));
+        assert_eq!(worst.causes, vec![Benefit::Irmaa]);
+
+        // The EITC and enhanced premium credit phase out gradually
+        let family = BenefitProfile {
+            qualifying_children: 2,
+            household_size: 3,
+            benchmark_premium: Some(dec!(15000)),
+            ..Default::default()
+        };
+        let scan = detector.scan(&input, &family, dec!(10000), dec!(80000), dec!(1000));
+        assert!(scan.cliffs.is_empty());
+        assert!(scan.points[20].benefits.earned_income_credit > Decimal::ZERO);
+        assert!(scan.points[40].benefits.premium_tax_credit > Decimal::ZERO);
+    }
+}
@@ -0,0 +1,246 @@
+//! Plot-ready bracket and tax curve series
+//!
+//! Charts on every platform draw the same three things: bands for each
+//! bracket, the cumulative tax curve, and a marker where the user sits.
+//! Computing them here keeps the apps from re-deriving bracket math.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::data::StateTaxType;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::rate::Rate;
+use crate::models::tax::{FilingStatus, TaxBracket};
+
+/// Smallest x-axis range, so low incomes still show a few brackets
+const MIN_AXIS_MAX: Decimal = dec!(100000);
+
+/// Axis maximums round up to a multiple of this
+const AXIS_STEP: Decimal = dec!(10000);
+
+/// Evenly spaced curve points between bracket boundaries
+const CURVE_SAMPLES: u32 = 50;
+
+/// One bracket's band on the chart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BracketBand {
+    pub floor: Decimal,
+    /// `None` for the top bracket
+    pub ceiling: Option<Decimal>,
+    pub rate: Rate,
+    /// Tax on income up to `floor`
+    pub tax_at_floor: Decimal,
+}
+
+/// A point on the cumulative tax curve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurvePoint {
+    pub taxable_income: Decimal,
+    pub tax: Decimal,
+    pub marginal_rate: Rate,
+    pub effective_rate: Rate,
+}
+
+/// Bands and curve for one jurisdiction, on its own taxable income axis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BracketSeries {
+    /// "Federal" or the state code
+    pub label: String,
+    pub bands: Vec<BracketBand>,
+    /// Points from zero to `axis_max`, including every bracket boundary
+    pub curve: Vec<CurvePoint>,
+    /// The user's taxable income on the curve, when it's known
+    pub marker: Option<CurvePoint>,
+    pub axis_max: Decimal,
+}
+
+/// Federal and state series for one calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BracketChart {
+    pub federal: BracketSeries,
+    /// `None` when the state has no income tax brackets
+    pub state: Option<BracketSeries>,
+}
+
+/// Bracket chart builder
+pub struct BracketChartAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> BracketChartAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Chart series for the input's filing status and state, marked at the
+    /// input's taxable income
+    ///
+    /// Curves use statutory brackets only, before credits. States with
+    /// flat rates get a single band and no marker, since credits and
+    /// deductions leave their taxable income unreported.
+    pub fn chart(&self, input: &TaxCalculationInput) -> BracketChart {
+        let data = self.engine.data_provider();
+        let year = self.engine.year();
+        let result = self.engine.calculate(input);
+
+        let federal = series(
+            "Federal",
+            &data.federal_brackets(input.filing_status, year),
+            Some(result.tax_breakdown.federal.taxable_income),
+        );
+
+        let config = data.state_config(input.state, year);
+        let state_result = &result.tax_breakdown.state;
+        let state = match config.tax_type {
+            StateTaxType::Progressive => {
+                let brackets = config
+                    .brackets
+                    .get(input.filing_status.as_str())
+                    .or_else(|| config.brackets.get(FilingStatus::Single.as_str()))
+                    .cloned()
+                    .unwrap_or_default();
+                let taxable = state_result.bracket_breakdown.as_ref().map(|breakdown| {
+                    breakdown
+                        .iter()
+                        .map(|amount| amount.taxable_in_bracket)
+                        .sum()
+                });
+                Some(series(input.state.code(), &brackets, taxable))
+            },
+            StateTaxType::FlatRate => config.flat_rate.map(|rate| {
+                let flat = [TaxBracket::new(Decimal::ZERO, None, rate, Decimal::ZERO)];
+                series(input.state.code(), &flat, None)
+            }),
+            _ => None,
+        };
+
+        BracketChart { federal, state }
+    }
+}
+
+fn series(label: &str, brackets: &[TaxBracket], marker_income: Option<Decimal>) -> BracketSeries {
+    let axis_max =
+        ((marker_income.unwrap_or(Decimal::ZERO) * dec!(2)).max(MIN_AXIS_MAX) / AXIS_STEP).ceil()
+            * AXIS_STEP;
+
+    let bands = brackets
+        .iter()
+        .map(|bracket| BracketBand {
+            floor: bracket.floor,
+            ceiling: bracket.ceiling,
+            rate: bracket.rate,
+            tax_at_floor: bracket.base_tax,
+        })
+        .collect();
+
+    let step = axis_max / Decimal::from(CURVE_SAMPLES);
+    let mut incomes: Vec<Decimal> = (0..=CURVE_SAMPLES)
+        .map(|i| step * Decimal::from(i))
+        .chain(
+            brackets
+                .iter()
+                .map(|bracket| bracket.floor)
+                .filter(|floor| *floor > Decimal::ZERO && *floor < axis_max),
+        )
+        .collect();
+    incomes.sort();
+    incomes.dedup();
+
+    BracketSeries {
+        label: label.to_string(),
+        bands,
+        curve: incomes
+            .into_iter()
+            .map(|income| point(brackets, income))
+            .collect(),
+        marker: marker_income.map(|income| point(brackets, income.max(Decimal::ZERO))),
+        axis_max,
+    }
+}
+
+fn point(brackets: &[TaxBracket], taxable_income: Decimal) -> CurvePoint {
+    let bracket = brackets
+        .iter()
+        .rev()
+        .find(|bracket| taxable_income >= bracket.floor);
+    let (tax, marginal_rate) = bracket.map_or((Decimal::ZERO, Rate::ZERO), |bracket| {
+        (
+            bracket.base_tax + (taxable_income - bracket.floor) * bracket.rate,
+            bracket.rate,
+        )
+    });
+    CurvePoint {
+        taxable_income,
+        tax,
+        marginal_rate,
+        effective_rate: Rate::of(tax, taxable_income),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_federal_series() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let chart = BracketChartAnalyzer::new(&engine).chart(&TaxCalculationInput {
+            gross_income: dec!(75000),
+            state: USState::Texas,
+            ..Default::default()
+        });
+
+        let federal = &chart.federal;
+        assert_eq!(federal.bands.len(), 7);
+        assert_eq!(federal.axis_max, dec!(130000));
+        // Boundaries inside the axis are on the curve, which only rises
+        for floor in [dec!(11600), dec!(47150), dec!(100525)] {
+            assert!(federal.curve.iter().any(|p| p.taxable_income == floor));
+        }
+        assert!(federal.curve.windows(2).all(|w| w[0].tax <= w[1].tax));
+
+        // $75,000 less the $14,600 standard deduction
+        let marker = federal.marker.as_ref().unwrap();
+        assert_eq!(marker.taxable_income, dec!(60400));
+        assert_eq!(marker.tax, dec!(5426) + dec!(13250) * dec!(0.22));
+        assert_eq!(marker.marginal_rate, Rate::from_percent(dec!(22)));
+        assert!(chart.state.is_none());
+    }
+
+    #[test]
+    fn test_state_series() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = BracketChartAnalyzer::new(&engine);
+        let input = TaxCalculationInput {
+            gross_income: dec!(120000),
+            state: USState::California,
+            ..Default::default()
+        };
+
+        let state = analyzer.chart(&input).state.unwrap();
+        assert_eq!(state.label, "CA");
+        let marker = state.marker.unwrap();
+        let result = engine.calculate(&input);
+        assert!(marker.taxable_income < result.tax_breakdown.state.taxable_income);
+        assert!(marker.tax > Decimal::ZERO);
+
+        let flat = analyzer
+            .chart(&TaxCalculationInput {
+                state: USState::Illinois,
+                ..input
+            })
+            .state
+            .unwrap();
+        assert_eq!(flat.bands.len(), 1);
+        assert!(flat.marker.is_none());
+    }
+}
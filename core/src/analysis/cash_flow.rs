@@ -0,0 +1,222 @@
+//! Month-by-month cash flow for budgeting
+//!
+//! Regular paychecks land on the pay calendar's dates, so biweekly and
+//! weekly earners see a few months with an extra paycheck. Bonuses and RSU
+//! vests arrive on their own dates, withheld at the supplemental flat rates
+//! rather than through the regular paycheck, and their net lands in the
+//! month they're paid.
+
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::pay_calendar::{PayCalendar, PayCalendarProjector};
+use crate::calculators::withholding::{
+    SupplementalKind, SupplementalWithholdingCalculator, SupplementalWithholdingInput,
+};
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+
+/// A bonus, RSU vest, or other supplemental payment on its pay date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SupplementalPayment {
+    pub date: NaiveDate,
+    /// Bonus amount or fair market value of vesting shares
+    pub amount: Decimal,
+    pub kind: SupplementalKind,
+}
+
+impl SupplementalPayment {
+    pub fn bonus(date: NaiveDate, amount: Decimal) -> Self {
+        Self {
+            date,
+            amount,
+            kind: SupplementalKind::Bonus,
+        }
+    }
+
+    pub fn rsu_vest(date: NaiveDate, value: Decimal) -> Self {
+        Self {
+            date,
+            amount: value,
+            kind: SupplementalKind::RsuVest,
+        }
+    }
+}
+
+/// Net cash received in one calendar month
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyCashFlow {
+    /// 1-12
+    pub month: u32,
+    pub paychecks: u32,
+    /// A third biweekly or fifth weekly paycheck lands this month
+    pub extra_paycheck: bool,
+    pub regular_net: Decimal,
+    /// Bonuses, severance, and PTO payouts after withholding
+    pub bonus_net: Decimal,
+    /// Shares delivered after sell-to-cover, valued at vest
+    pub rsu_net: Decimal,
+}
+
+impl MonthlyCashFlow {
+    pub fn total(&self) -> Decimal {
+        self.regular_net + self.bonus_net + self.rsu_net
+    }
+}
+
+/// A year of monthly cash flow, January first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashFlowProjection {
+    pub months: Vec<MonthlyCashFlow>,
+}
+
+impl CashFlowProjection {
+    pub fn total(&self) -> Decimal {
+        self.months.iter().map(MonthlyCashFlow::total).sum()
+    }
+
+    /// Months with more than the usual regular pay, or any bonus or vest
+    pub fn windfall_months(&self) -> Vec<u32> {
+        self.months
+            .iter()
+            .filter(|m| m.extra_paycheck || m.bonus_net + m.rsu_net > Decimal::ZERO)
+            .map(|m| m.month)
+            .collect()
+    }
+}
+
+/// Projects net cash per month from paychecks and supplemental payments
+pub struct CashFlowProjector<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> CashFlowProjector<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Net cash per month for the calendar's year
+    ///
+    /// `input.gross_income` is regular pay only. Payments outside the
+    /// calendar's year are ignored; the rest are withheld in date order, so
+    /// later ones see the wages and supplemental pay before them.
+    pub fn project(
+        &self,
+        input: &TaxCalculationInput,
+        calendar: &PayCalendar,
+        payments: &[SupplementalPayment],
+    ) -> CashFlowProjection {
+        let schedule = PayCalendarProjector::new(self.engine).project(input, calendar);
+        let extra_months = schedule.extra_paycheck_months();
+        let mut months: Vec<MonthlyCashFlow> = (1..=12)
+            .map(|month| {
+                let paychecks = schedule.paychecks_in_month(month);
+                let (count, regular_net) = paychecks.fold((0, Decimal::ZERO), |(n, net), p| {
+                    (n + 1, net + p.paystub.net)
+                });
+                MonthlyCashFlow {
+                    month,
+                    paychecks: count,
+                    extra_paycheck: extra_months.contains(&month),
+                    regular_net,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut payments: Vec<&SupplementalPayment> = payments
+            .iter()
+            .filter(|p| p.date.year() == calendar.year())
+            .collect();
+        payments.sort_by_key(|p| p.date);
+
+        let calculator = SupplementalWithholdingCalculator::new(self.engine.data_provider());
+        let mut ytd_supplemental = Decimal::ZERO;
+        for payment in payments {
+            let ytd_regular: Decimal = schedule
+                .paychecks
+                .iter()
+                .filter(|p| p.date.paid <= payment.date)
+                .map(|p| p.paystub.gross)
+                .sum();
+            let withholding = calculator.calculate(
+                &SupplementalWithholdingInput {
+                    amount: payment.amount,
+                    kind: payment.kind,
+                    state: input.state,
+                    filing_status: input.filing_status,
+                    annual_wages: input.gross_income,
+                    ytd_wages: ytd_regular + ytd_supplemental,
+                    ytd_supplemental_wages: ytd_supplemental,
+                },
+                self.engine.year(),
+            );
+            ytd_supplemental += payment.amount.max(Decimal::ZERO);
+
+            let month = &mut months[payment.date.month0() as usize];
+            let net = withholding.net.round_dp(2);
+            match payment.kind {
+                SupplementalKind::RsuVest => month.rsu_net += net,
+                _ => month.bonus_net += net,
+            }
+        }
+
+        CashFlowProjection { months }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::income::PayFrequency;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_bonus_and_vest_months() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(130000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let calendar = PayCalendar::new(PayFrequency::BiWeekly, date(1, 5));
+        let projection = CashFlowProjector::new(&engine).project(
+            &input,
+            &calendar,
+            &[
+                SupplementalPayment::rsu_vest(date(5, 15), dec!(20000)),
+                SupplementalPayment::bonus(date(3, 15), dec!(10000)),
+                SupplementalPayment::bonus(date(1, 15).with_year(2025).unwrap(), dec!(5000)),
+            ],
+        );
+
+        let march = &projection.months[2];
+        // 22% federal and 7.65% FICA withheld, no Texas income tax
+        assert_eq!(march.bonus_net, dec!(7035));
+        assert_eq!(projection.months[4].rsu_net, dec!(14070));
+        assert_eq!(projection.months[0].bonus_net, dec!(0));
+
+        // Paydays from January 5 put a third paycheck in March and August
+        assert_eq!(march.paychecks, 3);
+        assert!(march.extra_paycheck);
+        assert_eq!(projection.windfall_months(), vec![3, 5, 8]);
+        assert_eq!(
+            march.regular_net,
+            projection.months[1].regular_net * dec!(1.5)
+        );
+
+        let regular: Decimal = projection.months.iter().map(|m| m.regular_net).sum();
+        assert_eq!(projection.total(), regular + dec!(7035) + dec!(14070));
+    }
+}
@@ -0,0 +1,249 @@
+//! Charitable contribution bunching
+//!
+//! Donors whose other itemized deductions fall short of the standard
+//! deduction get no tax benefit from a steady gift: it only fills the gap.
+//! Giving two years' donations in one year and nothing the next lets the
+//! donor itemize once and take the standard deduction in the other year.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::solve_increasing;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::deduction::ItemizedDeductions;
+
+/// One year of a giving strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GivingYear {
+    pub donation: Decimal,
+    /// Deduction taken: itemized or standard, whichever is larger
+    pub deduction: Decimal,
+    pub itemized: bool,
+    pub total_taxes: Decimal,
+    pub take_home: Decimal,
+}
+
+/// Two years of giving under one strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GivingStrategy {
+    pub years: [GivingYear; 2],
+}
+
+impl GivingStrategy {
+    pub fn total_taxes(&self) -> Decimal {
+        self.years.iter().map(|y| y.total_taxes).sum()
+    }
+
+    pub fn take_home(&self) -> Decimal {
+        self.years.iter().map(|y| y.take_home).sum()
+    }
+}
+
+/// Spreading gifts evenly versus bunching them into the first year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BunchingComparison {
+    pub annual_donation: Decimal,
+    pub standard_deduction: Decimal,
+    /// The same gift each year
+    pub spread: GivingStrategy,
+    /// Both years' gifts in the first year
+    pub bunched: GivingStrategy,
+    /// Two-year tax saved by bunching (negative when spreading is better)
+    pub tax_savings: Decimal,
+    /// Annual gift above which bunching starts to save tax: the bunched
+    /// year's itemized deductions reach the standard deduction. `None` when
+    /// the charitable AGI limit keeps them below it.
+    pub breakeven_donation: Option<Decimal>,
+}
+
+/// Compares bunched and evenly spread charitable giving
+pub struct CharitableBunchingAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> CharitableBunchingAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Compare two identical years of income with `annual_donation` given
+    /// each year or twice that given in the first year
+    ///
+    /// Other itemized expenses come from the input's `itemized_deductions`
+    /// and `property_taxes`, and any charitable amount there is treated as
+    /// part of the annual gift.
+    pub fn compare(
+        &self,
+        input: &TaxCalculationInput,
+        annual_donation: Decimal,
+    ) -> BunchingComparison {
+        let annual_donation = annual_donation.max(Decimal::ZERO);
+        let spread_year = self.year(input, annual_donation);
+        let spread = GivingStrategy {
+            years: [spread_year.clone(), spread_year],
+        };
+        let bunched = GivingStrategy {
+            years: [
+                self.year(input, annual_donation * dec!(2)),
+                self.year(input, Decimal::ZERO),
+            ],
+        };
+
+        BunchingComparison {
+            annual_donation,
+            standard_deduction: self
+                .engine
+                .data_provider()
+                .standard_deduction(input.filing_status, self.engine.year()),
+            tax_savings: spread.total_taxes() - bunched.total_taxes(),
+            spread,
+            bunched,
+            breakeven_donation: self.breakeven_donation(input),
+        }
+    }
+
+    fn year(&self, input: &TaxCalculationInput, donation: Decimal) -> GivingYear {
+        let input = with_donation(input, donation);
+        let result = self.engine.calculate(&input);
+        let standard = self
+            .engine
+            .data_provider()
+            .standard_deduction(input.filing_status, self.engine.year());
+        let itemized = input.itemized_expenses().total(
+            result.adjusted_gross_income,
+            input.filing_status,
+            input.investment_income.gambling_winnings,
+            self.engine.year(),
+        );
+        GivingYear {
+            donation,
+            deduction: standard.max(itemized),
+            itemized: itemized > standard,
+            total_taxes: result.tax_breakdown.total_taxes,
+            take_home: result.income.net,
+        }
+    }
+
+    fn breakeven_donation(&self, input: &TaxCalculationInput) -> Option<Decimal> {
+        let agi = self.engine.calculate(input).adjusted_gross_income;
+        let standard = self
+            .engine
+            .data_provider()
+            .standard_deduction(input.filing_status, self.engine.year());
+        let itemized = |annual: Decimal| {
+            with_donation(input, annual * dec!(2))
+                .itemized_expenses()
+                .total(
+                    agi,
+                    input.filing_status,
+                    input.investment_income.gambling_winnings,
+                    self.engine.year(),
+                )
+        };
+        // The AGI limit caps the charitable deduction, so check the most
+        // the bunched year could deduct before searching
+        if itemized(agi) < standard {
+            return None;
+        }
+        Some(solve_increasing(itemized, standard))
+    }
+}
+
+fn with_donation(input: &TaxCalculationInput, donation: Decimal) -> TaxCalculationInput {
+    TaxCalculationInput {
+        itemized_deductions: ItemizedDeductions {
+            charitable: donation,
+            ..input.itemized_deductions.clone()
+        },
+        ..input.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use crate::models::tax::FilingStatus;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn input() -> TaxCalculationInput {
+        TaxCalculationInput {
+            gross_income: dec!(150000),
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::Texas,
+            itemized_deductions: ItemizedDeductions {
+                state_and_local_taxes: dec!(8000),
+                mortgage_interest: dec!(12000),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bunching_saves_below_the_standard_deduction() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let comparison = CharitableBunchingAnalyzer::new(&engine).compare(&input(), dec!(6000));
+
+        // $20,000 + $6,000 never beats the $29,200 standard deduction, but
+        // $20,000 + $12,000 does by $2,800, taxed at 22%
+        assert!(!comparison.spread.years[0].itemized);
+        assert!(comparison.bunched.years[0].itemized);
+        assert_eq!(comparison.bunched.years[0].deduction, dec!(32000));
+        assert_eq!(comparison.tax_savings, dec!(616));
+        assert_eq!(
+            comparison.bunched.take_home() - comparison.spread.take_home(),
+            comparison.tax_savings
+        );
+        // Half the $9,200 gap to the standard deduction
+        assert_eq!(comparison.breakeven_donation, Some(dec!(4600)));
+    }
+
+    #[test]
+    fn test_savings_around_breakeven() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = CharitableBunchingAnalyzer::new(&engine);
+
+        assert_eq!(analyzer.compare(&input(), dec!(4000)).tax_savings, dec!(0));
+
+        // Spread gifts that itemize every year still miss the standard
+        // deduction's excess over other expenses in the off year
+        let comparison = analyzer.compare(&input(), dec!(15000));
+        assert!(comparison.spread.years[0].itemized);
+        assert!(!comparison.bunched.years[1].itemized);
+        assert_eq!(comparison.tax_savings, dec!(2024));
+    }
+
+    #[test]
+    fn test_itemized_deduction_limits() {
+        let deductions = ItemizedDeductions {
+            state_and_local_taxes: dec!(18000),
+            mortgage_interest: dec!(5000),
+            charitable: dec!(40000),
+            medical: dec!(10000),
+            gambling_losses: Decimal::ZERO,
+        };
+
+        // $10,000 SALT cap, 60% of AGI for gifts, medical above 7.5% of AGI
+        assert_eq!(
+            deductions.total(dec!(50000), FilingStatus::Single, Decimal::ZERO, 2024),
+            dec!(51250)
+        );
+        assert_eq!(
+            deductions.total(
+                dec!(50000),
+                FilingStatus::MarriedFilingSeparately,
+                Decimal::ZERO,
+                2024
+            ),
+            dec!(46250)
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Paid overtime compared with compensatory time off
+//!
+//! Public employers can give comp time instead of overtime pay: an hour and
+//! a half of paid leave for each overtime hour. Overtime pay is taxed at the
+//! top of the year's income. Comp time adds nothing taxable; it's worth the
+//! take-home pay that the same leave would cost if taken unpaid, which comes
+//! off the top of the year's income at the marginal rate.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::extra_hours::{ExtraHourAnalyzer, OVERTIME_MULTIPLIER};
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::rate::Rate;
+
+/// Overtime pay and comp time for the same hours worked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompTimeComparison {
+    pub overtime_hours: Decimal,
+    /// Base hourly rate from the salary and work schedule
+    pub hourly_rate: Decimal,
+    pub overtime_gross: Decimal,
+    pub overtime_net: Decimal,
+    /// Share of the overtime pay lost to tax
+    pub overtime_marginal_rate: Rate,
+    /// Hours of paid leave banked instead
+    pub comp_hours: Decimal,
+    /// Take-home pay the comp hours would cost as unpaid leave
+    pub comp_time_value: Decimal,
+    /// Comp time value less overtime take-home; positive when comp time is
+    /// worth more
+    pub comp_time_advantage: Decimal,
+    /// Overtime take-home per hour of comp time given up for it
+    pub overtime_net_per_comp_hour: Decimal,
+}
+
+/// Compares overtime pay with comp time at the user's marginal rate
+pub struct CompTimeAnalyzer<'a> {
+    hours: ExtraHourAnalyzer<'a>,
+}
+
+impl<'a> CompTimeAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self {
+            hours: ExtraHourAnalyzer::new(engine),
+        }
+    }
+
+    /// Value of `overtime_hours` taken as time-and-a-half pay or as comp time
+    pub fn compare(
+        &self,
+        input: &TaxCalculationInput,
+        overtime_hours: Decimal,
+    ) -> CompTimeComparison {
+        let overtime_hours = overtime_hours.max(Decimal::ZERO);
+        let hourly_rate = ExtraHourAnalyzer::hourly_rate(input);
+        let comp_hours = overtime_hours * OVERTIME_MULTIPLIER;
+
+        let overtime_gross = (hourly_rate * comp_hours).round_dp(2);
+        let overtime_net = self.hours.net_gain(input, overtime_gross).round_dp(2);
+        // Leave can't cost more than the year's pay
+        let leave_gross = overtime_gross.min(input.gross_income);
+        let comp_time_value = (-self.hours.net_gain(input, -leave_gross)).round_dp(2);
+
+        CompTimeComparison {
+            overtime_hours,
+            hourly_rate: hourly_rate.round_dp(2),
+            overtime_gross,
+            overtime_net,
+            overtime_marginal_rate: Rate::of(overtime_gross - overtime_net, overtime_gross),
+            comp_hours,
+            comp_time_value,
+            comp_time_advantage: comp_time_value - overtime_net,
+            overtime_net_per_comp_hour: if comp_hours > Decimal::ZERO {
+                (overtime_net / comp_hours).round_dp(2)
+            } else {
+                Decimal::ZERO
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_overtime_vs_comp_time() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = CompTimeAnalyzer::new(&engine);
+        // $52/hour, in the 22% bracket, no state income tax
+        let input = TaxCalculationInput {
+            gross_income: dec!(108160),
+            state: USState::Texas,
+            ..Default::default()
+        };
+
+        let comparison = analyzer.compare(&input, dec!(10));
+        assert_eq!(comparison.comp_hours, dec!(15));
+        assert_eq!(comparison.overtime_gross, dec!(780));
+        // 22% federal and 7.65% FICA either way
+        assert_eq!(comparison.overtime_net, dec!(548.73));
+        assert_eq!(comparison.comp_time_value, dec!(548.73));
+        assert_eq!(comparison.comp_time_advantage, dec!(0));
+        assert_eq!(comparison.overtime_net_per_comp_hour, dec!(36.58));
+
+        // Overtime that crosses into the 24% bracket keeps less than the
+        // leave would have cost
+        let near_bracket = TaxCalculationInput {
+            gross_income: dec!(114000),
+            ..input.clone()
+        };
+        let comparison = analyzer.compare(&near_bracket, dec!(40));
+        assert!(comparison.comp_time_advantage > Decimal::ZERO);
+        assert!(comparison.overtime_marginal_rate > Rate::from_fraction(dec!(0.2965)));
+    }
+}
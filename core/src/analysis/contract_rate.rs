@@ -0,0 +1,254 @@
+//! W-2 salary ↔ 1099 contract rate conversion
+//!
+//! A contract rate matches a salary when the contractor keeps as much after
+//! self-employment and income taxes as the employee takes home plus the
+//! benefits the employer was paying for. Contractors also bill only part of
+//! the year: paid time off becomes unpaid, and not every available hour is
+//! billable.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::solve_increasing;
+use crate::calculators::self_employment::{HomeOffice, ScheduleC, SelfEmploymentResult};
+use crate::calculators::SelfEmploymentTaxCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Assumptions shared by both directions of the conversion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractAssumptions {
+    pub hours_per_week: Decimal,
+    /// Paid days off (vacation and holidays) the W-2 job provides
+    pub pto_days: Decimal,
+    /// Share of available hours billed to clients
+    pub utilization: Decimal,
+    /// Annual value of employer-paid benefits the contractor must buy
+    pub benefits_value: Decimal,
+    /// Annual deductible business expenses
+    pub business_expenses: Decimal,
+    /// Home office deducted on Schedule C
+    #[serde(default)]
+    pub home_office: Option<HomeOffice>,
+}
+
+impl Default for ContractAssumptions {
+    fn default() -> Self {
+        Self {
+            hours_per_week: dec!(40),
+            pto_days: dec!(15),
+            utilization: dec!(0.85),
+            benefits_value: Decimal::ZERO,
+            business_expenses: Decimal::ZERO,
+            home_office: None,
+        }
+    }
+}
+
+impl ContractAssumptions {
+    /// Hours billed in a year after time off and utilization
+    pub fn billable_hours(&self) -> Decimal {
+        let hours_per_day = self.hours_per_week / dec!(5);
+        let available = dec!(52) * self.hours_per_week - self.pto_days * hours_per_day;
+        (available * self.utilization).max(Decimal::ZERO)
+    }
+}
+
+/// Matching salary and contract rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractRateEquivalent {
+    pub salary: Decimal,
+    pub hourly_rate: Decimal,
+    /// Annual 1099 billings at `hourly_rate`
+    pub contract_revenue: Decimal,
+    pub billable_hours: Decimal,
+    /// W-2 take-home plus benefits value
+    pub w2_value: Decimal,
+    /// Contractor take-home after expenses, SE tax, and income taxes
+    pub contractor_take_home: Decimal,
+    pub self_employment_tax: Decimal,
+}
+
+/// Salary/contract rate converter built on the calculation engine
+pub struct ContractRateConverter<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> ContractRateConverter<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Hourly 1099 rate that matches a W-2 salary
+    pub fn hourly_rate_for_salary(
+        &self,
+        salary: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        assumptions: &ContractAssumptions,
+    ) -> ContractRateEquivalent {
+        let target = self.w2_value(salary, filing_status, state, assumptions);
+        let revenue = solve_increasing(
+            |revenue| self.contractor_take_home(revenue, filing_status, state, assumptions),
+            target,
+        );
+        self.equivalent(salary, revenue, filing_status, state, assumptions)
+    }
+
+    /// W-2 salary that matches an hourly 1099 rate
+    pub fn salary_for_hourly_rate(
+        &self,
+        hourly_rate: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        assumptions: &ContractAssumptions,
+    ) -> ContractRateEquivalent {
+        let revenue = hourly_rate * assumptions.billable_hours();
+        let target = self.contractor_take_home(revenue, filing_status, state, assumptions);
+        let salary = solve_increasing(
+            |salary| self.w2_value(salary, filing_status, state, assumptions),
+            target,
+        );
+        self.equivalent(salary, revenue, filing_status, state, assumptions)
+    }
+
+    fn w2_value(
+        &self,
+        salary: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        assumptions: &ContractAssumptions,
+    ) -> Decimal {
+        let result = self.engine.calculate(&TaxCalculationInput {
+            gross_income: salary,
+            filing_status,
+            state,
+            ..Default::default()
+        });
+        result.income.net + assumptions.benefits_value
+    }
+
+    fn contractor_take_home(
+        &self,
+        revenue: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        assumptions: &ContractAssumptions,
+    ) -> Decimal {
+        self.self_employment(revenue, filing_status, state, assumptions)
+            .after_tax_income
+    }
+
+    fn self_employment(
+        &self,
+        revenue: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        assumptions: &ContractAssumptions,
+    ) -> SelfEmploymentResult {
+        let schedule = ScheduleC {
+            gross_receipts: revenue,
+            expenses: assumptions.business_expenses,
+            home_office: assumptions.home_office.clone(),
+            vehicle: None,
+        };
+        SelfEmploymentTaxCalculator::new(self.engine.data_provider()).calculate_schedule_c(
+            &schedule,
+            filing_status,
+            state,
+            self.engine.year(),
+        )
+    }
+
+    fn equivalent(
+        &self,
+        salary: Decimal,
+        revenue: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        assumptions: &ContractAssumptions,
+    ) -> ContractRateEquivalent {
+        let billable_hours = assumptions.billable_hours();
+        let contractor = self.self_employment(revenue, filing_status, state, assumptions);
+        ContractRateEquivalent {
+            salary,
+            hourly_rate: if billable_hours > Decimal::ZERO {
+                revenue / billable_hours
+            } else {
+                Decimal::ZERO
+            },
+            contract_revenue: revenue,
+            billable_hours,
+            w2_value: self.w2_value(salary, filing_status, state, assumptions),
+            contractor_take_home: contractor.after_tax_income,
+            self_employment_tax: contractor.self_employment_tax.total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_billable_hours() {
+        let assumptions = ContractAssumptions::default();
+
+        // (2,080 - 15 days × 8 hours) × 85%
+        assert_eq!(assumptions.billable_hours(), dec!(1666));
+    }
+
+    #[test]
+    fn test_salary_to_hourly_rate() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let converter = ContractRateConverter::new(&engine);
+
+        let assumptions = ContractAssumptions {
+            benefits_value: dec!(15000),
+            ..Default::default()
+        };
+        let result = converter.hourly_rate_for_salary(
+            dec!(150000),
+            FilingStatus::Single,
+            USState::Texas,
+            &assumptions,
+        );
+
+        // The contractor nets what the employee takes home plus benefits
+        assert!((result.contractor_take_home - result.w2_value).abs() < dec!(0.05));
+        assert!(result.contract_revenue > dec!(150000) + dec!(15000));
+        // Fewer billable hours push the rate well above salary ÷ 2,080
+        assert!(result.hourly_rate > dec!(100));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let converter = ContractRateConverter::new(&engine);
+        let assumptions = ContractAssumptions::default();
+
+        let rate = converter.hourly_rate_for_salary(
+            dec!(120000),
+            FilingStatus::Single,
+            USState::California,
+            &assumptions,
+        );
+        let salary = converter.salary_for_hourly_rate(
+            rate.hourly_rate,
+            FilingStatus::Single,
+            USState::California,
+            &assumptions,
+        );
+
+        assert!((salary.salary - dec!(120000)).abs() < dec!(1));
+    }
+}
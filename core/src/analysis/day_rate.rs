@@ -0,0 +1,177 @@
+//! Consulting day-rate planner
+//!
+//! Works backward from the after-tax income an independent consultant
+//! wants to the day rate they must charge, given how many days they expect
+//! to bill and what the business costs to run.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::solve_increasing;
+use crate::calculators::self_employment::SelfEmploymentResult;
+use crate::calculators::SelfEmploymentTaxCalculator;
+use crate::engine::TaxCalculationEngine;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Weekdays in a year
+const WORKDAYS_PER_YEAR: Decimal = dec!(260);
+
+/// Input for the day-rate planner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayRateInput {
+    /// After-tax income the consultant wants to keep
+    pub target_after_tax: Decimal,
+    /// Share of available days billed to clients
+    pub utilization: Decimal,
+    /// Vacation, holidays, and sick days taken
+    pub days_off: Decimal,
+    /// Annual deductible business expenses
+    pub annual_expenses: Decimal,
+    pub filing_status: FilingStatus,
+    pub state: USState,
+}
+
+impl Default for DayRateInput {
+    fn default() -> Self {
+        Self {
+            target_after_tax: Decimal::ZERO,
+            utilization: dec!(0.7),
+            days_off: dec!(25),
+            annual_expenses: Decimal::ZERO,
+            filing_status: FilingStatus::Single,
+            state: USState::California,
+        }
+    }
+}
+
+impl DayRateInput {
+    /// Days billed in a year
+    pub fn billable_days(&self) -> Decimal {
+        ((WORKDAYS_PER_YEAR - self.days_off) * self.utilization).max(Decimal::ZERO)
+    }
+}
+
+/// Required day rate and the year it produces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayRatePlan {
+    pub day_rate: Decimal,
+    pub billable_days: Decimal,
+    /// Annual billings at `day_rate`
+    pub revenue: Decimal,
+    pub net_profit: Decimal,
+    pub self_employment_tax: Decimal,
+    /// Federal, state, and local income tax
+    pub income_tax: Decimal,
+    pub after_tax_income: Decimal,
+}
+
+/// Day-rate planner built on the calculation engine
+pub struct DayRatePlanner<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> DayRatePlanner<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Solve for the day rate that leaves the target after tax, or `None`
+    /// when the input leaves no billable days
+    pub fn plan(&self, input: &DayRateInput) -> Option<DayRatePlan> {
+        let billable_days = input.billable_days();
+        if billable_days <= Decimal::ZERO {
+            return None;
+        }
+
+        // Revenue must first cover expenses, then SE and income taxes
+        let net_profit = solve_increasing(
+            |profit| self.after_tax(profit, input).after_tax_income,
+            input.target_after_tax,
+        );
+        let revenue = net_profit + input.annual_expenses;
+        let result = self.after_tax(net_profit, input);
+
+        Some(DayRatePlan {
+            day_rate: (revenue / billable_days).round_dp(2),
+            billable_days,
+            revenue,
+            net_profit,
+            self_employment_tax: result.self_employment_tax.total,
+            income_tax: result.federal_income_tax + result.state_income_tax,
+            after_tax_income: result.after_tax_income,
+        })
+    }
+
+    fn after_tax(&self, net_profit: Decimal, input: &DayRateInput) -> SelfEmploymentResult {
+        SelfEmploymentTaxCalculator::new(self.engine.data_provider()).calculate(
+            net_profit,
+            input.filing_status,
+            input.state,
+            self.engine.year(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_day_rate_reaches_target() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let planner = DayRatePlanner::new(&engine);
+
+        let input = DayRateInput {
+            target_after_tax: dec!(120000),
+            annual_expenses: dec!(15000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let plan = planner.plan(&input).unwrap();
+
+        // (260 - 25) × 70%
+        assert_eq!(plan.billable_days, dec!(164.5));
+        assert!((plan.after_tax_income - dec!(120000)).abs() < dec!(0.05));
+        assert_eq!(plan.revenue, plan.net_profit + dec!(15000));
+        assert_eq!(
+            plan.net_profit - plan.self_employment_tax - plan.income_tax,
+            plan.after_tax_income
+        );
+        assert!(plan.day_rate * plan.billable_days >= plan.revenue - dec!(1));
+    }
+
+    #[test]
+    fn test_lower_utilization_raises_rate() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let planner = DayRatePlanner::new(&engine);
+
+        let busy = DayRateInput {
+            target_after_tax: dec!(100000),
+            utilization: dec!(0.8),
+            ..Default::default()
+        };
+        let quiet = DayRateInput {
+            utilization: dec!(0.5),
+            ..busy.clone()
+        };
+
+        let busy_rate = planner.plan(&busy).unwrap().day_rate;
+        let quiet_rate = planner.plan(&quiet).unwrap().day_rate;
+        assert!(quiet_rate > busy_rate);
+
+        let idle = DayRateInput {
+            utilization: dec!(0),
+            ..busy
+        };
+        assert!(planner.plan(&idle).is_none());
+    }
+}
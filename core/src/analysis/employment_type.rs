@@ -0,0 +1,208 @@
+//! W-2 employment compared with 1099 contracting
+//!
+//! Both sides run through the calculation engine as one scenario
+//! comparison. The employee's employer quietly pays its half of Social
+//! Security and Medicare; the contractor pays both halves as SECA tax, gets
+//! half of it back as a deduction, deducts business expenses, and takes the
+//! qualified business income deduction. The break-even rate is the hourly
+//! rate at which the contractor keeps as much as the employee's take-home
+//! plus benefits.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::contract_rate::ContractAssumptions;
+use super::solve_increasing;
+use crate::calculators::self_employment::ScheduleC;
+use crate::calculators::SelfEmploymentTaxCalculator;
+use crate::engine::{
+    ScenarioComparison, TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult,
+};
+
+/// A salary and a contract rate compared side by side
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmploymentTypeComparison {
+    pub w2_salary: Decimal,
+    pub contractor_rate: Decimal,
+    pub billable_hours: Decimal,
+    /// Annual 1099 billings at `contractor_rate`
+    pub contractor_revenue: Decimal,
+    /// Schedule C profit after business expenses
+    pub contractor_profit: Decimal,
+    /// Employer's share of Social Security and Medicare on the salary
+    pub employer_fica: Decimal,
+    /// Employee's share, withheld from the salary
+    pub employee_fica: Decimal,
+    pub self_employment_tax: Decimal,
+    pub qbi_deduction: Decimal,
+    /// W-2 take-home plus benefits value
+    pub w2_value: Decimal,
+    pub contractor_take_home: Decimal,
+    /// Contractor take-home less W-2 value; positive when contracting pays more
+    pub contractor_advantage: Decimal,
+    /// Hourly rate at which the contractor matches the W-2 value
+    pub break_even_hourly_rate: Decimal,
+    /// Employee (base) and contractor (scenario) results line by line
+    pub scenario: ScenarioComparison,
+}
+
+/// Compares a W-2 job with contracting at an hourly rate
+pub struct EmploymentTypeAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> EmploymentTypeAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Compare the job in `input` with billing `contractor_rate` an hour
+    ///
+    /// The contractor gives up the job's payroll deductions; any spouse's
+    /// wages and other income stay the same.
+    pub fn compare(
+        &self,
+        input: &TaxCalculationInput,
+        contractor_rate: Decimal,
+        assumptions: &ContractAssumptions,
+    ) -> EmploymentTypeComparison {
+        let billable_hours = assumptions.billable_hours();
+        let contractor_revenue = contractor_rate * billable_hours;
+        let contractor = self.contractor_input(input, contractor_revenue, assumptions);
+        let scenario = self.engine.compare_scenarios(input, &contractor);
+
+        let w2_value = scenario.base.income.net + assumptions.benefits_value;
+        let revenue_to_match = solve_increasing(
+            |revenue| {
+                self.engine
+                    .calculate(&self.contractor_input(input, revenue, assumptions))
+                    .income
+                    .net
+            },
+            w2_value,
+        );
+
+        // The contractor side keeps only a spouse's wages, so the FICA
+        // difference is the earner's own
+        let fica = |r: &TaxCalculationResult| {
+            (
+                r.tax_breakdown.fica.social_security + r.tax_breakdown.fica.medicare,
+                r.tax_breakdown.fica.total,
+            )
+        };
+        let (w2_base_fica, w2_fica) = fica(&scenario.base);
+        let (contractor_base_fica, contractor_fica) = fica(&scenario.scenario);
+        let contractor_take_home = scenario.scenario.income.net;
+
+        EmploymentTypeComparison {
+            w2_salary: input.gross_income - input.spouse_income,
+            contractor_rate,
+            billable_hours,
+            contractor_revenue,
+            contractor_profit: self.contractor_profit(contractor_revenue, assumptions),
+            employer_fica: w2_base_fica - contractor_base_fica,
+            employee_fica: w2_fica - contractor_fica,
+            self_employment_tax: scenario.scenario.tax_breakdown.self_employment.total,
+            qbi_deduction: scenario.scenario.tax_breakdown.federal.qbi_deduction,
+            w2_value,
+            contractor_take_home,
+            contractor_advantage: contractor_take_home - w2_value,
+            break_even_hourly_rate: if billable_hours > Decimal::ZERO {
+                (revenue_to_match / billable_hours).round_dp(2)
+            } else {
+                Decimal::ZERO
+            },
+            scenario,
+        }
+    }
+
+    fn schedule_c(revenue: Decimal, assumptions: &ContractAssumptions) -> ScheduleC {
+        ScheduleC {
+            gross_receipts: revenue,
+            expenses: assumptions.business_expenses,
+            home_office: assumptions.home_office.clone(),
+            vehicle: None,
+        }
+    }
+
+    /// Schedule C profit on `revenue`
+    fn contractor_profit(&self, revenue: Decimal, assumptions: &ContractAssumptions) -> Decimal {
+        SelfEmploymentTaxCalculator::new(self.engine.data_provider())
+            .net_profit(&Self::schedule_c(revenue, assumptions), self.engine.year())
+    }
+
+    /// The job's wages replaced by Schedule C profit on `revenue`
+    fn contractor_input(
+        &self,
+        input: &TaxCalculationInput,
+        revenue: Decimal,
+        assumptions: &ContractAssumptions,
+    ) -> TaxCalculationInput {
+        let profit = self
+            .contractor_profit(revenue, assumptions)
+            .max(Decimal::ZERO);
+        TaxCalculationInput {
+            gross_income: input.spouse_income,
+            pre_tax_deductions: Decimal::ZERO,
+            traditional_401k: Decimal::ZERO,
+            roth_401k: Decimal::ZERO,
+            hsa_contribution: Decimal::ZERO,
+            employer_hsa_contribution: Decimal::ZERO,
+            health_fsa: Decimal::ZERO,
+            dependent_care_fsa: Decimal::ZERO,
+            self_employment_income: input.self_employment_income + profit,
+            ..input.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_w2_vs_contractor() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = EmploymentTypeAnalyzer::new(&engine);
+        let input = TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let assumptions = ContractAssumptions {
+            business_expenses: dec!(5000),
+            ..Default::default()
+        };
+
+        // 1,666 billable hours at $70
+        let comparison = analyzer.compare(&input, dec!(70), &assumptions);
+        assert_eq!(comparison.billable_hours, dec!(1666));
+        assert_eq!(comparison.contractor_revenue, dec!(116620));
+        assert_eq!(comparison.contractor_profit, dec!(111620));
+        assert_eq!(comparison.employer_fica, dec!(7650));
+        assert_eq!(comparison.employee_fica, dec!(7650));
+        assert_eq!(
+            comparison.self_employment_tax,
+            dec!(111620) * dec!(0.9235) * dec!(0.153)
+        );
+        assert!(comparison.qbi_deduction > Decimal::ZERO);
+        assert_eq!(comparison.scenario.base.income.net, comparison.w2_value);
+        assert_eq!(
+            comparison.contractor_advantage,
+            comparison.scenario.net_difference
+        );
+
+        // At the break-even rate the contractor keeps what the employee does
+        let even = analyzer.compare(&input, comparison.break_even_hourly_rate, &assumptions);
+        assert!(even.contractor_advantage.abs() < dec!(20));
+        assert!(comparison.break_even_hourly_rate > dec!(100000) / dec!(2080));
+    }
+}
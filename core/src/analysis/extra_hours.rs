@@ -0,0 +1,152 @@
+//! After-tax value of working one more hour
+//!
+//! An extra hour is taxed at the top of the year's income, not at the
+//! average rate: federal and state brackets, FICA, and any credits phasing
+//! out all take their share. The analyzer prices the hour by calculating the
+//! year with and without it.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::solve_increasing;
+use crate::calculators::timeframe::TimeframeCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::income::TimeframeIncome;
+use crate::models::rate::Rate;
+
+/// Overtime pay for hours over 40 a week under the FLSA
+pub const OVERTIME_MULTIPLIER: Decimal = dec!(1.5);
+
+/// Pay for one more hour before and after tax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtraHourValue {
+    /// Base hourly rate from the salary and work schedule
+    pub hourly_rate: Decimal,
+    /// 1 for a regular hour, 1.5 for overtime
+    pub pay_multiplier: Decimal,
+    pub gross: Decimal,
+    pub net: Decimal,
+    /// Share of the hour's pay lost to tax and credit phase-outs
+    pub marginal_rate: Rate,
+}
+
+/// Prices extra hours of work at the user's marginal rate
+pub struct ExtraHourAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> ExtraHourAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Hourly rate implied by the gross income and work schedule
+    pub fn hourly_rate(input: &TaxCalculationInput) -> Decimal {
+        TimeframeIncome::from_schedule(input.gross_income, &input.work_schedule).hourly
+    }
+
+    /// One regular hour
+    pub fn regular_hour(&self, input: &TaxCalculationInput) -> ExtraHourValue {
+        self.value(input, Decimal::ONE)
+    }
+
+    /// One overtime hour at time and a half
+    pub fn overtime_hour(&self, input: &TaxCalculationInput) -> ExtraHourValue {
+        self.value(input, OVERTIME_MULTIPLIER)
+    }
+
+    /// One hour paid at `pay_multiplier` times the hourly rate
+    pub fn value(&self, input: &TaxCalculationInput, pay_multiplier: Decimal) -> ExtraHourValue {
+        let hourly_rate = Self::hourly_rate(input);
+        let gross = (hourly_rate * pay_multiplier).round_dp(2);
+        let net = self.net_gain(input, gross).round_dp(2);
+        ExtraHourValue {
+            hourly_rate: hourly_rate.round_dp(2),
+            pay_multiplier,
+            gross,
+            net,
+            marginal_rate: Rate::of(gross - net, gross),
+        }
+    }
+
+    /// Hours at `pay_multiplier` needed to take home `target` more, taxing
+    /// each hour at the rate it's actually reached at rather than the first
+    /// hour's rate
+    pub fn hours_to_take_home(
+        &self,
+        input: &TaxCalculationInput,
+        pay_multiplier: Decimal,
+        target: Decimal,
+    ) -> Decimal {
+        TimeframeCalculator::hours_to_earn(
+            Self::hourly_rate(input) * pay_multiplier,
+            self.gross_to_take_home(input, target),
+        )
+        .round_dp(2)
+    }
+
+    /// Extra gross wages needed to take home `target` more
+    pub fn gross_to_take_home(&self, input: &TaxCalculationInput, target: Decimal) -> Decimal {
+        if target <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        solve_increasing(|extra| self.net_gain(input, extra), target)
+    }
+
+    /// Net pay gained from `extra` more gross wages (lost, when negative)
+    pub(crate) fn net_gain(&self, input: &TaxCalculationInput, extra: Decimal) -> Decimal {
+        let net = |gross_income: Decimal| {
+            self.engine
+                .calculate(&TaxCalculationInput {
+                    gross_income,
+                    ..input.clone()
+                })
+                .income
+                .net
+        };
+        net(input.gross_income + extra) - net(input.gross_income)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_extra_hour_at_marginal_rate() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = ExtraHourAnalyzer::new(&engine);
+        // $52/hour, in the 22% bracket, no state income tax
+        let input = TaxCalculationInput {
+            gross_income: dec!(108160),
+            state: USState::Texas,
+            ..Default::default()
+        };
+
+        let regular = analyzer.regular_hour(&input);
+        assert_eq!(regular.hourly_rate, dec!(52));
+        assert_eq!(regular.gross, dec!(52));
+        assert_eq!(regular.net, dec!(36.58));
+        assert_eq!(regular.marginal_rate, Rate::of(dec!(15.42), dec!(52)));
+
+        let overtime = analyzer.overtime_hour(&input);
+        assert_eq!(overtime.gross, dec!(78));
+        assert_eq!(overtime.net, dec!(54.87));
+
+        // Net is 70.35% of each hour until the 24% bracket
+        let hours = analyzer.hours_to_take_home(&input, Decimal::ONE, dec!(1000));
+        assert_eq!(
+            hours,
+            TimeframeCalculator::hours_to_earn_net(dec!(52), regular.marginal_rate, dec!(1000))
+                .round_dp(2)
+        );
+    }
+}
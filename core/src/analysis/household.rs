@@ -0,0 +1,634 @@
+//! Dual-earner household contribution optimizer
+//!
+//! Splits a household's 401(k), HSA, and dependent care FSA contributions
+//! between two earners filing jointly. Income tax is computed on the joint
+//! return, so the split matters through per-earner payroll taxes (Social
+//! Security wage base, SDI wage base) and each employer's 401(k) match.
+//! The chosen split is also evaluated on separate returns, applying
+//! community-property income splitting where the state requires it.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::offers::MatchFormula;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Granularity of the allocation search
+const SEARCH_STEP: Decimal = dec!(250);
+
+/// Household dependent care FSA limit (married filing jointly)
+const DEPENDENT_CARE_FSA_LIMIT: Decimal = dec!(5000);
+
+/// Age at which 401(k) catch-up deferrals are allowed
+const DEFERRAL_CATCH_UP_AGE: u32 = 50;
+
+/// Age at which HSA catch-up contributions are allowed
+const HSA_CATCH_UP_AGE: u32 = 55;
+
+/// One earner in the household
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Earner {
+    pub name: String,
+    pub gross_income: Decimal,
+    pub age: u32,
+    /// Other pre-tax payroll deductions (premiums, etc.)
+    pub pre_tax_deductions: Decimal,
+    pub employer_match: MatchFormula,
+    /// Employer offers payroll HSA contributions
+    pub hsa_available: bool,
+    /// Employer offers a dependent care FSA
+    pub dependent_care_fsa_available: bool,
+}
+
+/// Total amounts the household wants to contribute
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContributionTargets {
+    pub traditional_401k: Decimal,
+    pub hsa: Decimal,
+    pub dependent_care_fsa: Decimal,
+}
+
+/// Input for the household optimizer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdOptimizationInput {
+    pub primary: Earner,
+    pub partner: Earner,
+    pub state: USState,
+    pub targets: ContributionTargets,
+    /// Family HSA coverage (shared household limit) vs self-only
+    pub hsa_family_coverage: bool,
+}
+
+/// Contributions assigned to one earner
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EarnerAllocation {
+    pub traditional_401k: Decimal,
+    pub hsa: Decimal,
+    pub dependent_care_fsa: Decimal,
+    pub employer_match: Decimal,
+}
+
+/// Evaluated household allocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdAllocation {
+    pub primary: EarnerAllocation,
+    pub partner: EarnerAllocation,
+    pub total_taxes: Decimal,
+    pub combined_take_home: Decimal,
+    pub employer_match: Decimal,
+    /// Take-home pay plus employer match, the optimization objective
+    pub combined_value: Decimal,
+}
+
+/// Optimizer result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdOptimization {
+    pub optimal: HouseholdAllocation,
+    /// Naive split: 401(k) by income share, HSA/FSA through the primary earner
+    pub baseline: HouseholdAllocation,
+    /// The optimal split filed married filing separately
+    pub separate_returns: HouseholdAllocation,
+    /// Contributions actually allocated after applying limits
+    pub allocated_targets: ContributionTargets,
+}
+
+impl HouseholdOptimization {
+    /// Annual gain of the optimal split over the naive one
+    pub fn improvement(&self) -> Decimal {
+        self.optimal.combined_value - self.baseline.combined_value
+    }
+
+    /// Annual gain from filing separately instead of jointly (usually negative)
+    pub fn separate_filing_difference(&self) -> Decimal {
+        self.separate_returns.combined_value - self.optimal.combined_value
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Contribution {
+    Retirement,
+    Hsa,
+    DependentCare,
+}
+
+/// Dual-earner contribution optimizer built on the calculation engine
+pub struct HouseholdOptimizer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> HouseholdOptimizer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Find the split that maximizes combined take-home plus employer match
+    pub fn optimize(&self, input: &HouseholdOptimizationInput) -> HouseholdOptimization {
+        let targets = self.feasible_targets(input);
+
+        let baseline = self.baseline(input, &targets);
+        let mut best = baseline.clone();
+
+        // Coordinate search: the objective is piecewise linear in each split,
+        // and two passes settle interactions between HSA/FSA and 401(k)
+        for _ in 0..2 {
+            for kind in [
+                Contribution::DependentCare,
+                Contribution::Hsa,
+                Contribution::Retirement,
+            ] {
+                best = self.best_split(input, &best, kind, &targets);
+            }
+        }
+
+        let separate_returns =
+            self.evaluate_separate(input, best.primary.clone(), best.partner.clone());
+
+        HouseholdOptimization {
+            optimal: best,
+            baseline,
+            separate_returns,
+            allocated_targets: targets,
+        }
+    }
+
+    /// Clip targets to what the two earners can legally contribute
+    fn feasible_targets(&self, input: &HouseholdOptimizationInput) -> ContributionTargets {
+        let retirement = input
+            .targets
+            .traditional_401k
+            .min(self.deferral_cap(&input.primary) + self.deferral_cap(&input.partner));
+
+        let limits = self
+            .engine
+            .data_provider()
+            .retirement_limits(self.engine.year());
+        let hsa_limit = if input.hsa_family_coverage {
+            limits.hsa_family
+        } else {
+            limits.hsa_self_only
+        };
+        let any_hsa = input.primary.hsa_available || input.partner.hsa_available;
+        let hsa_catch_up = [&input.primary, &input.partner]
+            .iter()
+            .filter(|e| e.hsa_available && e.age >= HSA_CATCH_UP_AGE)
+            .count();
+        let hsa = if any_hsa {
+            input
+                .targets
+                .hsa
+                .min(hsa_limit + limits.hsa_catch_up * Decimal::from(hsa_catch_up))
+        } else {
+            Decimal::ZERO
+        };
+
+        let any_fsa = input.primary.dependent_care_fsa_available
+            || input.partner.dependent_care_fsa_available;
+        let dependent_care_fsa = if any_fsa {
+            input
+                .targets
+                .dependent_care_fsa
+                .min(DEPENDENT_CARE_FSA_LIMIT)
+        } else {
+            Decimal::ZERO
+        };
+
+        ContributionTargets {
+            traditional_401k: retirement,
+            hsa,
+            dependent_care_fsa,
+        }
+    }
+
+    fn deferral_cap(&self, earner: &Earner) -> Decimal {
+        let limits = self
+            .engine
+            .data_provider()
+            .retirement_limits(self.engine.year());
+        let catch_up = if earner.age >= DEFERRAL_CATCH_UP_AGE {
+            limits.catch_up_deferral
+        } else {
+            Decimal::ZERO
+        };
+        (limits.elective_deferral + catch_up).min(earner.gross_income)
+    }
+
+    /// Maximum amount of a contribution type one earner can take
+    fn earner_cap(&self, earner: &Earner, kind: Contribution, total: Decimal) -> Decimal {
+        match kind {
+            Contribution::Retirement => self.deferral_cap(earner),
+            Contribution::Hsa if earner.hsa_available => total,
+            Contribution::DependentCare if earner.dependent_care_fsa_available => total,
+            _ => Decimal::ZERO,
+        }
+    }
+
+    fn baseline(
+        &self,
+        input: &HouseholdOptimizationInput,
+        targets: &ContributionTargets,
+    ) -> HouseholdAllocation {
+        let total_gross = input.primary.gross_income + input.partner.gross_income;
+        let share = if total_gross > Decimal::ZERO {
+            input.primary.gross_income / total_gross
+        } else {
+            dec!(0.5)
+        };
+
+        let mut primary = EarnerAllocation::default();
+        let mut partner = EarnerAllocation::default();
+
+        let retirement_primary = self.clamp_primary(
+            input,
+            Contribution::Retirement,
+            targets.traditional_401k,
+            targets.traditional_401k * share,
+        );
+        primary.traditional_401k = retirement_primary;
+        partner.traditional_401k = targets.traditional_401k - retirement_primary;
+
+        let hsa_primary = self.clamp_primary(input, Contribution::Hsa, targets.hsa, targets.hsa);
+        primary.hsa = hsa_primary;
+        partner.hsa = targets.hsa - hsa_primary;
+
+        let fsa_primary = self.clamp_primary(
+            input,
+            Contribution::DependentCare,
+            targets.dependent_care_fsa,
+            targets.dependent_care_fsa,
+        );
+        primary.dependent_care_fsa = fsa_primary;
+        partner.dependent_care_fsa = targets.dependent_care_fsa - fsa_primary;
+
+        self.evaluate(input, primary, partner)
+    }
+
+    /// Feasible range for the primary earner's share of a contribution
+    fn primary_range(
+        &self,
+        input: &HouseholdOptimizationInput,
+        kind: Contribution,
+        total: Decimal,
+    ) -> (Decimal, Decimal) {
+        let primary_cap = self.earner_cap(&input.primary, kind, total).min(total);
+        let partner_cap = self.earner_cap(&input.partner, kind, total).min(total);
+        let low = (total - partner_cap).max(Decimal::ZERO);
+        (low, primary_cap.max(low))
+    }
+
+    fn clamp_primary(
+        &self,
+        input: &HouseholdOptimizationInput,
+        kind: Contribution,
+        total: Decimal,
+        desired: Decimal,
+    ) -> Decimal {
+        let (low, high) = self.primary_range(input, kind, total);
+        desired.max(low).min(high)
+    }
+
+    /// Search one contribution type's split, holding the others fixed
+    fn best_split(
+        &self,
+        input: &HouseholdOptimizationInput,
+        current: &HouseholdAllocation,
+        kind: Contribution,
+        targets: &ContributionTargets,
+    ) -> HouseholdAllocation {
+        let total = match kind {
+            Contribution::Retirement => targets.traditional_401k,
+            Contribution::Hsa => targets.hsa,
+            Contribution::DependentCare => targets.dependent_care_fsa,
+        };
+        let (low, high) = self.primary_range(input, kind, total);
+
+        let mut best = current.clone();
+        let mut amount = low;
+        loop {
+            let mut primary = current.primary.clone();
+            let mut partner = current.partner.clone();
+            let (p, q) = match kind {
+                Contribution::Retirement => {
+                    (&mut primary.traditional_401k, &mut partner.traditional_401k)
+                },
+                Contribution::Hsa => (&mut primary.hsa, &mut partner.hsa),
+                Contribution::DependentCare => (
+                    &mut primary.dependent_care_fsa,
+                    &mut partner.dependent_care_fsa,
+                ),
+            };
+            *p = amount;
+            *q = total - amount;
+
+            let candidate = self.evaluate(input, primary, partner);
+            if candidate.combined_value > best.combined_value {
+                best = candidate;
+            }
+
+            if amount >= high {
+                break;
+            }
+            amount = (amount + SEARCH_STEP).min(high);
+        }
+
+        best
+    }
+
+    /// Evaluate a split: joint income tax, per-earner payroll taxes
+    fn evaluate(
+        &self,
+        input: &HouseholdOptimizationInput,
+        primary: EarnerAllocation,
+        partner: EarnerAllocation,
+    ) -> HouseholdAllocation {
+        let earner_input = |earner: &Earner, alloc: &EarnerAllocation| TaxCalculationInput {
+            gross_income: earner.gross_income,
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: input.state,
+            pre_tax_deductions: earner.pre_tax_deductions,
+            traditional_401k: alloc.traditional_401k,
+            hsa_contribution: alloc.hsa,
+            dependent_care_fsa: alloc.dependent_care_fsa,
+            ..Default::default()
+        };
+        let primary_input = earner_input(&input.primary, &primary);
+        let partner_input = earner_input(&input.partner, &partner);
+
+        let joint = self.engine.calculate(&TaxCalculationInput {
+            gross_income: primary_input.gross_income + partner_input.gross_income,
+            pre_tax_deductions: primary_input.pre_tax_deductions + partner_input.pre_tax_deductions,
+            traditional_401k: primary.traditional_401k + partner.traditional_401k,
+            hsa_contribution: primary.hsa + partner.hsa,
+            dependent_care_fsa: primary.dependent_care_fsa + partner.dependent_care_fsa,
+            spouse_income: partner_input.gross_income,
+            ..primary_input.clone()
+        });
+
+        // Social Security, Medicare, and SDI are capped per earner
+        let payroll = |i: &TaxCalculationInput| {
+            let r = self.engine.calculate(i);
+            r.tax_breakdown.fica.social_security
+                + r.tax_breakdown.fica.medicare
+                + r.tax_breakdown.state.sdi
+        };
+
+        let total_taxes = joint.tax_breakdown.federal.tax
+            + joint.tax_breakdown.state.income_tax
+            + joint.tax_breakdown.state.local_tax
+            + joint.tax_breakdown.fica.additional_medicare
+            + payroll(&primary_input)
+            + payroll(&partner_input);
+
+        self.allocation(input, primary, partner, total_taxes)
+    }
+
+    /// Evaluate a split on separate returns
+    ///
+    /// In community-property states each spouse reports half of the couple's
+    /// wages and of the pre-tax deductions taken from them; elsewhere each
+    /// reports their own. Payroll taxes, including additional Medicare, stay
+    /// with the spouse who earned the wages.
+    fn evaluate_separate(
+        &self,
+        input: &HouseholdOptimizationInput,
+        primary: EarnerAllocation,
+        partner: EarnerAllocation,
+    ) -> HouseholdAllocation {
+        let earner_input = |earner: &Earner, alloc: &EarnerAllocation| TaxCalculationInput {
+            gross_income: earner.gross_income,
+            filing_status: FilingStatus::MarriedFilingSeparately,
+            state: input.state,
+            pre_tax_deductions: earner.pre_tax_deductions,
+            traditional_401k: alloc.traditional_401k,
+            hsa_contribution: alloc.hsa,
+            dependent_care_fsa: alloc.dependent_care_fsa,
+            ..Default::default()
+        };
+        let primary_input = earner_input(&input.primary, &primary);
+        let partner_input = earner_input(&input.partner, &partner);
+
+        let (primary_return, partner_return) = if input.state.is_community_property() {
+            let half = |field: fn(&TaxCalculationInput) -> Decimal| {
+                (field(&primary_input) + field(&partner_input)) / dec!(2)
+            };
+            let community_half = TaxCalculationInput {
+                gross_income: half(|i| i.gross_income),
+                pre_tax_deductions: half(|i| i.pre_tax_deductions),
+                traditional_401k: half(|i| i.traditional_401k),
+                hsa_contribution: half(|i| i.hsa_contribution),
+                dependent_care_fsa: half(|i| i.dependent_care_fsa),
+                ..primary_input.clone()
+            };
+            (community_half.clone(), community_half)
+        } else {
+            (primary_input.clone(), partner_input.clone())
+        };
+
+        let income_tax = |i: &TaxCalculationInput| {
+            let r = self.engine.calculate(i);
+            r.tax_breakdown.federal.tax
+                + r.tax_breakdown.state.income_tax
+                + r.tax_breakdown.state.local_tax
+        };
+        let payroll = |i: &TaxCalculationInput| {
+            let r = self.engine.calculate(i);
+            r.tax_breakdown.fica.total + r.tax_breakdown.state.sdi
+        };
+
+        let total_taxes = income_tax(&primary_return)
+            + income_tax(&partner_return)
+            + payroll(&primary_input)
+            + payroll(&partner_input);
+
+        self.allocation(input, primary, partner, total_taxes)
+    }
+
+    /// Attach employer matches and take-home to an evaluated split
+    fn allocation(
+        &self,
+        input: &HouseholdOptimizationInput,
+        mut primary: EarnerAllocation,
+        mut partner: EarnerAllocation,
+        total_taxes: Decimal,
+    ) -> HouseholdAllocation {
+        primary.employer_match = input
+            .primary
+            .employer_match
+            .employer_match(input.primary.gross_income, primary.traditional_401k);
+        partner.employer_match = input
+            .partner
+            .employer_match
+            .employer_match(input.partner.gross_income, partner.traditional_401k);
+        let employer_match = primary.employer_match + partner.employer_match;
+
+        let deductions = [(&input.primary, &primary), (&input.partner, &partner)]
+            .iter()
+            .map(|(earner, alloc)| {
+                earner.pre_tax_deductions
+                    + alloc.traditional_401k
+                    + alloc.hsa
+                    + alloc.dependent_care_fsa
+            })
+            .sum::<Decimal>();
+        let gross = input.primary.gross_income + input.partner.gross_income;
+        let combined_take_home = gross - total_taxes - deductions;
+
+        HouseholdAllocation {
+            primary,
+            partner,
+            total_taxes,
+            combined_take_home,
+            employer_match,
+            combined_value: combined_take_home + employer_match,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn earner(name: &str, gross_income: Decimal) -> Earner {
+        Earner {
+            name: name.to_string(),
+            gross_income,
+            age: 40,
+            hsa_available: true,
+            dependent_care_fsa_available: true,
+            ..Default::default()
+        }
+    }
+
+    fn input(
+        primary: Earner,
+        partner: Earner,
+        targets: ContributionTargets,
+    ) -> HouseholdOptimizationInput {
+        HouseholdOptimizationInput {
+            primary,
+            partner,
+            state: USState::Texas,
+            targets,
+            hsa_family_coverage: true,
+        }
+    }
+
+    #[test]
+    fn test_hsa_routed_below_wage_base() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let optimizer = HouseholdOptimizer::new(&engine);
+
+        // Primary earns above the Social Security wage base, so cafeteria-plan
+        // dollars save 7.65% through the partner but only 1.45% through primary
+        let result = optimizer.optimize(&input(
+            earner("Primary", dec!(250000)),
+            earner("Partner", dec!(80000)),
+            ContributionTargets {
+                hsa: dec!(8300),
+                dependent_care_fsa: dec!(5000),
+                ..Default::default()
+            },
+        ));
+
+        assert_eq!(result.optimal.partner.hsa, dec!(8300));
+        assert_eq!(result.optimal.partner.dependent_care_fsa, dec!(5000));
+        // 6.2% Social Security on $13,300 moved below the cap
+        assert_eq!(result.improvement(), dec!(13300) * dec!(0.062));
+    }
+
+    #[test]
+    fn test_401k_captures_both_matches() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let optimizer = HouseholdOptimizer::new(&engine);
+
+        let matched = MatchFormula {
+            match_rate: dec!(1),
+            salary_cap: dec!(0.05),
+        };
+        let result = optimizer.optimize(&input(
+            Earner {
+                employer_match: MatchFormula::default(),
+                ..earner("Primary", dec!(200000))
+            },
+            Earner {
+                employer_match: matched,
+                ..earner("Partner", dec!(60000))
+            },
+            ContributionTargets {
+                traditional_401k: dec!(10000),
+                ..Default::default()
+            },
+        ));
+
+        // Only the partner's employer matches, up to 5% of $60,000
+        assert!(result.optimal.partner.traditional_401k >= dec!(3000));
+        assert_eq!(result.optimal.employer_match, dec!(3000));
+        assert!(result.improvement() > dec!(0));
+    }
+
+    #[test]
+    fn test_community_property_separate_returns() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let optimizer = HouseholdOptimizer::new(&engine);
+
+        let couple = |state| HouseholdOptimizationInput {
+            state,
+            ..input(
+                earner("Primary", dec!(200000)),
+                earner("Partner", dec!(0)),
+                ContributionTargets::default(),
+            )
+        };
+
+        // Texas splits the wages, so each separate return mirrors half the
+        // joint one; only additional Medicare above the $125,000 MFS
+        // threshold on the primary's own wages differs
+        let texas = optimizer.optimize(&couple(USState::Texas));
+        assert_eq!(
+            texas.separate_filing_difference(),
+            -(dec!(75000) * dec!(0.009))
+        );
+
+        // Florida leaves all wages on the primary's separate return
+        let florida = optimizer.optimize(&couple(USState::Florida));
+        assert!(florida.separate_filing_difference() < texas.separate_filing_difference());
+    }
+
+    #[test]
+    fn test_targets_clipped_to_limits() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let optimizer = HouseholdOptimizer::new(&engine);
+
+        let result = optimizer.optimize(&input(
+            earner("Primary", dec!(150000)),
+            Earner {
+                hsa_available: false,
+                dependent_care_fsa_available: false,
+                ..earner("Partner", dec!(100000))
+            },
+            ContributionTargets {
+                traditional_401k: dec!(60000),
+                hsa: dec!(10000),
+                dependent_care_fsa: dec!(7500),
+            },
+        ));
+
+        assert_eq!(result.allocated_targets.traditional_401k, dec!(46000));
+        assert_eq!(result.allocated_targets.hsa, dec!(8300));
+        assert_eq!(result.allocated_targets.dependent_care_fsa, dec!(5000));
+        assert_eq!(result.optimal.partner.hsa, dec!(0));
+        assert_eq!(result.optimal.primary.traditional_401k, dec!(23000));
+        assert_eq!(result.optimal.partner.traditional_401k, dec!(23000));
+    }
+}
@@ -0,0 +1,75 @@
+//! Higher-level analyses built on the calculation engine
+
+pub mod benefit_cliffs;
+pub mod bracket_chart;
+pub mod cash_flow;
+pub mod charitable;
+pub mod comp_time;
+pub mod contract_rate;
+pub mod day_rate;
+pub mod employment_type;
+pub mod extra_hours;
+pub mod household;
+pub mod multiple_jobs;
+pub mod offers;
+pub mod pay_calendar;
+pub mod pay_spread;
+pub mod paystub;
+pub mod projection;
+pub mod purchase_cost;
+pub mod retirement;
+pub mod savings;
+pub mod severance;
+pub mod side_income;
+pub mod social_security;
+pub mod student_loans;
+
+pub use benefit_cliffs::BenefitCliffDetector;
+pub use bracket_chart::BracketChartAnalyzer;
+pub use cash_flow::CashFlowProjector;
+pub use charitable::CharitableBunchingAnalyzer;
+pub use comp_time::CompTimeAnalyzer;
+pub use contract_rate::ContractRateConverter;
+pub use day_rate::DayRatePlanner;
+pub use employment_type::EmploymentTypeAnalyzer;
+pub use extra_hours::ExtraHourAnalyzer;
+pub use household::HouseholdOptimizer;
+pub use multiple_jobs::MultipleJobsAnalyzer;
+pub use offers::OfferEvaluator;
+pub use pay_calendar::PayCalendarProjector;
+pub use pay_spread::PaySpreadAnalyzer;
+pub use paystub::PaystubVerifier;
+pub use projection::MultiYearProjector;
+pub use purchase_cost::PurchaseCostConverter;
+pub use retirement::RetirementProjection;
+pub use savings::SavingsWaterfallRecommender;
+pub use severance::SeparationPayoutAnalyzer;
+pub use side_income::SideIncomeAnalyzer;
+pub use social_security::ClaimingAgeAnalyzer;
+pub use student_loans::IdrEstimator;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Search stops once the bracketing interval is narrower than this
+const SOLVE_PRECISION: Decimal = dec!(0.01);
+
+/// Smallest non-negative input (to the cent) at which an increasing
+/// function reaches the target
+pub(crate) fn solve_increasing(f: impl Fn(Decimal) -> Decimal, target: Decimal) -> Decimal {
+    let mut low = Decimal::ZERO;
+    let mut high = target.max(Decimal::ONE);
+    while f(high) < target {
+        low = high;
+        high *= dec!(2);
+    }
+    while high - low > SOLVE_PRECISION {
+        let mid = (low + high) / dec!(2);
+        if f(mid) < target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    high.round_dp(2)
+}
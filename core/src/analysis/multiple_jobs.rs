@@ -0,0 +1,272 @@
+//! Withholding across two or more jobs held at once
+//!
+//! Each employer withholds as if its job were the only income, taking the
+//! full standard deduction and the bottom brackets again. Checking W-4
+//! Step 2 on every job switches each to tables that assume two equal jobs.
+//! Steps 3 and 4 belong on the highest-paying job's W-4 only.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::withholding::FederalWithholdingCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::income::PayFrequency;
+use crate::models::profile::W4Settings;
+
+/// One W-2 job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Job {
+    pub name: String,
+    pub annual_wages: Decimal,
+    pub pay_frequency: PayFrequency,
+    /// 401(k), HSA, and other deductions that reduce federal wages
+    #[serde(default)]
+    pub pre_tax_deductions: Decimal,
+    /// Held by the spouse on a joint return rather than the taxpayer
+    #[serde(default)]
+    pub spouse: bool,
+}
+
+impl Job {
+    fn taxable_wages(&self) -> Decimal {
+        (self.annual_wages - self.pre_tax_deductions).max(Decimal::ZERO)
+    }
+}
+
+/// A job's federal withholding with and without Step 2
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobWithholding {
+    pub name: String,
+    pub per_paycheck_standard: Decimal,
+    pub per_paycheck_step_2: Decimal,
+    pub annual_standard: Decimal,
+    pub annual_step_2: Decimal,
+}
+
+/// Withholding on every job against the combined federal liability
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipleJobsAnalysis {
+    pub jobs: Vec<JobWithholding>,
+    /// Federal income tax on all jobs together
+    pub federal_liability: Decimal,
+    pub withheld_standard: Decimal,
+    pub withheld_step_2: Decimal,
+    /// Owed at filing with Step 2 unchecked; negative for a refund
+    pub shortfall_standard: Decimal,
+    /// Owed at filing with Step 2 checked on every job
+    pub shortfall_step_2: Decimal,
+    /// Social Security withheld beyond each person's annual maximum,
+    /// refunded at filing
+    pub excess_social_security: Decimal,
+}
+
+/// Multiple jobs withholding analyzer
+pub struct MultipleJobsAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> MultipleJobsAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Withholding on each job and the combined shortfall
+    ///
+    /// `input` supplies filing status, deductions, and non-wage income; its
+    /// wages and pre-tax deductions are replaced by the jobs'. `w4` applies
+    /// in full to the highest-paying job; the others get only its filing
+    /// status.
+    pub fn analyze(
+        &self,
+        input: &TaxCalculationInput,
+        jobs: &[Job],
+        w4: &W4Settings,
+    ) -> MultipleJobsAnalysis {
+        let data = self.engine.data_provider();
+        let year = self.engine.year();
+        let calc = FederalWithholdingCalculator::new(data);
+        let highest = jobs
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, job)| job.annual_wages)
+            .map(|(index, _)| index);
+        let other_jobs_w4 = W4Settings {
+            filing_status: w4.filing_status,
+            ..Default::default()
+        };
+
+        let withholding: Vec<JobWithholding> = jobs
+            .iter()
+            .enumerate()
+            .map(|(index, job)| {
+                let job_w4 = if Some(index) == highest {
+                    w4
+                } else {
+                    &other_jobs_w4
+                };
+                let periods = Decimal::from(job.pay_frequency.periods_per_year());
+                let paycheck = job.taxable_wages() / periods;
+                let withhold = |multiple_jobs| {
+                    calc.per_paycheck(
+                        paycheck,
+                        job.pay_frequency,
+                        &W4Settings {
+                            multiple_jobs,
+                            ..job_w4.clone()
+                        },
+                        input.filing_status,
+                        year,
+                    )
+                };
+                let (standard, step_2) = (withhold(false), withhold(true));
+                JobWithholding {
+                    name: job.name.clone(),
+                    per_paycheck_standard: standard,
+                    per_paycheck_step_2: step_2,
+                    annual_standard: standard * periods,
+                    annual_step_2: step_2 * periods,
+                }
+            })
+            .collect();
+
+        let wages = jobs.iter().map(|job| job.annual_wages).sum();
+        let pre_tax: Decimal = jobs.iter().map(|job| job.pre_tax_deductions).sum();
+        let combined = self.engine.calculate(&TaxCalculationInput {
+            gross_income: wages,
+            pre_tax_deductions: pre_tax,
+            traditional_401k: Decimal::ZERO,
+            roth_401k: Decimal::ZERO,
+            hsa_contribution: Decimal::ZERO,
+            health_fsa: Decimal::ZERO,
+            dependent_care_fsa: Decimal::ZERO,
+            ..input.clone()
+        });
+        let federal_liability = combined.tax_breakdown.federal.tax;
+        let withheld_standard = withholding.iter().map(|job| job.annual_standard).sum();
+        let withheld_step_2 = withholding.iter().map(|job| job.annual_step_2).sum();
+
+        // Each employer withholds up to the wage base on its own wages, but
+        // each person only owes up to the wage base on all of theirs
+        let fica = data.fica_config(year);
+        let excess_social_security: Decimal = [false, true]
+            .into_iter()
+            .map(|spouse| {
+                let (withheld, earned) = jobs.iter().filter(|job| job.spouse == spouse).fold(
+                    (Decimal::ZERO, Decimal::ZERO),
+                    |(withheld, earned), job| {
+                        (
+                            withheld + job.annual_wages.min(fica.wage_base),
+                            earned + job.annual_wages,
+                        )
+                    },
+                );
+                (withheld - earned.min(fica.wage_base)) * fica.social_security_rate
+            })
+            .sum();
+
+        MultipleJobsAnalysis {
+            jobs: withholding,
+            federal_liability,
+            withheld_standard,
+            withheld_step_2,
+            shortfall_standard: federal_liability - withheld_standard,
+            shortfall_step_2: federal_liability - withheld_step_2,
+            excess_social_security,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use crate::models::tax::FilingStatus;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_two_jobs_under_withhold_without_step_2() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = MultipleJobsAnalyzer::new(&engine);
+        let input = TaxCalculationInput {
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let job = |name: &str, spouse| Job {
+            name: name.to_string(),
+            annual_wages: dec!(90000),
+            pay_frequency: PayFrequency::BiWeekly,
+            pre_tax_deductions: Decimal::ZERO,
+            spouse,
+        };
+
+        let analysis = analyzer.analyze(
+            &input,
+            &[job("Taxpayer", false), job("Spouse", true)],
+            &W4Settings::default(),
+        );
+
+        // $180,000 less the $29,200 standard deduction
+        let liability = dec!(2320) + dec!(71100) * dec!(0.12) + dec!(56500) * dec!(0.22);
+        assert_eq!(analysis.federal_liability, liability);
+        // Each job withholds as if $60,800 were all the taxable income
+        assert_eq!(analysis.jobs[0].per_paycheck_standard, dec!(262.77));
+        assert!(analysis.shortfall_standard > dec!(9000));
+        // Equal jobs are what the Step 2 tables assume
+        assert!(analysis.shortfall_step_2.abs() < dec!(1));
+        assert_eq!(analysis.excess_social_security, dec!(0));
+    }
+
+    #[test]
+    fn test_w4_adjustments_only_on_highest_paying_job() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = MultipleJobsAnalyzer::new(&engine);
+        let jobs = [
+            Job {
+                name: "Side".to_string(),
+                annual_wages: dec!(30000),
+                pay_frequency: PayFrequency::Weekly,
+                pre_tax_deductions: Decimal::ZERO,
+                spouse: false,
+            },
+            Job {
+                name: "Main".to_string(),
+                annual_wages: dec!(160000),
+                pay_frequency: PayFrequency::SemiMonthly,
+                pre_tax_deductions: dec!(10000),
+                spouse: false,
+            },
+        ];
+        let w4 = W4Settings {
+            extra_withholding: dec!(50),
+            ..Default::default()
+        };
+
+        let analysis = analyzer.analyze(&TaxCalculationInput::default(), &jobs, &w4);
+        let plain = analyzer.analyze(
+            &TaxCalculationInput::default(),
+            &jobs,
+            &W4Settings::default(),
+        );
+        assert_eq!(
+            analysis.jobs[1].annual_standard,
+            plain.jobs[1].annual_standard + dec!(50) * dec!(24)
+        );
+        assert_eq!(
+            analysis.jobs[0].annual_standard,
+            plain.jobs[0].annual_standard
+        );
+        // $190,000 of wages against a $168,600 wage base
+        assert_eq!(
+            analysis.excess_social_security,
+            (dec!(190000) - dec!(168600)) * dec!(0.062)
+        );
+    }
+}
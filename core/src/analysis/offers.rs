@@ -0,0 +1,431 @@
+//! Total compensation valuation for comparing job offers
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+use crate::models::household::{BudgetSummary, MonthlyExpenses};
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Employer 401(k) match formula
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchFormula {
+    /// Employer dollars per employee dollar (e.g. 0.5 for a 50% match)
+    pub match_rate: Decimal,
+    /// Employee contributions matched, as a fraction of base salary (e.g. 0.06)
+    pub salary_cap: Decimal,
+}
+
+impl MatchFormula {
+    /// Annual employer match for an employee contribution
+    pub fn employer_match(&self, base_salary: Decimal, employee_contribution: Decimal) -> Decimal {
+        employee_contribution.min(base_salary * self.salary_cap) * self.match_rate
+    }
+}
+
+/// Employer-paid insurance premiums, valued at what the employer pays
+///
+/// Premiums the employer pays are excluded from wages, so each dollar here
+/// is worth a full after-tax dollar compared with buying the same coverage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmployerBenefits {
+    /// Employer share of health, dental, and vision premiums
+    pub health_premiums: Decimal,
+    /// Group-term life insurance premiums
+    #[serde(default)]
+    pub life_insurance: Decimal,
+    /// Short- and long-term disability premiums
+    #[serde(default)]
+    pub disability_insurance: Decimal,
+}
+
+impl EmployerBenefits {
+    pub fn total(&self) -> Decimal {
+        self.health_premiums + self.life_insurance + self.disability_insurance
+    }
+}
+
+/// Annual value of each employer benefit in a package
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenefitsValue {
+    pub health_premiums: Decimal,
+    /// Employer HSA seed money
+    pub hsa_contribution: Decimal,
+    pub life_insurance: Decimal,
+    pub disability_insurance: Decimal,
+    /// Benefits given as a single lump value
+    pub other: Decimal,
+}
+
+impl BenefitsValue {
+    pub fn total(&self) -> Decimal {
+        self.health_premiums
+            + self.hsa_contribution
+            + self.life_insurance
+            + self.disability_insurance
+            + self.other
+    }
+}
+
+/// A compensation package to evaluate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompensationPackage {
+    pub name: String,
+    pub base_salary: Decimal,
+    pub bonus: Decimal,
+    /// Target bonus as a fraction of base salary, on top of `bonus`
+    #[serde(default)]
+    pub bonus_percent: Decimal,
+    /// Annual value of equity vesting (taxed as wages at vest)
+    pub equity_vesting: Decimal,
+    pub filing_status: FilingStatus,
+    pub state: USState,
+    /// Employee traditional 401(k) contribution
+    pub traditional_401k: Decimal,
+    pub pre_tax_deductions: Decimal,
+    pub employer_match: MatchFormula,
+    /// Employer-paid premiums by type
+    #[serde(default)]
+    pub benefits: EmployerBenefits,
+    /// Annual value of other employer-paid benefits not itemized in `benefits`
+    pub employer_benefits: Decimal,
+    /// Employer HSA contribution, untaxed but counted toward the HSA limit
+    #[serde(default)]
+    pub employer_hsa_contribution: Decimal,
+    /// Cost of living where the job is, for relocation comparisons
+    #[serde(default)]
+    pub monthly_expenses: MonthlyExpenses,
+}
+
+impl CompensationPackage {
+    /// Fixed bonus plus the percentage-of-salary target
+    pub fn total_bonus(&self) -> Decimal {
+        self.bonus + self.base_salary * self.bonus_percent
+    }
+
+    /// Employer benefits, itemized
+    pub fn benefits_value(&self) -> BenefitsValue {
+        BenefitsValue {
+            health_premiums: self.benefits.health_premiums,
+            hsa_contribution: self.employer_hsa_contribution,
+            life_insurance: self.benefits.life_insurance,
+            disability_insurance: self.benefits.disability_insurance,
+            other: self.employer_benefits,
+        }
+    }
+
+    fn to_calculation_input(&self) -> TaxCalculationInput {
+        TaxCalculationInput {
+            gross_income: self.base_salary + self.total_bonus() + self.equity_vesting,
+            filing_status: self.filing_status,
+            state: self.state,
+            pre_tax_deductions: self.pre_tax_deductions,
+            traditional_401k: self.traditional_401k,
+            employer_hsa_contribution: self.employer_hsa_contribution,
+            ..Default::default()
+        }
+    }
+}
+
+/// After-tax valuation of a single package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfferEvaluation {
+    pub name: String,
+    /// Cash compensation plus employer match and benefits, before tax
+    pub total_compensation: Decimal,
+    pub employer_match: Decimal,
+    /// Employer-paid premiums, HSA seed, and other benefits
+    pub benefits: BenefitsValue,
+    pub calculation: TaxCalculationResult,
+    /// Take-home pay
+    pub after_tax_cash: Decimal,
+    /// Take-home pay plus retirement savings, employer match, and benefits
+    pub after_tax_value: Decimal,
+    /// Monthly take-home measured against the package's expenses
+    pub budget: BudgetSummary,
+}
+
+/// Ranked comparison of several packages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfferComparison {
+    /// Evaluations ordered from highest to lowest after-tax value
+    pub ranked: Vec<OfferEvaluation>,
+}
+
+impl OfferComparison {
+    pub fn best(&self) -> Option<&OfferEvaluation> {
+        self.ranked.first()
+    }
+
+    /// After-tax value shortfall of a package relative to the best one
+    pub fn gap_to_best(&self, name: &str) -> Option<Decimal> {
+        let best = self.best()?.after_tax_value;
+        self.ranked
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| best - e.after_tax_value)
+    }
+
+    /// Evaluations ordered by monthly take-home left after expenses
+    pub fn by_discretionary_income(&self) -> Vec<&OfferEvaluation> {
+        let mut ranked: Vec<_> = self.ranked.iter().collect();
+        ranked.sort_by_key(|e| std::cmp::Reverse(e.budget.discretionary_income));
+        ranked
+    }
+}
+
+/// Offer evaluator built on the calculation engine
+pub struct OfferEvaluator<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> OfferEvaluator<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Value a single package after tax
+    pub fn evaluate(&self, package: &CompensationPackage) -> OfferEvaluation {
+        let calculation = self.engine.calculate(&package.to_calculation_input());
+        Self::valuation(package, calculation)
+    }
+
+    fn valuation(
+        package: &CompensationPackage,
+        calculation: TaxCalculationResult,
+    ) -> OfferEvaluation {
+        let employer_match = package
+            .employer_match
+            .employer_match(package.base_salary, package.traditional_401k);
+        let benefits = package.benefits_value();
+
+        let total_compensation = package.base_salary
+            + package.total_bonus()
+            + package.equity_vesting
+            + employer_match
+            + benefits.total();
+
+        let after_tax_cash = calculation.income.net;
+        let budget = package
+            .monthly_expenses
+            .summarize(calculation.income.timeframes.monthly);
+        let after_tax_value =
+            after_tax_cash + package.traditional_401k + employer_match + benefits.total();
+
+        OfferEvaluation {
+            name: package.name.clone(),
+            total_compensation,
+            employer_match,
+            benefits,
+            calculation,
+            after_tax_cash,
+            after_tax_value,
+            budget,
+        }
+    }
+
+    /// Evaluate and rank packages by after-tax value in one batch
+    pub fn compare(&self, packages: &[CompensationPackage]) -> OfferComparison {
+        let calculations = self.engine.calculate_iter(
+            packages
+                .iter()
+                .map(CompensationPackage::to_calculation_input),
+        );
+        let mut ranked: Vec<_> = packages
+            .iter()
+            .zip(calculations)
+            .map(|(package, calculation)| Self::valuation(package, calculation))
+            .collect();
+        ranked.sort_by_key(|e| std::cmp::Reverse(e.after_tax_value));
+        OfferComparison { ranked }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::household::ExpenseCategory;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn package(name: &str, base_salary: Decimal, state: USState) -> CompensationPackage {
+        CompensationPackage {
+            name: name.to_string(),
+            base_salary,
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_match_formula() {
+        let formula = MatchFormula {
+            match_rate: dec!(0.5),
+            salary_cap: dec!(0.06),
+        };
+
+        // 50% of contributions up to 6% of $100,000
+        assert_eq!(
+            formula.employer_match(dec!(100000), dec!(10000)),
+            dec!(3000)
+        );
+        assert_eq!(formula.employer_match(dec!(100000), dec!(4000)), dec!(2000));
+    }
+
+    #[test]
+    fn test_evaluate_includes_equity_match_and_benefits() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let evaluator = OfferEvaluator::new(&engine);
+
+        let offer = CompensationPackage {
+            bonus: dec!(15000),
+            equity_vesting: dec!(25000),
+            traditional_401k: dec!(10000),
+            employer_match: MatchFormula {
+                match_rate: dec!(1),
+                salary_cap: dec!(0.04),
+            },
+            employer_benefits: dec!(12000),
+            employer_hsa_contribution: dec!(1000),
+            ..package("Startup", dec!(150000), USState::California)
+        };
+
+        let evaluation = evaluator.evaluate(&offer);
+
+        assert_eq!(evaluation.employer_match, dec!(6000));
+        assert_eq!(evaluation.total_compensation, dec!(209000));
+        assert_eq!(evaluation.calculation.income.gross, dec!(190000));
+        assert_eq!(
+            evaluation.after_tax_value,
+            evaluation.after_tax_cash + dec!(10000) + dec!(6000) + dec!(12000) + dec!(1000)
+        );
+    }
+
+    #[test]
+    fn test_compare_ranks_by_after_tax_value() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let evaluator = OfferEvaluator::new(&engine);
+
+        // Same salary: Texas beats California after tax
+        let comparison = evaluator.compare(&[
+            package("CA", dec!(150000), USState::California),
+            package("TX", dec!(150000), USState::Texas),
+        ]);
+
+        assert_eq!(comparison.best().unwrap().name, "TX");
+        assert_eq!(comparison.gap_to_best("TX"), Some(dec!(0)));
+        assert!(comparison.gap_to_best("CA").unwrap() > dec!(0));
+        assert_eq!(comparison.gap_to_best("NV"), None);
+    }
+
+    #[test]
+    fn test_bonus_percent() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let evaluator = OfferEvaluator::new(&engine);
+
+        let offer = CompensationPackage {
+            bonus: dec!(5000),
+            bonus_percent: dec!(0.15),
+            ..package("Bonus heavy", dec!(200000), USState::Texas)
+        };
+
+        assert_eq!(offer.total_bonus(), dec!(35000));
+        let evaluation = evaluator.evaluate(&offer);
+        assert_eq!(evaluation.calculation.income.gross, dec!(235000));
+        assert_eq!(evaluation.total_compensation, dec!(235000));
+    }
+
+    #[test]
+    fn test_cost_of_living_changes_ranking() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let evaluator = OfferEvaluator::new(&engine);
+
+        let comparison = evaluator.compare(&[
+            CompensationPackage {
+                monthly_expenses: MonthlyExpenses::new()
+                    .with(ExpenseCategory::Housing, dec!(4500))
+                    .with(ExpenseCategory::Transportation, dec!(300)),
+                ..package("San Francisco", dec!(170000), USState::California)
+            },
+            CompensationPackage {
+                monthly_expenses: MonthlyExpenses::new()
+                    .with(ExpenseCategory::Housing, dec!(1800))
+                    .with(ExpenseCategory::Transportation, dec!(600)),
+                ..package("Austin", dec!(150000), USState::Texas)
+            },
+        ]);
+
+        let austin = comparison
+            .ranked
+            .iter()
+            .find(|e| e.name == "Austin")
+            .unwrap();
+        assert_eq!(
+            austin.budget.discretionary_income,
+            austin.calculation.income.timeframes.monthly - dec!(2400)
+        );
+        assert_eq!(comparison.by_discretionary_income()[0].name, "Austin");
+    }
+
+    #[test]
+    fn test_benefits_can_outweigh_salary() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let evaluator = OfferEvaluator::new(&engine);
+
+        let comparison = evaluator.compare(&[
+            package("Higher salary", dec!(135000), USState::Texas),
+            CompensationPackage {
+                employer_benefits: dec!(15000),
+                ..package("Better benefits", dec!(130000), USState::Texas)
+            },
+        ]);
+
+        assert_eq!(comparison.best().unwrap().name, "Better benefits");
+    }
+
+    #[test]
+    fn test_itemized_benefits() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let evaluator = OfferEvaluator::new(&engine);
+
+        let great_benefits = CompensationPackage {
+            benefits: EmployerBenefits {
+                health_premiums: dec!(16000),
+                life_insurance: dec!(300),
+                disability_insurance: dec!(700),
+            },
+            employer_hsa_contribution: dec!(1500),
+            ..package("$130k, great benefits", dec!(130000), USState::Colorado)
+        };
+        let bad_benefits = CompensationPackage {
+            benefits: EmployerBenefits {
+                health_premiums: dec!(2500),
+                ..Default::default()
+            },
+            ..package("$145k, bad benefits", dec!(145000), USState::Colorado)
+        };
+
+        let evaluation = evaluator.evaluate(&great_benefits);
+        assert_eq!(evaluation.benefits.total(), dec!(18500));
+        assert_eq!(evaluation.benefits.hsa_contribution, dec!(1500));
+        assert_eq!(evaluation.total_compensation, dec!(148500));
+        // Premiums aren't wages
+        assert_eq!(evaluation.calculation.income.gross, dec!(130000));
+
+        // $15k more salary is worth less than $16k more in untaxed benefits
+        let comparison = evaluator.compare(&[bad_benefits, great_benefits]);
+        assert_eq!(comparison.best().unwrap().name, "$130k, great benefits");
+        assert!(comparison.gap_to_best("$145k, bad benefits").unwrap() > dec!(0));
+    }
+}
@@ -0,0 +1,545 @@
+//! Pay dates for a year and the paychecks that land on them
+//!
+//! A pay calendar starts from the first pay date of the year and steps by
+//! the pay frequency. Paydays that fall on a weekend or bank holiday move to
+//! the previous business day by default, the way most payroll providers
+//! fund direct deposits.
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::paystub::{paystub_over, Paystub};
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::income::PayFrequency;
+use crate::models::profile::UserProfile;
+use crate::models::ParseEnumError;
+
+/// Where a payday that isn't a business day moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DateShift {
+    /// Pay on the scheduled date regardless
+    None,
+    #[default]
+    PreviousBusinessDay,
+    NextBusinessDay,
+}
+
+impl DateShift {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateShift::None => "none",
+            DateShift::PreviousBusinessDay => "previous_business_day",
+            DateShift::NextBusinessDay => "next_business_day",
+        }
+    }
+}
+
+impl fmt::Display for DateShift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DateShift {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(DateShift::None),
+            "previous_business_day" => Ok(DateShift::PreviousBusinessDay),
+            "next_business_day" => Ok(DateShift::NextBusinessDay),
+            _ => Err(ParseEnumError::new("date shift", s)),
+        }
+    }
+}
+
+/// Federal Reserve bank holidays for a year
+///
+/// Holidays on a Sunday are observed the following Monday. The Federal
+/// Reserve doesn't close for holidays on a Saturday.
+pub fn bank_holidays(year: i32) -> Vec<NaiveDate> {
+    let date = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+    let nth = |month, weekday, n| NaiveDate::from_weekday_of_month_opt(year, month, weekday, n);
+    let last_monday_of_may = nth(5, Weekday::Mon, 5).or_else(|| nth(5, Weekday::Mon, 4));
+
+    [
+        date(1, 1),
+        nth(1, Weekday::Mon, 3),
+        nth(2, Weekday::Mon, 3),
+        last_monday_of_may,
+        date(6, 19),
+        date(7, 4),
+        nth(9, Weekday::Mon, 1),
+        nth(10, Weekday::Mon, 2),
+        date(11, 11),
+        nth(11, Weekday::Thu, 4),
+        date(12, 25),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|holiday| match holiday.weekday() {
+        Weekday::Sun => holiday + Days::new(1),
+        _ => holiday,
+    })
+    .collect()
+}
+
+/// A payday before and after shifting off weekends and holidays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayDate {
+    pub scheduled: NaiveDate,
+    /// Date the paycheck is actually paid
+    pub paid: NaiveDate,
+}
+
+/// Pay frequency, first pay date, and shifting rules for a year of paydays
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayCalendar {
+    pub frequency: PayFrequency,
+    /// First scheduled payday of the year; the calendar covers its year
+    pub first_pay_date: NaiveDate,
+    pub shift: DateShift,
+    /// Days that aren't business days besides weekends
+    pub holidays: Vec<NaiveDate>,
+}
+
+impl PayCalendar {
+    /// Calendar that moves paydays to the previous business day, skipping
+    /// bank holidays
+    pub fn new(frequency: PayFrequency, first_pay_date: NaiveDate) -> Self {
+        let year = first_pay_date.year();
+        let mut holidays = bank_holidays(year);
+        // A late-December payday can shift into January
+        holidays.extend(bank_holidays(year + 1));
+        Self {
+            frequency,
+            first_pay_date,
+            shift: DateShift::default(),
+            holidays,
+        }
+    }
+
+    pub fn with_shift(mut self, shift: DateShift) -> Self {
+        self.shift = shift;
+        self
+    }
+
+    /// Replace the bank holidays with an employer's own list
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDate>) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    pub fn year(&self) -> i32 {
+        self.first_pay_date.year()
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.holidays.contains(&date)
+    }
+
+    /// Paydays scheduled from the first pay date through the end of its year
+    ///
+    /// Daily pay runs every business day, so it never shifts.
+    pub fn pay_dates(&self) -> Vec<PayDate> {
+        let year = self.year();
+        let scheduled: Vec<NaiveDate> = match self.frequency {
+            PayFrequency::Daily => self
+                .first_pay_date
+                .iter_days()
+                .take_while(|date| date.year() == year)
+                .filter(|&date| self.is_business_day(date))
+                .collect(),
+            PayFrequency::Weekly => self.every_days(7),
+            PayFrequency::BiWeekly | PayFrequency::BiWeekly27 => self.every_days(14),
+            PayFrequency::SemiMonthly => self.semi_monthly(),
+            PayFrequency::Monthly => self.every_months(1),
+            PayFrequency::Quarterly => self.every_months(3),
+            PayFrequency::Annually => vec![self.first_pay_date],
+        };
+
+        scheduled
+            .into_iter()
+            .map(|scheduled| PayDate {
+                scheduled,
+                paid: self.shifted(scheduled),
+            })
+            .collect()
+    }
+
+    fn shifted(&self, date: NaiveDate) -> NaiveDate {
+        if self.frequency == PayFrequency::Daily {
+            return date;
+        }
+        let step = |date: NaiveDate| match self.shift {
+            DateShift::None => None,
+            DateShift::PreviousBusinessDay => date.pred_opt(),
+            DateShift::NextBusinessDay => date.succ_opt(),
+        };
+        let mut paid = date;
+        while !self.is_business_day(paid) {
+            match step(paid) {
+                Some(next) => paid = next,
+                None => break,
+            }
+        }
+        paid
+    }
+
+    fn every_days(&self, days: u64) -> Vec<NaiveDate> {
+        let year = self.year();
+        std::iter::successors(Some(self.first_pay_date), |date| {
+            date.checked_add_days(Days::new(days))
+        })
+        .take_while(|date| date.year() == year)
+        .collect()
+    }
+
+    /// Same day each period, or the month's last day when it's shorter or
+    /// the first pay date is a month end
+    fn every_months(&self, months: u32) -> Vec<NaiveDate> {
+        let year = self.year();
+        let month_end = is_month_end(self.first_pay_date);
+        (0..)
+            .map_while(|period| {
+                let date = self
+                    .first_pay_date
+                    .checked_add_months(Months::new(months * period))?;
+                Some(if month_end {
+                    last_day_of_month(date)
+                } else {
+                    date
+                })
+            })
+            .take_while(|date| date.year() == year)
+            .collect()
+    }
+
+    /// Two paydays a month: the first pay date's day and 15 days from it,
+    /// with month-end paydays pairing with the 15th
+    fn semi_monthly(&self) -> Vec<NaiveDate> {
+        let first = self.first_pay_date;
+        let day = first.day();
+        // Days of the month for the early and late paydays; 31 stands for
+        // the month's last day
+        let (early, late) = if day == 15 || is_month_end(first) {
+            (15, 31)
+        } else if day < 15 {
+            (day, day + 15)
+        } else {
+            (day - 15, day)
+        };
+
+        let year = self.year();
+        (first.month()..=12)
+            .flat_map(|month| [(month, early), (month, late)])
+            .filter_map(|(month, day)| {
+                let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+                Some(
+                    start
+                        .with_day(day)
+                        .unwrap_or_else(|| last_day_of_month(start)),
+                )
+            })
+            .filter(|date| *date >= first)
+            .collect()
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    let start = date.with_day(1).unwrap_or(date);
+    start
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date)
+}
+
+fn is_month_end(date: NaiveDate) -> bool {
+    last_day_of_month(date) == date
+}
+
+/// One paycheck on its pay date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paycheck {
+    pub date: PayDate,
+    pub paystub: Paystub,
+}
+
+/// A year of paychecks on calendar dates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaySchedule {
+    pub frequency: PayFrequency,
+    pub paychecks: Vec<Paycheck>,
+}
+
+impl PaySchedule {
+    /// Paychecks paid in a month (1-12)
+    pub fn paychecks_in_month(&self, month: u32) -> impl Iterator<Item = &Paycheck> {
+        self.paychecks
+            .iter()
+            .filter(move |paycheck| paycheck.date.paid.month() == month)
+    }
+
+    /// Months with more paychecks than usual: three for biweekly pay, five
+    /// for weekly. Other frequencies pay the same count every month.
+    pub fn extra_paycheck_months(&self) -> Vec<u32> {
+        let usual = match self.frequency {
+            PayFrequency::Weekly => 4,
+            PayFrequency::BiWeekly | PayFrequency::BiWeekly27 => 2,
+            _ => return Vec::new(),
+        };
+        (1..=12)
+            .filter(|&month| self.paychecks_in_month(month).count() > usual)
+            .collect()
+    }
+
+    /// Totals of every line through the first `paychecks` paychecks
+    pub fn year_to_date(&self, paychecks: usize) -> Paystub {
+        self.paychecks.iter().take(paychecks).fold(
+            Paystub {
+                pay_frequency: self.frequency,
+                ..Default::default()
+            },
+            |total, paycheck| {
+                let stub = &paycheck.paystub;
+                Paystub {
+                    gross: total.gross + stub.gross,
+                    pre_tax_deductions: total.pre_tax_deductions + stub.pre_tax_deductions,
+                    federal_withholding: total.federal_withholding + stub.federal_withholding,
+                    social_security: total.social_security + stub.social_security,
+                    medicare: total.medicare + stub.medicare,
+                    state_withholding: total.state_withholding + stub.state_withholding,
+                    local_withholding: total.local_withholding + stub.local_withholding,
+                    state_payroll_programs: total.state_payroll_programs
+                        + stub.state_payroll_programs,
+                    net: total.net + stub.net,
+                    ..total
+                }
+            },
+        )
+    }
+
+    /// Net pay received in each month, January first
+    pub fn net_by_month(&self) -> Vec<Decimal> {
+        (1..=12)
+            .map(|month| {
+                self.paychecks_in_month(month)
+                    .map(|paycheck| paycheck.paystub.net)
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+/// Places the engine's per-paycheck amounts on a pay calendar
+pub struct PayCalendarProjector<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> PayCalendarProjector<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// The year's paychecks, splitting annual amounts evenly over the pay
+    /// dates the calendar actually has (27 in some biweekly years)
+    pub fn project(&self, input: &TaxCalculationInput, calendar: &PayCalendar) -> PaySchedule {
+        let dates = calendar.pay_dates();
+        let result = self.engine.calculate(input);
+        let paystub = paystub_over(input, &result, calendar.frequency, dates.len() as u32);
+
+        PaySchedule {
+            frequency: calendar.frequency,
+            paychecks: dates
+                .into_iter()
+                .map(|date| Paycheck {
+                    date,
+                    paystub: paystub.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The year's paychecks for a profile, with payroll deductions taken
+    /// only in the pay periods they're active
+    ///
+    /// Taxes stay spread evenly; a deduction that starts or stops mid-year
+    /// moves each paycheck's deductions and net pay by the difference from
+    /// its average.
+    pub fn project_profile(&self, profile: &UserProfile, calendar: &PayCalendar) -> PaySchedule {
+        let mut schedule = self.project(&TaxCalculationInput::from(profile), calendar);
+        let periods = Decimal::from(schedule.paychecks.len().max(1));
+
+        let average = |pre_tax: bool| {
+            profile
+                .deductions
+                .iter()
+                .filter(|deduction| deduction.is_pre_tax == pre_tax)
+                .map(|deduction| deduction.annual_amount())
+                .sum::<Decimal>()
+                / periods
+        };
+        let (average_pre_tax, average_post_tax) = (average(true), average(false));
+
+        for (paycheck, period) in schedule.paychecks.iter_mut().zip(1..) {
+            let (mut pre_tax, mut post_tax) = (Decimal::ZERO, Decimal::ZERO);
+            for deduction in &profile.deductions {
+                let amount = deduction.amount_for_period(period);
+                if deduction.is_pre_tax {
+                    pre_tax += amount;
+                } else {
+                    post_tax += amount;
+                }
+            }
+            let pre_tax_change = pre_tax - average_pre_tax;
+            let post_tax_change = post_tax - average_post_tax;
+            let stub = &mut paycheck.paystub;
+            stub.pre_tax_deductions = (stub.pre_tax_deductions + pre_tax_change).round_dp(2);
+            stub.net = (stub.net - pre_tax_change - post_tax_change).round_dp(2);
+        }
+
+        schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_bank_holidays() {
+        let holidays = bank_holidays(2023);
+        assert_eq!(holidays.len(), 11);
+        // New Year's Day 2023 was a Sunday
+        assert!(holidays.contains(&date(2023, 1, 2)));
+        assert!(holidays.contains(&date(2023, 5, 29)));
+        assert!(holidays.contains(&date(2023, 11, 23)));
+        // Saturday holidays aren't moved
+        assert!(bank_holidays(2026).contains(&date(2026, 7, 4)));
+    }
+
+    #[test]
+    fn test_biweekly_dates_and_three_paycheck_months() {
+        let calendar = PayCalendar::new(PayFrequency::BiWeekly, date(2024, 1, 5));
+        let dates = calendar.pay_dates();
+        assert_eq!(dates.len(), 26);
+        assert_eq!(dates[25].scheduled, date(2024, 12, 20));
+
+        // 2021 had 27 Friday paydays starting January 1, a holiday
+        let calendar = PayCalendar::new(PayFrequency::BiWeekly, date(2021, 1, 1));
+        let dates = calendar.pay_dates();
+        assert_eq!(dates.len(), 27);
+        assert_eq!(dates[0].paid, date(2020, 12, 31));
+
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(78000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let calendar = PayCalendar::new(PayFrequency::BiWeekly, date(2024, 1, 5));
+        let schedule = PayCalendarProjector::new(&engine).project(&input, &calendar);
+        assert_eq!(schedule.paychecks[0].paystub.gross, dec!(3000));
+        assert_eq!(schedule.extra_paycheck_months(), vec![3, 8]);
+        let net = schedule.net_by_month();
+        assert_eq!(net[2], net[0] * dec!(1.5));
+    }
+
+    #[test]
+    fn test_mid_year_deductions_on_paychecks() {
+        use crate::models::deduction::{Deduction, DeductionFrequency, DeductionType};
+        use crate::models::income::IncomeInput;
+        use crate::models::tax::FilingStatus;
+
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let mut profile = UserProfile::new(
+            IncomeInput::new(dec!(78000)),
+            FilingStatus::Single,
+            USState::Texas,
+        );
+        // FSA election starts with July's first paycheck, the 14th
+        profile.deductions = vec![Deduction::new(
+            DeductionType::Fsa,
+            dec!(1300),
+            DeductionFrequency::Annual,
+            26,
+        )
+        .starting(14)];
+
+        let calendar = PayCalendar::new(PayFrequency::BiWeekly, date(2024, 1, 5));
+        let schedule = PayCalendarProjector::new(&engine).project_profile(&profile, &calendar);
+        let january = &schedule.paychecks[0].paystub;
+        let july = &schedule.paychecks[13].paystub;
+        assert_eq!(january.pre_tax_deductions, dec!(0));
+        assert_eq!(july.pre_tax_deductions, dec!(100));
+        assert_eq!(january.net - july.net, dec!(100));
+
+        let ytd = schedule.year_to_date(26);
+        assert_eq!(ytd.pre_tax_deductions, dec!(1300));
+        assert_eq!(ytd.gross, dec!(78000));
+        assert_eq!(schedule.year_to_date(13).pre_tax_deductions, dec!(0));
+    }
+
+    #[test]
+    fn test_weekend_and_holiday_shifting() {
+        // July 4, 2025 is a Friday holiday
+        let calendar = PayCalendar::new(PayFrequency::BiWeekly, date(2025, 1, 3));
+        let july = calendar
+            .pay_dates()
+            .into_iter()
+            .find(|d| d.scheduled == date(2025, 7, 4))
+            .unwrap();
+        assert_eq!(july.paid, date(2025, 7, 3));
+
+        let later = calendar.clone().with_shift(DateShift::NextBusinessDay);
+        let july = later.pay_dates()[13];
+        assert_eq!(july.paid, date(2025, 7, 7));
+
+        let unshifted = calendar
+            .with_shift(DateShift::None)
+            .with_holidays(Vec::new());
+        assert!(unshifted.pay_dates().iter().all(|d| d.paid == d.scheduled));
+    }
+
+    #[test]
+    fn test_monthly_and_semi_monthly() {
+        // Month-end paydays stay at month end; June 30, 2024 is a Sunday
+        let monthly = PayCalendar::new(PayFrequency::Monthly, date(2024, 1, 31)).pay_dates();
+        assert_eq!(monthly.len(), 12);
+        assert_eq!(monthly[1].scheduled, date(2024, 2, 29));
+        assert_eq!(monthly[5].paid, date(2024, 6, 28));
+
+        let semi = PayCalendar::new(PayFrequency::SemiMonthly, date(2024, 1, 15)).pay_dates();
+        assert_eq!(semi.len(), 24);
+        assert_eq!(semi[3].scheduled, date(2024, 2, 29));
+
+        let first_and_sixteenth =
+            PayCalendar::new(PayFrequency::SemiMonthly, date(2024, 3, 1)).pay_dates();
+        assert_eq!(first_and_sixteenth.len(), 20);
+        assert_eq!(first_and_sixteenth[1].scheduled, date(2024, 3, 16));
+        assert_eq!(first_and_sixteenth[1].paid, date(2024, 3, 15));
+
+        let quarterly = PayCalendar::new(PayFrequency::Quarterly, date(2024, 3, 15)).pay_dates();
+        assert_eq!(quarterly.len(), 4);
+        assert_eq!(
+            "next_business_day".parse::<DateShift>().unwrap(),
+            DateShift::NextBusinessDay
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! School-year and seasonal salaries paid over fewer months or spread over 12
+//!
+//! Teachers and other seasonal employees often choose between being paid
+//! only in the months they work and having the same salary spread over the
+//! whole year. The year's tax is the same either way, but withholding isn't:
+//! the percentage method annualizes each paycheck as if it came every pay
+//! period of the year, so the larger work-month paychecks are withheld as if
+//! the salary were higher and the difference comes back as a refund.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::withholding::FederalWithholdingCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+use crate::models::income::PayFrequency;
+use crate::models::profile::W4Settings;
+
+const MONTHS_PER_YEAR: u32 = 12;
+
+/// Paychecks under one pay election
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaySpreadOption {
+    pub paychecks: u32,
+    pub gross_per_paycheck: Decimal,
+    pub pre_tax_per_paycheck: Decimal,
+    pub federal_withholding_per_paycheck: Decimal,
+    pub fica_per_paycheck: Decimal,
+    /// The year's state and local tax spread evenly over the paychecks
+    pub state_per_paycheck: Decimal,
+    pub post_tax_per_paycheck: Decimal,
+    pub net_per_paycheck: Decimal,
+    /// Federal income tax withheld over the year
+    pub federal_withheld: Decimal,
+    /// Withholding less the year's federal tax after refundable credits;
+    /// positive is a refund, negative a balance due
+    pub federal_refund: Decimal,
+}
+
+/// Paid only in the work months compared with spread over 12
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaySpreadComparison {
+    pub pay_frequency: PayFrequency,
+    pub work_months: u32,
+    pub work_months_only: PaySpreadOption,
+    pub spread_over_twelve: PaySpreadOption,
+    /// How much more each work-month paycheck nets
+    pub per_paycheck_difference: Decimal,
+    /// Extra withholding the work-month election refunds at filing
+    pub extra_withholding: Decimal,
+    /// Net pay to set aside from each work-month paycheck to match the
+    /// spread election's pay in the months without one
+    pub set_aside_per_paycheck: Decimal,
+}
+
+/// Compares pay elections for salaries earned over part of the year
+pub struct PaySpreadAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> PaySpreadAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// The salary in `input` earned over `work_months` and paid on
+    /// `pay_frequency`, either in those months only or over the whole year
+    pub fn compare(
+        &self,
+        input: &TaxCalculationInput,
+        work_months: u32,
+        pay_frequency: PayFrequency,
+    ) -> PaySpreadComparison {
+        let work_months = work_months.clamp(1, MONTHS_PER_YEAR);
+        // Pay periods falling in the work months, to the nearest paycheck
+        let work_month_paychecks = ((pay_frequency.periods_per_year() * work_months
+            + MONTHS_PER_YEAR / 2)
+            / MONTHS_PER_YEAR)
+            .max(1);
+
+        // The year's tax doesn't depend on the election, only withholding does
+        let result = self.engine.calculate(input);
+        let work_months_only = self.option(input, &result, pay_frequency, work_month_paychecks);
+        let spread_over_twelve = self.option(
+            input,
+            &result,
+            pay_frequency,
+            pay_frequency.periods_per_year(),
+        );
+
+        let unpaid_paychecks = spread_over_twelve.paychecks - work_months_only.paychecks;
+        let set_aside = spread_over_twelve.net_per_paycheck * Decimal::from(unpaid_paychecks)
+            / Decimal::from(work_months_only.paychecks);
+
+        PaySpreadComparison {
+            pay_frequency,
+            work_months,
+            per_paycheck_difference: work_months_only.net_per_paycheck
+                - spread_over_twelve.net_per_paycheck,
+            extra_withholding: work_months_only.federal_withheld
+                - spread_over_twelve.federal_withheld,
+            set_aside_per_paycheck: set_aside.round_dp(2),
+            work_months_only,
+            spread_over_twelve,
+        }
+    }
+
+    fn option(
+        &self,
+        input: &TaxCalculationInput,
+        result: &TaxCalculationResult,
+        pay_frequency: PayFrequency,
+        paychecks: u32,
+    ) -> PaySpreadOption {
+        let breakdown = &result.tax_breakdown;
+        let count = Decimal::from(paychecks);
+        let per_paycheck = |annual: Decimal| (annual / count).round_dp(2);
+
+        let pre_tax = input.pre_tax_deductions
+            + input.traditional_401k
+            + input.hsa_contribution
+            + input.health_fsa
+            + input.dependent_care_fsa;
+        let gross_per_paycheck = per_paycheck(input.gross_income);
+        let pre_tax_per_paycheck = per_paycheck(pre_tax);
+        let federal_withholding_per_paycheck =
+            FederalWithholdingCalculator::new(self.engine.data_provider()).per_paycheck(
+                gross_per_paycheck - pre_tax_per_paycheck,
+                pay_frequency,
+                &W4Settings::default(),
+                input.filing_status,
+                self.engine.year(),
+            );
+        let state_total = breakdown.state.total_tax
+            + breakdown
+                .work_state
+                .as_ref()
+                .map(|w| w.total_tax)
+                .unwrap_or(Decimal::ZERO);
+        let fica_per_paycheck = per_paycheck(breakdown.fica.total);
+        let state_per_paycheck = per_paycheck(state_total);
+        let post_tax_per_paycheck = per_paycheck(input.post_tax_deductions + input.roth_401k);
+
+        let federal_withheld = federal_withholding_per_paycheck * count;
+        PaySpreadOption {
+            paychecks,
+            gross_per_paycheck,
+            pre_tax_per_paycheck,
+            federal_withholding_per_paycheck,
+            fica_per_paycheck,
+            state_per_paycheck,
+            post_tax_per_paycheck,
+            net_per_paycheck: gross_per_paycheck
+                - pre_tax_per_paycheck
+                - federal_withholding_per_paycheck
+                - fica_per_paycheck
+                - state_per_paycheck
+                - post_tax_per_paycheck,
+            federal_withheld,
+            federal_refund: federal_withheld
+                - (breakdown.federal.tax - breakdown.credits.refundable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_ten_month_salary() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(60000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let comparison = PaySpreadAnalyzer::new(&engine).compare(&input, 10, PayFrequency::Monthly);
+        let ten = &comparison.work_months_only;
+        let twelve = &comparison.spread_over_twelve;
+
+        assert_eq!(ten.paychecks, 10);
+        assert_eq!(ten.gross_per_paycheck, dec!(6000));
+        assert_eq!(twelve.paychecks, 12);
+        assert_eq!(twelve.gross_per_paycheck, dec!(5000));
+
+        // Spread over 12, withholding matches the year's $5,216 of tax;
+        // $6,000 paychecks are withheld as if the salary were $72,000
+        assert_eq!(twelve.federal_withheld, dec!(5216.04));
+        assert_eq!(ten.federal_withholding_per_paycheck, dec!(640.08));
+        assert_eq!(comparison.extra_withholding, dec!(1184.76));
+        assert_eq!(ten.federal_refund, dec!(1184.80));
+
+        // Each work-month paycheck nets more but has to carry two more months
+        assert!(comparison.per_paycheck_difference > Decimal::ZERO);
+        assert_eq!(
+            comparison.set_aside_per_paycheck,
+            (twelve.net_per_paycheck * dec!(2) / dec!(10)).round_dp(2)
+        );
+
+        // Bi-weekly pay over ten months is 22 paychecks
+        let biweekly = PaySpreadAnalyzer::new(&engine).compare(&input, 10, PayFrequency::BiWeekly);
+        assert_eq!(biweekly.work_months_only.paychecks, 22);
+    }
+}
@@ -0,0 +1,541 @@
+//! Checking an actual paystub against the expected calculation
+//!
+//! Expected lines are the year's calculated amounts spread evenly over the
+//! pay periods, except federal income tax, which follows the IRS
+//! percentage-method tables for the employee's W-4 the way the employer's
+//! payroll does. Only lines off by more than the tolerance are reported.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::calculators::withholding::FederalWithholdingCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+use crate::models::income::PayFrequency;
+use crate::models::profile::W4Settings;
+use crate::models::tax::FilingStatus;
+
+/// Filing statuses a W-4 can select (Step 1(c))
+const W4_FILING_STATUSES: [FilingStatus; 3] = [
+    FilingStatus::Single,
+    FilingStatus::MarriedFilingJointly,
+    FilingStatus::HeadOfHousehold,
+];
+
+/// Amounts printed on one paystub, for a single pay period
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Paystub {
+    pub pay_frequency: PayFrequency,
+    pub gross: Decimal,
+    /// 401(k), HSA, FSA, and insurance premiums taken before tax
+    pub pre_tax_deductions: Decimal,
+    pub federal_withholding: Decimal,
+    pub social_security: Decimal,
+    pub medicare: Decimal,
+    pub state_withholding: Decimal,
+    pub local_withholding: Decimal,
+    /// SDI, family leave, and other state payroll programs
+    pub state_payroll_programs: Decimal,
+    pub net: Decimal,
+}
+
+/// A line on a paystub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaystubLine {
+    Gross,
+    PreTaxDeductions,
+    FederalWithholding,
+    SocialSecurity,
+    Medicare,
+    StateWithholding,
+    LocalWithholding,
+    StatePayrollPrograms,
+    Net,
+}
+
+impl PaystubLine {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PaystubLine::Gross => "Gross pay",
+            PaystubLine::PreTaxDeductions => "Pre-tax deductions",
+            PaystubLine::FederalWithholding => "Federal income tax",
+            PaystubLine::SocialSecurity => "Social Security",
+            PaystubLine::Medicare => "Medicare",
+            PaystubLine::StateWithholding => "State income tax",
+            PaystubLine::LocalWithholding => "Local income tax",
+            PaystubLine::StatePayrollPrograms => "State payroll programs",
+            PaystubLine::Net => "Net pay",
+        }
+    }
+
+    fn amount(&self, paystub: &Paystub) -> Decimal {
+        match self {
+            PaystubLine::Gross => paystub.gross,
+            PaystubLine::PreTaxDeductions => paystub.pre_tax_deductions,
+            PaystubLine::FederalWithholding => paystub.federal_withholding,
+            PaystubLine::SocialSecurity => paystub.social_security,
+            PaystubLine::Medicare => paystub.medicare,
+            PaystubLine::StateWithholding => paystub.state_withholding,
+            PaystubLine::LocalWithholding => paystub.local_withholding,
+            PaystubLine::StatePayrollPrograms => paystub.state_payroll_programs,
+            PaystubLine::Net => paystub.net,
+        }
+    }
+
+    pub fn all() -> &'static [PaystubLine] {
+        &[
+            PaystubLine::Gross,
+            PaystubLine::PreTaxDeductions,
+            PaystubLine::FederalWithholding,
+            PaystubLine::SocialSecurity,
+            PaystubLine::Medicare,
+            PaystubLine::StateWithholding,
+            PaystubLine::LocalWithholding,
+            PaystubLine::StatePayrollPrograms,
+            PaystubLine::Net,
+        ]
+    }
+}
+
+impl fmt::Display for PaystubLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
+/// Likely reason a line differs from the calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DiscrepancyCause {
+    /// Federal withholding matches this W-4 filing status instead
+    W4FilingStatus(FilingStatus),
+    /// The paystub takes pre-tax deductions, annualized here, that the
+    /// calculation doesn't include
+    MissedPreTaxDeduction { annual: Decimal },
+    /// Withholding is higher than any filing status explains: W-4 extra
+    /// withholding (Step 4(c)) or the multiple-jobs box (Step 2)
+    ExtraWithholding,
+    /// Social Security stopped because wages passed the wage base
+    WageBaseReached,
+}
+
+impl DiscrepancyCause {
+    pub fn explanation(&self) -> String {
+        match self {
+            DiscrepancyCause::W4FilingStatus(status) => format!(
+                "Withholding matches a W-4 filed as {}; check the filing status on your W-4",
+                status.display_name()
+            ),
+            DiscrepancyCause::MissedPreTaxDeduction { annual } => format!(
+                "Your paystub takes about ${} a year in pre-tax deductions \
+                 that the calculation doesn't include",
+                annual.round_dp(0)
+            ),
+            DiscrepancyCause::ExtraWithholding => "Your W-4 may request extra withholding \
+                (Step 4(c)) or have the multiple-jobs box checked (Step 2)"
+                .to_string(),
+            DiscrepancyCause::WageBaseReached => "Year-to-date wages passed the Social \
+                Security wage base, so Social Security is no longer withheld"
+                .to_string(),
+        }
+    }
+}
+
+/// A paystub line off from the expected amount by more than the tolerance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaystubDiscrepancy {
+    pub line: PaystubLine,
+    pub actual: Decimal,
+    pub expected: Decimal,
+    /// Actual minus expected
+    pub difference: Decimal,
+    pub cause: Option<DiscrepancyCause>,
+}
+
+/// Result of checking a paystub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaystubVerification {
+    /// Per-period amounts the calculation expects
+    pub expected: Paystub,
+    pub tolerance: Decimal,
+    pub discrepancies: Vec<PaystubDiscrepancy>,
+}
+
+impl PaystubVerification {
+    /// Whether every line is within tolerance
+    pub fn is_correct(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    pub fn discrepancy(&self, line: PaystubLine) -> Option<&PaystubDiscrepancy> {
+        self.discrepancies.iter().find(|d| d.line == line)
+    }
+}
+
+/// Compares paystubs against the engine's calculation
+pub struct PaystubVerifier<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+    w4: W4Settings,
+}
+
+impl<'a> PaystubVerifier<'a> {
+    /// Verifier for a W-4 with default settings, filed with the return's
+    /// filing status
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self {
+            engine,
+            w4: W4Settings::default(),
+        }
+    }
+
+    /// Expect federal withholding under the employee's actual W-4
+    pub fn with_w4(mut self, w4: W4Settings) -> Self {
+        self.w4 = w4;
+        self
+    }
+
+    /// Per-period amounts for an input
+    pub fn expected_paystub(
+        &self,
+        input: &TaxCalculationInput,
+        pay_frequency: PayFrequency,
+    ) -> Paystub {
+        let result = self.engine.calculate(input);
+        let mut paystub = paystub_for(input, &result, pay_frequency);
+        let withholding = self.federal_withholding(&paystub, input, &self.w4);
+        paystub.net += paystub.federal_withholding - withholding;
+        paystub.federal_withholding = withholding;
+        paystub
+    }
+
+    /// Federal income tax the percentage-method tables withhold from a
+    /// paycheck under `w4`
+    fn federal_withholding(
+        &self,
+        paystub: &Paystub,
+        input: &TaxCalculationInput,
+        w4: &W4Settings,
+    ) -> Decimal {
+        FederalWithholdingCalculator::new(self.engine.data_provider()).per_paycheck(
+            paystub.gross - paystub.pre_tax_deductions,
+            paystub.pay_frequency,
+            w4,
+            input.filing_status,
+            self.engine.year(),
+        )
+    }
+
+    /// Report lines of `paystub` more than `tolerance` from the calculation
+    /// for `input`, with likely explanations
+    pub fn verify(
+        &self,
+        paystub: &Paystub,
+        input: &TaxCalculationInput,
+        tolerance: Decimal,
+    ) -> PaystubVerification {
+        let frequency = paystub.pay_frequency;
+        let expected = self.expected_paystub(input, frequency);
+        let off = |line: PaystubLine, expected: &Paystub| {
+            (line.amount(paystub) - line.amount(expected)).abs() > tolerance
+        };
+
+        // Pre-tax deductions on the stub that the input is missing
+        let missed_pre_tax = paystub.pre_tax_deductions - expected.pre_tax_deductions;
+        let missed_annual = (missed_pre_tax > tolerance)
+            .then(|| missed_pre_tax * Decimal::from(frequency.periods_per_year()));
+        let missed_pre_tax_cause =
+            missed_annual.map(|annual| DiscrepancyCause::MissedPreTaxDeduction { annual });
+        let with_missed_pre_tax = missed_annual.map(|annual| {
+            let adjusted = TaxCalculationInput {
+                pre_tax_deductions: input.pre_tax_deductions + annual,
+                ..input.clone()
+            };
+            self.expected_paystub(&adjusted, frequency)
+        });
+        let explained_by_pre_tax = |line: PaystubLine| {
+            with_missed_pre_tax
+                .as_ref()
+                .is_some_and(|adjusted| !off(line, adjusted))
+        };
+
+        let wage_base = self
+            .engine
+            .data_provider()
+            .fica_config(self.engine.year())
+            .wage_base;
+        let discrepancies = PaystubLine::all()
+            .iter()
+            .filter(|&&line| off(line, &expected))
+            .map(|&line| {
+                let cause = match line {
+                    PaystubLine::PreTaxDeductions => missed_pre_tax_cause,
+                    _ if explained_by_pre_tax(line) => missed_pre_tax_cause,
+                    PaystubLine::FederalWithholding => {
+                        self.federal_cause(paystub, input, tolerance)
+                    },
+                    PaystubLine::SocialSecurity
+                        if paystub.social_security.is_zero() && input.gross_income > wage_base =>
+                    {
+                        Some(DiscrepancyCause::WageBaseReached)
+                    },
+                    _ => None,
+                };
+                PaystubDiscrepancy {
+                    line,
+                    actual: line.amount(paystub),
+                    expected: line.amount(&expected),
+                    difference: line.amount(paystub) - line.amount(&expected),
+                    cause,
+                }
+            })
+            .collect();
+
+        PaystubVerification {
+            expected,
+            tolerance,
+            discrepancies,
+        }
+    }
+
+    /// Another W-4 filing status that explains the federal withholding, or
+    /// extra withholding when it's higher than any status explains
+    fn federal_cause(
+        &self,
+        paystub: &Paystub,
+        input: &TaxCalculationInput,
+        tolerance: Decimal,
+    ) -> Option<DiscrepancyCause> {
+        // The W-4 status changes withholding but not wages
+        let expected = self.expected_paystub(input, paystub.pay_frequency);
+        let filed_status = self.w4.filing_status.unwrap_or(input.filing_status);
+        let withholding_as = |status: FilingStatus| {
+            let w4 = W4Settings {
+                filing_status: Some(status),
+                ..self.w4.clone()
+            };
+            self.federal_withholding(&expected, input, &w4)
+        };
+
+        let matching_status = W4_FILING_STATUSES
+            .into_iter()
+            .filter(|&status| status != filed_status)
+            .find(|&status| {
+                (paystub.federal_withholding - withholding_as(status)).abs() <= tolerance
+            });
+        if let Some(status) = matching_status {
+            return Some(DiscrepancyCause::W4FilingStatus(status));
+        }
+
+        let highest = W4_FILING_STATUSES
+            .into_iter()
+            .map(withholding_as)
+            .max()
+            .unwrap_or_default();
+        (paystub.federal_withholding > highest + tolerance)
+            .then_some(DiscrepancyCause::ExtraWithholding)
+    }
+}
+
+fn paystub_for(
+    input: &TaxCalculationInput,
+    result: &TaxCalculationResult,
+    pay_frequency: PayFrequency,
+) -> Paystub {
+    paystub_over(
+        input,
+        result,
+        pay_frequency,
+        pay_frequency.periods_per_year(),
+    )
+}
+
+/// Per-paycheck amounts when the year's amounts are split over `periods`
+/// paychecks
+pub(crate) fn paystub_over(
+    input: &TaxCalculationInput,
+    result: &TaxCalculationResult,
+    pay_frequency: PayFrequency,
+    periods: u32,
+) -> Paystub {
+    let breakdown = &result.tax_breakdown;
+    let states = std::iter::once(&breakdown.state).chain(breakdown.work_state.as_ref());
+    let (mut state_income_tax, mut local_tax, mut payroll_programs) =
+        (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO);
+    for state in states {
+        state_income_tax += state.income_tax;
+        local_tax += state.local_tax + state.school_district_tax;
+        payroll_programs += state.sdi;
+    }
+    let pre_tax = input.pre_tax_deductions
+        + input.traditional_401k
+        + input.hsa_contribution
+        + input.health_fsa
+        + input.dependent_care_fsa;
+
+    let periods = Decimal::from(periods.max(1));
+    let period = |annual: Decimal| (annual / periods).round_dp(2);
+    Paystub {
+        pay_frequency,
+        gross: period(result.income.gross),
+        pre_tax_deductions: period(pre_tax),
+        federal_withholding: period(breakdown.federal.tax),
+        social_security: period(breakdown.fica.social_security),
+        medicare: period(breakdown.fica.medicare + breakdown.fica.additional_medicare),
+        state_withholding: period(state_income_tax),
+        local_withholding: period(local_tax),
+        state_payroll_programs: period(payroll_programs),
+        // Refundable credits come back with the return, not in the paycheck
+        net: period(result.income.net - breakdown.credits.refundable),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn input(filing_status: FilingStatus) -> TaxCalculationInput {
+        TaxCalculationInput {
+            gross_income: dec!(104000),
+            filing_status,
+            state: USState::California,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matching_paystub_has_no_discrepancies() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let verifier = PaystubVerifier::new(&engine);
+
+        let input = input(FilingStatus::Single);
+        let paystub = verifier.expected_paystub(&input, PayFrequency::BiWeekly);
+        assert_eq!(paystub.gross, dec!(4000));
+
+        let verification = verifier.verify(&paystub, &input, dec!(5));
+        assert!(verification.is_correct());
+    }
+
+    #[test]
+    fn test_federal_withholding_follows_w4_tables() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let withholding = FederalWithholdingCalculator::new(&data);
+        let input = input(FilingStatus::Single);
+
+        let paystub =
+            PaystubVerifier::new(&engine).expected_paystub(&input, PayFrequency::BiWeekly);
+        let tables = withholding.per_paycheck(
+            dec!(4000),
+            PayFrequency::BiWeekly,
+            &W4Settings::default(),
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(paystub.federal_withholding, tables);
+
+        // Step 3 credits and Step 4(c) extra withholding come off and onto
+        // each paycheck
+        let w4 = W4Settings {
+            dependents_credit: dec!(2000),
+            extra_withholding: dec!(50),
+            ..Default::default()
+        };
+        let with_w4 = PaystubVerifier::new(&engine)
+            .with_w4(w4.clone())
+            .expected_paystub(&input, PayFrequency::BiWeekly);
+        assert_eq!(
+            with_w4.federal_withholding,
+            withholding.per_paycheck(
+                dec!(4000),
+                PayFrequency::BiWeekly,
+                &w4,
+                FilingStatus::Single,
+                2024
+            )
+        );
+        assert_eq!(
+            with_w4.net - paystub.net,
+            paystub.federal_withholding - with_w4.federal_withholding
+        );
+    }
+
+    #[test]
+    fn test_explains_w4_filing_status() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let verifier = PaystubVerifier::new(&engine);
+
+        // Employer withholds from the married table while the return will be
+        // single
+        let mut paystub =
+            verifier.expected_paystub(&input(FilingStatus::Single), PayFrequency::BiWeekly);
+        paystub.federal_withholding = FederalWithholdingCalculator::new(&data).per_paycheck(
+            dec!(4000),
+            PayFrequency::BiWeekly,
+            &W4Settings::default(),
+            FilingStatus::MarriedFilingJointly,
+            2024,
+        );
+
+        let verification = verifier.verify(&paystub, &input(FilingStatus::Single), dec!(5));
+        let federal = verification
+            .discrepancy(PaystubLine::FederalWithholding)
+            .unwrap();
+        assert!(federal.difference < Decimal::ZERO);
+        assert_eq!(
+            federal.cause,
+            Some(DiscrepancyCause::W4FilingStatus(
+                FilingStatus::MarriedFilingJointly
+            ))
+        );
+
+        // Far more withholding than any status explains
+        paystub.federal_withholding += dec!(500);
+        let verification = verifier.verify(&paystub, &input(FilingStatus::Single), dec!(5));
+        assert_eq!(
+            verification
+                .discrepancy(PaystubLine::FederalWithholding)
+                .unwrap()
+                .cause,
+            Some(DiscrepancyCause::ExtraWithholding)
+        );
+    }
+
+    #[test]
+    fn test_explains_missed_pre_tax_deduction() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let verifier = PaystubVerifier::new(&engine);
+
+        // The stub reflects a $400/paycheck 401(k) the user didn't enter
+        let actual_input = TaxCalculationInput {
+            traditional_401k: dec!(10400),
+            ..input(FilingStatus::Single)
+        };
+        let paystub = verifier.expected_paystub(&actual_input, PayFrequency::BiWeekly);
+
+        let verification = verifier.verify(&paystub, &input(FilingStatus::Single), dec!(5));
+        let expected_cause = Some(DiscrepancyCause::MissedPreTaxDeduction {
+            annual: dec!(10400),
+        });
+        for line in [
+            PaystubLine::PreTaxDeductions,
+            PaystubLine::FederalWithholding,
+            PaystubLine::StateWithholding,
+        ] {
+            assert_eq!(
+                verification.discrepancy(line).unwrap().cause,
+                expected_cause,
+                "{line}"
+            );
+        }
+        assert!(verification.discrepancy(PaystubLine::Gross).is_none());
+    }
+}
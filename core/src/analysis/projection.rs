@@ -0,0 +1,380 @@
+//! Multi-year take-home projection with wage growth and inflation indexing
+//!
+//! Capital gains and losses and home energy spending in the input fall in
+//! the first year; capital losses beyond the annual limit and unused clean
+//! energy credit carry into later years until used.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::credits::adoption::AdoptionExpenses;
+use crate::calculators::credits::energy::EnergyImprovements;
+use crate::data::indexed::InflationIndexedData;
+use crate::data::TaxDataProvider;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::capital::{CapitalGains, CapitalLossCarryover};
+
+/// Assumptions applied to each projected year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionAssumptions {
+    /// Number of years to project, including the first (base) year
+    pub years: u32,
+    /// Annual salary growth rate (e.g. 0.03)
+    pub salary_growth: Decimal,
+    /// Annual increase in the traditional 401(k) deferral rate, in fractional
+    /// points (e.g. 0.01 raises a 6% deferral to 7%)
+    pub contribution_escalation: Decimal,
+    /// Cap on the escalated deferral rate
+    pub max_contribution_rate: Decimal,
+    /// Inflation rate used to index brackets, deductions, and limits
+    pub inflation_rate: Decimal,
+}
+
+impl Default for ProjectionAssumptions {
+    fn default() -> Self {
+        Self {
+            years: 10,
+            salary_growth: dec!(0.03),
+            contribution_escalation: Decimal::ZERO,
+            max_contribution_rate: dec!(0.15),
+            inflation_rate: dec!(0.025),
+        }
+    }
+}
+
+/// Projection for a single year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearProjection {
+    pub year: u32,
+    pub gross_income: Decimal,
+    pub traditional_401k: Decimal,
+    pub total_taxes: Decimal,
+    pub net_income: Decimal,
+    pub cumulative_taxes: Decimal,
+    pub cumulative_net: Decimal,
+    pub cumulative_401k: Decimal,
+    /// Capital losses still unused at the end of the year
+    pub capital_loss_carryover: CapitalLossCarryover,
+    /// Residential energy credits used this year
+    pub energy_credits: Decimal,
+    /// Clean energy credit still unused at the end of the year
+    pub energy_credit_carryforward: Decimal,
+}
+
+/// Year-by-year projection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiYearProjection {
+    pub years: Vec<YearProjection>,
+    pub total_taxes: Decimal,
+    pub total_net: Decimal,
+    pub total_401k: Decimal,
+}
+
+/// Multi-year projector built on the calculation engine
+///
+/// The engine's year is the first year. Years the engine's data provider
+/// supports use its own figures; years after the last of them are computed
+/// against an [`InflationIndexedData`] view indexed from that year.
+pub struct MultiYearProjector<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> MultiYearProjector<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Project `input` forward under the given assumptions
+    pub fn project(
+        &self,
+        input: &TaxCalculationInput,
+        assumptions: &ProjectionAssumptions,
+    ) -> MultiYearProjection {
+        let base_year = self.engine.year();
+        let provider = self.engine.data_provider();
+        let last_supported = (base_year..base_year + assumptions.years)
+            .take_while(|year| provider.supports_year(*year))
+            .last()
+            .unwrap_or(base_year);
+        let data = InflationIndexedData::new(provider, last_supported, assumptions.inflation_rate);
+
+        let base_rate = if input.gross_income > Decimal::ZERO {
+            input.traditional_401k / input.gross_income
+        } else {
+            Decimal::ZERO
+        };
+
+        let mut years = Vec::with_capacity(assumptions.years as usize);
+        let mut gross = input.gross_income;
+        let mut cumulative_taxes = Decimal::ZERO;
+        let mut cumulative_net = Decimal::ZERO;
+        let mut cumulative_401k = Decimal::ZERO;
+        let mut capital_gains = input.capital_gains.clone();
+        let mut energy_improvements = input.energy_improvements.clone();
+        let mut adoption = input.adoption.clone();
+
+        for offset in 0..assumptions.years {
+            let year = base_year + offset;
+            let engine = TaxCalculationEngine::new(&data, year);
+
+            let escalated_rate = if offset == 0 {
+                base_rate
+            } else {
+                (base_rate + assumptions.contribution_escalation * Decimal::from(offset))
+                    .min(assumptions.max_contribution_rate.max(base_rate))
+            };
+            let limit = data.retirement_limits(year).elective_deferral;
+            let traditional_401k = (gross * escalated_rate).min(limit);
+
+            let result = engine.calculate(&TaxCalculationInput {
+                gross_income: gross,
+                traditional_401k,
+                capital_gains,
+                energy_improvements,
+                adoption,
+                ..input.clone()
+            });
+
+            cumulative_taxes += result.tax_breakdown.total_taxes;
+            cumulative_net += result.income.net;
+            cumulative_401k += traditional_401k;
+
+            years.push(YearProjection {
+                year,
+                gross_income: gross,
+                traditional_401k,
+                total_taxes: result.tax_breakdown.total_taxes,
+                net_income: result.income.net,
+                cumulative_taxes,
+                cumulative_net,
+                cumulative_401k,
+                capital_loss_carryover: result.capital_loss_carryover.clone(),
+                energy_credits: result.energy_credits.total(),
+                energy_credit_carryforward: result.energy_credits.carryforward,
+            });
+
+            gross *= Decimal::ONE + assumptions.salary_growth;
+            capital_gains = CapitalGains {
+                carryover: result.capital_loss_carryover,
+                ..Default::default()
+            };
+            energy_improvements = EnergyImprovements {
+                carryforward: result.energy_credits.carryforward,
+                ..Default::default()
+            };
+            adoption = AdoptionExpenses {
+                adoptions: Vec::new(),
+                carryforward: result.adoption_credit.carryforward,
+            };
+        }
+
+        MultiYearProjection {
+            years,
+            total_taxes: cumulative_taxes,
+            total_net: cumulative_net,
+            total_401k: cumulative_401k,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use crate::models::tax::FilingStatus;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn input() -> TaxCalculationInput {
+        TaxCalculationInput {
+            gross_income: dec!(100000),
+            filing_status: FilingStatus::Single,
+            state: USState::Texas,
+            traditional_401k: dec!(6000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_year_matches_engine() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+
+        let projection = projector.project(&input(), &ProjectionAssumptions::default());
+        let direct = engine.calculate(&input());
+
+        assert_eq!(projection.years.len(), 10);
+        assert_eq!(projection.years[0].year, 2024);
+        assert_eq!(projection.years[0].net_income, direct.income.net);
+    }
+
+    #[test]
+    fn test_salary_growth_and_cumulative_totals() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+
+        let assumptions = ProjectionAssumptions {
+            years: 3,
+            salary_growth: dec!(0.05),
+            ..Default::default()
+        };
+        let projection = projector.project(&input(), &assumptions);
+
+        assert_eq!(projection.years[1].gross_income, dec!(105000));
+        assert_eq!(projection.years[2].gross_income, dec!(110250));
+
+        let summed: Decimal = projection.years.iter().map(|y| y.total_taxes).sum();
+        assert_eq!(projection.total_taxes, summed);
+        assert_eq!(projection.years[2].cumulative_net, projection.total_net);
+    }
+
+    #[test]
+    fn test_embedded_years_before_indexing() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+        let assumptions = ProjectionAssumptions {
+            years: 3,
+            salary_growth: Decimal::ZERO,
+            inflation_rate: dec!(0.10),
+            ..Default::default()
+        };
+        let projection = projector.project(&input(), &assumptions);
+
+        // 2025 uses its own figures, not 2024's indexed by 10%
+        let actual_2025 = TaxCalculationEngine::new(&data, 2025).calculate(&input());
+        assert_eq!(
+            projection.years[1].total_taxes,
+            actual_2025.tax_breakdown.total_taxes
+        );
+
+        // Indexing starts from 2025
+        let indexed = InflationIndexedData::new(&data, 2025, dec!(0.10));
+        let projected_2026 = TaxCalculationEngine::new(&indexed, 2026).calculate(&input());
+        assert_eq!(
+            projection.years[2].total_taxes,
+            projected_2026.tax_breakdown.total_taxes
+        );
+    }
+
+    #[test]
+    fn test_indexing_lowers_tax_versus_frozen_brackets() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+
+        let frozen = ProjectionAssumptions {
+            years: 5,
+            inflation_rate: Decimal::ZERO,
+            ..Default::default()
+        };
+        let indexed = ProjectionAssumptions {
+            inflation_rate: dec!(0.03),
+            ..frozen.clone()
+        };
+
+        let frozen_result = projector.project(&input(), &frozen);
+        let indexed_result = projector.project(&input(), &indexed);
+
+        // Bracket creep: frozen thresholds tax the same raises more heavily
+        assert!(indexed_result.total_taxes < frozen_result.total_taxes);
+    }
+
+    #[test]
+    fn test_capital_loss_carries_forward() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+
+        let assumptions = ProjectionAssumptions {
+            years: 4,
+            salary_growth: Decimal::ZERO,
+            inflation_rate: Decimal::ZERO,
+            ..Default::default()
+        };
+        let loss_year = TaxCalculationInput {
+            capital_gains: CapitalGains {
+                long_term: dec!(-7500),
+                ..Default::default()
+            },
+            ..input()
+        };
+        let with_loss = projector.project(&loss_year, &assumptions);
+        let without = projector.project(&input(), &assumptions);
+
+        // $3,000 a year until the $7,500 loss is used up
+        let carried: Vec<Decimal> = with_loss
+            .years
+            .iter()
+            .map(|y| y.capital_loss_carryover.total())
+            .collect();
+        assert_eq!(carried, vec![dec!(4500), dec!(1500), dec!(0), dec!(0)]);
+        // At the 22% bracket, each year's deduction saves its share of tax
+        let saved = |i: usize| without.years[i].total_taxes - with_loss.years[i].total_taxes;
+        assert_eq!(saved(0), dec!(660));
+        assert_eq!(saved(1), dec!(660));
+        assert_eq!(saved(2), dec!(330));
+        assert_eq!(saved(3), dec!(0));
+    }
+
+    #[test]
+    fn test_energy_credit_carries_forward() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+
+        let assumptions = ProjectionAssumptions {
+            years: 3,
+            salary_growth: Decimal::ZERO,
+            inflation_rate: Decimal::ZERO,
+            ..Default::default()
+        };
+        let solar = TaxCalculationInput {
+            energy_improvements: EnergyImprovements {
+                clean_energy: dec!(60000),
+                ..Default::default()
+            },
+            ..input()
+        };
+        let projection = projector.project(&solar, &assumptions);
+        let federal_tax = engine.calculate(&input()).tax_breakdown.federal.tax;
+
+        // An $18,000 credit wipes out the first year's federal tax and
+        // spills into the second
+        let years = &projection.years;
+        assert_eq!(years[0].energy_credits, federal_tax);
+        assert_eq!(
+            years[0].energy_credit_carryforward,
+            dec!(18000) - federal_tax
+        );
+        assert_eq!(years[1].energy_credits, dec!(18000) - federal_tax);
+        assert_eq!(years[1].energy_credit_carryforward, dec!(0));
+        assert_eq!(years[2].energy_credits, dec!(0));
+    }
+
+    #[test]
+    fn test_contribution_escalation_capped() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projector = MultiYearProjector::new(&engine);
+
+        let assumptions = ProjectionAssumptions {
+            years: 6,
+            salary_growth: Decimal::ZERO,
+            contribution_escalation: dec!(0.01),
+            max_contribution_rate: dec!(0.08),
+            inflation_rate: Decimal::ZERO,
+        };
+        let projection = projector.project(&input(), &assumptions);
+
+        assert_eq!(projection.years[0].traditional_401k, dec!(6000));
+        assert_eq!(projection.years[1].traditional_401k, dec!(7000));
+        assert_eq!(projection.years[5].traditional_401k, dec!(8000));
+    }
+}
@@ -0,0 +1,94 @@
+//! What a purchase really costs in earnings and hours
+//!
+//! Paying for something out of take-home pay means earning more than its
+//! price: every extra dollar of wages loses the marginal tax rate first. The
+//! converter finds the gross wages that leave enough after tax to cover a
+//! price and the hours of work those wages take.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::extra_hours::ExtraHourAnalyzer;
+use crate::calculators::timeframe::TimeframeCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::income::TimeframeIncome;
+use crate::models::rate::Rate;
+
+/// Earnings and work behind a purchase
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PurchaseCost {
+    pub price: Decimal,
+    /// Gross wages that take home the price
+    pub pre_tax_earnings: Decimal,
+    /// Hours at the regular hourly rate to earn them
+    pub hours_of_work: Decimal,
+    /// Workdays on the input's schedule to earn them
+    pub days_of_work: Decimal,
+    /// Share of those earnings lost to tax
+    pub marginal_rate: Rate,
+}
+
+/// Converts purchase prices into pre-tax earnings and hours of work
+pub struct PurchaseCostConverter<'a> {
+    hours: ExtraHourAnalyzer<'a>,
+}
+
+impl<'a> PurchaseCostConverter<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self {
+            hours: ExtraHourAnalyzer::new(engine),
+        }
+    }
+
+    /// Earnings and hours needed to pay `price` out of extra take-home pay
+    pub fn cost(&self, input: &TaxCalculationInput, price: Decimal) -> PurchaseCost {
+        let price = price.max(Decimal::ZERO);
+        let pre_tax_earnings = self.hours.gross_to_take_home(input, price);
+        let rates = TimeframeIncome::from_schedule(input.gross_income, &input.work_schedule);
+
+        PurchaseCost {
+            price,
+            pre_tax_earnings,
+            hours_of_work: TimeframeCalculator::hours_to_earn(rates.hourly, pre_tax_earnings)
+                .round_dp(2),
+            days_of_work: TimeframeCalculator::days_to_earn(rates.daily, pre_tax_earnings)
+                .round_dp(2),
+            marginal_rate: Rate::of(pre_tax_earnings - price, pre_tax_earnings),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_purchase_cost() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let converter = PurchaseCostConverter::new(&engine);
+        // $52/hour in the 22% bracket with no state income tax keeps 70.35%
+        let input = TaxCalculationInput {
+            gross_income: dec!(108160),
+            state: USState::Texas,
+            ..Default::default()
+        };
+
+        let cost = converter.cost(&input, dec!(1407));
+        assert_eq!(cost.pre_tax_earnings, dec!(2000));
+        assert_eq!(cost.hours_of_work, dec!(38.46));
+        assert_eq!(cost.days_of_work, dec!(4.81));
+        assert_eq!(cost.marginal_rate, Rate::of(dec!(593), dec!(2000)));
+
+        let free = converter.cost(&input, dec!(0));
+        assert_eq!(free.hours_of_work, dec!(0));
+        assert_eq!(free.marginal_rate, Rate::ZERO);
+    }
+}
@@ -0,0 +1,260 @@
+//! Retirement-year income tax projection
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::irmaa::{IrmaaCalculator, IrmaaEstimate, LOOKBACK_YEARS};
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+use crate::models::retirement::RetirementIncome;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Age Medicare coverage starts
+const MEDICARE_AGE: u32 = 65;
+
+/// Input describing a single retirement year
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetirementYearInput {
+    pub filing_status: FilingStatus,
+    pub state: USState,
+    pub income: RetirementIncome,
+    /// Wages from any part-time work (subject to FICA)
+    pub wages: Decimal,
+}
+
+impl RetirementYearInput {
+    fn to_calculation_input(&self) -> TaxCalculationInput {
+        TaxCalculationInput {
+            gross_income: self.wages,
+            filing_status: self.filing_status,
+            state: self.state,
+            retirement_income: self.income.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Projected taxes for a retirement year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetirementYearResult {
+    pub state: USState,
+    pub income: RetirementIncome,
+    pub calculation: TaxCalculationResult,
+    /// Federal plus state income tax (excludes FICA on any wages)
+    pub income_tax: Decimal,
+    pub after_tax_income: Decimal,
+    /// Medicare surcharges this year's income sets two years later, when
+    /// the retiree will be on Medicare by then
+    #[serde(default)]
+    pub irmaa: Option<IrmaaEstimate>,
+}
+
+/// A way of funding retirement spending from traditional and Roth accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalStrategy {
+    pub name: String,
+    pub traditional_withdrawals: Decimal,
+    pub roth_withdrawals: Decimal,
+}
+
+/// Result of applying a withdrawal strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyOutcome {
+    pub strategy: WithdrawalStrategy,
+    pub projection: RetirementYearResult,
+}
+
+/// Retirement-year projection built on the calculation engine
+pub struct RetirementProjection<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> RetirementProjection<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Project taxes for one retirement year
+    pub fn project(&self, input: &RetirementYearInput) -> RetirementYearResult {
+        let calculation = self.engine.calculate(&input.to_calculation_input());
+        let breakdown = &calculation.tax_breakdown;
+        let irmaa = (input.income.age + LOOKBACK_YEARS >= MEDICARE_AGE).then(|| {
+            IrmaaCalculator::new(self.engine.data_provider()).estimate(
+                calculation.adjusted_gross_income,
+                input.filing_status,
+                self.engine.year(),
+            )
+        });
+
+        RetirementYearResult {
+            state: input.state,
+            income: input.income.clone(),
+            income_tax: breakdown.federal.tax + breakdown.state.income_tax,
+            after_tax_income: calculation.income.net,
+            irmaa,
+            calculation,
+        }
+    }
+
+    /// Project the same retirement year in several states, best first
+    pub fn compare_states(
+        &self,
+        input: &RetirementYearInput,
+        states: &[USState],
+    ) -> Vec<RetirementYearResult> {
+        let mut results: Vec<_> = states
+            .iter()
+            .map(|state| {
+                self.project(&RetirementYearInput {
+                    state: *state,
+                    ..input.clone()
+                })
+            })
+            .collect();
+
+        results.sort_by_key(|r| std::cmp::Reverse(r.after_tax_income));
+        results
+    }
+
+    /// Project withdrawal strategies against the same base year, best first
+    pub fn compare_strategies(
+        &self,
+        input: &RetirementYearInput,
+        strategies: &[WithdrawalStrategy],
+    ) -> Vec<StrategyOutcome> {
+        let mut outcomes: Vec<_> = strategies
+            .iter()
+            .map(|strategy| {
+                let income = RetirementIncome {
+                    traditional_withdrawals: strategy.traditional_withdrawals,
+                    roth_withdrawals: strategy.roth_withdrawals,
+                    ..input.income.clone()
+                };
+                StrategyOutcome {
+                    strategy: strategy.clone(),
+                    projection: self.project(&RetirementYearInput {
+                        income,
+                        ..input.clone()
+                    }),
+                }
+            })
+            .collect();
+
+        outcomes.sort_by_key(|o| std::cmp::Reverse(o.projection.after_tax_income));
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn retiree() -> RetirementYearInput {
+        RetirementYearInput {
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::California,
+            income: RetirementIncome {
+                traditional_withdrawals: dec!(60000),
+                roth_withdrawals: dec!(0),
+                social_security_benefits: dec!(40000),
+                pension_income: dec!(20000),
+                age: 70,
+            },
+            wages: dec!(0),
+        }
+    }
+
+    #[test]
+    fn test_project_retirement_year() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projection = RetirementProjection::new(&engine);
+
+        let result = projection.project(&retiree());
+
+        assert_eq!(result.calculation.tax_breakdown.fica.total, dec!(0));
+        assert_eq!(result.calculation.tax_breakdown.state.sdi, dec!(0));
+        assert!(result.calculation.taxable_social_security > dec!(0));
+        assert!(result.income_tax > dec!(0));
+        assert_eq!(
+            result.after_tax_income,
+            dec!(120000) - result.calculation.tax_breakdown.total_taxes
+        );
+    }
+
+    #[test]
+    fn test_large_withdrawal_triggers_irmaa() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projection = RetirementProjection::new(&engine);
+
+        let base = projection.project(&retiree()).irmaa.unwrap();
+        assert_eq!(base.premium_year, 2026);
+        assert!(!base.has_surcharge());
+
+        // A large withdrawal (or Roth conversion) crosses into a surcharge tier
+        let mut input = retiree();
+        input.income.traditional_withdrawals = dec!(250000);
+        let result = projection.project(&input);
+        let irmaa = result.irmaa.unwrap();
+        assert_eq!(irmaa.magi, result.calculation.adjusted_gross_income);
+        assert!(irmaa.has_surcharge());
+
+        // Too young for the income to reach Medicare premiums
+        input.income.age = 55;
+        assert!(projection.project(&input).irmaa.is_none());
+    }
+
+    #[test]
+    fn test_compare_states_sorted() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projection = RetirementProjection::new(&engine);
+
+        let results = projection.compare_states(
+            &retiree(),
+            &[USState::California, USState::Florida, USState::Pennsylvania],
+        );
+
+        assert_eq!(results.len(), 3);
+        // Florida has no income tax and PA exempts retirement income
+        assert_eq!(results[2].state, USState::California);
+        assert_eq!(results[0].after_tax_income, results[1].after_tax_income);
+    }
+
+    #[test]
+    fn test_roth_withdrawals_reduce_tax() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let projection = RetirementProjection::new(&engine);
+
+        let strategies = [
+            WithdrawalStrategy {
+                name: "Traditional only".to_string(),
+                traditional_withdrawals: dec!(60000),
+                roth_withdrawals: dec!(0),
+            },
+            WithdrawalStrategy {
+                name: "Blended".to_string(),
+                traditional_withdrawals: dec!(30000),
+                roth_withdrawals: dec!(30000),
+            },
+        ];
+
+        let outcomes = projection.compare_strategies(&retiree(), &strategies);
+
+        assert_eq!(outcomes[0].strategy.name, "Blended");
+        assert!(outcomes[0].projection.income_tax < outcomes[1].projection.income_tax);
+        // Less traditional income also means less taxable Social Security
+        assert!(
+            outcomes[0].projection.calculation.taxable_social_security
+                < outcomes[1].projection.calculation.taxable_social_security
+        );
+    }
+}
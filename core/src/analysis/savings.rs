@@ -0,0 +1,448 @@
+//! Ordering of marginal savings dollars across tax-advantaged accounts
+//!
+//! Dollars fill accounts in the usual priority: enough 401(k) deferral to
+//! earn the full employer match, the HSA, the rest of the 401(k), an IRA,
+//! and finally a taxable account. Each step records the tax it saves this
+//! year given everything allocated before it.
+//!
+//! IRA dollars follow the income limits. Contributing to the 401(k) makes
+//! the earner a workplace plan participant, so the Traditional IRA
+//! deduction phases out with income; so does the Roth IRA contribution.
+//! IRA room neither covers goes in as a nondeductible contribution to
+//! convert to Roth (the "backdoor" Roth).
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::offers::MatchFormula;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+pub use crate::models::deduction::HsaCoverage;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Age from which 401(k) and IRA catch-up contributions are allowed
+const CATCH_UP_AGE: u32 = 50;
+
+/// Account a savings dollar can go to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavingsVehicle {
+    /// 401(k) deferral up to the full employer match
+    EmployerMatch,
+    Hsa,
+    Traditional401k,
+    Roth401k,
+    TraditionalIra,
+    RothIra,
+    /// Nondeductible Traditional IRA contribution converted to Roth
+    BackdoorRothIra,
+    Taxable,
+}
+
+/// Earner and budget to allocate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsProfile {
+    pub gross_income: Decimal,
+    pub filing_status: FilingStatus,
+    pub state: USState,
+    pub age: u32,
+    /// Annual amount to save across all accounts
+    pub annual_savings: Decimal,
+    pub employer_match: MatchFormula,
+    /// HDHP coverage, or `None` when not HSA-eligible
+    pub hsa_coverage: Option<HsaCoverage>,
+    /// Expected marginal rate on withdrawals in retirement
+    ///
+    /// Traditional accounts are chosen when this year's tax saved per dollar
+    /// is at least this rate; otherwise the Roth option is.
+    pub retirement_tax_rate: Decimal,
+}
+
+/// Dollars placed in one account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsAllocation {
+    pub vehicle: SavingsVehicle,
+    pub amount: Decimal,
+    /// Employer match earned by this allocation
+    pub employer_contribution: Decimal,
+    /// Reduction in this year's total taxes from this allocation
+    pub tax_saved: Decimal,
+}
+
+/// Recommended allocation in priority order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsWaterfall {
+    pub allocations: Vec<SavingsAllocation>,
+    pub total_tax_saved: Decimal,
+    pub total_employer_contribution: Decimal,
+}
+
+impl SavingsWaterfall {
+    /// Amount allocated to a vehicle (zero when it was skipped)
+    pub fn amount_for(&self, vehicle: SavingsVehicle) -> Decimal {
+        self.allocations
+            .iter()
+            .filter(|a| a.vehicle == vehicle)
+            .map(|a| a.amount)
+            .sum()
+    }
+
+    pub fn total_allocated(&self) -> Decimal {
+        self.allocations.iter().map(|a| a.amount).sum()
+    }
+}
+
+/// Savings waterfall recommender built on the calculation engine
+pub struct SavingsWaterfallRecommender<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> SavingsWaterfallRecommender<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Allocate the profile's savings budget across accounts
+    pub fn recommend(&self, profile: &SavingsProfile) -> SavingsWaterfall {
+        let limits = self
+            .engine
+            .data_provider()
+            .retirement_limits(self.engine.year());
+        let catch_up = profile.age >= CATCH_UP_AGE;
+        let deferral_limit = if catch_up {
+            limits.elective_deferral + limits.catch_up_deferral
+        } else {
+            limits.elective_deferral
+        };
+        let ira_limit = if catch_up {
+            limits.ira_contribution + limits.ira_catch_up
+        } else {
+            limits.ira_contribution
+        };
+        let hsa_limit = profile
+            .hsa_coverage
+            .map(|coverage| limits.hsa_limit(coverage, profile.age))
+            .unwrap_or(Decimal::ZERO);
+
+        let mut input = TaxCalculationInput {
+            gross_income: profile.gross_income,
+            filing_status: profile.filing_status,
+            state: profile.state,
+            ..Default::default()
+        };
+        input.retirement_income.age = profile.age;
+
+        let prefer_traditional = self.prefers_traditional(
+            &input,
+            profile.annual_savings.min(deferral_limit),
+            profile.retirement_tax_rate,
+        );
+        let deferral = if prefer_traditional {
+            SavingsVehicle::Traditional401k
+        } else {
+            SavingsVehicle::Roth401k
+        };
+
+        let match_deferral = if profile.employer_match.match_rate > Decimal::ZERO {
+            (profile.gross_income * profile.employer_match.salary_cap).min(deferral_limit)
+        } else {
+            Decimal::ZERO
+        };
+
+        // IRA dollars come only once the 401(k) and HSA are full, so
+        // modified AGI is wages less those contributions
+        let magi = (profile.gross_income
+            - hsa_limit
+            - if prefer_traditional {
+                deferral_limit
+            } else {
+                Decimal::ZERO
+            })
+        .max(Decimal::ZERO);
+        let deductible_ira = if prefer_traditional {
+            limits
+                .ira_deduction_phase_out
+                .allowed(ira_limit, magi, profile.filing_status)
+        } else {
+            Decimal::ZERO
+        };
+        let roth_ira = limits
+            .roth_ira_phase_out
+            .allowed(ira_limit, magi, profile.filing_status)
+            .min(ira_limit - deductible_ira);
+
+        let steps = [
+            (SavingsVehicle::EmployerMatch, match_deferral),
+            (SavingsVehicle::Hsa, hsa_limit),
+            (deferral, deferral_limit - match_deferral),
+            (SavingsVehicle::TraditionalIra, deductible_ira),
+            (SavingsVehicle::RothIra, roth_ira),
+            (
+                SavingsVehicle::BackdoorRothIra,
+                ira_limit - deductible_ira - roth_ira,
+            ),
+        ];
+
+        let mut remaining = profile.annual_savings.max(Decimal::ZERO);
+        let mut taxes = self.engine.calculate(&input).tax_breakdown.total_taxes;
+        let mut allocations = Vec::new();
+        for (vehicle, limit) in steps {
+            let amount = remaining.min(limit).min(Self::room(&input));
+            if amount <= Decimal::ZERO {
+                continue;
+            }
+            let target = match vehicle {
+                SavingsVehicle::EmployerMatch => deferral,
+                other => other,
+            };
+            Self::apply(&mut input, target, amount);
+            let after = self.engine.calculate(&input).tax_breakdown.total_taxes;
+            allocations.push(SavingsAllocation {
+                vehicle,
+                amount,
+                employer_contribution: if vehicle == SavingsVehicle::EmployerMatch {
+                    profile
+                        .employer_match
+                        .employer_match(profile.gross_income, amount)
+                } else {
+                    Decimal::ZERO
+                },
+                tax_saved: taxes - after,
+            });
+            taxes = after;
+            remaining -= amount;
+        }
+        if remaining > Decimal::ZERO {
+            allocations.push(SavingsAllocation {
+                vehicle: SavingsVehicle::Taxable,
+                amount: remaining,
+                employer_contribution: Decimal::ZERO,
+                tax_saved: Decimal::ZERO,
+            });
+        }
+
+        SavingsWaterfall {
+            total_tax_saved: allocations.iter().map(|a| a.tax_saved).sum(),
+            total_employer_contribution: allocations.iter().map(|a| a.employer_contribution).sum(),
+            allocations,
+        }
+    }
+
+    /// Whether a traditional deferral saves at least the retirement rate now
+    fn prefers_traditional(
+        &self,
+        input: &TaxCalculationInput,
+        amount: Decimal,
+        retirement_tax_rate: Decimal,
+    ) -> bool {
+        if amount <= Decimal::ZERO {
+            return true;
+        }
+        let before = self.engine.calculate(input).tax_breakdown.total_taxes;
+        let after = self
+            .engine
+            .calculate(&TaxCalculationInput {
+                traditional_401k: amount,
+                ..input.clone()
+            })
+            .tax_breakdown
+            .total_taxes;
+        (before - after) / amount >= retirement_tax_rate
+    }
+
+    /// Wages not yet committed to contributions
+    fn room(input: &TaxCalculationInput) -> Decimal {
+        input.gross_income
+            - input.pre_tax_deductions
+            - input.traditional_401k
+            - input.roth_401k
+            - input.hsa_contribution
+    }
+
+    fn apply(input: &mut TaxCalculationInput, vehicle: SavingsVehicle, amount: Decimal) {
+        match vehicle {
+            SavingsVehicle::Hsa => input.hsa_contribution += amount,
+            SavingsVehicle::Traditional401k => input.traditional_401k += amount,
+            SavingsVehicle::Roth401k => input.roth_401k += amount,
+            // Deducted on the return, so it reduces income tax but not FICA
+            SavingsVehicle::TraditionalIra => input.pre_tax_deductions += amount,
+            SavingsVehicle::EmployerMatch
+            | SavingsVehicle::RothIra
+            | SavingsVehicle::BackdoorRothIra
+            | SavingsVehicle::Taxable => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn profile(annual_savings: Decimal) -> SavingsProfile {
+        SavingsProfile {
+            gross_income: dec!(150000),
+            filing_status: FilingStatus::Single,
+            state: USState::California,
+            age: 35,
+            annual_savings,
+            employer_match: MatchFormula {
+                match_rate: dec!(0.5),
+                salary_cap: dec!(0.06),
+            },
+            hsa_coverage: Some(HsaCoverage::SelfOnly),
+            retirement_tax_rate: dec!(0.15),
+        }
+    }
+
+    #[test]
+    fn test_waterfall_order_and_limits() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let recommender = SavingsWaterfallRecommender::new(&engine);
+
+        let waterfall = recommender.recommend(&profile(dec!(40000)));
+        let vehicles: Vec<_> = waterfall.allocations.iter().map(|a| a.vehicle).collect();
+
+        assert_eq!(
+            vehicles,
+            vec![
+                SavingsVehicle::EmployerMatch,
+                SavingsVehicle::Hsa,
+                SavingsVehicle::Traditional401k,
+                SavingsVehicle::RothIra,
+                SavingsVehicle::Taxable,
+            ]
+        );
+        // 6% of $150,000 earns the $4,500 match; the 401(k) fills to $23,000
+        assert_eq!(
+            waterfall.amount_for(SavingsVehicle::EmployerMatch),
+            dec!(9000)
+        );
+        assert_eq!(waterfall.total_employer_contribution, dec!(4500));
+        assert_eq!(waterfall.amount_for(SavingsVehicle::Hsa), dec!(4150));
+        assert_eq!(
+            waterfall.amount_for(SavingsVehicle::Traditional401k),
+            dec!(14000)
+        );
+        // $122,850 of modified AGI is past the deduction phase-out for a
+        // 401(k) participant but under the Roth limit
+        assert_eq!(waterfall.amount_for(SavingsVehicle::RothIra), dec!(7000));
+        assert_eq!(waterfall.amount_for(SavingsVehicle::Taxable), dec!(5850));
+        assert_eq!(waterfall.total_allocated(), dec!(40000));
+    }
+
+    #[test]
+    fn test_tax_saved_per_step() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let recommender = SavingsWaterfallRecommender::new(&engine);
+
+        let waterfall = recommender.recommend(&SavingsProfile {
+            state: USState::Texas,
+            ..profile(dec!(40000))
+        });
+        let saved = |vehicle| {
+            waterfall
+                .allocations
+                .iter()
+                .find(|a| a.vehicle == vehicle)
+                .unwrap()
+                .tax_saved
+        };
+
+        // The HSA also escapes FICA, so it saves more per dollar than the 401(k)
+        assert!(
+            saved(SavingsVehicle::Hsa) / dec!(4150)
+                > saved(SavingsVehicle::Traditional401k) / dec!(14000)
+        );
+        assert_eq!(saved(SavingsVehicle::Taxable), dec!(0));
+        assert_eq!(
+            waterfall.total_tax_saved,
+            waterfall
+                .allocations
+                .iter()
+                .map(|a| a.tax_saved)
+                .sum::<Decimal>()
+        );
+
+        let with_savings = TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::Texas,
+            traditional_401k: dec!(23000),
+            hsa_contribution: dec!(4150),
+            ..Default::default()
+        };
+        let without = TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        assert_eq!(
+            waterfall.total_tax_saved,
+            engine.calculate(&without).tax_breakdown.total_taxes
+                - engine.calculate(&with_savings).tax_breakdown.total_taxes
+        );
+    }
+
+    #[test]
+    fn test_ira_income_phase_outs() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let recommender = SavingsWaterfallRecommender::new(&engine);
+
+        // $77,850 of modified AGI is $850 into the $77,000-$87,000 range:
+        // $6,405 rounds up to $6,410 deductible, the rest goes to the Roth
+        let partial = recommender.recommend(&SavingsProfile {
+            gross_income: dec!(105000),
+            ..profile(dec!(40000))
+        });
+        assert_eq!(
+            partial.amount_for(SavingsVehicle::TraditionalIra),
+            dec!(6410)
+        );
+        assert_eq!(partial.amount_for(SavingsVehicle::RothIra), dec!(590));
+
+        // Past the $161,000 Roth limit, IRA room goes in the back door
+        let high = recommender.recommend(&SavingsProfile {
+            gross_income: dec!(250000),
+            ..profile(dec!(40000))
+        });
+        assert_eq!(high.amount_for(SavingsVehicle::TraditionalIra), dec!(0));
+        assert_eq!(high.amount_for(SavingsVehicle::RothIra), dec!(0));
+        assert_eq!(high.amount_for(SavingsVehicle::BackdoorRothIra), dec!(7000));
+        let backdoor = high
+            .allocations
+            .iter()
+            .find(|a| a.vehicle == SavingsVehicle::BackdoorRothIra)
+            .unwrap();
+        assert_eq!(backdoor.tax_saved, dec!(0));
+    }
+
+    #[test]
+    fn test_low_bracket_prefers_roth() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let recommender = SavingsWaterfallRecommender::new(&engine);
+
+        let waterfall = recommender.recommend(&SavingsProfile {
+            gross_income: dec!(40000),
+            state: USState::Texas,
+            hsa_coverage: None,
+            retirement_tax_rate: dec!(0.22),
+            ..profile(dec!(10000))
+        });
+
+        assert_eq!(waterfall.amount_for(SavingsVehicle::Hsa), dec!(0));
+        assert_eq!(
+            waterfall.amount_for(SavingsVehicle::EmployerMatch),
+            dec!(2400)
+        );
+        assert_eq!(waterfall.amount_for(SavingsVehicle::Roth401k), dec!(7600));
+        assert_eq!(waterfall.total_tax_saved, dec!(0));
+    }
+}
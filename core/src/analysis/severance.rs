@@ -0,0 +1,200 @@
+//! Severance and PTO payouts on leaving a job
+//!
+//! A separation payout is a supplemental wage: the departing employer
+//! withholds federal tax at the flat 22% rate whatever the year's real
+//! bracket is. If the employee starts a new job the same year, the new
+//! employer withholds Social Security from its first paycheck too, so
+//! combined withholding can run past the annual maximum. The excess comes
+//! back as a credit at filing.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::withholding::{
+    SupplementalKind, SupplementalWithholding, SupplementalWithholdingInput,
+};
+use crate::calculators::SupplementalWithholdingCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+
+/// A payout from a departing employer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeparationPayout {
+    /// `Severance` or `PtoPayout`
+    pub kind: SupplementalKind,
+    pub amount: Decimal,
+    /// Regular wages the departing employer paid this year before the payout
+    pub prior_employer_wages: Decimal,
+    /// Wages from a new employer later in the same year
+    #[serde(default)]
+    pub new_employer_wages: Decimal,
+}
+
+/// What a payout withholds and what it really costs for the year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeparationPayoutImpact {
+    pub withholding: SupplementalWithholding,
+    /// Increase in the year's federal, state, and payroll taxes
+    pub tax_increase: Decimal,
+    pub federal_tax_increase: Decimal,
+    /// State and local income tax increase
+    pub state_tax_increase: Decimal,
+    /// Income tax owed at filing beyond what the payout withheld; negative
+    /// when it was over-withheld
+    pub withholding_shortfall: Decimal,
+    /// Social Security withheld by both employers beyond the annual maximum,
+    /// refunded as a credit at filing
+    pub excess_social_security: Decimal,
+    /// Payout kept after its true tax cost
+    pub after_tax_value: Decimal,
+}
+
+/// Separation payout analyzer
+pub struct SeparationPayoutAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> SeparationPayoutAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Withholding and annual tax impact of a payout
+    ///
+    /// `input` supplies filing status, state, and deductions; its gross
+    /// income is replaced by the wages from both employers.
+    pub fn analyze(
+        &self,
+        input: &TaxCalculationInput,
+        payout: &SeparationPayout,
+    ) -> SeparationPayoutImpact {
+        let data = self.engine.data_provider();
+        let year = self.engine.year();
+        let amount = payout.amount.max(Decimal::ZERO);
+
+        let withholding = SupplementalWithholdingCalculator::new(data).calculate(
+            &SupplementalWithholdingInput {
+                amount,
+                kind: payout.kind,
+                state: input.state,
+                filing_status: input.filing_status,
+                annual_wages: payout.prior_employer_wages,
+                ytd_wages: payout.prior_employer_wages,
+                ytd_supplemental_wages: Decimal::ZERO,
+            },
+            year,
+        );
+
+        let regular_wages = payout.prior_employer_wages + payout.new_employer_wages;
+        let without = self.engine.calculate(&TaxCalculationInput {
+            gross_income: regular_wages,
+            ..input.clone()
+        });
+        let with = self.engine.calculate(&TaxCalculationInput {
+            gross_income: regular_wages + amount,
+            ..input.clone()
+        });
+
+        let federal_tax_increase =
+            with.tax_breakdown.federal.tax - without.tax_breakdown.federal.tax;
+        let state_tax_increase = state_income_tax(&with) - state_income_tax(&without);
+        let withholding_shortfall =
+            federal_tax_increase + state_tax_increase - withholding.federal - withholding.state;
+
+        // Each employer withholds up to the wage base on its own wages
+        let fica = data.fica_config(year);
+        let withheld_by_each = (payout.prior_employer_wages + amount).min(fica.wage_base)
+            + payout.new_employer_wages.min(fica.wage_base);
+        let excess_social_security = (withheld_by_each
+            - (regular_wages + amount).min(fica.wage_base))
+            * fica.social_security_rate;
+
+        let tax_increase = with.tax_breakdown.total_taxes - without.tax_breakdown.total_taxes;
+        SeparationPayoutImpact {
+            withholding,
+            tax_increase,
+            federal_tax_increase,
+            state_tax_increase,
+            withholding_shortfall,
+            excess_social_security,
+            after_tax_value: amount - tax_increase,
+        }
+    }
+}
+
+fn state_income_tax(result: &TaxCalculationResult) -> Decimal {
+    let state = &result.tax_breakdown.state;
+    state.total_tax - state.sdi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use crate::models::tax::FilingStatus;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_flat_withholding_falls_short_in_higher_bracket() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = SeparationPayoutAnalyzer::new(&engine);
+        let input = TaxCalculationInput {
+            filing_status: FilingStatus::Single,
+            state: USState::Texas,
+            ..Default::default()
+        };
+
+        let impact = analyzer.analyze(
+            &input,
+            &SeparationPayout {
+                kind: SupplementalKind::Severance,
+                amount: dec!(60000),
+                prior_employer_wages: dec!(150000),
+                new_employer_wages: Decimal::ZERO,
+            },
+        );
+
+        // Withheld at 22%, but taxed at 24% and then 32%
+        assert_eq!(impact.withholding.federal, dec!(13200));
+        assert_eq!(
+            impact.federal_tax_increase,
+            dec!(56550) * dec!(0.24) + dec!(3450) * dec!(0.32)
+        );
+        assert_eq!(impact.withholding_shortfall, dec!(1476));
+        assert_eq!(impact.excess_social_security, dec!(0));
+        assert_eq!(impact.after_tax_value, dec!(60000) - impact.tax_increase);
+    }
+
+    #[test]
+    fn test_new_job_double_withholds_social_security() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = SeparationPayoutAnalyzer::new(&engine);
+        let input = TaxCalculationInput {
+            state: USState::Texas,
+            ..Default::default()
+        };
+
+        // $140,000 + $20,000 PTO at the old job, $90,000 at the new one
+        let impact = analyzer.analyze(
+            &input,
+            &SeparationPayout {
+                kind: SupplementalKind::PtoPayout,
+                amount: dec!(20000),
+                prior_employer_wages: dec!(140000),
+                new_employer_wages: dec!(90000),
+            },
+        );
+
+        // Withheld on $160,000 + $90,000 against a $168,600 wage base
+        assert_eq!(
+            impact.excess_social_security,
+            (dec!(250000) - dec!(168600)) * dec!(0.062)
+        );
+    }
+}
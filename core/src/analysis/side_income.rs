@@ -0,0 +1,245 @@
+//! After-tax value of a side job or gig on top of a primary job
+//!
+//! Side income is taxed at the top of the year's income. A part-time W-2
+//! job also owes FICA, and its employer withholds as if it were the only
+//! job, starting again from the bottom brackets. 1099 income owes both
+//! halves of FICA as self-employment tax and has nothing withheld, so the
+//! federal tax it adds is due at filing unless it's paid in estimates.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::seca::SecaCalculator;
+use crate::calculators::withholding::FederalWithholdingCalculator;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult};
+use crate::models::income::PayFrequency;
+use crate::models::investment::InvestmentIncome;
+use crate::models::profile::W4Settings;
+use crate::models::rate::Rate;
+
+/// Balance due at filing from which the underpayment penalty can apply
+const ESTIMATED_TAX_THRESHOLD: Decimal = dec!(1000);
+
+/// Income from a second job or gig
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SideIncome {
+    /// Part-time W-2 job, withheld on its own wages
+    W2 {
+        wages: Decimal,
+        pay_frequency: PayFrequency,
+    },
+    /// 1099 contract or gig work, as net profit after business expenses
+    Contract { net_profit: Decimal },
+}
+
+impl SideIncome {
+    pub fn gross(&self) -> Decimal {
+        match self {
+            SideIncome::W2 { wages, .. } => *wages,
+            SideIncome::Contract { net_profit } => *net_profit,
+        }
+    }
+}
+
+/// Taxes a side income adds and what's left of it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SideIncomeAnalysis {
+    pub gross: Decimal,
+    /// Added federal income tax, less any refundable credits lost
+    pub federal_income_tax: Decimal,
+    /// Added state and local tax, including payroll programs
+    pub state_tax: Decimal,
+    /// FICA on W-2 wages, or self-employment tax on 1099 profit
+    pub payroll_tax: Decimal,
+    pub total_tax: Decimal,
+    pub after_tax_value: Decimal,
+    /// Share of the side income lost to tax
+    pub marginal_rate: Rate,
+    /// Federal income tax the side job withholds (none for 1099 work)
+    pub federal_withheld: Decimal,
+    /// Added federal income and self-employment tax not withheld
+    pub withholding_shortfall: Decimal,
+    /// Each of four estimated payments covering the shortfall
+    pub quarterly_estimated_payment: Decimal,
+    /// The shortfall alone reaches the $1,000 underpayment penalty threshold
+    pub underpayment_risk: bool,
+}
+
+/// Side income analyzer
+pub struct SideIncomeAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> SideIncomeAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Value of adding `side` to the primary job in `input`
+    ///
+    /// The primary job is assumed to withhold its own tax accurately. 1099
+    /// profit is taxed without the qualified business income deduction.
+    pub fn analyze(&self, input: &TaxCalculationInput, side: &SideIncome) -> SideIncomeAnalysis {
+        let base = self.engine.calculate(input);
+        let year = self.engine.year();
+
+        let (with, payroll_tax, federal_withheld) = match side {
+            SideIncome::W2 {
+                wages,
+                pay_frequency,
+            } => {
+                let with = self.engine.calculate(&TaxCalculationInput {
+                    gross_income: input.gross_income + wages,
+                    ..input.clone()
+                });
+                let periods = Decimal::from(pay_frequency.withholding_periods());
+                let per_paycheck = FederalWithholdingCalculator::new(self.engine.data_provider())
+                    .per_paycheck(
+                        *wages / periods,
+                        *pay_frequency,
+                        &W4Settings::default(),
+                        input.filing_status,
+                        year,
+                    );
+                let fica = with.tax_breakdown.fica.total - base.tax_breakdown.fica.total;
+                (with, fica, per_paycheck * periods)
+            },
+            SideIncome::Contract { net_profit } => {
+                let se = SecaCalculator::new(self.engine.data_provider()).calculate(
+                    *net_profit,
+                    input.gross_income,
+                    input.filing_status,
+                    year,
+                );
+                // Profit less the deductible half of SE tax adds to AGI
+                let with = self.engine.calculate(&TaxCalculationInput {
+                    investment_income: InvestmentIncome {
+                        miscellaneous: input.investment_income.miscellaneous + net_profit
+                            - se.deductible_half,
+                        ..input.investment_income.clone()
+                    },
+                    ..input.clone()
+                });
+                (with, se.total, Decimal::ZERO)
+            },
+        };
+
+        let federal_income_tax = federal_after_refunds(&with) - federal_after_refunds(&base);
+        let state_tax = state_total(&with) - state_total(&base);
+        let total_tax = federal_income_tax + state_tax + payroll_tax;
+        let gross = side.gross();
+
+        let self_employment_tax = match side {
+            SideIncome::Contract { .. } => payroll_tax,
+            SideIncome::W2 { .. } => Decimal::ZERO,
+        };
+        let withholding_shortfall =
+            (federal_income_tax + self_employment_tax - federal_withheld).round_dp(2);
+
+        SideIncomeAnalysis {
+            gross,
+            federal_income_tax: federal_income_tax.round_dp(2),
+            state_tax: state_tax.round_dp(2),
+            payroll_tax: payroll_tax.round_dp(2),
+            total_tax: total_tax.round_dp(2),
+            after_tax_value: (gross - total_tax).round_dp(2),
+            marginal_rate: Rate::of(total_tax, gross),
+            federal_withheld: federal_withheld.round_dp(2),
+            withholding_shortfall,
+            quarterly_estimated_payment: (withholding_shortfall.max(Decimal::ZERO) / dec!(4))
+                .round_dp(2),
+            underpayment_risk: withholding_shortfall >= ESTIMATED_TAX_THRESHOLD,
+        }
+    }
+}
+
+fn federal_after_refunds(result: &TaxCalculationResult) -> Decimal {
+    result.tax_breakdown.federal.tax - result.tax_breakdown.credits.refundable
+}
+
+fn state_total(result: &TaxCalculationResult) -> Decimal {
+    let breakdown = &result.tax_breakdown;
+    breakdown.state.total_tax
+        + breakdown
+            .work_state
+            .as_ref()
+            .map(|w| w.total_tax)
+            .unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_w2_side_job_under_withholds() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(90000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let analysis = SideIncomeAnalyzer::new(&engine).analyze(
+            &input,
+            &SideIncome::W2 {
+                wages: dec!(20000),
+                pay_frequency: PayFrequency::BiWeekly,
+            },
+        );
+
+        // $20,000 at 22%, with FICA; the part-time job withholds as if it
+        // were the only one, taking its own standard deduction
+        assert_eq!(analysis.federal_income_tax, dec!(4400));
+        assert_eq!(analysis.payroll_tax, dec!(1530));
+        assert_eq!(analysis.after_tax_value, dec!(14070));
+        assert!(analysis.federal_withheld < dec!(700));
+        assert!(analysis.underpayment_risk);
+        assert_eq!(
+            analysis.withholding_shortfall,
+            dec!(4400) - analysis.federal_withheld
+        );
+    }
+
+    #[test]
+    fn test_contract_income_owes_self_employment_tax() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(90000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let analysis = SideIncomeAnalyzer::new(&engine).analyze(
+            &input,
+            &SideIncome::Contract {
+                net_profit: dec!(20000),
+            },
+        );
+
+        // $18,470 of net earnings at 15.3%; half of it comes off income
+        let se_tax = dec!(18470) * dec!(0.153);
+        assert_eq!(analysis.payroll_tax, se_tax.round_dp(2));
+        assert_eq!(
+            analysis.federal_income_tax,
+            ((dec!(20000) - se_tax / dec!(2)) * dec!(0.22)).round_dp(2)
+        );
+        assert_eq!(analysis.federal_withheld, dec!(0));
+        assert_eq!(
+            analysis.withholding_shortfall,
+            analysis.federal_income_tax + analysis.payroll_tax
+        );
+        assert_eq!(
+            analysis.quarterly_estimated_payment,
+            (analysis.withholding_shortfall / dec!(4)).round_dp(2)
+        );
+        assert!(analysis.after_tax_value < dec!(14070));
+    }
+}
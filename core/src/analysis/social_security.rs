@@ -0,0 +1,280 @@
+//! Social Security claiming-age comparison
+//!
+//! Compares claiming at 62, full retirement age (FRA), and 70 on an after-tax
+//! basis: each option's benefits are run through the engine on top of the
+//! household's other retirement income, so benefit taxation and state
+//! treatment are reflected in the comparison.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::retirement::RetirementIncome;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+const EARLIEST_CLAIM_MONTHS: u32 = 62 * 12;
+const LATEST_CLAIM_MONTHS: u32 = 70 * 12;
+
+/// Input for a claiming-age comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimingInput {
+    /// Monthly benefit at full retirement age
+    pub primary_insurance_amount: Decimal,
+    pub birth_year: u32,
+    pub filing_status: FilingStatus,
+    pub state: USState,
+    /// Other retirement income (any Social Security here is ignored)
+    pub other_income: RetirementIncome,
+    /// Age through which benefits are assumed to be collected
+    pub life_expectancy: u32,
+}
+
+/// After-tax outcome of claiming at a given age
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimingOption {
+    pub claim_age_months: u32,
+    /// Benefit as a fraction of the primary insurance amount
+    pub benefit_factor: Decimal,
+    pub monthly_benefit: Decimal,
+    pub annual_benefit: Decimal,
+    pub taxable_benefit: Decimal,
+    /// Additional federal and state tax caused by the benefit
+    pub tax_on_benefit: Decimal,
+    pub after_tax_annual_benefit: Decimal,
+    pub lifetime_after_tax_benefit: Decimal,
+}
+
+impl ClaimingOption {
+    /// Claim age in (fractional) years
+    pub fn claim_age_years(&self) -> Decimal {
+        Decimal::from(self.claim_age_months) / dec!(12)
+    }
+}
+
+/// Comparison of claiming at 62, FRA, and 70
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimingComparison {
+    pub full_retirement_age_months: u32,
+    pub at_62: ClaimingOption,
+    pub at_full_retirement_age: ClaimingOption,
+    pub at_70: ClaimingOption,
+    /// Age at which claiming at 70 overtakes claiming at 62 (after tax)
+    pub breakeven_age_70_vs_62: Option<Decimal>,
+}
+
+impl ClaimingComparison {
+    /// Option with the highest lifetime after-tax benefit
+    pub fn best(&self) -> &ClaimingOption {
+        [&self.at_62, &self.at_full_retirement_age, &self.at_70]
+            .into_iter()
+            .max_by_key(|o| o.lifetime_after_tax_benefit)
+            .unwrap_or(&self.at_full_retirement_age)
+    }
+}
+
+/// Social Security claiming-age analyzer
+pub struct ClaimingAgeAnalyzer<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> ClaimingAgeAnalyzer<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Full retirement age in months for a birth year
+    pub fn full_retirement_age_months(birth_year: u32) -> u32 {
+        match birth_year {
+            0..=1954 => 66 * 12,
+            1955..=1959 => 66 * 12 + (birth_year - 1954) * 2,
+            _ => 67 * 12,
+        }
+    }
+
+    /// Benefit factor relative to PIA when claiming at an age (in months)
+    pub fn benefit_factor(claim_age_months: u32, fra_months: u32) -> Decimal {
+        if claim_age_months < fra_months {
+            // 5/9 of 1% per month for the first 36 months, 5/12 of 1% beyond
+            let early = Decimal::from(fra_months - claim_age_months);
+            let first = early.min(dec!(36));
+            let beyond = early - first;
+            Decimal::ONE - first * dec!(5) / dec!(900) - beyond * dec!(5) / dec!(1200)
+        } else {
+            // Delayed retirement credits: 2/3 of 1% per month up to age 70
+            let delayed = Decimal::from(claim_age_months.min(LATEST_CLAIM_MONTHS) - fra_months);
+            Decimal::ONE + delayed * dec!(2) / dec!(300)
+        }
+    }
+
+    /// Compare claiming at 62, FRA, and 70
+    pub fn compare(&self, input: &ClaimingInput) -> ClaimingComparison {
+        let fra_months = Self::full_retirement_age_months(input.birth_year);
+
+        let at_62 = self.option(input, EARLIEST_CLAIM_MONTHS, fra_months);
+        let at_full_retirement_age = self.option(input, fra_months, fra_months);
+        let at_70 = self.option(input, LATEST_CLAIM_MONTHS, fra_months);
+
+        // Cumulative benefits are equal when
+        // early × (age − 62) = late × (age − 70)
+        let gap = at_70.after_tax_annual_benefit - at_62.after_tax_annual_benefit;
+        let breakeven_age_70_vs_62 = if gap > Decimal::ZERO {
+            Some(
+                (at_70.after_tax_annual_benefit * at_70.claim_age_years()
+                    - at_62.after_tax_annual_benefit * at_62.claim_age_years())
+                    / gap,
+            )
+        } else {
+            None
+        };
+
+        ClaimingComparison {
+            full_retirement_age_months: fra_months,
+            at_62,
+            at_full_retirement_age,
+            at_70,
+            breakeven_age_70_vs_62,
+        }
+    }
+
+    fn option(
+        &self,
+        input: &ClaimingInput,
+        claim_age_months: u32,
+        fra_months: u32,
+    ) -> ClaimingOption {
+        let benefit_factor = Self::benefit_factor(claim_age_months, fra_months);
+        let monthly_benefit = input.primary_insurance_amount * benefit_factor;
+        let annual_benefit = monthly_benefit * dec!(12);
+
+        let without = self
+            .engine
+            .calculate(&self.calculation_input(input, Decimal::ZERO));
+        let with = self
+            .engine
+            .calculate(&self.calculation_input(input, annual_benefit));
+
+        let tax_on_benefit = with.tax_breakdown.total_taxes - without.tax_breakdown.total_taxes;
+        let after_tax_annual_benefit = annual_benefit - tax_on_benefit;
+
+        let collection_months = (input.life_expectancy * 12).saturating_sub(claim_age_months);
+        let lifetime_after_tax_benefit =
+            after_tax_annual_benefit * Decimal::from(collection_months) / dec!(12);
+
+        ClaimingOption {
+            claim_age_months,
+            benefit_factor,
+            monthly_benefit,
+            annual_benefit,
+            taxable_benefit: with.taxable_social_security,
+            tax_on_benefit,
+            after_tax_annual_benefit,
+            lifetime_after_tax_benefit,
+        }
+    }
+
+    fn calculation_input(
+        &self,
+        input: &ClaimingInput,
+        annual_benefit: Decimal,
+    ) -> TaxCalculationInput {
+        TaxCalculationInput {
+            filing_status: input.filing_status,
+            state: input.state,
+            retirement_income: RetirementIncome {
+                social_security_benefits: annual_benefit,
+                ..input.other_income.clone()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn input(state: USState, traditional_withdrawals: Decimal) -> ClaimingInput {
+        ClaimingInput {
+            primary_insurance_amount: dec!(2500),
+            birth_year: 1962,
+            filing_status: FilingStatus::Single,
+            state,
+            other_income: RetirementIncome {
+                traditional_withdrawals,
+                age: 70,
+                ..Default::default()
+            },
+            life_expectancy: 90,
+        }
+    }
+
+    #[test]
+    fn test_full_retirement_age() {
+        assert_eq!(ClaimingAgeAnalyzer::full_retirement_age_months(1950), 792);
+        assert_eq!(ClaimingAgeAnalyzer::full_retirement_age_months(1957), 798);
+        assert_eq!(ClaimingAgeAnalyzer::full_retirement_age_months(1962), 804);
+    }
+
+    #[test]
+    fn test_benefit_factors() {
+        let fra = 67 * 12;
+        // 60 months early: 36 × 5/9% + 24 × 5/12% = 30% reduction
+        assert_eq!(ClaimingAgeAnalyzer::benefit_factor(62 * 12, fra), dec!(0.7));
+        assert_eq!(ClaimingAgeAnalyzer::benefit_factor(fra, fra), dec!(1));
+        // 36 months delayed at 8%/year
+        assert_eq!(
+            ClaimingAgeAnalyzer::benefit_factor(70 * 12, fra),
+            dec!(1.24)
+        );
+    }
+
+    #[test]
+    fn test_no_tax_without_other_income() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = ClaimingAgeAnalyzer::new(&engine);
+
+        let comparison = analyzer.compare(&input(USState::Florida, dec!(0)));
+
+        assert_eq!(comparison.at_62.monthly_benefit, dec!(1750));
+        assert_eq!(comparison.at_70.tax_on_benefit, dec!(0));
+        assert_eq!(comparison.at_70.after_tax_annual_benefit, dec!(37200));
+        // $21,000/yr from 62 vs $37,200/yr from 70: breakeven at 80.37
+        let breakeven = comparison.breakeven_age_70_vs_62.unwrap();
+        assert!(breakeven > dec!(80) && breakeven < dec!(81));
+        assert_eq!(comparison.best().claim_age_months, 70 * 12);
+    }
+
+    #[test]
+    fn test_other_income_makes_benefits_taxable() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = ClaimingAgeAnalyzer::new(&engine);
+
+        let comparison = analyzer.compare(&input(USState::Florida, dec!(60000)));
+
+        assert!(comparison.at_70.taxable_benefit > dec!(0));
+        assert!(comparison.at_70.tax_on_benefit > dec!(0));
+        assert!(comparison.at_70.after_tax_annual_benefit < comparison.at_70.annual_benefit);
+    }
+
+    #[test]
+    fn test_state_taxation_of_benefits() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let analyzer = ClaimingAgeAnalyzer::new(&engine);
+
+        // Utah taxes Social Security; Florida has no income tax
+        let utah = analyzer.compare(&input(USState::Utah, dec!(60000)));
+        let florida = analyzer.compare(&input(USState::Florida, dec!(60000)));
+
+        assert!(utah.at_70.tax_on_benefit > florida.at_70.tax_on_benefit);
+    }
+}
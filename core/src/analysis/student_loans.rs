@@ -0,0 +1,323 @@
+//! Income-driven student loan repayment estimates
+//!
+//! Federal income-driven plans charge a share of discretionary income: AGI
+//! above a multiple of the HHS poverty guideline for the borrower's family
+//! size. AGI comes from the engine, so pre-tax 401(k) and HSA contributions
+//! lower the payment the same way they lower the tax return.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::rate::Rate;
+use crate::models::state::USState;
+
+/// Months in the standard 10-year repayment plan
+const STANDARD_TERM_MONTHS: u32 = 120;
+
+/// Federal income-driven repayment plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepaymentPlan {
+    /// Saving on a Valuable Education
+    Save,
+    /// Pay As You Earn
+    Paye,
+    /// Income-Based Repayment
+    Ibr,
+}
+
+impl RepaymentPlan {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RepaymentPlan::Save => "SAVE",
+            RepaymentPlan::Paye => "PAYE",
+            RepaymentPlan::Ibr => "IBR",
+        }
+    }
+
+    pub fn all() -> &'static [RepaymentPlan] {
+        &[RepaymentPlan::Save, RepaymentPlan::Paye, RepaymentPlan::Ibr]
+    }
+
+    /// Income protected from payments, as a multiple of the poverty guideline
+    fn protected_multiple(&self) -> Decimal {
+        match self {
+            RepaymentPlan::Save => dec!(2.25),
+            RepaymentPlan::Paye | RepaymentPlan::Ibr => dec!(1.5),
+        }
+    }
+
+    /// Whether payments stop at the standard 10-year payment
+    fn capped_at_standard(&self) -> bool {
+        !matches!(self, RepaymentPlan::Save)
+    }
+}
+
+/// A borrower's federal loans and household
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentLoanProfile {
+    pub balance: Decimal,
+    /// Weighted average interest rate (e.g. 0.055)
+    pub interest_rate: Decimal,
+    /// Household size, including the borrower
+    pub family_size: u32,
+    /// Share of original principal borrowed for undergraduate study; SAVE
+    /// charges 5% on this share and 10% on the rest
+    pub undergraduate_share: Decimal,
+    /// First borrowed on or after July 1, 2014, qualifying for the 10% IBR rate
+    pub new_borrower: bool,
+}
+
+/// Monthly payment under one plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdrPayment {
+    pub plan: RepaymentPlan,
+    /// Annual AGI above the plan's protected income
+    pub discretionary_income: Decimal,
+    pub monthly_payment: Decimal,
+    /// Whether the standard 10-year payment limited the payment
+    pub capped: bool,
+    pub share_of_take_home: Rate,
+}
+
+/// Payments under each plan for a year's income
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdrEstimate {
+    pub adjusted_gross_income: Decimal,
+    /// Poverty guideline for the borrower's family size and state
+    pub poverty_guideline: Decimal,
+    pub monthly_take_home: Decimal,
+    /// Standard 10-year plan payment, for comparison
+    pub standard_monthly_payment: Decimal,
+    pub payments: Vec<IdrPayment>,
+}
+
+impl IdrEstimate {
+    pub fn payment(&self, plan: RepaymentPlan) -> Option<&IdrPayment> {
+        self.payments.iter().find(|p| p.plan == plan)
+    }
+
+    /// Plan with the smallest monthly payment
+    pub fn lowest(&self) -> Option<&IdrPayment> {
+        self.payments.iter().min_by_key(|p| p.monthly_payment)
+    }
+}
+
+/// Estimates income-driven repayment from the engine's AGI
+pub struct IdrEstimator<'a> {
+    engine: &'a TaxCalculationEngine<'a>,
+}
+
+impl<'a> IdrEstimator<'a> {
+    pub fn new(engine: &'a TaxCalculationEngine<'a>) -> Self {
+        Self { engine }
+    }
+
+    /// Payments for the AGI and take-home pay of a calculation
+    ///
+    /// Married borrowers filing separately have only their own income
+    /// counted; for joint filers the input's AGI is the household's.
+    pub fn estimate(&self, input: &TaxCalculationInput, loans: &StudentLoanProfile) -> IdrEstimate {
+        let result = self.engine.calculate(input);
+        self.estimate_for_agi(
+            result.adjusted_gross_income,
+            result.income.net / dec!(12),
+            input.state,
+            loans,
+        )
+    }
+
+    /// Payments for a known AGI
+    pub fn estimate_for_agi(
+        &self,
+        agi: Decimal,
+        monthly_take_home: Decimal,
+        state: USState,
+        loans: &StudentLoanProfile,
+    ) -> IdrEstimate {
+        let poverty_guideline = self
+            .engine
+            .data_provider()
+            .poverty_guideline(state, self.engine.year())
+            .for_family_size(loans.family_size);
+        let standard = standard_monthly_payment(loans.balance, loans.interest_rate);
+
+        let payments = RepaymentPlan::all()
+            .iter()
+            .map(|&plan| {
+                let discretionary_income =
+                    (agi - poverty_guideline * plan.protected_multiple()).max(Decimal::ZERO);
+                let income_based =
+                    (discretionary_income * payment_rate(plan, loans) / dec!(12)).round_dp(2);
+                let capped = plan.capped_at_standard() && income_based > standard;
+                let monthly_payment = if capped { standard } else { income_based };
+                IdrPayment {
+                    plan,
+                    discretionary_income,
+                    monthly_payment,
+                    capped,
+                    share_of_take_home: Rate::of(monthly_payment, monthly_take_home),
+                }
+            })
+            .collect();
+
+        IdrEstimate {
+            adjusted_gross_income: agi,
+            poverty_guideline,
+            monthly_take_home,
+            standard_monthly_payment: standard,
+            payments,
+        }
+    }
+}
+
+/// Share of discretionary income a plan charges
+fn payment_rate(plan: RepaymentPlan, loans: &StudentLoanProfile) -> Decimal {
+    match plan {
+        RepaymentPlan::Save => {
+            let undergraduate = loans.undergraduate_share.clamp(Decimal::ZERO, Decimal::ONE);
+            undergraduate * dec!(0.05) + (Decimal::ONE - undergraduate) * dec!(0.10)
+        },
+        RepaymentPlan::Paye => dec!(0.10),
+        RepaymentPlan::Ibr if loans.new_borrower => dec!(0.10),
+        RepaymentPlan::Ibr => dec!(0.15),
+    }
+}
+
+/// Level monthly payment retiring a balance over 10 years
+pub fn standard_monthly_payment(balance: Decimal, annual_rate: Decimal) -> Decimal {
+    let months = Decimal::from(STANDARD_TERM_MONTHS);
+    if balance <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    if annual_rate <= Decimal::ZERO {
+        return (balance / months).round_dp(2);
+    }
+    let monthly_rate = annual_rate / dec!(12);
+    let growth =
+        (0..STANDARD_TERM_MONTHS).fold(Decimal::ONE, |acc, _| acc * (Decimal::ONE + monthly_rate));
+    (balance * monthly_rate * growth / (growth - Decimal::ONE)).round_dp(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::data::TaxDataProvider;
+    use crate::models::tax::FilingStatus;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn loans() -> StudentLoanProfile {
+        StudentLoanProfile {
+            balance: dec!(60000),
+            interest_rate: dec!(0.06),
+            family_size: 1,
+            undergraduate_share: dec!(0.5),
+            new_borrower: true,
+        }
+    }
+
+    #[test]
+    fn test_standard_payment() {
+        assert_eq!(
+            standard_monthly_payment(dec!(60000), dec!(0.06)),
+            dec!(666.12)
+        );
+        assert_eq!(standard_monthly_payment(dec!(12000), dec!(0)), dec!(100));
+        assert_eq!(standard_monthly_payment(dec!(0), dec!(0.05)), dec!(0));
+    }
+
+    #[test]
+    fn test_plan_formulas() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let estimate = IdrEstimator::new(&engine).estimate_for_agi(
+            dec!(60000),
+            dec!(4000),
+            USState::Ohio,
+            &loans(),
+        );
+
+        assert_eq!(estimate.poverty_guideline, dec!(15060));
+        let guideline = data.poverty_guideline(USState::Ohio, 2024);
+        assert_eq!(guideline.for_family_size(4), dec!(31200));
+        // SAVE: 7.5% blended rate above 225% of poverty ($33,885)
+        let save = estimate.payment(RepaymentPlan::Save).unwrap();
+        assert_eq!(save.discretionary_income, dec!(26115));
+        assert_eq!(save.monthly_payment, dec!(163.22));
+        // PAYE and new-borrower IBR: 10% above 150% of poverty ($22,590)
+        let paye = estimate.payment(RepaymentPlan::Paye).unwrap();
+        assert_eq!(paye.monthly_payment, dec!(311.75));
+        assert_eq!(
+            estimate
+                .payment(RepaymentPlan::Ibr)
+                .unwrap()
+                .monthly_payment,
+            paye.monthly_payment
+        );
+        assert_eq!(estimate.lowest().unwrap().plan, RepaymentPlan::Save);
+
+        // Old IBR borrowers pay 15%, capped at the standard payment
+        let old_borrower = StudentLoanProfile {
+            new_borrower: false,
+            balance: dec!(20000),
+            ..loans()
+        };
+        let estimate = IdrEstimator::new(&engine).estimate_for_agi(
+            dec!(60000),
+            dec!(4000),
+            USState::Ohio,
+            &old_borrower,
+        );
+        let ibr = estimate.payment(RepaymentPlan::Ibr).unwrap();
+        assert!(ibr.capped);
+        assert_eq!(ibr.monthly_payment, estimate.standard_monthly_payment);
+    }
+
+    #[test]
+    fn test_uses_the_tax_years_guideline() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2025);
+        let estimate = IdrEstimator::new(&engine).estimate_for_agi(
+            dec!(60000),
+            dec!(4000),
+            USState::Ohio,
+            &loans(),
+        );
+
+        assert_eq!(estimate.poverty_guideline, dec!(15650));
+        // PAYE: 10% above 150% of the 2025 guideline ($23,475)
+        let paye = estimate.payment(RepaymentPlan::Paye).unwrap();
+        assert_eq!(paye.discretionary_income, dec!(36525));
+    }
+
+    #[test]
+    fn test_pre_tax_contributions_lower_payments() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let estimator = IdrEstimator::new(&engine);
+        let input = TaxCalculationInput {
+            gross_income: dec!(70000),
+            filing_status: FilingStatus::Single,
+            state: USState::Alaska,
+            ..Default::default()
+        };
+        let with_401k = TaxCalculationInput {
+            traditional_401k: dec!(10000),
+            ..input.clone()
+        };
+
+        let without = estimator.estimate(&input, &loans());
+        let with = estimator.estimate(&with_401k, &loans());
+
+        assert_eq!(without.poverty_guideline, dec!(18810));
+        let save = |e: &IdrEstimate| e.payment(RepaymentPlan::Save).unwrap().monthly_payment;
+        // $10,000 less AGI at a 7.5% SAVE rate is $62.50 a month
+        assert_eq!(save(&without) - save(&with), dec!(62.50));
+        assert!(without.monthly_take_home > Decimal::ZERO);
+    }
+}
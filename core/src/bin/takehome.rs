@@ -0,0 +1,469 @@
+//! Command-line front end for the calculation engine
+//!
+//! ```text
+//! takehome calc    --gross 120000 --state CA --status single [options]
+//! takehome compare --gross 120000 --state CA --to-state TX [--to-gross N] [--to-status S]
+//! takehome sweep   --from 50000 --to 250000 --step 25000 --state NY [options]
+//! takehome audit   --file reference-2025.csv --year 2025
+//! ```
+//!
+//! Options shared by every command: `--pre-tax`, `--post-tax`, `--401k`,
+//! `--roth-401k`, `--hsa` (annual amounts) and `--year`.
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+use rust_decimal::Decimal;
+use takehome_core::data::audit::{self, AuditReport, FindingKind};
+use takehome_core::data::embedded::get_embedded_data;
+use takehome_core::{
+    FilingStatus, Rate, TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult, USState,
+};
+
+const USAGE: &str = "usage:
+  takehome calc    --gross AMOUNT --state CODE [--status STATUS] [options]
+  takehome compare --gross AMOUNT --state CODE [--status STATUS] [options]
+                   [--to-gross AMOUNT] [--to-state CODE] [--to-status STATUS]
+  takehome sweep   --from AMOUNT --to AMOUNT --step AMOUNT --state CODE [options]
+  takehome audit   --file PATH [--year YEAR]
+
+options: --pre-tax, --post-tax, --401k, --roth-401k, --hsa (annual amounts), --year
+statuses: single, married_filing_jointly, married_filing_separately,
+          head_of_household, qualifying_widower";
+
+/// Sweeps longer than this are almost always a typo in `--step`
+const MAX_SWEEP_ROWS: usize = 500;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        },
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::from(2)
+        },
+    }
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, rest) = args.split_first().ok_or("missing command")?;
+    let flags = Flags::parse(rest)?;
+    let year = flags.number("year")?.unwrap_or(2024);
+    let engine = TaxCalculationEngine::new(get_embedded_data(), year);
+
+    match command.as_str() {
+        "calc" => {
+            let input = flags.input("")?;
+            let result = engine
+                .calculate_checked(&input)
+                .map_err(|e| e.to_string())?;
+            Ok(report(year, &input, &result))
+        },
+        "compare" => {
+            let base = flags.input("")?;
+            let scenario = flags.input("to-")?;
+            Ok(comparison(&engine, &base, &scenario))
+        },
+        "sweep" => {
+            let from = flags.required_money("from")?;
+            let to = flags.required_money("to")?;
+            let step = flags.required_money("step")?;
+            if step <= Decimal::ZERO || to < from {
+                return Err("sweep needs --from <= --to and a positive --step".to_string());
+            }
+            let rows = ((to - from) / step).floor() + Decimal::ONE;
+            if rows > Decimal::from(MAX_SWEEP_ROWS) {
+                return Err(format!("sweep would print more than {MAX_SWEEP_ROWS} rows"));
+            }
+            Ok(sweep(
+                &engine,
+                &flags.input_without_gross()?,
+                from,
+                to,
+                step,
+            ))
+        },
+        "audit" => {
+            let path = flags.get("file").ok_or("missing --file")?;
+            let text = std::fs::read_to_string(path).map_err(|e| format!("--file: {path}: {e}"))?;
+            let reference = audit::parse_reference(&text).map_err(|e| format!("{path}: {e}"))?;
+            Ok(audit_report(&audit::audit(
+                get_embedded_data(),
+                year,
+                &reference,
+            )))
+        },
+        "help" | "--help" | "-h" => Ok(format!("{USAGE}\n")),
+        other => Err(format!("unknown command: {other}")),
+    }
+}
+
+/// `--name value` pairs
+struct Flags(HashMap<String, String>);
+
+impl Flags {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut flags = HashMap::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let name = arg
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument: {arg}"))?;
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("--{name} needs a value"))?;
+            flags.insert(name.to_string(), value.clone());
+        }
+        Ok(Self(flags))
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    fn money(&self, name: &str) -> Result<Option<Decimal>, String> {
+        self.get(name)
+            .map(|v| {
+                v.replace([',', '$', '_'], "")
+                    .parse::<Decimal>()
+                    .map_err(|_| format!("--{name}: not an amount: {v}"))
+            })
+            .transpose()
+    }
+
+    fn required_money(&self, name: &str) -> Result<Decimal, String> {
+        self.money(name)?.ok_or_else(|| format!("missing --{name}"))
+    }
+
+    fn number(&self, name: &str) -> Result<Option<u32>, String> {
+        self.get(name)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| format!("--{name}: not a number: {v}"))
+            })
+            .transpose()
+    }
+
+    /// Input from the shared options; `prefix` selects overrides such as
+    /// `--to-state` that fall back to the unprefixed flag
+    fn input(&self, prefix: &str) -> Result<TaxCalculationInput, String> {
+        let pick = |name: &str| {
+            let prefixed = format!("{prefix}{name}");
+            if self.0.contains_key(&prefixed) {
+                prefixed
+            } else {
+                name.to_string()
+            }
+        };
+        let gross = self.required_money(&pick("gross"))?;
+        Ok(TaxCalculationInput {
+            gross_income: gross,
+            ..self.input_from(&pick("state"), &pick("status"))?
+        })
+    }
+
+    fn input_without_gross(&self) -> Result<TaxCalculationInput, String> {
+        self.input_from("state", "status")
+    }
+
+    fn input_from(
+        &self,
+        state_flag: &str,
+        status_flag: &str,
+    ) -> Result<TaxCalculationInput, String> {
+        let state: USState = self
+            .get(state_flag)
+            .ok_or_else(|| format!("missing --{state_flag}"))?
+            .parse()
+            .map_err(|e| format!("--{state_flag}: {e}"))?;
+        let filing_status: FilingStatus = self
+            .get(status_flag)
+            .unwrap_or("single")
+            .parse()
+            .map_err(|e| format!("--{status_flag}: {e}"))?;
+        let amount = |name| self.money(name).map(Option::unwrap_or_default);
+        Ok(TaxCalculationInput {
+            filing_status,
+            state,
+            pre_tax_deductions: amount("pre-tax")?,
+            post_tax_deductions: amount("post-tax")?,
+            traditional_401k: amount("401k")?,
+            roth_401k: amount("roth-401k")?,
+            hsa_contribution: amount("hsa")?,
+            ..Default::default()
+        })
+    }
+}
+
+/// Dollars with thousands separators and cents ("$1,234.50")
+fn dollars(amount: Decimal) -> String {
+    let rounded = amount.round_dp(2);
+    let text = format!("{:.2}", rounded.abs());
+    let (whole, cents) = text.split_once('.').unwrap_or((&text, "00"));
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if rounded.is_sign_negative() && !rounded.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{sign}${grouped}.{cents}")
+}
+
+fn percent(rate: Rate) -> String {
+    format!("{:.2}%", rate.as_percent())
+}
+
+fn report(year: u32, input: &TaxCalculationInput, result: &TaxCalculationResult) -> String {
+    let result = result.rounded_to_cents();
+    let breakdown = &result.tax_breakdown;
+    let mut lines = vec![
+        format!(
+            "{year} tax year, {}, {}",
+            input.state.code(),
+            input.filing_status.display_name()
+        ),
+        String::new(),
+    ];
+    let mut line = |label: &str, amount: Decimal| {
+        lines.push(format!("{label:<28}{:>16}", dollars(amount)));
+    };
+
+    line("Gross income", result.income.gross);
+    line("Federal income tax", breakdown.federal.tax);
+    line("State income tax", breakdown.state.income_tax);
+    if !breakdown.state.local_tax.is_zero() {
+        line("Local income tax", breakdown.state.local_tax);
+    }
+    for program in &breakdown.state.payroll_programs {
+        line(&format!("State {}", program.name), program.amount);
+    }
+    if let Some(work_state) = &breakdown.work_state {
+        line(
+            &format!("{} nonresident tax", work_state.state_code),
+            work_state.total_tax,
+        );
+    }
+    line("Social Security", breakdown.fica.social_security);
+    line(
+        "Medicare",
+        breakdown.fica.medicare + breakdown.fica.additional_medicare,
+    );
+    line("Total taxes", breakdown.total_taxes);
+    if !result.garnishments.total.is_zero() {
+        line("Garnishments", result.garnishments.total);
+    }
+    line("Take-home (annual)", result.income.net);
+    line("Take-home (monthly)", result.income.timeframes.monthly);
+    line("Take-home (bi-weekly)", result.income.timeframes.bi_weekly);
+
+    lines.push(format!(
+        "{:<28}{:>16}",
+        "Effective tax rate",
+        percent(result.effective_rates.total)
+    ));
+    lines.push(format!(
+        "{:<28}{:>16}",
+        "Federal marginal rate",
+        percent(Rate::from_fraction(breakdown.federal.marginal_rate))
+    ));
+    for warning in &result.warnings {
+        lines.push(format!("note: {}", warning.message()));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn comparison(
+    engine: &TaxCalculationEngine,
+    base: &TaxCalculationInput,
+    scenario: &TaxCalculationInput,
+) -> String {
+    let comparison = engine.compare_scenarios(base, scenario);
+    let label = |input: &TaxCalculationInput| {
+        format!("{} {}", dollars(input.gross_income), input.state.code())
+    };
+    let rows = [
+        ("Federal income tax", &comparison.federal),
+        ("State and local tax", &comparison.state),
+        ("State payroll programs", &comparison.sdi),
+        ("FICA", &comparison.fica),
+    ];
+
+    let mut lines = vec![format!(
+        "{:<24}{:>16}{:>16}{:>16}",
+        "",
+        label(base),
+        label(scenario),
+        "Difference"
+    )];
+    for (name, delta) in rows {
+        lines.push(format!(
+            "{name:<24}{:>16}{:>16}{:>16}",
+            dollars(delta.base),
+            dollars(delta.scenario),
+            dollars(delta.difference)
+        ));
+    }
+    lines.push(format!(
+        "{:<24}{:>16}{:>16}{:>16}",
+        "Take-home (annual)",
+        dollars(comparison.base.income.net),
+        dollars(comparison.scenario.income.net),
+        dollars(comparison.net_difference)
+    ));
+    lines.push(format!(
+        "{:<24}{:>16}{:>16}{:>16}",
+        "Take-home (monthly)",
+        dollars(comparison.base.income.timeframes.monthly),
+        dollars(comparison.scenario.income.timeframes.monthly),
+        dollars(comparison.monthly_difference)
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn sweep(
+    engine: &TaxCalculationEngine,
+    template: &TaxCalculationInput,
+    from: Decimal,
+    to: Decimal,
+    step: Decimal,
+) -> String {
+    let mut grosses = Vec::new();
+    let mut gross = from;
+    while gross <= to {
+        grosses.push(gross);
+        gross += step;
+    }
+    let results = engine.calculate_iter(grosses.iter().map(|&gross_income| TaxCalculationInput {
+        gross_income,
+        ..template.clone()
+    }));
+
+    let mut lines = vec![format!(
+        "{:>14}{:>14}{:>14}{:>14}{:>14}{:>10}",
+        "Gross", "Federal", "State", "FICA", "Take-home", "Rate"
+    )];
+    for result in results {
+        let breakdown = &result.tax_breakdown;
+        let state_total = breakdown.state.total_tax
+            + breakdown
+                .work_state
+                .as_ref()
+                .map(|w| w.total_tax)
+                .unwrap_or_default();
+        lines.push(format!(
+            "{:>14}{:>14}{:>14}{:>14}{:>14}{:>10}",
+            dollars(result.income.gross),
+            dollars(breakdown.federal.tax),
+            dollars(state_total),
+            dollars(breakdown.fica.total),
+            dollars(result.income.net),
+            percent(result.effective_rates.total)
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn audit_report(report: &AuditReport) -> String {
+    let mut lines: Vec<String> = report
+        .findings
+        .iter()
+        .map(|finding| {
+            let actual = match finding.kind {
+                FindingKind::Mismatch => finding.actual.unwrap_or_default().to_string(),
+                FindingKind::Missing => "missing".to_string(),
+                FindingKind::UnknownKey => "unknown key".to_string(),
+            };
+            format!("{:<44}{:>14}{:>14}", finding.key, finding.expected, actual)
+        })
+        .collect();
+    if !lines.is_empty() {
+        lines.insert(
+            0,
+            format!("{:<44}{:>14}{:>14}", "Key", "Reference", "Embedded"),
+        );
+    }
+    lines.push(format!(
+        "{} tax year: {} of {} reference figures differ",
+        report.year,
+        report.findings.len(),
+        report.checked
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_dollars() {
+        assert_eq!(dollars(Decimal::new(123456789, 2)), "$1,234,567.89");
+        assert_eq!(dollars(Decimal::new(-5, 1)), "-$0.50");
+        assert_eq!(dollars(Decimal::ZERO), "$0.00");
+    }
+
+    #[test]
+    fn test_calc_report() {
+        let output = run(&args("calc --gross 120000 --state CA --status single")).unwrap();
+
+        assert!(output.starts_with("2024 tax year, CA, Single"));
+        assert!(output.contains("Gross income"));
+        assert!(output.contains("$120,000.00"));
+        assert!(output.contains("State SDI"));
+        assert!(output.contains("Take-home (annual)"));
+    }
+
+    #[test]
+    fn test_compare_and_sweep() {
+        let output = run(&args("compare --gross 120000 --state CA --to-state TX")).unwrap();
+        assert!(output.contains("$120,000.00 CA"));
+        assert!(output.contains("$120,000.00 TX"));
+
+        let output = run(&args(
+            "sweep --from 50000 --to 100000 --step 25000 --state WA",
+        ))
+        .unwrap();
+        // Header plus three rows
+        assert_eq!(output.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_audit() {
+        let path = std::env::temp_dir().join("takehome-audit-reference.csv");
+        std::fs::write(&path, "fica.wage_base,168600\nmileage.business,0.70\n").unwrap();
+        let output = run(&args(&format!("audit --file {}", path.display()))).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(output.contains("mileage.business"));
+        assert!(output.contains("0.67"));
+        assert!(output.contains("1 of 2 reference figures differ"));
+        assert!(run(&args("audit --file /nonexistent/reference.csv")).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(run(&[]).is_err());
+        assert!(run(&args("calc --state CA")).is_err());
+        assert!(run(&args("calc --gross abc --state CA")).is_err());
+        assert!(run(&args("calc --gross 1 --state ZZ")).is_err());
+        assert!(run(&args("sweep --from 1 --to 2 --step 0 --state CA")).is_err());
+        assert!(run(&args("launch")).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Long-term capital gains and qualified dividends (§1(h))
+//!
+//! Preferential income is taxed at 0%, 15%, or 20%, with the brackets
+//! measured on total taxable income: ordinary income fills the bottom and
+//! the gains and dividends stack on top of it, so the same gain can fall
+//! in the 0% bracket for one taxpayer and at 15% for a higher earner.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::tax::FilingStatus;
+
+const FIFTEEN_PERCENT: Decimal = dec!(0.15);
+const TWENTY_PERCENT: Decimal = dec!(0.20);
+
+/// Preferential-rate income split across the 0%, 15%, and 20% brackets
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreferentialTaxResult {
+    /// Long-term gains and qualified dividends within taxable income
+    pub preferential_income: Decimal,
+    pub taxed_at_zero: Decimal,
+    pub taxed_at_fifteen: Decimal,
+    pub taxed_at_twenty: Decimal,
+    pub tax: Decimal,
+}
+
+/// Capital gains rate calculator
+pub struct CapitalGainsTaxCalculator;
+
+impl CapitalGainsTaxCalculator {
+    /// Taxable income where the 15% and 20% rates start
+    pub fn thresholds(filing_status: FilingStatus, year: u32) -> (Decimal, Decimal) {
+        let (single, joint, separate, head_of_household) = match year {
+            ..=2023 => (
+                (dec!(44625), dec!(492300)),
+                (dec!(89250), dec!(553850)),
+                (dec!(44625), dec!(276900)),
+                (dec!(59750), dec!(523050)),
+            ),
+            2024 => (
+                (dec!(47025), dec!(518900)),
+                (dec!(94050), dec!(583750)),
+                (dec!(47025), dec!(291850)),
+                (dec!(63000), dec!(551350)),
+            ),
+            _ => (
+                (dec!(48350), dec!(533400)),
+                (dec!(96700), dec!(600050)),
+                (dec!(48350), dec!(300000)),
+                (dec!(64750), dec!(566700)),
+            ),
+        };
+        match filing_status {
+            FilingStatus::Single => single,
+            FilingStatus::MarriedFilingJointly | FilingStatus::QualifyingWidower => joint,
+            FilingStatus::MarriedFilingSeparately => separate,
+            FilingStatus::HeadOfHousehold => head_of_household,
+        }
+    }
+
+    /// Tax on the preferential part of `taxable_income`, stacked on top of
+    /// the ordinary part
+    pub fn calculate(
+        taxable_income: Decimal,
+        preferential_income: Decimal,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> PreferentialTaxResult {
+        let taxable_income = taxable_income.max(Decimal::ZERO);
+        let preferential_income = preferential_income.max(Decimal::ZERO).min(taxable_income);
+        let ordinary = taxable_income - preferential_income;
+        let (fifteen_start, twenty_start) = Self::thresholds(filing_status, year);
+
+        // Portion of the stacked range [ordinary, taxable_income) below `ceiling`
+        let below = |ceiling: Decimal| {
+            (taxable_income.min(ceiling) - ordinary)
+                .max(Decimal::ZERO)
+                .min(preferential_income)
+        };
+        let taxed_at_zero = below(fifteen_start);
+        let taxed_at_fifteen = below(twenty_start) - taxed_at_zero;
+        let taxed_at_twenty = preferential_income - taxed_at_zero - taxed_at_fifteen;
+
+        PreferentialTaxResult {
+            preferential_income,
+            taxed_at_zero,
+            taxed_at_fifteen,
+            taxed_at_twenty,
+            tax: taxed_at_fifteen * FIFTEEN_PERCENT + taxed_at_twenty * TWENTY_PERCENT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gains_stack_on_ordinary_income() {
+        let calc = |taxable, preferential| {
+            CapitalGainsTaxCalculator::calculate(taxable, preferential, FilingStatus::Single, 2024)
+        };
+
+        // All of it fits under the $47,025 0% threshold
+        let low = calc(dec!(40000), dec!(10000));
+        assert_eq!(low.taxed_at_zero, dec!(10000));
+        assert_eq!(low.tax, dec!(0));
+
+        // $30,000 of ordinary income leaves $17,025 at 0%
+        let straddle = calc(dec!(50000), dec!(20000));
+        assert_eq!(straddle.taxed_at_zero, dec!(17025));
+        assert_eq!(straddle.taxed_at_fifteen, dec!(2975));
+        assert_eq!(straddle.tax, dec!(446.25));
+
+        // Above $518,900 the rest is at 20%
+        let high = calc(dec!(600000), dec!(100000));
+        assert_eq!(high.taxed_at_zero, dec!(0));
+        assert_eq!(high.taxed_at_fifteen, dec!(18900));
+        assert_eq!(high.taxed_at_twenty, dec!(81100));
+
+        // Preferential income can't exceed taxable income
+        assert_eq!(
+            calc(dec!(5000), dec!(20000)).preferential_income,
+            dec!(5000)
+        );
+    }
+}
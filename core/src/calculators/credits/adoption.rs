@@ -0,0 +1,181 @@
+//! Adoption credit (§23)
+//!
+//! Qualified adoption expenses are credited dollar for dollar up to a cap
+//! per child; a special needs adoption gets the full cap whatever was spent.
+//! The credit phases out over a $40,000 band of modified AGI, isn't
+//! available filing separately, and unused credit carries forward for five
+//! years.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::tax::FilingStatus;
+
+/// Width of the MAGI phase-out band
+const PHASE_OUT_RANGE: Decimal = dec!(40000);
+
+/// One child's adoption
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Adoption {
+    /// Qualified expenses paid for this child
+    pub qualified_expenses: Decimal,
+    /// State-determined special needs adoption, credited at the full cap
+    #[serde(default)]
+    pub special_needs: bool,
+}
+
+/// Adoptions finalized this year and credit carried in
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdoptionExpenses {
+    pub adoptions: Vec<Adoption>,
+    /// Unused adoption credit from the prior five years
+    #[serde(default)]
+    pub carryforward: Decimal,
+}
+
+/// Adoption credit applied against a year's tax
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdoptionCreditResult {
+    /// This year's credit after the per-child cap and phase-out
+    pub allowed: Decimal,
+    /// Credit removed by the MAGI phase-out
+    pub phase_out_reduction: Decimal,
+    /// Credit used against income tax
+    pub credit: Decimal,
+    /// Credit left for next year
+    pub carryforward: Decimal,
+}
+
+/// Adoption credit calculator
+pub struct AdoptionCreditCalculator;
+
+impl AdoptionCreditCalculator {
+    /// Per-child cap and the MAGI where the phase-out starts
+    pub fn limits(year: u32) -> (Decimal, Decimal) {
+        match year {
+            ..=2023 => (dec!(15950), dec!(239230)),
+            2024 => (dec!(16810), dec!(252150)),
+            _ => (dec!(17280), dec!(259190)),
+        }
+    }
+
+    /// Credit for this year's adoptions, plus any carryforward, applied to
+    /// `income_tax`
+    pub fn calculate(
+        expenses: &AdoptionExpenses,
+        filing_status: FilingStatus,
+        magi: Decimal,
+        year: u32,
+        income_tax: Decimal,
+    ) -> AdoptionCreditResult {
+        let (cap, phase_out_start) = Self::limits(year);
+        let before_phase_out: Decimal = if filing_status == FilingStatus::MarriedFilingSeparately {
+            Decimal::ZERO
+        } else {
+            expenses
+                .adoptions
+                .iter()
+                .map(|adoption| {
+                    if adoption.special_needs {
+                        cap
+                    } else {
+                        adoption.qualified_expenses.clamp(Decimal::ZERO, cap)
+                    }
+                })
+                .sum()
+        };
+        let phase_out_share =
+            ((magi - phase_out_start) / PHASE_OUT_RANGE).clamp(Decimal::ZERO, Decimal::ONE);
+        let phase_out_reduction = (before_phase_out * phase_out_share).round_dp(2);
+        let allowed = before_phase_out - phase_out_reduction;
+
+        let available = allowed + expenses.carryforward.max(Decimal::ZERO);
+        let credit = available.min(income_tax.max(Decimal::ZERO));
+        AdoptionCreditResult {
+            allowed,
+            phase_out_reduction,
+            credit,
+            carryforward: available - credit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expenses(adoptions: Vec<Adoption>) -> AdoptionExpenses {
+        AdoptionExpenses {
+            adoptions,
+            carryforward: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_per_child_cap_and_special_needs() {
+        let claims = expenses(vec![
+            Adoption {
+                qualified_expenses: dec!(25000),
+                special_needs: false,
+            },
+            Adoption {
+                qualified_expenses: dec!(2000),
+                special_needs: true,
+            },
+        ]);
+
+        let result = AdoptionCreditCalculator::calculate(
+            &claims,
+            FilingStatus::MarriedFilingJointly,
+            dec!(150000),
+            2024,
+            dec!(50000),
+        );
+        assert_eq!(result.allowed, dec!(33620));
+        assert_eq!(result.credit, dec!(33620));
+
+        let separate = AdoptionCreditCalculator::calculate(
+            &claims,
+            FilingStatus::MarriedFilingSeparately,
+            dec!(150000),
+            2024,
+            dec!(50000),
+        );
+        assert_eq!(separate.allowed, dec!(0));
+    }
+
+    #[test]
+    fn test_phase_out_and_carryforward() {
+        let claims = AdoptionExpenses {
+            carryforward: dec!(1000),
+            ..expenses(vec![Adoption {
+                qualified_expenses: dec!(20000),
+                special_needs: false,
+            }])
+        };
+
+        // A quarter of the way through the $40,000 phase-out band
+        let result = AdoptionCreditCalculator::calculate(
+            &claims,
+            FilingStatus::Single,
+            dec!(262150),
+            2024,
+            dec!(8000),
+        );
+        assert_eq!(result.phase_out_reduction, dec!(4202.50));
+        assert_eq!(result.allowed, dec!(12607.50));
+        assert_eq!(result.credit, dec!(8000));
+        assert_eq!(result.carryforward, dec!(5607.50));
+
+        let above = AdoptionCreditCalculator::calculate(
+            &claims,
+            FilingStatus::Single,
+            dec!(300000),
+            2024,
+            dec!(8000),
+        );
+        assert_eq!(above.allowed, dec!(0));
+        assert_eq!(above.credit, dec!(1000));
+    }
+}
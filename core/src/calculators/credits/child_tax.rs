@@ -0,0 +1,202 @@
+//! Child Tax Credit and Credit for Other Dependents (§24)
+//!
+//! Each child under 17 is worth the full child credit and every other
+//! dependent $500. Together they phase out by $50 for each $1,000 (or part)
+//! of modified AGI over $400,000 filing jointly or $200,000 otherwise. What
+//! income tax can't absorb comes back as the refundable additional child
+//! tax credit, up to a per-child cap and 15% of earnings over $2,500.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::dependent::Dependent;
+use crate::models::tax::FilingStatus;
+
+/// Credit for each dependent who isn't a child under 17
+const OTHER_DEPENDENT_CREDIT: Decimal = dec!(500);
+
+/// Reduction for each $1,000 (or part) of MAGI over the threshold
+const PHASE_OUT_STEP: Decimal = dec!(1000);
+const PHASE_OUT_PER_STEP: Decimal = dec!(50);
+
+/// Earnings above this count toward the refundable credit
+const REFUNDABLE_EARNINGS_FLOOR: Decimal = dec!(2500);
+const REFUNDABLE_EARNINGS_RATE: Decimal = dec!(0.15);
+
+/// Child and other-dependent credits for a year
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChildTaxCreditResult {
+    /// Children under 17
+    pub qualifying_children: u32,
+    pub other_dependents: u32,
+    /// Credit removed by the MAGI phase-out
+    pub phase_out_reduction: Decimal,
+    /// Child Tax Credit used against income tax
+    pub child_tax_credit: Decimal,
+    /// Credit for Other Dependents used against income tax
+    pub other_dependents_credit: Decimal,
+    /// Refundable additional child tax credit
+    pub additional_child_tax_credit: Decimal,
+}
+
+impl ChildTaxCreditResult {
+    /// Credit used against income tax
+    pub fn nonrefundable(&self) -> Decimal {
+        self.child_tax_credit + self.other_dependents_credit
+    }
+}
+
+/// Child Tax Credit calculator
+pub struct ChildTaxCreditCalculator;
+
+impl ChildTaxCreditCalculator {
+    /// Credit per child and refundable cap per child
+    pub fn limits(year: u32) -> (Decimal, Decimal) {
+        match year {
+            ..=2023 => (dec!(2000), dec!(1600)),
+            2024 => (dec!(2000), dec!(1700)),
+            _ => (dec!(2200), dec!(1700)),
+        }
+    }
+
+    /// MAGI where the phase-out starts
+    pub fn phase_out_threshold(filing_status: FilingStatus) -> Decimal {
+        match filing_status {
+            FilingStatus::MarriedFilingJointly => dec!(400000),
+            _ => dec!(200000),
+        }
+    }
+
+    /// Credits for `dependents` applied to `income_tax`, with the rest
+    /// refunded as far as earnings allow
+    pub fn calculate(
+        dependents: &[Dependent],
+        filing_status: FilingStatus,
+        magi: Decimal,
+        earned_income: Decimal,
+        income_tax: Decimal,
+        year: u32,
+    ) -> ChildTaxCreditResult {
+        let (per_child, refundable_per_child) = Self::limits(year);
+        let qualifying_children = dependents
+            .iter()
+            .filter(|d| d.qualifies_for_child_tax_credit())
+            .count() as u32;
+        let other_dependents = dependents.len() as u32 - qualifying_children;
+
+        let excess = (magi - Self::phase_out_threshold(filing_status)).max(Decimal::ZERO);
+        let phase_out = (excess / PHASE_OUT_STEP).ceil() * PHASE_OUT_PER_STEP;
+
+        // The phase-out comes out of the child credit first
+        let children = per_child * Decimal::from(qualifying_children);
+        let others = OTHER_DEPENDENT_CREDIT * Decimal::from(other_dependents);
+        let child_allowed = (children - phase_out).max(Decimal::ZERO);
+        let others_allowed =
+            (others - (phase_out - children).max(Decimal::ZERO)).max(Decimal::ZERO);
+
+        let mut remaining = income_tax.max(Decimal::ZERO);
+        let child_tax_credit = child_allowed.min(remaining);
+        remaining -= child_tax_credit;
+        let other_dependents_credit = others_allowed.min(remaining);
+
+        let unused = child_allowed + others_allowed - child_tax_credit - other_dependents_credit;
+        let additional_child_tax_credit = unused
+            .min(refundable_per_child * Decimal::from(qualifying_children))
+            .min(
+                ((earned_income - REFUNDABLE_EARNINGS_FLOOR) * REFUNDABLE_EARNINGS_RATE)
+                    .max(Decimal::ZERO),
+            )
+            .round_dp(2);
+
+        ChildTaxCreditResult {
+            qualifying_children,
+            other_dependents,
+            phase_out_reduction: (children + others) - (child_allowed + others_allowed),
+            child_tax_credit,
+            other_dependents_credit,
+            additional_child_tax_credit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dependent::DependentRelationship;
+
+    #[test]
+    fn test_credit_limited_by_tax_then_refunded() {
+        let dependents = [
+            Dependent::child(4),
+            Dependent::child(10),
+            Dependent {
+                relationship: DependentRelationship::Parent,
+                age: 80,
+                ..Default::default()
+            },
+        ];
+
+        // Plenty of tax: $4,000 for the children and $500 for the parent
+        let full = ChildTaxCreditCalculator::calculate(
+            &dependents,
+            FilingStatus::MarriedFilingJointly,
+            dec!(150000),
+            dec!(150000),
+            dec!(15000),
+            2024,
+        );
+        assert_eq!(full.child_tax_credit, dec!(4000));
+        assert_eq!(full.other_dependents_credit, dec!(500));
+        assert_eq!(full.additional_child_tax_credit, dec!(0));
+
+        // $1,000 of tax: the rest is refundable up to 15% of earnings over $2,500
+        let low = ChildTaxCreditCalculator::calculate(
+            &dependents,
+            FilingStatus::HeadOfHousehold,
+            dec!(30000),
+            dec!(30000),
+            dec!(1000),
+            2024,
+        );
+        assert_eq!(low.nonrefundable(), dec!(1000));
+        assert_eq!(low.other_dependents_credit, dec!(0));
+        assert_eq!(low.additional_child_tax_credit, dec!(3400));
+        let lower = ChildTaxCreditCalculator::calculate(
+            &dependents,
+            FilingStatus::HeadOfHousehold,
+            dec!(12500),
+            dec!(12500),
+            dec!(0),
+            2024,
+        );
+        assert_eq!(lower.additional_child_tax_credit, dec!(1500));
+    }
+
+    #[test]
+    fn test_phase_out() {
+        let dependents = [Dependent::child(5)];
+        // $10,001 over: 11 steps of $50
+        let result = ChildTaxCreditCalculator::calculate(
+            &dependents,
+            FilingStatus::Single,
+            dec!(210001),
+            dec!(210001),
+            dec!(40000),
+            2024,
+        );
+        assert_eq!(result.phase_out_reduction, dec!(550));
+        assert_eq!(result.child_tax_credit, dec!(1450));
+
+        let gone = ChildTaxCreditCalculator::calculate(
+            &dependents,
+            FilingStatus::Single,
+            dec!(250000),
+            dec!(250000),
+            dec!(50000),
+            2024,
+        );
+        assert_eq!(gone.nonrefundable(), dec!(0));
+        assert_eq!(ChildTaxCreditCalculator::limits(2025).0, dec!(2200));
+    }
+}
@@ -0,0 +1,226 @@
+//! Clean vehicle credit (§30D new, §25E used)
+//!
+//! The credit is all-or-nothing: it's allowed when modified AGI for either
+//! the purchase year or the year before is at or under the limit for the
+//! filing status, and lost entirely a dollar over. It's nonrefundable and
+//! doesn't carry forward for personal-use vehicles.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::tax::FilingStatus;
+
+/// Maximum credit for a new vehicle meeting both sourcing requirements
+const NEW_VEHICLE_MAX_CREDIT: Decimal = dec!(7500);
+
+/// Used vehicle credit: share of the sale price, up to a cap
+const USED_VEHICLE_RATE: Decimal = dec!(0.30);
+const USED_VEHICLE_MAX_CREDIT: Decimal = dec!(4000);
+
+/// Used vehicles must sell for no more than this
+const USED_VEHICLE_PRICE_CAP: Decimal = dec!(25000);
+
+/// Income within this share below the limit is flagged as near the cutoff
+const NEAR_LIMIT_MARGIN: Decimal = dec!(0.10);
+
+/// New or previously owned vehicle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VehicleCondition {
+    New,
+    Used,
+}
+
+/// A clean vehicle placed in service this year
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CleanVehicle {
+    pub condition: VehicleCondition,
+    pub sale_price: Decimal,
+    /// Credit the new vehicle qualifies for under the battery sourcing
+    /// rules ($3,750 or $7,500); ignored for used vehicles
+    #[serde(default)]
+    pub vehicle_credit: Decimal,
+    /// Prior year modified AGI, which qualifies the buyer if it's under the
+    /// limit even when this year's isn't
+    #[serde(default)]
+    pub prior_year_magi: Option<Decimal>,
+}
+
+/// Whether the buyer's income qualifies
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CleanVehicleEligibility {
+    pub magi_limit: Decimal,
+    /// The lower of this year's and the prior year's MAGI
+    pub qualifying_magi: Decimal,
+    pub eligible: bool,
+    /// Room left under the limit; negative when over it
+    pub headroom: Decimal,
+    /// Qualifying MAGI within 10% under the limit, where a raise or bonus
+    /// could cost the whole credit
+    pub near_limit: bool,
+    /// Credit before the tax limit, zero when ineligible
+    pub credit_available: Decimal,
+}
+
+/// Clean vehicle credit applied to a year's tax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CleanVehicleCredit {
+    pub eligibility: CleanVehicleEligibility,
+    /// Credit used against income tax
+    pub credit: Decimal,
+}
+
+/// Clean vehicle credit calculator
+pub struct CleanVehicleCreditCalculator;
+
+impl CleanVehicleCreditCalculator {
+    /// MAGI limit for a filing status and vehicle condition
+    pub fn magi_limit(filing_status: FilingStatus, condition: VehicleCondition) -> Decimal {
+        let new_vehicle_limit = match filing_status {
+            FilingStatus::MarriedFilingJointly | FilingStatus::QualifyingWidower => dec!(300000),
+            FilingStatus::HeadOfHousehold => dec!(225000),
+            FilingStatus::Single | FilingStatus::MarriedFilingSeparately => dec!(150000),
+        };
+        match condition {
+            VehicleCondition::New => new_vehicle_limit,
+            VehicleCondition::Used => new_vehicle_limit / dec!(2),
+        }
+    }
+
+    /// Check income eligibility for a purchase
+    pub fn eligibility(
+        vehicle: &CleanVehicle,
+        filing_status: FilingStatus,
+        magi: Decimal,
+    ) -> CleanVehicleEligibility {
+        let magi_limit = Self::magi_limit(filing_status, vehicle.condition);
+        let qualifying_magi = vehicle
+            .prior_year_magi
+            .map_or(magi, |prior| prior.min(magi));
+        let headroom = magi_limit - qualifying_magi;
+        let eligible = headroom >= Decimal::ZERO;
+
+        CleanVehicleEligibility {
+            magi_limit,
+            qualifying_magi,
+            eligible,
+            headroom,
+            near_limit: eligible && headroom <= magi_limit * NEAR_LIMIT_MARGIN,
+            credit_available: if eligible {
+                Self::vehicle_credit(vehicle)
+            } else {
+                Decimal::ZERO
+            },
+        }
+    }
+
+    /// Check eligibility and apply the credit to `income_tax`
+    pub fn calculate(
+        vehicle: &CleanVehicle,
+        filing_status: FilingStatus,
+        magi: Decimal,
+        income_tax: Decimal,
+    ) -> CleanVehicleCredit {
+        let eligibility = Self::eligibility(vehicle, filing_status, magi);
+        let credit = eligibility
+            .credit_available
+            .min(income_tax.max(Decimal::ZERO));
+        CleanVehicleCredit {
+            eligibility,
+            credit,
+        }
+    }
+
+    /// Credit the vehicle itself qualifies for, before income limits
+    pub fn vehicle_credit(vehicle: &CleanVehicle) -> Decimal {
+        match vehicle.condition {
+            VehicleCondition::New => vehicle
+                .vehicle_credit
+                .clamp(Decimal::ZERO, NEW_VEHICLE_MAX_CREDIT),
+            VehicleCondition::Used if vehicle.sale_price > USED_VEHICLE_PRICE_CAP => Decimal::ZERO,
+            VehicleCondition::Used => (vehicle.sale_price.max(Decimal::ZERO) * USED_VEHICLE_RATE)
+                .min(USED_VEHICLE_MAX_CREDIT),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_vehicle() -> CleanVehicle {
+        CleanVehicle {
+            condition: VehicleCondition::New,
+            sale_price: dec!(45000),
+            vehicle_credit: dec!(7500),
+            prior_year_magi: None,
+        }
+    }
+
+    #[test]
+    fn test_income_limit_is_a_cliff() {
+        let single = FilingStatus::Single;
+
+        let under = CleanVehicleCreditCalculator::eligibility(&new_vehicle(), single, dec!(150000));
+        assert!(under.eligible);
+        assert!(under.near_limit);
+        assert_eq!(under.credit_available, dec!(7500));
+
+        let over = CleanVehicleCreditCalculator::eligibility(&new_vehicle(), single, dec!(150001));
+        assert!(!over.eligible);
+        assert_eq!(over.headroom, dec!(-1));
+        assert_eq!(over.credit_available, dec!(0));
+
+        // Nonrefundable: limited to the year's income tax
+        let limited = CleanVehicleCreditCalculator::calculate(
+            &new_vehicle(),
+            single,
+            dec!(60000),
+            dec!(5000),
+        );
+        assert_eq!(limited.credit, dec!(5000));
+
+        let clear = CleanVehicleCreditCalculator::eligibility(
+            &new_vehicle(),
+            FilingStatus::MarriedFilingJointly,
+            dec!(150000),
+        );
+        assert!(clear.eligible);
+        assert!(!clear.near_limit);
+    }
+
+    #[test]
+    fn test_prior_year_income_and_used_vehicles() {
+        let vehicle = CleanVehicle {
+            prior_year_magi: Some(dec!(140000)),
+            ..new_vehicle()
+        };
+        let eligibility =
+            CleanVehicleCreditCalculator::eligibility(&vehicle, FilingStatus::Single, dec!(180000));
+        assert!(eligibility.eligible);
+        assert_eq!(eligibility.qualifying_magi, dec!(140000));
+
+        let used = CleanVehicle {
+            condition: VehicleCondition::Used,
+            sale_price: dec!(12000),
+            vehicle_credit: Decimal::ZERO,
+            prior_year_magi: None,
+        };
+        assert_eq!(
+            CleanVehicleCreditCalculator::magi_limit(FilingStatus::HeadOfHousehold, used.condition),
+            dec!(112500)
+        );
+        assert_eq!(
+            CleanVehicleCreditCalculator::vehicle_credit(&used),
+            dec!(3600)
+        );
+        let pricey = CleanVehicle {
+            sale_price: dec!(26000),
+            ..used
+        };
+        assert_eq!(
+            CleanVehicleCreditCalculator::vehicle_credit(&pricey),
+            dec!(0)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Earned income tax credit (§32)
+//!
+//! The credit phases in with earnings, plateaus at a maximum set by the
+//! number of qualifying children, then phases out on the greater of
+//! earnings and AGI. It's fully refundable, unavailable filing separately
+//! or with investment income over the year's limit, and workers without a
+//! qualifying child must be 25 through 64.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::models::tax::FilingStatus;
+
+/// Ages a worker without a qualifying child must be within
+const CHILDLESS_MIN_AGE: u32 = 25;
+const CHILDLESS_MAX_AGE: u32 = 64;
+
+/// Credit parameters for one count of qualifying children
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EitcSchedule {
+    pub phase_in_rate: Decimal,
+    pub maximum: Decimal,
+    pub phase_out_rate: Decimal,
+    pub phase_out_start: Decimal,
+    pub phase_out_start_joint: Decimal,
+}
+
+const fn schedule(
+    phase_in_rate: Decimal,
+    maximum: Decimal,
+    phase_out_rate: Decimal,
+    phase_out_start: Decimal,
+    phase_out_start_joint: Decimal,
+) -> EitcSchedule {
+    EitcSchedule {
+        phase_in_rate,
+        maximum,
+        phase_out_rate,
+        phase_out_start,
+        phase_out_start_joint,
+    }
+}
+
+/// Schedules for 0, 1, 2, and 3+ children
+const EITC_2023: [EitcSchedule; 4] = [
+    schedule(
+        dec!(0.0765),
+        dec!(600),
+        dec!(0.0765),
+        dec!(9800),
+        dec!(16370),
+    ),
+    schedule(
+        dec!(0.34),
+        dec!(3995),
+        dec!(0.1598),
+        dec!(21560),
+        dec!(28120),
+    ),
+    schedule(
+        dec!(0.40),
+        dec!(6604),
+        dec!(0.2106),
+        dec!(21560),
+        dec!(28120),
+    ),
+    schedule(
+        dec!(0.45),
+        dec!(7430),
+        dec!(0.2106),
+        dec!(21560),
+        dec!(28120),
+    ),
+];
+const EITC_2024: [EitcSchedule; 4] = [
+    schedule(
+        dec!(0.0765),
+        dec!(632),
+        dec!(0.0765),
+        dec!(10330),
+        dec!(17250),
+    ),
+    schedule(
+        dec!(0.34),
+        dec!(4213),
+        dec!(0.1598),
+        dec!(22720),
+        dec!(29640),
+    ),
+    schedule(
+        dec!(0.40),
+        dec!(6960),
+        dec!(0.2106),
+        dec!(22720),
+        dec!(29640),
+    ),
+    schedule(
+        dec!(0.45),
+        dec!(7830),
+        dec!(0.2106),
+        dec!(22720),
+        dec!(29640),
+    ),
+];
+const EITC_2025: [EitcSchedule; 4] = [
+    schedule(
+        dec!(0.0765),
+        dec!(649),
+        dec!(0.0765),
+        dec!(10620),
+        dec!(17730),
+    ),
+    schedule(
+        dec!(0.34),
+        dec!(4328),
+        dec!(0.1598),
+        dec!(23350),
+        dec!(30470),
+    ),
+    schedule(
+        dec!(0.40),
+        dec!(7152),
+        dec!(0.2106),
+        dec!(23350),
+        dec!(30470),
+    ),
+    schedule(
+        dec!(0.45),
+        dec!(8046),
+        dec!(0.2106),
+        dec!(23350),
+        dec!(30470),
+    ),
+];
+
+/// Earned income credit calculator
+pub struct EarnedIncomeCreditCalculator;
+
+impl EarnedIncomeCreditCalculator {
+    /// Schedule for a count of qualifying children
+    pub fn schedule(qualifying_children: u32, year: u32) -> EitcSchedule {
+        let schedules = match year {
+            ..=2023 => &EITC_2023,
+            2024 => &EITC_2024,
+            _ => &EITC_2025,
+        };
+        schedules[(qualifying_children as usize).min(schedules.len() - 1)]
+    }
+
+    /// Investment income above which there's no credit
+    pub fn investment_income_limit(year: u32) -> Decimal {
+        match year {
+            ..=2023 => dec!(11000),
+            2024 => dec!(11600),
+            _ => dec!(11950),
+        }
+    }
+
+    /// Credit phased in on earnings and out on the greater of earnings and AGI
+    pub fn credit(
+        earned_income: Decimal,
+        agi: Decimal,
+        filing_status: FilingStatus,
+        qualifying_children: u32,
+        year: u32,
+    ) -> Decimal {
+        if filing_status == FilingStatus::MarriedFilingSeparately || earned_income <= Decimal::ZERO
+        {
+            return Decimal::ZERO;
+        }
+        let schedule = Self::schedule(qualifying_children, year);
+        let phase_out_start = if filing_status == FilingStatus::MarriedFilingJointly {
+            schedule.phase_out_start_joint
+        } else {
+            schedule.phase_out_start
+        };
+        let phase_out =
+            (earned_income.max(agi) - phase_out_start).max(Decimal::ZERO) * schedule.phase_out_rate;
+        ((earned_income * schedule.phase_in_rate).min(schedule.maximum) - phase_out)
+            .max(Decimal::ZERO)
+            .round_dp(2)
+    }
+
+    /// Credit after the investment income and age tests
+    ///
+    /// `age` 0 means unknown, and is taken to pass the age test.
+    pub fn calculate(
+        earned_income: Decimal,
+        agi: Decimal,
+        investment_income: Decimal,
+        filing_status: FilingStatus,
+        qualifying_children: u32,
+        age: u32,
+        year: u32,
+    ) -> Decimal {
+        let age_ok = qualifying_children > 0
+            || age == 0
+            || (CHILDLESS_MIN_AGE..=CHILDLESS_MAX_AGE).contains(&age);
+        if !age_ok || investment_income > Self::investment_income_limit(year) {
+            return Decimal::ZERO;
+        }
+        Self::credit(earned_income, agi, filing_status, qualifying_children, year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eligibility_tests() {
+        let credit = |investment_income, children, age| {
+            EarnedIncomeCreditCalculator::calculate(
+                dec!(9000),
+                dec!(9000),
+                investment_income,
+                FilingStatus::Single,
+                children,
+                age,
+                2024,
+            )
+        };
+
+        // 7.65% of $9,000, capped at $632
+        assert_eq!(credit(dec!(0), 0, 30), dec!(632));
+        assert_eq!(credit(dec!(0), 0, 0), dec!(632));
+        assert_eq!(credit(dec!(0), 0, 22), dec!(0));
+        assert_eq!(credit(dec!(0), 1, 22), dec!(3060));
+        assert_eq!(credit(dec!(11601), 1, 30), dec!(0));
+
+        // 2025's larger maximum
+        assert_eq!(
+            EarnedIncomeCreditCalculator::credit(
+                dec!(20000),
+                dec!(20000),
+                FilingStatus::Single,
+                3,
+                2025,
+            ),
+            dec!(8046)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Residential energy credits
+//!
+//! Two nonrefundable federal credits for home energy spending:
+//!
+//! - Energy efficient home improvement credit (§25C): 30% of heat pumps,
+//!   heat pump water heaters, and biomass stoves up to $2,000, plus 30% of
+//!   insulation, windows, doors, and energy audits up to $1,200. Unused
+//!   credit is lost.
+//! - Residential clean energy credit (§25D): 30% of solar, battery storage,
+//!   geothermal, and small wind costs with no cap. Credit beyond the year's
+//!   tax carries forward.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Share of qualified costs credited
+const CREDIT_RATE: Decimal = dec!(0.30);
+
+/// Annual cap on the heat pump portion of §25C
+const HEAT_PUMP_CAP: Decimal = dec!(2000);
+
+/// Annual cap on the building envelope and audit portion of §25C
+const ENVELOPE_CAP: Decimal = dec!(1200);
+
+/// A year's qualified home energy spending
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnergyImprovements {
+    /// Solar, battery, geothermal, and small wind installations (§25D)
+    pub clean_energy: Decimal,
+    /// Heat pumps, heat pump water heaters, and biomass stoves (§25C)
+    pub heat_pumps: Decimal,
+    /// Insulation, windows, doors, panels, and audits (§25C)
+    pub envelope: Decimal,
+    /// Unused clean energy credit carried in from earlier years
+    #[serde(default)]
+    pub carryforward: Decimal,
+}
+
+/// Energy credits applied against a year's income tax
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnergyCreditResult {
+    /// §25C credit used
+    pub efficiency_credit: Decimal,
+    /// §25D credit used, including carryforward
+    pub clean_energy_credit: Decimal,
+    /// §25D credit left for next year
+    pub carryforward: Decimal,
+    /// §25C credit lost because tax ran out
+    pub efficiency_credit_lost: Decimal,
+}
+
+impl EnergyCreditResult {
+    /// Total reduction in income tax
+    pub fn total(&self) -> Decimal {
+        self.efficiency_credit + self.clean_energy_credit
+    }
+}
+
+/// Residential energy credit calculator
+pub struct EnergyCreditCalculator;
+
+impl EnergyCreditCalculator {
+    /// Credits available before the tax limit
+    pub fn available(improvements: &EnergyImprovements) -> (Decimal, Decimal) {
+        let positive = |amount: Decimal| amount.max(Decimal::ZERO);
+        let efficiency = (positive(improvements.heat_pumps) * CREDIT_RATE).min(HEAT_PUMP_CAP)
+            + (positive(improvements.envelope) * CREDIT_RATE).min(ENVELOPE_CAP);
+        let clean_energy =
+            positive(improvements.clean_energy) * CREDIT_RATE + positive(improvements.carryforward);
+        (efficiency, clean_energy)
+    }
+
+    /// Apply the credits to `income_tax`
+    ///
+    /// The §25C credit goes first since it can't be carried forward.
+    pub fn calculate(improvements: &EnergyImprovements, income_tax: Decimal) -> EnergyCreditResult {
+        let (efficiency, clean_energy) = Self::available(improvements);
+        let income_tax = income_tax.max(Decimal::ZERO);
+        let efficiency_credit = efficiency.min(income_tax);
+        let clean_energy_credit = clean_energy.min(income_tax - efficiency_credit);
+
+        EnergyCreditResult {
+            efficiency_credit,
+            clean_energy_credit,
+            carryforward: clean_energy - clean_energy_credit,
+            efficiency_credit_lost: efficiency - efficiency_credit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caps_and_ordering() {
+        let improvements = EnergyImprovements {
+            clean_energy: dec!(20000),
+            heat_pumps: dec!(12000),
+            envelope: dec!(2000),
+            carryforward: Decimal::ZERO,
+        };
+
+        // $2,000 heat pump cap + $600 envelope, then $6,000 of solar
+        let result = EnergyCreditCalculator::calculate(&improvements, dec!(10000));
+        assert_eq!(result.efficiency_credit, dec!(2600));
+        assert_eq!(result.clean_energy_credit, dec!(6000));
+        assert_eq!(result.carryforward, dec!(0));
+        assert_eq!(result.total(), dec!(8600));
+
+        // Limited tax: §25C first, unused solar credit carries forward
+        let result = EnergyCreditCalculator::calculate(&improvements, dec!(4000));
+        assert_eq!(result.efficiency_credit, dec!(2600));
+        assert_eq!(result.clean_energy_credit, dec!(1400));
+        assert_eq!(result.carryforward, dec!(4600));
+
+        let result = EnergyCreditCalculator::calculate(&improvements, dec!(1000));
+        assert_eq!(result.efficiency_credit_lost, dec!(1600));
+        assert_eq!(result.carryforward, dec!(6000));
+    }
+}
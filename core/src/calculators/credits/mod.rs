@@ -0,0 +1,134 @@
+//! Federal credits
+//!
+//! Each nonrefundable credit can only offset income tax left after the ones
+//! before it. The child and other-dependent credits go first, then credits
+//! lost when unused (clean vehicle, energy efficient home improvement),
+//! then the adoption credit, then the residential clean energy credit,
+//! whose unused balance carries forward with no expiration. The additional
+//! child tax credit and the earned income credit are refundable.
+
+pub mod adoption;
+pub mod child_tax;
+pub mod clean_vehicle;
+pub mod earned_income;
+pub mod energy;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::tax::FilingStatus;
+
+use adoption::{AdoptionCreditCalculator, AdoptionCreditResult, AdoptionExpenses};
+use clean_vehicle::{CleanVehicle, CleanVehicleCredit, CleanVehicleCreditCalculator};
+use energy::{EnergyCreditCalculator, EnergyCreditResult, EnergyImprovements};
+
+/// Spending and purchases that qualify for credits
+#[derive(Debug, Clone, Copy)]
+pub struct CreditClaims<'a> {
+    pub clean_vehicle: Option<&'a CleanVehicle>,
+    pub energy_improvements: &'a EnergyImprovements,
+    pub adoption: &'a AdoptionExpenses,
+}
+
+/// Credits applied against a year's income tax
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NonrefundableCredits {
+    pub clean_vehicle: Option<CleanVehicleCredit>,
+    pub energy: EnergyCreditResult,
+    pub adoption: AdoptionCreditResult,
+}
+
+impl NonrefundableCredits {
+    /// Apply every claimed credit to `income_tax` in order
+    pub fn calculate(
+        claims: CreditClaims,
+        filing_status: FilingStatus,
+        magi: Decimal,
+        income_tax: Decimal,
+        year: u32,
+    ) -> Self {
+        let mut remaining = income_tax.max(Decimal::ZERO);
+
+        let clean_vehicle = claims.clean_vehicle.map(|vehicle| {
+            CleanVehicleCreditCalculator::calculate(vehicle, filing_status, magi, remaining)
+        });
+        remaining -= clean_vehicle.as_ref().map_or(Decimal::ZERO, |v| v.credit);
+
+        let (efficiency, clean_energy) =
+            EnergyCreditCalculator::available(claims.energy_improvements);
+        let efficiency_credit = efficiency.min(remaining);
+        remaining -= efficiency_credit;
+
+        let adoption = AdoptionCreditCalculator::calculate(
+            claims.adoption,
+            filing_status,
+            magi,
+            year,
+            remaining,
+        );
+        remaining -= adoption.credit;
+
+        let clean_energy_credit = clean_energy.min(remaining);
+        Self {
+            clean_vehicle,
+            energy: EnergyCreditResult {
+                efficiency_credit,
+                clean_energy_credit,
+                carryforward: clean_energy - clean_energy_credit,
+                efficiency_credit_lost: efficiency - efficiency_credit,
+            },
+            adoption,
+        }
+    }
+
+    /// Total reduction in income tax
+    pub fn total(&self) -> Decimal {
+        self.clean_vehicle
+            .as_ref()
+            .map_or(Decimal::ZERO, |v| v.credit)
+            + self.energy.total()
+            + self.adoption.credit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adoption::Adoption;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_carryforward_credits_absorb_what_is_left() {
+        let energy_improvements = EnergyImprovements {
+            clean_energy: dec!(10000),
+            heat_pumps: dec!(5000),
+            ..Default::default()
+        };
+        let adoption = AdoptionExpenses {
+            adoptions: vec![Adoption {
+                qualified_expenses: dec!(4000),
+                special_needs: false,
+            }],
+            carryforward: Decimal::ZERO,
+        };
+        let claims = CreditClaims {
+            clean_vehicle: None,
+            energy_improvements: &energy_improvements,
+            adoption: &adoption,
+        };
+
+        // $1,500 heat pump credit, $4,000 adoption, $3,000 solar: $6,000 of tax
+        let credits = NonrefundableCredits::calculate(
+            claims,
+            FilingStatus::Single,
+            dec!(80000),
+            dec!(6000),
+            2024,
+        );
+        assert_eq!(credits.energy.efficiency_credit, dec!(1500));
+        assert_eq!(credits.adoption.credit, dec!(4000));
+        assert_eq!(credits.energy.clean_energy_credit, dec!(500));
+        assert_eq!(credits.energy.carryforward, dec!(2500));
+        assert_eq!(credits.total(), dec!(6000));
+    }
+}
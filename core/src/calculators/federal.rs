@@ -3,6 +3,7 @@
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
+use crate::calculators::capital_gains::CapitalGainsTaxCalculator;
 use crate::data::TaxDataProvider;
 use crate::models::tax::{BracketAmount, FederalTaxResult, FilingStatus, TaxBracket};
 
@@ -29,9 +30,18 @@ impl<'a> FederalTaxCalculator<'a> {
             return FederalTaxResult {
                 taxable_income: Decimal::ZERO,
                 tax: Decimal::ZERO,
-                marginal_rate: brackets.first().map(|b| b.rate).unwrap_or(dec!(0.10)),
+                marginal_rate: brackets
+                    .first()
+                    .map(|b| b.rate.as_fraction())
+                    .unwrap_or(dec!(0.10)),
                 effective_rate: Decimal::ZERO,
                 bracket_breakdown: vec![],
+                credits: Decimal::ZERO,
+                deduction: Decimal::ZERO,
+                itemized: false,
+                qbi_deduction: Decimal::ZERO,
+                ordinary_tax: Decimal::ZERO,
+                preferential_tax: Decimal::ZERO,
             };
         }
 
@@ -41,7 +51,7 @@ impl<'a> FederalTaxCalculator<'a> {
 
         for bracket in &brackets {
             if taxable_income > bracket.floor {
-                marginal_rate = bracket.rate;
+                marginal_rate = bracket.rate.as_fraction();
 
                 let ceiling = bracket.ceiling.unwrap_or(Decimal::MAX);
                 let income_in_bracket = taxable_income.min(ceiling) - bracket.floor;
@@ -69,9 +79,45 @@ impl<'a> FederalTaxCalculator<'a> {
             marginal_rate,
             effective_rate,
             bracket_breakdown: breakdown,
+            credits: Decimal::ZERO,
+            deduction: Decimal::ZERO,
+            itemized: false,
+            qbi_deduction: Decimal::ZERO,
+            ordinary_tax: tax,
+            preferential_tax: Decimal::ZERO,
         }
     }
 
+    /// Calculate federal income tax with `preferential_income` (long-term
+    /// gains and qualified dividends within `taxable_income`) taxed at the
+    /// capital gains rates and the rest through the brackets
+    pub fn calculate_with_preferential(
+        &self,
+        taxable_income: Decimal,
+        preferential_income: Decimal,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> FederalTaxResult {
+        let preferential = CapitalGainsTaxCalculator::calculate(
+            taxable_income,
+            preferential_income,
+            filing_status,
+            year,
+        );
+        let mut result = self.calculate(
+            taxable_income - preferential.preferential_income,
+            filing_status,
+            year,
+        );
+        if preferential.preferential_income > Decimal::ZERO {
+            result.taxable_income = taxable_income;
+            result.preferential_tax = preferential.tax;
+            result.tax += preferential.tax;
+            result.effective_rate = result.tax / taxable_income;
+        }
+        result
+    }
+
     /// Efficient calculation using base tax formula
     /// Tax = BaseTax + (Income - BracketFloor) × Rate
     fn calculate_with_base_tax(&self, taxable_income: Decimal, brackets: &[TaxBracket]) -> Decimal {
@@ -162,6 +208,23 @@ mod tests {
         assert_eq!(mfj, dec!(29200));
     }
 
+    #[test]
+    fn test_preferential_income_taxed_separately() {
+        let data = setup();
+        let calc = FederalTaxCalculator::new(&data);
+
+        // $60,000 of ordinary income is past the 0% threshold, so the gains
+        // are all at 15%
+        let result =
+            calc.calculate_with_preferential(dec!(80000), dec!(20000), FilingStatus::Single, 2024);
+        let ordinary = calc.calculate(dec!(60000), FilingStatus::Single, 2024);
+        assert_eq!(result.taxable_income, dec!(80000));
+        assert_eq!(result.ordinary_tax, ordinary.tax);
+        assert_eq!(result.preferential_tax, dec!(3000));
+        assert_eq!(result.tax, ordinary.tax + dec!(3000));
+        assert_eq!(result.marginal_rate, dec!(0.22));
+    }
+
     #[test]
     fn test_bracket_breakdown_adds_up() {
         let data = setup();
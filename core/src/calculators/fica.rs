@@ -21,6 +21,17 @@ impl<'a> FicaCalculator<'a> {
         self.calculate_with_status(gross_income, FilingStatus::Single, year)
     }
 
+    /// Wages above which additional Medicare tax applies
+    pub fn additional_medicare_threshold(filing_status: FilingStatus) -> Decimal {
+        match filing_status {
+            FilingStatus::Single
+            | FilingStatus::HeadOfHousehold
+            | FilingStatus::QualifyingWidower => dec!(200000),
+            FilingStatus::MarriedFilingJointly => dec!(250000),
+            FilingStatus::MarriedFilingSeparately => dec!(125000),
+        }
+    }
+
     /// Calculate FICA taxes with filing status for additional Medicare threshold
     pub fn calculate_with_status(
         &self,
@@ -38,14 +49,7 @@ impl<'a> FicaCalculator<'a> {
         let medicare = gross_income * config.medicare_rate;
 
         // Additional Medicare (0.9% above threshold)
-        // Threshold varies by filing status
-        let threshold = match filing_status {
-            FilingStatus::Single
-            | FilingStatus::HeadOfHousehold
-            | FilingStatus::QualifyingWidower => dec!(200000),
-            FilingStatus::MarriedFilingJointly => dec!(250000),
-            FilingStatus::MarriedFilingSeparately => dec!(125000),
-        };
+        let threshold = Self::additional_medicare_threshold(filing_status);
 
         let additional_medicare = if gross_income > threshold {
             (gross_income - threshold) * config.additional_medicare_rate
@@ -0,0 +1,222 @@
+//! Wage garnishment under the Consumer Credit Protection Act
+//!
+//! Garnishments come out of disposable earnings: wages less the deductions
+//! required by law (income taxes, FICA, state payroll programs). Voluntary
+//! deductions such as 401(k) contributions and insurance premiums don't
+//! reduce disposable earnings, and are taken only from what garnishment
+//! leaves.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::deduction::{Garnishment, GarnishmentKind};
+use crate::models::income::{TimeframeIncome, WorkSchedule};
+
+/// Ceiling on ordinary garnishments, as a share of disposable earnings
+const ORDINARY_LIMIT: Decimal = dec!(0.25);
+
+/// Ceiling on federal student loan garnishment (31 CFR 285.11)
+const STUDENT_LOAN_LIMIT: Decimal = dec!(0.15);
+
+/// Support ceiling for an earner supporting another spouse or child
+const SUPPORT_LIMIT_OTHER_FAMILY: Decimal = dec!(0.50);
+
+/// Support ceiling for an earner with no other family to support
+const SUPPORT_LIMIT: Decimal = dec!(0.60);
+
+/// Added to the support ceiling when payments are over 12 weeks behind
+const SUPPORT_ARREARS_INCREASE: Decimal = dec!(0.05);
+
+/// Weekly disposable earnings at or below 30 times the federal minimum wage
+/// ($7.25) are protected from ordinary garnishment
+const PROTECTED_WEEKLY_EARNINGS: Decimal = dec!(217.50);
+
+/// Withholding for one garnishment order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GarnishmentWithholding {
+    pub kind: GarnishmentKind,
+    /// Annual amount the order asked for
+    pub requested: Decimal,
+    /// Annual amount withheld after legal limits
+    pub withheld: Decimal,
+}
+
+impl GarnishmentWithholding {
+    /// Whether the legal limits cut the order short
+    pub fn is_limited(&self) -> bool {
+        self.withheld < self.requested
+    }
+}
+
+/// All garnishments on a year's wages
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GarnishmentResult {
+    /// Wages less legally required deductions
+    pub disposable_earnings: Decimal,
+    /// Orders in the sequence they were withheld: support first
+    pub withholdings: Vec<GarnishmentWithholding>,
+    pub total: Decimal,
+    /// `total` per paycheck and other timeframes
+    pub timeframes: TimeframeIncome,
+}
+
+/// CCPA garnishment limits (15 U.S.C. 1673)
+pub struct GarnishmentCalculator;
+
+impl GarnishmentCalculator {
+    /// Largest share of disposable earnings support orders may take
+    pub fn support_limit(supports_other_family: bool, arrears_over_12_weeks: bool) -> Decimal {
+        let base = if supports_other_family {
+            SUPPORT_LIMIT_OTHER_FAMILY
+        } else {
+            SUPPORT_LIMIT
+        };
+        if arrears_over_12_weeks {
+            base + SUPPORT_ARREARS_INCREASE
+        } else {
+            base
+        }
+    }
+
+    /// Annual ceiling on ordinary garnishments: 25% of disposable earnings,
+    /// or the amount above the protected weekly minimum if that's less
+    pub fn ordinary_limit(disposable_earnings: Decimal, schedule: &WorkSchedule) -> Decimal {
+        let protected = PROTECTED_WEEKLY_EARNINGS * schedule.paid_weeks();
+        (disposable_earnings * ORDINARY_LIMIT)
+            .min(disposable_earnings - protected)
+            .max(Decimal::ZERO)
+    }
+
+    /// Withhold each order up to its limit
+    ///
+    /// Support orders come first. Ordinary garnishments only get what the
+    /// 25% ceiling leaves after everything withheld before them, so support
+    /// taking a quarter or more of disposable earnings blocks them entirely.
+    pub fn calculate(
+        orders: &[Garnishment],
+        disposable_earnings: Decimal,
+        schedule: &WorkSchedule,
+    ) -> GarnishmentResult {
+        let disposable = disposable_earnings.max(Decimal::ZERO);
+        let ordinary_limit = Self::ordinary_limit(disposable, schedule);
+
+        let mut sorted: Vec<&Garnishment> = orders.iter().collect();
+        sorted.sort_by_key(|order| !order.kind.is_support());
+
+        let mut support_withheld = Decimal::ZERO;
+        let mut total = Decimal::ZERO;
+        let mut withholdings = Vec::with_capacity(sorted.len());
+        for order in sorted {
+            let room = match order.kind {
+                GarnishmentKind::Support {
+                    supports_other_family,
+                    arrears_over_12_weeks,
+                } => {
+                    disposable * Self::support_limit(supports_other_family, arrears_over_12_weeks)
+                        - support_withheld
+                },
+                GarnishmentKind::StudentLoan => {
+                    (disposable * STUDENT_LOAN_LIMIT).min(ordinary_limit - total)
+                },
+                GarnishmentKind::Creditor => ordinary_limit - total,
+            };
+            let withheld = order
+                .annual_amount
+                .max(Decimal::ZERO)
+                .min(room.max(Decimal::ZERO));
+            if order.kind.is_support() {
+                support_withheld += withheld;
+            }
+            total += withheld;
+            withholdings.push(GarnishmentWithholding {
+                kind: order.kind,
+                requested: order.annual_amount,
+                withheld,
+            });
+        }
+
+        GarnishmentResult {
+            disposable_earnings: disposable,
+            withholdings,
+            total,
+            timeframes: TimeframeIncome::from_schedule(total, schedule),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPORT: GarnishmentKind = GarnishmentKind::Support {
+        supports_other_family: false,
+        arrears_over_12_weeks: false,
+    };
+
+    #[test]
+    fn test_support_limits() {
+        assert_eq!(
+            GarnishmentCalculator::support_limit(true, false),
+            dec!(0.50)
+        );
+        assert_eq!(
+            GarnishmentCalculator::support_limit(false, false),
+            dec!(0.60)
+        );
+        assert_eq!(GarnishmentCalculator::support_limit(true, true), dec!(0.55));
+        assert_eq!(
+            GarnishmentCalculator::support_limit(false, true),
+            dec!(0.65)
+        );
+    }
+
+    #[test]
+    fn test_ordinary_limit_protects_low_earnings() {
+        let schedule = WorkSchedule::default();
+
+        assert_eq!(
+            GarnishmentCalculator::ordinary_limit(dec!(52000), &schedule),
+            dec!(13000)
+        );
+        // $250/week disposable: only the $32.50 above 30x minimum wage
+        assert_eq!(
+            GarnishmentCalculator::ordinary_limit(dec!(13000), &schedule),
+            dec!(1690)
+        );
+        assert_eq!(
+            GarnishmentCalculator::ordinary_limit(dec!(10000), &schedule),
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn test_support_takes_priority_over_creditors() {
+        let orders = [
+            Garnishment::new(GarnishmentKind::Creditor, dec!(20000)),
+            Garnishment::new(SUPPORT, dec!(10000)),
+            Garnishment::new(GarnishmentKind::StudentLoan, dec!(9000)),
+        ];
+        let result =
+            GarnishmentCalculator::calculate(&orders, dec!(60000), &WorkSchedule::default());
+
+        // Support first, then the 25% ($15,000) ceiling's remaining $5,000
+        assert_eq!(result.withholdings[0].kind, SUPPORT);
+        assert_eq!(result.withholdings[0].withheld, dec!(10000));
+        assert_eq!(result.withholdings[1].withheld, dec!(5000));
+        assert!(result.withholdings[1].is_limited());
+        assert_eq!(result.withholdings[2].withheld, dec!(0));
+        assert_eq!(result.total, dec!(15000));
+        assert_eq!(result.timeframes.monthly, dec!(1250));
+
+        // Support above 25% leaves nothing for ordinary debts
+        let orders = [
+            Garnishment::new(SUPPORT, dec!(40000)),
+            Garnishment::new(GarnishmentKind::Creditor, dec!(1000)),
+        ];
+        let result =
+            GarnishmentCalculator::calculate(&orders, dec!(60000), &WorkSchedule::default());
+        assert_eq!(result.withholdings[0].withheld, dec!(36000));
+        assert_eq!(result.withholdings[1].withheld, dec!(0));
+    }
+}
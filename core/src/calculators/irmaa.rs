@@ -0,0 +1,167 @@
+//! Medicare income-related monthly adjustment amounts (IRMAA)
+//!
+//! Medicare Part B and Part D premiums rise in steps with MAGI from two
+//! years earlier: income in 2024 sets the 2026 premiums. Each step is a
+//! cliff, so a dollar over a threshold costs the whole year's surcharge for
+//! that tier, per person on Medicare.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::data::TaxDataProvider;
+use crate::models::tax::FilingStatus;
+
+/// Years between the income year and the premium year it sets
+pub const LOOKBACK_YEARS: u32 = 2;
+
+/// Surcharges a year's MAGI triggers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrmaaEstimate {
+    /// Year the MAGI was earned
+    pub income_year: u32,
+    /// Year the surcharges are paid
+    pub premium_year: u32,
+    /// AGI plus tax-exempt interest
+    pub magi: Decimal,
+    /// 0 for standard premiums, then 1 through the top tier
+    pub tier: usize,
+    /// Standard monthly Part B premium
+    pub part_b_premium: Decimal,
+    /// Monthly amount added to the Part B premium
+    pub part_b_surcharge: Decimal,
+    /// Monthly amount added to the Part D plan premium
+    pub part_d_surcharge: Decimal,
+    /// Part B and D surcharges for the year, per person on Medicare
+    pub annual_surcharge: Decimal,
+    /// MAGI that would reach the next tier, `None` in the top tier
+    pub next_threshold: Option<Decimal>,
+    /// Added income that stays in this tier
+    pub headroom: Option<Decimal>,
+}
+
+impl IrmaaEstimate {
+    pub fn has_surcharge(&self) -> bool {
+        self.tier > 0
+    }
+}
+
+/// IRMAA calculator
+pub struct IrmaaCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> IrmaaCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Surcharges set by MAGI earned in `income_year`
+    ///
+    /// Married filing separately uses the tiers for spouses who lived
+    /// together during the year.
+    pub fn estimate(
+        &self,
+        magi: Decimal,
+        filing_status: FilingStatus,
+        income_year: u32,
+    ) -> IrmaaEstimate {
+        let premium_year = income_year + LOOKBACK_YEARS;
+        let schedule = self.data_provider.irmaa_schedule(premium_year);
+        let tiers = schedule.tiers(filing_status);
+
+        let reached = |index: usize| {
+            let threshold = tiers[index].magi_threshold;
+            if index + 1 == tiers.len() {
+                magi >= threshold
+            } else {
+                magi > threshold
+            }
+        };
+        let tier = (0..tiers.len()).take_while(|&index| reached(index)).count();
+        let (part_b_surcharge, part_d_surcharge) = match tier {
+            0 => (Decimal::ZERO, Decimal::ZERO),
+            tier => (
+                tiers[tier - 1].part_b_surcharge,
+                tiers[tier - 1].part_d_surcharge,
+            ),
+        };
+        let next_threshold = tiers.get(tier).map(|next| next.magi_threshold);
+
+        IrmaaEstimate {
+            income_year,
+            premium_year,
+            magi,
+            tier,
+            part_b_premium: schedule.part_b_premium,
+            part_b_surcharge,
+            part_d_surcharge,
+            annual_surcharge: (part_b_surcharge + part_d_surcharge) * Decimal::from(12),
+            next_threshold,
+            headroom: next_threshold.map(|threshold| {
+                if tier + 1 == tiers.len() {
+                    // The top tier starts at its threshold
+                    threshold - magi - Decimal::new(1, 2)
+                } else {
+                    threshold - magi
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_tiers_with_two_year_lookback() {
+        let data = setup();
+        let calc = IrmaaCalculator::new(&data);
+
+        // 2022 income sets 2024 premiums; the threshold itself is standard
+        let standard = calc.estimate(dec!(103000), FilingStatus::Single, 2022);
+        assert_eq!(standard.premium_year, 2024);
+        assert!(!standard.has_surcharge());
+        assert_eq!(standard.part_b_premium, dec!(174.70));
+        assert_eq!(standard.headroom, Some(dec!(0)));
+
+        let over = calc.estimate(dec!(103001), FilingStatus::Single, 2022);
+        assert_eq!(over.tier, 1);
+        assert_eq!(over.part_b_surcharge, dec!(69.90));
+        assert_eq!(
+            over.annual_surcharge,
+            (dec!(69.90) + dec!(12.90)) * dec!(12)
+        );
+        assert_eq!(over.next_threshold, Some(dec!(129000)));
+
+        let joint = calc.estimate(dec!(300000), FilingStatus::MarriedFilingJointly, 2023);
+        assert_eq!(joint.premium_year, 2025);
+        assert_eq!(joint.tier, 2);
+        assert_eq!(joint.part_b_surcharge, dec!(185.00));
+        assert_eq!(joint.headroom, Some(dec!(34000)));
+    }
+
+    #[test]
+    fn test_top_tier_starts_at_threshold() {
+        let data = setup();
+        let calc = IrmaaCalculator::new(&data);
+
+        let top = calc.estimate(dec!(500000), FilingStatus::HeadOfHousehold, 2022);
+        assert_eq!(top.tier, 5);
+        assert_eq!(top.part_b_surcharge, dec!(419.30));
+        assert_eq!(top.headroom, None);
+        let below = calc.estimate(dec!(499999.99), FilingStatus::HeadOfHousehold, 2022);
+        assert_eq!(below.tier, 4);
+        assert_eq!(below.headroom, Some(dec!(0)));
+
+        // Separate filers living together jump straight to the upper tiers
+        let separate = calc.estimate(dec!(110000), FilingStatus::MarriedFilingSeparately, 2022);
+        assert_eq!(separate.part_b_surcharge, dec!(384.30));
+    }
+}
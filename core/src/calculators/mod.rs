@@ -1,11 +1,41 @@
 //! Tax and income calculators
 
+pub mod capital_gains;
+pub mod credits;
 pub mod federal;
 pub mod fica;
+pub mod garnishment;
+pub mod irmaa;
+pub mod multi_state;
+pub mod premium_tax_credit;
+pub mod qbi;
+pub mod rmd;
+pub mod seca;
+pub mod self_employed_retirement;
+pub mod self_employment;
+pub mod social_security;
 pub mod state;
 pub mod timeframe;
+pub mod vehicle_expense;
+pub mod withholding;
 
+pub use capital_gains::CapitalGainsTaxCalculator;
+pub use credits::adoption::AdoptionCreditCalculator;
+pub use credits::clean_vehicle::CleanVehicleCreditCalculator;
+pub use credits::energy::EnergyCreditCalculator;
 pub use federal::FederalTaxCalculator;
 pub use fica::FicaCalculator;
+pub use garnishment::GarnishmentCalculator;
+pub use irmaa::IrmaaCalculator;
+pub use multi_state::MultiStateTaxCalculator;
+pub use premium_tax_credit::PremiumTaxCreditCalculator;
+pub use qbi::QbiDeductionCalculator;
+pub use rmd::RmdCalculator;
+pub use seca::SecaCalculator;
+pub use self_employed_retirement::SelfEmployedRetirementCalculator;
+pub use self_employment::SelfEmploymentTaxCalculator;
+pub use social_security::SocialSecurityTaxation;
 pub use state::StateTaxCalculator;
 pub use timeframe::TimeframeCalculator;
+pub use vehicle_expense::VehicleExpenseCalculator;
+pub use withholding::{FederalWithholdingCalculator, SupplementalWithholdingCalculator};
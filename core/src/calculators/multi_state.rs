@@ -0,0 +1,205 @@
+//! Multi-state tax for residents working across a state line
+//!
+//! The work state taxes the wages earned there as a nonresident, prorating
+//! its resident-basis tax by the share of income sourced to it. The resident
+//! state taxes all income and credits tax paid to the work state, limited to
+//! its own tax on that same income.
+
+use rust_decimal::Decimal;
+
+use crate::calculators::state::{StateTaxCalculator, StateTaxInput};
+use crate::data::TaxDataProvider;
+use crate::models::state::USState;
+use crate::models::tax::StateTaxResult;
+
+/// Resident and nonresident state tax with the credit for taxes paid
+#[derive(Debug, Clone)]
+pub struct MultiStateResult {
+    /// Resident state tax after the credit
+    pub resident: StateTaxResult,
+    /// Nonresident tax owed to the work state
+    pub work_state: StateTaxResult,
+    pub credit_for_taxes_paid: Decimal,
+    pub total_tax: Decimal,
+}
+
+/// Multi-state (resident + work state) tax calculator
+pub struct MultiStateTaxCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> MultiStateTaxCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Calculate tax for a resident of `input.state` earning `work_state_wages` in `work_state`
+    pub fn calculate(
+        &self,
+        input: &StateTaxInput,
+        work_state: USState,
+        work_state_wages: Decimal,
+        year: u32,
+    ) -> MultiStateResult {
+        let calc = StateTaxCalculator::new(self.data_provider);
+        let sourced_wages = work_state_wages.max(Decimal::ZERO).min(input.wages);
+
+        // Nonresident: tax all income as if resident, then keep the sourced share
+        let work_basis = calc.calculate_input(
+            &StateTaxInput {
+                state: work_state,
+                wages: sourced_wages,
+                retirement_distributions: input.retirement_distributions,
+                school_district: None,
+                local_tax_rate_override: None,
+                ..input.clone()
+            },
+            year,
+        );
+        let sourced_ratio = if work_basis.taxable_income > Decimal::ZERO {
+            (sourced_wages / work_basis.taxable_income).min(Decimal::ONE)
+        } else {
+            Decimal::ZERO
+        };
+        let work_income_tax = work_basis.income_tax * sourced_ratio;
+        // Only a commuter wage tax reaches nonresidents at the local level
+        let work_local_tax = input
+            .work_locality
+            .as_ref()
+            .map(|city| calc.nonresident_local_tax(work_state, city, sourced_wages, year))
+            .unwrap_or(Decimal::ZERO);
+        let work_total = work_income_tax + work_local_tax + work_basis.sdi;
+        let work_state_result = StateTaxResult {
+            taxable_income: sourced_wages,
+            income_tax: work_income_tax,
+            local_tax: work_local_tax,
+            local_tax_estimated: false,
+            school_district_tax: Decimal::ZERO,
+            total_tax: work_total,
+            effective_rate: if sourced_wages > Decimal::ZERO {
+                work_total / sourced_wages
+            } else {
+                Decimal::ZERO
+            },
+            bracket_breakdown: None,
+            ..work_basis
+        };
+
+        // Resident: SDI follows where the wages were earned
+        let resident_basis = calc.calculate_input(
+            &StateTaxInput {
+                wages: input.wages - sourced_wages,
+                ..input.clone()
+            },
+            year,
+        );
+        let resident_ratio = if resident_basis.taxable_income > Decimal::ZERO {
+            (sourced_wages / resident_basis.taxable_income).min(Decimal::ONE)
+        } else {
+            Decimal::ZERO
+        };
+        let credit_limit = resident_basis.income_tax * resident_ratio;
+        let credit = work_income_tax.min(credit_limit);
+
+        let resident_income_tax = resident_basis.income_tax - credit;
+        let resident_total = resident_basis.total_tax - credit;
+        let resident = StateTaxResult {
+            income_tax: resident_income_tax,
+            total_tax: resident_total,
+            effective_rate: if resident_basis.taxable_income > Decimal::ZERO {
+                resident_total / resident_basis.taxable_income
+            } else {
+                Decimal::ZERO
+            },
+            ..resident_basis
+        };
+
+        let total_tax = resident.total_tax + work_state_result.total_tax;
+
+        MultiStateResult {
+            resident,
+            work_state: work_state_result,
+            credit_for_taxes_paid: credit,
+            total_tax,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::tax::FilingStatus;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_kansas_resident_working_in_missouri() {
+        let data = setup();
+        let calc = MultiStateTaxCalculator::new(&data);
+
+        let input = StateTaxInput::new(dec!(80000), USState::Kansas, FilingStatus::Single);
+        let result = calc.calculate(&input, USState::Missouri, dec!(80000), 2024);
+
+        // Missouri: $65,400 after the standard deduction, all sourced to MO
+        assert_eq!(result.work_state.income_tax, dec!(2959.707));
+        assert_eq!(result.work_state.state_code, "MO");
+
+        // Kansas: $67,235 after deduction and exemption, credited for MO tax
+        assert_eq!(result.credit_for_taxes_paid, dec!(2959.707));
+        assert_eq!(result.resident.income_tax, dec!(704.606));
+
+        // Combined liability equals the higher of the two states' tax
+        assert_eq!(result.total_tax, dec!(3664.313));
+    }
+
+    #[test]
+    fn test_credit_limited_to_resident_tax() {
+        let data = setup();
+        let calc = MultiStateTaxCalculator::new(&data);
+
+        // Missouri resident working in Kansas: KS tax exceeds MO's, so the
+        // credit wipes out the MO tax on those wages but no more
+        let input = StateTaxInput::new(dec!(80000), USState::Missouri, FilingStatus::Single);
+        let result = calc.calculate(&input, USState::Kansas, dec!(80000), 2024);
+
+        assert_eq!(result.resident.income_tax, dec!(0));
+        assert_eq!(result.credit_for_taxes_paid, dec!(2959.707));
+        assert_eq!(result.total_tax, result.work_state.total_tax);
+    }
+
+    #[test]
+    fn test_partial_work_state_wages() {
+        let data = setup();
+        let calc = MultiStateTaxCalculator::new(&data);
+
+        let input = StateTaxInput::new(dec!(100000), USState::Kansas, FilingStatus::Single);
+        let all = calc.calculate(&input, USState::Missouri, dec!(100000), 2024);
+        let half = calc.calculate(&input, USState::Missouri, dec!(50000), 2024);
+
+        assert_eq!(
+            half.work_state.income_tax * dec!(2),
+            all.work_state.income_tax
+        );
+        assert!(half.resident.income_tax > all.resident.income_tax);
+    }
+
+    #[test]
+    fn test_commuter_wage_tax_in_work_state() {
+        let data = setup();
+        let calc = MultiStateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(100000), USState::NewJersey, FilingStatus::Single);
+        input.work_locality = Some("Philadelphia".to_string());
+        let result = calc.calculate(&input, USState::Pennsylvania, dec!(100000), 2024);
+
+        assert_eq!(result.work_state.local_tax, dec!(3440));
+        assert_eq!(
+            result.work_state.total_tax,
+            result.work_state.income_tax + dec!(3440)
+        );
+    }
+}
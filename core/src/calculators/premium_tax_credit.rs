@@ -0,0 +1,309 @@
+//! Marketplace premium tax credit (IRC §36B)
+//!
+//! The credit pays the benchmark (second-lowest-cost silver) premium beyond
+//! an expected contribution: a share of household MAGI that rises with
+//! income as a percentage of the prior year's poverty guideline. Each extra
+//! dollar of income raises the expected contribution, so the phase-out acts
+//! as a marginal tax rate on top of the income tax. Without the 2021-2025
+//! enhancement the credit ends entirely at 400% of poverty.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::data::TaxDataProvider;
+use crate::engine::CalcWarning;
+use crate::models::rate::Rate;
+use crate::models::state::USState;
+
+/// Households below the guideline fall under Medicaid rather than the credit
+const MINIMUM_FPL_PERCENT: Decimal = dec!(100);
+
+/// Eligibility ends here in years without the enhanced credit
+const CLIFF_FPL_PERCENT: Decimal = dec!(400);
+
+/// Income step for measuring the phase-out rate
+const MARGINAL_STEP: Decimal = dec!(100);
+
+/// Last year of the enhanced credit (American Rescue Plan, extended by the
+/// Inflation Reduction Act)
+const LAST_ENHANCED_YEAR: u32 = 2025;
+
+/// Enhanced applicable percentage bands: (FPL % floor, rate at floor, rate at ceiling)
+const ENHANCED_BANDS: [(Decimal, Decimal, Decimal); 5] = [
+    (dec!(0), dec!(0), dec!(0)),
+    (dec!(150), dec!(0), dec!(0.02)),
+    (dec!(200), dec!(0.02), dec!(0.04)),
+    (dec!(250), dec!(0.04), dec!(0.06)),
+    (dec!(300), dec!(0.06), dec!(0.085)),
+];
+
+/// Enhanced rate at and above 400% of poverty
+const ENHANCED_CAP: Decimal = dec!(0.085);
+
+/// Original applicable percentage bands, as indexed for 2026
+const ORIGINAL_BANDS: [(Decimal, Decimal, Decimal); 6] = [
+    (dec!(0), dec!(0.021), dec!(0.021)),
+    (dec!(133), dec!(0.0314), dec!(0.0419)),
+    (dec!(150), dec!(0.0419), dec!(0.066)),
+    (dec!(200), dec!(0.066), dec!(0.0844)),
+    (dec!(250), dec!(0.0844), dec!(0.0996)),
+    (dec!(300), dec!(0.0996), dec!(0.0996)),
+];
+
+/// Household and coverage for a premium tax credit estimate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PremiumTaxCreditInput {
+    /// Modified AGI: AGI plus untaxed Social Security and tax-exempt interest
+    pub magi: Decimal,
+    /// People in the tax household
+    pub household_size: u32,
+    pub state: USState,
+    /// Annual premium of the benchmark second-lowest-cost silver plan
+    pub benchmark_premium: Decimal,
+    /// Annual premium of the plan actually chosen, when different
+    #[serde(default)]
+    pub enrolled_premium: Option<Decimal>,
+}
+
+/// Income at which the credit ends outright
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsidyCliff {
+    /// First MAGI above 400% of poverty
+    pub magi: Decimal,
+    /// Credit lost by crossing it
+    pub credit_lost: Decimal,
+}
+
+/// Premium tax credit estimate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PremiumTaxCredit {
+    pub poverty_guideline: Decimal,
+    /// MAGI as a percentage of the poverty guideline (e.g. 250 for 250%)
+    pub fpl_percent: Decimal,
+    pub eligible: bool,
+    /// Share of MAGI the household is expected to pay for the benchmark plan
+    pub applicable_percentage: Decimal,
+    pub expected_contribution: Decimal,
+    /// Annual credit
+    pub credit: Decimal,
+    pub monthly_credit: Decimal,
+    /// Annual premium left to pay for the enrolled plan
+    pub net_premium: Decimal,
+    /// Credit lost per additional dollar of MAGI
+    pub phase_out_rate: Rate,
+    /// The 400% cliff, when it applies and is still ahead
+    pub cliff: Option<SubsidyCliff>,
+    #[serde(default)]
+    pub warnings: Vec<CalcWarning>,
+}
+
+/// Premium tax credit calculator
+pub struct PremiumTaxCreditCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> PremiumTaxCreditCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Estimate the credit for coverage in `year`
+    pub fn calculate(&self, input: &PremiumTaxCreditInput, year: u32) -> PremiumTaxCredit {
+        let poverty_guideline = self.poverty_guideline(input, year);
+        let fpl_percent = Self::fpl_percent(input.magi, poverty_guideline);
+        let eligible = Self::is_eligible(fpl_percent, year);
+        let applicable_percentage = Self::applicable_percentage(fpl_percent, year);
+        let credit = self.credit_for(input, input.magi, year);
+        let enrolled_premium = input.enrolled_premium.unwrap_or(input.benchmark_premium);
+
+        let lost = credit - self.credit_for(input, input.magi + MARGINAL_STEP, year);
+        let cliff_magi = (poverty_guideline * CLIFF_FPL_PERCENT / dec!(100)).floor() + Decimal::ONE;
+        let cliff = (year > LAST_ENHANCED_YEAR && eligible && credit > Decimal::ZERO).then(|| {
+            SubsidyCliff {
+                magi: cliff_magi,
+                credit_lost: self.credit_for(input, cliff_magi - Decimal::ONE, year),
+            }
+        });
+
+        let guideline_year = year.saturating_sub(1);
+        let data_year = self.data_provider.poverty_guideline_year(guideline_year);
+        let warnings = (data_year != guideline_year)
+            .then_some(CalcWarning::PovertyGuidelineFromOtherYear {
+                year: guideline_year,
+                guideline_year: data_year,
+            })
+            .into_iter()
+            .collect();
+
+        PremiumTaxCredit {
+            poverty_guideline,
+            fpl_percent,
+            eligible,
+            applicable_percentage,
+            expected_contribution: if eligible {
+                input.magi * applicable_percentage
+            } else {
+                Decimal::ZERO
+            },
+            credit,
+            monthly_credit: credit / dec!(12),
+            net_premium: (enrolled_premium - credit).max(Decimal::ZERO),
+            phase_out_rate: Rate::of(lost, MARGINAL_STEP),
+            cliff,
+            warnings,
+        }
+    }
+
+    /// Guideline for the household, from the year before coverage
+    fn poverty_guideline(&self, input: &PremiumTaxCreditInput, year: u32) -> Decimal {
+        self.data_provider
+            .poverty_guideline(input.state, year.saturating_sub(1))
+            .for_family_size(input.household_size)
+    }
+
+    fn fpl_percent(magi: Decimal, poverty_guideline: Decimal) -> Decimal {
+        if poverty_guideline <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        magi / poverty_guideline * dec!(100)
+    }
+
+    fn is_eligible(fpl_percent: Decimal, year: u32) -> bool {
+        fpl_percent >= MINIMUM_FPL_PERCENT
+            && (year <= LAST_ENHANCED_YEAR || fpl_percent <= CLIFF_FPL_PERCENT)
+    }
+
+    /// Expected contribution rate, interpolated within the band
+    pub fn applicable_percentage(fpl_percent: Decimal, year: u32) -> Decimal {
+        let (bands, cap): (&[(Decimal, Decimal, Decimal)], Decimal) = if year <= LAST_ENHANCED_YEAR
+        {
+            (&ENHANCED_BANDS, ENHANCED_CAP)
+        } else {
+            (&ORIGINAL_BANDS, ORIGINAL_BANDS[ORIGINAL_BANDS.len() - 1].2)
+        };
+        if fpl_percent >= CLIFF_FPL_PERCENT {
+            return cap;
+        }
+
+        let index = bands
+            .iter()
+            .rposition(|(floor, _, _)| fpl_percent >= *floor)
+            .unwrap_or(0);
+        let (floor, low_rate, high_rate) = bands[index];
+        let ceiling = bands
+            .get(index + 1)
+            .map(|(next, _, _)| *next)
+            .unwrap_or(CLIFF_FPL_PERCENT);
+        let position = (fpl_percent - floor) / (ceiling - floor);
+        (low_rate + (high_rate - low_rate) * position).round_dp(4)
+    }
+
+    fn credit_for(&self, input: &PremiumTaxCreditInput, magi: Decimal, year: u32) -> Decimal {
+        let poverty_guideline = self.poverty_guideline(input, year);
+        let fpl_percent = Self::fpl_percent(magi, poverty_guideline);
+        if !Self::is_eligible(fpl_percent, year) {
+            return Decimal::ZERO;
+        }
+        let expected = magi * Self::applicable_percentage(fpl_percent, year);
+        let enrolled_premium = input.enrolled_premium.unwrap_or(input.benchmark_premium);
+        (input.benchmark_premium - expected)
+            .max(Decimal::ZERO)
+            .min(enrolled_premium)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn input(magi: Decimal) -> PremiumTaxCreditInput {
+        PremiumTaxCreditInput {
+            magi,
+            household_size: 1,
+            state: USState::Ohio,
+            benchmark_premium: dec!(7200),
+            enrolled_premium: None,
+        }
+    }
+
+    #[test]
+    fn test_applicable_percentage_bands() {
+        let rate = PremiumTaxCreditCalculator::applicable_percentage;
+
+        assert_eq!(rate(dec!(140), 2024), dec!(0));
+        assert_eq!(rate(dec!(225), 2024), dec!(0.03));
+        assert_eq!(rate(dec!(350), 2024), dec!(0.0725));
+        assert_eq!(rate(dec!(600), 2024), dec!(0.085));
+        assert_eq!(rate(dec!(175), 2026), dec!(0.0540));
+    }
+
+    #[test]
+    fn test_credit_and_phase_out_rate() {
+        let data = setup();
+        let calc = PremiumTaxCreditCalculator::new(&data);
+
+        // 2024 coverage uses the 2023 guideline ($14,580): $43,740 is 300%
+        let credit = calc.calculate(&input(dec!(43740)), 2024);
+        assert_eq!(credit.poverty_guideline, dec!(14580));
+        assert_eq!(credit.fpl_percent, dec!(300));
+        assert!(credit.eligible);
+        assert_eq!(credit.applicable_percentage, dec!(0.06));
+        assert_eq!(credit.expected_contribution, dec!(2624.40));
+        assert_eq!(credit.credit, dec!(4575.60));
+        assert_eq!(credit.net_premium, dec!(2624.40));
+        // Each dollar adds 6% plus the rising rate on all income
+        assert!(credit.phase_out_rate.as_fraction() > dec!(0.06));
+        assert!(credit.cliff.is_none());
+
+        // Enhanced credit continues past 400% until the benchmark is affordable
+        let high = calc.calculate(&input(dec!(70000)), 2024);
+        assert!(high.eligible);
+        assert_eq!(high.credit, dec!(1250));
+        assert_eq!(high.phase_out_rate.as_fraction(), dec!(0.085));
+
+        let below = calc.calculate(&input(dec!(12000)), 2024);
+        assert!(!below.eligible);
+        assert_eq!(below.credit, dec!(0));
+    }
+
+    #[test]
+    fn test_cliff_after_enhancement_expires() {
+        let data = setup();
+        let calc = PremiumTaxCreditCalculator::new(&data);
+
+        // 2026 coverage uses the 2025 guideline ($15,650): cliff at $62,600
+        let credit = calc.calculate(&input(dec!(58000)), 2026);
+        assert_eq!(credit.poverty_guideline, dec!(15650));
+        assert!(credit.warnings.is_empty());
+        let cliff = credit.cliff.unwrap();
+        assert_eq!(cliff.magi, dec!(62601));
+        assert!(cliff.credit_lost > dec!(900));
+
+        let over = calc.calculate(&input(dec!(62601)), 2026);
+        assert!(!over.eligible);
+        assert_eq!(over.credit, dec!(0));
+    }
+
+    #[test]
+    fn test_guideline_from_other_year_warns() {
+        let data = setup();
+        let calc = PremiumTaxCreditCalculator::new(&data);
+
+        // No 2026 guideline is embedded, so 2027 coverage reuses 2025's
+        let credit = calc.calculate(&input(dec!(40000)), 2027);
+        assert_eq!(credit.poverty_guideline, dec!(15650));
+        assert_eq!(
+            credit.warnings,
+            vec![CalcWarning::PovertyGuidelineFromOtherYear {
+                year: 2026,
+                guideline_year: 2025,
+            }]
+        );
+    }
+}
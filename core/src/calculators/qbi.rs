@@ -0,0 +1,231 @@
+//! Qualified business income deduction (§199A)
+//!
+//! Sole proprietors and pass-through owners deduct 20% of qualified
+//! business income, capped at 20% of taxable income (before this deduction)
+//! less net capital gain. Above the taxable income threshold, the deduction
+//! phases down over the phase-in range to the larger of 50% of the W-2 wages
+//! the business paid, or 25% of those wages plus 2.5% of its qualified
+//! property. A specified service business (health, law, consulting,
+//! financial services, and the like) also loses the income, wages, and
+//! property themselves over the range, leaving nothing above it.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::tax::FilingStatus;
+
+const QBI_RATE: Decimal = dec!(0.20);
+
+/// Business details that limit the QBI deduction above the threshold
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualifiedBusiness {
+    /// Partnership and S corporation income from Schedule K-1, not subject
+    /// to self-employment tax
+    pub pass_through_income: Decimal,
+    /// W-2 wages the businesses paid their employees
+    pub w2_wages: Decimal,
+    /// Unadjusted basis of qualified property the businesses hold
+    pub property_basis: Decimal,
+    /// Specified service trade or business
+    pub specified_service: bool,
+}
+
+/// QBI deduction and the limits that shaped it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QbiDeduction {
+    pub qualified_business_income: Decimal,
+    /// 20% of taxable income before the deduction, less net capital gain
+    pub taxable_income_limit: Decimal,
+    /// Larger of 50% of W-2 wages or 25% of wages plus 2.5% of property,
+    /// after any specified service reduction
+    pub wage_and_property_limit: Decimal,
+    /// Share of the phase-in range taxable income has passed (0 at or below
+    /// the threshold, 1 above the range)
+    pub phase_in: Decimal,
+    pub deduction: Decimal,
+}
+
+/// Qualified business income deduction calculator
+pub struct QbiDeductionCalculator;
+
+impl QbiDeductionCalculator {
+    /// Taxable income where the limits start, and the range over which
+    /// they fully apply
+    pub fn threshold(filing_status: FilingStatus, year: u32) -> (Decimal, Decimal) {
+        let (single, joint) = match year {
+            ..=2023 => (dec!(182100), dec!(364200)),
+            2024 => (dec!(191950), dec!(383900)),
+            _ => (dec!(197300), dec!(394600)),
+        };
+        match filing_status {
+            FilingStatus::MarriedFilingJointly | FilingStatus::QualifyingWidower => {
+                (joint, dec!(100000))
+            },
+            FilingStatus::Single
+            | FilingStatus::MarriedFilingSeparately
+            | FilingStatus::HeadOfHousehold => (single, dec!(50000)),
+        }
+    }
+
+    /// Deduction for `qualified_business_income` from `business` given
+    /// taxable income before the deduction and the net capital gain within it
+    pub fn calculate(
+        qualified_business_income: Decimal,
+        business: &QualifiedBusiness,
+        taxable_income: Decimal,
+        net_capital_gain: Decimal,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> QbiDeduction {
+        let taxable_income = taxable_income.max(Decimal::ZERO);
+        let taxable_income_limit =
+            ((taxable_income - net_capital_gain.max(Decimal::ZERO)) * QBI_RATE).max(Decimal::ZERO);
+
+        let (threshold, range) = Self::threshold(filing_status, year);
+        let phase_in = ((taxable_income - threshold).max(Decimal::ZERO) / range).min(Decimal::ONE);
+        // A specified service business counts only the share not yet phased in
+        let applicable = if business.specified_service {
+            Decimal::ONE - phase_in
+        } else {
+            Decimal::ONE
+        };
+
+        let qualified_business_income = qualified_business_income.max(Decimal::ZERO) * applicable;
+        let wages = business.w2_wages.max(Decimal::ZERO) * applicable;
+        let property = business.property_basis.max(Decimal::ZERO) * applicable;
+        let wage_and_property_limit =
+            (wages * dec!(0.50)).max(wages * dec!(0.25) + property * dec!(0.025));
+
+        let tentative = qualified_business_income * QBI_RATE;
+        let excess = (tentative - wage_and_property_limit).max(Decimal::ZERO);
+
+        QbiDeduction {
+            qualified_business_income,
+            taxable_income_limit,
+            wage_and_property_limit,
+            phase_in,
+            deduction: (tentative - excess * phase_in).min(taxable_income_limit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deduction(
+        qualified_business_income: Decimal,
+        business: &QualifiedBusiness,
+        taxable_income: Decimal,
+    ) -> Decimal {
+        QbiDeductionCalculator::calculate(
+            qualified_business_income,
+            business,
+            taxable_income,
+            dec!(0),
+            FilingStatus::Single,
+            2024,
+        )
+        .deduction
+    }
+
+    #[test]
+    fn test_below_threshold() {
+        let business = QualifiedBusiness::default();
+        // 20% of $80,000 of profit
+        assert_eq!(deduction(dec!(80000), &business, dec!(120000)), dec!(16000));
+
+        // Capped at 20% of taxable income less capital gains
+        let capped = QbiDeductionCalculator::calculate(
+            dec!(80000),
+            &business,
+            dec!(70000),
+            dec!(10000),
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(capped.deduction, dec!(12000));
+    }
+
+    #[test]
+    fn test_wage_limit_phases_in() {
+        let no_employees = QualifiedBusiness::default();
+        let with_employees = QualifiedBusiness {
+            w2_wages: dec!(60000),
+            ..Default::default()
+        };
+
+        // Halfway through the $50,000 range above $191,950: half the
+        // $40,000 excess over the wage limit is lost
+        assert_eq!(
+            deduction(dec!(200000), &no_employees, dec!(216950)),
+            dec!(20000)
+        );
+        assert_eq!(
+            deduction(dec!(200000), &with_employees, dec!(216950)),
+            dec!(35000)
+        );
+
+        // Above the range, the wage limit applies in full
+        assert_eq!(
+            deduction(dec!(200000), &no_employees, dec!(300000)),
+            dec!(0)
+        );
+        assert_eq!(
+            deduction(dec!(200000), &with_employees, dec!(300000)),
+            dec!(30000)
+        );
+
+        // Property counts at 2.5% alongside 25% of wages
+        let capital_heavy = QualifiedBusiness {
+            w2_wages: dec!(20000),
+            property_basis: dec!(1000000),
+            ..Default::default()
+        };
+        assert_eq!(
+            deduction(dec!(200000), &capital_heavy, dec!(300000)),
+            dec!(30000)
+        );
+
+        // The joint range is twice as wide and starts higher
+        let joint = QbiDeductionCalculator::calculate(
+            dec!(200000),
+            &no_employees,
+            dec!(216950),
+            dec!(0),
+            FilingStatus::MarriedFilingJointly,
+            2024,
+        );
+        assert_eq!(joint.deduction, dec!(40000));
+    }
+
+    #[test]
+    fn test_specified_service_phases_out() {
+        let consulting = QualifiedBusiness {
+            w2_wages: dec!(60000),
+            specified_service: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            deduction(dec!(80000), &consulting, dec!(120000)),
+            dec!(16000)
+        );
+
+        // Halfway through the range only half the income and wages count:
+        // $20,000 tentative against a $15,000 wage limit, half phased in
+        let halfway = QbiDeductionCalculator::calculate(
+            dec!(200000),
+            &consulting,
+            dec!(216950),
+            dec!(0),
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(halfway.qualified_business_income, dec!(100000));
+        assert_eq!(halfway.wage_and_property_limit, dec!(15000));
+        assert_eq!(halfway.deduction, dec!(17500));
+
+        assert_eq!(deduction(dec!(200000), &consulting, dec!(300000)), dec!(0));
+    }
+}
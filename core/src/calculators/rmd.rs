@@ -0,0 +1,135 @@
+//! Required Minimum Distribution (RMD) calculator
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::data::TaxDataProvider;
+
+/// RMD calculation result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RmdResult {
+    pub age: u32,
+    /// Age at which RMDs begin for this account owner
+    pub required_beginning_age: u32,
+    pub is_required: bool,
+    pub prior_year_end_balance: Decimal,
+    pub distribution_period: Option<Decimal>,
+    pub required_distribution: Decimal,
+}
+
+/// Required Minimum Distribution calculator
+pub struct RmdCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> RmdCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Age RMDs must begin under SECURE 2.0, based on birth year
+    pub fn required_beginning_age(birth_year: u32) -> u32 {
+        match birth_year {
+            0..=1950 => 72,
+            1951..=1959 => 73,
+            _ => 75,
+        }
+    }
+
+    /// Calculate the RMD for a tax year
+    ///
+    /// `prior_year_end_balance` is the account balance on December 31 of the
+    /// previous year.
+    pub fn calculate(
+        &self,
+        birth_year: u32,
+        prior_year_end_balance: Decimal,
+        year: u32,
+    ) -> RmdResult {
+        let age = year.saturating_sub(birth_year);
+        let required_beginning_age = Self::required_beginning_age(birth_year);
+        let is_required = age >= required_beginning_age;
+
+        let distribution_period = self.data_provider.rmd_distribution_period(age, year);
+
+        let required_distribution = match distribution_period {
+            Some(period) if is_required && prior_year_end_balance > Decimal::ZERO => {
+                prior_year_end_balance / period
+            },
+            _ => Decimal::ZERO,
+        };
+
+        RmdResult {
+            age,
+            required_beginning_age,
+            is_required,
+            prior_year_end_balance,
+            distribution_period: if is_required {
+                distribution_period
+            } else {
+                None
+            },
+            required_distribution,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_required_beginning_age() {
+        assert_eq!(RmdCalculator::required_beginning_age(1950), 72);
+        assert_eq!(RmdCalculator::required_beginning_age(1955), 73);
+        assert_eq!(RmdCalculator::required_beginning_age(1960), 75);
+    }
+
+    #[test]
+    fn test_rmd_at_75() {
+        let data = setup();
+        let calc = RmdCalculator::new(&data);
+
+        // Born 1949, age 75 in 2024: $500,000 / 24.6
+        let result = calc.calculate(1949, dec!(500000), 2024);
+
+        assert!(result.is_required);
+        assert_eq!(result.distribution_period, Some(dec!(24.6)));
+        assert_eq!(result.required_distribution, dec!(500000) / dec!(24.6));
+    }
+
+    #[test]
+    fn test_not_required_before_beginning_age() {
+        let data = setup();
+        let calc = RmdCalculator::new(&data);
+
+        // Born 1952, age 72 in 2024: RMDs start at 73
+        let result = calc.calculate(1952, dec!(500000), 2024);
+
+        assert!(!result.is_required);
+        assert_eq!(result.required_distribution, dec!(0));
+    }
+
+    #[test]
+    fn test_feeds_retirement_income() {
+        use crate::models::retirement::RetirementIncome;
+
+        let data = setup();
+        let calc = RmdCalculator::new(&data);
+        let rmd = calc.calculate(1949, dec!(246000), 2024);
+
+        let income = RetirementIncome {
+            traditional_withdrawals: dec!(5000),
+            ..Default::default()
+        }
+        .with_minimum_distribution(rmd.required_distribution);
+
+        assert_eq!(income.traditional_withdrawals, dec!(10000));
+    }
+}
@@ -0,0 +1,88 @@
+//! Self-Employment Contributions Act tax (Schedule SE)
+//!
+//! Self-employed workers pay both the employee and employer halves of
+//! Social Security and Medicare on 92.35% of net profit, the share left
+//! after taking out the employer half itself. W-2 wages earned the same
+//! year use up the Social Security wage base and the additional Medicare
+//! threshold first. The employer-equivalent half is deductible from income.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::calculators::FicaCalculator;
+use crate::data::TaxDataProvider;
+use crate::models::tax::{FilingStatus, SelfEmploymentTax};
+
+/// Portion of net profit treated as net earnings from self-employment
+pub(crate) const SE_EARNINGS_FACTOR: Decimal = dec!(0.9235);
+
+/// SECA tax calculator
+pub struct SecaCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> SecaCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Self-employment tax on net profit earned alongside `wages`
+    pub fn calculate(
+        &self,
+        net_profit: Decimal,
+        wages: Decimal,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> SelfEmploymentTax {
+        let fica = self.data_provider.fica_config(year);
+        let wages = wages.max(Decimal::ZERO);
+
+        // SE tax covers both the employee and employer halves of FICA
+        let net_earnings = net_profit.max(Decimal::ZERO) * SE_EARNINGS_FACTOR;
+        let wage_base_left = (fica.wage_base - wages).max(Decimal::ZERO);
+        let social_security =
+            net_earnings.min(wage_base_left) * fica.social_security_rate * dec!(2);
+        let medicare = net_earnings * fica.medicare_rate * dec!(2);
+        let threshold = (FicaCalculator::additional_medicare_threshold(filing_status) - wages)
+            .max(Decimal::ZERO);
+        let additional_medicare =
+            (net_earnings - threshold).max(Decimal::ZERO) * fica.additional_medicare_rate;
+
+        SelfEmploymentTax {
+            net_earnings,
+            social_security,
+            medicare,
+            additional_medicare,
+            total: social_security + medicare + additional_medicare,
+            deductible_half: (social_security + medicare) / dec!(2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_wages_use_up_wage_base_first() {
+        let data = setup();
+        let calc = SecaCalculator::new(&data);
+
+        // $150,000 of wages leaves $18,600 of the wage base for Social Security
+        let se = calc.calculate(dec!(40000), dec!(150000), FilingStatus::Single, 2024);
+        assert_eq!(se.net_earnings, dec!(36940));
+        assert_eq!(se.social_security, dec!(18600) * dec!(0.124));
+        assert_eq!(se.medicare, dec!(36940) * dec!(0.029));
+        // $186,940 of combined earnings is under the $200,000 threshold
+        assert_eq!(se.additional_medicare, dec!(0));
+
+        let over = calc.calculate(dec!(40000), dec!(190000), FilingStatus::Single, 2024);
+        assert_eq!(over.social_security, dec!(0));
+        assert_eq!(over.additional_medicare, dec!(26940) * dec!(0.009));
+    }
+}
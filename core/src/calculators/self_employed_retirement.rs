@@ -0,0 +1,300 @@
+//! Solo 401(k) and SEP-IRA maximum contribution calculator
+
+use rust_decimal::Decimal;
+
+use crate::calculators::{FederalTaxCalculator, SelfEmploymentTaxCalculator, StateTaxCalculator};
+use crate::data::TaxDataProvider;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Age at which catch-up deferrals become available
+const CATCH_UP_AGE: u32 = 50;
+
+/// Input for self-employed retirement contribution limits
+#[derive(Debug, Clone)]
+pub struct SelfEmployedRetirementInput {
+    /// Schedule C net profit
+    pub net_profit: Decimal,
+    pub age: u32,
+    pub filing_status: FilingStatus,
+    pub state: USState,
+    /// Elective deferrals already made to other plans (e.g. a W-2 job's 401(k))
+    pub other_elective_deferrals: Decimal,
+}
+
+/// Maximum contribution to a single plan type and its tax effect
+#[derive(Debug, Clone, Default)]
+pub struct PlanContribution {
+    pub employee_deferral: Decimal,
+    pub catch_up: Decimal,
+    pub employer_contribution: Decimal,
+    pub total: Decimal,
+    pub federal_tax_savings: Decimal,
+    pub state_tax_savings: Decimal,
+    pub total_tax_savings: Decimal,
+}
+
+/// Solo 401(k) vs SEP-IRA contribution result
+#[derive(Debug, Clone)]
+pub struct SelfEmployedRetirementResult {
+    pub net_earnings_from_se: Decimal,
+    pub self_employment_tax: Decimal,
+    pub se_tax_deduction: Decimal,
+    /// Net profit less the deductible half of SE tax, capped at the compensation limit
+    pub plan_compensation: Decimal,
+    pub sep_ira: PlanContribution,
+    pub solo_401k: PlanContribution,
+}
+
+/// Self-employed retirement contribution calculator
+pub struct SelfEmployedRetirementCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> SelfEmployedRetirementCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Calculate maximum SEP-IRA and solo 401(k) contributions
+    pub fn calculate(
+        &self,
+        input: &SelfEmployedRetirementInput,
+        year: u32,
+    ) -> SelfEmployedRetirementResult {
+        let limits = self.data_provider.retirement_limits(year);
+
+        let profit = input.net_profit.max(Decimal::ZERO);
+
+        let se = SelfEmploymentTaxCalculator::new(self.data_provider).self_employment_tax(
+            profit,
+            input.filing_status,
+            year,
+        );
+        let se_tax_deduction = se.deductible_half;
+
+        let plan_compensation = (profit - se_tax_deduction)
+            .max(Decimal::ZERO)
+            .min(limits.compensation_limit);
+
+        // The employer contribution for an owner is a percentage of earnings
+        // *after* subtracting the contribution itself, so the nominal 25% rate
+        // resolves to rate / (1 + rate) = 20% of pre-contribution compensation.
+        let rate = limits.employer_contribution_rate;
+        let employer_max = plan_compensation * rate / (Decimal::ONE + rate);
+
+        // SEP-IRA: employer contribution only
+        let sep_employer = employer_max.min(limits.total_annual_additions);
+
+        // Solo 401(k): employee deferral + catch-up + employer contribution
+        let deferral_room =
+            (limits.elective_deferral - input.other_elective_deferrals).max(Decimal::ZERO);
+        let employee_deferral = deferral_room.min(plan_compensation);
+        let catch_up = if input.age >= CATCH_UP_AGE {
+            limits
+                .catch_up_deferral
+                .min(plan_compensation - employee_deferral)
+        } else {
+            Decimal::ZERO
+        };
+        let solo_employer = employer_max
+            .min(limits.total_annual_additions - employee_deferral)
+            .min(plan_compensation - employee_deferral - catch_up)
+            .max(Decimal::ZERO);
+
+        let agi_before_contributions = profit - se_tax_deduction;
+
+        let sep_ira = self.with_tax_savings(
+            Decimal::ZERO,
+            Decimal::ZERO,
+            sep_employer,
+            agi_before_contributions,
+            input,
+            year,
+        );
+        let solo_401k = self.with_tax_savings(
+            employee_deferral,
+            catch_up,
+            solo_employer,
+            agi_before_contributions,
+            input,
+            year,
+        );
+
+        SelfEmployedRetirementResult {
+            net_earnings_from_se: se.net_earnings,
+            self_employment_tax: se.total,
+            se_tax_deduction,
+            plan_compensation,
+            sep_ira,
+            solo_401k,
+        }
+    }
+
+    /// Build a plan contribution with income tax savings versus contributing nothing
+    fn with_tax_savings(
+        &self,
+        employee_deferral: Decimal,
+        catch_up: Decimal,
+        employer_contribution: Decimal,
+        agi_before_contributions: Decimal,
+        input: &SelfEmployedRetirementInput,
+        year: u32,
+    ) -> PlanContribution {
+        let total = employee_deferral + catch_up + employer_contribution;
+
+        let (federal_before, state_before) =
+            self.income_taxes(agi_before_contributions, input, year);
+        let (federal_after, state_after) =
+            self.income_taxes(agi_before_contributions - total, input, year);
+
+        let federal_tax_savings = federal_before - federal_after;
+        let state_tax_savings = state_before - state_after;
+
+        PlanContribution {
+            employee_deferral,
+            catch_up,
+            employer_contribution,
+            total,
+            federal_tax_savings,
+            state_tax_savings,
+            total_tax_savings: federal_tax_savings + state_tax_savings,
+        }
+    }
+
+    /// Federal and state income tax (excluding SDI/local) on an AGI
+    fn income_taxes(
+        &self,
+        agi: Decimal,
+        input: &SelfEmployedRetirementInput,
+        year: u32,
+    ) -> (Decimal, Decimal) {
+        let federal_calc = FederalTaxCalculator::new(self.data_provider);
+        let state_calc = StateTaxCalculator::new(self.data_provider);
+
+        let std_deduction = federal_calc.standard_deduction(input.filing_status, year);
+        let federal_taxable = (agi - std_deduction).max(Decimal::ZERO);
+        let federal = federal_calc
+            .calculate(federal_taxable, input.filing_status, year)
+            .tax;
+
+        let state = state_calc
+            .calculate(
+                agi.max(Decimal::ZERO),
+                input.state,
+                input.filing_status,
+                year,
+            )
+            .income_tax;
+
+        (federal, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn input(net_profit: Decimal, age: u32) -> SelfEmployedRetirementInput {
+        SelfEmployedRetirementInput {
+            net_profit,
+            age,
+            filing_status: FilingStatus::Single,
+            state: USState::Texas,
+            other_elective_deferrals: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_sep_is_twenty_percent_of_adjusted_earnings() {
+        let data = setup();
+        let calc = SelfEmployedRetirementCalculator::new(&data);
+
+        let result = calc.calculate(&input(dec!(100000), 40), 2024);
+
+        // SE tax: $92,350 × 15.3% = $14,129.55, half = $7,064.775
+        assert_eq!(result.self_employment_tax, dec!(14129.55));
+        assert_eq!(result.plan_compensation, dec!(92935.225));
+        // 20% of $92,935.225 = $18,587.045
+        assert_eq!(result.sep_ira.total, dec!(18587.045));
+    }
+
+    #[test]
+    fn test_solo_401k_adds_employee_deferral() {
+        let data = setup();
+        let calc = SelfEmployedRetirementCalculator::new(&data);
+
+        let result = calc.calculate(&input(dec!(100000), 40), 2024);
+
+        assert_eq!(result.solo_401k.employee_deferral, dec!(23000));
+        assert_eq!(result.solo_401k.catch_up, dec!(0));
+        assert_eq!(
+            result.solo_401k.employer_contribution,
+            result.sep_ira.employer_contribution
+        );
+        assert!(result.solo_401k.total > result.sep_ira.total);
+    }
+
+    #[test]
+    fn test_catch_up_at_fifty() {
+        let data = setup();
+        let calc = SelfEmployedRetirementCalculator::new(&data);
+
+        let result = calc.calculate(&input(dec!(100000), 55), 2024);
+
+        assert_eq!(result.solo_401k.catch_up, dec!(7500));
+    }
+
+    #[test]
+    fn test_total_additions_cap() {
+        let data = setup();
+        let calc = SelfEmployedRetirementCalculator::new(&data);
+
+        let result = calc.calculate(&input(dec!(500000), 55), 2024);
+
+        // $69,000 annual additions + $7,500 catch-up
+        assert_eq!(result.solo_401k.total, dec!(76500));
+        assert_eq!(result.sep_ira.total, dec!(69000));
+        // Compensation limit applies
+        assert_eq!(result.plan_compensation, dec!(345000));
+    }
+
+    #[test]
+    fn test_other_deferrals_reduce_room() {
+        let data = setup();
+        let calc = SelfEmployedRetirementCalculator::new(&data);
+
+        let mut with_w2 = input(dec!(100000), 40);
+        with_w2.other_elective_deferrals = dec!(15000);
+
+        let result = calc.calculate(&with_w2, 2024);
+
+        assert_eq!(result.solo_401k.employee_deferral, dec!(8000));
+    }
+
+    #[test]
+    fn test_tax_savings_include_state() {
+        let data = setup();
+        let calc = SelfEmployedRetirementCalculator::new(&data);
+
+        let mut ca = input(dec!(150000), 40);
+        ca.state = USState::California;
+
+        let result = calc.calculate(&ca, 2024);
+
+        assert!(result.solo_401k.federal_tax_savings > dec!(0));
+        assert!(result.solo_401k.state_tax_savings > dec!(0));
+        assert_eq!(
+            result.solo_401k.total_tax_savings,
+            result.solo_401k.federal_tax_savings + result.solo_401k.state_tax_savings
+        );
+        // Savings can never exceed the contribution itself
+        assert!(result.solo_401k.total_tax_savings < result.solo_401k.total);
+    }
+}
@@ -0,0 +1,326 @@
+//! Self-employment tax and contractor take-home
+//!
+//! Net profit from a sole proprietorship or 1099 work owes both halves of
+//! FICA as self-employment tax (see [`seca`](crate::calculators::seca)).
+//! Half of the Social Security and Medicare portion is deductible when
+//! computing AGI.
+//!
+//! A home office used regularly and exclusively for the business is a
+//! Schedule C expense, figured either at a flat rate per square foot or as
+//! the office's share of actual home costs. Either way it can't push the
+//! business into a loss. A car driven for the business is deducted under
+//! whichever vehicle expense method is larger.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::seca::SecaCalculator;
+use crate::calculators::state::StateTaxInput;
+use crate::calculators::vehicle_expense::{VehicleExpenseCalculator, VehicleUse};
+use crate::calculators::{FederalTaxCalculator, StateTaxCalculator};
+use crate::data::TaxDataProvider;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+pub use crate::models::tax::SelfEmploymentTax;
+
+/// Simplified method rate per square foot of office
+const SIMPLIFIED_RATE: Decimal = dec!(5);
+
+/// Most office space the simplified method counts
+const SIMPLIFIED_MAX_SQUARE_FEET: Decimal = dec!(300);
+
+/// How the home office deduction is figured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HomeOffice {
+    /// $5 per square foot, up to 300 square feet
+    Simplified { square_feet: Decimal },
+    /// Office's share of the home's mortgage interest or rent, taxes,
+    /// insurance, utilities, repairs, and depreciation (Form 8829)
+    Actual {
+        office_square_feet: Decimal,
+        home_square_feet: Decimal,
+        home_expenses: Decimal,
+    },
+}
+
+impl HomeOffice {
+    /// Deduction before the business income limit
+    pub fn tentative_deduction(&self) -> Decimal {
+        match *self {
+            Self::Simplified { square_feet } => {
+                square_feet.clamp(Decimal::ZERO, SIMPLIFIED_MAX_SQUARE_FEET) * SIMPLIFIED_RATE
+            },
+            Self::Actual {
+                office_square_feet,
+                home_square_feet,
+                home_expenses,
+            } => {
+                if home_square_feet <= Decimal::ZERO {
+                    return Decimal::ZERO;
+                }
+                let business_share =
+                    (office_square_feet / home_square_feet).clamp(Decimal::ZERO, Decimal::ONE);
+                (home_expenses.max(Decimal::ZERO) * business_share).round_dp(2)
+            },
+        }
+    }
+}
+
+/// A sole proprietorship's year (Schedule C)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleC {
+    pub gross_receipts: Decimal,
+    /// Business expenses other than the home office and vehicle
+    pub expenses: Decimal,
+    #[serde(default)]
+    pub home_office: Option<HomeOffice>,
+    #[serde(default)]
+    pub vehicle: Option<VehicleUse>,
+}
+
+/// Taxes and take-home on self-employment net profit
+#[derive(Debug, Clone, Default)]
+pub struct SelfEmploymentResult {
+    pub net_profit: Decimal,
+    /// Home office expense included in net profit
+    pub home_office_deduction: Decimal,
+    /// Vehicle expense included in net profit
+    pub vehicle_deduction: Decimal,
+    pub self_employment_tax: SelfEmploymentTax,
+    pub adjusted_gross_income: Decimal,
+    pub federal_income_tax: Decimal,
+    /// State and local income tax
+    pub state_income_tax: Decimal,
+    pub total_tax: Decimal,
+    pub after_tax_income: Decimal,
+}
+
+/// Self-employment tax calculator
+pub struct SelfEmploymentTaxCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> SelfEmploymentTaxCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Self-employment tax on net profit (Schedule SE)
+    pub fn self_employment_tax(
+        &self,
+        net_profit: Decimal,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> SelfEmploymentTax {
+        SecaCalculator::new(self.data_provider).calculate(
+            net_profit,
+            Decimal::ZERO,
+            filing_status,
+            year,
+        )
+    }
+
+    /// Self-employment and income taxes on net profit with no other income
+    ///
+    /// Uses the standard deduction and no qualified business income
+    /// deduction, so results are conservative for eligible businesses.
+    pub fn calculate(
+        &self,
+        net_profit: Decimal,
+        filing_status: FilingStatus,
+        state: USState,
+        year: u32,
+    ) -> SelfEmploymentResult {
+        let self_employment_tax = self.self_employment_tax(net_profit, filing_status, year);
+        let agi = (net_profit - self_employment_tax.deductible_half).max(Decimal::ZERO);
+
+        let federal_calc = FederalTaxCalculator::new(self.data_provider);
+        let std_deduction = federal_calc.standard_deduction(filing_status, year);
+        let federal_income_tax = federal_calc
+            .calculate(
+                (agi - std_deduction).max(Decimal::ZERO),
+                filing_status,
+                year,
+            )
+            .tax;
+
+        // No wages, so no SDI or payroll programs
+        let state_result = StateTaxCalculator::new(self.data_provider).calculate_input(
+            &StateTaxInput {
+                wages: Decimal::ZERO,
+                federal_income_tax,
+                ..StateTaxInput::new(agi, state, filing_status)
+            },
+            year,
+        );
+        let state_income_tax = state_result.income_tax + state_result.local_tax;
+
+        let total_tax = self_employment_tax.total + federal_income_tax + state_income_tax;
+
+        SelfEmploymentResult {
+            net_profit,
+            adjusted_gross_income: agi,
+            federal_income_tax,
+            state_income_tax,
+            total_tax,
+            after_tax_income: net_profit - total_tax,
+            self_employment_tax,
+            home_office_deduction: Decimal::ZERO,
+            vehicle_deduction: Decimal::ZERO,
+        }
+    }
+
+    /// Self-employment and income taxes on a Schedule C business
+    pub fn calculate_schedule_c(
+        &self,
+        schedule: &ScheduleC,
+        filing_status: FilingStatus,
+        state: USState,
+        year: u32,
+    ) -> SelfEmploymentResult {
+        SelfEmploymentResult {
+            home_office_deduction: self.home_office_deduction(schedule, year),
+            vehicle_deduction: self.vehicle_deduction(schedule, year),
+            ..self.calculate(self.net_profit(schedule, year), filing_status, state, year)
+        }
+    }
+
+    /// Vehicle expense under the larger of the standard mileage rate and
+    /// actual costs
+    pub fn vehicle_deduction(&self, schedule: &ScheduleC, year: u32) -> Decimal {
+        schedule.vehicle.as_ref().map_or(Decimal::ZERO, |vehicle| {
+            VehicleExpenseCalculator::new(self.data_provider)
+                .compare(vehicle, year)
+                .deduction()
+        })
+    }
+
+    /// Home office deduction, limited to profit before it
+    ///
+    /// Actual expenses disallowed by the limit carry over to next year's
+    /// Form 8829; the simplified method's excess is lost.
+    pub fn home_office_deduction(&self, schedule: &ScheduleC, year: u32) -> Decimal {
+        let profit =
+            (schedule.gross_receipts - schedule.expenses - self.vehicle_deduction(schedule, year))
+                .max(Decimal::ZERO);
+        schedule
+            .home_office
+            .as_ref()
+            .map_or(Decimal::ZERO, |office| {
+                office.tentative_deduction().min(profit)
+            })
+    }
+
+    /// Net profit after all expenses, including the vehicle and home office
+    pub fn net_profit(&self, schedule: &ScheduleC, year: u32) -> Decimal {
+        schedule.gross_receipts
+            - schedule.expenses
+            - self.vehicle_deduction(schedule, year)
+            - self.home_office_deduction(schedule, year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_self_employment_tax() {
+        let data = setup();
+        let calc = SelfEmploymentTaxCalculator::new(&data);
+
+        let se = calc.self_employment_tax(dec!(100000), FilingStatus::Single, 2024);
+
+        // $92,350 × 15.3%
+        assert_eq!(se.net_earnings, dec!(92350));
+        assert_eq!(se.total, dec!(14129.55));
+        assert_eq!(se.deductible_half, dec!(7064.775));
+
+        // Social Security stops at the wage base; additional Medicare starts at $200,000
+        let high = calc.self_employment_tax(dec!(300000), FilingStatus::Single, 2024);
+        assert_eq!(high.social_security, dec!(168600) * dec!(0.124));
+        assert_eq!(high.additional_medicare, dec!(77050) * dec!(0.009));
+    }
+
+    #[test]
+    fn test_contractor_take_home() {
+        let data = setup();
+        let calc = SelfEmploymentTaxCalculator::new(&data);
+
+        let result = calc.calculate(dec!(100000), FilingStatus::Single, USState::Texas, 2024);
+
+        assert_eq!(result.adjusted_gross_income, dec!(92935.225));
+        assert_eq!(result.state_income_tax, dec!(0));
+        assert_eq!(
+            result.after_tax_income,
+            dec!(100000) - dec!(14129.55) - result.federal_income_tax
+        );
+    }
+
+    #[test]
+    fn test_home_office_deduction() {
+        let data = setup();
+        let calc = SelfEmploymentTaxCalculator::new(&data);
+        let business = ScheduleC {
+            gross_receipts: dec!(110000),
+            expenses: dec!(10000),
+            ..Default::default()
+        };
+
+        // 400 square feet, but the simplified method stops at 300
+        let simplified = ScheduleC {
+            home_office: Some(HomeOffice::Simplified {
+                square_feet: dec!(400),
+            }),
+            ..business.clone()
+        };
+        assert_eq!(calc.home_office_deduction(&simplified, 2024), dec!(1500));
+        assert_eq!(calc.net_profit(&simplified, 2024), dec!(98500));
+
+        // 200 of 2,000 square feet: 10% of $30,000
+        let actual = ScheduleC {
+            home_office: Some(HomeOffice::Actual {
+                office_square_feet: dec!(200),
+                home_square_feet: dec!(2000),
+                home_expenses: dec!(30000),
+            }),
+            ..business.clone()
+        };
+        assert_eq!(calc.home_office_deduction(&actual, 2024), dec!(3000));
+
+        let base = calc.calculate_schedule_c(&business, FilingStatus::Single, USState::Texas, 2024);
+        let result = calc.calculate_schedule_c(&actual, FilingStatus::Single, USState::Texas, 2024);
+        assert_eq!(result.net_profit, dec!(97000));
+        assert_eq!(result.home_office_deduction, dec!(3000));
+        assert!(result.self_employment_tax.total < base.self_employment_tax.total);
+        assert!(result.federal_income_tax < base.federal_income_tax);
+
+        // Can't create a loss
+        let thin = ScheduleC {
+            gross_receipts: dec!(12000),
+            expenses: dec!(11000),
+            ..actual
+        };
+        assert_eq!(calc.home_office_deduction(&thin, 2024), dec!(1000));
+        assert_eq!(calc.net_profit(&thin, 2024), dec!(0));
+
+        // Vehicle costs come off first, leaving less room for the office
+        let driving = ScheduleC {
+            vehicle: Some(VehicleUse {
+                business_miles: dec!(1000),
+                total_miles: dec!(10000),
+                ..Default::default()
+            }),
+            ..thin
+        };
+        assert_eq!(calc.vehicle_deduction(&driving, 2024), dec!(670));
+        assert_eq!(calc.home_office_deduction(&driving, 2024), dec!(330));
+        assert_eq!(calc.net_profit(&driving, 2024), dec!(0));
+    }
+}
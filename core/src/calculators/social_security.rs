@@ -0,0 +1,124 @@
+//! Social Security benefit taxation calculator
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::models::tax::FilingStatus;
+
+/// Taxation of Social Security benefits (IRC §86)
+///
+/// The base and adjusted thresholds are statutory and not inflation indexed.
+pub struct SocialSecurityTaxation;
+
+impl SocialSecurityTaxation {
+    /// Provisional income thresholds (base, adjusted) for a filing status
+    fn thresholds(filing_status: FilingStatus) -> (Decimal, Decimal) {
+        match filing_status {
+            FilingStatus::MarriedFilingJointly => (dec!(32000), dec!(44000)),
+            // Assumes spouses lived together during the year
+            FilingStatus::MarriedFilingSeparately => (dec!(0), dec!(0)),
+            FilingStatus::Single
+            | FilingStatus::HeadOfHousehold
+            | FilingStatus::QualifyingWidower => (dec!(25000), dec!(34000)),
+        }
+    }
+
+    /// Provisional income: other income plus half of benefits
+    pub fn provisional_income(benefits: Decimal, other_income: Decimal) -> Decimal {
+        other_income + benefits / dec!(2)
+    }
+
+    /// Taxable portion of Social Security benefits
+    ///
+    /// `other_income` is AGI excluding Social Security plus tax-exempt interest.
+    pub fn taxable_benefits(
+        benefits: Decimal,
+        other_income: Decimal,
+        filing_status: FilingStatus,
+    ) -> Decimal {
+        if benefits <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        let (base, adjusted) = Self::thresholds(filing_status);
+        let provisional = Self::provisional_income(benefits, other_income);
+
+        if provisional <= base {
+            return Decimal::ZERO;
+        }
+
+        // Up to 50% of benefits for income between the thresholds
+        let tier_one = ((provisional.min(adjusted) - base) / dec!(2)).min(benefits / dec!(2));
+
+        if provisional <= adjusted {
+            return tier_one;
+        }
+
+        // 85% of income above the adjusted threshold, plus the smaller of the
+        // tier-one amount or half the threshold spread, capped at 85% of benefits
+        let tier_two =
+            (provisional - adjusted) * dec!(0.85) + tier_one.min((adjusted - base) / dec!(2));
+
+        tier_two.min(benefits * dec!(0.85))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_below_base_threshold() {
+        let taxable = SocialSecurityTaxation::taxable_benefits(
+            dec!(20000),
+            dec!(10000),
+            FilingStatus::Single,
+        );
+        // Provisional: $10,000 + $10,000 = $20,000 < $25,000
+        assert_eq!(taxable, dec!(0));
+    }
+
+    #[test]
+    fn test_between_thresholds() {
+        let taxable = SocialSecurityTaxation::taxable_benefits(
+            dec!(20000),
+            dec!(20000),
+            FilingStatus::Single,
+        );
+        // Provisional: $30,000, 50% of $5,000 over base
+        assert_eq!(taxable, dec!(2500));
+    }
+
+    #[test]
+    fn test_above_adjusted_threshold() {
+        let taxable = SocialSecurityTaxation::taxable_benefits(
+            dec!(24000),
+            dec!(40000),
+            FilingStatus::Single,
+        );
+        // Provisional: $52,000
+        // 85% × $18,000 + min($4,500, $4,500) = $19,800 (under the $20,400 cap)
+        assert_eq!(taxable, dec!(19800));
+    }
+
+    #[test]
+    fn test_capped_at_85_percent() {
+        let taxable = SocialSecurityTaxation::taxable_benefits(
+            dec!(30000),
+            dec!(200000),
+            FilingStatus::MarriedFilingJointly,
+        );
+        assert_eq!(taxable, dec!(25500));
+    }
+
+    #[test]
+    fn test_mfs_taxes_from_first_dollar() {
+        let taxable = SocialSecurityTaxation::taxable_benefits(
+            dec!(20000),
+            dec!(0),
+            FilingStatus::MarriedFilingSeparately,
+        );
+        // Provisional $10,000 entirely above zero thresholds
+        assert_eq!(taxable, dec!(8500));
+    }
+}
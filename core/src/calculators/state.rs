@@ -1,10 +1,84 @@
 //! State tax calculator
 
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
 
-use crate::data::TaxDataProvider;
+use crate::data::{SchoolDistrictBase, StateDeductionRule, StateTaxType, TaxDataProvider};
 use crate::models::state::USState;
-use crate::models::tax::{BracketAmount, FilingStatus, StateTaxResult, TaxBracket};
+use crate::models::tax::{
+    BracketAmount, FilingStatus, PayrollContribution, PayrollProgramKind, SdiCoverage,
+    StateTaxResult, TaxBracket,
+};
+
+/// Pay stub name of a state's disability insurance program
+pub(crate) fn disability_program_name(state: USState) -> &'static str {
+    match state {
+        USState::California => "SDI",
+        USState::NewYork => "DBL",
+        _ => "TDI",
+    }
+}
+
+/// Detailed input for a state tax calculation
+#[derive(Debug, Clone, Default)]
+pub struct StateTaxInput {
+    /// State income before state deductions and retirement adjustments
+    pub taxable_income: Decimal,
+    /// Payroll wages subject to SDI
+    pub wages: Decimal,
+    pub state: USState,
+    pub filing_status: FilingStatus,
+    /// Retirement distributions and pensions included in `taxable_income`
+    pub retirement_distributions: Decimal,
+    /// Federally taxable Social Security benefits (not included in `taxable_income`)
+    pub taxable_social_security: Decimal,
+    /// Taxpayer age, for age-gated exclusions
+    pub age: u32,
+    /// HSA contributions excluded from `taxable_income`
+    pub hsa_contributions: Decimal,
+    /// Federal income tax liability, for states that allow deducting it
+    pub federal_income_tax: Decimal,
+    /// School district number, for states with school district income taxes
+    pub school_district: Option<String>,
+    /// County or city of residence, for states with local income taxes
+    pub residence_locality: Option<String>,
+    /// City where wages are earned, for commuter wage taxes
+    pub work_locality: Option<String>,
+    /// Known local tax rate, used in place of any locality lookup or estimate
+    pub local_tax_rate_override: Option<Decimal>,
+    /// Whether SDI is withheld by the state plan, a voluntary plan, or not at all
+    pub sdi_coverage: SdiCoverage,
+    /// Portion of `wages` earned by the spouse on a joint return
+    pub spouse_wages: Decimal,
+    /// Property taxes paid on a primary residence or vehicle
+    pub property_taxes: Decimal,
+}
+
+impl StateTaxInput {
+    /// Input where all taxable income is wages
+    pub fn new(taxable_income: Decimal, state: USState, filing_status: FilingStatus) -> Self {
+        Self {
+            taxable_income,
+            wages: taxable_income,
+            state,
+            filing_status,
+            ..Default::default()
+        }
+    }
+}
+
+/// Look up a per-filing-status amount, defaulting to zero
+fn filing_amount(
+    amounts: &Option<HashMap<String, Decimal>>,
+    filing_status: FilingStatus,
+) -> Decimal {
+    amounts
+        .as_ref()
+        .and_then(|a| a.get(filing_status.as_str()))
+        .copied()
+        .unwrap_or(Decimal::ZERO)
+}
 
 /// State tax calculator
 pub struct StateTaxCalculator<'a> {
@@ -24,52 +98,99 @@ impl<'a> StateTaxCalculator<'a> {
         filing_status: FilingStatus,
         year: u32,
     ) -> StateTaxResult {
-        // No income tax states
+        self.calculate_input(
+            &StateTaxInput::new(taxable_income, state, filing_status),
+            year,
+        )
+    }
+
+    /// Calculate state income tax from a detailed input
+    pub fn calculate_input(&self, input: &StateTaxInput, year: u32) -> StateTaxResult {
+        let state = input.state;
+        let filing_status = input.filing_status;
+
+        let config = self.data_provider.state_config(state, year);
+
+        // No income tax states, though some still run payroll programs
         if state.has_no_income_tax() {
+            let payroll_programs =
+                self.payroll_programs(input.wages, state, input.sdi_coverage, &config);
+            let sdi = payroll_programs.iter().map(|p| p.amount).sum::<Decimal>();
             return StateTaxResult {
                 state_code: state.code().to_string(),
-                taxable_income,
+                taxable_income: input.taxable_income,
                 income_tax: Decimal::ZERO,
                 local_tax: Decimal::ZERO,
-                sdi: Decimal::ZERO,
-                total_tax: Decimal::ZERO,
+                school_district_tax: Decimal::ZERO,
+                sdi,
+                total_tax: sdi,
                 effective_rate: Decimal::ZERO,
                 bracket_breakdown: None,
+                local_tax_overridden: false,
+                local_tax_estimated: false,
+                single_brackets_used: false,
+                payroll_programs,
             };
         }
 
-        let config = self.data_provider.state_config(state, year);
+        let taxable_income = self.apply_state_adjustments(input, &config);
+
+        // State deductions apply to flat and progressive states alike
+        let std_deduction = self.standard_deduction(&config, taxable_income, filing_status, year);
+        let exemption = filing_amount(&config.personal_exemption, filing_status)
+            - config
+                .personal_exemption_phase_out
+                .as_ref()
+                .map(|phase_out| phase_out.reduction(taxable_income, filing_status))
+                .unwrap_or(Decimal::ZERO);
+        let exemption = exemption.max(Decimal::ZERO);
+        let federal_deduction = if config.deducts_federal_income_tax {
+            input.federal_income_tax.max(Decimal::ZERO)
+        } else {
+            Decimal::ZERO
+        };
+        let adjusted_income =
+            (taxable_income - std_deduction - exemption - federal_deduction).max(Decimal::ZERO);
 
         // Calculate income tax
-        let (income_tax, breakdown) = if state.has_flat_tax() {
-            let tax = taxable_income * config.flat_rate.unwrap_or(Decimal::ZERO);
+        let (income_tax, breakdown) = if config.tax_type == StateTaxType::FlatRate {
+            let tax = adjusted_income * config.flat_rate.unwrap_or(Decimal::ZERO);
             (tax, None)
+        } else if config.tax_type == StateTaxType::MirrorFederal {
+            (input.federal_income_tax.max(Decimal::ZERO), None)
         } else {
-            // Progressive brackets
+            // Progressive brackets, falling back to the single schedule
             let brackets = config
-                .brackets
-                .get(filing_status.as_str())
-                .cloned()
+                .brackets_for(filing_status)
+                .map(<[_]>::to_vec)
                 .unwrap_or_default();
 
-            let std_deduction = config
-                .standard_deduction
-                .as_ref()
-                .and_then(|d| d.get(filing_status.as_str()))
-                .copied()
-                .unwrap_or(Decimal::ZERO);
-
-            let adjusted_income = (taxable_income - std_deduction).max(Decimal::ZERO);
-            self.calculate_progressive(adjusted_income, &brackets)
+            let (tax, breakdown) = self.calculate_progressive(adjusted_income, &brackets);
+            let tax = tax + self.recapture(taxable_income, filing_status, &config);
+            let tax = tax
+                * (Decimal::ONE
+                    - self.percentage_credit_rate(taxable_income, filing_status, &config));
+            (tax, breakdown)
         };
 
-        // Calculate SDI if applicable
-        let sdi = self.calculate_sdi(taxable_income, state, &config);
+        // Personal credits can't reduce the tax below zero
+        let credit = self.personal_credit(taxable_income, filing_status, &config)
+            + self.married_couple_credit(input, &config)
+            + self.property_tax_credit(input, taxable_income, &config);
+        let income_tax = (income_tax - credit).max(Decimal::ZERO);
+
+        // Calculate SDI and other payroll programs if applicable
+        let payroll_programs =
+            self.payroll_programs(input.wages, state, input.sdi_coverage, &config);
+        let sdi = payroll_programs.iter().map(|p| p.amount).sum::<Decimal>();
 
         // Estimate local tax if applicable
-        let local_tax = self.estimate_local_tax(taxable_income, state, &config);
+        let (local_tax, local_tax_estimated) =
+            self.estimate_local_tax(input, taxable_income, adjusted_income, &config);
+
+        let school_district_tax = self.calculate_school_district(input, taxable_income, &config);
 
-        let total_tax = income_tax + sdi + local_tax;
+        let total_tax = income_tax + sdi + local_tax + school_district_tax;
         let effective_rate = if taxable_income > Decimal::ZERO {
             total_tax / taxable_income
         } else {
@@ -81,11 +202,181 @@ impl<'a> StateTaxCalculator<'a> {
             taxable_income,
             income_tax,
             local_tax,
+            school_district_tax,
             sdi,
             total_tax,
             effective_rate,
             bracket_breakdown: breakdown,
+            local_tax_overridden: input.local_tax_rate_override.is_some(),
+            local_tax_estimated,
+            single_brackets_used: config.uses_single_brackets(filing_status),
+            payroll_programs,
+        }
+    }
+
+    /// State standard deduction under the state's deduction rule
+    fn standard_deduction(
+        &self,
+        config: &crate::data::StateConfig,
+        income: Decimal,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> Decimal {
+        let status_amount = |amounts: &HashMap<String, Decimal>| {
+            amounts
+                .get(filing_status.as_str())
+                .copied()
+                .unwrap_or(Decimal::ZERO)
+        };
+
+        match &config.standard_deduction {
+            StateDeductionRule::None => Decimal::ZERO,
+            StateDeductionRule::Fixed(amounts) => status_amount(amounts),
+            StateDeductionRule::Federal => {
+                self.data_provider.standard_deduction(filing_status, year)
+            },
+            StateDeductionRule::FederalPercentage(share) => {
+                self.data_provider.standard_deduction(filing_status, year) * share
+            },
+            StateDeductionRule::PercentageOfIncome { rate, floor, cap } => (income * rate)
+                .max(status_amount(floor))
+                .min(status_amount(cap)),
+        }
+    }
+
+    /// Personal credit after any income-based phase-out
+    fn personal_credit(
+        &self,
+        taxable_income: Decimal,
+        filing_status: FilingStatus,
+        config: &crate::data::StateConfig,
+    ) -> Decimal {
+        let credit = filing_amount(&config.personal_credit, filing_status);
+        let reduction = config
+            .personal_credit_phase_out
+            .as_ref()
+            .map(|phase_out| phase_out.reduction(taxable_income, filing_status))
+            .unwrap_or(Decimal::ZERO);
+
+        (credit - reduction).max(Decimal::ZERO)
+    }
+
+    /// Share of tax forgiven by a percentage-of-tax credit at this income
+    fn percentage_credit_rate(
+        &self,
+        income: Decimal,
+        filing_status: FilingStatus,
+        config: &crate::data::StateConfig,
+    ) -> Decimal {
+        config
+            .percentage_credit_tiers
+            .as_ref()
+            .and_then(|tiers| tiers.get(filing_status.as_str()))
+            .and_then(|tiers| tiers.iter().find(|(upper, _)| income <= *upper))
+            .map(|(_, rate)| *rate)
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Added tax per income step (or fraction thereof) above the threshold
+    fn recapture(
+        &self,
+        income: Decimal,
+        filing_status: FilingStatus,
+        config: &crate::data::StateConfig,
+    ) -> Decimal {
+        let Some(recapture) = config.recapture.get(filing_status.as_str()) else {
+            return Decimal::ZERO;
+        };
+        if income <= recapture.threshold {
+            return Decimal::ZERO;
+        }
+
+        let steps = ((income - recapture.threshold) / recapture.step).ceil();
+        (steps * recapture.amount_per_step).min(recapture.max)
+    }
+
+    /// Property tax credit, reduced in steps above the income threshold
+    fn property_tax_credit(
+        &self,
+        input: &StateTaxInput,
+        income: Decimal,
+        config: &crate::data::StateConfig,
+    ) -> Decimal {
+        let Some(credit) = config.property_tax_credit.as_ref() else {
+            return Decimal::ZERO;
+        };
+
+        let threshold = credit
+            .thresholds
+            .get(input.filing_status.as_str())
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+        let steps = if income > threshold {
+            ((income - threshold) / credit.step).ceil()
+        } else {
+            Decimal::ZERO
+        };
+        let remaining = (Decimal::ONE - steps * credit.reduction_per_step).max(Decimal::ZERO);
+
+        input
+            .property_taxes
+            .max(Decimal::ZERO)
+            .min(credit.max_credit)
+            * remaining
+    }
+
+    /// Two-earner credit on the lower-earning spouse's wages (joint returns only)
+    fn married_couple_credit(
+        &self,
+        input: &StateTaxInput,
+        config: &crate::data::StateConfig,
+    ) -> Decimal {
+        let Some(credit) = config.married_couple_credit.as_ref() else {
+            return Decimal::ZERO;
+        };
+        if input.filing_status != FilingStatus::MarriedFilingJointly {
+            return Decimal::ZERO;
         }
+
+        let spouse = input.spouse_wages.max(Decimal::ZERO).min(input.wages);
+        let lower_earner = spouse.min(input.wages - spouse);
+        lower_earner.min(credit.max_earnings) * credit.rate
+    }
+
+    /// Apply state exclusions for retirement income and Social Security, and
+    /// add back HSA contributions in states that don't follow federal treatment
+    fn apply_state_adjustments(
+        &self,
+        input: &StateTaxInput,
+        config: &crate::data::StateConfig,
+    ) -> Decimal {
+        let rules = &config.retirement;
+        let distributions = input.retirement_distributions.max(Decimal::ZERO);
+
+        let exclusion = if rules.exempts_retirement_income {
+            distributions
+        } else {
+            match rules.retirement_exclusion {
+                Some(cap) if input.age >= rules.exclusion_min_age.unwrap_or(0) => {
+                    distributions.min(cap)
+                },
+                _ => Decimal::ZERO,
+            }
+        };
+
+        let social_security = if rules.taxes_social_security {
+            input.taxable_social_security
+        } else {
+            Decimal::ZERO
+        };
+
+        let hsa_addback = if config.taxes_hsa_contributions {
+            input.hsa_contributions
+        } else {
+            Decimal::ZERO
+        };
+
+        (input.taxable_income - exclusion + social_security + hsa_addback).max(Decimal::ZERO)
     }
 
     /// Calculate progressive tax with brackets
@@ -98,7 +389,6 @@ impl<'a> StateTaxCalculator<'a> {
             return (Decimal::ZERO, None);
         }
 
-        let mut total_tax = Decimal::ZERO;
         let mut breakdown = Vec::new();
 
         for bracket in brackets {
@@ -108,7 +398,6 @@ impl<'a> StateTaxCalculator<'a> {
 
                 if income_in_bracket > Decimal::ZERO {
                     let tax_in_bracket = income_in_bracket * bracket.rate;
-                    total_tax += tax_in_bracket;
 
                     breakdown.push(BracketAmount {
                         floor: bracket.floor,
@@ -121,44 +410,163 @@ impl<'a> StateTaxCalculator<'a> {
             }
         }
 
+        // Total uses the published base tax of the top bracket, like federal,
+        // so schedules with a base amount at a zero-rate floor (Ohio) work
+        let total_tax = brackets
+            .iter()
+            .rev()
+            .find(|b| taxable_income > b.floor)
+            .map(|b| b.calculate(taxable_income))
+            .unwrap_or(Decimal::ZERO);
+
         (total_tax, Some(breakdown))
     }
 
-    /// Calculate State Disability Insurance
-    fn calculate_sdi(
+    /// Calculate State Disability Insurance and the state's other payroll
+    /// programs, one line per program
+    fn payroll_programs(
         &self,
-        income: Decimal,
+        wages: Decimal,
         state: USState,
+        coverage: SdiCoverage,
+        config: &crate::data::StateConfig,
+    ) -> Vec<PayrollContribution> {
+        let capped = |rate: Decimal, wage_base: Option<Decimal>| {
+            wage_base.map_or(wages, |base| wages.min(base)) * rate
+        };
+
+        let disability_rate = match coverage {
+            _ if !state.has_sdi() => None,
+            SdiCoverage::State => config.sdi_rate,
+            SdiCoverage::Excluded => None,
+            SdiCoverage::VoluntaryPlan { rate } => Some(rate),
+        };
+        let disability = disability_rate.map(|rate| PayrollContribution {
+            kind: PayrollProgramKind::Disability,
+            name: disability_program_name(state).to_string(),
+            amount: capped(rate, config.sdi_wage_base),
+        });
+
+        disability
+            .into_iter()
+            .chain(
+                config
+                    .payroll_programs
+                    .iter()
+                    .map(|program| PayrollContribution {
+                        kind: program.kind,
+                        name: program.name.clone(),
+                        amount: capped(program.rate, program.wage_base),
+                    }),
+            )
+            .filter(|contribution| !contribution.amount.is_zero())
+            .collect()
+    }
+
+    /// School district income tax on state taxable income (after exemptions) or earned income
+    fn calculate_school_district(
+        &self,
+        input: &StateTaxInput,
+        taxable_income: Decimal,
         config: &crate::data::StateConfig,
     ) -> Decimal {
-        if !state.has_sdi() {
+        let Some(district) = input
+            .school_district
+            .as_ref()
+            .and_then(|number| config.school_districts.get(number))
+        else {
             return Decimal::ZERO;
-        }
-
-        let rate = config.sdi_rate.unwrap_or(Decimal::ZERO);
-        let wage_base = config.sdi_wage_base.unwrap_or(income);
-        let taxable = income.min(wage_base);
+        };
 
-        taxable * rate
+        let base = match district.base {
+            SchoolDistrictBase::Traditional => {
+                taxable_income - filing_amount(&config.personal_exemption, input.filing_status)
+            },
+            SchoolDistrictBase::EarnedIncome => input.wages,
+        };
+        base.max(Decimal::ZERO) * district.rate
     }
 
-    /// Estimate local tax (average rate)
+    /// Local tax: a caller-supplied rate if given, city wage taxes for the
+    /// residence and work localities plus a bracketed resident city income
+    /// tax on state taxable income, else a known county's rate on state
+    /// taxable income after deductions (piggyback taxes), otherwise the
+    /// state's average rate. Also returns whether the average was used.
     fn estimate_local_tax(
         &self,
+        input: &StateTaxInput,
         income: Decimal,
-        state: USState,
+        adjusted_income: Decimal,
         config: &crate::data::StateConfig,
-    ) -> Decimal {
-        if !state.has_local_tax() {
-            return Decimal::ZERO;
+    ) -> (Decimal, bool) {
+        if let Some(rate) = input.local_tax_rate_override {
+            return (income * rate, false);
+        }
+        if !input.state.has_local_tax() {
+            return (Decimal::ZERO, false);
+        }
+        let Some(info) = config.local_tax_info.as_ref() else {
+            return (Decimal::ZERO, false);
+        };
+
+        let wage_tax =
+            |locality: Option<&String>| locality.and_then(|name| info.wage_taxes.get(name));
+        let resident = wage_tax(input.residence_locality.as_ref());
+        // Working in the home city is taxed once, at the resident rate
+        let commuter = wage_tax(input.work_locality.as_ref())
+            .filter(|_| input.work_locality != input.residence_locality);
+        let city_income_tax = input
+            .residence_locality
+            .as_ref()
+            .and_then(|name| info.income_taxes.get(name))
+            .map(|tax| {
+                self.calculate_progressive(adjusted_income, tax.brackets_for(input.filing_status))
+                    .0
+            });
+        if resident.is_some() || commuter.is_some() || city_income_tax.is_some() {
+            let commuter_tax = commuter
+                .map(|tax| input.wages * tax.nonresident_rate)
+                .unwrap_or(Decimal::ZERO);
+            let resident_tax = resident
+                .map(|tax| {
+                    let gross = input.wages * tax.resident_rate;
+                    gross - (commuter_tax * tax.resident_credit_rate).min(gross)
+                })
+                .unwrap_or(Decimal::ZERO);
+            return (
+                resident_tax + commuter_tax + city_income_tax.unwrap_or(Decimal::ZERO),
+                false,
+            );
+        }
+
+        let locality_rate = input
+            .residence_locality
+            .as_ref()
+            .and_then(|locality| info.localities.get(locality));
+        if let Some(rate) = locality_rate {
+            return (adjusted_income * rate, false);
         }
 
         // Use average rate as estimate
-        config
+        match info.average_rate {
+            Some(rate) if income > Decimal::ZERO => (income * rate, true),
+            _ => (Decimal::ZERO, false),
+        }
+    }
+
+    /// Commuter wage tax owed to a city in `state` by a nonresident of that state
+    pub fn nonresident_local_tax(
+        &self,
+        state: USState,
+        work_locality: &str,
+        wages: Decimal,
+        year: u32,
+    ) -> Decimal {
+        self.data_provider
+            .state_config(state, year)
             .local_tax_info
-            .as_ref()
-            .and_then(|info| info.average_rate)
-            .map(|rate| income * rate)
+            .and_then(|info| info.wage_taxes.get(work_locality).cloned())
+            .map(|tax| wages * tax.nonresident_rate)
             .unwrap_or(Decimal::ZERO)
     }
 }
@@ -197,6 +605,41 @@ mod tests {
         assert_eq!(result.state_code, "CO");
     }
 
+    #[test]
+    fn test_iowa_flat_from_2025() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let tax = |year| {
+            calc.calculate(dec!(100000), USState::Iowa, FilingStatus::Single, year)
+                .income_tax
+        };
+
+        // 2024: $85,400 after the standard deduction, top bracket 5.7%
+        assert_eq!(tax(2024), dec!(4568.478));
+        // 2025: flat 3.8% on $84,250 after the larger federal deduction
+        assert_eq!(tax(2025), dec!(3201.5));
+    }
+
+    #[test]
+    fn test_flat_rates_after_state_deductions() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let tax = |state, year| {
+            calc.calculate(dec!(100000), state, FilingStatus::Single, year)
+                .income_tax
+        };
+
+        // North Carolina: $12,750 standard deduction at 4.5%, then 4.25%
+        assert_eq!(tax(USState::NorthCarolina, 2024), dec!(3926.25));
+        assert_eq!(tax(USState::NorthCarolina, 2025), dec!(3708.125));
+        // Kentucky: $3,160 standard deduction at 4%
+        assert_eq!(tax(USState::Kentucky, 2024), dec!(3873.6));
+        // Indiana: $1,000 personal exemption at 3.05%
+        assert_eq!(tax(USState::Indiana, 2024), dec!(3019.5));
+    }
+
     #[test]
     fn test_california_has_sdi() {
         let data = setup();
@@ -265,6 +708,517 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sdi_applies_to_wages_only() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let input = StateTaxInput {
+            taxable_income: dec!(60000),
+            wages: dec!(0),
+            state: USState::California,
+            filing_status: FilingStatus::Single,
+            retirement_distributions: dec!(60000),
+            ..Default::default()
+        };
+
+        let result = calc.calculate_input(&input, 2024);
+
+        assert_eq!(result.sdi, dec!(0));
+        assert!(result.income_tax > dec!(0));
+    }
+
+    #[test]
+    fn test_retirement_exclusions() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let retiree = |state, age| StateTaxInput {
+            taxable_income: dec!(50000),
+            wages: dec!(0),
+            state,
+            filing_status: FilingStatus::Single,
+            retirement_distributions: dec!(50000),
+            taxable_social_security: dec!(10000),
+            age,
+            ..Default::default()
+        };
+
+        // Pennsylvania exempts retirement distributions entirely
+        let pa = calc.calculate_input(&retiree(USState::Pennsylvania, 70), 2024);
+        assert_eq!(pa.income_tax, dec!(0));
+
+        // New York excludes up to $20,000 at 59+
+        let ny = calc.calculate_input(&retiree(USState::NewYork, 70), 2024);
+        assert_eq!(ny.taxable_income, dec!(30000));
+        let ny_young = calc.calculate_input(&retiree(USState::NewYork, 50), 2024);
+        assert_eq!(ny_young.taxable_income, dec!(50000));
+
+        // Utah taxes Social Security
+        let ut = calc.calculate_input(&retiree(USState::Utah, 70), 2024);
+        assert_eq!(ut.taxable_income, dec!(60000));
+    }
+
+    #[test]
+    fn test_exemptions_and_personal_credits() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // Mississippi: $50,000 - $2,300 std - $6,000 exemption = $41,700;
+        // 4.7% on the $31,700 above the zero band
+        let ms = calc.calculate(
+            dec!(50000),
+            USState::Mississippi,
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(ms.income_tax, dec!(1489.90));
+
+        // Delaware: $46,750 after std deduction, less the $110 personal credit
+        let de = calc.calculate(dec!(50000), USState::Delaware, FilingStatus::Single, 2024);
+        assert_eq!(de.income_tax, dec!(2098.125));
+
+        // Arkansas: $47,590 after std deduction, less the $29 personal credit
+        let ar = calc.calculate(dec!(50000), USState::Arkansas, FilingStatus::Single, 2024);
+        assert_eq!(ar.income_tax, dec!(1417.11));
+    }
+
+    #[test]
+    fn test_alabama_deducts_federal_tax() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(60000), USState::Alabama, FilingStatus::Single);
+        let without = calc.calculate_input(&input, 2024);
+
+        input.federal_income_tax = dec!(5000);
+        let with = calc.calculate_input(&input, 2024);
+
+        // $60,000 - $3,000 std - $1,500 exemption - $5,000 federal tax = $50,500
+        assert_eq!(with.income_tax, dec!(2485));
+        assert_eq!(without.income_tax - with.income_tax, dec!(250));
+    }
+
+    #[test]
+    fn test_hawaii_brackets_and_tdi() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let result = calc.calculate(dec!(100000), USState::Hawaii, FilingStatus::Single, 2024);
+
+        // $100,000 - $4,400 std - $1,144 exemption = $94,456 in the 7.6% bracket
+        assert_eq!(result.income_tax, dec!(6069.856));
+        // TDI capped at the annualized weekly wage base
+        assert_eq!(result.sdi, dec!(359.8504));
+
+        let joint = calc.calculate(
+            dec!(100000),
+            USState::Hawaii,
+            FilingStatus::MarriedFilingJointly,
+            2024,
+        );
+        assert!(joint.income_tax < result.income_tax);
+    }
+
+    #[test]
+    fn test_rhode_island_brackets_and_tdi() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let result = calc.calculate(
+            dec!(100000),
+            USState::RhodeIsland,
+            FilingStatus::Single,
+            2024,
+        );
+
+        // $100,000 - $10,550 std - $4,950 exemption = $84,500
+        assert_eq!(result.income_tax, dec!(3239.25));
+        // 1.2% TDI on the $87,000 wage base
+        assert_eq!(result.sdi, dec!(1044));
+    }
+
+    #[test]
+    fn test_ohio_brackets_and_school_district() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(100000), USState::Ohio, FilingStatus::Single);
+        let result = calc.calculate_input(&input, 2024);
+
+        // $98,100 after exemption: $360.69 base + 2.75% over $26,050
+        assert_eq!(result.income_tax, dec!(2342.065));
+        assert_eq!(result.school_district_tax, dec!(0));
+
+        // Earned-income district taxes wages only
+        input.school_district = Some("2102".to_string());
+        input.retirement_distributions = dec!(20000);
+        input.taxable_income = dec!(120000);
+        let result = calc.calculate_input(&input, 2024);
+        assert_eq!(result.school_district_tax, dec!(750));
+
+        // Traditional district taxes Ohio taxable income
+        input.school_district = Some("4504".to_string());
+        let result = calc.calculate_input(&input, 2024);
+        assert_eq!(result.school_district_tax, dec!(1181));
+        assert_eq!(
+            result.total_tax,
+            result.income_tax + result.local_tax + result.school_district_tax
+        );
+    }
+
+    #[test]
+    fn test_maryland_county_piggyback() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(100000), USState::Maryland, FilingStatus::Single);
+        input.residence_locality = Some("Montgomery".to_string());
+        let result = calc.calculate_input(&input, 2024);
+
+        // $100,000 - $2,700 std - $3,200 exemption = $94,100
+        assert_eq!(result.income_tax, dec!(4417.25));
+        // Montgomery County's 3.2% applies to the same Maryland taxable income
+        assert_eq!(result.local_tax, dec!(3011.2));
+        assert!(result.total_tax / dec!(100000) > dec!(0.07));
+
+        input.residence_locality = Some("Worcester".to_string());
+        let worcester = calc.calculate_input(&input, 2024);
+        assert_eq!(worcester.local_tax, dec!(2117.25));
+    }
+
+    #[test]
+    fn test_payroll_program_line_items() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // New Jersey: TDI and FLI to $161,400, UI/WF to $42,300
+        let input = StateTaxInput::new(dec!(100000), USState::NewJersey, FilingStatus::Single);
+        let result = calc.calculate_input(&input, 2024);
+        let programs: Vec<_> = result
+            .payroll_programs
+            .iter()
+            .map(|p| (p.name.as_str(), p.amount))
+            .collect();
+        assert_eq!(
+            programs,
+            vec![
+                ("TDI", dec!(90)),
+                ("FLI", dec!(90)),
+                ("UI/WF", dec!(179.775))
+            ]
+        );
+        assert_eq!(result.sdi, dec!(359.775));
+
+        // Washington has programs without an income tax
+        let input = StateTaxInput::new(dec!(100000), USState::Washington, FilingStatus::Single);
+        let result = calc.calculate_input(&input, 2024);
+        assert_eq!(result.income_tax, dec!(0));
+        assert_eq!(result.payroll_programs.len(), 2);
+        assert_eq!(result.sdi, dec!(528.582) + dec!(580));
+        assert_eq!(result.total_tax, result.sdi);
+
+        // Excluding state disability leaves the other programs
+        let mut input = StateTaxInput::new(dec!(50000), USState::NewYork, FilingStatus::Single);
+        input.sdi_coverage = SdiCoverage::Excluded;
+        let result = calc.calculate_input(&input, 2024);
+        assert_eq!(result.payroll_programs.len(), 1);
+        assert_eq!(
+            result.payroll_programs[0].kind,
+            PayrollProgramKind::PaidFamilyLeave
+        );
+        assert_eq!(result.sdi, dec!(186.5));
+    }
+
+    #[test]
+    fn test_sdi_voluntary_plan_and_exclusion() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(100000), USState::California, FilingStatus::Single);
+        let state_plan = calc.calculate_input(&input, 2024);
+        assert!(state_plan.sdi > dec!(0));
+
+        input.sdi_coverage = SdiCoverage::Excluded;
+        let excluded = calc.calculate_input(&input, 2024);
+        assert_eq!(excluded.sdi, dec!(0));
+        assert_eq!(excluded.income_tax, state_plan.income_tax);
+
+        input.sdi_coverage = SdiCoverage::VoluntaryPlan { rate: dec!(0.005) };
+        assert_eq!(calc.calculate_input(&input, 2024).sdi, dec!(500));
+
+        // Only states with an SDI program withhold for it
+        input.state = USState::Arizona;
+        assert_eq!(calc.calculate_input(&input, 2024).sdi, dec!(0));
+    }
+
+    #[test]
+    fn test_local_tax_rate_override() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(100000), USState::NewYork, FilingStatus::Single);
+        let estimated = calc.calculate_input(&input, 2024);
+        assert_eq!(estimated.local_tax, dec!(3500));
+        assert!(!estimated.local_tax_overridden);
+
+        input.local_tax_rate_override = Some(dec!(0.01));
+        let overridden = calc.calculate_input(&input, 2024);
+        assert_eq!(overridden.local_tax, dec!(1000));
+        assert!(overridden.local_tax_overridden);
+        assert_eq!(overridden.total_tax, estimated.total_tax - dec!(2500));
+    }
+
+    #[test]
+    fn test_philadelphia_resident_and_commuter_rates() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input =
+            StateTaxInput::new(dec!(100000), USState::Pennsylvania, FilingStatus::Single);
+        input.residence_locality = Some("Philadelphia".to_string());
+        input.work_locality = Some("Philadelphia".to_string());
+        assert_eq!(calc.calculate_input(&input, 2024).local_tax, dec!(3750));
+
+        // Suburban resident commuting into the city pays the nonresident rate
+        input.residence_locality = None;
+        assert_eq!(calc.calculate_input(&input, 2024).local_tax, dec!(3440));
+    }
+
+    #[test]
+    fn test_new_york_city_resident_brackets() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // $108,000 - $8,000 std = $100,000 of New York taxable income:
+        // $1,813.17 on the first $50,000, then 3.876%
+        let mut input = StateTaxInput::new(dec!(108000), USState::NewYork, FilingStatus::Single);
+        input.residence_locality = Some("New York City".to_string());
+        let result = calc.calculate_input(&input, 2024);
+        assert_eq!(result.local_tax, dec!(3751.17));
+
+        // The joint brackets are wider: $3,263.706 on the first $90,000
+        let mut joint = StateTaxInput::new(
+            dec!(116050),
+            USState::NewYork,
+            FilingStatus::MarriedFilingJointly,
+        );
+        joint.residence_locality = Some("New York City".to_string());
+        assert_eq!(calc.calculate_input(&joint, 2024).local_tax, dec!(3651.306));
+
+        // Low incomes stay in the 3.078% bracket
+        input.taxable_income = dec!(18000);
+        assert_eq!(calc.calculate_input(&input, 2024).local_tax, dec!(307.80));
+    }
+
+    #[test]
+    fn test_ohio_city_credit_for_work_city_tax() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let mut input = StateTaxInput::new(dec!(100000), USState::Ohio, FilingStatus::Single);
+        input.residence_locality = Some("Columbus".to_string());
+        input.work_locality = Some("Cincinnati".to_string());
+        // $1,800 to Cincinnati, then Columbus collects the remaining 0.7%
+        assert_eq!(calc.calculate_input(&input, 2024).local_tax, dec!(2500));
+
+        // Cincinnati's credit fully absorbs its tax for a Columbus commuter
+        input.residence_locality = Some("Cincinnati".to_string());
+        input.work_locality = Some("Columbus".to_string());
+        assert_eq!(calc.calculate_input(&input, 2024).local_tax, dec!(2500));
+    }
+
+    #[test]
+    fn test_wisconsin_married_couple_credit() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let single = calc.calculate(dec!(60000), USState::Wisconsin, FilingStatus::Single, 2024);
+        // $60,000 - $13,230 std - $700 exemption = $46,070
+        assert_eq!(single.income_tax, dec!(2055.07));
+
+        let mut joint = StateTaxInput::new(
+            dec!(120000),
+            USState::Wisconsin,
+            FilingStatus::MarriedFilingJointly,
+        );
+        let one_earner = calc.calculate_input(&joint, 2024);
+
+        // Two earners get 3% of the lower earner's wages, capped at $16,000
+        joint.spouse_wages = dec!(60000);
+        let two_earners = calc.calculate_input(&joint, 2024);
+        assert_eq!(one_earner.income_tax - two_earners.income_tax, dec!(480));
+
+        joint.spouse_wages = dec!(10000);
+        let small_spouse = calc.calculate_input(&joint, 2024);
+        assert_eq!(one_earner.income_tax - small_spouse.income_tax, dec!(300));
+    }
+
+    #[test]
+    fn test_minnesota_joint_brackets() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let joint = calc.calculate(
+            dec!(100000),
+            USState::Minnesota,
+            FilingStatus::MarriedFilingJointly,
+            2024,
+        );
+
+        // $70,850: $46,330 at 5.35% + $24,520 at 6.8%
+        assert_eq!(joint.income_tax, dec!(4146.015));
+    }
+
+    #[test]
+    fn test_utah_taxpayer_credit_phase_out() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let tax = |income| {
+            calc.calculate(income, USState::Utah, FilingStatus::Single, 2024)
+                .income_tax
+        };
+
+        // $876 credit less 1.3% of income above $17,652
+        assert_eq!(tax(dec!(50000)), dec!(1819.524));
+        // Fully phased out
+        assert_eq!(tax(dec!(100000)), dec!(4550));
+        // Below the phase-out threshold the credit wipes out the tax
+        assert_eq!(tax(dec!(15000)), dec!(0));
+    }
+
+    #[test]
+    fn test_idaho_and_montana_schedules() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // Both start from federal taxable income: $80,000 - $14,600
+        let id = calc.calculate(dec!(80000), USState::Idaho, FilingStatus::Single, 2024);
+        assert_eq!(id.income_tax, dec!(3458.40265));
+
+        let mt = calc.calculate(dec!(80000), USState::Montana, FilingStatus::Single, 2024);
+        assert_eq!(mt.income_tax, dec!(3612.6));
+    }
+
+    #[test]
+    fn test_connecticut_credits_and_recapture() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // $100,000: no exemption or personal credit; $4,750 of bracket tax plus
+        // $225 recapturing the 2% bracket (9 steps of $5,000 above $56,500)
+        let high = calc.calculate(
+            dec!(100000),
+            USState::Connecticut,
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(high.income_tax, dec!(4975));
+
+        // $40,000: exemption phased down to $5,000 and a 10% personal credit
+        let mut input = StateTaxInput::new(dec!(40000), USState::Connecticut, FilingStatus::Single);
+        let low = calc.calculate_input(&input, 2024);
+        assert_eq!(low.income_tax, dec!(1192.5));
+
+        // Property tax credit up to $300, full below the income threshold
+        input.property_taxes = dec!(2000);
+        let with_property = calc.calculate_input(&input, 2024);
+        assert_eq!(low.income_tax - with_property.income_tax, dec!(300));
+
+        // 15% lost per $10,000 above $49,500
+        input.taxable_income = dec!(70000);
+        input.wages = dec!(70000);
+        let reduced = calc.calculate_input(&input, 2024);
+        input.property_taxes = dec!(0);
+        let no_property = calc.calculate_input(&input, 2024);
+        assert_eq!(no_property.income_tax - reduced.income_tax, dec!(165));
+    }
+
+    #[test]
+    fn test_vermont_and_maine_schedules() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // $100,000 - $7,400 std - $5,100 exemption = $87,500
+        let vt = calc.calculate(dec!(100000), USState::Vermont, FilingStatus::Single, 2024);
+        // 3.35% to $47,900, then 6.6%
+        assert_eq!(vt.income_tax, dec!(4218.25));
+
+        // $100,000 - $14,600 std - $5,000 exemption = $80,400
+        let me = calc.calculate(dec!(100000), USState::Maine, FilingStatus::Single, 2024);
+        assert_eq!(me.income_tax, dec!(5254.725));
+    }
+
+    #[test]
+    fn test_southern_and_southwestern_schedules() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let tax = |state, year| {
+            calc.calculate(dec!(75000), state, FilingStatus::Single, year)
+                .income_tax
+        };
+
+        // South Carolina and New Mexico start from federal taxable income ($60,400)
+        assert_eq!(tax(USState::SouthCarolina, 2024), dec!(3086.44));
+        assert_eq!(tax(USState::NewMexico, 2024), dec!(2680.1));
+        // Oklahoma: $75,000 - $6,350 std - $1,000 exemption = $67,650
+        assert_eq!(tax(USState::Oklahoma, 2024), dec!(3024.875));
+        // Louisiana 2024: three brackets after the $4,500 combined exemption
+        assert_eq!(tax(USState::Louisiana, 2024), dec!(2415));
+        // Louisiana 2025: flat 3% after a $12,500 standard deduction
+        assert_eq!(tax(USState::Louisiana, 2025), dec!(1875));
+    }
+
+    #[test]
+    fn test_plains_states_and_dc() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        let tax = |income, state| {
+            calc.calculate(income, state, FilingStatus::Single, 2024)
+                .income_tax
+        };
+
+        // Nebraska: $51,700 after the std deduction, less the $157 exemption credit
+        assert_eq!(tax(dec!(60000), USState::Nebraska), dec!(2194.321));
+        // North Dakota: 1.95% above the $47,150 zero bracket on $85,400
+        assert_eq!(tax(dec!(100000), USState::NorthDakota), dec!(745.875));
+        // West Virginia: $58,000 after the $2,000 exemption
+        assert_eq!(tax(dec!(60000), USState::WestVirginia), dec!(2089.1));
+        // DC: $85,400 after the federal-linked std deduction
+        assert_eq!(tax(dec!(100000), USState::WashingtonDC), dec!(5659));
+    }
+
+    #[test]
+    fn test_deduction_rules() {
+        let data = setup();
+        let calc = StateTaxCalculator::new(&data);
+
+        // Maryland: 15% of income between the $1,800 floor and $2,700 cap
+        let md = |income| {
+            calc.calculate(income, USState::Maryland, FilingStatus::Single, 2024)
+                .income_tax
+        };
+        // $15,000 - $2,250 - $3,200 = $9,550
+        assert_eq!(md(dec!(15000)), dec!(401.125));
+        // $10,000 - $1,800 floor - $3,200 = $5,000
+        assert_eq!(md(dec!(10000)), dec!(185));
+        // $30,000 - $2,700 cap - $3,200 = $24,100
+        assert_eq!(md(dec!(30000)), dec!(1092.25));
+
+        // Federal-linked states pick up the federal deduction for the status
+        let dc_joint = calc.calculate(
+            dec!(100000),
+            USState::WashingtonDC,
+            FilingStatus::MarriedFilingJointly,
+            2024,
+        );
+        // $70,800 after the $29,200 federal joint deduction
+        assert_eq!(dc_joint.income_tax, dec!(4418));
+    }
+
     #[test]
     fn test_new_york_has_local_tax() {
         let data = setup();
@@ -1,13 +1,18 @@
 //! Timeframe conversion calculator
 
 use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
 
 use crate::models::income::TimeframeIncome;
+use crate::models::rate::Rate;
+use crate::models::ParseEnumError;
 
 /// Timeframe identifiers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Timeframe {
     Annual,
+    Quarterly,
     Monthly,
     BiWeekly,
     SemiMonthly,
@@ -21,6 +26,7 @@ impl Timeframe {
     pub fn divisor(&self) -> Decimal {
         match self {
             Timeframe::Annual => Decimal::ONE,
+            Timeframe::Quarterly => Decimal::from(4),
             Timeframe::Monthly => Decimal::from(12),
             Timeframe::BiWeekly => Decimal::from(26),
             Timeframe::SemiMonthly => Decimal::from(24),
@@ -30,9 +36,23 @@ impl Timeframe {
         }
     }
 
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Timeframe::Annual => "annual",
+            Timeframe::Quarterly => "quarterly",
+            Timeframe::Monthly => "monthly",
+            Timeframe::BiWeekly => "bi_weekly",
+            Timeframe::SemiMonthly => "semi_monthly",
+            Timeframe::Weekly => "weekly",
+            Timeframe::Daily => "daily",
+            Timeframe::Hourly => "hourly",
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Timeframe::Annual => "Annual",
+            Timeframe::Quarterly => "Quarterly",
             Timeframe::Monthly => "Monthly",
             Timeframe::BiWeekly => "Bi-Weekly",
             Timeframe::SemiMonthly => "Semi-Monthly",
@@ -43,6 +63,30 @@ impl Timeframe {
     }
 }
 
+impl fmt::Display for Timeframe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "annual" => Ok(Timeframe::Annual),
+            "quarterly" => Ok(Timeframe::Quarterly),
+            "monthly" => Ok(Timeframe::Monthly),
+            "bi_weekly" => Ok(Timeframe::BiWeekly),
+            "semi_monthly" => Ok(Timeframe::SemiMonthly),
+            "weekly" => Ok(Timeframe::Weekly),
+            "daily" => Ok(Timeframe::Daily),
+            "hourly" => Ok(Timeframe::Hourly),
+            _ => Err(ParseEnumError::new("timeframe", s)),
+        }
+    }
+}
+
 /// Timeframe calculator
 pub struct TimeframeCalculator;
 
@@ -81,6 +125,19 @@ impl TimeframeCalculator {
         target_amount / hourly_rate
     }
 
+    /// Calculate work hours needed to take home an amount
+    /// Each hour's pay is taxed at `marginal_rate` before counting toward the target
+    pub fn hours_to_earn_net(
+        hourly_rate: Decimal,
+        marginal_rate: Rate,
+        target_amount: Decimal,
+    ) -> Decimal {
+        Self::hours_to_earn(
+            hourly_rate * (Decimal::ONE - marginal_rate.as_fraction()),
+            target_amount,
+        )
+    }
+
     /// Calculate work days needed to earn an amount
     /// Given daily rate, how many days to earn target amount
     pub fn days_to_earn(daily_rate: Decimal, target_amount: Decimal) -> Decimal {
@@ -96,12 +153,21 @@ mod tests {
     use super::*;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn test_timeframe_parse() {
+        assert_eq!("bi_weekly".parse::<Timeframe>(), Ok(Timeframe::BiWeekly));
+        assert_eq!(Timeframe::SemiMonthly.to_string(), "semi_monthly");
+        assert!("fortnightly".parse::<Timeframe>().is_err());
+    }
+
     #[test]
     fn test_from_annual() {
         let income = TimeframeCalculator::from_annual(dec!(104000));
 
         assert_eq!(income.annual, dec!(104000));
         assert_eq!(income.monthly, dec!(104000) / dec!(12));
+        assert_eq!(income.quarterly, dec!(26000));
+        assert_eq!(income.semi_monthly, dec!(104000) / dec!(24));
         assert_eq!(income.bi_weekly, dec!(4000));
         assert_eq!(income.weekly, dec!(2000));
         assert_eq!(income.daily, dec!(400));
@@ -151,6 +217,25 @@ mod tests {
         assert_eq!(hours, dec!(0));
     }
 
+    #[test]
+    fn test_hours_to_earn_net() {
+        // At $50/hour taxed at 37.5%, each hour nets $31.25
+        let hours = TimeframeCalculator::hours_to_earn_net(
+            dec!(50),
+            Rate::from_percent(dec!(37.5)),
+            dec!(500),
+        );
+        assert_eq!(hours, dec!(16));
+
+        // Edge case: everything taxed away
+        let hours = TimeframeCalculator::hours_to_earn_net(
+            dec!(50),
+            Rate::from_percent(dec!(100)),
+            dec!(500),
+        );
+        assert_eq!(hours, dec!(0));
+    }
+
     #[test]
     fn test_days_to_earn() {
         // At $400/day, how many days to earn $2000?
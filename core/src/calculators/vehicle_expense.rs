@@ -0,0 +1,193 @@
+//! Business vehicle expense deduction
+//!
+//! A self-employed taxpayer deducts business use of a car either at the
+//! IRS standard mileage rate or as the business share of what the car
+//! actually cost to run, including depreciation. Parking and tolls for
+//! business trips are deductible under either method. The standard rate has
+//! to be used in the car's first business year to remain available later.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::data::TaxDataProvider;
+use crate::models::rate::Rate;
+
+/// A year of driving and car costs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VehicleUse {
+    pub business_miles: Decimal,
+    /// All miles driven, business and personal
+    pub total_miles: Decimal,
+    /// Gas, insurance, repairs, registration, and lease payments
+    pub operating_costs: Decimal,
+    pub depreciation: Decimal,
+    /// Business parking and tolls
+    #[serde(default)]
+    pub parking_and_tolls: Decimal,
+}
+
+/// Method for deducting vehicle expenses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VehicleExpenseMethod {
+    StandardMileage,
+    ActualExpenses,
+}
+
+/// Deduction under each method
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VehicleDeductionComparison {
+    pub mileage_rate: Decimal,
+    pub standard_mileage: Decimal,
+    /// Business miles ÷ total miles
+    pub business_share: Rate,
+    pub actual_expenses: Decimal,
+    pub better_method: VehicleExpenseMethod,
+    /// How much more the better method deducts
+    pub advantage: Decimal,
+}
+
+impl VehicleDeductionComparison {
+    /// Deduction under the better method
+    pub fn deduction(&self) -> Decimal {
+        self.standard_mileage.max(self.actual_expenses)
+    }
+}
+
+/// Business vehicle expense calculator
+pub struct VehicleExpenseCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> VehicleExpenseCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Deduction at the year's standard mileage rate
+    pub fn standard_mileage(&self, vehicle: &VehicleUse, year: u32) -> Decimal {
+        let rate = self.data_provider.business_mileage_rate(year);
+        (vehicle.business_miles.max(Decimal::ZERO) * rate).round_dp(2)
+            + vehicle.parking_and_tolls.max(Decimal::ZERO)
+    }
+
+    /// Business share of actual costs
+    pub fn actual_expenses(vehicle: &VehicleUse) -> Decimal {
+        let share = Self::business_share(vehicle);
+        let costs = (vehicle.operating_costs + vehicle.depreciation).max(Decimal::ZERO);
+        (costs * share.as_fraction()).round_dp(2) + vehicle.parking_and_tolls.max(Decimal::ZERO)
+    }
+
+    /// Deduction under both methods and which is larger
+    pub fn compare(&self, vehicle: &VehicleUse, year: u32) -> VehicleDeductionComparison {
+        let standard_mileage = self.standard_mileage(vehicle, year);
+        let actual_expenses = Self::actual_expenses(vehicle);
+        let better_method = if actual_expenses > standard_mileage {
+            VehicleExpenseMethod::ActualExpenses
+        } else {
+            VehicleExpenseMethod::StandardMileage
+        };
+
+        VehicleDeductionComparison {
+            mileage_rate: self.data_provider.business_mileage_rate(year),
+            standard_mileage,
+            business_share: Self::business_share(vehicle),
+            actual_expenses,
+            better_method,
+            advantage: (standard_mileage - actual_expenses).abs(),
+        }
+    }
+
+    fn business_share(vehicle: &VehicleUse) -> Rate {
+        let total_miles = vehicle.total_miles.max(Decimal::ZERO);
+        Rate::of(
+            vehicle.business_miles.clamp(Decimal::ZERO, total_miles),
+            total_miles,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculators::self_employment::ScheduleC;
+    use crate::calculators::SelfEmploymentTaxCalculator;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::models::state::USState;
+    use crate::models::tax::FilingStatus;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_mileage_rate_by_year() {
+        let data = setup();
+        let calc = VehicleExpenseCalculator::new(&data);
+        let vehicle = VehicleUse {
+            business_miles: dec!(10000),
+            ..Default::default()
+        };
+
+        assert_eq!(calc.standard_mileage(&vehicle, 2023), dec!(6550));
+        assert_eq!(calc.standard_mileage(&vehicle, 2024), dec!(6700));
+        assert_eq!(calc.standard_mileage(&vehicle, 2025), dec!(7000));
+        // Earlier years fall back to the earliest embedded rate
+        assert_eq!(calc.standard_mileage(&vehicle, 2020), dec!(6550));
+    }
+
+    #[test]
+    fn test_compare_methods() {
+        let data = setup();
+        let calc = VehicleExpenseCalculator::new(&data);
+
+        // 12,000 of 20,000 miles: 60% of $14,000 beats $8,040 at 67¢
+        let vehicle = VehicleUse {
+            business_miles: dec!(12000),
+            total_miles: dec!(20000),
+            operating_costs: dec!(9000),
+            depreciation: dec!(5000),
+            parking_and_tolls: dec!(300),
+        };
+        let comparison = calc.compare(&vehicle, 2024);
+        assert_eq!(comparison.standard_mileage, dec!(8340));
+        assert_eq!(comparison.actual_expenses, dec!(8700));
+        assert_eq!(
+            comparison.better_method,
+            VehicleExpenseMethod::ActualExpenses
+        );
+        assert_eq!(comparison.advantage, dec!(360));
+
+        // A cheap car favors the standard rate
+        let economical = VehicleUse {
+            operating_costs: dec!(4000),
+            depreciation: dec!(1000),
+            ..vehicle
+        };
+        let comparison = calc.compare(&economical, 2024);
+        assert_eq!(
+            comparison.better_method,
+            VehicleExpenseMethod::StandardMileage
+        );
+
+        // Lowers self-employment tax as a Schedule C expense
+        let se = SelfEmploymentTaxCalculator::new(&data);
+        let business = ScheduleC {
+            gross_receipts: dec!(80000),
+            ..Default::default()
+        };
+        let base = se.calculate_schedule_c(&business, FilingStatus::Single, USState::Texas, 2024);
+        let result = se.calculate_schedule_c(
+            &ScheduleC {
+                vehicle: Some(economical),
+                ..business
+            },
+            FilingStatus::Single,
+            USState::Texas,
+            2024,
+        );
+        assert_eq!(result.vehicle_deduction, comparison.deduction());
+        assert_eq!(result.net_profit, dec!(80000) - dec!(8340));
+        assert!(result.self_employment_tax.total < base.self_employment_tax.total);
+    }
+}
@@ -0,0 +1,520 @@
+//! Federal income tax withholding on wages
+//!
+//! Regular paychecks are withheld with the IRS Pub 15-T percentage method
+//! for Forms W-4 from 2020 on: the paycheck is annualized, adjusted for the
+//! W-4 entries, and run through the filing status's brackets. A simulated
+//! year of paychecks adds Social Security until the wage base is reached and
+//! additional Medicare once year-to-date wages pass $200,000.
+//!
+//! Supplemental wages (bonuses, RSU vests, and separation pay) are withheld
+//! at flat rates instead, which routinely under-withholds for high earners.
+//! A few states withhold bonuses and stock at a different rate than other
+//! supplemental wages, such as California's 10.23% and 6.6%.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::calculators::{FederalTaxCalculator, StateTaxCalculator};
+use crate::data::TaxDataProvider;
+use crate::models::income::PayFrequency;
+use crate::models::profile::W4Settings;
+use crate::models::state::USState;
+use crate::models::tax::FilingStatus;
+
+/// Federal flat rate on supplemental wages up to $1 million
+const FEDERAL_SUPPLEMENTAL_RATE: Decimal = dec!(0.22);
+
+/// Mandatory federal rate on supplemental wages above $1 million
+const FEDERAL_MANDATORY_RATE: Decimal = dec!(0.37);
+
+/// Year-to-date supplemental wages above which the mandatory rate applies
+const MANDATORY_THRESHOLD: Decimal = dec!(1000000);
+
+/// Wages above which employers withhold additional Medicare, regardless of filing status
+const ADDITIONAL_MEDICARE_WITHHOLDING_THRESHOLD: Decimal = dec!(200000);
+
+/// Kind of supplemental payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SupplementalKind {
+    #[default]
+    Bonus,
+    /// Restricted stock units, withheld at vest on the fair market value
+    RsuVest,
+    /// Severance paid on leaving a job
+    Severance,
+    /// Unused vacation or PTO cashed out, usually in the final paycheck
+    PtoPayout,
+}
+
+impl SupplementalKind {
+    /// Bonuses and stock compensation, which some states withhold at a
+    /// higher rate than other supplemental wages
+    pub fn is_bonus_or_stock(&self) -> bool {
+        matches!(self, Self::Bonus | Self::RsuVest)
+    }
+}
+
+/// Input for supplemental withholding
+#[derive(Debug, Clone, Default)]
+pub struct SupplementalWithholdingInput {
+    /// Bonus amount or fair market value of vesting shares
+    pub amount: Decimal,
+    pub kind: SupplementalKind,
+    pub state: USState,
+    pub filing_status: FilingStatus,
+    /// Expected regular wages for the year, for states without a supplemental rate
+    pub annual_wages: Decimal,
+    /// All wages paid earlier in the year (Social Security and Medicare thresholds)
+    pub ytd_wages: Decimal,
+    /// Supplemental wages paid earlier in the year ($1 million federal threshold)
+    pub ytd_supplemental_wages: Decimal,
+}
+
+impl SupplementalWithholdingInput {
+    /// Set `annual_wages` from regular per-paycheck pay, annualized the way
+    /// the withholding tables do
+    pub fn with_regular_pay(mut self, per_paycheck: Decimal, frequency: PayFrequency) -> Self {
+        self.annual_wages = per_paycheck * Decimal::from(frequency.withholding_periods());
+        self
+    }
+}
+
+/// Withholding on a supplemental payment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplementalWithholding {
+    pub federal: Decimal,
+    pub state: Decimal,
+    /// State rate used: its supplemental rate for the kind of payment, or
+    /// the marginal rate on annual wages
+    pub state_rate: Decimal,
+    pub social_security: Decimal,
+    pub medicare: Decimal,
+    pub total: Decimal,
+    /// Cash paid out (bonus) or value of shares delivered after sell-to-cover (RSU)
+    pub net: Decimal,
+}
+
+/// Supplemental wage withholding calculator
+pub struct SupplementalWithholdingCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> SupplementalWithholdingCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Calculate withholding on a bonus or RSU vest
+    pub fn calculate(
+        &self,
+        input: &SupplementalWithholdingInput,
+        year: u32,
+    ) -> SupplementalWithholding {
+        let amount = input.amount.max(Decimal::ZERO);
+
+        let federal = self.federal_withholding(amount, input.ytd_supplemental_wages);
+        let state_rate = self.state_rate(input, year);
+        let state = amount * state_rate;
+
+        // Social Security stops at the wage base; additional Medicare starts at $200k
+        let fica = self.data_provider.fica_config(year);
+        let ss_room = (fica.wage_base - input.ytd_wages).max(Decimal::ZERO);
+        let social_security = amount.min(ss_room) * fica.social_security_rate;
+        let over_threshold = (input.ytd_wages + amount
+            - ADDITIONAL_MEDICARE_WITHHOLDING_THRESHOLD.max(input.ytd_wages))
+        .max(Decimal::ZERO);
+        let medicare = amount * fica.medicare_rate + over_threshold * fica.additional_medicare_rate;
+
+        let total = federal + state + social_security + medicare;
+
+        SupplementalWithholding {
+            federal,
+            state,
+            state_rate,
+            social_security,
+            medicare,
+            total,
+            net: amount - total,
+        }
+    }
+
+    /// 22% flat rate, with the portion above $1 million YTD at 37%
+    fn federal_withholding(&self, amount: Decimal, ytd_supplemental: Decimal) -> Decimal {
+        let below_threshold = (MANDATORY_THRESHOLD - ytd_supplemental)
+            .max(Decimal::ZERO)
+            .min(amount);
+        let above_threshold = amount - below_threshold;
+
+        below_threshold * FEDERAL_SUPPLEMENTAL_RATE + above_threshold * FEDERAL_MANDATORY_RATE
+    }
+
+    /// State supplemental rate for the kind of payment, falling back to the
+    /// marginal rate on top of annual wages
+    fn state_rate(&self, input: &SupplementalWithholdingInput, year: u32) -> Decimal {
+        if input.state.has_no_income_tax() || input.amount <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        let config = self.data_provider.state_config(input.state, year);
+        let supplemental_rate = if input.kind.is_bonus_or_stock() {
+            config.supplemental_rate
+        } else {
+            config.other_supplemental_rate.or(config.supplemental_rate)
+        };
+        if let Some(rate) = supplemental_rate {
+            return rate;
+        }
+
+        let calc = StateTaxCalculator::new(self.data_provider);
+        let without = calc.calculate(input.annual_wages, input.state, input.filing_status, year);
+        let with = calc.calculate(
+            input.annual_wages + input.amount,
+            input.state,
+            input.filing_status,
+            year,
+        );
+        (with.income_tax - without.income_tax) / input.amount
+    }
+}
+
+/// Regular pay on each paycheck
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegularPay {
+    pub gross: Decimal,
+    pub frequency: PayFrequency,
+    /// Traditional 401(k) and other deferrals, taken off federal wages only
+    pub retirement_deferrals: Decimal,
+    /// Health premiums, HSA, and FSA through a cafeteria plan, taken off
+    /// federal and FICA wages
+    pub cafeteria_plan: Decimal,
+}
+
+/// Withholding on one regular paycheck
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaycheckWithholding {
+    /// 1 for the year's first paycheck
+    pub number: u32,
+    pub gross: Decimal,
+    pub pre_tax_deductions: Decimal,
+    pub federal_income_tax: Decimal,
+    pub social_security: Decimal,
+    /// Medicare, including additional Medicare once it starts
+    pub medicare: Decimal,
+    pub net: Decimal,
+    /// FICA wages paid through this paycheck
+    pub ytd_fica_wages: Decimal,
+}
+
+/// A year of regular paychecks under one W-4
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithholdingSimulation {
+    pub paychecks: Vec<PaycheckWithholding>,
+    pub federal_income_tax: Decimal,
+    pub social_security: Decimal,
+    pub medicare: Decimal,
+    pub net: Decimal,
+}
+
+/// Regular wage withholding calculator (Pub 15-T percentage method)
+pub struct FederalWithholdingCalculator<'a> {
+    data_provider: &'a dyn TaxDataProvider,
+}
+
+impl<'a> FederalWithholdingCalculator<'a> {
+    pub fn new(data_provider: &'a dyn TaxDataProvider) -> Self {
+        Self { data_provider }
+    }
+
+    /// Federal income tax withheld from one paycheck
+    ///
+    /// `wages` are the paycheck's federal taxable wages, after pre-tax
+    /// deductions. `filing_status` is the return's, used when the W-4
+    /// doesn't name its own.
+    pub fn per_paycheck(
+        &self,
+        wages: Decimal,
+        frequency: PayFrequency,
+        w4: &W4Settings,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> Decimal {
+        let periods = Decimal::from(frequency.withholding_periods());
+        let annual = self.annual_tentative(wages * periods, w4, filing_status, year);
+        let credits = w4.dependents_credit.max(Decimal::ZERO);
+        ((annual - credits).max(Decimal::ZERO) / periods).round_dp(2)
+            + w4.extra_withholding.max(Decimal::ZERO)
+    }
+
+    /// Every regular paycheck of the year, in order
+    ///
+    /// Federal withholding is the same on each paycheck; Social Security
+    /// drops off at the wage base and additional Medicare starts past
+    /// $200,000 of wages, regardless of filing status.
+    pub fn simulate_year(
+        &self,
+        pay: &RegularPay,
+        w4: &W4Settings,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> WithholdingSimulation {
+        let fica = self.data_provider.fica_config(year);
+        let gross = pay.gross.max(Decimal::ZERO);
+        let pre_tax = (pay.retirement_deferrals.max(Decimal::ZERO)
+            + pay.cafeteria_plan.max(Decimal::ZERO))
+        .min(gross);
+        let fica_wages = (gross - pay.cafeteria_plan.max(Decimal::ZERO)).max(Decimal::ZERO);
+        let federal_income_tax =
+            self.per_paycheck(gross - pre_tax, pay.frequency, w4, filing_status, year);
+
+        let mut ytd_fica_wages = Decimal::ZERO;
+        let paychecks: Vec<PaycheckWithholding> = (1..=pay.frequency.periods_per_year())
+            .map(|number| {
+                let before = ytd_fica_wages;
+                ytd_fica_wages += fica_wages;
+                let social_security = ((fica.wage_base - before).max(Decimal::ZERO))
+                    .min(fica_wages)
+                    * fica.social_security_rate;
+                let over_threshold = (ytd_fica_wages
+                    - ADDITIONAL_MEDICARE_WITHHOLDING_THRESHOLD.max(before))
+                .max(Decimal::ZERO);
+                let medicare = fica_wages * fica.medicare_rate
+                    + over_threshold * fica.additional_medicare_rate;
+                let social_security = social_security.round_dp(2);
+                let medicare = medicare.round_dp(2);
+
+                PaycheckWithholding {
+                    number,
+                    gross,
+                    pre_tax_deductions: pre_tax,
+                    federal_income_tax,
+                    social_security,
+                    medicare,
+                    net: gross - pre_tax - federal_income_tax - social_security - medicare,
+                    ytd_fica_wages,
+                }
+            })
+            .collect();
+
+        WithholdingSimulation {
+            federal_income_tax: paychecks.iter().map(|p| p.federal_income_tax).sum(),
+            social_security: paychecks.iter().map(|p| p.social_security).sum(),
+            medicare: paychecks.iter().map(|p| p.medicare).sum(),
+            net: paychecks.iter().map(|p| p.net).sum(),
+            paychecks,
+        }
+    }
+
+    /// Tentative annual withholding before Step 3 credits (Worksheet 1A)
+    ///
+    /// Worksheet 1A subtracts $12,900 ($8,600 unless joint) from wages and
+    /// starts the standard schedule that far below the standard deduction,
+    /// which nets to the filing status's brackets above the standard
+    /// deduction. The Step 2 schedule halves the standard deduction and
+    /// every bracket, as if the wages were one of two equal jobs.
+    fn annual_tentative(
+        &self,
+        annual_wages: Decimal,
+        w4: &W4Settings,
+        filing_status: FilingStatus,
+        year: u32,
+    ) -> Decimal {
+        let status = match w4.filing_status.unwrap_or(filing_status) {
+            FilingStatus::MarriedFilingJointly | FilingStatus::QualifyingWidower => {
+                FilingStatus::MarriedFilingJointly
+            },
+            FilingStatus::HeadOfHousehold => FilingStatus::HeadOfHousehold,
+            FilingStatus::Single | FilingStatus::MarriedFilingSeparately => FilingStatus::Single,
+        };
+        let federal = FederalTaxCalculator::new(self.data_provider);
+        let standard_deduction = federal.standard_deduction(status, year);
+        let adjusted = annual_wages + w4.other_income - w4.deductions;
+
+        if w4.multiple_jobs {
+            let taxable = (adjusted * dec!(2) - standard_deduction).max(Decimal::ZERO);
+            federal.calculate(taxable, status, year).tax / dec!(2)
+        } else {
+            let taxable = (adjusted - standard_deduction).max(Decimal::ZERO);
+            federal.calculate(taxable, status, year).tax
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn bonus(amount: Decimal, state: USState) -> SupplementalWithholdingInput {
+        SupplementalWithholdingInput {
+            amount,
+            state,
+            annual_wages: dec!(100000),
+            ytd_wages: dec!(50000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_percentage_method() {
+        let data = setup();
+        let calc = FederalWithholdingCalculator::new(&data);
+        let w4 = W4Settings::default();
+
+        // $52,000 a year: 10% of $11,600 and 12% of the next $25,800
+        let standard = calc.per_paycheck(
+            dec!(2000),
+            PayFrequency::BiWeekly,
+            &w4,
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(standard, (dec!(4256) / dec!(26)).round_dp(2));
+
+        // Step 2 taxes the paycheck as half of $104,000
+        let step_2 = calc.per_paycheck(
+            dec!(2000),
+            PayFrequency::BiWeekly,
+            &W4Settings {
+                multiple_jobs: true,
+                ..w4.clone()
+            },
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(step_2, dec!(283.10));
+
+        // Step 3 credits come off the annual amount; Step 4(c) is per paycheck
+        let adjusted = calc.per_paycheck(
+            dec!(2000),
+            PayFrequency::BiWeekly,
+            &W4Settings {
+                dependents_credit: dec!(2000),
+                extra_withholding: dec!(20),
+                ..w4
+            },
+            FilingStatus::Single,
+            2024,
+        );
+        assert_eq!(adjusted, dec!(106.77));
+    }
+
+    #[test]
+    fn test_simulated_year_crosses_fica_thresholds() {
+        let data = setup();
+        let calc = FederalWithholdingCalculator::new(&data);
+        // $260,000 a year paid bi-weekly, $500 a paycheck to a 401(k)
+        let pay = RegularPay {
+            gross: dec!(10000),
+            frequency: PayFrequency::BiWeekly,
+            retirement_deferrals: dec!(500),
+            ..Default::default()
+        };
+
+        let simulation =
+            calc.simulate_year(&pay, &W4Settings::default(), FilingStatus::Single, 2024);
+        let paychecks = &simulation.paychecks;
+        assert_eq!(paychecks.len(), 26);
+        assert!(paychecks
+            .iter()
+            .all(|p| p.federal_income_tax == paychecks[0].federal_income_tax));
+
+        // The $168,600 wage base is reached on the 17th paycheck
+        assert_eq!(paychecks[15].social_security, dec!(620));
+        assert_eq!(paychecks[16].social_security, dec!(8600) * dec!(0.062));
+        assert_eq!(paychecks[17].social_security, dec!(0));
+        assert_eq!(simulation.social_security, dec!(10453.20));
+
+        // Additional Medicare starts with the 21st paycheck, past $200,000
+        assert_eq!(paychecks[19].medicare, dec!(145));
+        assert_eq!(paychecks[20].medicare, dec!(145) + dec!(90));
+        assert_eq!(simulation.medicare, dec!(3770) + dec!(540));
+
+        assert_eq!(
+            paychecks[0].net,
+            dec!(10000) - dec!(500) - paychecks[0].federal_income_tax - dec!(620) - dec!(145)
+        );
+    }
+
+    #[test]
+    fn test_california_bonus_rate() {
+        let data = setup();
+        let calc = SupplementalWithholdingCalculator::new(&data);
+
+        let result = calc.calculate(&bonus(dec!(10000), USState::California), 2024);
+
+        assert_eq!(result.federal, dec!(2200));
+        assert_eq!(result.state_rate, dec!(0.1023));
+        assert_eq!(result.state, dec!(1023));
+        assert_eq!(result.social_security, dec!(620));
+        assert_eq!(result.medicare, dec!(145));
+        assert_eq!(result.net, dec!(10000) - result.total);
+
+        // Severance and PTO payouts get California's lower rate
+        for kind in [SupplementalKind::Severance, SupplementalKind::PtoPayout] {
+            let input = SupplementalWithholdingInput {
+                kind,
+                ..bonus(dec!(10000), USState::California)
+            };
+            let result = calc.calculate(&input, 2024);
+            assert_eq!(result.state_rate, dec!(0.066));
+            assert_eq!(result.state, dec!(660));
+        }
+        let vest = SupplementalWithholdingInput {
+            kind: SupplementalKind::RsuVest,
+            ..bonus(dec!(10000), USState::California)
+        };
+        assert_eq!(calc.calculate(&vest, 2024).state_rate, dec!(0.1023));
+    }
+
+    #[test]
+    fn test_mandatory_rate_above_one_million() {
+        let data = setup();
+        let calc = SupplementalWithholdingCalculator::new(&data);
+
+        let mut input = bonus(dec!(500000), USState::Texas);
+        input.ytd_supplemental_wages = dec!(800000);
+
+        let result = calc.calculate(&input, 2024);
+
+        // $200,000 at 22% + $300,000 at 37%
+        assert_eq!(result.federal, dec!(155000));
+        assert_eq!(result.state, dec!(0));
+    }
+
+    #[test]
+    fn test_rsu_vest_past_wage_base() {
+        let data = setup();
+        let calc = SupplementalWithholdingCalculator::new(&data);
+
+        let input = SupplementalWithholdingInput {
+            kind: SupplementalKind::RsuVest,
+            ytd_wages: dec!(190000),
+            ..bonus(dec!(20000), USState::NewYork)
+        };
+
+        let result = calc.calculate(&input, 2024);
+
+        assert_eq!(result.state, dec!(2340));
+        // Already past the $168,600 wage base
+        assert_eq!(result.social_security, dec!(0));
+        // 1.45% on all of it plus 0.9% on the $10,000 above $200k
+        assert_eq!(result.medicare, dec!(380));
+    }
+
+    #[test]
+    fn test_state_without_supplemental_rate_uses_marginal() {
+        let data = setup();
+        let calc = SupplementalWithholdingCalculator::new(&data);
+
+        // Rhode Island withholds supplemental wages via its regular brackets;
+        // $100,000 of wages is already in the 4.75% bracket
+        let result = calc.calculate(&bonus(dec!(10000), USState::RhodeIsland), 2024);
+
+        assert_eq!(result.state_rate, dec!(0.0475));
+    }
+}
@@ -0,0 +1,321 @@
+//! Cross-check of provider data against published reference figures
+//!
+//! Annual updates are checked by transcribing the IRS and state figures
+//! into a reference file and auditing the provider against it, rather than
+//! reading the tables side by side.
+//!
+//! A reference file is either CSV with `key,value` rows (a header row and
+//! `#` comments are skipped) or a JSON object mapping keys to values. Keys
+//! are dotted paths; rates are fractions:
+//!
+//! ```text
+//! federal.standard_deduction.single,14600
+//! federal.brackets.single.2.floor,47150
+//! federal.brackets.single.2.rate,0.22
+//! fica.wage_base,168600
+//! limits.elective_deferral,23000
+//! mileage.business,0.67
+//! state.CA.brackets.single.0.rate,0.01
+//! state.CA.sdi_rate,0.011
+//! ```
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{StateDeductionRule, TaxDataProvider};
+use crate::models::state::USState;
+use crate::models::tax::{FilingStatus, TaxBracket};
+
+/// Error reading a reference file
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    #[error("line {line}: {message}")]
+    Csv { line: usize, message: String },
+    #[error("reference JSON must be an object of key to value")]
+    NotAnObject,
+    #[error("{key}: value must be a number")]
+    InvalidValue { key: String },
+    #[error("invalid reference JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// One published figure
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceValue {
+    pub key: String,
+    pub value: Decimal,
+}
+
+/// How provider data disagrees with a reference figure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FindingKind {
+    Mismatch,
+    /// The key is valid but the provider has no such figure, such as a
+    /// bracket past the last one
+    Missing,
+    /// The key doesn't name any figure
+    UnknownKey,
+}
+
+/// A reference figure the provider doesn't match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub key: String,
+    pub kind: FindingKind,
+    pub expected: Decimal,
+    pub actual: Option<Decimal>,
+}
+
+/// Result of auditing a provider's year against a reference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub year: u32,
+    /// Reference figures compared
+    pub checked: usize,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl AuditReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Parse a CSV or JSON reference file, telling them apart by the first
+/// non-blank character
+pub fn parse_reference(text: &str) -> Result<Vec<ReferenceValue>, AuditError> {
+    if text.trim_start().starts_with('{') {
+        parse_json(text)
+    } else {
+        parse_csv(text)
+    }
+}
+
+fn parse_csv(text: &str) -> Result<Vec<ReferenceValue>, AuditError> {
+    let mut values = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let csv_error = |message: String| AuditError::Csv {
+            line: index + 1,
+            message,
+        };
+        let (key, value) = line
+            .split_once(',')
+            .ok_or_else(|| csv_error("expected key,value".to_string()))?;
+        let (key, value) = (key.trim(), value.trim().trim_matches('"'));
+        match value.replace([',', '$'], "").parse() {
+            Ok(value) => values.push(ReferenceValue {
+                key: key.to_string(),
+                value,
+            }),
+            // A header row
+            Err(_) if values.is_empty() && key.eq_ignore_ascii_case("key") => {},
+            Err(_) => return Err(csv_error(format!("not a number: {value}"))),
+        }
+    }
+    Ok(values)
+}
+
+fn parse_json(text: &str) -> Result<Vec<ReferenceValue>, AuditError> {
+    let Value::Object(map) = serde_json::from_str(text)? else {
+        return Err(AuditError::NotAnObject);
+    };
+    map.into_iter()
+        .map(|(key, value)| {
+            let value = match &value {
+                Value::Number(number) => number.to_string().parse().ok(),
+                Value::String(text) => text.parse().ok(),
+                _ => None,
+            };
+            match value {
+                Some(value) => Ok(ReferenceValue { key, value }),
+                None => Err(AuditError::InvalidValue { key }),
+            }
+        })
+        .collect()
+}
+
+/// Compare a provider's figures for a year against reference values
+pub fn audit(data: &dyn TaxDataProvider, year: u32, reference: &[ReferenceValue]) -> AuditReport {
+    let findings = reference
+        .iter()
+        .filter_map(|expected| {
+            let (kind, actual) = match lookup(data, year, &expected.key) {
+                None => (FindingKind::UnknownKey, None),
+                Some(None) => (FindingKind::Missing, None),
+                Some(Some(actual)) if actual == expected.value => return None,
+                Some(Some(actual)) => (FindingKind::Mismatch, Some(actual)),
+            };
+            Some(AuditFinding {
+                key: expected.key.clone(),
+                kind,
+                expected: expected.value,
+                actual,
+            })
+        })
+        .collect();
+
+    AuditReport {
+        year,
+        checked: reference.len(),
+        findings,
+    }
+}
+
+/// The provider's figure for a key: `None` for an unknown key, `Some(None)`
+/// when the provider has no such figure
+fn lookup(data: &dyn TaxDataProvider, year: u32, key: &str) -> Option<Option<Decimal>> {
+    let parts: Vec<&str> = key.split('.').collect();
+    match parts.as_slice() {
+        ["federal", "standard_deduction", status] => {
+            Some(Some(data.standard_deduction(status.parse().ok()?, year)))
+        },
+        ["federal", "brackets", status, index, field] => {
+            let status: FilingStatus = status.parse().ok()?;
+            bracket_field(&data.federal_brackets(status, year), index, field)
+        },
+        ["fica", field] => {
+            let fica = data.fica_config(year);
+            Some(Some(match *field {
+                "wage_base" => fica.wage_base,
+                "social_security_rate" => fica.social_security_rate,
+                "medicare_rate" => fica.medicare_rate,
+                "additional_medicare_rate" => fica.additional_medicare_rate,
+                _ => return None,
+            }))
+        },
+        ["limits", field] => {
+            let limits = data.retirement_limits(year);
+            Some(Some(match *field {
+                "elective_deferral" => limits.elective_deferral,
+                "catch_up_deferral" => limits.catch_up_deferral,
+                "total_annual_additions" => limits.total_annual_additions,
+                "compensation_limit" => limits.compensation_limit,
+                "ira_contribution" => limits.ira_contribution,
+                "ira_catch_up" => limits.ira_catch_up,
+                "hsa_self_only" => limits.hsa_self_only,
+                "hsa_family" => limits.hsa_family,
+                "hsa_catch_up" => limits.hsa_catch_up,
+                _ => return None,
+            }))
+        },
+        ["mileage", "business"] => Some(Some(data.business_mileage_rate(year))),
+        ["state", code, rest @ ..] => {
+            let config = data.state_config(code.parse::<USState>().ok()?, year);
+            match rest {
+                ["flat_rate"] => Some(config.flat_rate),
+                ["sdi_rate"] => Some(config.sdi_rate),
+                ["sdi_wage_base"] => Some(config.sdi_wage_base),
+                ["supplemental_rate"] => Some(config.supplemental_rate),
+                ["other_supplemental_rate"] => Some(config.other_supplemental_rate),
+                ["standard_deduction", status] => {
+                    let status: FilingStatus = status.parse().ok()?;
+                    Some(match &config.standard_deduction {
+                        StateDeductionRule::Fixed(amounts) => amounts.get(status.as_str()).copied(),
+                        _ => None,
+                    })
+                },
+                ["brackets", status, index, field] => {
+                    let status: FilingStatus = status.parse().ok()?;
+                    let brackets = config.brackets.get(status.as_str());
+                    bracket_field(brackets.map_or(&[], Vec::as_slice), index, field)
+                },
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+fn bracket_field(brackets: &[TaxBracket], index: &str, field: &str) -> Option<Option<Decimal>> {
+    let bracket = brackets.get(index.parse::<usize>().ok()?);
+    match field {
+        "floor" => Some(bracket.map(|b| b.floor)),
+        "ceiling" => Some(bracket.and_then(|b| b.ceiling)),
+        "rate" => Some(bracket.map(|b| b.rate.as_fraction())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        let csv = "key,value\n# 2024 Rev. Proc. 2023-34\nfica.wage_base,\"$168,600\"\n\nmileage.business,0.67\n";
+        let values = parse_reference(csv).unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].value, dec!(168600));
+
+        let json = r#"{"fica.wage_base": 168600, "mileage.business": "0.67"}"#;
+        assert_eq!(parse_reference(json).unwrap().len(), 2);
+
+        assert!(matches!(
+            parse_reference("fica.wage_base,168600\nmileage.business"),
+            Err(AuditError::Csv { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse_reference(r#"{"fica.wage_base": true}"#),
+            Err(AuditError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_audit_embedded_2024() {
+        let data = setup();
+        let reference = parse_reference(
+            "federal.standard_deduction.married_filing_jointly,29200
+federal.brackets.single.2.floor,47150
+federal.brackets.single.2.rate,0.22
+fica.wage_base,168600
+limits.elective_deferral,23000
+state.CA.sdi_rate,0.011
+state.TX.flat_rate,0.05
+federal.brackets.single.9.floor,1000000
+federal.brackets.single.2.floors,47150
+state.ZZ.flat_rate,0.05",
+        )
+        .unwrap();
+
+        let report = audit(&data, 2024, &reference);
+        assert_eq!(report.checked, 10);
+        let kinds: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.key.as_str(), finding.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("state.TX.flat_rate", FindingKind::Missing),
+                ("federal.brackets.single.9.floor", FindingKind::Missing),
+                ("federal.brackets.single.2.floors", FindingKind::UnknownKey),
+                ("state.ZZ.flat_rate", FindingKind::UnknownKey),
+            ]
+        );
+
+        // An outdated figure is a mismatch
+        let report = audit(
+            &data,
+            2024,
+            &[ReferenceValue {
+                key: "fica.wage_base".to_string(),
+                value: dec!(176100),
+            }],
+        );
+        assert!(!report.is_clean());
+        assert_eq!(report.findings[0].kind, FindingKind::Mismatch);
+        assert_eq!(report.findings[0].actual, Some(dec!(168600)));
+    }
+}
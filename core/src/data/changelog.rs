@@ -0,0 +1,426 @@
+//! What changed in the tax data from one year to another
+//!
+//! Compares everything a provider returns for two years and lists the
+//! differences in a form the apps can show on a "what changed" screen
+//! without knowing how the data is structured.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{StateConfig, StateDeductionRule, StateTaxType, TaxDataProvider};
+use crate::models::state::USState;
+use crate::models::tax::{FilingStatus, TaxBracket};
+
+const FILING_STATUSES: [FilingStatus; 5] = [
+    FilingStatus::Single,
+    FilingStatus::MarriedFilingJointly,
+    FilingStatus::MarriedFilingSeparately,
+    FilingStatus::HeadOfHousehold,
+    FilingStatus::QualifyingWidower,
+];
+
+/// Kind of change between years
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    /// A bracket's starting income moved
+    BracketThreshold,
+    /// Brackets were added or removed; values are bracket counts
+    BracketCount,
+    /// A tax or contribution rate changed; values are fractions
+    Rate,
+    StandardDeduction,
+    WageBase,
+    ContributionLimit,
+    MileageRate,
+    /// A state switched between no tax, flat, and progressive
+    TaxType,
+    /// A payroll program started; the new value is its rate
+    ProgramAdded,
+    /// A payroll program ended; the old value is its rate
+    ProgramRemoved,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::BracketThreshold => "bracket_threshold",
+            ChangeKind::BracketCount => "bracket_count",
+            ChangeKind::Rate => "rate",
+            ChangeKind::StandardDeduction => "standard_deduction",
+            ChangeKind::WageBase => "wage_base",
+            ChangeKind::ContributionLimit => "contribution_limit",
+            ChangeKind::MileageRate => "mileage_rate",
+            ChangeKind::TaxType => "tax_type",
+            ChangeKind::ProgramAdded => "program_added",
+            ChangeKind::ProgramRemoved => "program_removed",
+        }
+    }
+}
+
+/// One difference between the years
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataChange {
+    /// "Federal" or the state code
+    pub jurisdiction: String,
+    pub kind: ChangeKind,
+    /// What changed, e.g. "Single 22% bracket" or "Paid Family Leave"
+    pub item: String,
+    pub old_value: Option<Decimal>,
+    pub new_value: Option<Decimal>,
+}
+
+/// Changes from `from_year` to `to_year`, federal first, then states in
+/// alphabetical order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxDataChangelog {
+    pub from_year: u32,
+    pub to_year: u32,
+    pub changes: Vec<DataChange>,
+}
+
+impl TaxDataChangelog {
+    /// Changes for "Federal" or a state code
+    pub fn for_jurisdiction<'a>(
+        &'a self,
+        jurisdiction: &'a str,
+    ) -> impl Iterator<Item = &'a DataChange> + 'a {
+        self.changes
+            .iter()
+            .filter(move |change| change.jurisdiction == jurisdiction)
+    }
+}
+
+/// Everything that differs between a provider's data for two years
+pub fn diff_years(data: &dyn TaxDataProvider, from_year: u32, to_year: u32) -> TaxDataChangelog {
+    let mut log = Changes {
+        jurisdiction: "Federal".to_string(),
+        changes: Vec::new(),
+    };
+    for filing_status in FILING_STATUSES {
+        log.brackets(
+            filing_status,
+            &data.federal_brackets(filing_status, from_year),
+            &data.federal_brackets(filing_status, to_year),
+        );
+    }
+    for filing_status in FILING_STATUSES {
+        log.amount(
+            ChangeKind::StandardDeduction,
+            filing_status.display_name(),
+            data.standard_deduction(filing_status, from_year),
+            data.standard_deduction(filing_status, to_year),
+        );
+    }
+
+    let (old, new) = (data.fica_config(from_year), data.fica_config(to_year));
+    log.amount(
+        ChangeKind::WageBase,
+        "Social Security",
+        old.wage_base,
+        new.wage_base,
+    );
+    log.amount(
+        ChangeKind::Rate,
+        "Social Security",
+        old.social_security_rate,
+        new.social_security_rate,
+    );
+    log.amount(
+        ChangeKind::Rate,
+        "Medicare",
+        old.medicare_rate,
+        new.medicare_rate,
+    );
+    log.amount(
+        ChangeKind::Rate,
+        "Additional Medicare",
+        old.additional_medicare_rate,
+        new.additional_medicare_rate,
+    );
+
+    let (old, new) = (
+        data.retirement_limits(from_year),
+        data.retirement_limits(to_year),
+    );
+    for (item, old, new) in [
+        (
+            "401(k) elective deferral",
+            old.elective_deferral,
+            new.elective_deferral,
+        ),
+        (
+            "401(k) catch-up",
+            old.catch_up_deferral,
+            new.catch_up_deferral,
+        ),
+        (
+            "Total annual additions",
+            old.total_annual_additions,
+            new.total_annual_additions,
+        ),
+        ("IRA", old.ira_contribution, new.ira_contribution),
+        ("IRA catch-up", old.ira_catch_up, new.ira_catch_up),
+        ("HSA self-only", old.hsa_self_only, new.hsa_self_only),
+        ("HSA family", old.hsa_family, new.hsa_family),
+        ("HSA catch-up", old.hsa_catch_up, new.hsa_catch_up),
+    ] {
+        log.amount(ChangeKind::ContributionLimit, item, old, new);
+    }
+
+    log.amount(
+        ChangeKind::MileageRate,
+        "Business",
+        data.business_mileage_rate(from_year),
+        data.business_mileage_rate(to_year),
+    );
+
+    for state in USState::all() {
+        log.jurisdiction = state.code().to_string();
+        log.state(
+            &data.state_config(*state, from_year),
+            &data.state_config(*state, to_year),
+        );
+    }
+
+    TaxDataChangelog {
+        from_year,
+        to_year,
+        changes: log.changes,
+    }
+}
+
+/// Changes collected for the jurisdiction being compared
+#[derive(Default)]
+struct Changes {
+    jurisdiction: String,
+    changes: Vec<DataChange>,
+}
+
+impl Changes {
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        item: impl Into<String>,
+        old_value: Option<Decimal>,
+        new_value: Option<Decimal>,
+    ) {
+        self.changes.push(DataChange {
+            jurisdiction: self.jurisdiction.clone(),
+            kind,
+            item: item.into(),
+            old_value,
+            new_value,
+        });
+    }
+
+    fn amount(&mut self, kind: ChangeKind, item: &str, old: Decimal, new: Decimal) {
+        if old != new {
+            self.push(kind, item, Some(old), Some(new));
+        }
+    }
+
+    fn option(&mut self, kind: ChangeKind, item: &str, old: Option<Decimal>, new: Option<Decimal>) {
+        if old != new {
+            self.push(kind, item, old, new);
+        }
+    }
+
+    /// Brackets are matched by position; a different count is reported on
+    /// its own, since positions no longer line up
+    fn brackets(&mut self, filing_status: FilingStatus, old: &[TaxBracket], new: &[TaxBracket]) {
+        if old.len() != new.len() {
+            self.push(
+                ChangeKind::BracketCount,
+                format!("{} brackets", filing_status.display_name()),
+                Some(Decimal::from(old.len())),
+                Some(Decimal::from(new.len())),
+            );
+            return;
+        }
+        for (old, new) in old.iter().zip(new) {
+            let item = format!("{} {} bracket", filing_status.display_name(), new.rate);
+            if old.rate != new.rate {
+                self.push(
+                    ChangeKind::Rate,
+                    item.clone(),
+                    Some(old.rate.as_fraction()),
+                    Some(new.rate.as_fraction()),
+                );
+            }
+            self.amount(ChangeKind::BracketThreshold, &item, old.floor, new.floor);
+        }
+    }
+
+    fn state(&mut self, old: &StateConfig, new: &StateConfig) {
+        if old.tax_type != new.tax_type {
+            self.push(
+                ChangeKind::TaxType,
+                format!(
+                    "{} to {}",
+                    tax_type_name(old.tax_type),
+                    tax_type_name(new.tax_type)
+                ),
+                None,
+                None,
+            );
+        }
+        self.option(ChangeKind::Rate, "Flat rate", old.flat_rate, new.flat_rate);
+        if old.tax_type == StateTaxType::Progressive && new.tax_type == StateTaxType::Progressive {
+            for filing_status in FILING_STATUSES {
+                let key = filing_status.as_str();
+                if let (Some(old), Some(new)) = (old.brackets.get(key), new.brackets.get(key)) {
+                    self.brackets(filing_status, old, new);
+                }
+            }
+        }
+        if let (StateDeductionRule::Fixed(old), StateDeductionRule::Fixed(new)) =
+            (&old.standard_deduction, &new.standard_deduction)
+        {
+            for filing_status in FILING_STATUSES {
+                let key = filing_status.as_str();
+                self.option(
+                    ChangeKind::StandardDeduction,
+                    filing_status.display_name(),
+                    old.get(key).copied(),
+                    new.get(key).copied(),
+                );
+            }
+        }
+
+        self.option(
+            ChangeKind::Rate,
+            "Disability insurance",
+            old.sdi_rate,
+            new.sdi_rate,
+        );
+        self.option(
+            ChangeKind::WageBase,
+            "Disability insurance",
+            old.sdi_wage_base,
+            new.sdi_wage_base,
+        );
+        for program in &old.payroll_programs {
+            match new.payroll_programs.iter().find(|p| p.name == program.name) {
+                Some(current) => {
+                    self.amount(ChangeKind::Rate, &program.name, program.rate, current.rate);
+                    self.option(
+                        ChangeKind::WageBase,
+                        &program.name,
+                        program.wage_base,
+                        current.wage_base,
+                    );
+                },
+                None => self.push(
+                    ChangeKind::ProgramRemoved,
+                    program.name.clone(),
+                    Some(program.rate),
+                    None,
+                ),
+            }
+        }
+        for program in &new.payroll_programs {
+            if !old.payroll_programs.iter().any(|p| p.name == program.name) {
+                self.push(
+                    ChangeKind::ProgramAdded,
+                    program.name.clone(),
+                    None,
+                    Some(program.rate),
+                );
+            }
+        }
+    }
+}
+
+fn tax_type_name(tax_type: StateTaxType) -> &'static str {
+    match tax_type {
+        StateTaxType::NoTax => "No income tax",
+        StateTaxType::FlatRate => "Flat rate",
+        StateTaxType::Progressive => "Progressive brackets",
+        StateTaxType::MirrorFederal => "Mirror of federal tax",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::data::indexed::InflationIndexedData;
+    use crate::data::PayrollProgram;
+    use crate::models::tax::PayrollProgramKind;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    #[test]
+    fn test_embedded_2024_to_2025() {
+        let data = setup();
+        let log = diff_years(&data, 2024, 2025);
+
+        assert!(log.changes.contains(&DataChange {
+            jurisdiction: "Federal".to_string(),
+            kind: ChangeKind::MileageRate,
+            item: "Business".to_string(),
+            old_value: Some(dec!(0.67)),
+            new_value: Some(dec!(0.70)),
+        }));
+
+        // Louisiana moved to a flat 3% with a larger standard deduction
+        let louisiana: Vec<_> = log.for_jurisdiction("LA").collect();
+        assert_eq!(louisiana[0].kind, ChangeKind::TaxType);
+        assert_eq!(louisiana[0].item, "Progressive brackets to Flat rate");
+        assert!(louisiana
+            .iter()
+            .any(|change| change.kind == ChangeKind::Rate && change.new_value == Some(dec!(0.03))));
+
+        // Nothing else in the embedded data moves
+        assert_eq!(log.for_jurisdiction("CA").count(), 0);
+        assert!(diff_years(&data, 2024, 2024).changes.is_empty());
+    }
+
+    #[test]
+    fn test_indexed_thresholds_move() {
+        let data = setup();
+        let indexed = InflationIndexedData::new(&data, 2024, dec!(0.02));
+        let log = diff_years(&indexed, 2024, 2025);
+
+        let single_22 = log
+            .changes
+            .iter()
+            .find(|change| change.item == "Single 22% bracket")
+            .unwrap();
+        assert_eq!(single_22.kind, ChangeKind::BracketThreshold);
+        assert_eq!(single_22.old_value, Some(dec!(47150)));
+        assert_eq!(single_22.new_value, Some(dec!(48093)));
+        // Rates are never indexed
+        assert!(!log
+            .for_jurisdiction("Federal")
+            .any(|change| change.kind == ChangeKind::Rate));
+        assert!(log
+            .changes
+            .iter()
+            .any(|change| change.kind == ChangeKind::WageBase && change.item == "Social Security"));
+    }
+
+    #[test]
+    fn test_program_added_and_removed() {
+        let mut log = Changes::default();
+        let old = StateConfig::default();
+        let new = StateConfig {
+            payroll_programs: vec![PayrollProgram::new(
+                PayrollProgramKind::PaidFamilyLeave,
+                "PFML",
+                dec!(0.005),
+                None,
+            )],
+            ..Default::default()
+        };
+
+        log.state(&old, &new);
+        log.state(&new, &old);
+        assert_eq!(log.changes[0].kind, ChangeKind::ProgramAdded);
+        assert_eq!(log.changes[0].new_value, Some(dec!(0.005)));
+        assert_eq!(log.changes[1].kind, ChangeKind::ProgramRemoved);
+    }
+}
@@ -1,31 +1,108 @@
-//! Embedded tax data for 2024
+//! Embedded tax data for 2023 through 2025
+//!
+//! Every lookup takes the figures in effect for the year asked: the latest
+//! embedded year at or before it, or the earliest embedded year for years
+//! before that. `supports_year` reports only the years with their own
+//! federal figures, so callers that need exact data (`calculate_checked`,
+//! the FFI `TaxEngine`) reject other years instead of using the fallback.
 
 use once_cell::sync::Lazy;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use std::collections::HashMap;
 
-use super::{FicaConfig, LocalTaxInfo, StateConfig, StateTaxType, TaxDataProvider};
+use super::{
+    FicaConfig, IraPhaseOut, IrmaaSchedule, IrmaaTier, LocalIncomeTax, LocalTaxInfo, LocalWageTax,
+    MarriedCoupleCredit, PayrollProgram, PhaseOut, PovertyGuideline, PropertyTaxCredit,
+    RetirementLimits, RetirementTreatment, SchoolDistrictBase, SchoolDistrictTax, StateConfig,
+    StateDeductionRule, StateTaxType, TaxDataProvider, TaxRecapture,
+};
 use crate::models::state::USState;
-use crate::models::tax::{FilingStatus, TaxBracket};
+use crate::models::tax::{FilingStatus, PayrollProgramKind, TaxBracket};
 
-/// Embedded tax data provider with 2024 rates
-pub struct EmbeddedTaxData {
-    federal_brackets: HashMap<FilingStatus, Vec<TaxBracket>>,
+/// Latest tax year with embedded federal figures
+pub const LATEST_YEAR: u32 = 2025;
+
+/// Year the base state configs describe; other years are keyed separately
+const STATE_BASE_YEAR: u32 = 2024;
+
+/// Federal figures for one tax year
+struct FederalYear {
+    brackets: HashMap<FilingStatus, Vec<TaxBracket>>,
     standard_deductions: HashMap<FilingStatus, Decimal>,
     fica_config: FicaConfig,
-    state_configs: HashMap<USState, StateConfig>,
+    retirement_limits: RetirementLimits,
+}
+
+/// Embedded tax data provider with 2023 through 2025 rates
+pub struct EmbeddedTaxData {
+    /// Federal figures by tax year
+    federal_years: Vec<(u32, FederalYear)>,
+    /// State configs by the first year each applies
+    state_configs: HashMap<USState, Vec<(u32, StateConfig)>>,
+    uniform_lifetime_table: HashMap<u32, Decimal>,
+    /// Guidelines by year they were published; Alaska and Hawaii have their own
+    poverty_guidelines: Vec<(u32, PovertyGuideline, HashMap<USState, PovertyGuideline>)>,
+    mileage_rates: Vec<(u32, Decimal)>,
+    irmaa_schedules: Vec<(u32, IrmaaSchedule)>,
+    flat_rate_schedule: HashMap<USState, Vec<(u32, Decimal)>>,
 }
 
 impl EmbeddedTaxData {
     pub fn new() -> Self {
         Self {
-            federal_brackets: build_federal_brackets_2024(),
-            standard_deductions: build_standard_deductions_2024(),
-            fica_config: build_fica_config_2024(),
-            state_configs: build_state_configs_2024(),
+            federal_years: vec![
+                (
+                    2023,
+                    FederalYear {
+                        brackets: build_federal_brackets_2023(),
+                        standard_deductions: build_standard_deductions_2023(),
+                        fica_config: build_fica_config_2023(),
+                        retirement_limits: build_retirement_limits_2023(),
+                    },
+                ),
+                (
+                    2024,
+                    FederalYear {
+                        brackets: build_federal_brackets_2024(),
+                        standard_deductions: build_standard_deductions_2024(),
+                        fica_config: build_fica_config_2024(),
+                        retirement_limits: build_retirement_limits_2024(),
+                    },
+                ),
+                (
+                    2025,
+                    FederalYear {
+                        brackets: build_federal_brackets_2025(),
+                        standard_deductions: build_standard_deductions_2025(),
+                        fica_config: build_fica_config_2025(),
+                        retirement_limits: build_retirement_limits_2025(),
+                    },
+                ),
+            ],
+            state_configs: build_state_configs_by_year(),
+            uniform_lifetime_table: build_uniform_lifetime_table(),
+            poverty_guidelines: build_poverty_guidelines(),
+            mileage_rates: build_mileage_rates(),
+            irmaa_schedules: build_irmaa_schedules(),
+            flat_rate_schedule: build_flat_rate_schedule(),
         }
     }
+
+    fn federal_year(&self, year: u32) -> &FederalYear {
+        in_effect(&self.federal_years, year)
+    }
+}
+
+/// Entry in effect for `year` from entries sorted by year: the latest at or
+/// before it, or the earliest when `year` precedes them all
+fn in_effect<T>(entries: &[(u32, T)], year: u32) -> &T {
+    &entries
+        .iter()
+        .rev()
+        .find(|(effective, _)| *effective <= year)
+        .unwrap_or(&entries[0])
+        .1
 }
 
 impl Default for EmbeddedTaxData {
@@ -35,33 +112,124 @@ impl Default for EmbeddedTaxData {
 }
 
 impl TaxDataProvider for EmbeddedTaxData {
-    fn federal_brackets(&self, filing_status: FilingStatus, _year: u32) -> Vec<TaxBracket> {
-        self.federal_brackets
+    fn federal_brackets(&self, filing_status: FilingStatus, year: u32) -> Vec<TaxBracket> {
+        self.federal_year(year)
+            .brackets
             .get(&filing_status)
             .cloned()
             .unwrap_or_default()
     }
 
-    fn standard_deduction(&self, filing_status: FilingStatus, _year: u32) -> Decimal {
-        self.standard_deductions
+    fn standard_deduction(&self, filing_status: FilingStatus, year: u32) -> Decimal {
+        let deductions = &self.federal_year(year).standard_deductions;
+        deductions
             .get(&filing_status)
+            .or_else(|| deductions.get(&FilingStatus::Single))
             .copied()
-            .unwrap_or(dec!(14600))
+            .unwrap_or_default()
     }
 
-    fn fica_config(&self, _year: u32) -> FicaConfig {
-        self.fica_config.clone()
+    fn fica_config(&self, year: u32) -> FicaConfig {
+        self.federal_year(year).fica_config.clone()
     }
 
-    fn state_config(&self, state: USState, _year: u32) -> StateConfig {
-        self.state_configs
+    fn state_config(&self, state: USState, year: u32) -> StateConfig {
+        let mut config = self
+            .state_configs
             .get(&state)
+            .map(|configs| in_effect(configs, year))
             .cloned()
             .unwrap_or_else(|| StateConfig {
                 state_code: state.code().to_string(),
                 tax_type: StateTaxType::NoTax,
                 ..Default::default()
-            })
+            });
+
+        // Flat rates that change by year, using the latest rate in effect
+        let scheduled_rate = self.flat_rate_schedule.get(&state).and_then(|schedule| {
+            schedule
+                .iter()
+                .rev()
+                .find(|(effective, _)| *effective <= year)
+                .map(|(_, rate)| *rate)
+        });
+        if let Some(rate) = scheduled_rate {
+            config.tax_type = StateTaxType::FlatRate;
+            config.flat_rate = Some(rate);
+        }
+
+        config
+    }
+
+    fn retirement_limits(&self, year: u32) -> RetirementLimits {
+        self.federal_year(year).retirement_limits.clone()
+    }
+
+    fn rmd_distribution_period(&self, age: u32, _year: u32) -> Option<Decimal> {
+        // The table ends at 120; older ages use the final period
+        self.uniform_lifetime_table.get(&age.min(120)).copied()
+    }
+
+    /// Latest guidelines published by `year`, or the earliest embedded
+    fn poverty_guideline(&self, state: USState, year: u32) -> PovertyGuideline {
+        let (_, contiguous, by_state) = self
+            .poverty_guidelines
+            .iter()
+            .rev()
+            .find(|(published, _, _)| *published <= year)
+            .unwrap_or(&self.poverty_guidelines[0]);
+        by_state.get(&state).copied().unwrap_or(*contiguous)
+    }
+
+    /// Year of the guidelines `poverty_guideline` returns for `year`
+    fn poverty_guideline_year(&self, year: u32) -> u32 {
+        self.poverty_guidelines
+            .iter()
+            .rev()
+            .find(|(published, _, _)| *published <= year)
+            .unwrap_or(&self.poverty_guidelines[0])
+            .0
+    }
+
+    /// Latest rate in effect by `year`, or the earliest embedded
+    fn business_mileage_rate(&self, year: u32) -> Decimal {
+        *in_effect(&self.mileage_rates, year)
+    }
+
+    /// Latest schedule in effect by `year`, or the earliest embedded
+    fn irmaa_schedule(&self, year: u32) -> IrmaaSchedule {
+        in_effect(&self.irmaa_schedules, year).clone()
+    }
+
+    /// `year` when the state's config or flat rate was embedded for it,
+    /// otherwise the year of the config in effect
+    fn state_data_year(&self, state: USState, year: u32) -> u32 {
+        let Some(configs) = self.state_configs.get(&state) else {
+            return year;
+        };
+        let rate_embedded = self
+            .flat_rate_schedule
+            .get(&state)
+            .is_some_and(|schedule| schedule.iter().any(|(effective, _)| *effective == year));
+        if state.has_no_income_tax()
+            || rate_embedded
+            || configs.iter().any(|(effective, _)| *effective == year)
+        {
+            return year;
+        }
+        configs
+            .iter()
+            .rev()
+            .find(|(effective, _)| *effective <= year)
+            .unwrap_or(&configs[0])
+            .0
+    }
+
+    /// Years with their own federal figures; others reuse the nearest year's
+    fn supports_year(&self, year: u32) -> bool {
+        self.federal_years
+            .iter()
+            .any(|(embedded, _)| *embedded == year)
     }
 }
 
@@ -74,9 +242,80 @@ pub fn get_embedded_data() -> &'static EmbeddedTaxData {
 }
 
 // ============================================================================
-// 2024 Federal Tax Brackets
+// Federal Tax Brackets
 // ============================================================================
 
+fn build_federal_brackets_2023() -> HashMap<FilingStatus, Vec<TaxBracket>> {
+    let mut brackets = HashMap::new();
+
+    // Single
+    brackets.insert(
+        FilingStatus::Single,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(11000)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(11000), Some(dec!(44725)), dec!(0.12), dec!(1100)),
+            TaxBracket::new(dec!(44725), Some(dec!(95375)), dec!(0.22), dec!(5147)),
+            TaxBracket::new(dec!(95375), Some(dec!(182100)), dec!(0.24), dec!(16290)),
+            TaxBracket::new(dec!(182100), Some(dec!(231250)), dec!(0.32), dec!(37104)),
+            TaxBracket::new(dec!(231250), Some(dec!(578125)), dec!(0.35), dec!(52832)),
+            TaxBracket::new(dec!(578125), None, dec!(0.37), dec!(174238.25)),
+        ],
+    );
+
+    // Married Filing Jointly
+    brackets.insert(
+        FilingStatus::MarriedFilingJointly,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(22000)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(22000), Some(dec!(89450)), dec!(0.12), dec!(2200)),
+            TaxBracket::new(dec!(89450), Some(dec!(190750)), dec!(0.22), dec!(10294)),
+            TaxBracket::new(dec!(190750), Some(dec!(364200)), dec!(0.24), dec!(32580)),
+            TaxBracket::new(dec!(364200), Some(dec!(462500)), dec!(0.32), dec!(74208)),
+            TaxBracket::new(dec!(462500), Some(dec!(693750)), dec!(0.35), dec!(105664)),
+            TaxBracket::new(dec!(693750), None, dec!(0.37), dec!(186601.50)),
+        ],
+    );
+
+    // Married Filing Separately
+    brackets.insert(
+        FilingStatus::MarriedFilingSeparately,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(11000)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(11000), Some(dec!(44725)), dec!(0.12), dec!(1100)),
+            TaxBracket::new(dec!(44725), Some(dec!(95375)), dec!(0.22), dec!(5147)),
+            TaxBracket::new(dec!(95375), Some(dec!(182100)), dec!(0.24), dec!(16290)),
+            TaxBracket::new(dec!(182100), Some(dec!(231250)), dec!(0.32), dec!(37104)),
+            TaxBracket::new(dec!(231250), Some(dec!(346875)), dec!(0.35), dec!(52832)),
+            TaxBracket::new(dec!(346875), None, dec!(0.37), dec!(93300.75)),
+        ],
+    );
+
+    // Head of Household
+    brackets.insert(
+        FilingStatus::HeadOfHousehold,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(15700)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(15700), Some(dec!(59850)), dec!(0.12), dec!(1570)),
+            TaxBracket::new(dec!(59850), Some(dec!(95350)), dec!(0.22), dec!(6868)),
+            TaxBracket::new(dec!(95350), Some(dec!(182100)), dec!(0.24), dec!(14678)),
+            TaxBracket::new(dec!(182100), Some(dec!(231250)), dec!(0.32), dec!(35498)),
+            TaxBracket::new(dec!(231250), Some(dec!(578100)), dec!(0.35), dec!(51226)),
+            TaxBracket::new(dec!(578100), None, dec!(0.37), dec!(172623.50)),
+        ],
+    );
+
+    // Qualifying Widower (same as MFJ)
+    brackets.insert(
+        FilingStatus::QualifyingWidower,
+        brackets
+            .get(&FilingStatus::MarriedFilingJointly)
+            .unwrap()
+            .clone(),
+    );
+
+    brackets
+}
+
 fn build_federal_brackets_2024() -> HashMap<FilingStatus, Vec<TaxBracket>> {
     let mut brackets = HashMap::new();
 
@@ -148,6 +387,87 @@ fn build_federal_brackets_2024() -> HashMap<FilingStatus, Vec<TaxBracket>> {
     brackets
 }
 
+fn build_federal_brackets_2025() -> HashMap<FilingStatus, Vec<TaxBracket>> {
+    let mut brackets = HashMap::new();
+
+    // Single
+    brackets.insert(
+        FilingStatus::Single,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(11925)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(11925), Some(dec!(48475)), dec!(0.12), dec!(1192.50)),
+            TaxBracket::new(dec!(48475), Some(dec!(103350)), dec!(0.22), dec!(5578.50)),
+            TaxBracket::new(dec!(103350), Some(dec!(197300)), dec!(0.24), dec!(17651)),
+            TaxBracket::new(dec!(197300), Some(dec!(250525)), dec!(0.32), dec!(40199)),
+            TaxBracket::new(dec!(250525), Some(dec!(626350)), dec!(0.35), dec!(57231)),
+            TaxBracket::new(dec!(626350), None, dec!(0.37), dec!(188769.75)),
+        ],
+    );
+
+    // Married Filing Jointly
+    brackets.insert(
+        FilingStatus::MarriedFilingJointly,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(23850)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(23850), Some(dec!(96950)), dec!(0.12), dec!(2385)),
+            TaxBracket::new(dec!(96950), Some(dec!(206700)), dec!(0.22), dec!(11157)),
+            TaxBracket::new(dec!(206700), Some(dec!(394600)), dec!(0.24), dec!(35302)),
+            TaxBracket::new(dec!(394600), Some(dec!(501050)), dec!(0.32), dec!(80398)),
+            TaxBracket::new(dec!(501050), Some(dec!(751600)), dec!(0.35), dec!(114462)),
+            TaxBracket::new(dec!(751600), None, dec!(0.37), dec!(202154.50)),
+        ],
+    );
+
+    // Married Filing Separately
+    brackets.insert(
+        FilingStatus::MarriedFilingSeparately,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(11925)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(11925), Some(dec!(48475)), dec!(0.12), dec!(1192.50)),
+            TaxBracket::new(dec!(48475), Some(dec!(103350)), dec!(0.22), dec!(5578.50)),
+            TaxBracket::new(dec!(103350), Some(dec!(197300)), dec!(0.24), dec!(17651)),
+            TaxBracket::new(dec!(197300), Some(dec!(250525)), dec!(0.32), dec!(40199)),
+            TaxBracket::new(dec!(250525), Some(dec!(375800)), dec!(0.35), dec!(57231)),
+            TaxBracket::new(dec!(375800), None, dec!(0.37), dec!(101077.25)),
+        ],
+    );
+
+    // Head of Household
+    brackets.insert(
+        FilingStatus::HeadOfHousehold,
+        vec![
+            TaxBracket::new(dec!(0), Some(dec!(17000)), dec!(0.10), dec!(0)),
+            TaxBracket::new(dec!(17000), Some(dec!(64850)), dec!(0.12), dec!(1700)),
+            TaxBracket::new(dec!(64850), Some(dec!(103350)), dec!(0.22), dec!(7442)),
+            TaxBracket::new(dec!(103350), Some(dec!(197300)), dec!(0.24), dec!(15912)),
+            TaxBracket::new(dec!(197300), Some(dec!(250500)), dec!(0.32), dec!(38460)),
+            TaxBracket::new(dec!(250500), Some(dec!(626350)), dec!(0.35), dec!(55484)),
+            TaxBracket::new(dec!(626350), None, dec!(0.37), dec!(187031.50)),
+        ],
+    );
+
+    // Qualifying Widower (same as MFJ)
+    brackets.insert(
+        FilingStatus::QualifyingWidower,
+        brackets
+            .get(&FilingStatus::MarriedFilingJointly)
+            .unwrap()
+            .clone(),
+    );
+
+    brackets
+}
+
+fn build_standard_deductions_2023() -> HashMap<FilingStatus, Decimal> {
+    let mut deductions = HashMap::new();
+    deductions.insert(FilingStatus::Single, dec!(13850));
+    deductions.insert(FilingStatus::MarriedFilingJointly, dec!(27700));
+    deductions.insert(FilingStatus::MarriedFilingSeparately, dec!(13850));
+    deductions.insert(FilingStatus::HeadOfHousehold, dec!(20800));
+    deductions.insert(FilingStatus::QualifyingWidower, dec!(27700));
+    deductions
+}
+
 fn build_standard_deductions_2024() -> HashMap<FilingStatus, Decimal> {
     let mut deductions = HashMap::new();
     deductions.insert(FilingStatus::Single, dec!(14600));
@@ -158,6 +478,24 @@ fn build_standard_deductions_2024() -> HashMap<FilingStatus, Decimal> {
     deductions
 }
 
+/// 2025 amounts as raised mid-year by the One Big Beautiful Bill Act
+fn build_standard_deductions_2025() -> HashMap<FilingStatus, Decimal> {
+    let mut deductions = HashMap::new();
+    deductions.insert(FilingStatus::Single, dec!(15750));
+    deductions.insert(FilingStatus::MarriedFilingJointly, dec!(31500));
+    deductions.insert(FilingStatus::MarriedFilingSeparately, dec!(15750));
+    deductions.insert(FilingStatus::HeadOfHousehold, dec!(23625));
+    deductions.insert(FilingStatus::QualifyingWidower, dec!(31500));
+    deductions
+}
+
+fn build_fica_config_2023() -> FicaConfig {
+    FicaConfig {
+        wage_base: dec!(160200),
+        ..build_fica_config_2024()
+    }
+}
+
 fn build_fica_config_2024() -> FicaConfig {
     FicaConfig {
         social_security_rate: dec!(0.062),
@@ -167,6 +505,251 @@ fn build_fica_config_2024() -> FicaConfig {
     }
 }
 
+fn build_fica_config_2025() -> FicaConfig {
+    FicaConfig {
+        wage_base: dec!(176100),
+        ..build_fica_config_2024()
+    }
+}
+
+fn build_retirement_limits_2023() -> RetirementLimits {
+    RetirementLimits {
+        elective_deferral: dec!(22500),
+        catch_up_deferral: dec!(7500),
+        total_annual_additions: dec!(66000),
+        compensation_limit: dec!(330000),
+        employer_contribution_rate: dec!(0.25),
+        ira_contribution: dec!(6500),
+        ira_catch_up: dec!(1000),
+        hsa_self_only: dec!(3850),
+        hsa_family: dec!(7750),
+        hsa_catch_up: dec!(1000),
+        ira_deduction_phase_out: IraPhaseOut {
+            single_start: dec!(73000),
+            single_range: dec!(10000),
+            joint_start: dec!(116000),
+            joint_range: dec!(20000),
+        },
+        roth_ira_phase_out: IraPhaseOut {
+            single_start: dec!(138000),
+            single_range: dec!(15000),
+            joint_start: dec!(218000),
+            joint_range: dec!(10000),
+        },
+    }
+}
+
+fn build_retirement_limits_2024() -> RetirementLimits {
+    RetirementLimits {
+        elective_deferral: dec!(23000),
+        catch_up_deferral: dec!(7500),
+        total_annual_additions: dec!(69000),
+        compensation_limit: dec!(345000),
+        employer_contribution_rate: dec!(0.25),
+        ira_contribution: dec!(7000),
+        ira_catch_up: dec!(1000),
+        hsa_self_only: dec!(4150),
+        hsa_family: dec!(8300),
+        hsa_catch_up: dec!(1000),
+        ira_deduction_phase_out: IraPhaseOut {
+            single_start: dec!(77000),
+            single_range: dec!(10000),
+            joint_start: dec!(123000),
+            joint_range: dec!(20000),
+        },
+        roth_ira_phase_out: IraPhaseOut {
+            single_start: dec!(146000),
+            single_range: dec!(15000),
+            joint_start: dec!(230000),
+            joint_range: dec!(10000),
+        },
+    }
+}
+
+fn build_retirement_limits_2025() -> RetirementLimits {
+    RetirementLimits {
+        elective_deferral: dec!(23500),
+        total_annual_additions: dec!(70000),
+        compensation_limit: dec!(350000),
+        hsa_self_only: dec!(4300),
+        hsa_family: dec!(8550),
+        ira_deduction_phase_out: IraPhaseOut {
+            single_start: dec!(79000),
+            single_range: dec!(10000),
+            joint_start: dec!(126000),
+            joint_range: dec!(20000),
+        },
+        roth_ira_phase_out: IraPhaseOut {
+            single_start: dec!(150000),
+            single_range: dec!(15000),
+            joint_start: dec!(236000),
+            joint_range: dec!(10000),
+        },
+        ..build_retirement_limits_2024()
+    }
+}
+
+/// HHS poverty guidelines for the 48 contiguous states and DC, with
+/// Alaska's and Hawaii's separate figures
+fn build_poverty_guidelines() -> Vec<(u32, PovertyGuideline, HashMap<USState, PovertyGuideline>)> {
+    let guideline = |first_person, each_additional_person| PovertyGuideline {
+        first_person,
+        each_additional_person,
+    };
+    vec![
+        (
+            2023,
+            guideline(dec!(14580), dec!(5140)),
+            HashMap::from([
+                (USState::Alaska, guideline(dec!(18210), dec!(6430))),
+                (USState::Hawaii, guideline(dec!(16770), dec!(5910))),
+            ]),
+        ),
+        (
+            2024,
+            guideline(dec!(15060), dec!(5380)),
+            HashMap::from([
+                (USState::Alaska, guideline(dec!(18810), dec!(6730))),
+                (USState::Hawaii, guideline(dec!(17310), dec!(6190))),
+            ]),
+        ),
+        (
+            2025,
+            guideline(dec!(15650), dec!(5500)),
+            HashMap::from([
+                (USState::Alaska, guideline(dec!(19550), dec!(6880))),
+                (USState::Hawaii, guideline(dec!(17990), dec!(6325))),
+            ]),
+        ),
+    ]
+}
+
+/// IRS standard mileage rates for business use of a vehicle
+fn build_mileage_rates() -> Vec<(u32, Decimal)> {
+    vec![(2023, dec!(0.655)), (2024, dec!(0.67)), (2025, dec!(0.70))]
+}
+
+/// CMS Medicare Part B premiums and IRMAA tiers (Part D surcharges are
+/// added to the plan's own premium)
+fn build_irmaa_schedules() -> Vec<(u32, IrmaaSchedule)> {
+    // Thresholds for individual and joint filers, with Part B and Part D
+    // surcharges
+    let schedule = |part_b_premium,
+                    tiers: [(Decimal, Decimal, Decimal, Decimal); 5],
+                    separate: [(Decimal, Decimal, Decimal); 2]| {
+        IrmaaSchedule {
+            part_b_premium,
+            individual: tiers
+                .iter()
+                .map(|&(individual, _, b, d)| IrmaaTier::new(individual, b, d))
+                .collect(),
+            joint: tiers
+                .iter()
+                .map(|&(_, joint, b, d)| IrmaaTier::new(joint, b, d))
+                .collect(),
+            married_separately: separate
+                .iter()
+                .map(|&(threshold, b, d)| IrmaaTier::new(threshold, b, d))
+                .collect(),
+        }
+    };
+
+    vec![
+        (
+            2024,
+            schedule(
+                dec!(174.70),
+                [
+                    (dec!(103000), dec!(206000), dec!(69.90), dec!(12.90)),
+                    (dec!(129000), dec!(258000), dec!(174.70), dec!(33.30)),
+                    (dec!(161000), dec!(322000), dec!(279.50), dec!(53.80)),
+                    (dec!(193000), dec!(386000), dec!(384.30), dec!(74.20)),
+                    (dec!(500000), dec!(750000), dec!(419.30), dec!(81.00)),
+                ],
+                [
+                    (dec!(103000), dec!(384.30), dec!(74.20)),
+                    (dec!(397000), dec!(419.30), dec!(81.00)),
+                ],
+            ),
+        ),
+        (
+            2025,
+            schedule(
+                dec!(185.00),
+                [
+                    (dec!(106000), dec!(212000), dec!(74.00), dec!(13.70)),
+                    (dec!(133000), dec!(266000), dec!(185.00), dec!(35.30)),
+                    (dec!(167000), dec!(334000), dec!(295.90), dec!(57.00)),
+                    (dec!(200000), dec!(400000), dec!(406.90), dec!(78.60)),
+                    (dec!(500000), dec!(750000), dec!(443.90), dec!(85.80)),
+                ],
+                [
+                    (dec!(106000), dec!(406.90), dec!(78.60)),
+                    (dec!(394000), dec!(443.90), dec!(85.80)),
+                ],
+            ),
+        ),
+    ]
+}
+
+/// IRS Uniform Lifetime Table (Treas. Reg. §1.401(a)(9)-9, effective 2022)
+fn build_uniform_lifetime_table() -> HashMap<u32, Decimal> {
+    let periods = [
+        dec!(27.4),
+        dec!(26.5),
+        dec!(25.5),
+        dec!(24.6),
+        dec!(23.7),
+        dec!(22.9),
+        dec!(22.0),
+        dec!(21.1),
+        dec!(20.2),
+        dec!(19.4),
+        dec!(18.5),
+        dec!(17.7),
+        dec!(16.8),
+        dec!(16.0),
+        dec!(15.2),
+        dec!(14.4),
+        dec!(13.7),
+        dec!(12.9),
+        dec!(12.2),
+        dec!(11.5),
+        dec!(10.8),
+        dec!(10.1),
+        dec!(9.5),
+        dec!(8.9),
+        dec!(8.4),
+        dec!(7.8),
+        dec!(7.3),
+        dec!(6.8),
+        dec!(6.4),
+        dec!(6.0),
+        dec!(5.6),
+        dec!(5.2),
+        dec!(4.9),
+        dec!(4.6),
+        dec!(4.3),
+        dec!(4.1),
+        dec!(3.9),
+        dec!(3.7),
+        dec!(3.5),
+        dec!(3.4),
+        dec!(3.3),
+        dec!(3.1),
+        dec!(3.0),
+        dec!(2.9),
+        dec!(2.8),
+        dec!(2.7),
+        dec!(2.5),
+        dec!(2.3),
+        dec!(2.0),
+    ];
+
+    // Table starts at age 72
+    (72u32..).zip(periods).collect()
+}
+
 // ============================================================================
 // 2024 State Tax Configurations
 // ============================================================================
@@ -196,16 +779,81 @@ fn build_state_configs_2024() -> HashMap<USState, StateConfig> {
         );
     }
 
+    // Employee share (71.43%) of the 0.74% PFML premium, and WA Cares
+    configs
+        .get_mut(&USState::Washington)
+        .expect("Washington configured above")
+        .payroll_programs = vec![
+        PayrollProgram::new(
+            PayrollProgramKind::PaidFamilyLeave,
+            "PFML",
+            dec!(0.00528582),
+            Some(dec!(168600)),
+        ),
+        PayrollProgram::new(
+            PayrollProgramKind::LongTermCare,
+            "WA Cares",
+            dec!(0.0058),
+            None,
+        ),
+    ];
+
     // Flat tax states
     configs.insert(USState::Colorado, flat_tax_config("CO", dec!(0.044)));
     configs.insert(USState::Illinois, flat_tax_config("IL", dec!(0.0495)));
-    configs.insert(USState::Indiana, flat_tax_config("IN", dec!(0.0305)));
-    configs.insert(USState::Kentucky, flat_tax_config("KY", dec!(0.04)));
-    configs.insert(USState::Massachusetts, flat_tax_config("MA", dec!(0.05)));
+    configs.insert(
+        USState::Indiana,
+        StateConfig {
+            // $1,000 per taxpayer
+            personal_exemption: Some(by_filing_status(
+                dec!(1000),
+                dec!(2000),
+                dec!(1000),
+                dec!(1000),
+            )),
+            ..flat_tax_config("IN", dec!(0.0305))
+        },
+    );
+    configs.insert(
+        USState::Kentucky,
+        StateConfig {
+            standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+                dec!(3160),
+                dec!(3160),
+                dec!(3160),
+                dec!(3160),
+            )),
+            ..flat_tax_config("KY", dec!(0.04))
+        },
+    );
+    configs.insert(
+        USState::Massachusetts,
+        StateConfig {
+            // Employee share of PFML: all family leave, 40% of medical leave
+            payroll_programs: vec![PayrollProgram::new(
+                PayrollProgramKind::PaidFamilyLeave,
+                "PFML",
+                dec!(0.0046),
+                Some(dec!(168600)),
+            )],
+            ..flat_tax_config("MA", dec!(0.05))
+        },
+    );
     configs.insert(USState::Michigan, flat_tax_config("MI", dec!(0.0425)));
-    configs.insert(USState::NorthCarolina, flat_tax_config("NC", dec!(0.0525)));
-    configs.insert(USState::Pennsylvania, flat_tax_config("PA", dec!(0.0307)));
-    configs.insert(USState::Utah, flat_tax_config("UT", dec!(0.0465)));
+    configs.insert(
+        USState::NorthCarolina,
+        StateConfig {
+            standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+                dec!(12750),
+                dec!(25500),
+                dec!(12750),
+                dec!(19125),
+            )),
+            ..flat_tax_config("NC", dec!(0.045))
+        },
+    );
+    configs.insert(USState::Pennsylvania, pennsylvania_config());
+    configs.insert(USState::Utah, utah_config());
 
     // California - progressive with SDI
     configs.insert(USState::California, california_config());
@@ -220,25 +868,214 @@ fn build_state_configs_2024() -> HashMap<USState, StateConfig> {
     configs.insert(USState::NewJersey, new_jersey_config());
     configs.insert(USState::Oregon, oregon_config());
     configs.insert(USState::Virginia, virginia_config());
+    configs.insert(USState::Vermont, vermont_config());
+    configs.insert(USState::Wisconsin, wisconsin_config());
+    configs.insert(USState::Alabama, alabama_config());
+    configs.insert(USState::Arkansas, arkansas_config());
+    configs.insert(USState::Connecticut, connecticut_config());
+    configs.insert(USState::Delaware, delaware_config());
+    configs.insert(USState::Hawaii, hawaii_config());
+    configs.insert(USState::Idaho, idaho_config(dec!(0.05695)));
+    configs.insert(USState::Iowa, iowa_config());
+    configs.insert(USState::Kansas, kansas_config());
+    configs.insert(USState::Louisiana, louisiana_config());
+    configs.insert(USState::Maine, maine_config());
+    configs.insert(USState::Maryland, maryland_config());
+    configs.insert(USState::Mississippi, mississippi_config(dec!(0.047)));
+    configs.insert(USState::Missouri, missouri_config());
+    configs.insert(USState::Montana, montana_config());
+    configs.insert(USState::NewMexico, new_mexico_config());
+    configs.insert(USState::Oklahoma, oklahoma_config());
+    configs.insert(USState::Ohio, ohio_config());
+    configs.insert(USState::RhodeIsland, rhode_island_config());
+    configs.insert(USState::SouthCarolina, south_carolina_config());
+    configs.insert(USState::Nebraska, nebraska_config());
+    configs.insert(USState::NorthDakota, north_dakota_config());
+    configs.insert(USState::WestVirginia, west_virginia_config());
+    configs.insert(USState::WashingtonDC, district_of_columbia_config());
+
+    // Territories
+    configs.insert(USState::PuertoRico, puerto_rico_config());
+    configs.insert(USState::Guam, mirror_federal_config("GU"));
+    configs.insert(USState::VirginIslands, mirror_federal_config("VI"));
+
+    for (state, config) in configs.iter_mut() {
+        config.retirement = retirement_treatment(state);
+        config.supplemental_rate = supplemental_rate(state);
+        config.other_supplemental_rate = other_supplemental_rate(state);
+    }
 
-    // Default config for remaining states (simplified)
-    for state in USState::all() {
-        if !configs.contains_key(state) {
-            configs.insert(
-                *state,
-                StateConfig {
-                    state_code: state.code().to_string(),
-                    tax_type: StateTaxType::Progressive,
-                    brackets: default_brackets(state),
-                    ..Default::default()
-                },
-            );
-        }
+    configs
+}
+
+/// Flat rates by effective tax year for states whose rate steps each year.
+/// Years before the first entry use the state's base config; Iowa becomes
+/// a flat-tax state in 2025.
+fn build_flat_rate_schedule() -> HashMap<USState, Vec<(u32, Decimal)>> {
+    let mut schedule = HashMap::new();
+
+    schedule.insert(
+        USState::NorthCarolina,
+        vec![
+            (2023, dec!(0.0475)),
+            (2024, dec!(0.045)),
+            (2025, dec!(0.0425)),
+        ],
+    );
+    schedule.insert(
+        USState::Kentucky,
+        vec![(2023, dec!(0.045)), (2024, dec!(0.04))],
+    );
+    schedule.insert(
+        USState::Indiana,
+        vec![
+            (2023, dec!(0.0315)),
+            (2024, dec!(0.0305)),
+            (2025, dec!(0.03)),
+        ],
+    );
+    schedule.insert(
+        USState::Utah,
+        vec![
+            (2023, dec!(0.0465)),
+            (2024, dec!(0.0455)),
+            (2025, dec!(0.045)),
+        ],
+    );
+    schedule.insert(
+        USState::Michigan,
+        vec![(2023, dec!(0.0405)), (2024, dec!(0.0425))],
+    );
+    schedule.insert(USState::Iowa, vec![(2025, dec!(0.038))]);
+
+    schedule
+}
+
+/// State configs keyed by the first year each applies: the 2024 base
+/// configs plus the years a state changed its rates or structure.
+fn build_state_configs_by_year() -> HashMap<USState, Vec<(u32, StateConfig)>> {
+    let mut configs: HashMap<_, _> = build_state_configs_2024()
+        .into_iter()
+        .map(|(state, config)| (state, vec![(STATE_BASE_YEAR, config)]))
+        .collect();
+
+    for (state, year, mut config) in build_other_year_state_configs() {
+        config.retirement = retirement_treatment(&state);
+        config.supplemental_rate = supplemental_rate(&state);
+        config.other_supplemental_rate = other_supplemental_rate(&state);
+        let years = configs.entry(state).or_default();
+        years.push((year, config));
+        years.sort_by_key(|(effective, _)| *effective);
     }
 
     configs
 }
 
+/// Configs for years other than 2024 where a state's schedule differs.
+/// Retirement treatment and supplemental rates match the 2024 config.
+fn build_other_year_state_configs() -> Vec<(USState, u32, StateConfig)> {
+    // Louisiana: flat 3% with a much larger standard deduction from 2025
+    let louisiana_2025 = StateConfig {
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(12500),
+            dec!(25000),
+            dec!(12500),
+            dec!(25000),
+        )),
+        ..flat_tax_config("LA", dec!(0.03))
+    };
+
+    vec![
+        (USState::Idaho, 2023, idaho_config(dec!(0.058))),
+        (USState::Idaho, 2025, idaho_config(dec!(0.053))),
+        (USState::Louisiana, 2025, louisiana_2025),
+        (USState::Mississippi, 2023, mississippi_config(dec!(0.05))),
+        (USState::Mississippi, 2025, mississippi_config(dec!(0.044))),
+    ]
+}
+
+/// Retirement income treatment (simplified to a single exclusion tier per state)
+fn retirement_treatment(state: &USState) -> RetirementTreatment {
+    match state {
+        // States that tax Social Security benefits (using the federal taxable amount)
+        USState::Colorado
+        | USState::Connecticut
+        | USState::Minnesota
+        | USState::Montana
+        | USState::NewMexico
+        | USState::RhodeIsland
+        | USState::Utah
+        | USState::Vermont
+        | USState::WestVirginia => RetirementTreatment {
+            taxes_social_security: true,
+            retirement_exclusion: match state {
+                USState::Colorado => Some(dec!(24000)),
+                _ => None,
+            },
+            exclusion_min_age: match state {
+                USState::Colorado => Some(65),
+                _ => None,
+            },
+            ..Default::default()
+        },
+        // States exempting retirement distributions entirely
+        USState::Illinois | USState::Mississippi | USState::Pennsylvania => RetirementTreatment {
+            exempts_retirement_income: true,
+            ..Default::default()
+        },
+        USState::NewYork => partial_exclusion(dec!(20000), 59),
+        USState::Georgia => partial_exclusion(dec!(65000), 65),
+        USState::SouthCarolina => partial_exclusion(dec!(10000), 65),
+        USState::Alabama => partial_exclusion(dec!(6000), 65),
+        _ => RetirementTreatment::default(),
+    }
+}
+
+/// Published flat withholding rates for supplemental wages; states not listed
+/// withhold supplemental wages through their regular wage tables
+fn supplemental_rate(state: &USState) -> Option<Decimal> {
+    let rate = match state {
+        USState::California => dec!(0.1023),
+        USState::Colorado => dec!(0.044),
+        USState::Connecticut => dec!(0.0699),
+        USState::Idaho => dec!(0.058),
+        USState::Illinois => dec!(0.0495),
+        USState::Indiana => dec!(0.0305),
+        USState::Iowa => dec!(0.06),
+        USState::Kansas => dec!(0.05),
+        USState::Kentucky => dec!(0.04),
+        USState::Massachusetts => dec!(0.05),
+        USState::Michigan => dec!(0.0425),
+        USState::Minnesota => dec!(0.0625),
+        USState::Nebraska => dec!(0.05),
+        USState::NewMexico => dec!(0.049),
+        USState::NewYork => dec!(0.117),
+        USState::Oregon => dec!(0.08),
+        USState::Pennsylvania => dec!(0.0307),
+        USState::Utah => dec!(0.0455),
+        USState::Virginia => dec!(0.0575),
+        _ => return None,
+    };
+    Some(rate)
+}
+
+/// Supplemental rates for wages other than bonuses and stock compensation,
+/// for states that withhold them at a different rate
+fn other_supplemental_rate(state: &USState) -> Option<Decimal> {
+    match state {
+        USState::California => Some(dec!(0.066)),
+        _ => None,
+    }
+}
+
+fn partial_exclusion(amount: Decimal, min_age: u32) -> RetirementTreatment {
+    RetirementTreatment {
+        retirement_exclusion: Some(amount),
+        exclusion_min_age: Some(min_age),
+        ..Default::default()
+    }
+}
+
 fn flat_tax_config(code: &str, rate: Decimal) -> StateConfig {
     StateConfig {
         state_code: code.to_string(),
@@ -248,6 +1085,25 @@ fn flat_tax_config(code: &str, rate: Decimal) -> StateConfig {
     }
 }
 
+fn pennsylvania_config() -> StateConfig {
+    StateConfig {
+        local_tax_info: Some(LocalTaxInfo {
+            has_local_tax: true,
+            // Philadelphia wage tax as of July 2024
+            wage_taxes: HashMap::from([(
+                "Philadelphia".to_string(),
+                LocalWageTax {
+                    resident_rate: dec!(0.0375),
+                    nonresident_rate: dec!(0.0344),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        }),
+        ..flat_tax_config("PA", dec!(0.0307))
+    }
+}
+
 fn california_config() -> StateConfig {
     let mut brackets = HashMap::new();
 
@@ -322,9 +1178,10 @@ fn california_config() -> StateConfig {
         state_code: "CA".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        standard_deduction: Some(std_ded),
+        standard_deduction: StateDeductionRule::Fixed(std_ded),
         sdi_rate: Some(dec!(0.011)),
         sdi_wage_base: Some(dec!(153164)),
+        taxes_hsa_contributions: true,
         ..Default::default()
     }
 }
@@ -370,15 +1227,58 @@ fn new_york_config() -> StateConfig {
         state_code: "NY".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        standard_deduction: Some(std_ded),
+        standard_deduction: StateDeductionRule::Fixed(std_ded),
         local_tax_info: Some(LocalTaxInfo {
             has_local_tax: true,
             average_rate: Some(dec!(0.035)), // Estimate for NYC
+            // NYC taxes residents on New York taxable income; the commuter
+            // tax was repealed in 1999
+            income_taxes: HashMap::from([("New York City".to_string(), nyc_income_tax())]),
+            ..Default::default()
         }),
+        // Disability benefits: 0.5% up to $0.60 per week
+        sdi_rate: Some(dec!(0.005)),
+        sdi_wage_base: Some(dec!(6240)),
+        payroll_programs: vec![PayrollProgram::new(
+            PayrollProgramKind::PaidFamilyLeave,
+            "PFL",
+            dec!(0.00373),
+            Some(dec!(89343.80)),
+        )],
         ..Default::default()
     }
 }
 
+/// New York City resident income tax: 3.078% to 3.876% over four brackets
+fn nyc_income_tax() -> LocalIncomeTax {
+    let schedule = |first: Decimal, second: Decimal, third: Decimal| {
+        let base_second = first * dec!(0.03078);
+        let base_third = base_second + (second - first) * dec!(0.03762);
+        let base_top = base_third + (third - second) * dec!(0.03819);
+        vec![
+            TaxBracket::new(dec!(0), Some(first), dec!(0.03078), dec!(0)),
+            TaxBracket::new(first, Some(second), dec!(0.03762), base_second),
+            TaxBracket::new(second, Some(third), dec!(0.03819), base_third),
+            TaxBracket::new(third, None, dec!(0.03876), base_top),
+        ]
+    };
+
+    let single = schedule(dec!(12000), dec!(25000), dec!(50000));
+    let joint = schedule(dec!(21600), dec!(45000), dec!(90000));
+    LocalIncomeTax {
+        brackets: HashMap::from([
+            ("single".to_string(), single.clone()),
+            ("married_filing_separately".to_string(), single),
+            ("married_filing_jointly".to_string(), joint.clone()),
+            ("qualifying_widower".to_string(), joint),
+            (
+                "head_of_household".to_string(),
+                schedule(dec!(14400), dec!(30000), dec!(60000)),
+            ),
+        ]),
+    }
+}
+
 fn arizona_config() -> StateConfig {
     let mut brackets = HashMap::new();
 
@@ -421,33 +1321,31 @@ fn georgia_config() -> StateConfig {
         state_code: "GA".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        standard_deduction: Some(std_ded),
+        standard_deduction: StateDeductionRule::Fixed(std_ded),
         ..Default::default()
     }
 }
 
 fn minnesota_config() -> StateConfig {
-    let mut brackets = HashMap::new();
-
-    brackets.insert(
-        "single".to_string(),
-        vec![
-            TaxBracket::new(dec!(0), Some(dec!(30070)), dec!(0.0535), dec!(0)),
-            TaxBracket::new(dec!(30070), Some(dec!(98760)), dec!(0.068), dec!(1608.75)),
-            TaxBracket::new(dec!(98760), Some(dec!(183340)), dec!(0.0785), dec!(6279.67)),
-            TaxBracket::new(dec!(183340), None, dec!(0.0985), dec!(12919.20)),
-        ],
+    let rates = [dec!(0.0535), dec!(0.068), dec!(0.0785), dec!(0.0985)];
+    let brackets = filing_status_brackets(
+        &rates,
+        [dec!(31690), dec!(104090), dec!(193240)],
+        [dec!(46330), dec!(184040), dec!(321450)],
+        [dec!(23165), dec!(92020), dec!(160725)],
+        [dec!(39010), dec!(156760), dec!(256880)],
     );
 
-    let mut std_ded = HashMap::new();
-    std_ded.insert("single".to_string(), dec!(14575));
-    std_ded.insert("married_filing_jointly".to_string(), dec!(29150));
-
     StateConfig {
         state_code: "MN".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        standard_deduction: Some(std_ded),
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(14575),
+            dec!(29150),
+            dec!(14575),
+            dec!(21900),
+        )),
         ..Default::default()
     }
 }
@@ -477,7 +1375,23 @@ fn new_jersey_config() -> StateConfig {
         state_code: "NJ".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        sdi_rate: Some(dec!(0.0014)),
+        sdi_rate: Some(dec!(0.0009)),
+        sdi_wage_base: Some(dec!(161400)),
+        payroll_programs: vec![
+            PayrollProgram::new(
+                PayrollProgramKind::PaidFamilyLeave,
+                "FLI",
+                dec!(0.0009),
+                Some(dec!(161400)),
+            ),
+            PayrollProgram::new(
+                PayrollProgramKind::Unemployment,
+                "UI/WF",
+                dec!(0.00425),
+                Some(dec!(42300)),
+            ),
+        ],
+        taxes_hsa_contributions: true,
         ..Default::default()
     }
 }
@@ -503,7 +1417,7 @@ fn oregon_config() -> StateConfig {
         state_code: "OR".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        standard_deduction: Some(std_ded),
+        standard_deduction: StateDeductionRule::Fixed(std_ded),
         ..Default::default()
     }
 }
@@ -529,39 +1443,1136 @@ fn virginia_config() -> StateConfig {
         state_code: "VA".to_string(),
         tax_type: StateTaxType::Progressive,
         brackets,
-        standard_deduction: Some(std_ded),
+        standard_deduction: StateDeductionRule::Fixed(std_ded),
         ..Default::default()
     }
 }
 
-fn default_brackets(_state: &USState) -> HashMap<String, Vec<TaxBracket>> {
-    // Simple default: 5% flat equivalent as progressive
+/// Per-filing-status amounts; qualifying widow(er)s use the joint amount
+fn by_filing_status(
+    single: Decimal,
+    married_joint: Decimal,
+    married_separate: Decimal,
+    head_of_household: Decimal,
+) -> HashMap<String, Decimal> {
+    let mut amounts = HashMap::new();
+    amounts.insert("single".to_string(), single);
+    amounts.insert("married_filing_jointly".to_string(), married_joint);
+    amounts.insert("married_filing_separately".to_string(), married_separate);
+    amounts.insert("head_of_household".to_string(), head_of_household);
+    amounts.insert("qualifying_widower".to_string(), married_joint);
+    amounts
+}
+
+/// Brackets with the same rates but separate thresholds per filing status,
+/// given as the upper bounds of every bracket but the last
+fn filing_status_brackets<const N: usize>(
+    rates: &[Decimal],
+    single: [Decimal; N],
+    joint: [Decimal; N],
+    separate: [Decimal; N],
+    head_of_household: [Decimal; N],
+) -> HashMap<String, Vec<TaxBracket>> {
+    let schedule = |ceilings: [Decimal; N]| {
+        let pairs: Vec<_> = ceilings
+            .into_iter()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .zip(rates.iter().copied())
+            .collect();
+        scaled_brackets(&pairs, Decimal::ONE)
+    };
+
     let mut brackets = HashMap::new();
-    brackets.insert(
-        "single".to_string(),
-        vec![TaxBracket::new(dec!(0), None, dec!(0.05), dec!(0))],
-    );
+    brackets.insert("single".to_string(), schedule(single));
+    brackets.insert("married_filing_jointly".to_string(), schedule(joint));
+    brackets.insert("married_filing_separately".to_string(), schedule(separate));
+    brackets.insert("head_of_household".to_string(), schedule(head_of_household));
+    brackets.insert("qualifying_widower".to_string(), schedule(joint));
     brackets
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Brackets shared by every filing status
+fn all_filing_statuses(brackets: Vec<TaxBracket>) -> HashMap<String, Vec<TaxBracket>> {
+    [
+        FilingStatus::Single,
+        FilingStatus::MarriedFilingJointly,
+        FilingStatus::MarriedFilingSeparately,
+        FilingStatus::HeadOfHousehold,
+        FilingStatus::QualifyingWidower,
+    ]
+    .iter()
+    .map(|status| (status.as_str().to_string(), brackets.clone()))
+    .collect()
+}
 
-    #[test]
-    fn test_embedded_data_creation() {
-        let data = EmbeddedTaxData::new();
+fn alabama_config() -> StateConfig {
+    let single = vec![
+        TaxBracket::new(dec!(0), Some(dec!(500)), dec!(0.02), dec!(0)),
+        TaxBracket::new(dec!(500), Some(dec!(3000)), dec!(0.04), dec!(10)),
+        TaxBracket::new(dec!(3000), None, dec!(0.05), dec!(110)),
+    ];
+    let joint = vec![
+        TaxBracket::new(dec!(0), Some(dec!(1000)), dec!(0.02), dec!(0)),
+        TaxBracket::new(dec!(1000), Some(dec!(6000)), dec!(0.04), dec!(20)),
+        TaxBracket::new(dec!(6000), None, dec!(0.05), dec!(220)),
+    ];
+
+    let mut brackets = all_filing_statuses(single);
+    brackets.insert("married_filing_jointly".to_string(), joint.clone());
+    brackets.insert("qualifying_widower".to_string(), joint);
+
+    // Standard deduction phases down above $23,000 AGI; the maximum is used here
+    StateConfig {
+        state_code: "AL".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(3000),
+            dec!(8500),
+            dec!(4250),
+            dec!(5200),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(1500),
+            dec!(3000),
+            dec!(1500),
+            dec!(3000),
+        )),
+        deducts_federal_income_tax: true,
+        ..Default::default()
+    }
+}
 
-        // Check federal brackets exist
-        let single_brackets = data.federal_brackets(FilingStatus::Single, 2024);
-        assert_eq!(single_brackets.len(), 7);
+fn arkansas_config() -> StateConfig {
+    // 2024 rates after the top rate was cut to 3.9%
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(5500)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(5500), Some(dec!(10900)), dec!(0.02), dec!(0)),
+        TaxBracket::new(dec!(10900), Some(dec!(15600)), dec!(0.03), dec!(108)),
+        TaxBracket::new(dec!(15600), Some(dec!(25700)), dec!(0.034), dec!(249)),
+        TaxBracket::new(dec!(25700), None, dec!(0.039), dec!(592.40)),
+    ]);
 
-        // Check first bracket
-        assert_eq!(single_brackets[0].floor, dec!(0));
-        assert_eq!(single_brackets[0].rate, dec!(0.10));
+    StateConfig {
+        state_code: "AR".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(2410),
+            dec!(4820),
+            dec!(2410),
+            dec!(2410),
+        )),
+        personal_credit: Some(by_filing_status(dec!(29), dec!(58), dec!(29), dec!(29))),
+        ..Default::default()
     }
+}
 
-    #[test]
+fn connecticut_config() -> StateConfig {
+    let rates = [
+        dec!(0.02),
+        dec!(0.045),
+        dec!(0.055),
+        dec!(0.06),
+        dec!(0.065),
+        dec!(0.069),
+        dec!(0.0699),
+    ];
+    let brackets = filing_status_brackets(
+        &rates,
+        [
+            dec!(10000),
+            dec!(50000),
+            dec!(100000),
+            dec!(200000),
+            dec!(250000),
+            dec!(500000),
+        ],
+        [
+            dec!(20000),
+            dec!(100000),
+            dec!(200000),
+            dec!(400000),
+            dec!(500000),
+            dec!(1000000),
+        ],
+        [
+            dec!(10000),
+            dec!(50000),
+            dec!(100000),
+            dec!(200000),
+            dec!(250000),
+            dec!(500000),
+        ],
+        [
+            dec!(16000),
+            dec!(80000),
+            dec!(160000),
+            dec!(320000),
+            dec!(400000),
+            dec!(800000),
+        ],
+    );
+
+    let recapture = |threshold, step, amount_per_step, max| TaxRecapture {
+        threshold,
+        step,
+        amount_per_step,
+        max,
+    };
+    let mut lower_rate_recapture = HashMap::new();
+    lower_rate_recapture.insert(
+        "single".to_string(),
+        recapture(dec!(56500), dec!(5000), dec!(25), dec!(250)),
+    );
+    lower_rate_recapture.insert(
+        "married_filing_jointly".to_string(),
+        recapture(dec!(100500), dec!(10000), dec!(50), dec!(500)),
+    );
+    lower_rate_recapture.insert(
+        "married_filing_separately".to_string(),
+        recapture(dec!(50250), dec!(5000), dec!(25), dec!(250)),
+    );
+    lower_rate_recapture.insert(
+        "head_of_household".to_string(),
+        recapture(dec!(78500), dec!(8000), dec!(40), dec!(400)),
+    );
+
+    let mut credit_tiers = HashMap::new();
+    credit_tiers.insert(
+        "single".to_string(),
+        connecticut_credit_tiers(
+            &[
+                (dec!(18800), dec!(0.75)),
+                (dec!(19300), dec!(0.70)),
+                (dec!(19800), dec!(0.65)),
+                (dec!(20300), dec!(0.60)),
+                (dec!(20800), dec!(0.55)),
+                (dec!(21300), dec!(0.50)),
+                (dec!(21800), dec!(0.45)),
+                (dec!(22300), dec!(0.40)),
+                (dec!(25000), dec!(0.35)),
+                (dec!(25500), dec!(0.30)),
+                (dec!(26000), dec!(0.25)),
+                (dec!(26500), dec!(0.20)),
+                (dec!(31300), dec!(0.15)),
+            ],
+            dec!(31300),
+            dec!(60000),
+        ),
+    );
+    credit_tiers.insert(
+        "married_filing_jointly".to_string(),
+        connecticut_credit_tiers(
+            &[
+                (dec!(30000), dec!(0.75)),
+                (dec!(30500), dec!(0.70)),
+                (dec!(31000), dec!(0.65)),
+                (dec!(31500), dec!(0.60)),
+                (dec!(32000), dec!(0.55)),
+                (dec!(32500), dec!(0.50)),
+                (dec!(33000), dec!(0.45)),
+                (dec!(33500), dec!(0.40)),
+                (dec!(40000), dec!(0.35)),
+                (dec!(40500), dec!(0.30)),
+                (dec!(41000), dec!(0.25)),
+                (dec!(41500), dec!(0.20)),
+                (dec!(50000), dec!(0.15)),
+            ],
+            dec!(50000),
+            dec!(96000),
+        ),
+    );
+
+    // Property tax credit on a residence or vehicle (age 65+ or with
+    // dependents; eligibility is not checked here)
+    let property_tax_credit = PropertyTaxCredit {
+        max_credit: dec!(300),
+        thresholds: by_filing_status(dec!(49500), dec!(70500), dec!(35250), dec!(54500)),
+        step: dec!(10000),
+        reduction_per_step: dec!(0.15),
+    };
+
+    StateConfig {
+        state_code: "CT".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        personal_exemption: Some(by_filing_status(
+            dec!(15000),
+            dec!(24000),
+            dec!(12000),
+            dec!(19000),
+        )),
+        personal_exemption_phase_out: Some(PhaseOut {
+            thresholds: by_filing_status(dec!(30000), dec!(48000), dec!(24000), dec!(38000)),
+            rate: Decimal::ONE,
+        }),
+        percentage_credit_tiers: Some(credit_tiers),
+        recapture: lower_rate_recapture,
+        property_tax_credit: Some(property_tax_credit),
+        ..Default::default()
+    }
+}
+
+/// Connecticut personal tax credit tiers: the listed low-income tiers, then
+/// 1-point steps from 14% down to 11% every $500, a flat 10% band, and
+/// 1-point steps from 9% to 1% every $500 above it
+fn connecticut_credit_tiers(
+    low_income: &[(Decimal, Decimal)],
+    steps_start: Decimal,
+    flat_band_end: Decimal,
+) -> Vec<(Decimal, Decimal)> {
+    let mut tiers = low_income.to_vec();
+    for i in 1..=4 {
+        tiers.push((
+            steps_start + dec!(500) * Decimal::from(i),
+            dec!(0.15) - dec!(0.01) * Decimal::from(i),
+        ));
+    }
+    tiers.push((flat_band_end, dec!(0.10)));
+    for i in 1..=9 {
+        tiers.push((
+            flat_band_end + dec!(500) * Decimal::from(i),
+            dec!(0.10) - dec!(0.01) * Decimal::from(i),
+        ));
+    }
+    tiers
+}
+
+fn delaware_config() -> StateConfig {
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(2000)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(2000), Some(dec!(5000)), dec!(0.022), dec!(0)),
+        TaxBracket::new(dec!(5000), Some(dec!(10000)), dec!(0.039), dec!(66)),
+        TaxBracket::new(dec!(10000), Some(dec!(20000)), dec!(0.048), dec!(261)),
+        TaxBracket::new(dec!(20000), Some(dec!(25000)), dec!(0.052), dec!(741)),
+        TaxBracket::new(dec!(25000), Some(dec!(60000)), dec!(0.0555), dec!(1001)),
+        TaxBracket::new(dec!(60000), None, dec!(0.066), dec!(2943.50)),
+    ]);
+
+    StateConfig {
+        state_code: "DE".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(3250),
+            dec!(6500),
+            dec!(3250),
+            dec!(3250),
+        )),
+        personal_credit: Some(by_filing_status(dec!(110), dec!(220), dec!(110), dec!(110))),
+        ..Default::default()
+    }
+}
+
+/// Build a bracket schedule from (ceiling, rate) pairs, scaling the
+/// thresholds and accumulating base tax at each floor
+fn scaled_brackets(schedule: &[(Option<Decimal>, Decimal)], scale: Decimal) -> Vec<TaxBracket> {
+    let mut brackets = Vec::with_capacity(schedule.len());
+    let mut floor = Decimal::ZERO;
+    let mut base_tax = Decimal::ZERO;
+
+    for (ceiling, rate) in schedule {
+        let ceiling = ceiling.map(|c| c * scale);
+        brackets.push(TaxBracket::new(floor, ceiling, *rate, base_tax));
+        if let Some(ceiling) = ceiling {
+            base_tax += (ceiling - floor) * rate;
+            floor = ceiling;
+        }
+    }
+
+    brackets
+}
+
+fn hawaii_config() -> StateConfig {
+    let schedule = [
+        (Some(dec!(9600)), dec!(0.014)),
+        (Some(dec!(14400)), dec!(0.032)),
+        (Some(dec!(19200)), dec!(0.055)),
+        (Some(dec!(24000)), dec!(0.064)),
+        (Some(dec!(36000)), dec!(0.068)),
+        (Some(dec!(48000)), dec!(0.072)),
+        (Some(dec!(125000)), dec!(0.076)),
+        (Some(dec!(175000)), dec!(0.079)),
+        (Some(dec!(225000)), dec!(0.0825)),
+        (Some(dec!(275000)), dec!(0.09)),
+        (Some(dec!(325000)), dec!(0.10)),
+        (None, dec!(0.11)),
+    ];
+
+    // Joint thresholds are double the single ones, head of household 1.5x
+    let mut brackets = all_filing_statuses(scaled_brackets(&schedule, dec!(1)));
+    brackets.insert(
+        "married_filing_jointly".to_string(),
+        scaled_brackets(&schedule, dec!(2)),
+    );
+    brackets.insert(
+        "qualifying_widower".to_string(),
+        scaled_brackets(&schedule, dec!(2)),
+    );
+    brackets.insert(
+        "head_of_household".to_string(),
+        scaled_brackets(&schedule, dec!(1.5)),
+    );
+
+    StateConfig {
+        state_code: "HI".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(4400),
+            dec!(8800),
+            dec!(4400),
+            dec!(6424),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(1144),
+            dec!(2288),
+            dec!(1144),
+            dec!(1144),
+        )),
+        // TDI: 0.5% of wages up to $1,384.04/week ($6.92 weekly maximum)
+        sdi_rate: Some(dec!(0.005)),
+        sdi_wage_base: Some(dec!(71970.08)),
+        ..Default::default()
+    }
+}
+
+fn idaho_config(rate: Decimal) -> StateConfig {
+    // Single flat rate above a zero-rate band; Idaho follows the federal
+    // standard deduction
+    let single = vec![
+        TaxBracket::new(dec!(0), Some(dec!(4673)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(4673), None, rate, dec!(0)),
+    ];
+    let joint = vec![
+        TaxBracket::new(dec!(0), Some(dec!(9346)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(9346), None, rate, dec!(0)),
+    ];
+
+    let mut brackets = all_filing_statuses(single);
+    brackets.insert("married_filing_jointly".to_string(), joint.clone());
+    brackets.insert("head_of_household".to_string(), joint.clone());
+    brackets.insert("qualifying_widower".to_string(), joint);
+
+    StateConfig {
+        state_code: "ID".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn iowa_config() -> StateConfig {
+    // Iowa starts from federal taxable income, so it shares the federal
+    // standard deduction. The 2025 flat rate comes from the rate schedule.
+    let single = vec![
+        TaxBracket::new(dec!(0), Some(dec!(6210)), dec!(0.044), dec!(0)),
+        TaxBracket::new(dec!(6210), Some(dec!(31050)), dec!(0.0482), dec!(273.24)),
+        TaxBracket::new(dec!(31050), None, dec!(0.057), dec!(1470.528)),
+    ];
+    let joint = vec![
+        TaxBracket::new(dec!(0), Some(dec!(12420)), dec!(0.044), dec!(0)),
+        TaxBracket::new(dec!(12420), Some(dec!(62100)), dec!(0.0482), dec!(546.48)),
+        TaxBracket::new(dec!(62100), None, dec!(0.057), dec!(2941.056)),
+    ];
+
+    let mut brackets = all_filing_statuses(single);
+    brackets.insert("married_filing_jointly".to_string(), joint.clone());
+    brackets.insert("qualifying_widower".to_string(), joint);
+
+    StateConfig {
+        state_code: "IA".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn kansas_config() -> StateConfig {
+    let single = vec![
+        TaxBracket::new(dec!(0), Some(dec!(23000)), dec!(0.052), dec!(0)),
+        TaxBracket::new(dec!(23000), None, dec!(0.0558), dec!(1196)),
+    ];
+    let joint = vec![
+        TaxBracket::new(dec!(0), Some(dec!(46000)), dec!(0.052), dec!(0)),
+        TaxBracket::new(dec!(46000), None, dec!(0.0558), dec!(2392)),
+    ];
+
+    let mut brackets = all_filing_statuses(single);
+    brackets.insert("married_filing_jointly".to_string(), joint.clone());
+    brackets.insert("qualifying_widower".to_string(), joint);
+
+    StateConfig {
+        state_code: "KS".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(3605),
+            dec!(8240),
+            dec!(4120),
+            dec!(6180),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(9160),
+            dec!(18320),
+            dec!(9160),
+            dec!(9160),
+        )),
+        ..Default::default()
+    }
+}
+
+fn missouri_config() -> StateConfig {
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(1273)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(1273), Some(dec!(2546)), dec!(0.02), dec!(0)),
+        TaxBracket::new(dec!(2546), Some(dec!(3819)), dec!(0.025), dec!(25.46)),
+        TaxBracket::new(dec!(3819), Some(dec!(5092)), dec!(0.03), dec!(57.285)),
+        TaxBracket::new(dec!(5092), Some(dec!(6365)), dec!(0.035), dec!(95.475)),
+        TaxBracket::new(dec!(6365), Some(dec!(7638)), dec!(0.04), dec!(140.03)),
+        TaxBracket::new(dec!(7638), Some(dec!(8911)), dec!(0.045), dec!(190.95)),
+        TaxBracket::new(dec!(8911), None, dec!(0.048), dec!(248.235)),
+    ]);
+
+    // Missouri follows the federal standard deduction
+    StateConfig {
+        state_code: "MO".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn maryland_config() -> StateConfig {
+    let single = vec![
+        TaxBracket::new(dec!(0), Some(dec!(1000)), dec!(0.02), dec!(0)),
+        TaxBracket::new(dec!(1000), Some(dec!(2000)), dec!(0.03), dec!(20)),
+        TaxBracket::new(dec!(2000), Some(dec!(3000)), dec!(0.04), dec!(50)),
+        TaxBracket::new(dec!(3000), Some(dec!(100000)), dec!(0.0475), dec!(90)),
+        TaxBracket::new(dec!(100000), Some(dec!(125000)), dec!(0.05), dec!(4697.50)),
+        TaxBracket::new(
+            dec!(125000),
+            Some(dec!(150000)),
+            dec!(0.0525),
+            dec!(5947.50),
+        ),
+        TaxBracket::new(dec!(150000), Some(dec!(250000)), dec!(0.055), dec!(7260)),
+        TaxBracket::new(dec!(250000), None, dec!(0.0575), dec!(12760)),
+    ];
+    let joint = vec![
+        TaxBracket::new(dec!(0), Some(dec!(1000)), dec!(0.02), dec!(0)),
+        TaxBracket::new(dec!(1000), Some(dec!(2000)), dec!(0.03), dec!(20)),
+        TaxBracket::new(dec!(2000), Some(dec!(3000)), dec!(0.04), dec!(50)),
+        TaxBracket::new(dec!(3000), Some(dec!(150000)), dec!(0.0475), dec!(90)),
+        TaxBracket::new(dec!(150000), Some(dec!(175000)), dec!(0.05), dec!(7072.50)),
+        TaxBracket::new(
+            dec!(175000),
+            Some(dec!(225000)),
+            dec!(0.0525),
+            dec!(8322.50),
+        ),
+        TaxBracket::new(
+            dec!(225000),
+            Some(dec!(300000)),
+            dec!(0.055),
+            dec!(10947.50),
+        ),
+        TaxBracket::new(dec!(300000), None, dec!(0.0575), dec!(15072.50)),
+    ];
+
+    let mut brackets = all_filing_statuses(single);
+    brackets.insert("married_filing_jointly".to_string(), joint.clone());
+    brackets.insert("head_of_household".to_string(), joint.clone());
+    brackets.insert("qualifying_widower".to_string(), joint);
+
+    StateConfig {
+        state_code: "MD".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        // 15% of income, within a per-status floor and cap
+        standard_deduction: StateDeductionRule::PercentageOfIncome {
+            rate: dec!(0.15),
+            floor: by_filing_status(dec!(1800), dec!(3650), dec!(1800), dec!(3650)),
+            cap: by_filing_status(dec!(2700), dec!(5450), dec!(2700), dec!(5450)),
+        },
+        personal_exemption: Some(by_filing_status(
+            dec!(3200),
+            dec!(6400),
+            dec!(3200),
+            dec!(3200),
+        )),
+        local_tax_info: Some(LocalTaxInfo {
+            has_local_tax: true,
+            average_rate: Some(dec!(0.03)),
+            localities: maryland_county_rates(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// 2024 Maryland county (and Baltimore City) piggyback income tax rates
+fn maryland_county_rates() -> HashMap<String, Decimal> {
+    [
+        ("Allegany", dec!(0.0303)),
+        ("Anne Arundel", dec!(0.0281)),
+        ("Baltimore City", dec!(0.032)),
+        ("Baltimore County", dec!(0.032)),
+        ("Calvert", dec!(0.03)),
+        ("Caroline", dec!(0.032)),
+        ("Carroll", dec!(0.0303)),
+        ("Cecil", dec!(0.0274)),
+        ("Charles", dec!(0.0303)),
+        ("Dorchester", dec!(0.032)),
+        ("Frederick", dec!(0.0296)),
+        ("Garrett", dec!(0.0265)),
+        ("Harford", dec!(0.0306)),
+        ("Howard", dec!(0.032)),
+        ("Kent", dec!(0.032)),
+        ("Montgomery", dec!(0.032)),
+        ("Prince George's", dec!(0.032)),
+        ("Queen Anne's", dec!(0.032)),
+        ("St. Mary's", dec!(0.03)),
+        ("Somerset", dec!(0.032)),
+        ("Talbot", dec!(0.024)),
+        ("Washington", dec!(0.0295)),
+        ("Wicomico", dec!(0.032)),
+        ("Worcester", dec!(0.0225)),
+    ]
+    .into_iter()
+    .map(|(county, rate)| (county.to_string(), rate))
+    .collect()
+}
+
+fn montana_config() -> StateConfig {
+    // Two brackets since 2024, starting from federal taxable income
+    let brackets = filing_status_brackets(
+        &[dec!(0.047), dec!(0.059)],
+        [dec!(20500)],
+        [dec!(41000)],
+        [dec!(20500)],
+        [dec!(30750)],
+    );
+
+    StateConfig {
+        state_code: "MT".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn louisiana_config() -> StateConfig {
+    let brackets = filing_status_brackets(
+        &[dec!(0.0185), dec!(0.035), dec!(0.0425)],
+        [dec!(12500), dec!(50000)],
+        [dec!(25000), dec!(100000)],
+        [dec!(12500), dec!(50000)],
+        [dec!(12500), dec!(50000)],
+    );
+
+    // Combined personal exemption and standard deduction
+    StateConfig {
+        state_code: "LA".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        personal_exemption: Some(by_filing_status(
+            dec!(4500),
+            dec!(9000),
+            dec!(4500),
+            dec!(9000),
+        )),
+        ..Default::default()
+    }
+}
+
+fn maine_config() -> StateConfig {
+    let brackets = filing_status_brackets(
+        &[dec!(0.058), dec!(0.0675), dec!(0.0715)],
+        [dec!(26050), dec!(61600)],
+        [dec!(52100), dec!(123250)],
+        [dec!(26050), dec!(61600)],
+        [dec!(39050), dec!(92450)],
+    );
+
+    // Maine follows the federal standard deduction
+    StateConfig {
+        state_code: "ME".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        personal_exemption: Some(by_filing_status(
+            dec!(5000),
+            dec!(10000),
+            dec!(5000),
+            dec!(5000),
+        )),
+        ..Default::default()
+    }
+}
+
+fn mississippi_config(rate: Decimal) -> StateConfig {
+    // Flat rate on income above the $10,000 zero-rate band
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(10000)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(10000), None, rate, dec!(0)),
+    ]);
+
+    StateConfig {
+        state_code: "MS".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(2300),
+            dec!(4600),
+            dec!(2300),
+            dec!(3400),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(6000),
+            dec!(12000),
+            dec!(6000),
+            dec!(9500),
+        )),
+        ..Default::default()
+    }
+}
+
+fn new_mexico_config() -> StateConfig {
+    let rates = [
+        dec!(0.017),
+        dec!(0.032),
+        dec!(0.047),
+        dec!(0.049),
+        dec!(0.059),
+    ];
+    let brackets = filing_status_brackets(
+        &rates,
+        [dec!(5500), dec!(11000), dec!(16000), dec!(210000)],
+        [dec!(8000), dec!(16000), dec!(24000), dec!(315000)],
+        [dec!(4000), dec!(8000), dec!(12000), dec!(157500)],
+        [dec!(8000), dec!(16000), dec!(24000), dec!(315000)],
+    );
+
+    // New Mexico follows the federal standard deduction
+    StateConfig {
+        state_code: "NM".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn ohio_config() -> StateConfig {
+    // Zero-rate band, then a published base of $360.69 at the 2.75% bracket
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(26050)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(26050), Some(dec!(100000)), dec!(0.0275), dec!(360.69))
+            .with_step(dec!(360.69)),
+        TaxBracket::new(dec!(100000), None, dec!(0.035), dec!(2394.32)),
+    ]);
+
+    // Exemption for MAGI above $80,000 ($2,150 at $40k-$80k, $2,400 below)
+    StateConfig {
+        state_code: "OH".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        personal_exemption: Some(by_filing_status(
+            dec!(1900),
+            dec!(3800),
+            dec!(1900),
+            dec!(1900),
+        )),
+        local_tax_info: Some(LocalTaxInfo {
+            has_local_tax: true,
+            average_rate: Some(dec!(0.02)), // Typical municipal rate
+            wage_taxes: ohio_city_wage_taxes(),
+            ..Default::default()
+        }),
+        school_districts: ohio_school_districts(),
+        ..Default::default()
+    }
+}
+
+/// Ohio municipal income taxes; residents are credited for tax withheld by
+/// the city they work in, up to their own city's rate
+fn ohio_city_wage_taxes() -> HashMap<String, LocalWageTax> {
+    [
+        ("Columbus", dec!(0.025)),
+        ("Cleveland", dec!(0.025)),
+        ("Cincinnati", dec!(0.018)),
+        ("Toledo", dec!(0.025)),
+        ("Akron", dec!(0.025)),
+        ("Dayton", dec!(0.025)),
+    ]
+    .into_iter()
+    .map(|(city, rate)| {
+        let tax = LocalWageTax {
+            resident_rate: rate,
+            nonresident_rate: rate,
+            resident_credit_rate: Decimal::ONE,
+        };
+        (city.to_string(), tax)
+    })
+    .collect()
+}
+
+/// Ohio school district income taxes by district number
+fn ohio_school_districts() -> HashMap<String, SchoolDistrictTax> {
+    use SchoolDistrictBase::{EarnedIncome, Traditional};
+
+    [
+        ("0904", "Talawanda CSD", dec!(0.005), Traditional),
+        ("1803", "Wayne Trace LSD", dec!(0.0125), Traditional),
+        ("2102", "Big Walnut LSD", dec!(0.0075), EarnedIncome),
+        ("2103", "Buckeye Valley LSD", dec!(0.01), EarnedIncome),
+        ("4504", "Fairfield Union LSD", dec!(0.01), Traditional),
+    ]
+    .into_iter()
+    .map(|(number, name, rate, base)| {
+        (
+            number.to_string(),
+            SchoolDistrictTax {
+                name: name.to_string(),
+                rate,
+                base,
+            },
+        )
+    })
+    .collect()
+}
+
+fn oklahoma_config() -> StateConfig {
+    let rates = [
+        dec!(0.0025),
+        dec!(0.0075),
+        dec!(0.0175),
+        dec!(0.0275),
+        dec!(0.0375),
+        dec!(0.0475),
+    ];
+    let brackets = filing_status_brackets(
+        &rates,
+        [dec!(1000), dec!(2500), dec!(3750), dec!(4900), dec!(7200)],
+        [dec!(2000), dec!(5000), dec!(7500), dec!(9800), dec!(14400)],
+        [dec!(1000), dec!(2500), dec!(3750), dec!(4900), dec!(7200)],
+        [dec!(2000), dec!(5000), dec!(7500), dec!(9800), dec!(14400)],
+    );
+
+    StateConfig {
+        state_code: "OK".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(6350),
+            dec!(12700),
+            dec!(6350),
+            dec!(9350),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(1000),
+            dec!(2000),
+            dec!(1000),
+            dec!(1000),
+        )),
+        ..Default::default()
+    }
+}
+
+fn rhode_island_config() -> StateConfig {
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(77450)), dec!(0.0375), dec!(0)),
+        TaxBracket::new(
+            dec!(77450),
+            Some(dec!(176050)),
+            dec!(0.0475),
+            dec!(2904.375),
+        ),
+        TaxBracket::new(dec!(176050), None, dec!(0.0599), dec!(7587.875)),
+    ]);
+
+    StateConfig {
+        state_code: "RI".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(10550),
+            dec!(21150),
+            dec!(10575),
+            dec!(15850),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(4950),
+            dec!(9900),
+            dec!(4950),
+            dec!(4950),
+        )),
+        // TDI: 1.2% of wages up to $87,000
+        sdi_rate: Some(dec!(0.012)),
+        sdi_wage_base: Some(dec!(87000)),
+        ..Default::default()
+    }
+}
+
+fn south_carolina_config() -> StateConfig {
+    // South Carolina starts from federal taxable income
+    let brackets = all_filing_statuses(vec![
+        TaxBracket::new(dec!(0), Some(dec!(3460)), dec!(0), dec!(0)),
+        TaxBracket::new(dec!(3460), Some(dec!(17330)), dec!(0.03), dec!(0)),
+        TaxBracket::new(dec!(17330), None, dec!(0.062), dec!(416.10)),
+    ]);
+
+    StateConfig {
+        state_code: "SC".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn utah_config() -> StateConfig {
+    // Taxpayer credit: 6% of the federal standard deduction, reduced by
+    // 1.3% of income above the base amount
+    StateConfig {
+        personal_credit: Some(by_filing_status(
+            dec!(876),
+            dec!(1752),
+            dec!(876),
+            dec!(1314),
+        )),
+        personal_credit_phase_out: Some(PhaseOut {
+            thresholds: by_filing_status(dec!(17652), dec!(35304), dec!(17652), dec!(26478)),
+            rate: dec!(0.013),
+        }),
+        ..flat_tax_config("UT", dec!(0.0455))
+    }
+}
+
+fn vermont_config() -> StateConfig {
+    let brackets = filing_status_brackets(
+        &[dec!(0.0335), dec!(0.066), dec!(0.076), dec!(0.0875)],
+        [dec!(47900), dec!(116000), dec!(242000)],
+        [dec!(79950), dec!(193300), dec!(294600)],
+        [dec!(39975), dec!(96650), dec!(147300)],
+        [dec!(64200), dec!(165700), dec!(268300)],
+    );
+
+    StateConfig {
+        state_code: "VT".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(7400),
+            dec!(14850),
+            dec!(7400),
+            dec!(11100),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(5100),
+            dec!(10200),
+            dec!(5100),
+            dec!(5100),
+        )),
+        ..Default::default()
+    }
+}
+
+fn wisconsin_config() -> StateConfig {
+    let rates = [dec!(0.035), dec!(0.044), dec!(0.053), dec!(0.0765)];
+    let brackets = filing_status_brackets(
+        &rates,
+        [dec!(14320), dec!(28640), dec!(315310)],
+        [dec!(19090), dec!(38190), dec!(420420)],
+        [dec!(9550), dec!(19090), dec!(210210)],
+        [dec!(14320), dec!(28640), dec!(315310)],
+    );
+
+    // The sliding-scale standard deduction phases out with income; the
+    // maximum is used here
+    StateConfig {
+        state_code: "WI".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(13230),
+            dec!(24490),
+            dec!(11630),
+            dec!(17090),
+        )),
+        personal_exemption: Some(by_filing_status(
+            dec!(700),
+            dec!(1400),
+            dec!(700),
+            dec!(700),
+        )),
+        married_couple_credit: Some(MarriedCoupleCredit {
+            rate: dec!(0.03),
+            max_earnings: dec!(16000),
+        }),
+        ..Default::default()
+    }
+}
+
+fn nebraska_config() -> StateConfig {
+    let brackets = filing_status_brackets(
+        &[dec!(0.0246), dec!(0.0351), dec!(0.0501), dec!(0.0584)],
+        [dec!(3700), dec!(22170), dec!(35730)],
+        [dec!(7390), dec!(44350), dec!(71460)],
+        [dec!(3700), dec!(22170), dec!(35730)],
+        [dec!(6920), dec!(35480), dec!(47590)],
+    );
+
+    StateConfig {
+        state_code: "NE".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Fixed(by_filing_status(
+            dec!(8300),
+            dec!(16600),
+            dec!(8300),
+            dec!(12200),
+        )),
+        // Personal exemption credit of $157 per person
+        personal_credit: Some(by_filing_status(dec!(157), dec!(314), dec!(157), dec!(157))),
+        ..Default::default()
+    }
+}
+
+fn north_dakota_config() -> StateConfig {
+    // North Dakota starts from federal taxable income
+    let brackets = filing_status_brackets(
+        &[dec!(0), dec!(0.0195), dec!(0.025)],
+        [dec!(47150), dec!(238200)],
+        [dec!(78775), dec!(289975)],
+        [dec!(39375), dec!(144975)],
+        [dec!(63175), dec!(264100)],
+    );
+
+    StateConfig {
+        state_code: "ND".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+fn west_virginia_config() -> StateConfig {
+    let rates = [
+        dec!(0.0236),
+        dec!(0.0315),
+        dec!(0.0354),
+        dec!(0.0472),
+        dec!(0.0512),
+    ];
+    let thresholds = [dec!(10000), dec!(25000), dec!(40000), dec!(60000)];
+    let brackets = filing_status_brackets(
+        &rates,
+        thresholds,
+        thresholds,
+        [dec!(5000), dec!(12500), dec!(20000), dec!(30000)],
+        thresholds,
+    );
+
+    StateConfig {
+        state_code: "WV".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        personal_exemption: Some(by_filing_status(
+            dec!(2000),
+            dec!(4000),
+            dec!(2000),
+            dec!(2000),
+        )),
+        ..Default::default()
+    }
+}
+
+/// 2024 Puerto Rico schedule, shared by all filing statuses
+fn puerto_rico_config() -> StateConfig {
+    let brackets = scaled_brackets(
+        &[
+            (Some(dec!(9000)), dec!(0)),
+            (Some(dec!(25000)), dec!(0.07)),
+            (Some(dec!(41500)), dec!(0.14)),
+            (Some(dec!(61500)), dec!(0.25)),
+            (None, dec!(0.33)),
+        ],
+        Decimal::ONE,
+    );
+
+    StateConfig {
+        state_code: "PR".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets: all_filing_statuses(brackets),
+        personal_exemption: Some(by_filing_status(
+            dec!(3500),
+            dec!(7000),
+            dec!(3500),
+            dec!(3500),
+        )),
+        ..Default::default()
+    }
+}
+
+fn mirror_federal_config(code: &str) -> StateConfig {
+    StateConfig {
+        state_code: code.to_string(),
+        tax_type: StateTaxType::MirrorFederal,
+        ..Default::default()
+    }
+}
+
+fn district_of_columbia_config() -> StateConfig {
+    let brackets = all_filing_statuses(scaled_brackets(
+        &[
+            (Some(dec!(10000)), dec!(0.04)),
+            (Some(dec!(40000)), dec!(0.06)),
+            (Some(dec!(60000)), dec!(0.065)),
+            (Some(dec!(250000)), dec!(0.085)),
+            (Some(dec!(500000)), dec!(0.0925)),
+            (Some(dec!(1000000)), dec!(0.0975)),
+            (None, dec!(0.1075)),
+        ],
+        Decimal::ONE,
+    ));
+
+    // DC follows the federal standard deduction
+    StateConfig {
+        state_code: "DC".to_string(),
+        tax_type: StateTaxType::Progressive,
+        brackets,
+        standard_deduction: StateDeductionRule::Federal,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_data_creation() {
+        let data = EmbeddedTaxData::new();
+
+        // Check federal brackets exist
+        let single_brackets = data.federal_brackets(FilingStatus::Single, 2024);
+        assert_eq!(single_brackets.len(), 7);
+
+        // Check first bracket
+        assert_eq!(single_brackets[0].floor, dec!(0));
+        assert_eq!(single_brackets[0].rate, dec!(0.10));
+    }
+
+    #[test]
     fn test_standard_deductions() {
         let data = EmbeddedTaxData::new();
 
@@ -575,6 +2586,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_federal_figures_by_year() {
+        let data = EmbeddedTaxData::new();
+
+        let single = |year| data.standard_deduction(FilingStatus::Single, year);
+        assert_eq!(single(2023), dec!(13850));
+        assert_eq!(single(2025), dec!(15750));
+        // Outside the embedded years, the nearest year's figures apply
+        assert_eq!(single(2022), dec!(13850));
+        assert_eq!(single(2027), dec!(15750));
+        assert!(!data.supports_year(2022));
+        assert!((2023..=LATEST_YEAR).all(|year| data.supports_year(year)));
+
+        let top_bracket =
+            |year| data.federal_brackets(FilingStatus::MarriedFilingJointly, year)[6].floor;
+        assert_eq!(top_bracket(2023), dec!(693750));
+        assert_eq!(top_bracket(2025), dec!(751600));
+
+        assert_eq!(data.fica_config(2023).wage_base, dec!(160200));
+        assert_eq!(data.fica_config(2025).wage_base, dec!(176100));
+        assert_eq!(data.retirement_limits(2023).elective_deferral, dec!(22500));
+        assert_eq!(data.retirement_limits(2025).elective_deferral, dec!(23500));
+    }
+
     #[test]
     fn test_fica_config() {
         let data = EmbeddedTaxData::new();
@@ -585,6 +2620,27 @@ mod tests {
         assert_eq!(fica.medicare_rate, dec!(0.0145));
     }
 
+    #[test]
+    fn test_retirement_limits() {
+        let data = EmbeddedTaxData::new();
+        let limits = data.retirement_limits(2024);
+
+        assert_eq!(limits.elective_deferral, dec!(23000));
+        assert_eq!(limits.total_annual_additions, dec!(69000));
+        assert_eq!(limits.hsa_family, dec!(8300));
+    }
+
+    #[test]
+    fn test_uniform_lifetime_table() {
+        let data = EmbeddedTaxData::new();
+
+        assert_eq!(data.rmd_distribution_period(72, 2024), Some(dec!(27.4)));
+        assert_eq!(data.rmd_distribution_period(75, 2024), Some(dec!(24.6)));
+        assert_eq!(data.rmd_distribution_period(120, 2024), Some(dec!(2.0)));
+        assert_eq!(data.rmd_distribution_period(125, 2024), Some(dec!(2.0)));
+        assert_eq!(data.rmd_distribution_period(60, 2024), None);
+    }
+
     #[test]
     fn test_california_config() {
         let data = EmbeddedTaxData::new();
@@ -606,6 +2662,34 @@ mod tests {
         assert_eq!(fl.tax_type, StateTaxType::NoTax);
     }
 
+    #[test]
+    fn test_retirement_treatment() {
+        let data = EmbeddedTaxData::new();
+
+        assert!(
+            data.state_config(USState::Colorado, 2024)
+                .retirement
+                .taxes_social_security
+        );
+        assert!(
+            !data
+                .state_config(USState::California, 2024)
+                .retirement
+                .taxes_social_security
+        );
+        assert!(
+            data.state_config(USState::Pennsylvania, 2024)
+                .retirement
+                .exempts_retirement_income
+        );
+        assert_eq!(
+            data.state_config(USState::NewYork, 2024)
+                .retirement
+                .retirement_exclusion,
+            Some(dec!(20000))
+        );
+    }
+
     #[test]
     fn test_flat_tax_states() {
         let data = EmbeddedTaxData::new();
@@ -618,4 +2702,108 @@ mod tests {
         assert_eq!(il.tax_type, StateTaxType::FlatRate);
         assert_eq!(il.flat_rate, Some(dec!(0.0495)));
     }
+
+    #[test]
+    fn test_flat_rates_by_year() {
+        let data = EmbeddedTaxData::new();
+
+        let nc_rate = |year| data.state_config(USState::NorthCarolina, year).flat_rate;
+        assert_eq!(nc_rate(2023), Some(dec!(0.0475)));
+        assert_eq!(nc_rate(2024), Some(dec!(0.045)));
+        assert_eq!(nc_rate(2025), Some(dec!(0.0425)));
+        // Later years keep the most recent rate
+        assert_eq!(nc_rate(2026), Some(dec!(0.0425)));
+
+        let michigan = |year| data.state_config(USState::Michigan, year).flat_rate;
+        assert_eq!(michigan(2023), Some(dec!(0.0405)));
+        assert_eq!(michigan(2025), Some(dec!(0.0425)));
+
+        // Zero-band states keep their structure while the rate moves
+        let mississippi =
+            |year| data.state_config(USState::Mississippi, year).brackets["single"][1].rate;
+        assert_eq!(mississippi(2022), dec!(0.05));
+        assert_eq!(mississippi(2024), dec!(0.047));
+        assert_eq!(mississippi(2025), dec!(0.044));
+        assert_eq!(
+            data.state_config(USState::Mississippi, 2025)
+                .supplemental_rate,
+            data.state_config(USState::Mississippi, 2024)
+                .supplemental_rate
+        );
+
+        assert_eq!(
+            data.state_config(USState::Kentucky, 2023).flat_rate,
+            Some(dec!(0.045))
+        );
+
+        // Iowa moves from brackets to a flat rate in 2025
+        let ia_2024 = data.state_config(USState::Iowa, 2024);
+        assert_eq!(ia_2024.tax_type, StateTaxType::Progressive);
+        let ia_2025 = data.state_config(USState::Iowa, 2025);
+        assert_eq!(ia_2025.tax_type, StateTaxType::FlatRate);
+        assert_eq!(ia_2025.flat_rate, Some(dec!(0.038)));
+    }
+
+    #[test]
+    fn test_state_data_year() {
+        let data = EmbeddedTaxData::new();
+
+        // Only 2024 is embedded for California and New York
+        assert_eq!(data.state_data_year(USState::California, 2024), 2024);
+        assert_eq!(data.state_data_year(USState::California, 2025), 2024);
+        assert_eq!(data.state_data_year(USState::NewYork, 2023), 2024);
+
+        // States with a schedule or rate for the year use their own
+        assert_eq!(data.state_data_year(USState::Idaho, 2023), 2023);
+        assert_eq!(data.state_data_year(USState::Louisiana, 2025), 2025);
+        assert_eq!(data.state_data_year(USState::Louisiana, 2023), 2024);
+        assert_eq!(data.state_data_year(USState::NorthCarolina, 2025), 2025);
+        assert_eq!(data.state_data_year(USState::Kentucky, 2025), 2024);
+
+        // No income tax, nothing to be out of date
+        assert_eq!(data.state_data_year(USState::Texas, 2025), 2025);
+    }
+
+    #[test]
+    fn test_poverty_guideline_year() {
+        let data = EmbeddedTaxData::new();
+
+        assert_eq!(
+            data.poverty_guideline(USState::Hawaii, 2025),
+            PovertyGuideline {
+                first_person: dec!(17990),
+                each_additional_person: dec!(6325),
+            }
+        );
+        assert_eq!(data.poverty_guideline_year(2025), 2025);
+        assert_eq!(data.poverty_guideline_year(2026), 2025);
+        assert_eq!(data.poverty_guideline_year(2022), 2023);
+    }
+
+    #[test]
+    fn test_every_state_explicitly_configured() {
+        let configs = build_state_configs_2024();
+
+        for state in USState::all().iter().chain(USState::territories()) {
+            let config = configs
+                .get(state)
+                .unwrap_or_else(|| panic!("{} has no config", state.name()));
+            assert_eq!(config.state_code, state.code());
+
+            match config.tax_type {
+                StateTaxType::NoTax => assert!(state.has_no_income_tax()),
+                StateTaxType::FlatRate => assert!(config.flat_rate.is_some()),
+                StateTaxType::MirrorFederal => assert!(state.is_territory()),
+                StateTaxType::Progressive => {
+                    let single = &config.brackets["single"];
+                    // The old placeholder was a single 5% bracket
+                    assert!(
+                        single.len() > 1,
+                        "{} uses a placeholder schedule",
+                        state.name()
+                    );
+                },
+            }
+        }
+    }
 }
@@ -0,0 +1,312 @@
+//! Inflation-indexed tax data for future-year projections
+
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use super::{
+    FicaConfig, IraPhaseOut, IrmaaSchedule, IrmaaTier, PayrollProgram, PovertyGuideline,
+    RetirementLimits, StateConfig, StateDeductionRule, TaxDataProvider,
+};
+use crate::models::state::USState;
+use crate::models::tax::{FilingStatus, TaxBracket};
+
+/// Data provider that projects a base year's data forward by an inflation rate
+///
+/// Years at or before the base year are delegated unchanged. Later years have
+/// bracket thresholds, standard deductions, wage bases, and contribution
+/// limits grown by `(1 + inflation_rate)^(year - base_year)`, rounded to whole
+/// dollars. Rates are never indexed.
+pub struct InflationIndexedData<'a> {
+    base: &'a dyn TaxDataProvider,
+    base_year: u32,
+    inflation_rate: Decimal,
+}
+
+impl<'a> InflationIndexedData<'a> {
+    pub fn new(base: &'a dyn TaxDataProvider, base_year: u32, inflation_rate: Decimal) -> Self {
+        Self {
+            base,
+            base_year,
+            inflation_rate,
+        }
+    }
+
+    /// Cumulative inflation factor for a year
+    pub fn factor(&self, year: u32) -> Decimal {
+        let growth = Decimal::ONE + self.inflation_rate;
+        (self.base_year..year).fold(Decimal::ONE, |acc, _| acc * growth)
+    }
+
+    fn index(&self, amount: Decimal, year: u32) -> Decimal {
+        (amount * self.factor(year)).round_dp(0)
+    }
+
+    /// Index where an IRA phase-out starts; its width is set by statute
+    fn index_phase_out(&self, phase_out: IraPhaseOut, year: u32) -> IraPhaseOut {
+        IraPhaseOut {
+            single_start: self.index(phase_out.single_start, year),
+            joint_start: self.index(phase_out.joint_start, year),
+            ..phase_out
+        }
+    }
+
+    /// Index bracket floors/ceilings and steps, and recompute base taxes
+    fn index_brackets(&self, brackets: &[TaxBracket], year: u32) -> Vec<TaxBracket> {
+        let mut base_tax = Decimal::ZERO;
+        brackets
+            .iter()
+            .map(|b| {
+                let floor = self.index(b.floor, year);
+                let ceiling = b.ceiling.map(|c| self.index(c, year));
+                let step = (b.step * self.factor(year)).round_dp(2);
+                base_tax += step;
+                let bracket =
+                    TaxBracket::new(floor, ceiling, b.rate.as_fraction(), base_tax).with_step(step);
+                if let Some(c) = ceiling {
+                    base_tax += (c - floor) * b.rate;
+                }
+                bracket
+            })
+            .collect()
+    }
+
+    fn index_amounts(
+        &self,
+        amounts: &HashMap<String, Decimal>,
+        year: u32,
+    ) -> HashMap<String, Decimal> {
+        amounts
+            .iter()
+            .map(|(status, amount)| (status.clone(), self.index(*amount, year)))
+            .collect()
+    }
+}
+
+impl TaxDataProvider for InflationIndexedData<'_> {
+    fn federal_brackets(&self, filing_status: FilingStatus, year: u32) -> Vec<TaxBracket> {
+        let brackets = self
+            .base
+            .federal_brackets(filing_status, year.min(self.base_year));
+        if year <= self.base_year {
+            return brackets;
+        }
+        self.index_brackets(&brackets, year)
+    }
+
+    fn standard_deduction(&self, filing_status: FilingStatus, year: u32) -> Decimal {
+        let deduction = self
+            .base
+            .standard_deduction(filing_status, year.min(self.base_year));
+        self.index(deduction, year)
+    }
+
+    fn fica_config(&self, year: u32) -> FicaConfig {
+        let config = self.base.fica_config(year.min(self.base_year));
+        FicaConfig {
+            wage_base: self.index(config.wage_base, year),
+            ..config
+        }
+    }
+
+    fn state_config(&self, state: USState, year: u32) -> StateConfig {
+        let config = self.base.state_config(state, year.min(self.base_year));
+        if year <= self.base_year {
+            return config;
+        }
+
+        StateConfig {
+            brackets: config
+                .brackets
+                .iter()
+                .map(|(status, brackets)| (status.clone(), self.index_brackets(brackets, year)))
+                .collect(),
+            standard_deduction: match &config.standard_deduction {
+                StateDeductionRule::Fixed(amounts) => {
+                    StateDeductionRule::Fixed(self.index_amounts(amounts, year))
+                },
+                StateDeductionRule::PercentageOfIncome { rate, floor, cap } => {
+                    StateDeductionRule::PercentageOfIncome {
+                        rate: *rate,
+                        floor: self.index_amounts(floor, year),
+                        cap: self.index_amounts(cap, year),
+                    }
+                },
+                // Federal-linked rules follow the indexed federal deduction
+                rule => rule.clone(),
+            },
+            personal_exemption: config
+                .personal_exemption
+                .as_ref()
+                .map(|exemptions| self.index_amounts(exemptions, year)),
+            sdi_wage_base: config.sdi_wage_base.map(|base| self.index(base, year)),
+            payroll_programs: config
+                .payroll_programs
+                .iter()
+                .map(|program| PayrollProgram {
+                    wage_base: program.wage_base.map(|base| self.index(base, year)),
+                    ..program.clone()
+                })
+                .collect(),
+            ..config
+        }
+    }
+
+    fn retirement_limits(&self, year: u32) -> RetirementLimits {
+        let limits = self.base.retirement_limits(year.min(self.base_year));
+        RetirementLimits {
+            elective_deferral: self.index(limits.elective_deferral, year),
+            catch_up_deferral: self.index(limits.catch_up_deferral, year),
+            total_annual_additions: self.index(limits.total_annual_additions, year),
+            compensation_limit: self.index(limits.compensation_limit, year),
+            ira_contribution: self.index(limits.ira_contribution, year),
+            ira_catch_up: self.index(limits.ira_catch_up, year),
+            hsa_self_only: self.index(limits.hsa_self_only, year),
+            hsa_family: self.index(limits.hsa_family, year),
+            hsa_catch_up: self.index(limits.hsa_catch_up, year),
+            ira_deduction_phase_out: self.index_phase_out(limits.ira_deduction_phase_out, year),
+            roth_ira_phase_out: self.index_phase_out(limits.roth_ira_phase_out, year),
+            ..limits
+        }
+    }
+
+    fn rmd_distribution_period(&self, age: u32, year: u32) -> Option<Decimal> {
+        self.base
+            .rmd_distribution_period(age, year.min(self.base_year))
+    }
+
+    fn poverty_guideline(&self, state: USState, year: u32) -> PovertyGuideline {
+        let guideline = self.base.poverty_guideline(state, year.min(self.base_year));
+        PovertyGuideline {
+            first_person: self.index(guideline.first_person, year),
+            each_additional_person: self.index(guideline.each_additional_person, year),
+        }
+    }
+
+    fn business_mileage_rate(&self, year: u32) -> Decimal {
+        self.base.business_mileage_rate(year.min(self.base_year))
+    }
+
+    /// Thresholds are indexed; premiums and surcharges stay at the base
+    /// year's, since they follow Medicare costs rather than prices
+    fn irmaa_schedule(&self, year: u32) -> IrmaaSchedule {
+        let schedule = self.base.irmaa_schedule(year.min(self.base_year));
+        let index_tiers = |tiers: &[IrmaaTier]| {
+            tiers
+                .iter()
+                .map(|tier| IrmaaTier {
+                    magi_threshold: self.index(tier.magi_threshold, year),
+                    ..tier.clone()
+                })
+                .collect()
+        };
+        IrmaaSchedule {
+            individual: index_tiers(&schedule.individual),
+            joint: index_tiers(&schedule.joint),
+            married_separately: index_tiers(&schedule.married_separately),
+            ..schedule
+        }
+    }
+
+    fn supports_year(&self, year: u32) -> bool {
+        year > self.base_year || self.base.supports_year(year)
+    }
+
+    /// Projected years are indexed on purpose; earlier years are the base's
+    fn state_data_year(&self, state: USState, year: u32) -> u32 {
+        if year > self.base_year {
+            year
+        } else {
+            self.base.state_data_year(state, year)
+        }
+    }
+
+    fn poverty_guideline_year(&self, year: u32) -> u32 {
+        if year > self.base_year {
+            year
+        } else {
+            self.base.poverty_guideline_year(year)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_base_year_unchanged() {
+        let base = EmbeddedTaxData::new();
+        let indexed = InflationIndexedData::new(&base, 2024, dec!(0.03));
+
+        assert_eq!(
+            indexed.federal_brackets(FilingStatus::Single, 2024),
+            base.federal_brackets(FilingStatus::Single, 2024)
+        );
+        assert_eq!(
+            indexed.standard_deduction(FilingStatus::Single, 2024),
+            dec!(14600)
+        );
+    }
+
+    #[test]
+    fn test_thresholds_indexed() {
+        let base = EmbeddedTaxData::new();
+        let indexed = InflationIndexedData::new(&base, 2024, dec!(0.03));
+
+        // Two years at 3%: factor 1.0609
+        assert_eq!(indexed.factor(2026), dec!(1.0609));
+        assert_eq!(
+            indexed.standard_deduction(FilingStatus::Single, 2026),
+            dec!(15489)
+        );
+
+        let brackets = indexed.federal_brackets(FilingStatus::Single, 2026);
+        assert_eq!(brackets[1].floor, dec!(12306));
+        assert_eq!(brackets[1].rate, dec!(0.12));
+        // Base tax stays consistent with the indexed floors
+        assert_eq!(brackets[1].base_tax, dec!(12306) * dec!(0.10));
+    }
+
+    #[test]
+    fn test_rates_not_indexed() {
+        let base = EmbeddedTaxData::new();
+        let indexed = InflationIndexedData::new(&base, 2024, dec!(0.03));
+
+        let fica = indexed.fica_config(2025);
+        assert_eq!(fica.social_security_rate, dec!(0.062));
+        assert_eq!(fica.wage_base, dec!(173658));
+
+        let co = indexed.state_config(USState::Colorado, 2030);
+        assert_eq!(co.flat_rate, Some(dec!(0.044)));
+    }
+
+    #[test]
+    fn test_state_deductions_indexed() {
+        let base = EmbeddedTaxData::new();
+        let indexed = InflationIndexedData::new(&base, 2024, dec!(0.03));
+
+        // Fixed deductions are indexed directly
+        match indexed
+            .state_config(USState::Virginia, 2026)
+            .standard_deduction
+        {
+            StateDeductionRule::Fixed(amounts) => {
+                assert_eq!(amounts["single"], dec!(9018));
+            },
+            rule => panic!("unexpected rule {rule:?}"),
+        }
+
+        // Ohio's step at the end of its zero bracket is indexed and carried
+        // into every bracket above it
+        let ohio = &indexed.state_config(USState::Ohio, 2026).brackets["single"];
+        assert_eq!(ohio[1].step, dec!(382.66));
+        assert_eq!(ohio[1].base_tax, dec!(382.66));
+        assert_eq!(
+            ohio[2].base_tax,
+            dec!(382.66) + (dec!(106090) - dec!(27636)) * dec!(0.0275)
+        );
+        assert_eq!(ohio[2].step, Decimal::ZERO);
+    }
+}
@@ -1,12 +1,21 @@
 //! Tax data handling
 
+pub mod audit;
+pub mod changelog;
 pub mod embedded;
+pub mod indexed;
+pub mod overridden;
+
+pub use audit::{audit, AuditReport};
+pub use changelog::{diff_years, TaxDataChangelog};
 
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use std::collections::HashMap;
 
+use crate::models::deduction::HsaCoverage;
 use crate::models::state::USState;
-use crate::models::tax::{FilingStatus, TaxBracket};
+use crate::models::tax::{FilingStatus, PayrollProgramKind, TaxBracket};
 
 /// Tax data provider trait
 pub trait TaxDataProvider: Send + Sync {
@@ -21,6 +30,37 @@ pub trait TaxDataProvider: Send + Sync {
 
     /// Get state tax configuration
     fn state_config(&self, state: USState, year: u32) -> StateConfig;
+
+    /// Get retirement and tax-advantaged account contribution limits
+    fn retirement_limits(&self, year: u32) -> RetirementLimits;
+
+    /// Get the IRS Uniform Lifetime Table distribution period for an age
+    fn rmd_distribution_period(&self, age: u32, year: u32) -> Option<Decimal>;
+
+    /// Get the HHS poverty guideline for households in a state
+    fn poverty_guideline(&self, state: USState, year: u32) -> PovertyGuideline;
+
+    /// Get the IRS standard mileage rate per business mile
+    fn business_mileage_rate(&self, year: u32) -> Decimal;
+
+    /// Get Medicare premiums and IRMAA tiers for a premium year
+    fn irmaa_schedule(&self, year: u32) -> IrmaaSchedule;
+
+    /// Whether the provider has data for a tax year rather than falling back
+    /// to another year's figures
+    fn supports_year(&self, _year: u32) -> bool {
+        true
+    }
+
+    /// Year whose figures `state_config` returns for `year`
+    fn state_data_year(&self, _state: USState, year: u32) -> u32 {
+        year
+    }
+
+    /// Year whose guideline `poverty_guideline` returns for `year`
+    fn poverty_guideline_year(&self, year: u32) -> u32 {
+        year
+    }
 }
 
 /// FICA configuration
@@ -32,6 +72,155 @@ pub struct FicaConfig {
     pub additional_medicare_rate: Decimal,
 }
 
+/// Contribution limits for retirement and tax-advantaged accounts
+#[derive(Debug, Clone)]
+pub struct RetirementLimits {
+    /// 401(k)/403(b) employee elective deferral limit
+    pub elective_deferral: Decimal,
+    /// Additional elective deferral allowed at age 50+
+    pub catch_up_deferral: Decimal,
+    /// Annual additions limit (employee + employer, excluding catch-up)
+    pub total_annual_additions: Decimal,
+    /// Maximum compensation considered for employer contributions
+    pub compensation_limit: Decimal,
+    /// Employer contribution rate for SEP/profit-sharing (of compensation)
+    pub employer_contribution_rate: Decimal,
+    pub ira_contribution: Decimal,
+    pub ira_catch_up: Decimal,
+    pub hsa_self_only: Decimal,
+    pub hsa_family: Decimal,
+    pub hsa_catch_up: Decimal,
+    /// Traditional IRA deduction phase-out for workplace plan participants
+    pub ira_deduction_phase_out: IraPhaseOut,
+    /// Roth IRA contribution phase-out
+    pub roth_ira_phase_out: IraPhaseOut,
+}
+
+/// Modified AGI over which an IRA limit shrinks to zero
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IraPhaseOut {
+    pub single_start: Decimal,
+    pub single_range: Decimal,
+    pub joint_start: Decimal,
+    pub joint_range: Decimal,
+}
+
+impl IraPhaseOut {
+    /// Married filing separately phases out over the first $10,000
+    const SEPARATE_RANGE: Decimal = dec!(10000);
+    /// Smallest allowed amount short of a full phase-out
+    const MINIMUM: Decimal = dec!(200);
+
+    /// Part of `limit` allowed at `magi`: reduced pro rata through the
+    /// range, rounded up to the next $10, and at least $200 until the range
+    /// ends
+    pub fn allowed(&self, limit: Decimal, magi: Decimal, filing_status: FilingStatus) -> Decimal {
+        let (start, range) = match filing_status {
+            FilingStatus::Single | FilingStatus::HeadOfHousehold => {
+                (self.single_start, self.single_range)
+            },
+            FilingStatus::MarriedFilingJointly | FilingStatus::QualifyingWidower => {
+                (self.joint_start, self.joint_range)
+            },
+            FilingStatus::MarriedFilingSeparately => (Decimal::ZERO, Self::SEPARATE_RANGE),
+        };
+        if magi <= start {
+            return limit;
+        }
+        if magi >= start + range {
+            return Decimal::ZERO;
+        }
+        let reduced = limit * (start + range - magi) / range;
+        ((reduced / Decimal::TEN).ceil() * Decimal::TEN)
+            .max(Self::MINIMUM)
+            .min(limit)
+    }
+}
+
+/// Age from which HSA catch-up contributions are allowed
+const HSA_CATCH_UP_AGE: u32 = 55;
+
+impl RetirementLimits {
+    /// Combined employee and employer HSA limit for a coverage tier and age
+    pub fn hsa_limit(&self, coverage: HsaCoverage, age: u32) -> Decimal {
+        let base = match coverage {
+            HsaCoverage::SelfOnly => self.hsa_self_only,
+            HsaCoverage::Family => self.hsa_family,
+        };
+        if age >= HSA_CATCH_UP_AGE {
+            base + self.hsa_catch_up
+        } else {
+            base
+        }
+    }
+}
+
+/// HHS poverty guideline, the baseline for income-driven loan repayment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PovertyGuideline {
+    /// Guideline for a household of one
+    pub first_person: Decimal,
+    /// Added for each additional household member
+    pub each_additional_person: Decimal,
+}
+
+impl PovertyGuideline {
+    /// Guideline for a household; sizes below one count as one
+    pub fn for_family_size(&self, family_size: u32) -> Decimal {
+        self.first_person + self.each_additional_person * Decimal::from(family_size.max(1) - 1)
+    }
+}
+
+/// Medicare Part B premium and income-related surcharges (IRMAA) for a
+/// premium year, set by MAGI from two years earlier
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrmaaSchedule {
+    /// Standard monthly Part B premium
+    pub part_b_premium: Decimal,
+    /// Single, head of household, qualifying surviving spouse, and married
+    /// filing separately while living apart all year
+    pub individual: Vec<IrmaaTier>,
+    pub joint: Vec<IrmaaTier>,
+    /// Married filing separately while living with the spouse
+    pub married_separately: Vec<IrmaaTier>,
+}
+
+impl IrmaaSchedule {
+    /// Tiers for a filing status, treating married filing separately as
+    /// living with the spouse
+    pub fn tiers(&self, filing_status: FilingStatus) -> &[IrmaaTier] {
+        match filing_status {
+            FilingStatus::MarriedFilingJointly => &self.joint,
+            FilingStatus::MarriedFilingSeparately => &self.married_separately,
+            _ => &self.individual,
+        }
+    }
+}
+
+/// One IRMAA tier: monthly surcharges on top of the standard premiums
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrmaaTier {
+    /// The tier applies to MAGI above this, or at and above it for the
+    /// last tier
+    pub magi_threshold: Decimal,
+    pub part_b_surcharge: Decimal,
+    pub part_d_surcharge: Decimal,
+}
+
+impl IrmaaTier {
+    pub fn new(
+        magi_threshold: Decimal,
+        part_b_surcharge: Decimal,
+        part_d_surcharge: Decimal,
+    ) -> Self {
+        Self {
+            magi_threshold,
+            part_b_surcharge,
+            part_d_surcharge,
+        }
+    }
+}
+
 /// State tax configuration
 #[derive(Debug, Clone, Default)]
 pub struct StateConfig {
@@ -39,10 +228,105 @@ pub struct StateConfig {
     pub tax_type: StateTaxType,
     pub flat_rate: Option<Decimal>,
     pub brackets: HashMap<String, Vec<TaxBracket>>,
-    pub standard_deduction: Option<HashMap<String, Decimal>>,
+    pub standard_deduction: StateDeductionRule,
+    /// Personal exemption deducted alongside the standard deduction
+    pub personal_exemption: Option<HashMap<String, Decimal>>,
+    /// Nonrefundable personal credit subtracted from the computed tax
+    pub personal_credit: Option<HashMap<String, Decimal>>,
+    /// Income-based reduction of the personal credit
+    pub personal_credit_phase_out: Option<PhaseOut>,
+    /// Income-based reduction of the personal exemption
+    pub personal_exemption_phase_out: Option<PhaseOut>,
+    /// Credit as a percentage of tax, by filing status: (income upper bound, rate) tiers
+    pub percentage_credit_tiers: Option<HashMap<String, Vec<(Decimal, Decimal)>>>,
+    /// Added tax recapturing low-bracket benefits, by filing status
+    pub recapture: HashMap<String, TaxRecapture>,
+    pub property_tax_credit: Option<PropertyTaxCredit>,
+    /// Whether federal income tax paid is deductible from state income
+    pub deducts_federal_income_tax: bool,
+    /// Credit for two-earner married couples filing jointly
+    pub married_couple_credit: Option<MarriedCoupleCredit>,
+    /// Flat withholding rate for supplemental wages (bonuses, RSU vests)
+    pub supplemental_rate: Option<Decimal>,
+    /// Supplemental rate for severance, PTO payouts, and other wages that
+    /// aren't bonuses or stock, where the state sets a different one
+    pub other_supplemental_rate: Option<Decimal>,
     pub sdi_rate: Option<Decimal>,
     pub sdi_wage_base: Option<Decimal>,
+    /// Employee payroll programs withheld alongside disability insurance
+    pub payroll_programs: Vec<PayrollProgram>,
     pub local_tax_info: Option<LocalTaxInfo>,
+    /// School district income taxes keyed by district number
+    pub school_districts: HashMap<String, SchoolDistrictTax>,
+    pub retirement: RetirementTreatment,
+    /// Whether HSA contributions are included in state taxable income
+    pub taxes_hsa_contributions: bool,
+}
+
+impl StateConfig {
+    /// Progressive brackets for the filing status, or the single schedule
+    /// when the state has none for it; `None` when neither exists
+    pub fn brackets_for(&self, filing_status: FilingStatus) -> Option<&[TaxBracket]> {
+        [filing_status, FilingStatus::Single]
+            .iter()
+            .filter_map(|status| self.brackets.get(status.as_str()))
+            .find(|brackets| !brackets.is_empty())
+            .map(Vec::as_slice)
+    }
+
+    /// Whether `brackets_for` falls back to the single schedule
+    pub fn uses_single_brackets(&self, filing_status: FilingStatus) -> bool {
+        self.tax_type == StateTaxType::Progressive
+            && self
+                .brackets
+                .get(filing_status.as_str())
+                .is_none_or(|brackets| brackets.is_empty())
+    }
+}
+
+/// Employee-paid state payroll program other than disability insurance
+#[derive(Debug, Clone)]
+pub struct PayrollProgram {
+    pub kind: PayrollProgramKind,
+    pub name: String,
+    pub rate: Decimal,
+    /// Annual wage cap, or `None` when all wages are subject
+    pub wage_base: Option<Decimal>,
+}
+
+impl PayrollProgram {
+    pub fn new(
+        kind: PayrollProgramKind,
+        name: &str,
+        rate: Decimal,
+        wage_base: Option<Decimal>,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            rate,
+            wage_base,
+        }
+    }
+}
+
+/// How a state's standard deduction is determined
+#[derive(Debug, Clone, Default)]
+pub enum StateDeductionRule {
+    #[default]
+    None,
+    /// Fixed amount per filing status
+    Fixed(HashMap<String, Decimal>),
+    /// Same as the federal standard deduction (states starting from federal taxable income)
+    Federal,
+    /// A share of the federal standard deduction
+    FederalPercentage(Decimal),
+    /// Percentage of state income, bounded per filing status
+    PercentageOfIncome {
+        rate: Decimal,
+        floor: HashMap<String, Decimal>,
+        cap: HashMap<String, Decimal>,
+    },
 }
 
 /// State tax type
@@ -52,6 +336,84 @@ pub enum StateTaxType {
     NoTax,
     FlatRate,
     Progressive,
+    /// Territory tax equal to the federal income tax (Guam, Virgin Islands)
+    MirrorFederal,
+}
+
+/// State treatment of retirement income
+#[derive(Debug, Clone, Default)]
+pub struct RetirementTreatment {
+    /// Whether federally taxable Social Security benefits are taxed by the state
+    pub taxes_social_security: bool,
+    /// Whether all retirement distributions and pensions are exempt
+    pub exempts_retirement_income: bool,
+    /// Cap on excluded retirement distributions and pension income
+    pub retirement_exclusion: Option<Decimal>,
+    /// Minimum age for the exclusion to apply
+    pub exclusion_min_age: Option<u32>,
+}
+
+/// Amount reduced by a rate on state income above a per-filing-status threshold
+#[derive(Debug, Clone)]
+pub struct PhaseOut {
+    pub thresholds: HashMap<String, Decimal>,
+    pub rate: Decimal,
+}
+
+impl PhaseOut {
+    /// Reduction for a given income and filing status
+    pub fn reduction(&self, income: Decimal, filing_status: FilingStatus) -> Decimal {
+        let threshold = self
+            .thresholds
+            .get(filing_status.as_str())
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+        (income - threshold).max(Decimal::ZERO) * self.rate
+    }
+}
+
+/// Flat amount of added tax per income step above a threshold, up to a cap
+/// (Connecticut's phase-out of its lowest bracket rate)
+#[derive(Debug, Clone)]
+pub struct TaxRecapture {
+    pub threshold: Decimal,
+    pub step: Decimal,
+    pub amount_per_step: Decimal,
+    pub max: Decimal,
+}
+
+/// Credit for property taxes paid, lost in percentage steps as income rises
+#[derive(Debug, Clone)]
+pub struct PropertyTaxCredit {
+    pub max_credit: Decimal,
+    pub thresholds: HashMap<String, Decimal>,
+    pub step: Decimal,
+    /// Fraction of the credit lost per income step above the threshold
+    pub reduction_per_step: Decimal,
+}
+
+/// Two-earner credit: a percentage of the lower-earning spouse's wages, up to a cap
+#[derive(Debug, Clone)]
+pub struct MarriedCoupleCredit {
+    pub rate: Decimal,
+    pub max_earnings: Decimal,
+}
+
+/// Income base for a school district tax
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchoolDistrictBase {
+    /// State taxable income, including retirement and investment income
+    Traditional,
+    /// Wages and self-employment income only
+    EarnedIncome,
+}
+
+/// School district income tax
+#[derive(Debug, Clone)]
+pub struct SchoolDistrictTax {
+    pub name: String,
+    pub rate: Decimal,
+    pub base: SchoolDistrictBase,
 }
 
 /// Local tax information
@@ -59,4 +421,37 @@ pub enum StateTaxType {
 pub struct LocalTaxInfo {
     pub has_local_tax: bool,
     pub average_rate: Option<Decimal>,
+    /// Rates for specific counties or cities, keyed by name
+    pub localities: HashMap<String, Decimal>,
+    /// City wage taxes that distinguish residents from commuters, keyed by name
+    pub wage_taxes: HashMap<String, LocalWageTax>,
+    /// Resident income taxes with their own brackets on state taxable
+    /// income, keyed by name
+    pub income_taxes: HashMap<String, LocalIncomeTax>,
+}
+
+/// City income tax on residents' state taxable income, by filing status
+#[derive(Debug, Clone, Default)]
+pub struct LocalIncomeTax {
+    pub brackets: HashMap<String, Vec<TaxBracket>>,
+}
+
+impl LocalIncomeTax {
+    /// Brackets for the filing status, or the single schedule
+    pub fn brackets_for(&self, filing_status: FilingStatus) -> &[TaxBracket] {
+        self.brackets
+            .get(filing_status.as_str())
+            .or_else(|| self.brackets.get(FilingStatus::Single.as_str()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Municipal wage tax with separate resident and nonresident rates
+#[derive(Debug, Clone, Default)]
+pub struct LocalWageTax {
+    pub resident_rate: Decimal,
+    pub nonresident_rate: Decimal,
+    /// Share of tax paid to another work city credited against the resident tax
+    pub resident_credit_rate: Decimal,
 }
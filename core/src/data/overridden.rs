@@ -0,0 +1,159 @@
+//! Caller-supplied overrides layered over another data provider
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    FicaConfig, IrmaaSchedule, PovertyGuideline, RetirementLimits, StateConfig, StateTaxType,
+    TaxDataProvider,
+};
+use crate::models::state::USState;
+use crate::models::tax::{FilingStatus, TaxBracket};
+
+/// Published figures to replace for a single calculation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Overrides {
+    /// Federal standard deduction, for every filing status
+    #[serde(default)]
+    pub federal_standard_deduction: Option<Decimal>,
+    /// Flat income tax rate for the resident state, replacing its brackets
+    #[serde(default)]
+    pub state_flat_rate: Option<Decimal>,
+    /// State disability insurance rate
+    #[serde(default)]
+    pub sdi_rate: Option<Decimal>,
+    /// Social Security wage base
+    #[serde(default)]
+    pub fica_wage_base: Option<Decimal>,
+}
+
+impl Overrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Data provider that applies `Overrides` on top of a base provider
+pub struct OverriddenData<'a> {
+    base: &'a dyn TaxDataProvider,
+    overrides: Overrides,
+    state: USState,
+}
+
+impl<'a> OverriddenData<'a> {
+    /// `state` is the resident state that `state_flat_rate` applies to
+    pub fn new(base: &'a dyn TaxDataProvider, overrides: Overrides, state: USState) -> Self {
+        Self {
+            base,
+            overrides,
+            state,
+        }
+    }
+}
+
+impl TaxDataProvider for OverriddenData<'_> {
+    fn federal_brackets(&self, filing_status: FilingStatus, year: u32) -> Vec<TaxBracket> {
+        self.base.federal_brackets(filing_status, year)
+    }
+
+    fn standard_deduction(&self, filing_status: FilingStatus, year: u32) -> Decimal {
+        self.overrides
+            .federal_standard_deduction
+            .unwrap_or_else(|| self.base.standard_deduction(filing_status, year))
+    }
+
+    fn fica_config(&self, year: u32) -> FicaConfig {
+        let config = self.base.fica_config(year);
+        FicaConfig {
+            wage_base: self.overrides.fica_wage_base.unwrap_or(config.wage_base),
+            ..config
+        }
+    }
+
+    fn state_config(&self, state: USState, year: u32) -> StateConfig {
+        let mut config = self.base.state_config(state, year);
+        if let Some(rate) = self
+            .overrides
+            .state_flat_rate
+            .filter(|_| state == self.state)
+        {
+            config.tax_type = StateTaxType::FlatRate;
+            config.flat_rate = Some(rate);
+        }
+        if let Some(rate) = self.overrides.sdi_rate {
+            config.sdi_rate = Some(rate);
+        }
+        config
+    }
+
+    fn retirement_limits(&self, year: u32) -> RetirementLimits {
+        self.base.retirement_limits(year)
+    }
+
+    fn rmd_distribution_period(&self, age: u32, year: u32) -> Option<Decimal> {
+        self.base.rmd_distribution_period(age, year)
+    }
+
+    fn poverty_guideline(&self, state: USState, year: u32) -> PovertyGuideline {
+        self.base.poverty_guideline(state, year)
+    }
+
+    fn business_mileage_rate(&self, year: u32) -> Decimal {
+        self.base.business_mileage_rate(year)
+    }
+
+    fn irmaa_schedule(&self, year: u32) -> IrmaaSchedule {
+        self.base.irmaa_schedule(year)
+    }
+
+    fn supports_year(&self, year: u32) -> bool {
+        self.base.supports_year(year)
+    }
+
+    fn state_data_year(&self, state: USState, year: u32) -> u32 {
+        self.base.state_data_year(state, year)
+    }
+
+    fn poverty_guideline_year(&self, year: u32) -> u32 {
+        self.base.poverty_guideline_year(year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_empty_overrides_pass_through() {
+        let base = EmbeddedTaxData::new();
+        let data = OverriddenData::new(&base, Overrides::default(), USState::California);
+
+        assert!(Overrides::default().is_empty());
+        assert_eq!(
+            data.standard_deduction(FilingStatus::Single, 2024),
+            dec!(14600)
+        );
+        assert_eq!(data.fica_config(2024).wage_base, dec!(168600));
+    }
+
+    #[test]
+    fn test_state_flat_rate_only_for_resident_state() {
+        let base = EmbeddedTaxData::new();
+        let overrides = Overrides {
+            state_flat_rate: Some(dec!(0.05)),
+            sdi_rate: Some(dec!(0.011)),
+            ..Default::default()
+        };
+        let data = OverriddenData::new(&base, overrides, USState::California);
+
+        let california = data.state_config(USState::California, 2024);
+        assert_eq!(california.tax_type, StateTaxType::FlatRate);
+        assert_eq!(california.flat_rate, Some(dec!(0.05)));
+        assert_eq!(california.sdi_rate, Some(dec!(0.011)));
+
+        let oregon = data.state_config(USState::Oregon, 2024);
+        assert_eq!(oregon.tax_type, StateTaxType::Progressive);
+    }
+}
@@ -3,15 +3,46 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use crate::calculators::{FederalTaxCalculator, FicaCalculator, StateTaxCalculator};
-use crate::data::TaxDataProvider;
-use crate::models::income::{CalculatedIncome, TimeframeIncome};
+use crate::calculators::credits::adoption::{AdoptionCreditResult, AdoptionExpenses};
+use crate::calculators::credits::child_tax::ChildTaxCreditCalculator;
+use crate::calculators::credits::clean_vehicle::{CleanVehicle, CleanVehicleCredit};
+use crate::calculators::credits::earned_income::EarnedIncomeCreditCalculator;
+use crate::calculators::credits::energy::{EnergyCreditResult, EnergyImprovements};
+use crate::calculators::credits::{CreditClaims, NonrefundableCredits};
+use crate::calculators::garnishment::{GarnishmentCalculator, GarnishmentResult};
+use crate::calculators::qbi::{QbiDeductionCalculator, QualifiedBusiness};
+use crate::calculators::seca::SecaCalculator;
+use crate::calculators::self_employment::ScheduleC;
+use crate::calculators::social_security::SocialSecurityTaxation;
+use crate::calculators::state::StateTaxInput;
+use crate::calculators::{
+    FederalTaxCalculator, FicaCalculator, MultiStateTaxCalculator, SelfEmploymentTaxCalculator,
+    StateTaxCalculator,
+};
+use crate::data::overridden::{OverriddenData, Overrides};
+use crate::data::{StateTaxType, TaxDataProvider};
+use crate::models::capital::{CapitalGains, CapitalLossCarryover};
+use crate::models::deduction::{DeductionType, Garnishment, HsaCoverage, ItemizedDeductions};
+use crate::models::dependent::Dependent;
+use crate::models::financial_independence::{FiAssumptions, FiMetrics, PayrollSavings};
+use crate::models::income::{CalculatedIncome, TimeframeIncome, WorkSchedule};
+use crate::models::investment::InvestmentIncome;
+use crate::models::money::Money;
+use crate::models::profile::UserProfile;
+use crate::models::rate::Rate;
+use crate::models::retirement::RetirementIncome;
+use crate::models::simulation::SimulationConfig;
 use crate::models::state::USState;
-use crate::models::tax::{EffectiveRates, FilingStatus, TaxBreakdown};
+use crate::models::tax::{
+    BreakdownConfidence, BreakdownExplanations, Confidence, EffectiveRates, FederalTaxResult,
+    FicaResult, FilingStatus, PayrollProgramKind, SdiCoverage, SelfEmploymentTax, StateTaxResult,
+    TaxBreakdown, TaxCredits,
+};
 
 /// Input for complete tax calculation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaxCalculationInput {
+    /// W-2 wages
     pub gross_income: Decimal,
     pub filing_status: FilingStatus,
     pub state: USState,
@@ -19,6 +50,99 @@ pub struct TaxCalculationInput {
     pub post_tax_deductions: Decimal,
     pub traditional_401k: Decimal,
     pub roth_401k: Decimal,
+    /// Payroll HSA contributions (exempt from FICA)
+    #[serde(default)]
+    pub hsa_contribution: Decimal,
+    /// Employer HSA contributions, outside taxable and FICA wages but
+    /// counted toward the HSA limit
+    #[serde(default)]
+    pub employer_hsa_contribution: Decimal,
+    /// HDHP coverage tier for the HSA limit; when unknown, the family
+    /// limit is assumed
+    #[serde(default)]
+    pub hsa_coverage: Option<HsaCoverage>,
+    /// Health care FSA contributions (exempt from FICA)
+    #[serde(default)]
+    pub health_fsa: Decimal,
+    /// Dependent care FSA contributions (exempt from FICA)
+    #[serde(default)]
+    pub dependent_care_fsa: Decimal,
+    #[serde(default)]
+    pub retirement_income: RetirementIncome,
+    /// School district number (Ohio school district income tax)
+    #[serde(default)]
+    pub school_district: Option<String>,
+    /// State where wages are earned, when different from the resident `state`
+    #[serde(default)]
+    pub work_state: Option<USState>,
+    /// County or city of residence for local income tax (e.g. a Maryland county)
+    #[serde(default)]
+    pub residence_locality: Option<String>,
+    /// City where wages are earned, for commuter wage taxes (e.g. Philadelphia)
+    #[serde(default)]
+    pub work_locality: Option<String>,
+    /// Exact local income tax rate, replacing the estimated local tax
+    #[serde(default)]
+    pub local_tax_rate_override: Option<Decimal>,
+    /// State SDI withholding, or a voluntary plan replacing it (CA, NJ)
+    #[serde(default)]
+    pub sdi_coverage: SdiCoverage,
+    /// Portion of `gross_income` earned by a spouse, for two-earner credits
+    #[serde(default)]
+    pub spouse_income: Decimal,
+    /// Property taxes paid, for state property tax credits; also counted in
+    /// itemized state and local taxes
+    #[serde(default)]
+    pub property_taxes: Decimal,
+    /// Published figures to replace for this calculation
+    #[serde(default)]
+    pub overrides: Overrides,
+    /// Work year behind `gross_income`; unpaid leave reduces the earner's
+    /// wages pro rata
+    #[serde(default)]
+    pub work_schedule: WorkSchedule,
+    /// Court and agency orders withheld after taxes, before `post_tax_deductions`
+    #[serde(default)]
+    pub garnishments: Vec<Garnishment>,
+    /// Schedule A expenses; the larger of these and the standard deduction
+    /// is taken
+    #[serde(default)]
+    pub itemized_deductions: ItemizedDeductions,
+    /// Realized capital gains and losses, with losses carried in
+    #[serde(default)]
+    pub capital_gains: CapitalGains,
+    /// Crypto, staking, and 1099-MISC income
+    #[serde(default)]
+    pub investment_income: InvestmentIncome,
+    /// Net profit from a sole proprietorship or 1099 work (Schedule C),
+    /// subject to self-employment tax
+    #[serde(default)]
+    pub self_employment_income: Decimal,
+    /// A sole proprietorship's receipts and expenses, including a home
+    /// office and business vehicle; its net profit, if any, is added to
+    /// `self_employment_income`
+    #[serde(default)]
+    pub schedule_c: Option<ScheduleC>,
+    /// Pass-through income and the business details that limit the
+    /// qualified business income deduction on it and on self-employment income
+    #[serde(default)]
+    pub qualified_business: QualifiedBusiness,
+    /// Home energy spending for the residential energy credits
+    #[serde(default)]
+    pub energy_improvements: EnergyImprovements,
+    /// Clean vehicle bought this year, for the EV credit
+    #[serde(default)]
+    pub clean_vehicle: Option<CleanVehicle>,
+    /// Adoptions finalized this year, for the adoption credit
+    #[serde(default)]
+    pub adoption: AdoptionExpenses,
+    /// People the taxpayer claims, checked for head of household eligibility
+    #[serde(default)]
+    pub dependents: Vec<Dependent>,
+    /// Tax years since the spouse died (0 for the year of death), checked
+    /// for qualifying surviving spouse eligibility
+    #[serde(default)]
+    pub years_since_spouse_death: Option<u32>,
 }
 
 impl Default for TaxCalculationInput {
@@ -31,6 +155,618 @@ impl Default for TaxCalculationInput {
             post_tax_deductions: Decimal::ZERO,
             traditional_401k: Decimal::ZERO,
             roth_401k: Decimal::ZERO,
+            hsa_contribution: Decimal::ZERO,
+            employer_hsa_contribution: Decimal::ZERO,
+            hsa_coverage: None,
+            health_fsa: Decimal::ZERO,
+            dependent_care_fsa: Decimal::ZERO,
+            retirement_income: RetirementIncome::default(),
+            school_district: None,
+            work_state: None,
+            residence_locality: None,
+            work_locality: None,
+            local_tax_rate_override: None,
+            sdi_coverage: SdiCoverage::State,
+            spouse_income: Decimal::ZERO,
+            property_taxes: Decimal::ZERO,
+            overrides: Overrides::default(),
+            work_schedule: WorkSchedule::default(),
+            garnishments: Vec::new(),
+            itemized_deductions: ItemizedDeductions::default(),
+            capital_gains: CapitalGains::default(),
+            investment_income: InvestmentIncome::default(),
+            self_employment_income: Decimal::ZERO,
+            schedule_c: None,
+            qualified_business: QualifiedBusiness::default(),
+            energy_improvements: EnergyImprovements::default(),
+            clean_vehicle: None,
+            adoption: AdoptionExpenses::default(),
+            dependents: Vec::new(),
+            years_since_spouse_death: None,
+        }
+    }
+}
+
+impl TaxCalculationInput {
+    /// Start building an input from the defaults (single, California, no deductions)
+    pub fn builder() -> TaxCalculationInputBuilder {
+        TaxCalculationInputBuilder::default()
+    }
+
+    /// Filing status the taxpayer can actually use
+    ///
+    /// A surviving spouse files jointly for the year of death and as a
+    /// qualifying surviving spouse for the two years after, if a dependent
+    /// child lives with them. Later years, or those years without such a
+    /// child, fall back to head of household with a qualifying person,
+    /// otherwise single. Other statuses, and a surviving spouse with no year
+    /// given, are kept.
+    pub fn eligible_filing_status(&self) -> FilingStatus {
+        let has_dependent_child = self
+            .dependents
+            .iter()
+            .any(Dependent::qualifies_for_surviving_spouse);
+        match (self.filing_status, self.years_since_spouse_death) {
+            (FilingStatus::QualifyingWidower, Some(0)) => FilingStatus::MarriedFilingJointly,
+            (FilingStatus::QualifyingWidower, Some(years)) if years > 2 || !has_dependent_child => {
+                if self
+                    .dependents
+                    .iter()
+                    .any(Dependent::qualifies_for_head_of_household)
+                {
+                    FilingStatus::HeadOfHousehold
+                } else {
+                    FilingStatus::Single
+                }
+            },
+            (status, _) => status,
+        }
+    }
+
+    /// Schedule A expenses, with `property_taxes` added to state and local
+    /// taxes
+    pub fn itemized_expenses(&self) -> ItemizedDeductions {
+        ItemizedDeductions {
+            state_and_local_taxes: self.itemized_deductions.state_and_local_taxes
+                + self.property_taxes,
+            ..self.itemized_deductions.clone()
+        }
+    }
+
+    /// Check the input is internally consistent
+    ///
+    /// Amounts must be non-negative, payroll deductions can't exceed wages,
+    /// the spouse's share can't exceed total wages, a local rate override
+    /// must be a fraction below 1, and the work schedule and dependents' time
+    /// in the home must fit in a year.
+    pub fn validate(&self) -> Result<(), CalculationError> {
+        let input = self;
+        let invalid = |message: &str| CalculationError::InvalidInput {
+            message: message.to_string(),
+        };
+
+        let amounts = [
+            ("gross_income", input.gross_income),
+            ("pre_tax_deductions", input.pre_tax_deductions),
+            ("post_tax_deductions", input.post_tax_deductions),
+            ("traditional_401k", input.traditional_401k),
+            ("roth_401k", input.roth_401k),
+            ("hsa_contribution", input.hsa_contribution),
+            ("employer_hsa_contribution", input.employer_hsa_contribution),
+            ("health_fsa", input.health_fsa),
+            ("dependent_care_fsa", input.dependent_care_fsa),
+            ("spouse_income", input.spouse_income),
+            ("property_taxes", input.property_taxes),
+            ("self_employment_income", input.self_employment_income),
+            (
+                "qualified_business.pass_through_income",
+                input.qualified_business.pass_through_income,
+            ),
+            (
+                "qualified_business.w2_wages",
+                input.qualified_business.w2_wages,
+            ),
+            (
+                "qualified_business.property_basis",
+                input.qualified_business.property_basis,
+            ),
+            (
+                "investment_income.staking_rewards",
+                input.investment_income.staking_rewards,
+            ),
+            (
+                "investment_income.miscellaneous",
+                input.investment_income.miscellaneous,
+            ),
+            (
+                "investment_income.gambling_winnings",
+                input.investment_income.gambling_winnings,
+            ),
+            (
+                "investment_income.qualified_dividends",
+                input.investment_income.qualified_dividends,
+            ),
+            (
+                "energy_improvements.clean_energy",
+                input.energy_improvements.clean_energy,
+            ),
+            (
+                "energy_improvements.heat_pumps",
+                input.energy_improvements.heat_pumps,
+            ),
+            (
+                "energy_improvements.envelope",
+                input.energy_improvements.envelope,
+            ),
+            (
+                "energy_improvements.carryforward",
+                input.energy_improvements.carryforward,
+            ),
+            ("adoption.carryforward", input.adoption.carryforward),
+            (
+                "capital_gains.carryover.short_term",
+                input.capital_gains.carryover.short_term,
+            ),
+            (
+                "capital_gains.carryover.long_term",
+                input.capital_gains.carryover.long_term,
+            ),
+        ];
+        if let Some((name, _)) = amounts.iter().find(|(_, amount)| *amount < Decimal::ZERO) {
+            return Err(invalid(&format!("{name} cannot be negative")));
+        }
+        if let Some((name, _)) = input
+            .itemized_deductions
+            .amounts()
+            .iter()
+            .find(|(_, amount)| *amount < Decimal::ZERO)
+        {
+            return Err(invalid(&format!("{name} cannot be negative")));
+        }
+        if let Some(schedule) = &input.schedule_c {
+            let vehicle = schedule.vehicle.as_ref();
+            if [schedule.gross_receipts, schedule.expenses]
+                .into_iter()
+                .chain(vehicle.map(|v| v.operating_costs))
+                .chain(vehicle.map(|v| v.depreciation))
+                .chain(vehicle.map(|v| v.parking_and_tolls))
+                .any(|amount| amount < Decimal::ZERO)
+            {
+                return Err(invalid("schedule_c amounts cannot be negative"));
+            }
+        }
+        if let Some(vehicle) = &input.clean_vehicle {
+            if vehicle.sale_price < Decimal::ZERO || vehicle.vehicle_credit < Decimal::ZERO {
+                return Err(invalid("clean vehicle amounts cannot be negative"));
+            }
+        }
+        if input
+            .adoption
+            .adoptions
+            .iter()
+            .any(|a| a.qualified_expenses < Decimal::ZERO)
+        {
+            return Err(invalid("adoption expenses cannot be negative"));
+        }
+        if input
+            .garnishments
+            .iter()
+            .any(|g| g.annual_amount < Decimal::ZERO)
+        {
+            return Err(invalid("garnishment amounts cannot be negative"));
+        }
+        if input.dependents.iter().any(|d| d.months_in_home > 12) {
+            return Err(invalid("dependent months_in_home cannot exceed 12"));
+        }
+
+        let payroll_deductions = input.pre_tax_deductions
+            + input.traditional_401k
+            + input.roth_401k
+            + input.hsa_contribution
+            + input.health_fsa
+            + input.dependent_care_fsa;
+        if payroll_deductions > input.gross_income {
+            return Err(invalid("payroll deductions exceed gross income"));
+        }
+        if input.spouse_income > input.gross_income {
+            return Err(invalid("spouse_income exceeds gross income"));
+        }
+        if let Some(rate) = input.local_tax_rate_override {
+            if rate < Decimal::ZERO || rate >= Decimal::ONE {
+                return Err(invalid("local_tax_rate_override must be between 0 and 1"));
+            }
+        }
+        let schedule = &input.work_schedule;
+        if schedule.weeks_per_year <= Decimal::ZERO || schedule.weeks_per_year > Decimal::from(52) {
+            return Err(invalid("weeks_per_year must be between 1 and 52"));
+        }
+        if schedule.unpaid_leave_weeks < Decimal::ZERO
+            || schedule.unpaid_leave_weeks > schedule.weeks_per_year
+        {
+            return Err(invalid("unpaid_leave_weeks must be within the work year"));
+        }
+        if schedule.hours_per_week <= Decimal::ZERO
+            || schedule.days_per_week <= Decimal::ZERO
+            || schedule.days_per_week > Decimal::from(7)
+        {
+            return Err(invalid(
+                "work schedule needs positive hours and 1 to 7 days",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Fluent builder for [`TaxCalculationInput`] that validates on `build()`
+#[derive(Debug, Clone, Default)]
+pub struct TaxCalculationInputBuilder {
+    input: TaxCalculationInput,
+}
+
+impl TaxCalculationInputBuilder {
+    pub fn gross_income(mut self, amount: Decimal) -> Self {
+        self.input.gross_income = amount;
+        self
+    }
+
+    pub fn filing_status(mut self, filing_status: FilingStatus) -> Self {
+        self.input.filing_status = filing_status;
+        self
+    }
+
+    pub fn state(mut self, state: USState) -> Self {
+        self.input.state = state;
+        self
+    }
+
+    pub fn pre_tax_deductions(mut self, amount: Decimal) -> Self {
+        self.input.pre_tax_deductions = amount;
+        self
+    }
+
+    pub fn post_tax_deductions(mut self, amount: Decimal) -> Self {
+        self.input.post_tax_deductions = amount;
+        self
+    }
+
+    pub fn traditional_401k(mut self, amount: Decimal) -> Self {
+        self.input.traditional_401k = amount;
+        self
+    }
+
+    pub fn roth_401k(mut self, amount: Decimal) -> Self {
+        self.input.roth_401k = amount;
+        self
+    }
+
+    pub fn hsa_contribution(mut self, amount: Decimal) -> Self {
+        self.input.hsa_contribution = amount;
+        self
+    }
+
+    pub fn employer_hsa_contribution(mut self, amount: Decimal) -> Self {
+        self.input.employer_hsa_contribution = amount;
+        self
+    }
+
+    pub fn hsa_coverage(mut self, coverage: HsaCoverage) -> Self {
+        self.input.hsa_coverage = Some(coverage);
+        self
+    }
+
+    pub fn health_fsa(mut self, amount: Decimal) -> Self {
+        self.input.health_fsa = amount;
+        self
+    }
+
+    pub fn dependent_care_fsa(mut self, amount: Decimal) -> Self {
+        self.input.dependent_care_fsa = amount;
+        self
+    }
+
+    pub fn retirement_income(mut self, retirement_income: RetirementIncome) -> Self {
+        self.input.retirement_income = retirement_income;
+        self
+    }
+
+    pub fn school_district(mut self, district: impl Into<String>) -> Self {
+        self.input.school_district = Some(district.into());
+        self
+    }
+
+    pub fn work_state(mut self, state: USState) -> Self {
+        self.input.work_state = Some(state);
+        self
+    }
+
+    pub fn residence_locality(mut self, locality: impl Into<String>) -> Self {
+        self.input.residence_locality = Some(locality.into());
+        self
+    }
+
+    pub fn work_locality(mut self, locality: impl Into<String>) -> Self {
+        self.input.work_locality = Some(locality.into());
+        self
+    }
+
+    pub fn local_tax_rate_override(mut self, rate: Decimal) -> Self {
+        self.input.local_tax_rate_override = Some(rate);
+        self
+    }
+
+    pub fn sdi_coverage(mut self, coverage: SdiCoverage) -> Self {
+        self.input.sdi_coverage = coverage;
+        self
+    }
+
+    pub fn spouse_income(mut self, amount: Decimal) -> Self {
+        self.input.spouse_income = amount;
+        self
+    }
+
+    pub fn property_taxes(mut self, amount: Decimal) -> Self {
+        self.input.property_taxes = amount;
+        self
+    }
+
+    pub fn overrides(mut self, overrides: Overrides) -> Self {
+        self.input.overrides = overrides;
+        self
+    }
+
+    pub fn work_schedule(mut self, schedule: WorkSchedule) -> Self {
+        self.input.work_schedule = schedule;
+        self
+    }
+
+    pub fn garnishment(mut self, garnishment: Garnishment) -> Self {
+        self.input.garnishments.push(garnishment);
+        self
+    }
+
+    pub fn itemized_deductions(mut self, deductions: ItemizedDeductions) -> Self {
+        self.input.itemized_deductions = deductions;
+        self
+    }
+
+    pub fn capital_gains(mut self, capital_gains: CapitalGains) -> Self {
+        self.input.capital_gains = capital_gains;
+        self
+    }
+
+    pub fn investment_income(mut self, investment_income: InvestmentIncome) -> Self {
+        self.input.investment_income = investment_income;
+        self
+    }
+
+    pub fn self_employment_income(mut self, amount: Decimal) -> Self {
+        self.input.self_employment_income = amount;
+        self
+    }
+
+    pub fn schedule_c(mut self, schedule: ScheduleC) -> Self {
+        self.input.schedule_c = Some(schedule);
+        self
+    }
+
+    pub fn qualified_business(mut self, business: QualifiedBusiness) -> Self {
+        self.input.qualified_business = business;
+        self
+    }
+
+    pub fn energy_improvements(mut self, improvements: EnergyImprovements) -> Self {
+        self.input.energy_improvements = improvements;
+        self
+    }
+
+    pub fn clean_vehicle(mut self, vehicle: CleanVehicle) -> Self {
+        self.input.clean_vehicle = Some(vehicle);
+        self
+    }
+
+    pub fn adoption(mut self, adoption: AdoptionExpenses) -> Self {
+        self.input.adoption = adoption;
+        self
+    }
+
+    pub fn dependent(mut self, dependent: Dependent) -> Self {
+        self.input.dependents.push(dependent);
+        self
+    }
+
+    pub fn years_since_spouse_death(mut self, years: u32) -> Self {
+        self.input.years_since_spouse_death = Some(years);
+        self
+    }
+
+    /// Validate and return the input (see [`TaxCalculationInput::validate`])
+    pub fn build(self) -> Result<TaxCalculationInput, CalculationError> {
+        self.input.validate()?;
+        Ok(self.input)
+    }
+}
+
+impl From<&UserProfile> for TaxCalculationInput {
+    /// Annualize the profile's payroll deductions into the engine's buckets;
+    /// on a joint return the partner's wages join `gross_income`
+    fn from(profile: &UserProfile) -> Self {
+        let mut input = TaxCalculationInput {
+            gross_income: profile.income.total_gross() + profile.joint_spouse_income(),
+            filing_status: profile.filing_status,
+            state: profile.location.state,
+            traditional_401k: profile.retirement.traditional_401k,
+            roth_401k: profile.retirement.roth_401k,
+            retirement_income: profile.retirement_income.clone(),
+            school_district: profile.location.school_district.clone(),
+            work_state: profile.location.work_state,
+            residence_locality: profile.location.residence_locality.clone(),
+            work_locality: profile.location.work_locality.clone(),
+            sdi_coverage: profile.sdi_coverage,
+            spouse_income: profile.joint_spouse_income(),
+            property_taxes: profile.property_taxes,
+            work_schedule: profile.work_schedule.clone(),
+            ..Default::default()
+        };
+        for deduction in &profile.deductions {
+            let amount = deduction.annual_amount();
+            match deduction.deduction_type {
+                DeductionType::Hsa => input.hsa_contribution += amount,
+                DeductionType::Fsa => input.health_fsa += amount,
+                DeductionType::Traditional401k => input.traditional_401k += amount,
+                DeductionType::Roth401k => input.roth_401k += amount,
+                _ if deduction.is_pre_tax => input.pre_tax_deductions += amount,
+                _ => input.post_tax_deductions += amount,
+            }
+        }
+        input
+    }
+}
+
+/// Inconsistent input, or a data problem that would otherwise be
+/// calculated around silently
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CalculationError {
+    #[error("invalid input: {message}")]
+    InvalidInput { message: String },
+    #[error("no tax data for {year}")]
+    UnsupportedYear { year: u32 },
+    #[error("no federal brackets for {filing_status} in {year}")]
+    MissingFederalBrackets {
+        filing_status: FilingStatus,
+        year: u32,
+    },
+    #[error("no tax configuration for {state}")]
+    MissingStateConfig { state: USState },
+    #[error("no {state} brackets for {filing_status}")]
+    MissingStateBrackets {
+        state: USState,
+        filing_status: FilingStatus,
+    },
+}
+
+/// Non-fatal caveat about a calculation, for apps to show alongside results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CalcWarning {
+    /// Employee 401(k) deferrals above the annual limit, including any catch-up
+    ElectiveDeferralOverLimit {
+        contributed: Decimal,
+        limit: Decimal,
+    },
+    /// Employee and employer HSA contributions above the annual limit
+    HsaContributionOverLimit {
+        contributed: Decimal,
+        limit: Decimal,
+    },
+    /// Income is within 10% of the clean vehicle credit's MAGI limit
+    CleanVehicleIncomeNearLimit { magi: Decimal, limit: Decimal },
+    /// Income is over the clean vehicle credit's MAGI limit, so no credit
+    CleanVehicleIncomeOverLimit { magi: Decimal, limit: Decimal },
+    /// The state has no schedule for the filing status, so single was used
+    StateBracketsFallback {
+        state: USState,
+        filing_status: FilingStatus,
+    },
+    /// Local tax uses the state's average rate, not the taxpayer's locality
+    LocalTaxEstimated { state: USState },
+    /// Wages above the Social Security wage base; multiple employers may over-withhold
+    SocialSecurityWageBaseExceeded { wages: Decimal, wage_base: Decimal },
+    /// Head of household with no qualifying person among the dependents, so
+    /// the standard deduction is overstated by the difference from single
+    HeadOfHouseholdWithoutQualifyingPerson { overstated_deduction: Decimal },
+    /// Qualifying surviving spouse outside its two-year window, or without a
+    /// dependent child, so another status was used
+    SurvivingSpouseStatusReplaced {
+        years_since_spouse_death: u32,
+        filing_status: FilingStatus,
+    },
+    /// The state's figures for the tax year aren't embedded, so another
+    /// year's were used
+    StateDataFromOtherYear {
+        state: USState,
+        year: u32,
+        data_year: u32,
+    },
+    /// The poverty guideline for the year isn't embedded, so another
+    /// year's was used
+    PovertyGuidelineFromOtherYear { year: u32, guideline_year: u32 },
+}
+
+impl CalcWarning {
+    pub fn message(&self) -> String {
+        match self {
+            CalcWarning::ElectiveDeferralOverLimit { contributed, limit } => {
+                format!("401(k) contributions of ${contributed} exceed the ${limit} limit")
+            },
+            CalcWarning::HsaContributionOverLimit { contributed, limit } => {
+                format!("HSA contributions of ${contributed} exceed the ${limit} limit")
+            },
+            CalcWarning::CleanVehicleIncomeNearLimit { magi, limit } => format!(
+                "MAGI of ${magi} is close to the ${limit} clean vehicle credit limit; \
+                 going over it loses the whole credit"
+            ),
+            CalcWarning::CleanVehicleIncomeOverLimit { magi, limit } => {
+                format!("MAGI of ${magi} exceeds the ${limit} clean vehicle credit limit")
+            },
+            CalcWarning::StateBracketsFallback {
+                state,
+                filing_status,
+            } => format!(
+                "{} has no {} brackets; single brackets were used",
+                state.name(),
+                filing_status.display_name()
+            ),
+            CalcWarning::LocalTaxEstimated { state } => format!(
+                "{} local tax is estimated from an average rate",
+                state.name()
+            ),
+            CalcWarning::SocialSecurityWageBaseExceeded { wages, wage_base } => {
+                format!("Wages of ${wages} exceed the ${wage_base} Social Security wage base")
+            },
+            CalcWarning::HeadOfHouseholdWithoutQualifyingPerson {
+                overstated_deduction,
+            } => format!(
+                "Head of household requires a qualifying child or relative; without one, \
+                 filing single lowers the standard deduction by ${overstated_deduction}"
+            ),
+            CalcWarning::SurvivingSpouseStatusReplaced {
+                years_since_spouse_death: 0,
+                filing_status,
+            } => format!(
+                "A spouse's year of death is filed jointly; {} was used",
+                filing_status.display_name()
+            ),
+            CalcWarning::SurvivingSpouseStatusReplaced {
+                years_since_spouse_death: 1..=2,
+                filing_status,
+            } => format!(
+                "Qualifying surviving spouse status requires a dependent child living \
+                 at home; {} was used",
+                filing_status.display_name()
+            ),
+            CalcWarning::SurvivingSpouseStatusReplaced {
+                years_since_spouse_death,
+                filing_status,
+            } => format!(
+                "Qualifying surviving spouse status lasts two years after a spouse's death, \
+                 not {years_since_spouse_death}; {} was used",
+                filing_status.display_name()
+            ),
+            CalcWarning::StateDataFromOtherYear {
+                state,
+                year,
+                data_year,
+            } => format!(
+                "{} figures for {year} aren't available; {data_year} figures were used",
+                state.name()
+            ),
+            CalcWarning::PovertyGuidelineFromOtherYear {
+                year,
+                guideline_year,
+            } => format!(
+                "The {year} poverty guideline isn't available; the {guideline_year} \
+                 guideline was used"
+            ),
         }
     }
 }
@@ -41,6 +777,167 @@ pub struct TaxCalculationResult {
     pub income: CalculatedIncome,
     pub tax_breakdown: TaxBreakdown,
     pub effective_rates: EffectiveRates,
+    pub adjusted_gross_income: Decimal,
+    pub taxable_social_security: Decimal,
+    #[serde(default)]
+    pub warnings: Vec<CalcWarning>,
+    /// Garnishments withheld from take-home pay
+    #[serde(default)]
+    pub garnishments: GarnishmentResult,
+    /// Capital losses left to carry into next year
+    #[serde(default)]
+    pub capital_loss_carryover: CapitalLossCarryover,
+    /// Residential energy credits used and carried forward
+    #[serde(default)]
+    pub energy_credits: EnergyCreditResult,
+    /// Clean vehicle credit eligibility and amount used
+    #[serde(default)]
+    pub clean_vehicle: Option<CleanVehicleCredit>,
+    /// Adoption credit used and carried forward
+    #[serde(default)]
+    pub adoption_credit: AdoptionCreditResult,
+    /// 401(k) and HSA contributions taken from pay
+    #[serde(default)]
+    pub payroll_savings: PayrollSavings,
+}
+
+impl TaxCalculationResult {
+    /// Take-home pay left after `annual_expenses`
+    pub fn savings_capacity(&self, annual_expenses: Decimal) -> Decimal {
+        self.income.net - annual_expenses
+    }
+
+    /// Payroll savings and the employer match as a share of take-home pay
+    /// plus those savings
+    pub fn savings_rate(&self, employer_match: Decimal) -> Rate {
+        let saved = self.payroll_savings.total() + employer_match;
+        Rate::of(saved, self.income.net + saved)
+    }
+
+    /// Savings rate, savings capacity, and years to financial independence,
+    /// counting take-home pay not spent as saved
+    pub fn fi_metrics(&self, assumptions: &FiAssumptions) -> FiMetrics {
+        let payroll_savings = self.payroll_savings.total() + assumptions.employer_match;
+        let savings_capacity = self.savings_capacity(assumptions.annual_expenses);
+        let annual_savings = payroll_savings + savings_capacity.max(Decimal::ZERO);
+        FiMetrics {
+            payroll_savings,
+            savings_capacity,
+            annual_savings,
+            savings_rate: Rate::of(annual_savings, self.income.net + payroll_savings),
+            fi_number: assumptions.fi_number(),
+            years_to_fi: assumptions.years_to_fi(annual_savings),
+        }
+    }
+
+    /// Copy with every displayed amount rounded to cents and totals that add up
+    ///
+    /// Each tax line is rounded on its own; subtotals (`state.total_tax`,
+    /// `fica.total`, `self_employment.total`), `total_taxes`, and `net` are
+    /// the remainder lines, rebuilt from the rounded parts so that `federal +
+    /// state + fica + self_employment - refundable credits == total_taxes` and
+    /// `gross - total_taxes - garnishments - deductions == net` hold exactly.
+    pub fn rounded_to_cents(&self) -> Self {
+        let mut result = self.clone();
+        let deductions = self.income.gross - self.tax_breakdown.total_taxes - self.income.net;
+
+        let breakdown = &mut result.tax_breakdown;
+        breakdown.federal.tax = cents(breakdown.federal.tax);
+        breakdown.federal.ordinary_tax = cents(breakdown.federal.ordinary_tax);
+        breakdown.federal.preferential_tax = cents(breakdown.federal.preferential_tax);
+        for state in std::iter::once(&mut breakdown.state).chain(breakdown.work_state.as_mut()) {
+            state.income_tax = cents(state.income_tax);
+            state.local_tax = cents(state.local_tax);
+            state.school_district_tax = cents(state.school_district_tax);
+            for program in &mut state.payroll_programs {
+                program.amount = cents(program.amount);
+            }
+            state.sdi = state.payroll_programs.iter().map(|p| p.amount).sum();
+            state.total_tax =
+                state.income_tax + state.local_tax + state.school_district_tax + state.sdi;
+        }
+        let fica = &mut breakdown.fica;
+        fica.social_security = cents(fica.social_security);
+        fica.medicare = cents(fica.medicare);
+        fica.additional_medicare = cents(fica.additional_medicare);
+        fica.total = fica.social_security + fica.medicare + fica.additional_medicare;
+        let se = &mut breakdown.self_employment;
+        se.social_security = cents(se.social_security);
+        se.medicare = cents(se.medicare);
+        se.additional_medicare = cents(se.additional_medicare);
+        se.total = se.social_security + se.medicare + se.additional_medicare;
+        breakdown.total_taxes = breakdown.federal.tax
+            + breakdown.state.total_tax
+            + breakdown
+                .work_state
+                .as_ref()
+                .map(|w| w.total_tax)
+                .unwrap_or(Decimal::ZERO)
+            + breakdown.fica.total
+            + breakdown.self_employment.total
+            - breakdown.credits.refundable;
+
+        let schedule = &self.income.work_schedule;
+        let timeframes = |annual: Decimal| {
+            let timeframes = TimeframeIncome::from_schedule(annual, schedule);
+            TimeframeIncome {
+                annual,
+                quarterly: cents(timeframes.quarterly),
+                monthly: cents(timeframes.monthly),
+                semi_monthly: cents(timeframes.semi_monthly),
+                bi_weekly: cents(timeframes.bi_weekly),
+                weekly: cents(timeframes.weekly),
+                daily: cents(timeframes.daily),
+                hourly: cents(timeframes.hourly),
+            }
+        };
+
+        let garnishments = &mut result.garnishments;
+        for withholding in &mut garnishments.withholdings {
+            withholding.withheld = cents(withholding.withheld);
+        }
+        garnishments.total = garnishments.withholdings.iter().map(|w| w.withheld).sum();
+        garnishments.timeframes = timeframes(garnishments.total);
+        let deductions = deductions - self.garnishments.total;
+
+        let gross = cents(self.income.gross);
+        let net = gross - breakdown.total_taxes - garnishments.total - cents(deductions);
+        result.income = CalculatedIncome {
+            gross,
+            net,
+            timeframes: timeframes(net),
+            take_home_percentage: self.income.take_home_percentage,
+            work_schedule: self.income.work_schedule.clone(),
+        };
+        result
+    }
+}
+
+fn cents(amount: Decimal) -> Decimal {
+    Money::from(amount).cents().amount()
+}
+
+/// Change in one breakdown line between two scenarios
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDelta {
+    pub base: Decimal,
+    pub scenario: Decimal,
+    /// Scenario minus base
+    pub difference: Decimal,
+    /// Percent change from base, or `None` when the base is zero
+    pub percent_change: Option<Decimal>,
+}
+
+impl ComponentDelta {
+    pub fn new(base: Decimal, scenario: Decimal) -> Self {
+        let difference = scenario - base;
+        Self {
+            base,
+            scenario,
+            difference,
+            percent_change: (!base.is_zero()).then(|| difference / base * Decimal::from(100)),
+        }
+    }
 }
 
 /// Scenario comparison result
@@ -50,6 +947,13 @@ pub struct ScenarioComparison {
     pub scenario: TaxCalculationResult,
     pub net_difference: Decimal,
     pub monthly_difference: Decimal,
+    pub federal: ComponentDelta,
+    /// State and local income taxes across resident and work states, excluding SDI
+    pub state: ComponentDelta,
+    pub sdi: ComponentDelta,
+    pub fica: ComponentDelta,
+    /// Total effective rate, as a fraction
+    pub effective_rate: ComponentDelta,
 }
 
 impl ScenarioComparison {
@@ -66,8 +970,63 @@ impl ScenarioComparison {
     }
 }
 
+/// Low, expected, and high estimates of an uncertain amount
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ValueRange {
+    pub low: Decimal,
+    pub expected: Decimal,
+    pub high: Decimal,
+}
+
+impl ValueRange {
+    pub fn new(low: Decimal, expected: Decimal, high: Decimal) -> Self {
+        Self {
+            low,
+            expected,
+            high,
+        }
+    }
+
+    /// A known amount with no uncertainty
+    pub fn fixed(amount: Decimal) -> Self {
+        Self::new(amount, amount, amount)
+    }
+
+    pub fn is_ordered(&self) -> bool {
+        self.low <= self.expected && self.expected <= self.high
+    }
+}
+
+/// Results at the low, expected, and high ends of an income range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultBands {
+    pub pessimistic: TaxCalculationResult,
+    pub expected: TaxCalculationResult,
+    pub optimistic: TaxCalculationResult,
+}
+
+impl ResultBands {
+    /// Take-home difference between the optimistic and pessimistic cases
+    pub fn net_spread(&self) -> Decimal {
+        self.optimistic.income.net - self.pessimistic.income.net
+    }
+}
+
+/// Spread of annual take-home over sampled outcomes of uncertain income
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TakeHomeDistribution {
+    pub config: SimulationConfig,
+    /// Samples actually drawn
+    pub iterations: u32,
+    pub mean: Decimal,
+    pub p10: Decimal,
+    pub median: Decimal,
+    pub p90: Decimal,
+}
+
 /// Main calculation engine
 pub struct TaxCalculationEngine<'a> {
+    data_provider: &'a dyn TaxDataProvider,
     federal_calc: FederalTaxCalculator<'a>,
     state_calc: StateTaxCalculator<'a>,
     fica_calc: FicaCalculator<'a>,
@@ -78,6 +1037,7 @@ impl<'a> TaxCalculationEngine<'a> {
     /// Create a new calculation engine
     pub fn new(data_provider: &'a dyn TaxDataProvider, year: u32) -> Self {
         Self {
+            data_provider,
             federal_calc: FederalTaxCalculator::new(data_provider),
             state_calc: StateTaxCalculator::new(data_provider),
             fica_calc: FicaCalculator::new(data_provider),
@@ -85,110 +1045,873 @@ impl<'a> TaxCalculationEngine<'a> {
         }
     }
 
+    /// Tax year used for calculations
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Data provider backing this engine
+    pub fn data_provider(&self) -> &'a dyn TaxDataProvider {
+        self.data_provider
+    }
+
     /// Perform complete tax calculation
     pub fn calculate(&self, input: &TaxCalculationInput) -> TaxCalculationResult {
+        if !input.overrides.is_empty() {
+            let data =
+                OverriddenData::new(self.data_provider, input.overrides.clone(), input.state);
+            let input = TaxCalculationInput {
+                overrides: Overrides::default(),
+                ..input.clone()
+            };
+            return TaxCalculationEngine::new(&data, self.year).calculate(&input);
+        }
+
+        let filing_status = input.eligible_filing_status();
+        if filing_status != input.filing_status {
+            let mut result = self.calculate(&TaxCalculationInput {
+                filing_status,
+                ..input.clone()
+            });
+            result.warnings.insert(
+                0,
+                CalcWarning::SurvivingSpouseStatusReplaced {
+                    years_since_spouse_death: input.years_since_spouse_death.unwrap_or_default(),
+                    filing_status,
+                },
+            );
+            return result;
+        }
+
+        // Unpaid leave forfeits part of the earner's scheduled salary
+        let schedule = &input.work_schedule;
+        if schedule.unpaid_leave_weeks > Decimal::ZERO {
+            let earner_wages = input.gross_income - input.spouse_income;
+            let input = TaxCalculationInput {
+                gross_income: earner_wages * schedule.paid_share() + input.spouse_income,
+                work_schedule: WorkSchedule {
+                    weeks_per_year: schedule.paid_weeks(),
+                    unpaid_leave_weeks: Decimal::ZERO,
+                    ..schedule.clone()
+                },
+                ..input.clone()
+            };
+            return self.calculate(&input);
+        }
+
+        let retirement = &input.retirement_income;
+
         // Step 1: Calculate total pre-tax deductions
-        let total_pre_tax = input.pre_tax_deductions + input.traditional_401k;
+        let cafeteria_plan = input.hsa_contribution + input.health_fsa + input.dependent_care_fsa;
+        let total_pre_tax = input.pre_tax_deductions + input.traditional_401k + cafeteria_plan;
+
+        // Step 2: Calculate self-employment tax, with FICA wages using up the
+        // wage base first; half of it is deducted from income
+        let fica_wages = input.gross_income - cafeteria_plan;
+        let self_employment_income = input.self_employment_income
+            + input.schedule_c.as_ref().map_or(Decimal::ZERO, |schedule| {
+                SelfEmploymentTaxCalculator::new(self.data_provider)
+                    .net_profit(schedule, self.year)
+                    .max(Decimal::ZERO)
+            });
+        let self_employment = SecaCalculator::new(self.data_provider).calculate(
+            self_employment_income,
+            fica_wages,
+            input.filing_status,
+            self.year,
+        );
+        let self_employment_earnings = self_employment_income - self_employment.deductible_half;
+
+        // Step 3: Determine taxable Social Security from provisional income.
+        // Crypto disposals net with other capital gains, which are taxed as
+        // ordinary income; a net loss offsets up to $3,000 of other income
+        let wages_after_pre_tax = input.gross_income - total_pre_tax;
+        let earned_income = wages_after_pre_tax + self_employment_earnings;
+        let investment = &input.investment_income;
+        let capital = investment
+            .with_capital_gains(&input.capital_gains)
+            .net(input.filing_status);
+        let business = &input.qualified_business;
+        let other_income = earned_income
+            + business.pass_through_income
+            + retirement.taxable_distributions()
+            + investment.ordinary()
+            + investment.qualified_dividends
+            + capital.income();
+        let taxable_social_security = SocialSecurityTaxation::taxable_benefits(
+            retirement.social_security_benefits,
+            other_income.max(Decimal::ZERO),
+            input.filing_status,
+        );
+        let agi = (other_income + taxable_social_security).max(Decimal::ZERO);
 
-        // Step 2: Calculate federal taxable income
+        // Step 4: Calculate federal taxable income
         let std_deduction = self
             .federal_calc
             .standard_deduction(input.filing_status, self.year);
-        let federal_taxable =
-            (input.gross_income - total_pre_tax - std_deduction).max(Decimal::ZERO);
-
-        // Step 3: Calculate federal tax
-        let federal_result =
-            self.federal_calc
-                .calculate(federal_taxable, input.filing_status, self.year);
-
-        // Step 4: Calculate state tax (state may have different deductions)
-        let state_taxable = input.gross_income - total_pre_tax;
-        let state_result =
-            self.state_calc
-                .calculate(state_taxable, input.state, input.filing_status, self.year);
-
-        // Step 5: Calculate FICA (on gross income, not reduced by 401k for SS)
-        let fica_result = self.fica_calc.calculate_with_status(
-            input.gross_income,
+        let itemized = input.itemized_expenses().total(
+            agi,
             input.filing_status,
+            investment.gambling_winnings,
             self.year,
         );
+        let deduction = std_deduction.max(itemized);
+        let qbi = QbiDeductionCalculator::calculate(
+            self_employment_earnings + business.pass_through_income,
+            business,
+            agi - deduction,
+            capital.long_term_gain + investment.qualified_dividends,
+            input.filing_status,
+            self.year,
+        );
+        let federal_taxable = (agi - deduction - qbi.deduction).max(Decimal::ZERO);
+
+        // Step 5: Calculate federal tax, less the credits for dependents and
+        // then the other nonrefundable credits
+        let mut federal_result = self.federal_calc.calculate_with_preferential(
+            federal_taxable,
+            capital.long_term_gain + investment.qualified_dividends,
+            input.filing_status,
+            self.year,
+        );
+        federal_result.deduction = deduction;
+        federal_result.itemized = itemized > std_deduction;
+        federal_result.qbi_deduction = qbi.deduction;
+        let child_credits = ChildTaxCreditCalculator::calculate(
+            &input.dependents,
+            input.filing_status,
+            agi,
+            earned_income,
+            federal_result.tax,
+            self.year,
+        );
+        federal_result.apply_credits(child_credits.nonrefundable());
+        let claims = CreditClaims {
+            clean_vehicle: input.clean_vehicle.as_ref(),
+            energy_improvements: &input.energy_improvements,
+            adoption: &input.adoption,
+        };
+        let credits = NonrefundableCredits::calculate(
+            claims,
+            input.filing_status,
+            agi,
+            federal_result.tax,
+            self.year,
+        );
+        federal_result.apply_credits(credits.total());
+        let earned_income_credit = EarnedIncomeCreditCalculator::calculate(
+            earned_income,
+            agi,
+            capital.short_term_gain + capital.long_term_gain + investment.qualified_dividends,
+            input.filing_status,
+            input
+                .dependents
+                .iter()
+                .filter(|d| d.is_qualifying_child())
+                .count() as u32,
+            retirement.age,
+            self.year,
+        );
+        let tax_credits = TaxCredits {
+            child_tax_credit: child_credits.child_tax_credit,
+            other_dependents_credit: child_credits.other_dependents_credit,
+            other_nonrefundable: credits.total(),
+            additional_child_tax_credit: child_credits.additional_child_tax_credit,
+            earned_income_credit,
+            nonrefundable: child_credits.nonrefundable() + credits.total(),
+            refundable: child_credits.additional_child_tax_credit + earned_income_credit,
+        };
+        let NonrefundableCredits {
+            clean_vehicle,
+            energy: energy_credits,
+            adoption: adoption_credit,
+        } = credits;
+
+        // Step 6: Calculate state tax (state may have different deductions)
+        let state_input = StateTaxInput {
+            taxable_income: other_income,
+            wages: wages_after_pre_tax,
+            state: input.state,
+            filing_status: input.filing_status,
+            retirement_distributions: retirement.taxable_distributions(),
+            taxable_social_security,
+            age: retirement.age,
+            hsa_contributions: input.hsa_contribution + input.employer_hsa_contribution,
+            federal_income_tax: federal_result.tax,
+            school_district: input.school_district.clone(),
+            residence_locality: input.residence_locality.clone(),
+            work_locality: input.work_locality.clone(),
+            local_tax_rate_override: input.local_tax_rate_override,
+            sdi_coverage: input.sdi_coverage,
+            spouse_wages: input.spouse_income,
+            property_taxes: input.property_taxes,
+        };
+        let (state_result, work_state_result) = match input.work_state {
+            Some(work_state) if work_state != input.state => {
+                let multi_state = MultiStateTaxCalculator::new(self.data_provider).calculate(
+                    &state_input,
+                    work_state,
+                    wages_after_pre_tax,
+                    self.year,
+                );
+                (multi_state.resident, Some(multi_state.work_state))
+            },
+            _ => (
+                self.state_calc.calculate_input(&state_input, self.year),
+                None,
+            ),
+        };
+        // Bona fide territory residents owe no federal income tax on their
+        // wages; Guam and the Virgin Islands collected it above as a mirror tax
+        let (federal_result, tax_credits) = if input.state.is_territory() {
+            (FederalTaxResult::default(), TaxCredits::default())
+        } else {
+            (federal_result, tax_credits)
+        };
+        let state_total = state_result.total_tax
+            + work_state_result
+                .as_ref()
+                .map(|r| r.total_tax)
+                .unwrap_or(Decimal::ZERO);
 
-        // Step 6: Calculate total taxes
-        let total_taxes = federal_result.tax + state_result.total_tax + fica_result.total;
+        // Step 7: Calculate FICA (on wages only, not reduced by 401k for SS,
+        // but reduced by cafeteria-plan HSA and dependent care contributions)
+        let fica_result =
+            self.fica_calc
+                .calculate_with_status(fica_wages, input.filing_status, self.year);
 
-        // Step 7: Calculate post-tax deductions
+        // Step 8: Calculate total taxes, less the refundable credits
+        let total_taxes =
+            federal_result.tax + state_total + fica_result.total + self_employment.total
+                - tax_credits.refundable;
+
+        // Wages and business income plus all retirement cash received
+        let gross = input.gross_income
+            + self_employment_income
+            + business.pass_through_income
+            + retirement.total()
+            + investment.ordinary()
+            + investment.qualified_dividends
+            + capital.short_term_gain
+            + capital.long_term_gain;
+
+        // Step 9: Garnish the earner's disposable earnings: their own wages
+        // less the payroll taxes on them and the wages' share of income tax,
+        // before any voluntary deductions or refunds
+        let earner_wages = input.gross_income - input.spouse_income;
+        let share_of = |total: Decimal| {
+            if total > Decimal::ZERO {
+                (earner_wages / total).min(Decimal::ONE)
+            } else {
+                Decimal::ZERO
+            }
+        };
+        let earner_fica = self
+            .fica_calc
+            .calculate_with_status(
+                (earner_wages - cafeteria_plan).max(Decimal::ZERO),
+                input.filing_status,
+                self.year,
+            )
+            .total;
+        let state_payroll = state_result.sdi
+            + work_state_result
+                .as_ref()
+                .map(|r| r.sdi)
+                .unwrap_or(Decimal::ZERO);
+        let income_taxes = federal_result.tax + state_total - state_payroll;
+        let garnishments = GarnishmentCalculator::calculate(
+            &input.garnishments,
+            earner_wages
+                - earner_fica
+                - state_payroll * share_of(input.gross_income)
+                - income_taxes * share_of(gross),
+            &input.work_schedule,
+        );
+
+        // Step 10: Calculate post-tax deductions
         let total_post_tax = input.post_tax_deductions + input.roth_401k;
 
-        // Step 8: Calculate net income
-        let net_income = input.gross_income - total_taxes - total_pre_tax - total_post_tax;
+        // Step 11: Calculate net income
+        let net_income = gross - total_taxes - total_pre_tax - garnishments.total - total_post_tax;
 
-        // Step 9: Build timeframes
-        let timeframes = TimeframeIncome::from_annual(net_income);
+        // Step 12: Build timeframes
+        let timeframes = TimeframeIncome::from_schedule(net_income, &input.work_schedule);
 
-        // Step 10: Calculate take-home percentage
-        let take_home_pct = if input.gross_income > Decimal::ZERO {
-            (net_income / input.gross_income) * Decimal::from(100)
+        // Step 13: Calculate take-home percentage
+        let take_home_pct = if gross > Decimal::ZERO {
+            (net_income / gross) * Decimal::from(100)
         } else {
             Decimal::ZERO
         };
 
         // Build effective rates
-        let effective_rates = if input.gross_income > Decimal::ZERO {
-            EffectiveRates {
-                federal: federal_result.tax / input.gross_income,
-                state: state_result.total_tax / input.gross_income,
-                fica: fica_result.total / input.gross_income,
-                total: total_taxes / input.gross_income,
-            }
-        } else {
-            EffectiveRates::default()
+        let effective_rates = EffectiveRates {
+            federal: Rate::of(federal_result.tax - tax_credits.refundable, gross),
+            state: Rate::of(state_total, gross),
+            fica: Rate::of(fica_result.total + self_employment.total, gross),
+            total: Rate::of(total_taxes, gross),
         };
 
+        let mut warnings = self.warnings(input, &state_result, work_state_result.as_ref());
+        if let Some(vehicle) = &clean_vehicle {
+            let eligibility = &vehicle.eligibility;
+            let (magi, limit) = (eligibility.qualifying_magi, eligibility.magi_limit);
+            if !eligibility.eligible {
+                warnings.push(CalcWarning::CleanVehicleIncomeOverLimit { magi, limit });
+            } else if eligibility.near_limit {
+                warnings.push(CalcWarning::CleanVehicleIncomeNearLimit { magi, limit });
+            }
+        }
+        let confidence = Self::confidence(&state_result, work_state_result.as_ref());
+        let mut explanations = self.explanations(
+            input,
+            fica_wages,
+            &federal_result,
+            &state_result,
+            work_state_result.as_ref(),
+            &fica_result,
+        );
+        explanations.self_employment =
+            Self::self_employment_explanation(self_employment_income, &self_employment);
+
         TaxCalculationResult {
             income: CalculatedIncome {
-                gross: input.gross_income,
+                gross,
                 net: net_income,
                 timeframes,
                 take_home_percentage: take_home_pct,
+                work_schedule: input.work_schedule.clone(),
             },
             tax_breakdown: TaxBreakdown {
                 federal: federal_result,
                 state: state_result,
+                work_state: work_state_result,
                 fica: fica_result,
+                self_employment,
+                credits: tax_credits,
                 total_taxes,
-                effective_rate: effective_rates.total,
+                effective_rate: effective_rates.total.as_fraction(),
+                confidence,
+                explanations,
             },
             effective_rates,
+            adjusted_gross_income: agi,
+            taxable_social_security,
+            warnings,
+            garnishments,
+            capital_loss_carryover: capital.carryover,
+            energy_credits,
+            clean_vehicle,
+            adoption_credit,
+            payroll_savings: PayrollSavings {
+                traditional_401k: input.traditional_401k,
+                roth_401k: input.roth_401k,
+                hsa: input.hsa_contribution,
+                employer_hsa: input.employer_hsa_contribution,
+            },
         }
     }
 
-    /// Compare two scenarios
-    pub fn compare_scenarios(
+    /// Mark the breakdown lines that rest on approximations
+    fn confidence(
+        state: &StateTaxResult,
+        work_state: Option<&StateTaxResult>,
+    ) -> BreakdownConfidence {
+        let estimated_if = |approximate: bool| {
+            if approximate {
+                Confidence::Estimated
+            } else {
+                Confidence::Exact
+            }
+        };
+        BreakdownConfidence {
+            state_income_tax: estimated_if(
+                state.single_brackets_used || work_state.is_some_and(|w| w.single_brackets_used),
+            ),
+            local_tax: estimated_if(state.local_tax_estimated),
+            // Nonresident tax prorates a resident-basis calculation by sourced wages
+            work_state: estimated_if(work_state.is_some()),
+            ..Default::default()
+        }
+    }
+
+    /// Describe how each breakdown line was reached
+    fn explanations(
         &self,
-        base: &TaxCalculationInput,
-        scenario: &TaxCalculationInput,
-    ) -> ScenarioComparison {
-        let base_result = self.calculate(base);
-        let scenario_result = self.calculate(scenario);
+        input: &TaxCalculationInput,
+        fica_wages: Decimal,
+        federal: &FederalTaxResult,
+        state: &StateTaxResult,
+        work_state: Option<&StateTaxResult>,
+        fica: &FicaResult,
+    ) -> BreakdownExplanations {
+        let money = |amount: Decimal| format!("${}", Money::from(amount));
+        let rate = Rate::from_fraction;
+        let name = input.state.name();
+        let config = self.data_provider.state_config(input.state, self.year);
+        let fica_config = self.data_provider.fica_config(self.year);
 
-        let net_diff = scenario_result.income.net - base_result.income.net;
-        let monthly_diff = net_diff / Decimal::from(12);
+        let federal_line = if input.state.is_territory() {
+            format!("Federal income tax: none on wages of bona fide {name} residents")
+        } else {
+            let qbi = if federal.qbi_deduction.is_zero() {
+                String::new()
+            } else {
+                format!(" and {} QBI deduction", money(federal.qbi_deduction))
+            };
+            format!(
+                "Federal income tax: {} on {} taxable income after the {} {} deduction{qbi}, top bracket {}",
+                money(federal.tax),
+                money(federal.taxable_income),
+                money(federal.deduction),
+                if federal.itemized { "itemized" } else { "standard" },
+                rate(federal.marginal_rate)
+            )
+        };
 
-        ScenarioComparison {
-            base: base_result,
-            scenario: scenario_result,
-            net_difference: net_diff,
-            monthly_difference: monthly_diff,
+        let mut state_line = match config.tax_type {
+            StateTaxType::NoTax => format!("{name} has no state income tax"),
+            StateTaxType::FlatRate => format!(
+                "{name} income tax: {} flat rate on {} taxable income",
+                rate(config.flat_rate.unwrap_or_default()),
+                money(state.taxable_income)
+            ),
+            StateTaxType::Progressive => {
+                let top_rate = state
+                    .bracket_breakdown
+                    .as_ref()
+                    .and_then(|brackets| brackets.last())
+                    .map(|b| format!(", top bracket {}", b.rate))
+                    .unwrap_or_default();
+                format!(
+                    "{name} income tax: {} on {} taxable income{top_rate}",
+                    money(state.income_tax),
+                    money(state.taxable_income)
+                )
+            },
+            StateTaxType::MirrorFederal => format!(
+                "{name} income tax: {} under the federal schedule on {} taxable income",
+                money(state.income_tax),
+                money(federal.taxable_income)
+            ),
+        };
+        if state.single_brackets_used {
+            state_line.push_str(&format!(
+                " (single brackets; no {} schedule)",
+                input.filing_status.display_name()
+            ));
         }
-    }
-}
 
-#[cfg(test)]
+        let local_tax = (!state.local_tax.is_zero()).then(|| {
+            let amount = money(state.local_tax);
+            if let Some(override_rate) = input
+                .local_tax_rate_override
+                .filter(|_| state.local_tax_overridden)
+            {
+                format!(
+                    "Local tax: {amount} at the provided {} rate",
+                    rate(override_rate)
+                )
+            } else if state.local_tax_estimated {
+                let average = config
+                    .local_tax_info
+                    .as_ref()
+                    .and_then(|info| info.average_rate)
+                    .unwrap_or_default();
+                format!(
+                    "Local tax: {amount} estimated at the {name} average of {}",
+                    rate(average)
+                )
+            } else if let Some(locality) = &input.residence_locality {
+                format!("Local tax: {amount} for {locality}")
+            } else {
+                format!("Local tax: {amount}")
+            }
+        });
+
+        let school_district_tax = (!state.school_district_tax.is_zero()).then(|| {
+            let amount = money(state.school_district_tax);
+            match &input.school_district {
+                Some(district) => format!("School district tax: {amount} for district {district}"),
+                None => format!("School district tax: {amount}"),
+            }
+        });
+
+        let sdi = (!state.payroll_programs.is_empty()).then(|| {
+            let program_rate = |kind: PayrollProgramKind, name: &str| {
+                if kind == PayrollProgramKind::Disability {
+                    let disability_rate = match input.sdi_coverage {
+                        SdiCoverage::VoluntaryPlan { rate } => rate,
+                        _ => config.sdi_rate.unwrap_or_default(),
+                    };
+                    (disability_rate, config.sdi_wage_base)
+                } else {
+                    config
+                        .payroll_programs
+                        .iter()
+                        .find(|p| p.name == name)
+                        .map(|p| (p.rate, p.wage_base))
+                        .unwrap_or_default()
+                }
+            };
+            state
+                .payroll_programs
+                .iter()
+                .map(|program| {
+                    let (program_rate, wage_base) = program_rate(program.kind, &program.name);
+                    let cap = wage_base
+                        .map(|base| format!(" up to {}", money(base)))
+                        .unwrap_or_default();
+                    format!("{}: {} of wages{cap}", program.name, rate(program_rate))
+                })
+                .collect::<Vec<_>>()
+                .join("; ")
+        });
+
+        let work_state = work_state.map(|w| {
+            let work_name = input.work_state.map(|s| s.name()).unwrap_or_default();
+            format!(
+                "{work_name} nonresident tax: {} on wages earned there, prorated from a \
+                 resident-basis calculation",
+                money(w.total_tax)
+            )
+        });
+
+        let social_security = if fica_wages > fica.social_security_wage_base {
+            format!(
+                "Social Security: {} of {} wage base (income exceeds cap)",
+                rate(fica_config.social_security_rate),
+                money(fica.social_security_wage_base)
+            )
+        } else {
+            format!(
+                "Social Security: {} of {} wages",
+                rate(fica_config.social_security_rate),
+                money(fica_wages)
+            )
+        };
+
+        let threshold = FicaCalculator::additional_medicare_threshold(input.filing_status);
+        let additional_medicare = (!fica.additional_medicare.is_zero()).then(|| {
+            format!(
+                "Additional Medicare: {} of {} wages above {}",
+                rate(fica_config.additional_medicare_rate),
+                money(fica_wages - threshold),
+                money(threshold)
+            )
+        });
+
+        BreakdownExplanations {
+            federal: Some(federal_line),
+            state_income_tax: Some(state_line),
+            local_tax,
+            school_district_tax,
+            sdi,
+            work_state,
+            social_security: Some(social_security),
+            medicare: Some(format!(
+                "Medicare: {} of {} wages",
+                rate(fica_config.medicare_rate),
+                money(fica_wages)
+            )),
+            additional_medicare,
+            self_employment: None,
+        }
+    }
+
+    /// Describe self-employment tax, if any was owed
+    fn self_employment_explanation(
+        profit: Decimal,
+        self_employment: &SelfEmploymentTax,
+    ) -> Option<String> {
+        let money = |amount: Decimal| format!("${}", Money::from(amount));
+        (!self_employment.total.is_zero()).then(|| {
+            format!(
+                "Self-employment tax: {} on {} net earnings (92.35% of {} profit); \
+                 {} deducted from income",
+                money(self_employment.total),
+                money(self_employment.net_earnings),
+                money(profit),
+                money(self_employment.deductible_half)
+            )
+        })
+    }
+
+    /// Caveats the calculation itself can't resolve
+    fn warnings(
+        &self,
+        input: &TaxCalculationInput,
+        state: &StateTaxResult,
+        work_state: Option<&StateTaxResult>,
+    ) -> Vec<CalcWarning> {
+        let mut warnings = Vec::new();
+
+        let limits = self.data_provider.retirement_limits(self.year);
+        let deferral_limit = if input.retirement_income.age >= 50 {
+            limits.elective_deferral + limits.catch_up_deferral
+        } else {
+            limits.elective_deferral
+        };
+        let deferrals = input.traditional_401k + input.roth_401k;
+        if deferrals > deferral_limit {
+            warnings.push(CalcWarning::ElectiveDeferralOverLimit {
+                contributed: deferrals,
+                limit: deferral_limit,
+            });
+        }
+
+        let hsa_contributions = input.hsa_contribution + input.employer_hsa_contribution;
+        let hsa_limit = limits.hsa_limit(
+            input.hsa_coverage.unwrap_or(HsaCoverage::Family),
+            input.retirement_income.age,
+        );
+        if hsa_contributions > hsa_limit {
+            warnings.push(CalcWarning::HsaContributionOverLimit {
+                contributed: hsa_contributions,
+                limit: hsa_limit,
+            });
+        }
+
+        if input.filing_status == FilingStatus::HeadOfHousehold
+            && !input
+                .dependents
+                .iter()
+                .any(Dependent::qualifies_for_head_of_household)
+        {
+            let deduction = |status| self.data_provider.standard_deduction(status, self.year);
+            warnings.push(CalcWarning::HeadOfHouseholdWithoutQualifyingPerson {
+                overstated_deduction: deduction(FilingStatus::HeadOfHousehold)
+                    - deduction(FilingStatus::Single),
+            });
+        }
+
+        let states = std::iter::once((input.state, state)).chain(input.work_state.zip(work_state));
+        for (code, result) in states {
+            let data_year = self.data_provider.state_data_year(code, self.year);
+            if data_year != self.year {
+                warnings.push(CalcWarning::StateDataFromOtherYear {
+                    state: code,
+                    year: self.year,
+                    data_year,
+                });
+            }
+            if result.single_brackets_used {
+                warnings.push(CalcWarning::StateBracketsFallback {
+                    state: code,
+                    filing_status: input.filing_status,
+                });
+            }
+        }
+        if state.local_tax_estimated {
+            warnings.push(CalcWarning::LocalTaxEstimated { state: input.state });
+        }
+
+        let wage_base = self.data_provider.fica_config(self.year).wage_base;
+        let fica_wages = input.gross_income
+            - input.hsa_contribution
+            - input.health_fsa
+            - input.dependent_care_fsa;
+        if fica_wages > wage_base {
+            warnings.push(CalcWarning::SocialSecurityWageBaseExceeded {
+                wages: fica_wages,
+                wage_base,
+            });
+        }
+
+        warnings
+    }
+
+    /// Perform a tax calculation, failing on missing or incomplete tax data
+    /// instead of computing with empty brackets
+    pub fn calculate_checked(
+        &self,
+        input: &TaxCalculationInput,
+    ) -> Result<TaxCalculationResult, CalculationError> {
+        self.validate_data(input)?;
+        Ok(self.calculate(input))
+    }
+
+    /// Check that the data provider covers the year, filing status, and states
+    pub fn validate_data(&self, input: &TaxCalculationInput) -> Result<(), CalculationError> {
+        let year = self.year;
+        let filing_status = input.filing_status;
+        if !self.data_provider.supports_year(year) {
+            return Err(CalculationError::UnsupportedYear { year });
+        }
+        if self
+            .data_provider
+            .federal_brackets(filing_status, year)
+            .is_empty()
+        {
+            return Err(CalculationError::MissingFederalBrackets {
+                filing_status,
+                year,
+            });
+        }
+
+        for state in std::iter::once(input.state).chain(input.work_state) {
+            if state.has_no_income_tax() {
+                continue;
+            }
+            let config = self.data_provider.state_config(state, year);
+            match config.tax_type {
+                StateTaxType::NoTax => {
+                    return Err(CalculationError::MissingStateConfig { state });
+                },
+                StateTaxType::FlatRate if config.flat_rate.is_none() => {
+                    return Err(CalculationError::MissingStateConfig { state });
+                },
+                // Without a schedule for the filing status, the calculation
+                // falls back to single and warns
+                StateTaxType::Progressive if config.brackets_for(filing_status).is_none() => {
+                    return Err(CalculationError::MissingStateBrackets {
+                        state,
+                        filing_status,
+                    });
+                },
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
+    /// Lazily calculate a stream of inputs, one result per input in order
+    pub fn calculate_iter<'s, I>(
+        &'s self,
+        inputs: I,
+    ) -> impl Iterator<Item = TaxCalculationResult> + 's
+    where
+        I: IntoIterator<Item = TaxCalculationInput>,
+        I::IntoIter: 's,
+    {
+        inputs.into_iter().map(move |input| self.calculate(&input))
+    }
+
+    /// Calculate pessimistic, expected, and optimistic results for uncertain
+    /// income (such as a bonus) added on top of the input's gross income
+    pub fn calculate_bands(
+        &self,
+        input: &TaxCalculationInput,
+        variable_income: &ValueRange,
+    ) -> ResultBands {
+        let with = |amount: Decimal| {
+            self.calculate(&TaxCalculationInput {
+                gross_income: input.gross_income + amount,
+                ..input.clone()
+            })
+        };
+        ResultBands {
+            pessimistic: with(variable_income.low),
+            expected: with(variable_income.expected),
+            optimistic: with(variable_income.high),
+        }
+    }
+
+    /// Sample uncertain income from a triangular distribution over its range
+    /// and report the spread of take-home pay. The same config always gives
+    /// the same distribution.
+    pub fn simulate_bands(
+        &self,
+        input: &TaxCalculationInput,
+        variable_income: &ValueRange,
+        config: &SimulationConfig,
+    ) -> TakeHomeDistribution {
+        let mut rng = config.rng();
+        let iterations = config.sample_count();
+        let mut nets: Vec<Decimal> = (0..iterations)
+            .map(|_| {
+                let amount = rng.triangular(
+                    variable_income.low,
+                    variable_income.expected,
+                    variable_income.high,
+                );
+                self.calculate(&TaxCalculationInput {
+                    gross_income: input.gross_income + amount,
+                    ..input.clone()
+                })
+                .income
+                .net
+            })
+            .collect();
+        nets.sort();
+
+        let percentile = |p: Decimal| {
+            let index = (p * Decimal::from(nets.len() - 1)).floor();
+            nets[usize::try_from(index).unwrap_or_default()]
+        };
+        TakeHomeDistribution {
+            config: *config,
+            iterations,
+            mean: nets.iter().sum::<Decimal>() / Decimal::from(nets.len()),
+            p10: percentile(Decimal::new(1, 1)),
+            median: percentile(Decimal::new(5, 1)),
+            p90: percentile(Decimal::new(9, 1)),
+        }
+    }
+
+    /// Compare two scenarios
+    pub fn compare_scenarios(
+        &self,
+        base: &TaxCalculationInput,
+        scenario: &TaxCalculationInput,
+    ) -> ScenarioComparison {
+        let base_result = self.calculate(base);
+        let scenario_result = self.calculate(scenario);
+
+        let net_diff = scenario_result.income.net - base_result.income.net;
+        let monthly_diff = net_diff / Decimal::from(12);
+
+        let delta = |line: fn(&TaxCalculationResult) -> Decimal| {
+            ComponentDelta::new(line(&base_result), line(&scenario_result))
+        };
+        let sdi = |r: &TaxCalculationResult| {
+            r.tax_breakdown.state.sdi
+                + r.tax_breakdown
+                    .work_state
+                    .as_ref()
+                    .map(|w| w.sdi)
+                    .unwrap_or(Decimal::ZERO)
+        };
+        let federal = delta(|r| r.tax_breakdown.federal.tax);
+        let state = delta(|r| {
+            let work_state = r.tax_breakdown.work_state.as_ref();
+            r.tax_breakdown.state.total_tax - r.tax_breakdown.state.sdi
+                + work_state
+                    .map(|w| w.total_tax - w.sdi)
+                    .unwrap_or(Decimal::ZERO)
+        });
+        let sdi = delta(sdi);
+        let fica = delta(|r| r.tax_breakdown.fica.total);
+        let effective_rate = delta(|r| r.effective_rates.total.as_fraction());
+
+        ScenarioComparison {
+            base: base_result,
+            scenario: scenario_result,
+            net_difference: net_diff,
+            monthly_difference: monthly_diff,
+            federal,
+            state,
+            sdi,
+            fica,
+            effective_rate,
+        }
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::calculators::credits::adoption::Adoption;
+    use crate::calculators::credits::clean_vehicle::VehicleCondition;
+    use crate::calculators::self_employment::HomeOffice;
+    use crate::calculators::vehicle_expense::VehicleUse;
     use crate::data::embedded::EmbeddedTaxData;
+    use crate::data::{FicaConfig, IrmaaSchedule, PovertyGuideline, RetirementLimits, StateConfig};
+    use crate::models::deduction::GarnishmentKind;
+    use crate::models::dependent::DependentRelationship;
+    use crate::models::income::PayFrequency;
+    use crate::models::tax::TaxBracket;
     use rust_decimal_macros::dec;
 
     fn setup() -> EmbeddedTaxData {
@@ -208,6 +1931,7 @@ mod tests {
             post_tax_deductions: dec!(0),
             traditional_401k: dec!(0),
             roth_401k: dec!(0),
+            ..Default::default()
         };
 
         let result = engine.calculate(&input);
@@ -334,13 +2058,1663 @@ mod tests {
             + result.effective_rates.state
             + result.effective_rates.fica;
 
-        let diff = (result.effective_rates.total - sum).abs();
+        let diff = (result.effective_rates.total - sum).as_fraction().abs();
         assert!(diff < dec!(0.001));
 
         // Effective rate should be less than 50%
         assert!(result.effective_rates.total < dec!(0.5));
     }
 
+    #[test]
+    fn test_retirement_income_skips_fica() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(0),
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::Pennsylvania,
+            retirement_income: RetirementIncome {
+                traditional_withdrawals: dec!(40000),
+                roth_withdrawals: dec!(10000),
+                social_security_benefits: dec!(36000),
+                pension_income: dec!(0),
+                age: 68,
+            },
+            ..Default::default()
+        };
+
+        let result = engine.calculate(&input);
+
+        assert_eq!(result.income.gross, dec!(86000));
+        assert_eq!(result.tax_breakdown.fica.total, dec!(0));
+        // Provisional income: $40,000 + $18,000 = $58,000 (MFJ)
+        // 85% × $14,000 + $6,000 = $17,900
+        assert_eq!(result.taxable_social_security, dec!(17900));
+        assert_eq!(result.adjusted_gross_income, dec!(57900));
+        // Pennsylvania exempts retirement income
+        assert_eq!(result.tax_breakdown.state.income_tax, dec!(0));
+    }
+
+    #[test]
+    fn test_hsa_and_dependent_care_reduce_fica() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let base = TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let with_hsa = TaxCalculationInput {
+            hsa_contribution: dec!(4000),
+            dependent_care_fsa: dec!(1000),
+            ..base.clone()
+        };
+        let with_401k = TaxCalculationInput {
+            traditional_401k: dec!(5000),
+            ..base.clone()
+        };
+
+        let hsa_result = engine.calculate(&with_hsa);
+        let k_result = engine.calculate(&with_401k);
+
+        // Same income tax effect, but only cafeteria-plan dollars avoid FICA
+        assert_eq!(
+            hsa_result.tax_breakdown.federal.tax,
+            k_result.tax_breakdown.federal.tax
+        );
+        assert_eq!(
+            k_result.tax_breakdown.fica.total - hsa_result.tax_breakdown.fica.total,
+            dec!(5000) * dec!(0.0765)
+        );
+        assert!(hsa_result.income.net > k_result.income.net);
+    }
+
+    #[test]
+    fn test_california_taxes_hsa_contributions() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::California,
+            hsa_contribution: dec!(4000),
+            ..Default::default()
+        };
+
+        let result = engine.calculate(&input);
+
+        assert_eq!(result.tax_breakdown.state.taxable_income, dec!(100000));
+        assert_eq!(result.adjusted_gross_income, dec!(96000));
+    }
+
+    #[test]
+    fn test_kansas_resident_working_in_missouri() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(80000),
+            state: USState::Kansas,
+            work_state: Some(USState::Missouri),
+            ..Default::default()
+        };
+        let kansas_only = TaxCalculationInput {
+            work_state: None,
+            ..input.clone()
+        };
+
+        let result = engine.calculate(&input);
+        let work_state = result.tax_breakdown.work_state.as_ref().unwrap();
+
+        assert_eq!(work_state.state_code, "MO");
+        assert!(work_state.income_tax > dec!(0));
+        // Kansas credits the Missouri tax, so total state tax matches Kansas alone
+        assert_eq!(
+            result.tax_breakdown.total_taxes,
+            engine.calculate(&kansas_only).tax_breakdown.total_taxes
+        );
+    }
+
+    #[test]
+    fn test_territory_residents_owe_no_federal_income_tax() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let texas = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(80000),
+            state: USState::Texas,
+            ..Default::default()
+        });
+        let guam = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(80000),
+            state: USState::Guam,
+            ..Default::default()
+        });
+
+        // Guam's mirror tax replaces the federal tax dollar for dollar
+        assert_eq!(guam.tax_breakdown.federal.tax, dec!(0));
+        assert_eq!(
+            guam.tax_breakdown.state.income_tax,
+            texas.tax_breakdown.federal.tax
+        );
+        assert_eq!(guam.income.net, texas.income.net);
+
+        let puerto_rico = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(80000),
+            state: USState::PuertoRico,
+            ..Default::default()
+        });
+        assert_eq!(puerto_rico.tax_breakdown.federal.tax, dec!(0));
+        // $76,500 after the $3,500 exemption: $8,430 + 33% over $61,500
+        assert_eq!(puerto_rico.tax_breakdown.state.income_tax, dec!(13380));
+        assert_eq!(
+            puerto_rico.tax_breakdown.fica.total,
+            texas.tax_breakdown.fica.total
+        );
+    }
+
+    #[test]
+    fn test_builder_matches_struct_literal() {
+        let built = TaxCalculationInput::builder()
+            .gross_income(dec!(120000))
+            .filing_status(FilingStatus::MarriedFilingJointly)
+            .state(USState::Ohio)
+            .traditional_401k(dec!(10000))
+            .school_district("2501")
+            .residence_locality("Columbus")
+            .build()
+            .unwrap();
+
+        assert_eq!(built.gross_income, dec!(120000));
+        assert_eq!(built.school_district.as_deref(), Some("2501"));
+        assert_eq!(built.roth_401k, dec!(0));
+
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let literal = TaxCalculationInput {
+            gross_income: dec!(120000),
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::Ohio,
+            traditional_401k: dec!(10000),
+            school_district: Some("2501".to_string()),
+            residence_locality: Some("Columbus".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            engine.calculate(&built).income.net,
+            engine.calculate(&literal).income.net
+        );
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let negative = TaxCalculationInput::builder()
+            .gross_income(dec!(50000))
+            .roth_401k(dec!(-1))
+            .build();
+        assert!(matches!(
+            negative,
+            Err(CalculationError::InvalidInput { .. })
+        ));
+
+        let over_deferred = TaxCalculationInput::builder()
+            .gross_income(dec!(20000))
+            .traditional_401k(dec!(15000))
+            .hsa_contribution(dec!(6000))
+            .build();
+        assert!(over_deferred.is_err());
+
+        let bad_rate = TaxCalculationInput::builder()
+            .gross_income(dec!(50000))
+            .local_tax_rate_override(dec!(2.5))
+            .build();
+        assert!(bad_rate.is_err());
+
+        assert!(TaxCalculationInput::builder().build().is_ok());
+    }
+
+    #[test]
+    fn test_calculate_iter_top_states() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let inputs = USState::all().iter().map(|&state| TaxCalculationInput {
+            gross_income: dec!(100000),
+            state,
+            ..Default::default()
+        });
+        let mut results: Vec<_> = engine.calculate_iter(inputs).collect();
+        assert_eq!(results.len(), 51);
+
+        results.sort_by_key(|r| std::cmp::Reverse(r.income.net));
+        let best: Vec<_> = results
+            .iter()
+            .take(3)
+            .map(|r| r.tax_breakdown.state.total_tax)
+            .collect();
+        assert_eq!(best, vec![dec!(0); 3]);
+
+        // Stops at the first state with an income tax
+        let first_taxed = engine
+            .calculate_iter(USState::all().iter().map(|&state| TaxCalculationInput {
+                gross_income: dec!(100000),
+                state,
+                ..Default::default()
+            }))
+            .find(|r| r.tax_breakdown.state.total_tax > dec!(0))
+            .unwrap();
+        assert_eq!(first_taxed.tax_breakdown.state.state_code, "AL");
+    }
+
+    /// Embedded data with Oregon, Virginia's brackets, and head-of-household
+    /// federal brackets missing
+    struct IncompleteData(EmbeddedTaxData);
+
+    impl TaxDataProvider for IncompleteData {
+        fn federal_brackets(&self, filing_status: FilingStatus, year: u32) -> Vec<TaxBracket> {
+            match filing_status {
+                FilingStatus::HeadOfHousehold => vec![],
+                _ => self.0.federal_brackets(filing_status, year),
+            }
+        }
+
+        fn standard_deduction(&self, filing_status: FilingStatus, year: u32) -> Decimal {
+            self.0.standard_deduction(filing_status, year)
+        }
+
+        fn fica_config(&self, year: u32) -> FicaConfig {
+            self.0.fica_config(year)
+        }
+
+        fn state_config(&self, state: USState, year: u32) -> StateConfig {
+            match state {
+                USState::Oregon => StateConfig::default(),
+                USState::Virginia => StateConfig {
+                    brackets: Default::default(),
+                    ..self.0.state_config(state, year)
+                },
+                _ => self.0.state_config(state, year),
+            }
+        }
+
+        fn retirement_limits(&self, year: u32) -> RetirementLimits {
+            self.0.retirement_limits(year)
+        }
+
+        fn rmd_distribution_period(&self, age: u32, year: u32) -> Option<Decimal> {
+            self.0.rmd_distribution_period(age, year)
+        }
+
+        fn poverty_guideline(&self, state: USState, year: u32) -> PovertyGuideline {
+            self.0.poverty_guideline(state, year)
+        }
+
+        fn business_mileage_rate(&self, year: u32) -> Decimal {
+            self.0.business_mileage_rate(year)
+        }
+
+        fn irmaa_schedule(&self, year: u32) -> IrmaaSchedule {
+            self.0.irmaa_schedule(year)
+        }
+    }
+
+    #[test]
+    fn test_calculate_checked_reports_data_problems() {
+        let data = IncompleteData(setup());
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(80000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        assert!(engine.calculate_checked(&input).is_ok());
+
+        let oregon = TaxCalculationInput {
+            state: USState::Oregon,
+            ..input.clone()
+        };
+        assert_eq!(
+            engine.calculate_checked(&oregon).unwrap_err(),
+            CalculationError::MissingStateConfig {
+                state: USState::Oregon
+            }
+        );
+
+        // A work state is checked too
+        let commuter = TaxCalculationInput {
+            work_state: Some(USState::Oregon),
+            ..input.clone()
+        };
+        assert!(engine.calculate_checked(&commuter).is_err());
+
+        let head_of_household = TaxCalculationInput {
+            filing_status: FilingStatus::HeadOfHousehold,
+            ..input.clone()
+        };
+        assert_eq!(
+            engine
+                .calculate_checked(&head_of_household)
+                .unwrap_err()
+                .to_string(),
+            "no federal brackets for head_of_household in 2024"
+        );
+    }
+
+    #[test]
+    fn test_calculate_checked_accepts_state_brackets_fallback() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(100000),
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::NewYork,
+            ..Default::default()
+        };
+
+        // New York has no joint schedule, so single is used with a warning
+        let checked = engine.calculate_checked(&input).unwrap();
+        assert_eq!(
+            checked.tax_breakdown.state.total_tax,
+            engine.calculate(&input).tax_breakdown.state.total_tax
+        );
+        assert!(checked
+            .warnings
+            .contains(&CalcWarning::StateBracketsFallback {
+                state: USState::NewYork,
+                filing_status: FilingStatus::MarriedFilingJointly,
+            }));
+
+        // Without a single schedule either, there's nothing to fall back to
+        let incomplete = IncompleteData(setup());
+        let engine = TaxCalculationEngine::new(&incomplete, 2024);
+        let virginia = TaxCalculationInput {
+            state: USState::Virginia,
+            ..input
+        };
+        assert_eq!(
+            engine.calculate_checked(&virginia).unwrap_err(),
+            CalculationError::MissingStateBrackets {
+                state: USState::Virginia,
+                filing_status: FilingStatus::MarriedFilingJointly,
+            }
+        );
+    }
+
+    #[test]
+    fn test_calculate_checked_rejects_unsupported_year() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2030);
+
+        assert_eq!(
+            engine
+                .calculate_checked(&TaxCalculationInput::default())
+                .unwrap_err(),
+            CalculationError::UnsupportedYear { year: 2030 }
+        );
+    }
+
+    #[test]
+    fn test_overrides_applied_per_calculation() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(200000),
+            state: USState::California,
+            ..Default::default()
+        };
+        let base = engine.calculate(&input);
+
+        let what_if = engine.calculate(&TaxCalculationInput {
+            overrides: Overrides {
+                federal_standard_deduction: Some(dec!(20000)),
+                state_flat_rate: Some(dec!(0.05)),
+                sdi_rate: Some(dec!(0)),
+                fica_wage_base: Some(dec!(200000)),
+            },
+            ..input.clone()
+        });
+
+        assert!(what_if.tax_breakdown.federal.tax < base.tax_breakdown.federal.tax);
+        assert_eq!(what_if.tax_breakdown.state.sdi, dec!(0));
+        // $200,000 - $5,363 CA standard deduction at a flat 5%
+        assert_eq!(what_if.tax_breakdown.state.income_tax, dec!(9731.85));
+        assert_eq!(
+            what_if.tax_breakdown.fica.social_security,
+            dec!(200000) * dec!(0.062)
+        );
+
+        // The engine itself is unchanged for later calculations
+        assert_eq!(engine.calculate(&input).income.net, base.income.net);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let quiet = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::Texas,
+            traditional_401k: dec!(23000),
+            ..Default::default()
+        });
+        assert!(quiet.warnings.is_empty());
+
+        let result = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(200000),
+            state: USState::NewYork,
+            filing_status: FilingStatus::HeadOfHousehold,
+            traditional_401k: dec!(20000),
+            roth_401k: dec!(5000),
+            dependents: vec![Dependent::child(8)],
+            ..Default::default()
+        });
+        assert_eq!(
+            result.warnings,
+            vec![
+                CalcWarning::ElectiveDeferralOverLimit {
+                    contributed: dec!(25000),
+                    limit: dec!(23000),
+                },
+                CalcWarning::StateBracketsFallback {
+                    state: USState::NewYork,
+                    filing_status: FilingStatus::HeadOfHousehold,
+                },
+                CalcWarning::LocalTaxEstimated {
+                    state: USState::NewYork
+                },
+                CalcWarning::SocialSecurityWageBaseExceeded {
+                    wages: dec!(200000),
+                    wage_base: dec!(168600),
+                },
+            ]
+        );
+        assert_eq!(
+            result.warnings[2].message(),
+            "New York local tax is estimated from an average rate"
+        );
+    }
+
+    #[test]
+    fn test_state_data_from_other_year_warning() {
+        let data = setup();
+        let input = TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::California,
+            work_state: Some(USState::Idaho),
+            ..Default::default()
+        };
+
+        let current = TaxCalculationEngine::new(&data, 2024).calculate(&input);
+        assert!(current.warnings.is_empty());
+
+        // Idaho has its 2025 rate; California's 2024 figures stand in
+        let result = TaxCalculationEngine::new(&data, 2025).calculate(&input);
+        assert_eq!(
+            result.warnings,
+            vec![CalcWarning::StateDataFromOtherYear {
+                state: USState::California,
+                year: 2025,
+                data_year: 2024,
+            }]
+        );
+        assert_eq!(
+            result.warnings[0].message(),
+            "California figures for 2025 aren't available; 2024 figures were used"
+        );
+    }
+
+    #[test]
+    fn test_surviving_spouse_two_year_limit() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(90000),
+            state: USState::Texas,
+            filing_status: FilingStatus::QualifyingWidower,
+            dependents: vec![Dependent::child(9)],
+            ..Default::default()
+        };
+        let with_status = |filing_status| {
+            engine.calculate(&TaxCalculationInput {
+                filing_status,
+                years_since_spouse_death: None,
+                ..input.clone()
+            })
+        };
+
+        for years in [1, 2] {
+            let result = engine.calculate(&TaxCalculationInput {
+                years_since_spouse_death: Some(years),
+                ..input.clone()
+            });
+            assert!(result.warnings.is_empty());
+        }
+
+        // Past the window, the child makes head of household available
+        let later = engine.calculate(&TaxCalculationInput {
+            years_since_spouse_death: Some(3),
+            ..input.clone()
+        });
+        assert_eq!(
+            later.warnings,
+            vec![CalcWarning::SurvivingSpouseStatusReplaced {
+                years_since_spouse_death: 3,
+                filing_status: FilingStatus::HeadOfHousehold,
+            }]
+        );
+        assert_eq!(
+            later.income.net,
+            with_status(FilingStatus::HeadOfHousehold).income.net
+        );
+
+        let no_child = TaxCalculationInput {
+            years_since_spouse_death: Some(5),
+            dependents: Vec::new(),
+            ..input.clone()
+        };
+        assert_eq!(no_child.eligible_filing_status(), FilingStatus::Single);
+
+        // Within the window, the status still needs a dependent child at home
+        let childless = engine.calculate(&TaxCalculationInput {
+            years_since_spouse_death: Some(1),
+            dependents: Vec::new(),
+            ..input.clone()
+        });
+        assert_eq!(
+            childless.warnings,
+            vec![CalcWarning::SurvivingSpouseStatusReplaced {
+                years_since_spouse_death: 1,
+                filing_status: FilingStatus::Single,
+            }]
+        );
+        assert_eq!(
+            childless.warnings[0].message(),
+            "Qualifying surviving spouse status requires a dependent child living at home; \
+             Single was used"
+        );
+        let parent_only = TaxCalculationInput {
+            years_since_spouse_death: Some(2),
+            dependents: vec![Dependent {
+                relationship: DependentRelationship::Parent,
+                age: 80,
+                ..Default::default()
+            }],
+            ..input.clone()
+        };
+        assert_eq!(
+            parent_only.eligible_filing_status(),
+            FilingStatus::HeadOfHousehold
+        );
+
+        let year_of_death = engine.calculate(&TaxCalculationInput {
+            years_since_spouse_death: Some(0),
+            ..input.clone()
+        });
+        assert_eq!(
+            year_of_death.income.net,
+            with_status(FilingStatus::MarriedFilingJointly).income.net
+        );
+        assert_eq!(
+            year_of_death.warnings[0].message(),
+            "A spouse's year of death is filed jointly; Married Filing Jointly was used"
+        );
+    }
+
+    #[test]
+    fn test_head_of_household_needs_qualifying_person() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(60000),
+            state: USState::Texas,
+            filing_status: FilingStatus::HeadOfHousehold,
+            ..Default::default()
+        };
+
+        // $21,900 head of household against $14,600 single
+        let warning = CalcWarning::HeadOfHouseholdWithoutQualifyingPerson {
+            overstated_deduction: dec!(7300),
+        };
+        assert_eq!(engine.calculate(&input).warnings, vec![warning.clone()]);
+        let adult_child = TaxCalculationInput {
+            dependents: vec![Dependent::child(25)],
+            ..input.clone()
+        };
+        assert_eq!(engine.calculate(&adult_child).warnings, vec![warning]);
+        let teenager = TaxCalculationInput {
+            dependents: vec![Dependent::child(17)],
+            ..input.clone()
+        };
+        assert!(engine.calculate(&teenager).warnings.is_empty());
+
+        let invalid = TaxCalculationInput::builder()
+            .dependent(Dependent {
+                months_in_home: 13,
+                ..Dependent::child(5)
+            })
+            .build();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_employer_hsa_contribution() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::California,
+            hsa_contribution: dec!(3000),
+            hsa_coverage: Some(HsaCoverage::SelfOnly),
+            ..Default::default()
+        };
+        let base = engine.calculate(&input);
+
+        let result = engine.calculate(&TaxCalculationInput {
+            employer_hsa_contribution: dec!(1500),
+            ..input.clone()
+        });
+
+        // Outside federal taxable and FICA wages; California taxes it
+        assert_eq!(result.adjusted_gross_income, base.adjusted_gross_income);
+        assert_eq!(
+            result.tax_breakdown.federal.tax,
+            base.tax_breakdown.federal.tax
+        );
+        assert_eq!(
+            result.tax_breakdown.fica.total,
+            base.tax_breakdown.fica.total
+        );
+        assert!(result.tax_breakdown.state.income_tax > base.tax_breakdown.state.income_tax);
+        assert_eq!(
+            result.warnings,
+            vec![CalcWarning::HsaContributionOverLimit {
+                contributed: dec!(4500),
+                limit: dec!(4150),
+            }]
+        );
+
+        // Without a coverage tier, only the family limit is enforced
+        let unknown = engine.calculate(&TaxCalculationInput {
+            employer_hsa_contribution: dec!(1500),
+            hsa_coverage: None,
+            ..input
+        });
+        assert!(unknown.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_clean_vehicle_credit() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(140000),
+            state: USState::Texas,
+            clean_vehicle: Some(CleanVehicle {
+                condition: VehicleCondition::New,
+                sale_price: dec!(48000),
+                vehicle_credit: dec!(7500),
+                prior_year_magi: None,
+            }),
+            ..Default::default()
+        };
+        let without = engine.calculate(&TaxCalculationInput {
+            clean_vehicle: None,
+            ..input.clone()
+        });
+
+        let result = engine.calculate(&input);
+        assert_eq!(
+            result.tax_breakdown.federal.tax,
+            without.tax_breakdown.federal.tax - dec!(7500)
+        );
+        assert_eq!(result.tax_breakdown.federal.credits, dec!(7500));
+        assert_eq!(
+            result.warnings,
+            vec![CalcWarning::CleanVehicleIncomeNearLimit {
+                magi: dec!(140000),
+                limit: dec!(150000),
+            }]
+        );
+
+        // A bonus over the limit loses the credit outright
+        let over = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(151000),
+            ..input
+        });
+        assert_eq!(over.tax_breakdown.federal.credits, dec!(0));
+        assert!(matches!(
+            over.warnings[0],
+            CalcWarning::CleanVehicleIncomeOverLimit { .. }
+        ));
+    }
+
+    #[test]
+    fn test_adoption_credit_before_clean_energy() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(90000),
+            state: USState::Texas,
+            adoption: AdoptionExpenses {
+                adoptions: vec![Adoption {
+                    qualified_expenses: dec!(30000),
+                    special_needs: false,
+                }],
+                carryforward: Decimal::ZERO,
+            },
+            energy_improvements: EnergyImprovements {
+                clean_energy: dec!(10000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let income_tax = engine
+            .calculate(&TaxCalculationInput {
+                adoption: AdoptionExpenses::default(),
+                energy_improvements: EnergyImprovements::default(),
+                ..input.clone()
+            })
+            .tax_breakdown
+            .federal
+            .tax;
+
+        // The capped adoption credit wipes out the tax; solar waits a year
+        let result = engine.calculate(&input);
+        assert_eq!(result.tax_breakdown.federal.tax, dec!(0));
+        assert_eq!(result.adoption_credit.allowed, dec!(16810));
+        assert_eq!(result.adoption_credit.credit, income_tax);
+        assert_eq!(
+            result.adoption_credit.carryforward,
+            dec!(16810) - income_tax
+        );
+        assert_eq!(result.energy_credits.clean_energy_credit, dec!(0));
+        assert_eq!(result.energy_credits.carryforward, dec!(3000));
+    }
+
+    #[test]
+    fn test_credits_for_dependents() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let result = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(30000),
+            state: USState::Texas,
+            filing_status: FilingStatus::HeadOfHousehold,
+            dependents: vec![Dependent::child(4), Dependent::child(10)],
+            ..Default::default()
+        });
+        let breakdown = &result.tax_breakdown;
+        let credits = &breakdown.credits;
+
+        // $810 of tax on $8,100 taxable absorbs part of the $4,000 credit;
+        // the rest is refundable, and the EITC phases out from $22,720
+        assert_eq!(credits.child_tax_credit, dec!(810));
+        assert_eq!(breakdown.federal.tax, dec!(0));
+        assert_eq!(credits.additional_child_tax_credit, dec!(3190));
+        assert_eq!(credits.earned_income_credit, dec!(5426.83));
+        assert_eq!(credits.refundable, dec!(8616.83));
+        assert_eq!(breakdown.total_taxes, dec!(2295) - dec!(8616.83));
+        assert_eq!(result.income.net, dec!(36321.83));
+
+        // Territory residents file no federal return to claim them
+        let territory = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(30000),
+            state: USState::PuertoRico,
+            filing_status: FilingStatus::HeadOfHousehold,
+            dependents: vec![Dependent::child(4)],
+            ..Default::default()
+        });
+        assert_eq!(territory.tax_breakdown.credits, TaxCredits::default());
+    }
+
+    #[test]
+    fn test_self_employment_income() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let wages_only = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::Texas,
+            ..Default::default()
+        });
+        let result = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::Texas,
+            self_employment_income: dec!(40000),
+            ..Default::default()
+        });
+        let breakdown = &result.tax_breakdown;
+        let se = &breakdown.self_employment;
+
+        // Wages leave $18,600 of the wage base; half of SE tax is deducted
+        assert_eq!(se.net_earnings, dec!(36940));
+        assert_eq!(se.total, dec!(2306.40) + dec!(1071.26));
+        assert_eq!(se.deductible_half, dec!(1688.83));
+        assert_eq!(result.adjusted_gross_income, dec!(188311.17));
+        assert_eq!(result.income.gross, dec!(190000));
+        assert_eq!(breakdown.fica.total, wages_only.tax_breakdown.fica.total);
+        // 20% of the profit less the deductible half comes off taxable income
+        assert_eq!(breakdown.federal.qbi_deduction, dec!(7662.234));
+        assert_eq!(
+            breakdown.federal.tax - wages_only.tax_breakdown.federal.tax,
+            (dec!(38311.17) - dec!(7662.234)) * dec!(0.24)
+        );
+        assert_eq!(
+            breakdown.total_taxes,
+            breakdown.federal.tax + breakdown.fica.total + se.total
+        );
+        assert!(breakdown.explanations.self_employment.is_some());
+        assert!(wages_only
+            .tax_breakdown
+            .explanations
+            .self_employment
+            .is_none());
+
+        let rounded = result.rounded_to_cents();
+        assert_eq!(
+            rounded.tax_breakdown.total_taxes,
+            rounded.tax_breakdown.federal.tax
+                + rounded.tax_breakdown.fica.total
+                + rounded.tax_breakdown.self_employment.total
+        );
+    }
+
+    #[test]
+    fn test_schedule_c_income() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let profit = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::Texas,
+            self_employment_income: dec!(40000),
+            ..Default::default()
+        });
+
+        // $50,000 less $5,150 of expenses, 5,000 miles at 67¢, and a
+        // 300-square-foot office at $5
+        let schedule = ScheduleC {
+            gross_receipts: dec!(50000),
+            expenses: dec!(5150),
+            home_office: Some(HomeOffice::Simplified {
+                square_feet: dec!(300),
+            }),
+            vehicle: Some(VehicleUse {
+                business_miles: dec!(5000),
+                total_miles: dec!(12000),
+                ..Default::default()
+            }),
+        };
+        let input = TaxCalculationInput::builder()
+            .gross_income(dec!(150000))
+            .state(USState::Texas)
+            .schedule_c(schedule.clone())
+            .build()
+            .unwrap();
+        let result = engine.calculate(&input);
+
+        assert_eq!(result.income.gross, dec!(190000));
+        assert_eq!(
+            result.tax_breakdown.self_employment,
+            profit.tax_breakdown.self_employment
+        );
+        assert_eq!(
+            result.tax_breakdown.total_taxes,
+            profit.tax_breakdown.total_taxes
+        );
+
+        let negative = TaxCalculationInput {
+            schedule_c: Some(ScheduleC {
+                expenses: dec!(-1),
+                ..schedule
+            }),
+            ..input
+        };
+        assert!(negative.validate().is_err());
+    }
+
+    #[test]
+    fn test_pass_through_income_qbi_deduction() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            state: USState::Texas,
+            qualified_business: QualifiedBusiness {
+                pass_through_income: dec!(100000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = engine.calculate(&input);
+        let federal = &result.tax_breakdown.federal;
+
+        // 20% of $100,000 is capped at 20% of the $85,400 taxable income;
+        // K-1 income owes no self-employment tax
+        assert_eq!(result.income.gross, dec!(100000));
+        assert_eq!(federal.qbi_deduction, dec!(17080));
+        assert_eq!(federal.taxable_income, dec!(68320));
+        assert_eq!(result.tax_breakdown.self_employment.total, dec!(0));
+
+        // A specified service business above the phase-in range gets none
+        let consulting = engine.calculate(&TaxCalculationInput {
+            qualified_business: QualifiedBusiness {
+                pass_through_income: dec!(300000),
+                specified_service: true,
+                ..Default::default()
+            },
+            ..input
+        });
+        assert_eq!(consulting.tax_breakdown.federal.qbi_deduction, dec!(0));
+    }
+
+    #[test]
+    fn test_savings_rate_and_fi_metrics() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let result = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::Texas,
+            traditional_401k: dec!(10000),
+            roth_401k: dec!(5000),
+            hsa_contribution: dec!(3000),
+            employer_hsa_contribution: dec!(1000),
+            ..Default::default()
+        });
+        let net = result.income.net;
+
+        // $19,000 through payroll and a $5,000 match
+        assert_eq!(result.payroll_savings.total(), dec!(19000));
+        assert_eq!(
+            result.savings_rate(dec!(5000)),
+            Rate::of(dec!(24000), net + dec!(24000))
+        );
+
+        let metrics = result.fi_metrics(&FiAssumptions {
+            annual_expenses: dec!(40000),
+            employer_match: dec!(5000),
+            ..Default::default()
+        });
+        assert_eq!(metrics.savings_capacity, net - dec!(40000));
+        assert_eq!(metrics.annual_savings, dec!(24000) + net - dec!(40000));
+        assert_eq!(
+            metrics.savings_rate,
+            Rate::of(metrics.annual_savings, net + dec!(24000))
+        );
+        assert_eq!(metrics.fi_number, dec!(1000000));
+        assert!(metrics.years_to_fi.is_some_and(|years| years < 20));
+
+        // Spending more than take-home pay saves only through payroll
+        let overspent = result.fi_metrics(&FiAssumptions {
+            annual_expenses: net + dec!(1000),
+            ..Default::default()
+        });
+        assert_eq!(overspent.savings_capacity, dec!(-1000));
+        assert_eq!(overspent.annual_savings, dec!(19000));
+    }
+
+    #[test]
+    fn test_rounded_to_cents_reconciles() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let result = engine
+            .calculate(&TaxCalculationInput {
+                gross_income: dec!(87654.32),
+                state: USState::NewYork,
+                pre_tax_deductions: dec!(1234.567),
+                post_tax_deductions: dec!(99.995),
+                ..Default::default()
+            })
+            .rounded_to_cents();
+
+        let breakdown = &result.tax_breakdown;
+        for amount in [
+            breakdown.federal.tax,
+            breakdown.state.income_tax,
+            breakdown.state.local_tax,
+            breakdown.state.sdi,
+            breakdown.fica.total,
+            result.income.net,
+            result.income.timeframes.monthly,
+        ] {
+            assert_eq!(amount, amount.round_dp(2));
+        }
+        assert_eq!(
+            breakdown.federal.tax + breakdown.state.total_tax + breakdown.fica.total,
+            breakdown.total_taxes
+        );
+        // $1,334.562 of combined deductions rounds to $1,334.56
+        assert_eq!(
+            result.income.gross - breakdown.total_taxes - dec!(1334.56),
+            result.income.net
+        );
+    }
+
+    #[test]
+    fn test_garnishment_before_voluntary_deductions() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let support = GarnishmentKind::Support {
+            supports_other_family: true,
+            arrears_over_12_weeks: false,
+        };
+        let base = TaxCalculationInput {
+            gross_income: dec!(60000),
+            state: USState::Texas,
+            traditional_401k: dec!(6000),
+            ..Default::default()
+        };
+        let garnished = TaxCalculationInput {
+            garnishments: vec![
+                Garnishment::per_paycheck(support, dec!(400), PayFrequency::BiWeekly),
+                Garnishment::new(GarnishmentKind::Creditor, dec!(50000)),
+            ],
+            ..base.clone()
+        };
+
+        let without = engine.calculate(&base);
+        let with = engine.calculate(&garnished);
+        let disposable = dec!(60000) - with.tax_breakdown.total_taxes;
+
+        // The 401(k) doesn't shrink disposable earnings; the creditor gets
+        // what support leaves under the 25% ceiling
+        let garnishments = &with.garnishments;
+        assert_eq!(garnishments.disposable_earnings, disposable);
+        assert_eq!(garnishments.withholdings[0].withheld, dec!(10400));
+        assert_eq!(garnishments.total, disposable * dec!(0.25));
+        assert_eq!(with.income.net, without.income.net - garnishments.total);
+        assert_eq!(
+            with.garnishments.timeframes.bi_weekly,
+            garnishments.total / dec!(26)
+        );
+
+        let rounded = with.rounded_to_cents();
+        assert_eq!(
+            rounded.income.net,
+            rounded.income.gross
+                - rounded.tax_breakdown.total_taxes
+                - rounded.garnishments.total
+                - dec!(6000)
+        );
+    }
+
+    #[test]
+    fn test_garnishment_excludes_spouse_wages_and_other_income() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let earner = TaxCalculationInput {
+            gross_income: dec!(60000),
+            filing_status: FilingStatus::MarriedFilingJointly,
+            state: USState::Texas,
+            garnishments: vec![Garnishment::new(GarnishmentKind::Creditor, dec!(50000))],
+            ..Default::default()
+        };
+        let joint = TaxCalculationInput {
+            gross_income: dec!(100000),
+            spouse_income: dec!(40000),
+            investment_income: InvestmentIncome {
+                miscellaneous: dec!(20000),
+                ..Default::default()
+            },
+            ..earner.clone()
+        };
+
+        let alone = engine.calculate(&earner).garnishments;
+        let result = engine.calculate(&joint);
+        let breakdown = &result.tax_breakdown;
+
+        // The earner's $60,000 bears their own FICA and 60/120 of the
+        // federal income tax; the spouse's wages aren't garnished
+        let disposable = dec!(60000) - dec!(4590) - breakdown.federal.tax / dec!(2);
+        assert_eq!(result.garnishments.disposable_earnings, disposable);
+        assert!(disposable < alone.disposable_earnings);
+        assert!(disposable > dec!(100000) - breakdown.total_taxes - dec!(40000));
+    }
+
+    #[test]
+    fn test_input_from_user_profile() {
+        use crate::models::deduction::{Deduction, DeductionFrequency};
+        use crate::models::household::{Household, PartnerProfile, SplitMethod};
+        use crate::models::income::IncomeInput;
+
+        let mut profile = UserProfile::new(
+            IncomeInput {
+                bonuses: dec!(10000),
+                ..IncomeInput::new(dec!(120000))
+            },
+            FilingStatus::MarriedFilingJointly,
+            USState::Maryland,
+        );
+        profile.location.residence_locality = Some("Baltimore City".to_string());
+        profile.retirement.traditional_401k = dec!(6000);
+        profile.deductions = vec![
+            Deduction::per_paycheck(DeductionType::Hsa, dec!(150), PayFrequency::BiWeekly),
+            Deduction::per_paycheck(
+                DeductionType::Traditional401k,
+                dec!(200),
+                PayFrequency::BiWeekly,
+            ),
+            Deduction::new(
+                DeductionType::HealthInsurance,
+                dec!(200),
+                DeductionFrequency::Monthly,
+                12,
+            ),
+            Deduction::new(
+                DeductionType::UnionDues,
+                dec!(50),
+                DeductionFrequency::Monthly,
+                12,
+            ),
+            Deduction::new(
+                DeductionType::Fsa,
+                dec!(100),
+                DeductionFrequency::Monthly,
+                12,
+            ),
+        ];
+        profile.household = Some(Household::new(
+            PartnerProfile::new("Alex".to_string(), dec!(70000), dec!(55000)),
+            SplitMethod::Equal,
+        ));
+
+        let input = TaxCalculationInput::from(&profile);
+
+        assert_eq!(input.gross_income, dec!(200000));
+        assert_eq!(input.spouse_income, dec!(70000));
+        assert_eq!(input.hsa_contribution, dec!(3900));
+        // A health FSA isn't dependent care
+        assert_eq!(input.health_fsa, dec!(1200));
+        assert_eq!(input.dependent_care_fsa, dec!(0));
+        assert_eq!(input.traditional_401k, dec!(11200));
+        assert_eq!(input.pre_tax_deductions, dec!(2400));
+        assert_eq!(input.post_tax_deductions, dec!(600));
+        assert_eq!(input.residence_locality.as_deref(), Some("Baltimore City"));
+
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let result = engine.calculate(&input);
+        assert_eq!(result.income.gross, dec!(200000));
+
+        // The FSA comes off FICA wages; past the wage base that's Medicare only
+        let taxed_for_fica = engine.calculate(&TaxCalculationInput {
+            health_fsa: Decimal::ZERO,
+            pre_tax_deductions: input.pre_tax_deductions + dec!(1200),
+            ..input.clone()
+        });
+        assert_eq!(
+            taxed_for_fica.tax_breakdown.fica.total - result.tax_breakdown.fica.total,
+            dec!(1200) * dec!(0.0145)
+        );
+    }
+
+    #[test]
+    fn test_bonus_range_bands() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(120000),
+            state: USState::California,
+            ..Default::default()
+        };
+        let bonus = ValueRange::new(dec!(0), dec!(15000), dec!(30000));
+        let bands = engine.calculate_bands(&input, &bonus);
+
+        assert_eq!(bands.pessimistic.income.gross, dec!(120000));
+        assert_eq!(bands.expected.income.gross, dec!(135000));
+        assert_eq!(bands.optimistic.income.gross, dec!(150000));
+        assert_eq!(
+            bands.expected.income.net,
+            engine
+                .calculate(&TaxCalculationInput {
+                    gross_income: dec!(135000),
+                    ..input.clone()
+                })
+                .income
+                .net
+        );
+        assert!(bands.net_spread() > dec!(0) && bands.net_spread() < dec!(30000));
+
+        let fixed = engine.calculate_bands(&input, &ValueRange::fixed(dec!(0)));
+        assert_eq!(fixed.net_spread(), dec!(0));
+        assert!(!ValueRange::new(dec!(10), dec!(5), dec!(20)).is_ordered());
+    }
+
+    #[test]
+    fn test_simulated_bands_reproducible() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(120000),
+            state: USState::California,
+            ..Default::default()
+        };
+        let bonus = ValueRange::new(dec!(0), dec!(15000), dec!(30000));
+        let config = SimulationConfig::new(42, 200);
+
+        let distribution = engine.simulate_bands(&input, &bonus, &config);
+        assert_eq!(distribution, engine.simulate_bands(&input, &bonus, &config));
+        assert_ne!(
+            distribution,
+            engine.simulate_bands(&input, &bonus, &SimulationConfig::new(43, 200))
+        );
+
+        let bands = engine.calculate_bands(&input, &bonus);
+        assert_eq!(distribution.iterations, 200);
+        assert!(bands.pessimistic.income.net <= distribution.p10);
+        assert!(distribution.p10 < distribution.median && distribution.median < distribution.p90);
+        assert!(distribution.p90 <= bands.optimistic.income.net);
+    }
+
+    #[test]
+    fn test_comparison_component_deltas() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let california = TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::California,
+            ..Default::default()
+        };
+        let texas = TaxCalculationInput {
+            state: USState::Texas,
+            ..california.clone()
+        };
+        let comparison = engine.compare_scenarios(&california, &texas);
+
+        // Moving to Texas only changes state-level lines
+        assert_eq!(comparison.federal.difference, dec!(0));
+        assert_eq!(comparison.federal.percent_change, Some(dec!(0)));
+        assert_eq!(comparison.fica.difference, dec!(0));
+        assert_eq!(comparison.state.scenario, dec!(0));
+        assert_eq!(comparison.state.percent_change, Some(dec!(-100)));
+        assert_eq!(
+            comparison.sdi.difference,
+            -comparison.base.tax_breakdown.state.sdi
+        );
+        assert_eq!(
+            comparison.net_difference,
+            -(comparison.state.difference + comparison.sdi.difference)
+        );
+        assert!(comparison.effective_rate.difference < dec!(0));
+
+        // No percent change from a zero base
+        let back = engine.compare_scenarios(&texas, &california);
+        assert_eq!(back.state.percent_change, None);
+    }
+
+    #[test]
+    fn test_unpaid_leave_schedule() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(104000),
+            state: USState::Texas,
+            work_schedule: WorkSchedule {
+                unpaid_leave_weeks: dec!(12),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let on_leave = engine.calculate(&input);
+        let reduced = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(80000),
+            work_schedule: WorkSchedule {
+                weeks_per_year: dec!(40),
+                ..Default::default()
+            },
+            ..input.clone()
+        });
+
+        // 40 of 52 weeks paid, and weekly pay reflects weeks actually worked
+        assert_eq!(on_leave.income.gross, dec!(80000));
+        assert_eq!(on_leave.income.net, reduced.income.net);
+        assert_eq!(
+            on_leave.income.timeframes.weekly,
+            on_leave.income.net / dec!(40)
+        );
+
+        let invalid = TaxCalculationInput::builder()
+            .gross_income(dec!(50000))
+            .work_schedule(WorkSchedule {
+                unpaid_leave_weeks: dec!(60),
+                ..Default::default()
+            })
+            .build();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_line_item_explanations() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let input = TaxCalculationInput {
+            gross_income: dec!(250000),
+            state: USState::California,
+            ..Default::default()
+        };
+        let explanations = engine.calculate(&input).tax_breakdown.explanations;
+
+        assert_eq!(
+            explanations.social_security.as_deref(),
+            Some("Social Security: 6.2% of $168600 wage base (income exceeds cap)")
+        );
+        assert_eq!(
+            explanations.additional_medicare.as_deref(),
+            Some("Additional Medicare: 0.9% of $50000 wages above $200000")
+        );
+        assert!(explanations
+            .federal
+            .as_deref()
+            .is_some_and(|line| line.ends_with("top bracket 32%")));
+        assert!(explanations.sdi.is_some());
+        // No work state or local tax, so no line for them
+        assert_eq!(explanations.work_state, None);
+        assert_eq!(explanations.local_tax, None);
+        assert_eq!(explanations.lines().len(), 6);
+
+        let texas = TaxCalculationInput {
+            gross_income: dec!(60000),
+            state: USState::Texas,
+            ..Default::default()
+        };
+        let explanations = engine.calculate(&texas).tax_breakdown.explanations;
+        assert_eq!(
+            explanations.state_income_tax.as_deref(),
+            Some("Texas has no state income tax")
+        );
+        assert_eq!(
+            explanations.social_security.as_deref(),
+            Some("Social Security: 6.2% of $60000 wages")
+        );
+    }
+
+    #[test]
+    fn test_line_item_confidence() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let exact = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::California,
+            ..Default::default()
+        });
+        assert!(!exact.tax_breakdown.confidence.has_estimates());
+
+        let estimated = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::NewYork,
+            ..Default::default()
+        });
+        let confidence = &estimated.tax_breakdown.confidence;
+        assert_eq!(confidence.local_tax, Confidence::Estimated);
+        assert_eq!(confidence.state_income_tax, Confidence::Exact);
+        assert_eq!(confidence.federal, Confidence::Exact);
+
+        // A known locality makes the local line exact
+        let located = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::NewYork,
+            residence_locality: Some("New York City".to_string()),
+            ..Default::default()
+        });
+        assert!(!located.tax_breakdown.confidence.has_estimates());
+    }
+
+    #[test]
+    fn test_no_state_residence() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+
+        let result = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(90000),
+            state: USState::NoState,
+            ..Default::default()
+        });
+
+        assert_eq!(result.tax_breakdown.state.total_tax, dec!(0));
+        assert_eq!(
+            result.tax_breakdown.total_taxes,
+            result.tax_breakdown.federal.tax + result.tax_breakdown.fica.total
+        );
+    }
+
+    #[test]
+    fn test_expanded_salt_cap_in_2025() {
+        let data = setup();
+        let input = TaxCalculationInput {
+            gross_income: dec!(150000),
+            state: USState::Texas,
+            itemized_deductions: ItemizedDeductions {
+                state_and_local_taxes: dec!(20000),
+                mortgage_interest: dec!(3000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // $10,000 cap + $3,000 is under the $14,600 standard deduction
+        let federal_2024 = TaxCalculationEngine::new(&data, 2024)
+            .calculate(&input)
+            .tax_breakdown
+            .federal;
+        assert!(!federal_2024.itemized);
+
+        // Under the $40,000 cap all $20,000 counts, beating $15,750
+        let federal_2025 = TaxCalculationEngine::new(&data, 2025)
+            .calculate(&input)
+            .tax_breakdown
+            .federal;
+        assert!(federal_2025.itemized);
+        assert_eq!(federal_2025.deduction, dec!(23000));
+    }
+
+    #[test]
+    fn test_standard_or_itemized_deduction() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let input = TaxCalculationInput {
+            gross_income: dec!(100000),
+            state: USState::Texas,
+            itemized_deductions: ItemizedDeductions {
+                state_and_local_taxes: dec!(15000),
+                mortgage_interest: dec!(8000),
+                charitable: dec!(2000),
+                medical: dec!(9000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // $10,000 SALT cap + $8,000 + $2,000 + $1,500 of medical over 7.5% of AGI
+        let federal = engine.calculate(&input).tax_breakdown.federal;
+        assert!(federal.itemized);
+        assert_eq!(federal.deduction, dec!(21500));
+        assert_eq!(federal.taxable_income, dec!(78500));
+
+        // Property taxes given for state credits count toward SALT too
+        let with_property_taxes = engine
+            .calculate(&TaxCalculationInput {
+                property_taxes: dec!(4000),
+                itemized_deductions: ItemizedDeductions {
+                    state_and_local_taxes: dec!(4000),
+                    ..input.itemized_deductions.clone()
+                },
+                ..input.clone()
+            })
+            .tax_breakdown
+            .federal;
+        assert_eq!(with_property_taxes.deduction, dec!(19500));
+
+        let standard = engine
+            .calculate(&TaxCalculationInput {
+                itemized_deductions: ItemizedDeductions {
+                    mortgage_interest: dec!(5000),
+                    ..Default::default()
+                },
+                ..input
+            })
+            .tax_breakdown;
+        assert!(!standard.federal.itemized);
+        assert_eq!(standard.federal.deduction, dec!(14600));
+        assert!(standard
+            .explanations
+            .federal
+            .is_some_and(|line| line.contains("after the $14600 standard deduction")));
+    }
+
+    #[test]
+    fn test_gambling_losses_capped_at_winnings() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let wages = TaxCalculationInput {
+            gross_income: dec!(90000),
+            state: USState::Illinois,
+            itemized_deductions: ItemizedDeductions {
+                state_and_local_taxes: dec!(9000),
+                mortgage_interest: dec!(12000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let base = engine.calculate(&wages);
+
+        // $25,000 of losses against a $20,000 win only deducts $20,000
+        let result = engine.calculate(&TaxCalculationInput {
+            investment_income: InvestmentIncome {
+                gambling_winnings: dec!(20000),
+                ..Default::default()
+            },
+            itemized_deductions: ItemizedDeductions {
+                gambling_losses: dec!(25000),
+                ..wages.itemized_deductions.clone()
+            },
+            ..wages.clone()
+        });
+        assert_eq!(
+            result.adjusted_gross_income,
+            base.adjusted_gross_income + dec!(20000)
+        );
+        assert_eq!(
+            result.tax_breakdown.federal.taxable_income,
+            base.tax_breakdown.federal.taxable_income
+        );
+        assert_eq!(
+            result.tax_breakdown.fica.total,
+            base.tax_breakdown.fica.total
+        );
+        // Illinois taxes the winnings with no loss deduction
+        assert!(result.tax_breakdown.state.income_tax > base.tax_breakdown.state.income_tax);
+
+        // Taking the standard deduction forfeits the losses
+        let standard = engine.calculate(&TaxCalculationInput {
+            investment_income: InvestmentIncome {
+                gambling_winnings: dec!(20000),
+                ..Default::default()
+            },
+            itemized_deductions: ItemizedDeductions {
+                gambling_losses: dec!(5000),
+                ..Default::default()
+            },
+            ..wages
+        });
+        assert_eq!(
+            standard.tax_breakdown.federal.taxable_income,
+            dec!(110000) - dec!(14600)
+        );
+    }
+
+    #[test]
+    fn test_crypto_and_miscellaneous_income() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let wages = TaxCalculationInput {
+            gross_income: dec!(80000),
+            state: USState::Colorado,
+            capital_gains: CapitalGains {
+                long_term: dec!(4000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let base = engine.calculate(&wages);
+
+        let result = engine.calculate(&TaxCalculationInput {
+            investment_income: InvestmentIncome {
+                crypto_short_term: dec!(-6000),
+                staking_rewards: dec!(1500),
+                miscellaneous: dec!(500),
+                ..Default::default()
+            },
+            ..wages.clone()
+        });
+
+        // The crypto loss wipes out the stock gain and deducts $2,000 more;
+        // rewards and 1099-MISC income add $2,000 back, with no FICA
+        assert_eq!(
+            result.adjusted_gross_income,
+            base.adjusted_gross_income - dec!(4000)
+        );
+        assert_eq!(result.income.gross, base.income.gross - dec!(2000));
+        assert_eq!(
+            result.tax_breakdown.fica.total,
+            base.tax_breakdown.fica.total
+        );
+        assert!(result.tax_breakdown.state.income_tax < base.tax_breakdown.state.income_tax);
+        assert_eq!(result.capital_loss_carryover.total(), dec!(0));
+
+        let negative = TaxCalculationInput {
+            investment_income: InvestmentIncome {
+                staking_rewards: dec!(-1),
+                ..Default::default()
+            },
+            ..wages
+        };
+        assert!(negative.validate().is_err());
+    }
+
+    #[test]
+    fn test_long_term_gains_and_qualified_dividends() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let result = engine.calculate(&TaxCalculationInput {
+            gross_income: dec!(50000),
+            state: USState::Texas,
+            capital_gains: CapitalGains {
+                short_term: dec!(5000),
+                long_term: dec!(20000),
+                ..Default::default()
+            },
+            investment_income: InvestmentIncome {
+                qualified_dividends: dec!(5000),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let federal = &result.tax_breakdown.federal;
+
+        // $80,000 of income less the $14,600 deduction; the $25,000 of
+        // preferential income stacks on $40,400 of ordinary income, with
+        // $6,625 reaching the 0% threshold of $47,025
+        assert_eq!(result.adjusted_gross_income, dec!(80000));
+        assert_eq!(federal.taxable_income, dec!(65400));
+        assert_eq!(federal.preferential_tax, dec!(18375) * dec!(0.15));
+        assert_eq!(
+            federal.ordinary_tax,
+            engine
+                .federal_calc
+                .calculate(dec!(40400), FilingStatus::Single, 2024)
+                .tax
+        );
+        assert_eq!(federal.tax, federal.ordinary_tax + federal.preferential_tax);
+        // Investment income isn't wages, so no FICA
+        assert_eq!(result.tax_breakdown.fica.total, dec!(50000) * dec!(0.0765));
+    }
+
     #[test]
     fn test_zero_income() {
         let data = setup();
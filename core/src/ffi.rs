@@ -4,15 +4,49 @@
 #![allow(clippy::too_many_arguments)]
 
 use rust_decimal::Decimal;
+use std::sync::{Arc, Mutex};
 
-use crate::data::embedded::get_embedded_data;
+use crate::analysis::bracket_chart::{
+    BracketChart, BracketChartAnalyzer, BracketSeries, CurvePoint,
+};
+use crate::analysis::comp_time::{CompTimeAnalyzer, CompTimeComparison};
+use crate::analysis::contract_rate::{
+    ContractAssumptions, ContractRateConverter, ContractRateEquivalent,
+};
+use crate::analysis::employment_type::{EmploymentTypeAnalyzer, EmploymentTypeComparison};
+use crate::analysis::extra_hours::{ExtraHourAnalyzer, ExtraHourValue};
+use crate::analysis::offers::{
+    CompensationPackage, EmployerBenefits, OfferComparison, OfferEvaluator,
+};
+use crate::analysis::pay_calendar::{DateShift, PayCalendar, PayCalendarProjector, PaySchedule};
+use crate::analysis::pay_spread::{PaySpreadAnalyzer, PaySpreadComparison, PaySpreadOption};
+use crate::analysis::paystub::{Paystub, PaystubVerification, PaystubVerifier};
+use crate::analysis::purchase_cost::{PurchaseCost, PurchaseCostConverter};
+use crate::analysis::side_income::{SideIncome, SideIncomeAnalysis, SideIncomeAnalyzer};
+use crate::calculators::withholding::{
+    FederalWithholdingCalculator, PaycheckWithholding, RegularPay, WithholdingSimulation,
+};
+use crate::data::changelog::{diff_years, TaxDataChangelog};
+use crate::data::embedded::{get_embedded_data, EmbeddedTaxData, LATEST_YEAR};
+use crate::data::indexed::InflationIndexedData;
+use crate::data::TaxDataProvider;
 use crate::engine::{
-    ScenarioComparison, TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult,
+    CalculationError, ResultBands, ScenarioComparison, TakeHomeDistribution, TaxCalculationEngine,
+    TaxCalculationInput, TaxCalculationResult, ValueRange,
 };
-use crate::models::household::{calculate_split, HouseholdSplit, SplitMethod};
-use crate::models::income::TimeframeIncome;
+use crate::models::dependent::Dependent;
+use crate::models::household::{
+    calculate_itemized_split, calculate_split, HouseholdSplit, ItemizedHouseholdSplit,
+    SharedExpense, SplitMethod,
+};
+use crate::models::income::{PayFrequency, TimeframeIncome};
+use crate::models::locale::{Locale, LocalizedName};
+use crate::models::money::Money;
+use crate::models::profile::W4Settings;
+use crate::models::simulation::{SimulationConfig, MAX_ITERATIONS};
 use crate::models::state::USState;
 use crate::models::tax::FilingStatus;
+use crate::storage::{SavedScenario, ScenarioStore, StorageError};
 
 // ============================================================================
 // Error Type
@@ -27,10 +61,36 @@ pub enum TaxCalcError {
     InvalidFilingStatus { message: String },
     #[error("Invalid state code: {message}")]
     InvalidState { message: String },
+    #[error("Invalid input: {message}")]
+    InvalidInput { message: String },
     #[error("Calculation error: {message}")]
     CalculationError { message: String },
+    #[error("Storage error: {message}")]
+    StorageError { message: String },
+}
+
+impl From<StorageError> for TaxCalcError {
+    fn from(e: StorageError) -> Self {
+        TaxCalcError::StorageError {
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<CalculationError> for TaxCalcError {
+    fn from(e: CalculationError) -> Self {
+        match e {
+            CalculationError::InvalidInput { message } => TaxCalcError::InvalidInput { message },
+            _ => TaxCalcError::CalculationError {
+                message: e.to_string(),
+            },
+        }
+    }
 }
 
+/// Tax year the free functions calculate for; `TaxEngine` takes its own
+const TAX_YEAR: u32 = 2024;
+
 // ============================================================================
 // Public FFI Functions
 // ============================================================================
@@ -44,7 +104,7 @@ pub fn get_version() -> String {
 /// Get current tax year
 #[uniffi::export]
 pub fn get_tax_year() -> u32 {
-    2024
+    TAX_YEAR
 }
 
 /// Calculate taxes with full breakdown
@@ -69,12 +129,97 @@ pub fn calculate_taxes(
     )?;
 
     let data = get_embedded_data();
-    let engine = TaxCalculationEngine::new(data, 2024);
+    let engine = TaxCalculationEngine::new(data, TAX_YEAR);
     let result = engine.calculate(&input);
 
     Ok(TaxResultFFI::from(result))
 }
 
+/// Calculate pessimistic, expected, and optimistic results for a variable
+/// amount (bonus, commission) on top of base income
+#[uniffi::export]
+pub fn calculate_taxes_banded(
+    base_income: String,
+    variable_low: String,
+    variable_expected: String,
+    variable_high: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    post_tax_deductions: String,
+    traditional_401k: String,
+    roth_401k: String,
+) -> Result<ResultBandsFFI, TaxCalcError> {
+    let input = parse_input(
+        &base_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        &post_tax_deductions,
+        &traditional_401k,
+        &roth_401k,
+    )?;
+    let range = ValueRange::new(
+        parse_money(&variable_low)?,
+        parse_money(&variable_expected)?,
+        parse_money(&variable_high)?,
+    );
+    if !range.is_ordered() {
+        return Err(TaxCalcError::InvalidInput {
+            message: "variable income range must be low <= expected <= high".to_string(),
+        });
+    }
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(ResultBandsFFI::from(engine.calculate_bands(&input, &range)))
+}
+
+/// Take-home spread over sampled outcomes of a variable income amount;
+/// the same seed gives the same result on every platform
+#[uniffi::export]
+pub fn simulate_taxes_banded(
+    base_income: String,
+    variable_low: String,
+    variable_expected: String,
+    variable_high: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    traditional_401k: String,
+    simulation: SimulationConfigFFI,
+) -> Result<TakeHomeDistributionFFI, TaxCalcError> {
+    let input = parse_input(
+        &base_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        "0",
+        &traditional_401k,
+        "0",
+    )?;
+    let range = ValueRange::new(
+        parse_money(&variable_low)?,
+        parse_money(&variable_expected)?,
+        parse_money(&variable_high)?,
+    );
+    if !range.is_ordered() {
+        return Err(TaxCalcError::InvalidInput {
+            message: "variable income range must be low <= expected <= high".to_string(),
+        });
+    }
+    if simulation.iterations == 0 || simulation.iterations > MAX_ITERATIONS {
+        return Err(TaxCalcError::InvalidInput {
+            message: format!("iterations must be between 1 and {MAX_ITERATIONS}"),
+        });
+    }
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let config = SimulationConfig::new(simulation.seed, simulation.iterations);
+    Ok(TakeHomeDistributionFFI::from(
+        engine.simulate_bands(&input, &range, &config),
+    ))
+}
+
 /// Compare two scenarios
 #[uniffi::export]
 pub fn compare_scenarios(
@@ -116,7 +261,7 @@ pub fn compare_scenarios(
     )?;
 
     let data = get_embedded_data();
-    let engine = TaxCalculationEngine::new(data, 2024);
+    let engine = TaxCalculationEngine::new(data, TAX_YEAR);
     let comparison = engine.compare_scenarios(&base, &scenario);
 
     Ok(ScenarioComparisonFFI::from(comparison))
@@ -125,11 +270,465 @@ pub fn compare_scenarios(
 /// Convert annual amount to all timeframes
 #[uniffi::export]
 pub fn convert_timeframes(annual: String) -> Result<TimeframeFFI, TaxCalcError> {
-    let amount = parse_decimal(&annual)?;
+    let amount = parse_money(&annual)?;
     let timeframes = TimeframeIncome::from_annual(amount);
     Ok(TimeframeFFI::from(timeframes))
 }
 
+/// Split an annual amount into per-paycheck pay
+#[uniffi::export]
+pub fn per_paycheck_amount(annual: String, pay_frequency: String) -> Result<String, TaxCalcError> {
+    let amount = parse_money(&annual)?;
+    let frequency =
+        pay_frequency
+            .parse::<PayFrequency>()
+            .map_err(|e| TaxCalcError::InvalidInput {
+                message: e.to_string(),
+            })?;
+    Ok(format_money(frequency.per_period(amount)))
+}
+
+/// Hourly 1099 contract rate equivalent to a W-2 salary
+#[uniffi::export]
+pub fn salary_to_contract_rate(
+    salary: String,
+    filing_status: String,
+    state_code: String,
+    hours_per_week: String,
+    pto_days: String,
+    utilization: String,
+    benefits_value: String,
+    business_expenses: String,
+) -> Result<ContractRateFFI, TaxCalcError> {
+    let salary = parse_money(&salary)?;
+    let filing_status = parse_filing_status(&filing_status)?;
+    let state = parse_state(&state_code)?;
+    let assumptions = parse_contract_assumptions(
+        &hours_per_week,
+        &pto_days,
+        &utilization,
+        &benefits_value,
+        &business_expenses,
+    )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let result = ContractRateConverter::new(&engine).hourly_rate_for_salary(
+        salary,
+        filing_status,
+        state,
+        &assumptions,
+    );
+    Ok(ContractRateFFI::from(result))
+}
+
+/// W-2 salary equivalent to an hourly 1099 contract rate
+#[uniffi::export]
+pub fn contract_rate_to_salary(
+    hourly_rate: String,
+    filing_status: String,
+    state_code: String,
+    hours_per_week: String,
+    pto_days: String,
+    utilization: String,
+    benefits_value: String,
+    business_expenses: String,
+) -> Result<ContractRateFFI, TaxCalcError> {
+    let hourly_rate = parse_money(&hourly_rate)?;
+    let filing_status = parse_filing_status(&filing_status)?;
+    let state = parse_state(&state_code)?;
+    let assumptions = parse_contract_assumptions(
+        &hours_per_week,
+        &pto_days,
+        &utilization,
+        &benefits_value,
+        &business_expenses,
+    )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let result = ContractRateConverter::new(&engine).salary_for_hourly_rate(
+        hourly_rate,
+        filing_status,
+        state,
+        &assumptions,
+    );
+    Ok(ContractRateFFI::from(result))
+}
+
+/// W-2 salary compared with 1099 contracting at an hourly rate, with the
+/// hourly rate at which contracting breaks even
+#[uniffi::export]
+pub fn compare_employment_types(
+    w2_salary: String,
+    contractor_rate: String,
+    filing_status: String,
+    state_code: String,
+    hours_per_week: String,
+    pto_days: String,
+    utilization: String,
+    benefits_value: String,
+    business_expenses: String,
+) -> Result<EmploymentTypeFFI, TaxCalcError> {
+    let input = parse_input(&w2_salary, &filing_status, &state_code, "0", "0", "0", "0")?;
+    let contractor_rate = parse_money(&contractor_rate)?;
+    let assumptions = parse_contract_assumptions(
+        &hours_per_week,
+        &pto_days,
+        &utilization,
+        &benefits_value,
+        &business_expenses,
+    )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(EmploymentTypeFFI::from(
+        EmploymentTypeAnalyzer::new(&engine).compare(&input, contractor_rate, &assumptions),
+    ))
+}
+
+/// Take-home pay from one more regular hour and one more overtime hour
+#[uniffi::export]
+pub fn get_extra_hour_value(
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    traditional_401k: String,
+) -> Result<ExtraHourFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        "0",
+        &traditional_401k,
+        "0",
+    )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let analyzer = ExtraHourAnalyzer::new(&engine);
+    Ok(ExtraHourFFI::new(
+        analyzer.regular_hour(&input),
+        analyzer.overtime_hour(&input),
+    ))
+}
+
+/// Overtime hours taken as time-and-a-half pay compared with comp time
+#[uniffi::export]
+pub fn compare_overtime_comp_time(
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    traditional_401k: String,
+    overtime_hours: String,
+) -> Result<CompTimeFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        "0",
+        &traditional_401k,
+        "0",
+    )?;
+    let overtime_hours = parse_money(&overtime_hours)?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(CompTimeFFI::from(
+        CompTimeAnalyzer::new(&engine).compare(&input, overtime_hours),
+    ))
+}
+
+/// After-tax value of side income on top of a primary job
+///
+/// `side_kind` is "w2" for a part-time job paid on `pay_frequency`, or
+/// "1099" for contract work, with `side_income` its net profit.
+#[uniffi::export]
+pub fn analyze_side_income(
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    side_income: String,
+    side_kind: String,
+    pay_frequency: String,
+) -> Result<SideIncomeFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        "0",
+        "0",
+        "0",
+        "0",
+    )?;
+    let amount = parse_money(&side_income)?;
+    let side =
+        match side_kind.to_ascii_lowercase().as_str() {
+            "w2" => SideIncome::W2 {
+                wages: amount,
+                pay_frequency: pay_frequency.parse().map_err(
+                    |e: crate::models::ParseEnumError| TaxCalcError::InvalidInput {
+                        message: e.to_string(),
+                    },
+                )?,
+            },
+            "1099" => SideIncome::Contract { net_profit: amount },
+            _ => {
+                return Err(TaxCalcError::InvalidInput {
+                    message: format!("invalid side income kind: {side_kind}"),
+                })
+            },
+        };
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(SideIncomeFFI::from(
+        SideIncomeAnalyzer::new(&engine).analyze(&input, &side),
+    ))
+}
+
+/// Each regular paycheck of the year under the given W-4 settings
+///
+/// `filing_status` is the W-4's Step 1(c) status. `retirement_deferrals`
+/// come off federal wages only; `cafeteria_plan` deductions come off
+/// federal and FICA wages.
+#[uniffi::export]
+pub fn simulate_paychecks(
+    gross_per_paycheck: String,
+    pay_frequency: String,
+    filing_status: String,
+    multiple_jobs: bool,
+    dependents_credit: String,
+    extra_withholding: String,
+    retirement_deferrals: String,
+    cafeteria_plan: String,
+) -> Result<PaycheckSimulationFFI, TaxCalcError> {
+    let pay = RegularPay {
+        gross: parse_money(&gross_per_paycheck)?,
+        frequency: pay_frequency
+            .parse()
+            .map_err(
+                |e: crate::models::ParseEnumError| TaxCalcError::InvalidInput {
+                    message: e.to_string(),
+                },
+            )?,
+        retirement_deferrals: parse_money(&retirement_deferrals)?,
+        cafeteria_plan: parse_money(&cafeteria_plan)?,
+    };
+    let filing_status = parse_filing_status(&filing_status)?;
+    let w4 = W4Settings {
+        multiple_jobs,
+        dependents_credit: parse_money(&dependents_credit)?,
+        extra_withholding: parse_money(&extra_withholding)?,
+        ..Default::default()
+    };
+    if [
+        pay.gross,
+        pay.retirement_deferrals,
+        pay.cafeteria_plan,
+        w4.dependents_credit,
+        w4.extra_withholding,
+    ]
+    .iter()
+    .any(|amount| *amount < Decimal::ZERO)
+    {
+        return Err(TaxCalcError::InvalidInput {
+            message: "paycheck and W-4 amounts cannot be negative".to_string(),
+        });
+    }
+
+    let simulation = FederalWithholdingCalculator::new(get_embedded_data()).simulate_year(
+        &pay,
+        &w4,
+        filing_status,
+        TAX_YEAR,
+    );
+    Ok(PaycheckSimulationFFI::from(simulation))
+}
+
+/// A salary earned over `work_months` paid in those months only or spread
+/// over 12, with each election's paychecks and withholding
+#[uniffi::export]
+pub fn compare_pay_spread(
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    traditional_401k: String,
+    work_months: u32,
+    pay_frequency: String,
+) -> Result<PaySpreadFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        "0",
+        &traditional_401k,
+        "0",
+    )?;
+    if !(1..=12).contains(&work_months) {
+        return Err(TaxCalcError::InvalidInput {
+            message: "work_months must be between 1 and 12".to_string(),
+        });
+    }
+    let pay_frequency = pay_frequency
+        .parse()
+        .map_err(
+            |e: crate::models::ParseEnumError| TaxCalcError::InvalidInput {
+                message: e.to_string(),
+            },
+        )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(PaySpreadFFI::from(PaySpreadAnalyzer::new(&engine).compare(
+        &input,
+        work_months,
+        pay_frequency,
+    )))
+}
+
+/// Pre-tax earnings and hours of work it takes to pay `price` out of
+/// take-home pay
+#[uniffi::export]
+pub fn get_purchase_cost(
+    price: String,
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    traditional_401k: String,
+) -> Result<PurchaseCostFFI, TaxCalcError> {
+    let price = parse_money(&price)?;
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        "0",
+        &traditional_401k,
+        "0",
+    )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(PurchaseCostFFI::from(
+        PurchaseCostConverter::new(&engine).cost(&input, price),
+    ))
+}
+
+/// Evaluate and rank compensation packages after tax in one call
+#[uniffi::export]
+pub fn compare_offers(
+    packages: Vec<CompensationPackageFFI>,
+) -> Result<Vec<OfferEvaluationFFI>, TaxCalcError> {
+    let packages = packages
+        .iter()
+        .map(parse_package)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let comparison = OfferEvaluator::new(&engine).compare(&packages);
+    Ok(OfferEvaluationFFI::from_comparison(&comparison))
+}
+
+/// Check a paystub's lines against the calculation for the same income,
+/// reporting lines off by more than `tolerance` per paycheck
+#[uniffi::export]
+pub fn verify_paystub(
+    paystub: PaystubFFI,
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    post_tax_deductions: String,
+    traditional_401k: String,
+    roth_401k: String,
+    tolerance: String,
+) -> Result<PaystubVerificationFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        &post_tax_deductions,
+        &traditional_401k,
+        &roth_401k,
+    )?;
+    let paystub = parse_paystub(&paystub)?;
+    let tolerance = parse_money(&tolerance)?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let verification = PaystubVerifier::new(&engine).verify(&paystub, &input, tolerance);
+    Ok(PaystubVerificationFFI::from(verification))
+}
+
+/// A year of paydays from `first_pay_date` (YYYY-MM-DD), shifted off
+/// weekends and bank holidays, with each paycheck's gross and net
+#[uniffi::export]
+pub fn get_pay_calendar(
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pay_frequency: String,
+    first_pay_date: String,
+    date_shift: String,
+) -> Result<PayScheduleFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        "0",
+        "0",
+        "0",
+        "0",
+    )?;
+    let invalid = |e: crate::models::ParseEnumError| TaxCalcError::InvalidInput {
+        message: e.to_string(),
+    };
+    let frequency: PayFrequency = pay_frequency.parse().map_err(invalid)?;
+    let shift: DateShift = date_shift.parse().map_err(invalid)?;
+    let first_pay_date =
+        chrono::NaiveDate::parse_from_str(&first_pay_date, "%Y-%m-%d").map_err(|_| {
+            TaxCalcError::InvalidInput {
+                message: format!("invalid date: {first_pay_date}"),
+            }
+        })?;
+
+    let calendar = PayCalendar::new(frequency, first_pay_date).with_shift(shift);
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    let schedule = PayCalendarProjector::new(&engine).project(&input, &calendar);
+    Ok(PayScheduleFFI::from(schedule))
+}
+
+/// Bracket bands, tax curves, and the user's position for charts
+#[uniffi::export]
+pub fn get_bracket_chart(
+    gross_income: String,
+    filing_status: String,
+    state_code: String,
+    pre_tax_deductions: String,
+    traditional_401k: String,
+) -> Result<BracketChartFFI, TaxCalcError> {
+    let input = parse_input(
+        &gross_income,
+        &filing_status,
+        &state_code,
+        &pre_tax_deductions,
+        "0",
+        &traditional_401k,
+        "0",
+    )?;
+
+    let engine = TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR);
+    Ok(BracketChartFFI::from(
+        BracketChartAnalyzer::new(&engine).chart(&input),
+    ))
+}
+
+/// Differences in the embedded tax data between two years
+#[uniffi::export]
+pub fn get_tax_data_changelog(from_year: u32, to_year: u32) -> TaxDataChangelogFFI {
+    TaxDataChangelogFFI::from(diff_years(get_embedded_data(), from_year, to_year))
+}
+
 /// Calculate household expense split
 #[uniffi::export]
 pub fn calculate_household_split(
@@ -138,122 +737,1153 @@ pub fn calculate_household_split(
     shared_expense: String,
     split_method: String,
 ) -> Result<HouseholdSplitFFI, TaxCalcError> {
-    let primary = parse_decimal(&primary_net)?;
-    let partner = parse_decimal(&partner_net)?;
-    let expense = parse_decimal(&shared_expense)?;
+    let primary = parse_money(&primary_net)?;
+    let partner = parse_money(&partner_net)?;
+    let expense = parse_money(&shared_expense)?;
+    let method = parse_split_method(&split_method)?;
 
-    let method = match split_method.as_str() {
-        "proportional" => SplitMethod::Proportional,
-        "equal" => SplitMethod::Equal,
-        s if s.starts_with("custom:") => {
-            let pct = parse_decimal(&s[7..])?;
-            SplitMethod::Custom(pct)
-        },
-        _ => SplitMethod::Proportional,
-    };
+    let split = calculate_split(primary, partner, expense, method);
+    Ok(HouseholdSplitFFI::from(split))
+}
+
+/// Split several shared expenses, each by its own method
+#[uniffi::export]
+pub fn calculate_itemized_household_split(
+    primary_net: String,
+    partner_net: String,
+    expenses: Vec<SharedExpenseFFI>,
+) -> Result<ItemizedHouseholdSplitFFI, TaxCalcError> {
+    let primary = parse_money(&primary_net)?;
+    let partner = parse_money(&partner_net)?;
+    let expenses = expenses
+        .iter()
+        .map(|e| {
+            Ok(SharedExpense {
+                category: e.category.parse().map_err(|_| TaxCalcError::InvalidInput {
+                    message: format!("unknown expense category: {}", e.category),
+                })?,
+                amount: parse_money(&e.amount)?,
+                split_method: parse_split_method(&e.split_method)?,
+            })
+        })
+        .collect::<Result<Vec<_>, TaxCalcError>>()?;
+
+    let split = calculate_itemized_split(primary, partner, &expenses);
+    Ok(ItemizedHouseholdSplitFFI::from(split))
+}
+
+/// Get list of all state codes
+#[uniffi::export]
+pub fn get_all_state_codes() -> Vec<String> {
+    USState::all()
+        .iter()
+        .chain(USState::territories())
+        .chain(std::iter::once(&USState::NoState))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Get list of all filing statuses
+#[uniffi::export]
+pub fn get_all_filing_statuses() -> Vec<String> {
+    [
+        FilingStatus::Single,
+        FilingStatus::MarriedFilingJointly,
+        FilingStatus::MarriedFilingSeparately,
+        FilingStatus::HeadOfHousehold,
+        FilingStatus::QualifyingWidower,
+    ]
+    .iter()
+    .map(|status| status.to_string())
+    .collect()
+}
+
+/// Language codes with translated display names
+#[uniffi::export]
+pub fn get_supported_locales() -> Vec<String> {
+    Locale::all()
+        .iter()
+        .map(|locale| locale.to_string())
+        .collect()
+}
+
+/// State or territory name for a device locale tag ("es-MX"), in English
+/// when the language isn't supported
+#[uniffi::export]
+pub fn get_state_display_name(state_code: String, locale: String) -> Result<String, TaxCalcError> {
+    let state = parse_state(&state_code)?;
+    Ok(state.localized_name(Locale::from_tag(&locale)).to_string())
+}
+
+/// Filing status name for a device locale tag
+#[uniffi::export]
+pub fn get_filing_status_display_name(
+    filing_status: String,
+    locale: String,
+) -> Result<String, TaxCalcError> {
+    let status = parse_filing_status(&filing_status)?;
+    Ok(status.localized_name(Locale::from_tag(&locale)).to_string())
+}
+
+/// Whether any dependent is a qualifying person for head of household
+#[uniffi::export]
+pub fn is_head_of_household_eligible(dependents: Vec<DependentFFI>) -> Result<bool, TaxCalcError> {
+    let dependents = dependents
+        .iter()
+        .map(Dependent::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(dependents
+        .iter()
+        .any(Dependent::qualifies_for_head_of_household))
+}
+
+/// Get list of all pay frequencies
+#[uniffi::export]
+pub fn get_all_pay_frequencies() -> Vec<String> {
+    PayFrequency::all()
+        .iter()
+        .map(|frequency| frequency.to_string())
+        .collect()
+}
+
+/// Check if state has no income tax
+#[uniffi::export]
+pub fn state_has_no_income_tax(state_code: String) -> bool {
+    USState::from_code(&state_code)
+        .map(|s| s.has_no_income_tax())
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Engine Object
+// ============================================================================
+
+/// Calculation engine bound to a tax year and data source
+///
+/// Data sources: `"embedded"` (the bundled 2023 through 2025 tables) or
+/// `"indexed:<rate>"` (the bundled tables grown by an annual inflation rate,
+/// e.g. `"indexed:0.03"`, for projecting later years).
+#[derive(uniffi::Object)]
+pub struct TaxEngine {
+    data: Box<dyn TaxDataProvider>,
+    year: u32,
+}
+
+#[uniffi::export]
+impl TaxEngine {
+    /// Engine over the embedded data for a year
+    #[uniffi::constructor]
+    pub fn new(year: u32) -> Result<Arc<Self>, TaxCalcError> {
+        Self::with_data_source(year, "embedded".to_string())
+    }
+
+    /// Engine over a named data source for a year
+    #[uniffi::constructor]
+    pub fn with_data_source(year: u32, data_source: String) -> Result<Arc<Self>, TaxCalcError> {
+        let data: Box<dyn TaxDataProvider> = match data_source.split_once(':') {
+            None if data_source == "embedded" => Box::new(EmbeddedTaxData::new()),
+            Some(("indexed", rate)) => Box::new(InflationIndexedData::new(
+                get_embedded_data(),
+                LATEST_YEAR,
+                parse_decimal(rate)?,
+            )),
+            _ => {
+                return Err(TaxCalcError::InvalidInput {
+                    message: format!("unknown data source: {data_source}"),
+                })
+            },
+        };
+        if !data.supports_year(year) {
+            return Err(TaxCalcError::InvalidInput {
+                message: format!("{data_source} has no data for {year}"),
+            });
+        }
+        Ok(Arc::new(Self { data, year }))
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Calculate taxes with full breakdown
+    pub fn calculate_taxes(
+        &self,
+        gross_income: String,
+        filing_status: String,
+        state_code: String,
+        pre_tax_deductions: String,
+        post_tax_deductions: String,
+        traditional_401k: String,
+        roth_401k: String,
+    ) -> Result<TaxResultFFI, TaxCalcError> {
+        let input = parse_input(
+            &gross_income,
+            &filing_status,
+            &state_code,
+            &pre_tax_deductions,
+            &post_tax_deductions,
+            &traditional_401k,
+            &roth_401k,
+        )?;
+
+        let result = self.engine().calculate_checked(&input)?;
+        Ok(TaxResultFFI::from(result))
+    }
+
+    /// Compare two scenarios
+    pub fn compare_scenarios(
+        &self,
+        base_gross: String,
+        base_filing_status: String,
+        base_state: String,
+        base_pre_tax: String,
+        base_post_tax: String,
+        base_traditional_401k: String,
+        base_roth_401k: String,
+        scenario_gross: String,
+        scenario_filing_status: String,
+        scenario_state: String,
+        scenario_pre_tax: String,
+        scenario_post_tax: String,
+        scenario_traditional_401k: String,
+        scenario_roth_401k: String,
+    ) -> Result<ScenarioComparisonFFI, TaxCalcError> {
+        let base = parse_input(
+            &base_gross,
+            &base_filing_status,
+            &base_state,
+            &base_pre_tax,
+            &base_post_tax,
+            &base_traditional_401k,
+            &base_roth_401k,
+        )?;
+        let scenario = parse_input(
+            &scenario_gross,
+            &scenario_filing_status,
+            &scenario_state,
+            &scenario_pre_tax,
+            &scenario_post_tax,
+            &scenario_traditional_401k,
+            &scenario_roth_401k,
+        )?;
+
+        let engine = self.engine();
+        for input in [&base, &scenario] {
+            engine.validate_data(input)?;
+        }
+        Ok(ScenarioComparisonFFI::from(
+            engine.compare_scenarios(&base, &scenario),
+        ))
+    }
+}
+
+impl TaxEngine {
+    fn engine(&self) -> TaxCalculationEngine<'_> {
+        TaxCalculationEngine::new(self.data.as_ref(), self.year)
+    }
+}
+
+// ============================================================================
+// Scenario Storage Object
+// ============================================================================
+
+/// Named scenarios persisted to a JSON file shared by every platform
+#[derive(uniffi::Object)]
+pub struct ScenarioStorage {
+    store: Mutex<ScenarioStore>,
+}
+
+#[uniffi::export]
+impl ScenarioStorage {
+    /// Open the store at a file path, creating it on first save
+    #[uniffi::constructor]
+    pub fn open(path: String) -> Result<Arc<Self>, TaxCalcError> {
+        Ok(Arc::new(Self {
+            store: Mutex::new(ScenarioStore::open(path)?),
+        }))
+    }
+
+    /// Saved scenario names in order
+    pub fn names(&self) -> Vec<String> {
+        self.store().names()
+    }
+
+    /// Create or replace a scenario, optionally caching its calculated result
+    pub fn save_scenario(
+        &self,
+        name: String,
+        gross_income: String,
+        filing_status: String,
+        state_code: String,
+        pre_tax_deductions: String,
+        post_tax_deductions: String,
+        traditional_401k: String,
+        roth_401k: String,
+        cache_result: bool,
+    ) -> Result<(), TaxCalcError> {
+        let input = parse_input(
+            &gross_income,
+            &filing_status,
+            &state_code,
+            &pre_tax_deductions,
+            &post_tax_deductions,
+            &traditional_401k,
+            &roth_401k,
+        )?;
+        let result = cache_result
+            .then(|| TaxCalculationEngine::new(get_embedded_data(), TAX_YEAR).calculate(&input));
+        Ok(self.store().save(&name, input, result)?)
+    }
+
+    pub fn load_scenario(&self, name: String) -> Option<SavedScenarioFFI> {
+        self.store().get(&name).cloned().map(SavedScenarioFFI::from)
+    }
+
+    pub fn rename_scenario(&self, from: String, to: String) -> Result<(), TaxCalcError> {
+        Ok(self.store().rename(&from, &to)?)
+    }
+
+    /// Delete a scenario, returning whether it existed
+    pub fn delete_scenario(&self, name: String) -> Result<bool, TaxCalcError> {
+        Ok(self.store().delete(&name)?)
+    }
+}
+
+impl ScenarioStorage {
+    fn store(&self) -> std::sync::MutexGuard<'_, ScenarioStore> {
+        // A panic mid-write leaves the file intact, so the map is still usable
+        self.store.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ============================================================================
+// FFI Data Types (String-based for cross-platform compatibility)
+// ============================================================================
+
+/// Tax calculation result for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TaxResultFFI {
+    // Income
+    pub gross_annual: String,
+    pub net_annual: String,
+    pub net_quarterly: String,
+    pub net_monthly: String,
+    pub net_semi_monthly: String,
+    pub net_biweekly: String,
+    pub net_weekly: String,
+    pub net_daily: String,
+    pub net_hourly: String,
+    /// Percent of gross (e.g. "72.5")
+    pub take_home_percentage: String,
+
+    // Federal (rates are fractions, e.g. "0.22")
+    pub federal_tax: String,
+    pub federal_effective_rate: String,
+    pub federal_marginal_rate: String,
+    /// Federal tax before credits on ordinary income
+    pub federal_ordinary_tax: String,
+    /// Federal tax before credits on long-term gains and qualified dividends
+    pub federal_preferential_tax: String,
+    /// Standard or itemized deduction, whichever was larger
+    pub federal_deduction: String,
+    /// "standard" or "itemized"
+    pub deduction_method: String,
+    /// Qualified business income deduction on self-employment income
+    pub federal_qbi_deduction: String,
+
+    // State
+    pub state_code: String,
+    pub state_income_tax: String,
+    pub state_local_tax: String,
+    /// Total of `state_payroll_programs`
+    pub state_sdi: String,
+    pub state_payroll_programs: Vec<PayrollContributionFFI>,
+    pub state_total_tax: String,
+
+    // FICA
+    pub social_security: String,
+    pub medicare: String,
+    pub additional_medicare: String,
+    pub fica_total: String,
+    /// Both halves of Social Security and Medicare on self-employment income
+    pub self_employment_tax: String,
+
+    // Credits
+    /// Child, other-dependent, and other credits used against federal tax
+    pub nonrefundable_credits: String,
+    /// Additional child tax credit and EITC, paid with the return
+    pub refundable_credits: String,
+
+    // Totals
+    pub total_taxes: String,
+    /// Fraction of gross
+    pub total_effective_rate: String,
+
+    /// Caveats to show with the result
+    pub warnings: Vec<String>,
+    /// How each breakdown line was computed, in display order
+    pub explanations: Vec<String>,
+}
+
+impl From<TaxCalculationResult> for TaxResultFFI {
+    fn from(r: TaxCalculationResult) -> Self {
+        // Lines shown side by side must add up at display precision
+        let r = r.rounded_to_cents();
+        Self {
+            gross_annual: format_money(r.income.gross),
+            net_annual: format_money(r.income.net),
+            net_quarterly: format_money(r.income.timeframes.quarterly),
+            net_monthly: format_money(r.income.timeframes.monthly),
+            net_semi_monthly: format_money(r.income.timeframes.semi_monthly),
+            net_biweekly: format_money(r.income.timeframes.bi_weekly),
+            net_weekly: format_money(r.income.timeframes.weekly),
+            net_daily: format_money(r.income.timeframes.daily),
+            net_hourly: format_money(r.income.timeframes.hourly),
+            take_home_percentage: r.income.take_home_percentage.to_string(),
+
+            federal_tax: format_money(r.tax_breakdown.federal.tax),
+            federal_effective_rate: r.tax_breakdown.federal.effective_rate.to_string(),
+            federal_marginal_rate: r.tax_breakdown.federal.marginal_rate.to_string(),
+            federal_ordinary_tax: format_money(r.tax_breakdown.federal.ordinary_tax),
+            federal_preferential_tax: format_money(r.tax_breakdown.federal.preferential_tax),
+            federal_deduction: format_money(r.tax_breakdown.federal.deduction),
+            deduction_method: if r.tax_breakdown.federal.itemized {
+                "itemized"
+            } else {
+                "standard"
+            }
+            .to_string(),
+            federal_qbi_deduction: format_money(r.tax_breakdown.federal.qbi_deduction),
+
+            state_code: r.tax_breakdown.state.state_code,
+            state_income_tax: format_money(r.tax_breakdown.state.income_tax),
+            state_local_tax: format_money(r.tax_breakdown.state.local_tax),
+            state_sdi: format_money(r.tax_breakdown.state.sdi),
+            state_payroll_programs: r
+                .tax_breakdown
+                .state
+                .payroll_programs
+                .iter()
+                .map(|p| PayrollContributionFFI {
+                    name: p.name.clone(),
+                    amount: format_money(p.amount),
+                })
+                .collect(),
+            state_total_tax: format_money(r.tax_breakdown.state.total_tax),
+
+            social_security: format_money(r.tax_breakdown.fica.social_security),
+            medicare: format_money(r.tax_breakdown.fica.medicare),
+            additional_medicare: format_money(r.tax_breakdown.fica.additional_medicare),
+            fica_total: format_money(r.tax_breakdown.fica.total),
+            self_employment_tax: format_money(r.tax_breakdown.self_employment.total),
+
+            nonrefundable_credits: format_money(r.tax_breakdown.credits.nonrefundable),
+            refundable_credits: format_money(r.tax_breakdown.credits.refundable),
+
+            total_taxes: format_money(r.tax_breakdown.total_taxes),
+            total_effective_rate: r.effective_rates.total.as_fraction().to_string(),
+
+            warnings: r.warnings.iter().map(|w| w.message()).collect(),
+            explanations: r
+                .tax_breakdown
+                .explanations
+                .lines()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// State payroll program line for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PayrollContributionFFI {
+    pub name: String,
+    pub amount: String,
+}
+
+/// Salary and contract rate equivalence for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ContractRateFFI {
+    pub salary: String,
+    pub hourly_rate: String,
+    pub contract_revenue: String,
+    pub billable_hours: String,
+    pub w2_value: String,
+    pub contractor_take_home: String,
+    pub self_employment_tax: String,
+}
+
+impl From<ContractRateEquivalent> for ContractRateFFI {
+    fn from(r: ContractRateEquivalent) -> Self {
+        Self {
+            salary: format_money(r.salary),
+            hourly_rate: format_money(r.hourly_rate),
+            contract_revenue: format_money(r.contract_revenue),
+            billable_hours: r.billable_hours.normalize().to_string(),
+            w2_value: format_money(r.w2_value),
+            contractor_take_home: format_money(r.contractor_take_home),
+            self_employment_tax: format_money(r.self_employment_tax),
+        }
+    }
+}
+
+/// W-2 vs 1099 comparison for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct EmploymentTypeFFI {
+    pub contractor_revenue: String,
+    pub contractor_profit: String,
+    pub billable_hours: String,
+    pub employer_fica: String,
+    pub employee_fica: String,
+    pub self_employment_tax: String,
+    pub qbi_deduction: String,
+    pub w2_value: String,
+    pub contractor_take_home: String,
+    /// Positive when contracting takes home more
+    pub contractor_advantage: String,
+    pub break_even_hourly_rate: String,
+}
+
+impl From<EmploymentTypeComparison> for EmploymentTypeFFI {
+    fn from(c: EmploymentTypeComparison) -> Self {
+        Self {
+            contractor_revenue: format_money(c.contractor_revenue),
+            contractor_profit: format_money(c.contractor_profit),
+            billable_hours: c.billable_hours.normalize().to_string(),
+            employer_fica: format_money(c.employer_fica),
+            employee_fica: format_money(c.employee_fica),
+            self_employment_tax: format_money(c.self_employment_tax),
+            qbi_deduction: format_money(c.qbi_deduction),
+            w2_value: format_money(c.w2_value),
+            contractor_take_home: format_money(c.contractor_take_home),
+            contractor_advantage: format_money(c.contractor_advantage),
+            break_even_hourly_rate: format_money(c.break_even_hourly_rate),
+        }
+    }
+}
+
+/// Value of an extra hour of work for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ExtraHourFFI {
+    pub hourly_rate: String,
+    pub regular_net: String,
+    pub overtime_gross: String,
+    pub overtime_net: String,
+    /// Fraction of each extra dollar lost to tax
+    pub marginal_rate: String,
+}
+
+impl ExtraHourFFI {
+    fn new(regular: ExtraHourValue, overtime: ExtraHourValue) -> Self {
+        Self {
+            hourly_rate: format_money(regular.hourly_rate),
+            regular_net: format_money(regular.net),
+            overtime_gross: format_money(overtime.gross),
+            overtime_net: format_money(overtime.net),
+            marginal_rate: regular.marginal_rate.as_fraction().round_dp(4).to_string(),
+        }
+    }
+}
+
+/// Overtime pay vs comp time comparison for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CompTimeFFI {
+    pub overtime_gross: String,
+    pub overtime_net: String,
+    pub overtime_marginal_rate: String,
+    pub comp_hours: String,
+    pub comp_time_value: String,
+    /// Positive when comp time is worth more than the overtime pay
+    pub comp_time_advantage: String,
+    pub overtime_net_per_comp_hour: String,
+}
+
+impl From<CompTimeComparison> for CompTimeFFI {
+    fn from(c: CompTimeComparison) -> Self {
+        Self {
+            overtime_gross: format_money(c.overtime_gross),
+            overtime_net: format_money(c.overtime_net),
+            overtime_marginal_rate: c
+                .overtime_marginal_rate
+                .as_fraction()
+                .round_dp(4)
+                .to_string(),
+            comp_hours: c.comp_hours.normalize().to_string(),
+            comp_time_value: format_money(c.comp_time_value),
+            comp_time_advantage: format_money(c.comp_time_advantage),
+            overtime_net_per_comp_hour: format_money(c.overtime_net_per_comp_hour),
+        }
+    }
+}
+
+/// One simulated paycheck for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SimulatedPaycheckFFI {
+    pub number: u32,
+    pub gross: String,
+    pub pre_tax_deductions: String,
+    pub federal_income_tax: String,
+    pub social_security: String,
+    pub medicare: String,
+    pub net: String,
+}
+
+impl From<PaycheckWithholding> for SimulatedPaycheckFFI {
+    fn from(p: PaycheckWithholding) -> Self {
+        Self {
+            number: p.number,
+            gross: format_money(p.gross),
+            pre_tax_deductions: format_money(p.pre_tax_deductions),
+            federal_income_tax: format_money(p.federal_income_tax),
+            social_security: format_money(p.social_security),
+            medicare: format_money(p.medicare),
+            net: format_money(p.net),
+        }
+    }
+}
+
+/// A simulated year of paychecks for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaycheckSimulationFFI {
+    pub paychecks: Vec<SimulatedPaycheckFFI>,
+    pub federal_income_tax: String,
+    pub social_security: String,
+    pub medicare: String,
+    pub net: String,
+}
+
+impl From<WithholdingSimulation> for PaycheckSimulationFFI {
+    fn from(s: WithholdingSimulation) -> Self {
+        Self {
+            federal_income_tax: format_money(s.federal_income_tax),
+            social_security: format_money(s.social_security),
+            medicare: format_money(s.medicare),
+            net: format_money(s.net),
+            paychecks: s
+                .paychecks
+                .into_iter()
+                .map(SimulatedPaycheckFFI::from)
+                .collect(),
+        }
+    }
+}
+
+/// Paychecks under one pay election for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaySpreadOptionFFI {
+    pub paychecks: u32,
+    pub gross_per_paycheck: String,
+    pub federal_withholding_per_paycheck: String,
+    pub net_per_paycheck: String,
+    pub federal_withheld: String,
+    /// Positive is a refund, negative a balance due
+    pub federal_refund: String,
+}
+
+impl From<PaySpreadOption> for PaySpreadOptionFFI {
+    fn from(o: PaySpreadOption) -> Self {
+        Self {
+            paychecks: o.paychecks,
+            gross_per_paycheck: format_money(o.gross_per_paycheck),
+            federal_withholding_per_paycheck: format_money(o.federal_withholding_per_paycheck),
+            net_per_paycheck: format_money(o.net_per_paycheck),
+            federal_withheld: format_money(o.federal_withheld),
+            federal_refund: format_money(o.federal_refund),
+        }
+    }
+}
+
+/// Work-months vs 12-month pay comparison for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaySpreadFFI {
+    pub work_months_only: PaySpreadOptionFFI,
+    pub spread_over_twelve: PaySpreadOptionFFI,
+    pub per_paycheck_difference: String,
+    pub extra_withholding: String,
+    pub set_aside_per_paycheck: String,
+}
+
+impl From<PaySpreadComparison> for PaySpreadFFI {
+    fn from(c: PaySpreadComparison) -> Self {
+        Self {
+            work_months_only: c.work_months_only.into(),
+            spread_over_twelve: c.spread_over_twelve.into(),
+            per_paycheck_difference: format_money(c.per_paycheck_difference),
+            extra_withholding: format_money(c.extra_withholding),
+            set_aside_per_paycheck: format_money(c.set_aside_per_paycheck),
+        }
+    }
+}
+
+/// Side income analysis for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SideIncomeFFI {
+    pub federal_income_tax: String,
+    pub state_tax: String,
+    /// FICA, or self-employment tax for 1099 income
+    pub payroll_tax: String,
+    pub total_tax: String,
+    pub after_tax_value: String,
+    pub marginal_rate: String,
+    pub federal_withheld: String,
+    pub withholding_shortfall: String,
+    pub quarterly_estimated_payment: String,
+    pub underpayment_risk: bool,
+}
+
+impl From<SideIncomeAnalysis> for SideIncomeFFI {
+    fn from(a: SideIncomeAnalysis) -> Self {
+        Self {
+            federal_income_tax: format_money(a.federal_income_tax),
+            state_tax: format_money(a.state_tax),
+            payroll_tax: format_money(a.payroll_tax),
+            total_tax: format_money(a.total_tax),
+            after_tax_value: format_money(a.after_tax_value),
+            marginal_rate: a.marginal_rate.as_fraction().round_dp(4).to_string(),
+            federal_withheld: format_money(a.federal_withheld),
+            withholding_shortfall: format_money(a.withholding_shortfall),
+            quarterly_estimated_payment: format_money(a.quarterly_estimated_payment),
+            underpayment_risk: a.underpayment_risk,
+        }
+    }
+}
+
+/// Real cost of a purchase for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PurchaseCostFFI {
+    pub price: String,
+    pub pre_tax_earnings: String,
+    pub hours_of_work: String,
+    pub days_of_work: String,
+    /// Fraction of the earnings lost to tax
+    pub marginal_rate: String,
+}
+
+impl From<PurchaseCost> for PurchaseCostFFI {
+    fn from(c: PurchaseCost) -> Self {
+        Self {
+            price: format_money(c.price),
+            pre_tax_earnings: format_money(c.pre_tax_earnings),
+            hours_of_work: c.hours_of_work.normalize().to_string(),
+            days_of_work: c.days_of_work.normalize().to_string(),
+            marginal_rate: c.marginal_rate.as_fraction().round_dp(4).to_string(),
+        }
+    }
+}
+
+/// Compensation package for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CompensationPackageFFI {
+    pub name: String,
+    pub base_salary: String,
+    /// Target bonus as a fraction of base salary (e.g. "0.15")
+    pub bonus_percent: String,
+    /// Annual value of equity vesting
+    pub equity_vesting: String,
+    pub state_code: String,
+    pub filing_status: String,
+    pub traditional_401k: String,
+    /// Employer share of health, dental, and vision premiums
+    pub employer_health_premiums: String,
+    /// Employer HSA seed money
+    pub employer_hsa_contribution: String,
+    /// Employer-paid life and disability insurance premiums
+    pub employer_life_insurance: String,
+    pub employer_disability_insurance: String,
+    /// Annual value of other employer-paid benefits
+    pub employer_benefits: String,
+}
+
+/// One pay period's paystub amounts for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaystubFFI {
+    pub pay_frequency: String,
+    pub gross: String,
+    pub pre_tax_deductions: String,
+    pub federal_withholding: String,
+    pub social_security: String,
+    pub medicare: String,
+    pub state_withholding: String,
+    pub local_withholding: String,
+    /// SDI, family leave, and other state payroll programs
+    pub state_payroll_programs: String,
+    pub net: String,
+}
+
+impl From<Paystub> for PaystubFFI {
+    fn from(p: Paystub) -> Self {
+        Self {
+            pay_frequency: p.pay_frequency.as_str().to_string(),
+            gross: format_money(p.gross),
+            pre_tax_deductions: format_money(p.pre_tax_deductions),
+            federal_withholding: format_money(p.federal_withholding),
+            social_security: format_money(p.social_security),
+            medicare: format_money(p.medicare),
+            state_withholding: format_money(p.state_withholding),
+            local_withholding: format_money(p.local_withholding),
+            state_payroll_programs: format_money(p.state_payroll_programs),
+            net: format_money(p.net),
+        }
+    }
+}
+
+/// Paystub line outside tolerance for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaystubDiscrepancyFFI {
+    pub line: String,
+    pub actual: String,
+    pub expected: String,
+    /// Actual minus expected
+    pub difference: String,
+    /// Likely explanation, when one fits
+    pub explanation: Option<String>,
+}
+
+/// One paycheck on the calendar for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaycheckFFI {
+    /// YYYY-MM-DD before shifting off weekends and holidays
+    pub scheduled_date: String,
+    pub pay_date: String,
+    pub gross: String,
+    pub net: String,
+}
+
+/// A year of paychecks for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PayScheduleFFI {
+    pub paychecks: Vec<PaycheckFFI>,
+    /// Months (1-12) with a third biweekly or fifth weekly paycheck
+    pub extra_paycheck_months: Vec<u32>,
+}
+
+impl From<PaySchedule> for PayScheduleFFI {
+    fn from(schedule: PaySchedule) -> Self {
+        Self {
+            extra_paycheck_months: schedule.extra_paycheck_months(),
+            paychecks: schedule
+                .paychecks
+                .into_iter()
+                .map(|paycheck| PaycheckFFI {
+                    scheduled_date: paycheck.date.scheduled.to_string(),
+                    pay_date: paycheck.date.paid.to_string(),
+                    gross: format_money(paycheck.paystub.gross),
+                    net: format_money(paycheck.paystub.net),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Paystub check for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PaystubVerificationFFI {
+    pub is_correct: bool,
+    /// Per-paycheck amounts the calculation expects
+    pub expected: PaystubFFI,
+    pub discrepancies: Vec<PaystubDiscrepancyFFI>,
+}
+
+impl From<PaystubVerification> for PaystubVerificationFFI {
+    fn from(v: PaystubVerification) -> Self {
+        Self {
+            is_correct: v.is_correct(),
+            discrepancies: v
+                .discrepancies
+                .into_iter()
+                .map(|d| PaystubDiscrepancyFFI {
+                    line: d.line.to_string(),
+                    actual: format_money(d.actual),
+                    expected: format_money(d.expected),
+                    difference: format_money(d.difference),
+                    explanation: d.cause.map(|c| c.explanation()),
+                })
+                .collect(),
+            expected: PaystubFFI::from(v.expected),
+        }
+    }
+}
+
+/// Ranked package evaluation for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct OfferEvaluationFFI {
+    /// 1 for the best package
+    pub rank: u32,
+    pub name: String,
+    pub total_compensation: String,
+    /// Employer-paid premiums, HSA seed, and other benefits
+    pub benefits_value: String,
+    pub total_taxes: String,
+    pub after_tax_cash: String,
+    pub after_tax_value: String,
+    /// Fraction of gross
+    pub effective_rate: String,
+    /// After-tax value shortfall relative to the best package
+    pub gap_to_best: String,
+}
+
+impl OfferEvaluationFFI {
+    fn from_comparison(comparison: &OfferComparison) -> Vec<Self> {
+        let best = comparison
+            .best()
+            .map(|e| e.after_tax_value)
+            .unwrap_or(Decimal::ZERO);
+        comparison
+            .ranked
+            .iter()
+            .zip(1..)
+            .map(|(e, rank)| Self {
+                rank,
+                name: e.name.clone(),
+                total_compensation: format_money(e.total_compensation),
+                benefits_value: format_money(e.benefits.total()),
+                total_taxes: format_money(e.calculation.tax_breakdown.total_taxes),
+                after_tax_cash: format_money(e.after_tax_cash),
+                after_tax_value: format_money(e.after_tax_value),
+                effective_rate: e
+                    .calculation
+                    .effective_rates
+                    .total
+                    .as_fraction()
+                    .to_string(),
+                gap_to_best: format_money(best - e.after_tax_value),
+            })
+            .collect()
+    }
+}
+
+/// Banded results for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ResultBandsFFI {
+    pub pessimistic: TaxResultFFI,
+    pub expected: TaxResultFFI,
+    pub optimistic: TaxResultFFI,
+    /// Take-home difference between the optimistic and pessimistic cases
+    pub net_spread: String,
+}
+
+impl From<ResultBands> for ResultBandsFFI {
+    fn from(b: ResultBands) -> Self {
+        Self {
+            net_spread: format_money(b.net_spread()),
+            pessimistic: TaxResultFFI::from(b.pessimistic),
+            expected: TaxResultFFI::from(b.expected),
+            optimistic: TaxResultFFI::from(b.optimistic),
+        }
+    }
+}
+
+/// Dependent for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct DependentFFI {
+    /// "child", "parent", or "other_relative"
+    pub relationship: String,
+    pub age: u32,
+    pub months_in_home: u32,
+    pub full_time_student: bool,
+    pub permanently_disabled: bool,
+}
+
+impl TryFrom<&DependentFFI> for Dependent {
+    type Error = TaxCalcError;
+
+    fn try_from(d: &DependentFFI) -> Result<Self, Self::Error> {
+        if d.months_in_home > 12 {
+            return Err(TaxCalcError::InvalidInput {
+                message: "dependent months_in_home cannot exceed 12".to_string(),
+            });
+        }
+        Ok(Dependent {
+            relationship: d
+                .relationship
+                .parse()
+                .map_err(
+                    |e: crate::models::ParseEnumError| TaxCalcError::InvalidInput {
+                        message: e.to_string(),
+                    },
+                )?,
+            age: d.age,
+            months_in_home: d.months_in_home,
+            full_time_student: d.full_time_student,
+            permanently_disabled: d.permanently_disabled,
+        })
+    }
+}
 
-    let split = calculate_split(primary, partner, expense, method);
-    Ok(HouseholdSplitFFI::from(split))
+/// Simulation seed and sample count for FFI
+#[derive(Debug, Clone, Copy, uniffi::Record)]
+pub struct SimulationConfigFFI {
+    pub seed: u64,
+    pub iterations: u32,
 }
 
-/// Get list of all state codes
-#[uniffi::export]
-pub fn get_all_state_codes() -> Vec<String> {
-    USState::all()
-        .iter()
-        .map(|s| s.code().to_string())
-        .collect()
+/// Simulated take-home spread for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TakeHomeDistributionFFI {
+    pub seed: u64,
+    pub iterations: u32,
+    pub mean: String,
+    pub p10: String,
+    pub median: String,
+    pub p90: String,
 }
 
-/// Get list of all filing statuses
-#[uniffi::export]
-pub fn get_all_filing_statuses() -> Vec<String> {
-    vec![
-        "single".to_string(),
-        "married_filing_jointly".to_string(),
-        "married_filing_separately".to_string(),
-        "head_of_household".to_string(),
-        "qualifying_widower".to_string(),
-    ]
+impl From<TakeHomeDistribution> for TakeHomeDistributionFFI {
+    fn from(d: TakeHomeDistribution) -> Self {
+        Self {
+            seed: d.config.seed,
+            iterations: d.iterations,
+            mean: format_money(d.mean),
+            p10: format_money(d.p10),
+            median: format_money(d.median),
+            p90: format_money(d.p90),
+        }
+    }
 }
 
-/// Check if state has no income tax
-#[uniffi::export]
-pub fn state_has_no_income_tax(state_code: String) -> bool {
-    USState::from_code(&state_code)
-        .map(|s| s.has_no_income_tax())
-        .unwrap_or(false)
+/// Bracket chart for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BracketChartFFI {
+    pub federal: BracketSeriesFFI,
+    pub state: Option<BracketSeriesFFI>,
 }
 
-// ============================================================================
-// FFI Data Types (String-based for cross-platform compatibility)
-// ============================================================================
+impl From<BracketChart> for BracketChartFFI {
+    fn from(c: BracketChart) -> Self {
+        Self {
+            federal: BracketSeriesFFI::from(c.federal),
+            state: c.state.map(BracketSeriesFFI::from),
+        }
+    }
+}
 
-/// Tax calculation result for FFI
+/// One jurisdiction's bands and curve for FFI (rates are fractions)
 #[derive(Debug, Clone, uniffi::Record)]
-pub struct TaxResultFFI {
-    // Income
-    pub gross_annual: String,
-    pub net_annual: String,
-    pub net_monthly: String,
-    pub net_biweekly: String,
-    pub net_weekly: String,
-    pub net_daily: String,
-    pub net_hourly: String,
-    pub take_home_percentage: String,
+pub struct BracketSeriesFFI {
+    pub label: String,
+    pub bands: Vec<BracketBandFFI>,
+    pub curve: Vec<CurvePointFFI>,
+    pub marker: Option<CurvePointFFI>,
+    pub axis_max: String,
+}
 
-    // Federal
-    pub federal_tax: String,
-    pub federal_effective_rate: String,
-    pub federal_marginal_rate: String,
+impl From<BracketSeries> for BracketSeriesFFI {
+    fn from(s: BracketSeries) -> Self {
+        Self {
+            label: s.label,
+            bands: s
+                .bands
+                .iter()
+                .map(|b| BracketBandFFI {
+                    floor: format_money(b.floor),
+                    ceiling: b.ceiling.map(format_money),
+                    rate: b.rate.as_fraction().to_string(),
+                    tax_at_floor: format_money(b.tax_at_floor),
+                })
+                .collect(),
+            curve: s.curve.into_iter().map(CurvePointFFI::from).collect(),
+            marker: s.marker.map(CurvePointFFI::from),
+            axis_max: format_money(s.axis_max),
+        }
+    }
+}
 
-    // State
-    pub state_code: String,
-    pub state_income_tax: String,
-    pub state_local_tax: String,
-    pub state_sdi: String,
-    pub state_total_tax: String,
+/// Bracket band for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BracketBandFFI {
+    pub floor: String,
+    pub ceiling: Option<String>,
+    pub rate: String,
+    pub tax_at_floor: String,
+}
 
-    // FICA
-    pub social_security: String,
-    pub medicare: String,
-    pub additional_medicare: String,
-    pub fica_total: String,
+/// Tax curve point for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CurvePointFFI {
+    pub taxable_income: String,
+    pub tax: String,
+    pub marginal_rate: String,
+    pub effective_rate: String,
+}
 
-    // Totals
-    pub total_taxes: String,
-    pub total_effective_rate: String,
+impl From<CurvePoint> for CurvePointFFI {
+    fn from(p: CurvePoint) -> Self {
+        Self {
+            taxable_income: format_money(p.taxable_income),
+            tax: format_money(p.tax),
+            marginal_rate: p.marginal_rate.as_fraction().to_string(),
+            effective_rate: p.effective_rate.as_fraction().round_dp(4).to_string(),
+        }
+    }
 }
 
-impl From<TaxCalculationResult> for TaxResultFFI {
-    fn from(r: TaxCalculationResult) -> Self {
+/// Tax data changelog for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TaxDataChangelogFFI {
+    pub from_year: u32,
+    pub to_year: u32,
+    pub changes: Vec<DataChangeFFI>,
+}
+
+impl From<TaxDataChangelog> for TaxDataChangelogFFI {
+    fn from(log: TaxDataChangelog) -> Self {
         Self {
-            gross_annual: r.income.gross.to_string(),
-            net_annual: r.income.net.to_string(),
-            net_monthly: r.income.timeframes.monthly.to_string(),
-            net_biweekly: r.income.timeframes.bi_weekly.to_string(),
-            net_weekly: r.income.timeframes.weekly.to_string(),
-            net_daily: r.income.timeframes.daily.to_string(),
-            net_hourly: r.income.timeframes.hourly.to_string(),
-            take_home_percentage: r.income.take_home_percentage.to_string(),
+            from_year: log.from_year,
+            to_year: log.to_year,
+            changes: log
+                .changes
+                .into_iter()
+                .map(|change| DataChangeFFI {
+                    jurisdiction: change.jurisdiction,
+                    kind: change.kind.as_str().to_string(),
+                    item: change.item,
+                    old_value: change.old_value.map(|v| v.normalize().to_string()),
+                    new_value: change.new_value.map(|v| v.normalize().to_string()),
+                })
+                .collect(),
+        }
+    }
+}
 
-            federal_tax: r.tax_breakdown.federal.tax.to_string(),
-            federal_effective_rate: r.tax_breakdown.federal.effective_rate.to_string(),
-            federal_marginal_rate: r.tax_breakdown.federal.marginal_rate.to_string(),
+/// One tax data change for FFI (rates are fractions)
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct DataChangeFFI {
+    pub jurisdiction: String,
+    pub kind: String,
+    pub item: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
 
-            state_code: r.tax_breakdown.state.state_code,
-            state_income_tax: r.tax_breakdown.state.income_tax.to_string(),
-            state_local_tax: r.tax_breakdown.state.local_tax.to_string(),
-            state_sdi: r.tax_breakdown.state.sdi.to_string(),
-            state_total_tax: r.tax_breakdown.state.total_tax.to_string(),
-
-            social_security: r.tax_breakdown.fica.social_security.to_string(),
-            medicare: r.tax_breakdown.fica.medicare.to_string(),
-            additional_medicare: r.tax_breakdown.fica.additional_medicare.to_string(),
-            fica_total: r.tax_breakdown.fica.total.to_string(),
-
-            total_taxes: r.tax_breakdown.total_taxes.to_string(),
-            total_effective_rate: r.effective_rates.total.to_string(),
+/// Saved scenario for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SavedScenarioFFI {
+    pub name: String,
+    pub gross_income: String,
+    pub filing_status: String,
+    pub state_code: String,
+    pub pre_tax_deductions: String,
+    pub post_tax_deductions: String,
+    pub traditional_401k: String,
+    pub roth_401k: String,
+    /// Result cached when the scenario was saved
+    pub result: Option<TaxResultFFI>,
+}
+
+impl From<SavedScenario> for SavedScenarioFFI {
+    fn from(s: SavedScenario) -> Self {
+        let input = &s.input;
+        Self {
+            gross_income: format_money(input.gross_income),
+            filing_status: input.filing_status.to_string(),
+            state_code: input.state.code().to_string(),
+            pre_tax_deductions: format_money(input.pre_tax_deductions),
+            post_tax_deductions: format_money(input.post_tax_deductions),
+            traditional_401k: format_money(input.traditional_401k),
+            roth_401k: format_money(input.roth_401k),
+            result: s.result.map(TaxResultFFI::from),
+            name: s.name,
         }
     }
 }
@@ -266,6 +1896,12 @@ pub struct ScenarioComparisonFFI {
     pub net_difference: String,
     pub monthly_difference: String,
     pub is_positive: bool,
+    pub federal_difference: String,
+    pub state_difference: String,
+    pub sdi_difference: String,
+    pub fica_difference: String,
+    /// Fraction of gross
+    pub effective_rate_difference: String,
 }
 
 impl From<ScenarioComparison> for ScenarioComparisonFFI {
@@ -274,9 +1910,14 @@ impl From<ScenarioComparison> for ScenarioComparisonFFI {
         Self {
             base: TaxResultFFI::from(c.base),
             scenario: TaxResultFFI::from(c.scenario),
-            net_difference: c.net_difference.to_string(),
-            monthly_difference: c.monthly_difference.to_string(),
+            net_difference: format_money(c.net_difference),
+            monthly_difference: format_money(c.monthly_difference),
             is_positive,
+            federal_difference: format_money(c.federal.difference),
+            state_difference: format_money(c.state.difference),
+            sdi_difference: format_money(c.sdi.difference),
+            fica_difference: format_money(c.fica.difference),
+            effective_rate_difference: c.effective_rate.difference.to_string(),
         }
     }
 }
@@ -285,7 +1926,9 @@ impl From<ScenarioComparison> for ScenarioComparisonFFI {
 #[derive(Debug, Clone, uniffi::Record)]
 pub struct TimeframeFFI {
     pub annual: String,
+    pub quarterly: String,
     pub monthly: String,
+    pub semi_monthly: String,
     pub bi_weekly: String,
     pub weekly: String,
     pub daily: String,
@@ -295,12 +1938,14 @@ pub struct TimeframeFFI {
 impl From<TimeframeIncome> for TimeframeFFI {
     fn from(t: TimeframeIncome) -> Self {
         Self {
-            annual: t.annual.to_string(),
-            monthly: t.monthly.to_string(),
-            bi_weekly: t.bi_weekly.to_string(),
-            weekly: t.weekly.to_string(),
-            daily: t.daily.to_string(),
-            hourly: t.hourly.to_string(),
+            annual: format_money(t.annual),
+            quarterly: format_money(t.quarterly),
+            monthly: format_money(t.monthly),
+            semi_monthly: format_money(t.semi_monthly),
+            bi_weekly: format_money(t.bi_weekly),
+            weekly: format_money(t.weekly),
+            daily: format_money(t.daily),
+            hourly: format_money(t.hourly),
         }
     }
 }
@@ -319,8 +1964,50 @@ impl From<HouseholdSplit> for HouseholdSplitFFI {
         Self {
             primary_ratio: h.primary_ratio.to_string(),
             partner_ratio: h.partner_ratio.to_string(),
-            primary_amount: h.primary_monthly_amount.to_string(),
-            partner_amount: h.partner_monthly_amount.to_string(),
+            primary_amount: format_money(h.primary_monthly_amount),
+            partner_amount: format_money(h.partner_monthly_amount),
+        }
+    }
+}
+
+/// Shared expense for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SharedExpenseFFI {
+    /// e.g. "housing", "food", "childcare"
+    pub category: String,
+    pub amount: String,
+    /// "proportional", "equal", or "custom:<primary fraction>"
+    pub split_method: String,
+}
+
+/// One category's split for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct CategorySplitFFI {
+    pub category: String,
+    pub split: HouseholdSplitFFI,
+}
+
+/// Itemized household split for FFI
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ItemizedHouseholdSplitFFI {
+    pub categories: Vec<CategorySplitFFI>,
+    pub primary_total: String,
+    pub partner_total: String,
+}
+
+impl From<ItemizedHouseholdSplit> for ItemizedHouseholdSplitFFI {
+    fn from(h: ItemizedHouseholdSplit) -> Self {
+        Self {
+            primary_total: format_money(h.primary_monthly_total),
+            partner_total: format_money(h.partner_monthly_total),
+            categories: h
+                .categories
+                .into_iter()
+                .map(|c| CategorySplitFFI {
+                    category: c.category.to_string(),
+                    split: HouseholdSplitFFI::from(c.split),
+                })
+                .collect(),
         }
     }
 }
@@ -329,6 +2016,20 @@ impl From<HouseholdSplit> for HouseholdSplitFFI {
 // Helper Functions
 // ============================================================================
 
+/// Format an amount at cents precision
+fn format_money(amount: Decimal) -> String {
+    Money::from(amount).to_string()
+}
+
+/// Parse an input amount, rounded to cents
+fn parse_money(s: &str) -> Result<Decimal, TaxCalcError> {
+    s.parse::<Money>()
+        .map(|money| money.cents().amount())
+        .map_err(|_| TaxCalcError::InvalidDecimal {
+            message: s.to_string(),
+        })
+}
+
 fn parse_decimal(s: &str) -> Result<Decimal, TaxCalcError> {
     s.parse::<Decimal>()
         .map_err(|_| TaxCalcError::InvalidDecimal {
@@ -336,20 +2037,90 @@ fn parse_decimal(s: &str) -> Result<Decimal, TaxCalcError> {
         })
 }
 
-fn parse_filing_status(s: &str) -> Result<FilingStatus, TaxCalcError> {
-    match s {
-        "single" => Ok(FilingStatus::Single),
-        "married_filing_jointly" => Ok(FilingStatus::MarriedFilingJointly),
-        "married_filing_separately" => Ok(FilingStatus::MarriedFilingSeparately),
-        "head_of_household" => Ok(FilingStatus::HeadOfHousehold),
-        "qualifying_widower" => Ok(FilingStatus::QualifyingWidower),
-        _ => Err(TaxCalcError::InvalidFilingStatus {
-            message: s.to_string(),
-        }),
+fn parse_contract_assumptions(
+    hours_per_week: &str,
+    pto_days: &str,
+    utilization: &str,
+    benefits_value: &str,
+    business_expenses: &str,
+) -> Result<ContractAssumptions, TaxCalcError> {
+    let assumptions = ContractAssumptions {
+        hours_per_week: parse_decimal(hours_per_week)?,
+        pto_days: parse_decimal(pto_days)?,
+        utilization: parse_decimal(utilization)?,
+        benefits_value: parse_money(benefits_value)?,
+        business_expenses: parse_money(business_expenses)?,
+        home_office: None,
+    };
+    if assumptions.billable_hours() <= Decimal::ZERO || assumptions.utilization > Decimal::ONE {
+        return Err(TaxCalcError::InvalidInput {
+            message: "assumptions leave no billable hours".to_string(),
+        });
     }
+    Ok(assumptions)
+}
+
+fn parse_package(package: &CompensationPackageFFI) -> Result<CompensationPackage, TaxCalcError> {
+    Ok(CompensationPackage {
+        name: package.name.clone(),
+        base_salary: parse_money(&package.base_salary)?,
+        bonus_percent: parse_decimal(&package.bonus_percent)?,
+        equity_vesting: parse_money(&package.equity_vesting)?,
+        state: parse_state(&package.state_code)?,
+        filing_status: parse_filing_status(&package.filing_status)?,
+        traditional_401k: parse_money(&package.traditional_401k)?,
+        benefits: EmployerBenefits {
+            health_premiums: parse_money(&package.employer_health_premiums)?,
+            life_insurance: parse_money(&package.employer_life_insurance)?,
+            disability_insurance: parse_money(&package.employer_disability_insurance)?,
+        },
+        employer_hsa_contribution: parse_money(&package.employer_hsa_contribution)?,
+        employer_benefits: parse_money(&package.employer_benefits)?,
+        ..Default::default()
+    })
+}
+
+fn parse_paystub(paystub: &PaystubFFI) -> Result<Paystub, TaxCalcError> {
+    Ok(Paystub {
+        pay_frequency: paystub.pay_frequency.parse().map_err(
+            |e: crate::models::ParseEnumError| TaxCalcError::InvalidInput {
+                message: e.to_string(),
+            },
+        )?,
+        gross: parse_money(&paystub.gross)?,
+        pre_tax_deductions: parse_money(&paystub.pre_tax_deductions)?,
+        federal_withholding: parse_money(&paystub.federal_withholding)?,
+        social_security: parse_money(&paystub.social_security)?,
+        medicare: parse_money(&paystub.medicare)?,
+        state_withholding: parse_money(&paystub.state_withholding)?,
+        local_withholding: parse_money(&paystub.local_withholding)?,
+        state_payroll_programs: parse_money(&paystub.state_payroll_programs)?,
+        net: parse_money(&paystub.net)?,
+    })
+}
+
+fn parse_split_method(s: &str) -> Result<SplitMethod, TaxCalcError> {
+    Ok(match s {
+        "proportional" => SplitMethod::Proportional,
+        "equal" => SplitMethod::Equal,
+        s if s.starts_with("custom:") => SplitMethod::Custom(parse_decimal(&s[7..])?),
+        _ => SplitMethod::Proportional,
+    })
+}
+
+fn parse_filing_status(s: &str) -> Result<FilingStatus, TaxCalcError> {
+    s.parse().map_err(|_| TaxCalcError::InvalidFilingStatus {
+        message: s.to_string(),
+    })
+}
+
+fn parse_state(s: &str) -> Result<USState, TaxCalcError> {
+    s.parse().map_err(|_| TaxCalcError::InvalidState {
+        message: s.to_string(),
+    })
 }
 
-fn parse_input(
+pub(crate) fn parse_input(
     gross: &str,
     filing_status: &str,
     state: &str,
@@ -359,15 +2130,14 @@ fn parse_input(
     roth: &str,
 ) -> Result<TaxCalculationInput, TaxCalcError> {
     Ok(TaxCalculationInput {
-        gross_income: parse_decimal(gross)?,
+        gross_income: parse_money(gross)?,
         filing_status: parse_filing_status(filing_status)?,
-        state: USState::from_code(state).ok_or_else(|| TaxCalcError::InvalidState {
-            message: state.to_string(),
-        })?,
-        pre_tax_deductions: parse_decimal(pre_tax)?,
-        post_tax_deductions: parse_decimal(post_tax)?,
-        traditional_401k: parse_decimal(traditional)?,
-        roth_401k: parse_decimal(roth)?,
+        state: parse_state(state)?,
+        pre_tax_deductions: parse_money(pre_tax)?,
+        post_tax_deductions: parse_money(post_tax)?,
+        traditional_401k: parse_money(traditional)?,
+        roth_401k: parse_money(roth)?,
+        ..Default::default()
     })
 }
 
@@ -393,6 +2163,113 @@ mod tests {
         assert!(!r.net_annual.is_empty());
     }
 
+    #[test]
+    fn test_head_of_household_eligibility_ffi() {
+        let dependent = |relationship: &str, age, months_in_home| DependentFFI {
+            relationship: relationship.to_string(),
+            age,
+            months_in_home,
+            full_time_student: false,
+            permanently_disabled: false,
+        };
+
+        assert!(!is_head_of_household_eligible(vec![]).unwrap());
+        assert!(is_head_of_household_eligible(vec![dependent("child", 12, 12)]).unwrap());
+        assert!(!is_head_of_household_eligible(vec![dependent("child", 30, 12)]).unwrap());
+        assert!(is_head_of_household_eligible(vec![dependent("parent", 70, 0)]).unwrap());
+        assert!(is_head_of_household_eligible(vec![dependent("cousin", 12, 12)]).is_err());
+    }
+
+    #[test]
+    fn test_simulate_taxes_banded_ffi() {
+        let simulate = |seed, iterations| {
+            simulate_taxes_banded(
+                "100000".to_string(),
+                "0".to_string(),
+                "10000".to_string(),
+                "20000".to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                SimulationConfigFFI { seed, iterations },
+            )
+        };
+
+        let first = simulate(1, 100).unwrap();
+        let again = simulate(1, 100).unwrap();
+        assert_eq!(first.median, again.median);
+        assert_eq!(first.p90, again.p90);
+        assert!(simulate(1, 0).is_err());
+    }
+
+    #[test]
+    fn test_tax_data_changelog_ffi() {
+        let log = get_tax_data_changelog(2024, 2025);
+        let mileage = log
+            .changes
+            .iter()
+            .find(|change| change.kind == "mileage_rate")
+            .unwrap();
+        assert_eq!(mileage.old_value.as_deref(), Some("0.67"));
+        assert_eq!(mileage.new_value.as_deref(), Some("0.7"));
+        assert!(log.changes.iter().any(|change| change.jurisdiction == "LA"));
+    }
+
+    #[test]
+    fn test_get_pay_calendar_ffi() {
+        let calendar = |frequency: &str, first: &str| {
+            get_pay_calendar(
+                "78000".to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                frequency.to_string(),
+                first.to_string(),
+                "previous_business_day".to_string(),
+            )
+        };
+
+        let schedule = calendar("bi_weekly", "2024-01-05").unwrap();
+        assert_eq!(schedule.paychecks.len(), 26);
+        assert_eq!(schedule.paychecks[0].pay_date, "2024-01-05");
+        assert_eq!(schedule.paychecks[0].gross, "3000");
+        assert_eq!(schedule.extra_paycheck_months, vec![3, 8]);
+
+        assert!(calendar("bi_weekly", "01/05/2024").is_err());
+        assert!(calendar("fortnightly", "2024-01-05").is_err());
+    }
+
+    #[test]
+    fn test_get_bracket_chart_ffi() {
+        let chart = get_bracket_chart(
+            "75000".to_string(),
+            "single".to_string(),
+            "CA".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(chart.federal.label, "Federal");
+        assert_eq!(chart.federal.bands[0].rate, "0.10");
+        assert_eq!(chart.federal.marker.unwrap().taxable_income, "60400");
+        assert!(chart.state.is_some());
+    }
+
+    #[test]
+    fn test_localized_display_names_ffi() {
+        assert_eq!(get_supported_locales(), vec!["en", "es"]);
+        assert_eq!(
+            get_state_display_name("NC".to_string(), "es-US".to_string()).unwrap(),
+            "Carolina del Norte"
+        );
+        assert_eq!(
+            get_filing_status_display_name("single".to_string(), "de".to_string()).unwrap(),
+            "Single"
+        );
+        assert!(get_state_display_name("XX".to_string(), "es".to_string()).is_err());
+    }
+
     #[test]
     fn test_convert_timeframes_ffi() {
         let result = convert_timeframes("104000".to_string());
@@ -402,6 +2279,212 @@ mod tests {
         assert_eq!(t.annual, "104000");
         assert_eq!(t.bi_weekly, "4000");
         assert_eq!(t.hourly, "50");
+
+        // Amounts cross the boundary at cents precision
+        let t = convert_timeframes("100000".to_string()).unwrap();
+        assert_eq!(t.monthly, "8333.33");
+        assert_eq!(t.semi_monthly, "4166.67");
+        assert_eq!(t.quarterly, "25000");
+    }
+
+    #[test]
+    fn test_per_paycheck_amount_ffi() {
+        let paycheck = per_paycheck_amount("108000".to_string(), "bi_weekly_27".to_string());
+        assert_eq!(paycheck.unwrap(), "4000");
+        assert_eq!(
+            per_paycheck_amount("100000".to_string(), "quarterly".to_string()).unwrap(),
+            "25000"
+        );
+        assert!(per_paycheck_amount("100000".to_string(), "hourly".to_string()).is_err());
+        assert_eq!(get_all_pay_frequencies().len(), 8);
+    }
+
+    #[test]
+    fn test_compare_employment_types_ffi() {
+        let comparison = compare_employment_types(
+            "100000".to_string(),
+            "70".to_string(),
+            "single".to_string(),
+            "TX".to_string(),
+            "40".to_string(),
+            "15".to_string(),
+            "0.85".to_string(),
+            "0".to_string(),
+            "5000".to_string(),
+        )
+        .unwrap();
+        assert_eq!(comparison.contractor_profit, "111620");
+        assert_eq!(comparison.employer_fica, "7650");
+        // Above the salary's $48.08 an hour
+        let break_even: Decimal = comparison.break_even_hourly_rate.parse().unwrap();
+        assert!(break_even > "48.08".parse().unwrap());
+    }
+
+    #[test]
+    fn test_contract_rate_ffi() {
+        let args = |amount: &str| {
+            (
+                amount.to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "40".to_string(),
+                "15".to_string(),
+                "0.85".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+            )
+        };
+        let (salary, fs, st, hours, pto, util, benefits, expenses) = args("150000");
+        let rate =
+            salary_to_contract_rate(salary, fs, st, hours, pto, util, benefits, expenses).unwrap();
+        assert_eq!(rate.salary, "150000");
+        assert_eq!(rate.billable_hours, "1666");
+
+        let (_, fs, st, hours, pto, _, benefits, expenses) = args("");
+        let zero_utilization = contract_rate_to_salary(
+            "100".to_string(),
+            fs,
+            st,
+            hours,
+            pto,
+            "0".to_string(),
+            benefits,
+            expenses,
+        );
+        assert!(zero_utilization.is_err());
+    }
+
+    #[test]
+    fn test_compare_offers_ffi() {
+        let package = |name: &str, salary: &str, state: &str| CompensationPackageFFI {
+            name: name.to_string(),
+            base_salary: salary.to_string(),
+            bonus_percent: "0.1".to_string(),
+            equity_vesting: "0".to_string(),
+            state_code: state.to_string(),
+            filing_status: "single".to_string(),
+            traditional_401k: "0".to_string(),
+            employer_health_premiums: "0".to_string(),
+            employer_hsa_contribution: "0".to_string(),
+            employer_life_insurance: "0".to_string(),
+            employer_disability_insurance: "0".to_string(),
+            employer_benefits: "0".to_string(),
+        };
+
+        let ranked = compare_offers(vec![
+            package("Seattle", "180000", "WA"),
+            package("San Francisco", "190000", "CA"),
+        ])
+        .unwrap();
+
+        assert_eq!(ranked[0].rank, 1);
+        assert_eq!(ranked[0].name, "Seattle");
+        assert_eq!(ranked[0].gap_to_best, "0");
+        assert_eq!(ranked[1].total_compensation, "209000");
+        assert_eq!(ranked[1].benefits_value, "0");
+
+        assert!(compare_offers(vec![package("Nowhere", "100000", "ZZ")]).is_err());
+    }
+
+    #[test]
+    fn test_verify_paystub_ffi() {
+        let verify = |paystub: PaystubFFI| {
+            verify_paystub(
+                paystub,
+                "78000".to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "2".to_string(),
+            )
+            .unwrap()
+        };
+        let blank = PaystubFFI {
+            pay_frequency: "bi_weekly".to_string(),
+            gross: "3000".to_string(),
+            pre_tax_deductions: "0".to_string(),
+            federal_withholding: "0".to_string(),
+            social_security: "0".to_string(),
+            medicare: "0".to_string(),
+            state_withholding: "0".to_string(),
+            local_withholding: "0".to_string(),
+            state_payroll_programs: "0".to_string(),
+            net: "0".to_string(),
+        };
+
+        let expected = verify(blank).expected;
+        assert_eq!(expected.gross, "3000");
+        assert!(verify(expected.clone()).is_correct);
+
+        let mut paystub = expected;
+        paystub.federal_withholding = "900".to_string();
+        let verification = verify(paystub);
+        assert!(!verification.is_correct);
+        assert_eq!(verification.discrepancies[0].line, "Federal income tax");
+        assert!(verification.discrepancies[0].explanation.is_some());
+    }
+
+    #[test]
+    fn test_calculate_taxes_banded_ffi() {
+        let banded = |low: &str, expected: &str, high: &str| {
+            calculate_taxes_banded(
+                "100000".to_string(),
+                low.to_string(),
+                expected.to_string(),
+                high.to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+            )
+        };
+
+        let bands = banded("0", "10000", "20000").unwrap();
+        assert_eq!(bands.pessimistic.gross_annual, "100000");
+        assert_eq!(bands.optimistic.gross_annual, "120000");
+        assert!(banded("20000", "10000", "0").is_err());
+    }
+
+    #[test]
+    fn test_scenario_storage_ffi() {
+        let path = std::env::temp_dir()
+            .join(format!("takehome-ffi-{}", std::process::id()))
+            .join("scenarios.json");
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_string_lossy().to_string();
+
+        let storage = ScenarioStorage::open(path.clone()).unwrap();
+        storage
+            .save_scenario(
+                "Baseline".to_string(),
+                "100000".to_string(),
+                "single".to_string(),
+                "CA".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "5000".to_string(),
+                "0".to_string(),
+                true,
+            )
+            .unwrap();
+
+        let reopened = ScenarioStorage::open(path).unwrap();
+        assert_eq!(reopened.names(), vec!["Baseline"]);
+        let saved = reopened.load_scenario("Baseline".to_string()).unwrap();
+        assert_eq!(saved.state_code, "CA");
+        assert_eq!(saved.traditional_401k, "5000");
+        assert!(saved.result.is_some());
+
+        assert!(reopened
+            .rename_scenario("Missing".to_string(), "Other".to_string())
+            .is_err());
+        assert!(reopened.delete_scenario("Baseline".to_string()).unwrap());
+        assert!(reopened.load_scenario("Baseline".to_string()).is_none());
     }
 
     #[test]
@@ -420,11 +2503,259 @@ mod tests {
         assert!(s.primary_amount == "800" || s.primary_amount == "800.00");
     }
 
+    #[test]
+    fn test_side_income_ffi() {
+        let analyze = |kind: &str| {
+            analyze_side_income(
+                "90000".to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "20000".to_string(),
+                kind.to_string(),
+                "bi_weekly".to_string(),
+            )
+        };
+        let w2 = analyze("w2").unwrap();
+        assert_eq!(w2.after_tax_value, "14070");
+        let contract = analyze("1099").unwrap();
+        assert_eq!(contract.federal_withheld, "0");
+        assert!(contract.underpayment_risk);
+        assert!(analyze("barter").is_err());
+    }
+
+    #[test]
+    fn test_compare_overtime_comp_time_ffi() {
+        let comparison = compare_overtime_comp_time(
+            "108160".to_string(),
+            "single".to_string(),
+            "TX".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "10".to_string(),
+        )
+        .unwrap();
+        assert_eq!(comparison.comp_hours, "15");
+        assert_eq!(comparison.overtime_net, "548.73");
+        assert_eq!(comparison.comp_time_value, "548.73");
+        assert_eq!(comparison.comp_time_advantage, "0");
+    }
+
+    #[test]
+    fn test_simulate_paychecks_ffi() {
+        let simulate = |multiple_jobs: bool| {
+            simulate_paychecks(
+                "2000".to_string(),
+                "bi_weekly".to_string(),
+                "single".to_string(),
+                multiple_jobs,
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+            )
+            .unwrap()
+        };
+        let simulation = simulate(false);
+        assert_eq!(simulation.paychecks.len(), 26);
+        assert_eq!(simulation.paychecks[0].federal_income_tax, "163.69");
+        assert_eq!(simulation.paychecks[0].social_security, "124");
+        // Step 2 withholds each paycheck as half of two equal jobs
+        assert_eq!(simulate(true).paychecks[0].federal_income_tax, "283.1");
+
+        assert!(simulate_paychecks(
+            "-2000".to_string(),
+            "bi_weekly".to_string(),
+            "single".to_string(),
+            false,
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_compare_pay_spread_ffi() {
+        let args = |work_months: u32| {
+            compare_pay_spread(
+                "60000".to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                work_months,
+                "monthly".to_string(),
+            )
+        };
+        let spread = args(10).unwrap();
+        assert_eq!(spread.work_months_only.paychecks, 10);
+        assert_eq!(spread.work_months_only.gross_per_paycheck, "6000");
+        assert_eq!(spread.spread_over_twelve.gross_per_paycheck, "5000");
+        assert_eq!(spread.extra_withholding, "1184.76");
+
+        assert!(args(0).is_err());
+    }
+
+    #[test]
+    fn test_purchase_cost_ffi() {
+        let cost = get_purchase_cost(
+            "1407".to_string(),
+            "108160".to_string(),
+            "single".to_string(),
+            "TX".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        )
+        .unwrap();
+        assert_eq!(cost.pre_tax_earnings, "2000");
+        assert_eq!(cost.hours_of_work, "38.46");
+        assert_eq!(cost.marginal_rate, "0.2965");
+
+        assert!(get_purchase_cost(
+            "a lot".to_string(),
+            "108160".to_string(),
+            "single".to_string(),
+            "TX".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_itemized_household_split_ffi() {
+        let expense = |category: &str, amount: &str, split_method: &str| SharedExpenseFFI {
+            category: category.to_string(),
+            amount: amount.to_string(),
+            split_method: split_method.to_string(),
+        };
+
+        let result = calculate_itemized_household_split(
+            "6000".to_string(),
+            "4000".to_string(),
+            vec![
+                expense("housing", "3000", "proportional"),
+                expense("childcare", "1500", "custom:0.2"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(result.categories[1].category, "childcare");
+        assert_eq!(result.categories[1].split.primary_amount, "300");
+        assert_eq!(result.primary_total, "2100");
+
+        assert!(calculate_itemized_household_split(
+            "6000".to_string(),
+            "4000".to_string(),
+            vec![expense("yachts", "100", "equal")],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_engine_object_year_and_source() {
+        let args = || {
+            (
+                "100000".to_string(),
+                "single".to_string(),
+                "TX".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+            )
+        };
+        let calc = |engine: &TaxEngine| {
+            let (gross, status, state, pre, post, trad, roth) = args();
+            engine
+                .calculate_taxes(gross, status, state, pre, post, trad, roth)
+                .unwrap()
+        };
+
+        let current = TaxEngine::new(2024).unwrap();
+        let (gross, status, state, pre, post, trad, roth) = args();
+        assert_eq!(
+            calc(&current).federal_tax,
+            calculate_taxes(gross, status, state, pre, post, trad, roth)
+                .unwrap()
+                .federal_tax
+        );
+
+        // Indexed thresholds lower the tax on the same income in later years
+        let projected = TaxEngine::with_data_source(2027, "indexed:0.03".to_string()).unwrap();
+        assert_eq!(projected.year(), 2027);
+        let federal = |r: TaxResultFFI| r.federal_tax.parse::<Decimal>().unwrap();
+        assert!(federal(calc(&projected)) < federal(calc(&current)));
+
+        assert!(TaxEngine::new(2019).is_err());
+        assert!(TaxEngine::with_data_source(2024, "irs_live".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_engine_object_state_brackets_fallback() {
+        let engine = TaxEngine::new(2024).unwrap();
+        let zero = || "0".to_string();
+        for state in ["NY", "NJ", "GA", "VA", "OR", "AZ", "CA"] {
+            for status in [
+                "married_filing_jointly",
+                "head_of_household",
+                "married_filing_separately",
+            ] {
+                let result = engine
+                    .calculate_taxes(
+                        "100000".to_string(),
+                        status.to_string(),
+                        state.to_string(),
+                        zero(),
+                        zero(),
+                        zero(),
+                        zero(),
+                    )
+                    .unwrap();
+                let free = calculate_taxes(
+                    "100000".to_string(),
+                    status.to_string(),
+                    state.to_string(),
+                    zero(),
+                    zero(),
+                    zero(),
+                    zero(),
+                )
+                .unwrap();
+                assert_eq!(
+                    result.state_total_tax, free.state_total_tax,
+                    "{state} {status}"
+                );
+                assert_eq!(result.warnings, free.warnings, "{state} {status}");
+            }
+        }
+
+        let comparison = engine.compare_scenarios(
+            "100000".to_string(),
+            "married_filing_jointly".to_string(),
+            "NY".to_string(),
+            zero(),
+            zero(),
+            zero(),
+            zero(),
+            "100000".to_string(),
+            "married_filing_jointly".to_string(),
+            "TX".to_string(),
+            zero(),
+            zero(),
+            zero(),
+            zero(),
+        );
+        assert!(comparison.is_ok());
+    }
+
     #[test]
     fn test_state_codes() {
         let codes = get_all_state_codes();
-        assert_eq!(codes.len(), 51);
+        assert_eq!(codes.len(), 55); // 50 states + DC + 3 territories + none
         assert!(codes.contains(&"CA".to_string()));
+        assert!(codes.contains(&"PR".to_string()));
         assert!(codes.contains(&"TX".to_string()));
     }
 
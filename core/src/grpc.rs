@@ -0,0 +1,397 @@
+//! gRPC service over the calculation engine
+//!
+//! Implements `takehome.v1.TakeHome` from `proto/takehome.proto` for backend
+//! callers that speak gRPC rather than JSON over HTTP. Requests and results
+//! carry the same decimal strings as the mobile bindings, and conversions go
+//! through the same parsing, so every front end agrees to the cent.
+
+// `tonic::Status` is the error type of the generated service trait
+#![allow(clippy::result_large_err)]
+
+use std::net::SocketAddr;
+
+use tonic::{Request, Response, Status};
+
+use crate::data::embedded::get_embedded_data;
+use crate::data::TaxDataProvider;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::ffi::{parse_input, ScenarioComparisonFFI, TaxCalcError, TaxResultFFI};
+use crate::models::state::USState;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TaxInput {
+    #[prost(string, tag = "1")]
+    pub gross_income: String,
+    #[prost(string, tag = "2")]
+    pub filing_status: String,
+    #[prost(string, tag = "3")]
+    pub state: String,
+    #[prost(string, tag = "4")]
+    pub pre_tax_deductions: String,
+    #[prost(string, tag = "5")]
+    pub post_tax_deductions: String,
+    #[prost(string, tag = "6")]
+    pub traditional_401k: String,
+    #[prost(string, tag = "7")]
+    pub roth_401k: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PayrollContribution {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TaxResult {
+    #[prost(string, tag = "1")]
+    pub gross_annual: String,
+    #[prost(string, tag = "2")]
+    pub net_annual: String,
+    #[prost(string, tag = "3")]
+    pub net_monthly: String,
+    #[prost(string, tag = "4")]
+    pub net_biweekly: String,
+    #[prost(string, tag = "5")]
+    pub take_home_percentage: String,
+    #[prost(string, tag = "6")]
+    pub federal_tax: String,
+    #[prost(string, tag = "7")]
+    pub federal_effective_rate: String,
+    #[prost(string, tag = "8")]
+    pub federal_marginal_rate: String,
+    #[prost(string, tag = "9")]
+    pub state_code: String,
+    #[prost(string, tag = "10")]
+    pub state_income_tax: String,
+    #[prost(string, tag = "11")]
+    pub state_local_tax: String,
+    #[prost(message, repeated, tag = "12")]
+    pub state_payroll_programs: Vec<PayrollContribution>,
+    #[prost(string, tag = "13")]
+    pub state_total_tax: String,
+    #[prost(string, tag = "14")]
+    pub social_security: String,
+    #[prost(string, tag = "15")]
+    pub medicare: String,
+    #[prost(string, tag = "16")]
+    pub additional_medicare: String,
+    #[prost(string, tag = "17")]
+    pub fica_total: String,
+    #[prost(string, tag = "18")]
+    pub total_taxes: String,
+    #[prost(string, tag = "19")]
+    pub total_effective_rate: String,
+    #[prost(string, repeated, tag = "20")]
+    pub warnings: Vec<String>,
+    #[prost(string, repeated, tag = "21")]
+    pub explanations: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompareRequest {
+    #[prost(message, optional, tag = "1")]
+    pub base: Option<TaxInput>,
+    #[prost(message, optional, tag = "2")]
+    pub scenario: Option<TaxInput>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ScenarioComparison {
+    #[prost(message, optional, tag = "1")]
+    pub base: Option<TaxResult>,
+    #[prost(message, optional, tag = "2")]
+    pub scenario: Option<TaxResult>,
+    #[prost(string, tag = "3")]
+    pub net_difference: String,
+    #[prost(string, tag = "4")]
+    pub monthly_difference: String,
+    #[prost(string, tag = "5")]
+    pub federal_difference: String,
+    #[prost(string, tag = "6")]
+    pub state_difference: String,
+    #[prost(string, tag = "7")]
+    pub fica_difference: String,
+    #[prost(string, tag = "8")]
+    pub effective_rate_difference: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListStatesRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct State {
+    #[prost(string, tag = "1")]
+    pub code: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(bool, tag = "3")]
+    pub has_income_tax: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListStatesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub states: Vec<State>,
+}
+
+// Generated `take_home_client` and `take_home_server` modules
+include!(concat!(env!("OUT_DIR"), "/takehome.v1.TakeHome.rs"));
+
+pub use take_home_client::TakeHomeClient;
+pub use take_home_server::{TakeHome, TakeHomeServer};
+
+impl From<TaxResultFFI> for TaxResult {
+    fn from(r: TaxResultFFI) -> Self {
+        Self {
+            gross_annual: r.gross_annual,
+            net_annual: r.net_annual,
+            net_monthly: r.net_monthly,
+            net_biweekly: r.net_biweekly,
+            take_home_percentage: r.take_home_percentage,
+            federal_tax: r.federal_tax,
+            federal_effective_rate: r.federal_effective_rate,
+            federal_marginal_rate: r.federal_marginal_rate,
+            state_code: r.state_code,
+            state_income_tax: r.state_income_tax,
+            state_local_tax: r.state_local_tax,
+            state_payroll_programs: r
+                .state_payroll_programs
+                .into_iter()
+                .map(|p| PayrollContribution {
+                    name: p.name,
+                    amount: p.amount,
+                })
+                .collect(),
+            state_total_tax: r.state_total_tax,
+            social_security: r.social_security,
+            medicare: r.medicare,
+            additional_medicare: r.additional_medicare,
+            fica_total: r.fica_total,
+            total_taxes: r.total_taxes,
+            total_effective_rate: r.total_effective_rate,
+            warnings: r.warnings,
+            explanations: r.explanations,
+        }
+    }
+}
+
+impl From<ScenarioComparisonFFI> for ScenarioComparison {
+    fn from(c: ScenarioComparisonFFI) -> Self {
+        Self {
+            base: Some(c.base.into()),
+            scenario: Some(c.scenario.into()),
+            net_difference: c.net_difference,
+            monthly_difference: c.monthly_difference,
+            federal_difference: c.federal_difference,
+            state_difference: c.state_difference,
+            fica_difference: c.fica_difference,
+            effective_rate_difference: c.effective_rate_difference,
+        }
+    }
+}
+
+impl From<TaxCalcError> for Status {
+    fn from(e: TaxCalcError) -> Self {
+        match e {
+            TaxCalcError::CalculationError { .. } | TaxCalcError::StorageError { .. } => {
+                Status::failed_precondition(e.to_string())
+            },
+            _ => Status::invalid_argument(e.to_string()),
+        }
+    }
+}
+
+/// Deduction amounts left unset on the wire mean none
+fn amount_or_zero(amount: &str) -> &str {
+    if amount.trim().is_empty() {
+        "0"
+    } else {
+        amount
+    }
+}
+
+/// `TakeHome` implementation over a tax data provider and year
+#[derive(Clone, Copy)]
+pub struct TakeHomeService {
+    data: &'static dyn TaxDataProvider,
+    year: u32,
+}
+
+impl TakeHomeService {
+    /// Service over the embedded data for a tax year
+    pub fn new(year: u32) -> Self {
+        Self {
+            data: get_embedded_data(),
+            year,
+        }
+    }
+
+    fn engine(&self) -> TaxCalculationEngine<'static> {
+        TaxCalculationEngine::new(self.data, self.year)
+    }
+
+    /// Parse, validate, and check data coverage for one input message
+    fn input(&self, message: &TaxInput) -> Result<TaxCalculationInput, Status> {
+        let input = parse_input(
+            &message.gross_income,
+            &message.filing_status,
+            &message.state,
+            amount_or_zero(&message.pre_tax_deductions),
+            amount_or_zero(&message.post_tax_deductions),
+            amount_or_zero(&message.traditional_401k),
+            amount_or_zero(&message.roth_401k),
+        )?;
+        input
+            .validate()
+            .and_then(|()| self.engine().validate_data(&input))
+            .map_err(TaxCalcError::from)?;
+        Ok(input)
+    }
+
+    fn calculate_message(&self, message: &TaxInput) -> Result<TaxResult, Status> {
+        let input = self.input(message)?;
+        Ok(TaxResultFFI::from(self.engine().calculate(&input)).into())
+    }
+
+    fn compare_message(&self, request: &CompareRequest) -> Result<ScenarioComparison, Status> {
+        let required = |input: &Option<TaxInput>, field: &str| {
+            input
+                .as_ref()
+                .ok_or_else(|| Status::invalid_argument(format!("missing {field}")))
+                .and_then(|message| self.input(message))
+        };
+        let base = required(&request.base, "base")?;
+        let scenario = required(&request.scenario, "scenario")?;
+        let comparison = self.engine().compare_scenarios(&base, &scenario);
+        Ok(ScenarioComparisonFFI::from(comparison).into())
+    }
+}
+
+fn states() -> ListStatesResponse {
+    ListStatesResponse {
+        states: USState::all()
+            .iter()
+            .map(|state| State {
+                code: state.code().to_string(),
+                name: state.name().to_string(),
+                has_income_tax: !state.has_no_income_tax(),
+            })
+            .collect(),
+    }
+}
+
+#[tonic::async_trait]
+impl TakeHome for TakeHomeService {
+    async fn calculate(&self, request: Request<TaxInput>) -> Result<Response<TaxResult>, Status> {
+        self.calculate_message(request.get_ref()).map(Response::new)
+    }
+
+    async fn compare(
+        &self,
+        request: Request<CompareRequest>,
+    ) -> Result<Response<ScenarioComparison>, Status> {
+        self.compare_message(request.get_ref()).map(Response::new)
+    }
+
+    async fn list_states(
+        &self,
+        _request: Request<ListStatesRequest>,
+    ) -> Result<Response<ListStatesResponse>, Status> {
+        Ok(Response::new(states()))
+    }
+}
+
+/// Serve `TakeHome` on `addr` until the process exits
+pub async fn serve(addr: SocketAddr, year: u32) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(TakeHomeServer::new(TakeHomeService::new(year)))
+        .serve(addr)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    fn input(gross_income: &str, state: &str) -> TaxInput {
+        TaxInput {
+            gross_income: gross_income.to_string(),
+            filing_status: "single".to_string(),
+            state: state.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calculate_matches_ffi() {
+        let service = TakeHomeService::new(2024);
+        let result = service.calculate_message(&input("100000", "CA")).unwrap();
+
+        let ffi = crate::ffi::calculate_taxes(
+            "100000".to_string(),
+            "single".to_string(),
+            "CA".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        )
+        .unwrap();
+        assert_eq!(result.gross_annual, "100000");
+        assert_eq!(result.net_annual, ffi.net_annual);
+        assert_eq!(result.state_payroll_programs.len(), 1);
+    }
+
+    #[test]
+    fn test_compare_and_errors() {
+        let service = TakeHomeService::new(2024);
+        let comparison = service
+            .compare_message(&CompareRequest {
+                base: Some(input("100000", "CA")),
+                scenario: Some(input("100000", "TX")),
+            })
+            .unwrap();
+        assert!(comparison.net_difference.parse::<f64>().unwrap() > 0.0);
+        assert_eq!(comparison.scenario.unwrap().state_code, "TX");
+
+        let missing = service.compare_message(&CompareRequest {
+            base: Some(input("1", "CA")),
+            scenario: None,
+        });
+        assert_eq!(missing.unwrap_err().code(), Code::InvalidArgument);
+
+        let bad_state = service.calculate_message(&input("100000", "ZZ"));
+        assert_eq!(bad_state.unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_state_brackets_fallback_is_a_warning() {
+        let service = TakeHomeService::new(2024);
+        let joint = TaxInput {
+            filing_status: "married_filing_jointly".to_string(),
+            ..input("100000", "NY")
+        };
+        let result = service.calculate_message(&joint).unwrap();
+        assert!(result.state_income_tax.parse::<f64>().unwrap() > 0.0);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("single brackets were used")));
+    }
+
+    #[test]
+    fn test_messages_round_trip_on_the_wire() {
+        use prost::Message;
+
+        let listing = states();
+        let decoded = ListStatesResponse::decode(listing.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, listing);
+        assert_eq!(decoded.states.len(), USState::all().len());
+        let texas = decoded.states.iter().find(|s| s.code == "TX").unwrap();
+        assert!(!texas.has_income_tax);
+    }
+}
@@ -7,10 +7,17 @@
 // Allow the function pointer comparison warning from UniFFI macro
 #![allow(unpredictable_function_pointer_comparisons)]
 
+pub mod analysis;
 pub mod calculators;
 pub mod data;
 pub mod engine;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod models;
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod storage;
 
 mod ffi;
 
@@ -18,12 +25,26 @@ mod ffi;
 uniffi::setup_scaffolding!();
 
 pub use engine::{
-    ScenarioComparison, TaxCalculationEngine, TaxCalculationInput, TaxCalculationResult,
+    CalcWarning, CalculationError, ComponentDelta, ResultBands, ScenarioComparison,
+    TakeHomeDistribution, TaxCalculationEngine, TaxCalculationInput, TaxCalculationInputBuilder,
+    TaxCalculationResult, ValueRange,
 };
 pub use ffi::TaxCalcError;
-pub use models::income::{CalculatedIncome, IncomeInput, PayFrequency, TimeframeIncome};
+pub use models::income::{
+    CalculatedIncome, IncomeInput, PayFrequency, TimeframeIncome, WorkSchedule,
+};
+pub use models::locale::{Locale, LocalizedName};
+pub use models::money::Money;
+pub use models::profile::{UserProfile, W4Settings};
+pub use models::rate::Rate;
+pub use models::retirement::RetirementIncome;
+pub use models::simulation::SimulationConfig;
 pub use models::state::USState;
-pub use models::tax::{FederalTaxResult, FicaResult, FilingStatus, StateTaxResult, TaxBreakdown};
+pub use models::tax::{
+    BreakdownConfidence, BreakdownExplanations, Confidence, FederalTaxResult, FicaResult,
+    FilingStatus, PayrollContribution, PayrollProgramKind, StateTaxResult, TaxBreakdown,
+};
+pub use models::ParseEnumError;
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
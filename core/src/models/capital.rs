@@ -0,0 +1,150 @@
+//! Capital gains and losses
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::tax::FilingStatus;
+
+/// Net capital losses deductible against other income each year
+const CAPITAL_LOSS_LIMIT: Decimal = dec!(3000);
+
+/// Unused capital losses carried into the next year, as positive amounts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapitalLossCarryover {
+    pub short_term: Decimal,
+    pub long_term: Decimal,
+}
+
+impl CapitalLossCarryover {
+    pub fn total(&self) -> Decimal {
+        self.short_term + self.long_term
+    }
+}
+
+/// A year's realized capital gains and losses
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapitalGains {
+    /// Net short-term gain for the year; negative for a net loss
+    pub short_term: Decimal,
+    /// Net long-term gain for the year; negative for a net loss
+    pub long_term: Decimal,
+    /// Losses carried in from earlier years
+    #[serde(default)]
+    pub carryover: CapitalLossCarryover,
+}
+
+/// Schedule D netting of a year's gains, losses, and carryover
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapitalGainsNetting {
+    /// Net short-term gain after all losses
+    pub short_term_gain: Decimal,
+    /// Net long-term gain after all losses
+    pub long_term_gain: Decimal,
+    /// Net loss deducted from other income, up to $3,000
+    pub loss_deduction: Decimal,
+    /// Loss left for next year
+    pub carryover: CapitalLossCarryover,
+}
+
+impl CapitalGainsNetting {
+    /// Amount added to (or, for a loss, subtracted from) other income
+    pub fn income(&self) -> Decimal {
+        self.short_term_gain + self.long_term_gain - self.loss_deduction
+    }
+}
+
+impl CapitalGains {
+    /// Net short- and long-term results, applying carried-in losses first
+    ///
+    /// Losses in one term offset gains in the other. A combined net loss is
+    /// deductible up to $3,000 ($1,500 married filing separately), taken
+    /// from short-term losses before long-term ones; the rest carries over
+    /// keeping its character.
+    pub fn net(&self, filing_status: FilingStatus) -> CapitalGainsNetting {
+        let short_term = self.short_term - self.carryover.short_term;
+        let long_term = self.long_term - self.carryover.long_term;
+        let combined = short_term + long_term;
+
+        if combined >= Decimal::ZERO {
+            return CapitalGainsNetting {
+                short_term_gain: short_term.min(combined).max(Decimal::ZERO),
+                long_term_gain: long_term.min(combined).max(Decimal::ZERO),
+                ..Default::default()
+            };
+        }
+
+        let limit = if filing_status == FilingStatus::MarriedFilingSeparately {
+            CAPITAL_LOSS_LIMIT / dec!(2)
+        } else {
+            CAPITAL_LOSS_LIMIT
+        };
+        let loss_deduction = (-combined).min(limit);
+        let short_term_loss = (-short_term).max(Decimal::ZERO);
+        let long_term_loss = (-long_term).max(Decimal::ZERO);
+        let short_term_gain = short_term.max(Decimal::ZERO);
+        let long_term_gain = long_term.max(Decimal::ZERO);
+
+        CapitalGainsNetting {
+            short_term_gain: Decimal::ZERO,
+            long_term_gain: Decimal::ZERO,
+            loss_deduction,
+            carryover: CapitalLossCarryover {
+                short_term: (short_term_loss - long_term_gain - loss_deduction).max(Decimal::ZERO),
+                long_term: (long_term_loss
+                    - short_term_gain
+                    - (loss_deduction - short_term_loss).max(Decimal::ZERO))
+                .max(Decimal::ZERO),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gains(short_term: Decimal, long_term: Decimal) -> CapitalGains {
+        CapitalGains {
+            short_term,
+            long_term,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_losses_offset_gains_across_terms() {
+        let netting = gains(dec!(-2000), dec!(5000)).net(FilingStatus::Single);
+        assert_eq!(netting.short_term_gain, dec!(0));
+        assert_eq!(netting.long_term_gain, dec!(3000));
+        assert_eq!(netting.income(), dec!(3000));
+        assert_eq!(netting.carryover.total(), dec!(0));
+    }
+
+    #[test]
+    fn test_loss_limit_and_carryover_character() {
+        let netting = gains(dec!(-5000), dec!(-2000)).net(FilingStatus::Single);
+        assert_eq!(netting.loss_deduction, dec!(3000));
+        assert_eq!(netting.income(), dec!(-3000));
+        // The deduction uses short-term losses first
+        assert_eq!(netting.carryover.short_term, dec!(2000));
+        assert_eq!(netting.carryover.long_term, dec!(2000));
+
+        let netting = gains(dec!(2000), dec!(-10000)).net(FilingStatus::MarriedFilingSeparately);
+        assert_eq!(netting.loss_deduction, dec!(1500));
+        assert_eq!(netting.carryover.short_term, dec!(0));
+        assert_eq!(netting.carryover.long_term, dec!(6500));
+
+        // A carryover absorbs this year's gains before any deduction
+        let next_year = CapitalGains {
+            carryover: CapitalLossCarryover {
+                short_term: dec!(0),
+                long_term: dec!(6500),
+            },
+            ..gains(dec!(1000), dec!(0))
+        };
+        let netting = next_year.net(FilingStatus::Single);
+        assert_eq!(netting.loss_deduction, dec!(3000));
+        assert_eq!(netting.carryover.long_term, dec!(2500));
+    }
+}
@@ -1,8 +1,12 @@
 //! Deduction models
 
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use super::income::PayFrequency;
+use super::tax::FilingStatus;
+
 /// Types of deductions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeductionType {
@@ -70,6 +74,13 @@ pub struct Deduction {
     pub frequency: DeductionFrequency,
     pub periods_per_year: u32,
     pub is_pre_tax: bool,
+    /// First pay period (1-based) the deduction is taken, when it starts
+    /// mid-year
+    #[serde(default)]
+    pub start_period: Option<u32>,
+    /// Last pay period the deduction is taken, when it stops mid-year
+    #[serde(default)]
+    pub end_period: Option<u32>,
 }
 
 impl Deduction {
@@ -86,11 +97,80 @@ impl Deduction {
             frequency,
             periods_per_year,
             is_pre_tax: deduction_type.is_pre_tax(),
+            start_period: None,
+            end_period: None,
+        }
+    }
+
+    /// Deduction taken from every paycheck at the given pay frequency
+    pub fn per_paycheck(
+        deduction_type: DeductionType,
+        amount: Decimal,
+        pay_frequency: PayFrequency,
+    ) -> Self {
+        Self::new(
+            deduction_type,
+            amount,
+            DeductionFrequency::PerPaycheck,
+            pay_frequency.periods_per_year(),
+        )
+    }
+
+    /// Start taking the deduction in pay period `period`
+    pub fn starting(mut self, period: u32) -> Self {
+        self.start_period = Some(period);
+        self
+    }
+
+    /// Stop taking the deduction after pay period `period`
+    pub fn ending(mut self, period: u32) -> Self {
+        self.end_period = Some(period);
+        self
+    }
+
+    /// Whether the deduction is taken in a pay period (1-based)
+    pub fn is_active(&self, period: u32) -> bool {
+        (1..=self.periods_per_year).contains(&period)
+            && self.start_period.is_none_or(|start| period >= start)
+            && self.end_period.is_none_or(|end| period <= end)
+    }
+
+    /// Pay periods the deduction is taken in
+    pub fn active_periods(&self) -> u32 {
+        (1..=self.periods_per_year)
+            .filter(|&period| self.is_active(period))
+            .count() as u32
+    }
+
+    /// Amount taken in a pay period
+    ///
+    /// Monthly amounts are spread evenly over the year's paychecks. An
+    /// annual election (an FSA starting after open enrollment, say) is
+    /// spread over the paychecks it's active in.
+    pub fn amount_for_period(&self, period: u32) -> Decimal {
+        if !self.is_active(period) {
+            return Decimal::ZERO;
+        }
+        let periods = Decimal::from(self.periods_per_year.max(1));
+        match self.frequency {
+            DeductionFrequency::PerPaycheck => self.amount,
+            DeductionFrequency::Monthly => self.amount * Decimal::from(12) / periods,
+            DeductionFrequency::Annual => self.amount / Decimal::from(self.active_periods()),
         }
     }
 
-    /// Calculate annual amount
+    /// Amount taken through the end of pay period `period`
+    pub fn year_to_date(&self, period: u32) -> Decimal {
+        (1..=period.min(self.periods_per_year))
+            .map(|period| self.amount_for_period(period))
+            .sum()
+    }
+
+    /// Calculate annual amount, counting only the periods it's active
     pub fn annual_amount(&self) -> Decimal {
+        if self.start_period.is_some() || self.end_period.is_some() {
+            return self.year_to_date(self.periods_per_year);
+        }
         match self.frequency {
             DeductionFrequency::PerPaycheck => self.amount * Decimal::from(self.periods_per_year),
             DeductionFrequency::Monthly => self.amount * Decimal::from(12),
@@ -99,6 +179,163 @@ impl Deduction {
     }
 }
 
+/// High-deductible health plan coverage tier, which sets the HSA limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HsaCoverage {
+    SelfOnly,
+    Family,
+}
+
+/// Kind of order garnishing wages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GarnishmentKind {
+    /// Child support or alimony
+    Support {
+        /// Whether the earner supports another spouse or child
+        supports_other_family: bool,
+        /// Whether payments are more than 12 weeks in arrears
+        arrears_over_12_weeks: bool,
+    },
+    /// Administrative wage garnishment for defaulted federal student loans
+    StudentLoan,
+    /// Judgment for an ordinary debt
+    Creditor,
+}
+
+impl GarnishmentKind {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            GarnishmentKind::Support { .. } => "Child Support",
+            GarnishmentKind::StudentLoan => "Student Loan Garnishment",
+            GarnishmentKind::Creditor => "Creditor Garnishment",
+        }
+    }
+
+    pub fn is_support(&self) -> bool {
+        matches!(self, GarnishmentKind::Support { .. })
+    }
+}
+
+/// A garnishment order, withheld from pay after taxes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Garnishment {
+    pub kind: GarnishmentKind,
+    /// Annual amount the order asks for, before legal limits
+    pub annual_amount: Decimal,
+}
+
+impl Garnishment {
+    pub fn new(kind: GarnishmentKind, annual_amount: Decimal) -> Self {
+        Self {
+            kind,
+            annual_amount,
+        }
+    }
+
+    /// Order for a fixed amount from every paycheck
+    pub fn per_paycheck(
+        kind: GarnishmentKind,
+        amount: Decimal,
+        pay_frequency: PayFrequency,
+    ) -> Self {
+        Self::new(
+            kind,
+            amount * Decimal::from(pay_frequency.periods_per_year()),
+        )
+    }
+}
+
+/// Cap on the state and local tax deduction, and the floor the expanded
+/// cap phases down to
+const SALT_CAP: Decimal = dec!(10000);
+
+/// Expanded SALT cap for 2025, rising 1% a year through 2029 (One Big
+/// Beautiful Bill Act)
+const EXPANDED_SALT_CAP: Decimal = dec!(40000);
+
+/// MAGI above which the expanded cap phases down, indexed like the cap
+const EXPANDED_SALT_PHASE_DOWN_START: Decimal = dec!(500000);
+
+/// Share of MAGI over the threshold taken off the expanded cap
+const EXPANDED_SALT_PHASE_DOWN_RATE: Decimal = dec!(0.30);
+
+/// Years the expanded cap applies
+const EXPANDED_SALT_YEARS: std::ops::RangeInclusive<u32> = 2025..=2029;
+
+/// Only medical expenses above this share of AGI are deductible
+const MEDICAL_AGI_FLOOR: Decimal = dec!(0.075);
+
+/// Cash gifts to public charities are deductible up to this share of AGI
+const CHARITABLE_AGI_LIMIT: Decimal = dec!(0.60);
+
+/// Schedule A expenses, deducted instead of the standard deduction when larger
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemizedDeductions {
+    /// State and local income (or sales) taxes paid, and property taxes the
+    /// input doesn't already give as `property_taxes`
+    pub state_and_local_taxes: Decimal,
+    pub mortgage_interest: Decimal,
+    /// Cash gifts to public charities
+    pub charitable: Decimal,
+    /// Unreimbursed medical and dental expenses
+    pub medical: Decimal,
+    /// Gambling losses, deductible only up to the year's winnings
+    #[serde(default)]
+    pub gambling_losses: Decimal,
+}
+
+impl ItemizedDeductions {
+    /// Deductible total after the SALT cap, the medical floor, the
+    /// charitable AGI limit (excess gifts carry forward, not modeled here),
+    /// and the cap of gambling losses at `gambling_winnings`
+    pub fn total(
+        &self,
+        agi: Decimal,
+        filing_status: FilingStatus,
+        gambling_winnings: Decimal,
+        year: u32,
+    ) -> Decimal {
+        let agi = agi.max(Decimal::ZERO);
+        self.state_and_local_taxes
+            .min(Self::salt_cap(agi, filing_status, year))
+            + self.mortgage_interest
+            + self.charitable.min(agi * CHARITABLE_AGI_LIMIT)
+            + (self.medical - agi * MEDICAL_AGI_FLOOR).max(Decimal::ZERO)
+            + self
+                .gambling_losses
+                .min(gambling_winnings.max(Decimal::ZERO))
+    }
+
+    /// SALT cap for `year`, halved for married filing separately. From 2025
+    /// the expanded cap shrinks by 30% of MAGI over the threshold, but never
+    /// below the original cap.
+    pub fn salt_cap(magi: Decimal, filing_status: FilingStatus, year: u32) -> Decimal {
+        let share = if filing_status == FilingStatus::MarriedFilingSeparately {
+            dec!(0.5)
+        } else {
+            Decimal::ONE
+        };
+        if !EXPANDED_SALT_YEARS.contains(&year) {
+            return SALT_CAP * share;
+        }
+        let growth = (*EXPANDED_SALT_YEARS.start()..year).fold(Decimal::ONE, |g, _| g * dec!(1.01));
+        let cap = (EXPANDED_SALT_CAP * growth * share).round_dp(0);
+        let threshold = (EXPANDED_SALT_PHASE_DOWN_START * growth * share).round_dp(0);
+        let reduction = (magi - threshold).max(Decimal::ZERO) * EXPANDED_SALT_PHASE_DOWN_RATE;
+        (cap - reduction).max(SALT_CAP * share)
+    }
+
+    pub(crate) fn amounts(&self) -> [(&'static str, Decimal); 5] {
+        [
+            ("state_and_local_taxes", self.state_and_local_taxes),
+            ("mortgage_interest", self.mortgage_interest),
+            ("charitable", self.charitable),
+            ("medical", self.medical),
+            ("gambling_losses", self.gambling_losses),
+        ]
+    }
+}
+
 /// Retirement contributions
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RetirementContributions {
@@ -146,3 +383,81 @@ impl DeductionsSummary {
         self.pre_tax_total + self.post_tax_total + self.retirement.total_employee_contributions()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mid_year_start_and_stop() {
+        // FSA election of $1,300 beginning with the 14th biweekly paycheck
+        let fsa = Deduction::new(
+            DeductionType::Fsa,
+            dec!(1300),
+            DeductionFrequency::Annual,
+            26,
+        )
+        .starting(14);
+        assert_eq!(fsa.active_periods(), 13);
+        assert_eq!(fsa.amount_for_period(13), dec!(0));
+        assert_eq!(fsa.amount_for_period(14), dec!(100));
+        assert_eq!(fsa.year_to_date(20), dec!(700));
+        assert_eq!(fsa.annual_amount(), dec!(1300));
+
+        // $200 a paycheck, raised to $500 for the last six paychecks
+        let before = Deduction::per_paycheck(
+            DeductionType::Traditional401k,
+            dec!(200),
+            PayFrequency::BiWeekly,
+        )
+        .ending(20);
+        let after = Deduction::per_paycheck(
+            DeductionType::Traditional401k,
+            dec!(500),
+            PayFrequency::BiWeekly,
+        )
+        .starting(21);
+        assert_eq!(before.annual_amount() + after.annual_amount(), dec!(7000));
+        assert_eq!(before.year_to_date(26), dec!(4000));
+
+        // Monthly premiums spread over the active paychecks
+        let health = Deduction::new(
+            DeductionType::HealthInsurance,
+            dec!(260),
+            DeductionFrequency::Monthly,
+            26,
+        )
+        .starting(14);
+        assert_eq!(health.amount_for_period(14), dec!(120));
+        assert_eq!(health.annual_amount(), dec!(1560));
+
+        // Without dates the annual amount is unchanged
+        let dues = Deduction::new(
+            DeductionType::UnionDues,
+            dec!(50),
+            DeductionFrequency::Monthly,
+            26,
+        );
+        assert_eq!(dues.annual_amount(), dec!(600));
+        assert!(!dues.is_active(27));
+    }
+
+    #[test]
+    fn test_salt_cap_by_year() {
+        let cap = ItemizedDeductions::salt_cap;
+        let mfs = FilingStatus::MarriedFilingSeparately;
+
+        assert_eq!(cap(dec!(300000), FilingStatus::Single, 2024), dec!(10000));
+        assert_eq!(cap(dec!(300000), mfs, 2024), dec!(5000));
+
+        // 2025: $40,000 less 30% of MAGI over $500,000, down to $10,000
+        assert_eq!(cap(dec!(300000), FilingStatus::Single, 2025), dec!(40000));
+        assert_eq!(cap(dec!(550000), FilingStatus::Single, 2025), dec!(25000));
+        assert_eq!(cap(dec!(700000), FilingStatus::Single, 2025), dec!(10000));
+        assert_eq!(cap(dec!(260000), mfs, 2025), dec!(17000));
+        assert_eq!(cap(dec!(400000), mfs, 2025), dec!(5000));
+
+        assert_eq!(cap(dec!(300000), FilingStatus::Single, 2026), dec!(40400));
+        assert_eq!(cap(dec!(300000), FilingStatus::Single, 2030), dec!(10000));
+    }
+}
@@ -0,0 +1,152 @@
+//! Dependents and the qualifying-person test for head of household
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::ParseEnumError;
+
+/// Age a child must be under to qualify without being a student
+const CHILD_AGE_LIMIT: u32 = 19;
+
+/// Age a full-time student must be under to qualify
+const STUDENT_AGE_LIMIT: u32 = 24;
+
+/// Age a child must be under for the Child Tax Credit
+const CHILD_TAX_CREDIT_AGE_LIMIT: u32 = 17;
+
+/// How a dependent is related to the taxpayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DependentRelationship {
+    /// Son, daughter, stepchild, foster child, sibling, or their descendant
+    #[default]
+    Child,
+    Parent,
+    /// Any other relative the taxpayer supports
+    OtherRelative,
+}
+
+impl FromStr for DependentRelationship {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "child" => Ok(DependentRelationship::Child),
+            "parent" => Ok(DependentRelationship::Parent),
+            "other_relative" => Ok(DependentRelationship::OtherRelative),
+            _ => Err(ParseEnumError::new("dependent relationship", s)),
+        }
+    }
+}
+
+/// A person the taxpayer claims
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Dependent {
+    pub relationship: DependentRelationship,
+    /// Age at the end of the tax year
+    pub age: u32,
+    /// Months of the year lived in the taxpayer's home
+    pub months_in_home: u32,
+    #[serde(default)]
+    pub full_time_student: bool,
+    #[serde(default)]
+    pub permanently_disabled: bool,
+}
+
+impl Dependent {
+    /// A child who lived with the taxpayer all year
+    pub fn child(age: u32) -> Self {
+        Self {
+            relationship: DependentRelationship::Child,
+            age,
+            months_in_home: 12,
+            ..Default::default()
+        }
+    }
+
+    fn lived_with_taxpayer(&self) -> bool {
+        self.months_in_home > 6
+    }
+
+    /// Qualifying child: a child who lived with the taxpayer more than half
+    /// the year and is under 19, a student under 24, or disabled. This is
+    /// also the EITC's qualifying child.
+    pub fn is_qualifying_child(&self) -> bool {
+        self.relationship == DependentRelationship::Child
+            && self.lived_with_taxpayer()
+            && (self.age < CHILD_AGE_LIMIT
+                || (self.full_time_student && self.age < STUDENT_AGE_LIMIT)
+                || self.permanently_disabled)
+    }
+
+    /// Qualifying child under 17, eligible for the Child Tax Credit
+    pub fn qualifies_for_child_tax_credit(&self) -> bool {
+        self.is_qualifying_child() && self.age < CHILD_TAX_CREDIT_AGE_LIMIT
+    }
+
+    /// Whether this person makes the taxpayer eligible for head of household
+    ///
+    /// A child must be a qualifying child. A dependent parent qualifies
+    /// without living there. Other relatives must live there more than half
+    /// the year. Support and gross income tests are assumed met.
+    pub fn qualifies_for_head_of_household(&self) -> bool {
+        match self.relationship {
+            DependentRelationship::Child => self.is_qualifying_child(),
+            DependentRelationship::Parent => true,
+            DependentRelationship::OtherRelative => self.lived_with_taxpayer(),
+        }
+    }
+
+    /// Whether this person lets a surviving spouse keep qualifying surviving
+    /// spouse status: a child who lived in the home all year, at any age.
+    /// Foster children and siblings don't count, but aren't told apart here.
+    pub fn qualifies_for_surviving_spouse(&self) -> bool {
+        self.relationship == DependentRelationship::Child && self.months_in_home == 12
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_of_household_qualifying_person() {
+        assert!(Dependent::child(10).qualifies_for_head_of_household());
+        assert!(!Dependent::child(20).qualifies_for_head_of_household());
+        assert!(Dependent {
+            full_time_student: true,
+            ..Dependent::child(22)
+        }
+        .qualifies_for_head_of_household());
+        assert!(Dependent {
+            permanently_disabled: true,
+            ..Dependent::child(40)
+        }
+        .qualifies_for_head_of_household());
+        // Half the year isn't more than half
+        assert!(!Dependent {
+            months_in_home: 6,
+            ..Dependent::child(10)
+        }
+        .qualifies_for_head_of_household());
+
+        let parent = Dependent {
+            relationship: DependentRelationship::Parent,
+            age: 75,
+            months_in_home: 0,
+            ..Default::default()
+        };
+        assert!(parent.qualifies_for_head_of_household());
+        assert!(!Dependent {
+            relationship: DependentRelationship::OtherRelative,
+            ..parent
+        }
+        .qualifies_for_head_of_household());
+    }
+
+    #[test]
+    fn test_child_tax_credit_age() {
+        assert!(Dependent::child(16).qualifies_for_child_tax_credit());
+        assert!(!Dependent::child(17).qualifies_for_child_tax_credit());
+        assert!(Dependent::child(17).is_qualifying_child());
+    }
+}
@@ -0,0 +1,128 @@
+//! Savings rate and financial independence definitions
+//!
+//! The savings rate counts every dollar set aside, including pre-tax
+//! deferrals and employer money, over after-tax income plus those same
+//! payroll savings, so traditional and Roth savers with the same habits get
+//! the same rate. Financial independence is a portfolio of annual expenses
+//! divided by the safe withdrawal rate.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::rate::Rate;
+
+/// Years simulated before giving up on reaching the target
+const MAX_YEARS_TO_FI: u32 = 100;
+
+/// Retirement and HSA money put away through payroll
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PayrollSavings {
+    pub traditional_401k: Decimal,
+    pub roth_401k: Decimal,
+    pub hsa: Decimal,
+    pub employer_hsa: Decimal,
+}
+
+impl PayrollSavings {
+    pub fn total(&self) -> Decimal {
+        self.traditional_401k + self.roth_401k + self.hsa + self.employer_hsa
+    }
+}
+
+/// Spending and investing assumptions for FI metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiAssumptions {
+    /// Annual spending out of take-home pay
+    pub annual_expenses: Decimal,
+    /// Employer 401(k) match for the year
+    pub employer_match: Decimal,
+    /// Invested assets today
+    pub current_portfolio: Decimal,
+    /// Annual return after inflation
+    pub real_return: Decimal,
+    /// Share of the portfolio withdrawn each year in retirement
+    pub withdrawal_rate: Decimal,
+}
+
+impl Default for FiAssumptions {
+    fn default() -> Self {
+        Self {
+            annual_expenses: Decimal::ZERO,
+            employer_match: Decimal::ZERO,
+            current_portfolio: Decimal::ZERO,
+            real_return: dec!(0.05),
+            withdrawal_rate: dec!(0.04),
+        }
+    }
+}
+
+impl FiAssumptions {
+    /// Portfolio whose withdrawals cover the expenses
+    pub fn fi_number(&self) -> Decimal {
+        if self.withdrawal_rate > Decimal::ZERO {
+            self.annual_expenses / self.withdrawal_rate
+        } else {
+            Decimal::ZERO
+        }
+    }
+
+    /// Whole years of saving `annual_savings` (invested at the end of each
+    /// year) until the portfolio reaches the FI number, or `None` when it
+    /// doesn't within a century
+    pub fn years_to_fi(&self, annual_savings: Decimal) -> Option<u32> {
+        let target = self.fi_number();
+        let mut portfolio = self.current_portfolio;
+        for years in 0..=MAX_YEARS_TO_FI {
+            if portfolio >= target {
+                return Some(years);
+            }
+            portfolio = portfolio * (Decimal::ONE + self.real_return) + annual_savings;
+        }
+        None
+    }
+}
+
+/// Savings metrics derived from a calculation result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiMetrics {
+    /// Payroll savings plus the employer match
+    pub payroll_savings: Decimal,
+    /// Take-home pay left after expenses; negative when spending exceeds it
+    pub savings_capacity: Decimal,
+    /// Payroll savings plus any positive savings capacity
+    pub annual_savings: Decimal,
+    pub savings_rate: Rate,
+    pub fi_number: Decimal,
+    pub years_to_fi: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_years_to_fi() {
+        let assumptions = FiAssumptions {
+            annual_expenses: dec!(40000),
+            current_portfolio: dec!(100000),
+            ..Default::default()
+        };
+        assert_eq!(assumptions.fi_number(), dec!(1000000));
+        // $100,000 growing at 5% plus $50,000 a year passes $1M in year 13
+        assert_eq!(assumptions.years_to_fi(dec!(50000)), Some(13));
+        // Growth alone takes 48 years; without it, never
+        assert_eq!(assumptions.years_to_fi(dec!(0)), Some(48));
+        let flat = FiAssumptions {
+            real_return: dec!(0),
+            ..assumptions.clone()
+        };
+        assert_eq!(flat.years_to_fi(dec!(0)), None);
+
+        let retired = FiAssumptions {
+            current_portfolio: dec!(1000000),
+            ..assumptions
+        };
+        assert_eq!(retired.years_to_fi(dec!(0)), Some(0));
+    }
+}
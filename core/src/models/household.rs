@@ -2,6 +2,11 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::rate::Rate;
+use super::ParseEnumError;
 
 /// How to split shared expenses
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -39,6 +44,9 @@ pub struct Household {
     pub partner: PartnerProfile,
     pub split_method: SplitMethod,
     pub shared_expenses_monthly: Decimal,
+    /// Categorized monthly budget for the whole household
+    #[serde(default)]
+    pub expenses: MonthlyExpenses,
 }
 
 impl Household {
@@ -47,10 +55,142 @@ impl Household {
             partner,
             split_method,
             shared_expenses_monthly: Decimal::ZERO,
+            expenses: MonthlyExpenses::default(),
         }
     }
 }
 
+/// Budget category for a monthly expense
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExpenseCategory {
+    /// Rent or mortgage, property tax, and insurance
+    Housing,
+    Childcare,
+    Transportation,
+    Food,
+    Healthcare,
+    Utilities,
+    Debt,
+    Other,
+}
+
+impl ExpenseCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpenseCategory::Housing => "housing",
+            ExpenseCategory::Childcare => "childcare",
+            ExpenseCategory::Transportation => "transportation",
+            ExpenseCategory::Food => "food",
+            ExpenseCategory::Healthcare => "healthcare",
+            ExpenseCategory::Utilities => "utilities",
+            ExpenseCategory::Debt => "debt",
+            ExpenseCategory::Other => "other",
+        }
+    }
+
+    pub fn all() -> &'static [ExpenseCategory] {
+        &[
+            ExpenseCategory::Housing,
+            ExpenseCategory::Childcare,
+            ExpenseCategory::Transportation,
+            ExpenseCategory::Food,
+            ExpenseCategory::Healthcare,
+            ExpenseCategory::Utilities,
+            ExpenseCategory::Debt,
+            ExpenseCategory::Other,
+        ]
+    }
+}
+
+impl fmt::Display for ExpenseCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExpenseCategory {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s_lower = s.to_ascii_lowercase();
+        ExpenseCategory::all()
+            .iter()
+            .find(|category| category.as_str() == s_lower)
+            .copied()
+            .ok_or_else(|| ParseEnumError::new("expense category", s))
+    }
+}
+
+/// A monthly expense in one category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpenseItem {
+    pub category: ExpenseCategory,
+    pub amount: Decimal,
+}
+
+/// Categorized monthly expenses
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyExpenses {
+    pub items: Vec<ExpenseItem>,
+}
+
+impl MonthlyExpenses {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an expense (categories may repeat)
+    pub fn with(mut self, category: ExpenseCategory, amount: Decimal) -> Self {
+        self.items.push(ExpenseItem { category, amount });
+        self
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.items.iter().map(|item| item.amount).sum()
+    }
+
+    /// Total for one category
+    pub fn category_total(&self, category: ExpenseCategory) -> Decimal {
+        self.items
+            .iter()
+            .filter(|item| item.category == category)
+            .map(|item| item.amount)
+            .sum()
+    }
+
+    /// What remains of a monthly take-home after these expenses
+    pub fn summarize(&self, monthly_net: Decimal) -> BudgetSummary {
+        let total_expenses = self.total();
+        let discretionary_income = monthly_net - total_expenses;
+        BudgetSummary {
+            monthly_net,
+            total_expenses,
+            discretionary_income,
+            savings_rate: Rate::of(discretionary_income.max(Decimal::ZERO), monthly_net),
+            housing_share: Rate::of(self.category_total(ExpenseCategory::Housing), monthly_net),
+        }
+    }
+}
+
+/// Monthly budget measured against take-home pay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetSummary {
+    pub monthly_net: Decimal,
+    pub total_expenses: Decimal,
+    /// Take-home left after expenses (negative when over budget)
+    pub discretionary_income: Decimal,
+    /// Share of take-home left over to save, zero when over budget
+    pub savings_rate: Rate,
+    /// Share of take-home spent on housing
+    pub housing_share: Rate,
+}
+
+impl BudgetSummary {
+    pub fn is_over_budget(&self) -> bool {
+        self.discretionary_income < Decimal::ZERO
+    }
+}
+
 /// Result of household split calculation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HouseholdSplit {
@@ -102,6 +242,68 @@ pub fn calculate_split(
     }
 }
 
+/// A shared monthly expense with its own split rule
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SharedExpense {
+    pub category: ExpenseCategory,
+    pub amount: Decimal,
+    pub split_method: SplitMethod,
+}
+
+/// One shared expense divided between partners
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySplit {
+    pub category: ExpenseCategory,
+    pub split: HouseholdSplit,
+}
+
+/// Shared expenses split category by category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemizedHouseholdSplit {
+    /// Splits in the order the expenses were given
+    pub categories: Vec<CategorySplit>,
+    pub primary_monthly_total: Decimal,
+    pub partner_monthly_total: Decimal,
+}
+
+impl ItemizedHouseholdSplit {
+    pub fn monthly_total(&self) -> Decimal {
+        self.primary_monthly_total + self.partner_monthly_total
+    }
+}
+
+/// Split each shared expense by its own method
+pub fn calculate_itemized_split(
+    primary_net: Decimal,
+    partner_net: Decimal,
+    expenses: &[SharedExpense],
+) -> ItemizedHouseholdSplit {
+    let categories: Vec<_> = expenses
+        .iter()
+        .map(|expense| CategorySplit {
+            category: expense.category,
+            split: calculate_split(
+                primary_net,
+                partner_net,
+                expense.amount,
+                expense.split_method,
+            ),
+        })
+        .collect();
+
+    ItemizedHouseholdSplit {
+        primary_monthly_total: categories
+            .iter()
+            .map(|c| c.split.primary_monthly_amount)
+            .sum(),
+        partner_monthly_total: categories
+            .iter()
+            .map(|c| c.split.partner_monthly_amount)
+            .sum(),
+        categories,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +348,72 @@ mod tests {
         assert_eq!(split.primary_monthly_amount, dec!(700));
         assert_eq!(split.partner_monthly_amount, dec!(300));
     }
+
+    #[test]
+    fn test_itemized_split() {
+        let expense = |category, amount, split_method| SharedExpense {
+            category,
+            amount,
+            split_method,
+        };
+        let result = calculate_itemized_split(
+            dec!(6000),
+            dec!(4000),
+            &[
+                expense(
+                    ExpenseCategory::Housing,
+                    dec!(3000),
+                    SplitMethod::Proportional,
+                ),
+                expense(ExpenseCategory::Food, dec!(800), SplitMethod::Equal),
+                expense(
+                    ExpenseCategory::Childcare,
+                    dec!(1500),
+                    SplitMethod::Custom(dec!(0.2)),
+                ),
+            ],
+        );
+
+        let amounts: Vec<_> = result
+            .categories
+            .iter()
+            .map(|c| (c.category, c.split.primary_monthly_amount))
+            .collect();
+        assert_eq!(
+            amounts,
+            vec![
+                (ExpenseCategory::Housing, dec!(1800)),
+                (ExpenseCategory::Food, dec!(400)),
+                (ExpenseCategory::Childcare, dec!(300)),
+            ]
+        );
+        assert_eq!(result.primary_monthly_total, dec!(2500));
+        assert_eq!(result.partner_monthly_total, dec!(2800));
+        assert_eq!(result.monthly_total(), dec!(5300));
+    }
+
+    #[test]
+    fn test_budget_summary() {
+        let expenses = MonthlyExpenses::new()
+            .with(ExpenseCategory::Housing, dec!(2400))
+            .with(ExpenseCategory::Childcare, dec!(1200))
+            .with(ExpenseCategory::Transportation, dec!(400))
+            .with(ExpenseCategory::Housing, dec!(200));
+
+        assert_eq!(
+            expenses.category_total(ExpenseCategory::Housing),
+            dec!(2600)
+        );
+        assert_eq!(expenses.total(), dec!(4200));
+
+        let summary = expenses.summarize(dec!(6000));
+        assert_eq!(summary.discretionary_income, dec!(1800));
+        assert_eq!(summary.savings_rate.as_fraction(), dec!(0.3));
+        assert!(!summary.is_over_budget());
+
+        let tight = expenses.summarize(dec!(4000));
+        assert_eq!(tight.discretionary_income, dec!(-200));
+        assert_eq!(tight.savings_rate, Rate::ZERO);
+        assert!(tight.is_over_budget());
+    }
 }
@@ -2,36 +2,102 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::ParseEnumError;
 
 /// Pay frequency options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum PayFrequency {
+    /// Every workday (260 per year)
+    Daily,
     Weekly,
     #[default]
     BiWeekly,
+    /// Biweekly in a year with 27 paydays (every 11 years or so)
+    BiWeekly27,
     SemiMonthly,
     Monthly,
+    Quarterly,
+    Annually,
 }
 
 impl PayFrequency {
     /// Number of pay periods per year
     pub fn periods_per_year(&self) -> u32 {
         match self {
+            PayFrequency::Daily => 260,
             PayFrequency::Weekly => 52,
             PayFrequency::BiWeekly => 26,
+            PayFrequency::BiWeekly27 => 27,
             PayFrequency::SemiMonthly => 24,
             PayFrequency::Monthly => 12,
+            PayFrequency::Quarterly => 4,
+            PayFrequency::Annually => 1,
+        }
+    }
+
+    /// Periods the withholding tables annualize a paycheck by
+    ///
+    /// A 27-payday year still uses the biweekly table, so each paycheck is
+    /// withheld as if it were 1/26 of the year.
+    pub fn withholding_periods(&self) -> u32 {
+        match self {
+            PayFrequency::BiWeekly27 => 26,
+            frequency => frequency.periods_per_year(),
         }
     }
 
+    /// Share of an annual amount paid each period
+    pub fn per_period(&self, annual: Decimal) -> Decimal {
+        annual / Decimal::from(self.periods_per_year())
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
+            PayFrequency::Daily => "daily",
             PayFrequency::Weekly => "weekly",
             PayFrequency::BiWeekly => "bi_weekly",
+            PayFrequency::BiWeekly27 => "bi_weekly_27",
             PayFrequency::SemiMonthly => "semi_monthly",
             PayFrequency::Monthly => "monthly",
+            PayFrequency::Quarterly => "quarterly",
+            PayFrequency::Annually => "annually",
         }
     }
+
+    pub fn all() -> &'static [PayFrequency] {
+        &[
+            PayFrequency::Daily,
+            PayFrequency::Weekly,
+            PayFrequency::BiWeekly,
+            PayFrequency::BiWeekly27,
+            PayFrequency::SemiMonthly,
+            PayFrequency::Monthly,
+            PayFrequency::Quarterly,
+            PayFrequency::Annually,
+        ]
+    }
+}
+
+impl fmt::Display for PayFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PayFrequency {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s_lower = s.to_ascii_lowercase();
+        PayFrequency::all()
+            .iter()
+            .find(|frequency| frequency.as_str() == s_lower)
+            .copied()
+            .ok_or_else(|| ParseEnumError::new("pay frequency", s))
+    }
 }
 
 /// Income input for calculations
@@ -56,6 +122,11 @@ impl IncomeInput {
     pub fn total_gross(&self) -> Decimal {
         self.gross_annual_salary + self.bonuses + self.other_income
     }
+
+    /// Salary paid each pay period
+    pub fn salary_per_paycheck(&self) -> Decimal {
+        self.pay_frequency.per_period(self.gross_annual_salary)
+    }
 }
 
 impl Default for IncomeInput {
@@ -69,11 +140,56 @@ impl Default for IncomeInput {
     }
 }
 
+/// The working year behind an annual salary
+///
+/// Paid time off counts as time worked. Unpaid leave, including an unpaid
+/// sabbatical, shortens the paid year and forfeits salary pro rata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkSchedule {
+    pub hours_per_week: Decimal,
+    pub days_per_week: Decimal,
+    /// Weeks in the work year (52, or about 40 for a school year)
+    pub weeks_per_year: Decimal,
+    /// Unpaid weeks within the work year
+    pub unpaid_leave_weeks: Decimal,
+}
+
+impl WorkSchedule {
+    /// Weeks actually paid
+    pub fn paid_weeks(&self) -> Decimal {
+        (self.weeks_per_year - self.unpaid_leave_weeks).max(Decimal::ZERO)
+    }
+
+    /// Share of the scheduled salary paid after unpaid leave
+    pub fn paid_share(&self) -> Decimal {
+        if self.weeks_per_year > Decimal::ZERO {
+            self.paid_weeks() / self.weeks_per_year
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
+
+impl Default for WorkSchedule {
+    fn default() -> Self {
+        Self {
+            hours_per_week: Decimal::from(40),
+            days_per_week: Decimal::from(5),
+            weeks_per_year: Decimal::from(52),
+            unpaid_leave_weeks: Decimal::ZERO,
+        }
+    }
+}
+
 /// Income broken down by timeframe
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TimeframeIncome {
     pub annual: Decimal,
+    #[serde(default)]
+    pub quarterly: Decimal,
     pub monthly: Decimal,
+    #[serde(default)]
+    pub semi_monthly: Decimal,
     pub bi_weekly: Decimal,
     pub weekly: Decimal,
     pub daily: Decimal,
@@ -84,14 +200,7 @@ impl TimeframeIncome {
     /// Create timeframe breakdown from annual amount
     /// Uses standard 40 hours/week, 5 days/week
     pub fn from_annual(annual: Decimal) -> Self {
-        Self {
-            annual,
-            monthly: annual / Decimal::from(12),
-            bi_weekly: annual / Decimal::from(26),
-            weekly: annual / Decimal::from(52),
-            daily: annual / Decimal::from(260), // 52 weeks × 5 days
-            hourly: annual / Decimal::from(2080), // 52 weeks × 40 hours
-        }
+        Self::from_schedule(annual, &WorkSchedule::default())
     }
 
     /// Create with custom working schedule
@@ -100,14 +209,38 @@ impl TimeframeIncome {
         hours_per_week: Decimal,
         days_per_week: Decimal,
     ) -> Self {
-        let weeks = Decimal::from(52);
+        Self::from_schedule(
+            annual,
+            &WorkSchedule {
+                hours_per_week,
+                days_per_week,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create from a work schedule
+    ///
+    /// Pay periods (quarterly through bi-weekly) follow the calendar; weekly,
+    /// daily, and hourly amounts are per unit of paid work.
+    pub fn from_schedule(annual: Decimal, schedule: &WorkSchedule) -> Self {
+        let weeks = schedule.paid_weeks();
+        let per = |units: Decimal| {
+            if units > Decimal::ZERO {
+                annual / units
+            } else {
+                Decimal::ZERO
+            }
+        };
         Self {
             annual,
+            quarterly: annual / Decimal::from(4),
             monthly: annual / Decimal::from(12),
+            semi_monthly: annual / Decimal::from(24),
             bi_weekly: annual / Decimal::from(26),
-            weekly: annual / weeks,
-            daily: annual / (weeks * days_per_week),
-            hourly: annual / (weeks * hours_per_week),
+            weekly: per(weeks),
+            daily: per(weeks * schedule.days_per_week),
+            hourly: per(weeks * schedule.hours_per_week),
         }
     }
 }
@@ -125,6 +258,9 @@ pub struct CalculatedIncome {
     pub net: Decimal,
     pub timeframes: TimeframeIncome,
     pub take_home_percentage: Decimal,
+    /// Schedule the timeframes are annualized over
+    #[serde(default)]
+    pub work_schedule: WorkSchedule,
 }
 
 impl CalculatedIncome {
@@ -140,6 +276,7 @@ impl CalculatedIncome {
             net,
             timeframes: TimeframeIncome::from_annual(net),
             take_home_percentage,
+            work_schedule: WorkSchedule::default(),
         }
     }
 }
@@ -149,6 +286,20 @@ mod tests {
     use super::*;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn test_pay_frequency_parse() {
+        assert_eq!("Monthly".parse::<PayFrequency>(), Ok(PayFrequency::Monthly));
+        assert_eq!(PayFrequency::BiWeekly.to_string(), "bi_weekly");
+        assert_eq!("daily".parse::<PayFrequency>(), Ok(PayFrequency::Daily));
+        assert!("fortnightly".parse::<PayFrequency>().is_err());
+        for frequency in PayFrequency::all() {
+            assert_eq!(
+                frequency.to_string().parse::<PayFrequency>(),
+                Ok(*frequency)
+            );
+        }
+    }
+
     #[test]
     fn test_timeframe_from_annual() {
         let income = TimeframeIncome::from_annual(dec!(104000));
@@ -167,5 +318,42 @@ mod tests {
         assert_eq!(PayFrequency::BiWeekly.periods_per_year(), 26);
         assert_eq!(PayFrequency::SemiMonthly.periods_per_year(), 24);
         assert_eq!(PayFrequency::Monthly.periods_per_year(), 12);
+        assert_eq!(PayFrequency::Daily.periods_per_year(), 260);
+        assert_eq!(PayFrequency::Quarterly.periods_per_year(), 4);
+        assert_eq!(PayFrequency::Annually.periods_per_year(), 1);
+    }
+
+    #[test]
+    fn test_school_year_schedule() {
+        let schedule = WorkSchedule {
+            weeks_per_year: dec!(40),
+            ..Default::default()
+        };
+        let income = TimeframeIncome::from_schedule(dec!(60000), &schedule);
+
+        // Paid over twelve months, earned over forty weeks
+        assert_eq!(income.monthly, dec!(5000));
+        assert_eq!(income.weekly, dec!(1500));
+        assert_eq!(income.hourly, dec!(37.5));
+
+        let leave = WorkSchedule {
+            unpaid_leave_weeks: dec!(13),
+            ..Default::default()
+        };
+        assert_eq!(leave.paid_weeks(), dec!(39));
+        assert_eq!(leave.paid_share(), dec!(0.75));
+    }
+
+    #[test]
+    fn test_27_payday_year() {
+        let income = IncomeInput {
+            pay_frequency: PayFrequency::BiWeekly27,
+            ..IncomeInput::new(dec!(108000))
+        };
+
+        // Same salary over one more paycheck
+        assert_eq!(income.salary_per_paycheck(), dec!(4000));
+        assert_eq!(PayFrequency::BiWeekly27.withholding_periods(), 26);
+        assert_eq!(PayFrequency::BiWeekly.withholding_periods(), 26);
     }
 }
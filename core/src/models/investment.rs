@@ -0,0 +1,47 @@
+//! Investment and miscellaneous non-wage income
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::capital::CapitalGains;
+
+/// Income reported on 1099s rather than a W-2
+///
+/// None of it is subject to FICA. Crypto disposals are property sales,
+/// netted with other capital gains and losses; staking rewards and 1099-MISC
+/// other income are ordinary income. Qualified dividends are taxed at the
+/// long-term capital gains rates. Gambling winnings are ordinary income
+/// in full; losses are only an itemized deduction.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InvestmentIncome {
+    /// Net short-term gain on crypto held a year or less; negative for a loss
+    pub crypto_short_term: Decimal,
+    /// Net long-term gain on crypto held over a year; negative for a loss
+    pub crypto_long_term: Decimal,
+    /// Staking and mining rewards, at fair market value when received
+    pub staking_rewards: Decimal,
+    /// 1099-MISC other income (prizes, awards, non-business royalties)
+    pub miscellaneous: Decimal,
+    /// Lottery, casino, and sports betting winnings, before losses
+    #[serde(default)]
+    pub gambling_winnings: Decimal,
+    /// 1099-DIV qualified dividends
+    #[serde(default)]
+    pub qualified_dividends: Decimal,
+}
+
+impl InvestmentIncome {
+    /// Income taxed at ordinary rates
+    pub fn ordinary(&self) -> Decimal {
+        self.staking_rewards + self.miscellaneous + self.gambling_winnings
+    }
+
+    /// `gains` with the crypto results added to each term
+    pub fn with_capital_gains(&self, gains: &CapitalGains) -> CapitalGains {
+        CapitalGains {
+            short_term: gains.short_term + self.crypto_short_term,
+            long_term: gains.long_term + self.crypto_long_term,
+            carryover: gains.carryover.clone(),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Display names in the languages the apps ship
+//!
+//! English names are the enums' own `display_name()`/`name()`; other
+//! languages are tables here, so every platform shows the same wording.
+
+use std::fmt;
+
+use super::state::USState;
+use super::tax::FilingStatus;
+
+/// Language for display names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// ISO 639-1 language code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    pub fn all() -> &'static [Locale] {
+        &[Locale::English, Locale::Spanish]
+    }
+
+    /// Locale for a BCP 47 tag such as "es-MX" or "en_US", by language
+    /// alone; unsupported languages fall back to English
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        if language.eq_ignore_ascii_case("es") {
+            Locale::Spanish
+        } else {
+            Locale::English
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A user-facing name in each supported locale
+pub trait LocalizedName {
+    fn localized_name(&self, locale: Locale) -> &'static str;
+}
+
+impl LocalizedName for FilingStatus {
+    fn localized_name(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::English => self.display_name(),
+            Locale::Spanish => match self {
+                FilingStatus::Single => "Soltero",
+                FilingStatus::MarriedFilingJointly => {
+                    "Casado que presenta una declaración conjunta"
+                },
+                FilingStatus::MarriedFilingSeparately => {
+                    "Casado que presenta una declaración por separado"
+                },
+                FilingStatus::HeadOfHousehold => "Cabeza de familia",
+                FilingStatus::QualifyingWidower => "Cónyuge sobreviviente que reúne los requisitos",
+            },
+        }
+    }
+}
+
+impl LocalizedName for USState {
+    fn localized_name(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::English => self.name(),
+            Locale::Spanish => match self {
+                USState::Hawaii => "Hawái",
+                USState::Louisiana => "Luisiana",
+                USState::Mississippi => "Misisipi",
+                USState::Missouri => "Misuri",
+                USState::NewHampshire => "Nuevo Hampshire",
+                USState::NewJersey => "Nueva Jersey",
+                USState::NewMexico => "Nuevo México",
+                USState::NewYork => "Nueva York",
+                USState::NorthCarolina => "Carolina del Norte",
+                USState::NorthDakota => "Dakota del Norte",
+                USState::Oregon => "Oregón",
+                USState::Pennsylvania => "Pensilvania",
+                USState::SouthCarolina => "Carolina del Sur",
+                USState::SouthDakota => "Dakota del Sur",
+                USState::WestVirginia => "Virginia Occidental",
+                USState::WashingtonDC => "Washington D. C.",
+                USState::VirginIslands => "Islas Vírgenes de EE. UU.",
+                USState::NoState => "Sin estado",
+                // The rest are spelled the same in Spanish
+                state => state.name(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("es"), Locale::Spanish);
+        assert_eq!(Locale::from_tag("es-MX"), Locale::Spanish);
+        assert_eq!(Locale::from_tag("ES_us"), Locale::Spanish);
+        assert_eq!(Locale::from_tag("en-US"), Locale::English);
+        // Unsupported languages show English
+        assert_eq!(Locale::from_tag("fr-CA"), Locale::English);
+        assert_eq!(Locale::from_tag(""), Locale::English);
+    }
+
+    #[test]
+    fn test_localized_names() {
+        assert_eq!(
+            FilingStatus::HeadOfHousehold.localized_name(Locale::English),
+            "Head of Household"
+        );
+        assert_eq!(
+            FilingStatus::HeadOfHousehold.localized_name(Locale::Spanish),
+            "Cabeza de familia"
+        );
+        assert_eq!(
+            USState::NewYork.localized_name(Locale::Spanish),
+            "Nueva York"
+        );
+        assert_eq!(USState::Texas.localized_name(Locale::Spanish), "Texas");
+
+        // Every state and status has a name in every locale
+        for locale in Locale::all() {
+            for state in USState::all().iter().chain(USState::territories()) {
+                assert!(!state.localized_name(*locale).is_empty());
+            }
+        }
+    }
+}
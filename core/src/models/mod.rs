@@ -1,7 +1,34 @@
 //! Domain models for TakeHome calculations
 
+pub mod capital;
 pub mod deduction;
+pub mod dependent;
+pub mod financial_independence;
 pub mod household;
 pub mod income;
+pub mod investment;
+pub mod locale;
+pub mod money;
+pub mod profile;
+pub mod rate;
+pub mod retirement;
+pub mod simulation;
 pub mod state;
 pub mod tax;
+
+/// Error parsing one of the model enums from its string form
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind}: {value}")]
+pub struct ParseEnumError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl ParseEnumError {
+    pub(crate) fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Currency amounts
+//!
+//! `Money` is the boundary type for amounts: FFI strings are parsed into and
+//! formatted from it, explanation text prints through it, and
+//! `TaxCalculationResult::rounded_to_cents` rounds with it. The engine's
+//! input and result fields stay plain `Decimal` so intermediate arithmetic
+//! keeps full precision; the result's effective rates are typed
+//! [`Rate`](super::rate::Rate), which can scale an amount but not be added
+//! to one.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A dollar amount
+///
+/// Calculations keep full precision; `cents()` and `Display` round half away
+/// from zero to whole cents at the boundary. Money can be scaled by a plain
+/// `Decimal` rate but never added to one, and dividing two amounts yields a
+/// ratio rather than more money.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Money(Decimal);
+
+impl Money {
+    pub const ZERO: Money = Money(Decimal::ZERO);
+
+    pub fn new(amount: Decimal) -> Self {
+        Self(amount)
+    }
+
+    /// Underlying unrounded amount
+    pub fn amount(&self) -> Decimal {
+        self.0
+    }
+
+    /// Amount rounded to whole cents
+    pub fn cents(&self) -> Self {
+        Self(
+            self.0
+                .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero),
+        )
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.is_sign_negative() && !self.0.is_zero()
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(amount: Decimal) -> Self {
+        Self(amount)
+    }
+}
+
+impl From<Money> for Decimal {
+    fn from(money: Money) -> Self {
+        money.0
+    }
+}
+
+impl fmt::Display for Money {
+    /// Cents precision without trailing zeros ("1234.5", "800")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cents().0.normalize())
+    }
+}
+
+impl FromStr for Money {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse::<Decimal>().map(Money)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        Money(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Money) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        Money(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Money) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+/// Scale by a rate or count
+impl Mul<Decimal> for Money {
+    type Output = Money;
+
+    fn mul(self, rhs: Decimal) -> Money {
+        Money(self.0 * rhs)
+    }
+}
+
+/// Split into equal parts (e.g. per pay period)
+impl Div<Decimal> for Money {
+    type Output = Money;
+
+    fn div(self, rhs: Decimal) -> Money {
+        Money(self.0 / rhs)
+    }
+}
+
+/// Ratio of two amounts
+impl Div for Money {
+    type Output = Decimal;
+
+    fn div(self, rhs: Money) -> Decimal {
+        self.0 / rhs.0
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+uniffi::custom_type!(Money, String);
+
+impl crate::UniffiCustomTypeConverter for Money {
+    type Builtin = String;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(val.parse()?)
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_cents_rounding() {
+        assert_eq!(Money::new(dec!(10.005)).cents(), Money::new(dec!(10.01)));
+        assert_eq!(Money::new(dec!(-10.005)).cents(), Money::new(dec!(-10.01)));
+        assert_eq!(Money::new(dec!(4000.0000)).to_string(), "4000");
+        assert_eq!(Money::new(dec!(1234.567)).to_string(), "1234.57");
+    }
+
+    #[test]
+    fn test_arithmetic_keeps_units() {
+        let salary = Money::new(dec!(104000));
+        let per_period = salary / dec!(26);
+        assert_eq!(per_period, Money::new(dec!(4000)));
+
+        let tax = salary * dec!(0.05);
+        let ratio: Decimal = tax / salary;
+        assert_eq!(ratio, dec!(0.05));
+
+        let total: Money = [tax, per_period].into_iter().sum();
+        assert_eq!(total, Money::new(dec!(9200)));
+    }
+
+    #[test]
+    fn test_parse_and_serde() {
+        assert_eq!(" 12.5 ".parse::<Money>(), Ok(Money::new(dec!(12.5))));
+        assert!("twelve".parse::<Money>().is_err());
+
+        let json = serde_json::to_string(&Money::new(dec!(99.99))).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Money>(&json).unwrap(),
+            Money::new(dec!(99.99))
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! User profile bundling everything known about an earner
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::deduction::{Deduction, DeductionType, RetirementContributions};
+use super::household::Household;
+use super::income::{IncomeInput, WorkSchedule};
+use super::retirement::RetirementIncome;
+use super::state::USState;
+use super::tax::{FilingStatus, SdiCoverage};
+
+/// Settings from Form W-4, which drive paycheck withholding but not the
+/// year's actual tax
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct W4Settings {
+    /// Withholding filing status, when it differs from the return's
+    pub filing_status: Option<FilingStatus>,
+    /// Step 2 checkbox: two jobs in the household
+    pub multiple_jobs: bool,
+    /// Step 3: annual credits for dependents
+    pub dependents_credit: Decimal,
+    /// Step 4(a): annual non-job income to withhold for
+    pub other_income: Decimal,
+    /// Step 4(b): annual deductions beyond the standard deduction
+    pub deductions: Decimal,
+    /// Step 4(c): extra withholding per paycheck
+    pub extra_withholding: Decimal,
+}
+
+/// Where the earner lives and works
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaxLocation {
+    pub state: USState,
+    /// County or city of residence
+    pub residence_locality: Option<String>,
+    /// Ohio school district number
+    pub school_district: Option<String>,
+    /// State where wages are earned, when different from `state`
+    pub work_state: Option<USState>,
+    /// City where wages are earned
+    pub work_locality: Option<String>,
+}
+
+/// One earner's income, deductions, household, and withholding setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub income: IncomeInput,
+    pub filing_status: FilingStatus,
+    pub location: TaxLocation,
+    /// Payroll deductions (insurance, HSA, FSA, commuter, 401(k), ...)
+    #[serde(default)]
+    pub deductions: Vec<Deduction>,
+    #[serde(default)]
+    pub retirement: RetirementContributions,
+    #[serde(default)]
+    pub retirement_income: RetirementIncome,
+    /// Partner and shared expenses
+    #[serde(default)]
+    pub household: Option<Household>,
+    #[serde(default)]
+    pub w4: W4Settings,
+    #[serde(default)]
+    pub work_schedule: WorkSchedule,
+    #[serde(default)]
+    pub sdi_coverage: SdiCoverage,
+    /// Annual property taxes paid
+    #[serde(default)]
+    pub property_taxes: Decimal,
+}
+
+impl UserProfile {
+    pub fn new(income: IncomeInput, filing_status: FilingStatus, state: USState) -> Self {
+        Self {
+            income,
+            filing_status,
+            location: TaxLocation {
+                state,
+                ..Default::default()
+            },
+            deductions: Vec::new(),
+            retirement: RetirementContributions::new(),
+            retirement_income: RetirementIncome::default(),
+            household: None,
+            w4: W4Settings::default(),
+            work_schedule: WorkSchedule::default(),
+            sdi_coverage: SdiCoverage::default(),
+            property_taxes: Decimal::ZERO,
+        }
+    }
+
+    /// Annual total of the payroll deductions of one type
+    pub fn annual_deductions(&self, deduction_type: DeductionType) -> Decimal {
+        self.deductions
+            .iter()
+            .filter(|d| d.deduction_type == deduction_type)
+            .map(Deduction::annual_amount)
+            .sum()
+    }
+
+    /// Partner's wages when they're reported on this earner's joint return
+    pub fn joint_spouse_income(&self) -> Decimal {
+        match (&self.household, self.filing_status) {
+            (Some(household), FilingStatus::MarriedFilingJointly) => household.partner.gross_income,
+            _ => Decimal::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::deduction::DeductionFrequency;
+    use crate::models::household::{PartnerProfile, SplitMethod};
+    use crate::models::income::PayFrequency;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_annual_deductions_by_type() {
+        let mut profile = UserProfile::new(
+            IncomeInput::new(dec!(104000)),
+            FilingStatus::Single,
+            USState::Colorado,
+        );
+        profile.deductions = vec![
+            Deduction::per_paycheck(DeductionType::Hsa, dec!(100), PayFrequency::BiWeekly),
+            Deduction::new(
+                DeductionType::HealthInsurance,
+                dec!(150),
+                DeductionFrequency::Monthly,
+                12,
+            ),
+            Deduction::per_paycheck(DeductionType::Hsa, dec!(25), PayFrequency::BiWeekly),
+        ];
+
+        assert_eq!(profile.annual_deductions(DeductionType::Hsa), dec!(3250));
+        assert_eq!(
+            profile.annual_deductions(DeductionType::HealthInsurance),
+            dec!(1800)
+        );
+        assert_eq!(profile.annual_deductions(DeductionType::Commuter), dec!(0));
+    }
+
+    #[test]
+    fn test_joint_spouse_income() {
+        let mut profile = UserProfile::new(
+            IncomeInput::new(dec!(90000)),
+            FilingStatus::MarriedFilingJointly,
+            USState::Ohio,
+        );
+        assert_eq!(profile.joint_spouse_income(), dec!(0));
+
+        profile.household = Some(Household::new(
+            PartnerProfile::new("Sam".to_string(), dec!(60000), dec!(47000)),
+            SplitMethod::Proportional,
+        ));
+        assert_eq!(profile.joint_spouse_income(), dec!(60000));
+
+        profile.filing_status = FilingStatus::MarriedFilingSeparately;
+        assert_eq!(profile.joint_spouse_income(), dec!(0));
+    }
+}
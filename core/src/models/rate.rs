@@ -0,0 +1,134 @@
+//! Tax rates
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use super::money::Money;
+
+/// A rate stored as a fraction (0.22 for 22%)
+///
+/// Read it back with `as_fraction()` for arithmetic or `as_percent()` for
+/// display; `Display` prints the percent form ("22%"). Serializes as the
+/// fraction.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Rate(Decimal);
+
+impl Rate {
+    pub const ZERO: Rate = Rate(Decimal::ZERO);
+
+    /// Rate from a fraction (0.22)
+    pub fn from_fraction(fraction: Decimal) -> Self {
+        Self(fraction)
+    }
+
+    /// Rate from a percentage (22)
+    pub fn from_percent(percent: Decimal) -> Self {
+        Self(percent / Decimal::ONE_HUNDRED)
+    }
+
+    /// Ratio of a part to a whole, zero when the whole is zero
+    pub fn of(part: Decimal, whole: Decimal) -> Self {
+        if whole.is_zero() {
+            Self::ZERO
+        } else {
+            Self(part / whole)
+        }
+    }
+
+    pub fn as_fraction(&self) -> Decimal {
+        self.0
+    }
+
+    pub fn as_percent(&self) -> Decimal {
+        self.0 * Decimal::ONE_HUNDRED
+    }
+}
+
+impl From<Decimal> for Rate {
+    /// Interprets the decimal as a fraction
+    fn from(fraction: Decimal) -> Self {
+        Self(fraction)
+    }
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.as_percent().normalize())
+    }
+}
+
+impl PartialEq<Decimal> for Rate {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialOrd<Decimal> for Rate {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+impl Add for Rate {
+    type Output = Rate;
+
+    fn add(self, rhs: Rate) -> Rate {
+        Rate(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Rate {
+    type Output = Rate;
+
+    fn sub(self, rhs: Rate) -> Rate {
+        Rate(self.0 - rhs.0)
+    }
+}
+
+/// Apply a rate to an amount
+impl Mul<Rate> for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, rhs: Rate) -> Decimal {
+        self * rhs.0
+    }
+}
+
+impl Mul<Rate> for Money {
+    type Output = Money;
+
+    fn mul(self, rhs: Rate) -> Money {
+        self * rhs.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_fraction_and_percent() {
+        let rate = Rate::from_percent(dec!(22));
+        assert_eq!(rate.as_fraction(), dec!(0.22));
+        assert_eq!(rate.as_percent(), dec!(22));
+        assert_eq!(rate, Rate::from_fraction(dec!(0.22)));
+        assert_eq!(rate.to_string(), "22%");
+        assert_eq!(Rate::from_fraction(dec!(0.0765)).to_string(), "7.65%");
+    }
+
+    #[test]
+    fn test_apply_to_amounts() {
+        let rate = Rate::from_fraction(dec!(0.05));
+        assert_eq!(dec!(1000) * rate, dec!(50));
+        assert_eq!(Money::new(dec!(1000)) * rate, Money::new(dec!(50)));
+        assert_eq!(Rate::of(dec!(25), dec!(100)), dec!(0.25));
+        assert_eq!(Rate::of(dec!(25), dec!(0)), Rate::ZERO);
+    }
+}
@@ -0,0 +1,84 @@
+//! Retirement income models
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Income received in a retirement year
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetirementIncome {
+    /// Traditional 401(k)/IRA distributions (fully taxable)
+    pub traditional_withdrawals: Decimal,
+    /// Qualified Roth distributions (tax-free)
+    pub roth_withdrawals: Decimal,
+    /// Gross Social Security benefits received
+    pub social_security_benefits: Decimal,
+    /// Defined-benefit pension income
+    pub pension_income: Decimal,
+    /// Age of the recipient, used for age-gated state exclusions
+    pub age: u32,
+}
+
+impl RetirementIncome {
+    /// Total cash received from all retirement sources
+    pub fn total(&self) -> Decimal {
+        self.traditional_withdrawals
+            + self.roth_withdrawals
+            + self.social_security_benefits
+            + self.pension_income
+    }
+
+    /// Retirement income that is ordinary taxable income before any exclusions
+    pub fn taxable_distributions(&self) -> Decimal {
+        self.traditional_withdrawals + self.pension_income
+    }
+
+    /// Raise traditional withdrawals to at least a required minimum distribution
+    pub fn with_minimum_distribution(self, required_distribution: Decimal) -> Self {
+        Self {
+            traditional_withdrawals: self.traditional_withdrawals.max(required_distribution),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_totals() {
+        let income = RetirementIncome {
+            traditional_withdrawals: dec!(30000),
+            roth_withdrawals: dec!(10000),
+            social_security_benefits: dec!(24000),
+            pension_income: dec!(12000),
+            age: 67,
+        };
+
+        assert_eq!(income.total(), dec!(76000));
+        assert_eq!(income.taxable_distributions(), dec!(42000));
+    }
+
+    #[test]
+    fn test_minimum_distribution_only_raises() {
+        let income = RetirementIncome {
+            traditional_withdrawals: dec!(30000),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            income
+                .clone()
+                .with_minimum_distribution(dec!(20000))
+                .traditional_withdrawals,
+            dec!(30000)
+        );
+        assert_eq!(
+            income
+                .with_minimum_distribution(dec!(45000))
+                .traditional_withdrawals,
+            dec!(45000)
+        );
+    }
+}
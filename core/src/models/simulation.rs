@@ -0,0 +1,137 @@
+//! Reproducible random sampling
+//!
+//! Sampling analyses draw from a small seeded generator rather than the
+//! platform's, so a seed gives the same results in tests and on every
+//! platform the engine is bound to.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Seed used when the caller doesn't pick one
+pub const DEFAULT_SEED: u64 = 2024;
+
+/// Most iterations a simulation will run
+pub const MAX_ITERATIONS: u32 = 100_000;
+
+/// Seed and sample count for a sampling analysis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    pub seed: u64,
+    pub iterations: u32,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: DEFAULT_SEED,
+            iterations: 1000,
+        }
+    }
+}
+
+impl SimulationConfig {
+    pub fn new(seed: u64, iterations: u32) -> Self {
+        Self { seed, iterations }
+    }
+
+    /// Iterations clamped to `1..=MAX_ITERATIONS`
+    pub fn sample_count(&self) -> u32 {
+        self.iterations.clamp(1, MAX_ITERATIONS)
+    }
+
+    pub fn rng(&self) -> SimulationRng {
+        SimulationRng::new(self.seed)
+    }
+}
+
+/// SplitMix64 generator: fast, seedable, and the same on every platform
+#[derive(Debug, Clone)]
+pub struct SimulationRng {
+    state: u64,
+}
+
+impl SimulationRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform fraction in [0, 1) with 53 bits of precision
+    pub fn next_fraction(&mut self) -> Decimal {
+        Decimal::from(self.next_u64() >> 11) / Decimal::from(1u64 << 53)
+    }
+
+    /// Draw from a triangular distribution between `low` and `high` peaking
+    /// at `mode`, rounded to cents
+    pub fn triangular(&mut self, low: Decimal, mode: Decimal, high: Decimal) -> Decimal {
+        let width = high - low;
+        if width <= Decimal::ZERO {
+            return low;
+        }
+        let u = self.next_fraction();
+        let sample = if u < (mode - low) / width {
+            low + sqrt(u * width * (mode - low))
+        } else {
+            high - sqrt((Decimal::ONE - u) * width * (high - mode))
+        };
+        sample.round_dp(2)
+    }
+}
+
+/// Square root by Newton's method, exact to well under a cent for amounts
+/// in the millions
+fn sqrt(value: Decimal) -> Decimal {
+    if value <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    let mut root = value.max(Decimal::ONE);
+    for _ in 0..100 {
+        let next = (root + value / root) / Decimal::TWO;
+        if (next - root).abs() < Decimal::new(1, 6) {
+            return next;
+        }
+        root = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_seed_reproduces_sequence() {
+        let mut a = SimulationConfig::new(7, 10).rng();
+        let mut b = SimulationConfig::new(7, 10).rng();
+        let mut c = SimulationConfig::new(8, 10).rng();
+        let first: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(first, (0..5).map(|_| c.next_u64()).collect::<Vec<_>>());
+
+        // SplitMix64's published first output for seed 0
+        assert_eq!(SimulationRng::new(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn test_triangular_stays_in_range() {
+        let mut rng = SimulationRng::new(DEFAULT_SEED);
+        let samples: Vec<Decimal> = (0..2000)
+            .map(|_| rng.triangular(dec!(0), dec!(10000), dec!(40000)))
+            .collect();
+        assert!(samples.iter().all(|s| *s >= dec!(0) && *s <= dec!(40000)));
+        // The mean of a triangular distribution is (low + mode + high) / 3
+        let mean = samples.iter().sum::<Decimal>() / Decimal::from(samples.len());
+        assert!((mean - dec!(16666.67)).abs() < dec!(500));
+
+        assert_eq!(rng.triangular(dec!(5), dec!(5), dec!(5)), dec!(5));
+        assert_eq!(sqrt(dec!(144)).round_dp(4), dec!(12));
+    }
+}
@@ -1,6 +1,10 @@
 //! US State definitions and properties
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::ParseEnumError;
 
 /// All US states and territories
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -57,6 +61,11 @@ pub enum USState {
     WestVirginia,
     Wisconsin,
     Wyoming,
+    PuertoRico,
+    Guam,
+    VirginIslands,
+    /// No state residence (living abroad, or federal and FICA only)
+    NoState,
 }
 
 impl USState {
@@ -114,6 +123,10 @@ impl USState {
             USState::WestVirginia => "WV",
             USState::Wisconsin => "WI",
             USState::Wyoming => "WY",
+            USState::PuertoRico => "PR",
+            USState::Guam => "GU",
+            USState::VirginIslands => "VI",
+            USState::NoState => "NONE",
         }
     }
 
@@ -171,14 +184,36 @@ impl USState {
             USState::WestVirginia => "West Virginia",
             USState::Wisconsin => "Wisconsin",
             USState::Wyoming => "Wyoming",
+            USState::PuertoRico => "Puerto Rico",
+            USState::Guam => "Guam",
+            USState::VirginIslands => "U.S. Virgin Islands",
+            USState::NoState => "No State",
         }
     }
 
+    /// Community-property states, where married couples' wages belong half
+    /// to each spouse on separate returns
+    pub fn is_community_property(&self) -> bool {
+        matches!(
+            self,
+            USState::Arizona
+                | USState::California
+                | USState::Idaho
+                | USState::Louisiana
+                | USState::Nevada
+                | USState::NewMexico
+                | USState::Texas
+                | USState::Washington
+                | USState::Wisconsin
+        )
+    }
+
     /// States with no income tax
     pub fn has_no_income_tax(&self) -> bool {
         matches!(
             self,
-            USState::Alaska
+            USState::NoState
+                | USState::Alaska
                 | USState::Florida
                 | USState::Nevada
                 | USState::NewHampshire
@@ -240,6 +275,22 @@ impl USState {
         )
     }
 
+    /// Territories with their own income tax systems. Bona fide residents
+    /// owe no federal income tax on territory-source income: Puerto Rico
+    /// excludes it outright, while Guam and the Virgin Islands collect a
+    /// mirror of the federal tax in place of the IRS.
+    pub fn is_territory(&self) -> bool {
+        matches!(
+            self,
+            USState::PuertoRico | USState::Guam | USState::VirginIslands
+        )
+    }
+
+    /// Get all territories
+    pub fn territories() -> &'static [USState] {
+        &[USState::PuertoRico, USState::Guam, USState::VirginIslands]
+    }
+
     /// Get all states
     pub fn all() -> &'static [USState] {
         &[
@@ -299,59 +350,78 @@ impl USState {
 
     /// Parse from state code
     pub fn from_code(code: &str) -> Option<USState> {
+        code.parse().ok()
+    }
+}
+
+impl fmt::Display for USState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for USState {
+    type Err = ParseEnumError;
+
+    /// Parse a state code, case-insensitively
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
         match code.to_uppercase().as_str() {
-            "AL" => Some(USState::Alabama),
-            "AK" => Some(USState::Alaska),
-            "AZ" => Some(USState::Arizona),
-            "AR" => Some(USState::Arkansas),
-            "CA" => Some(USState::California),
-            "CO" => Some(USState::Colorado),
-            "CT" => Some(USState::Connecticut),
-            "DE" => Some(USState::Delaware),
-            "FL" => Some(USState::Florida),
-            "GA" => Some(USState::Georgia),
-            "HI" => Some(USState::Hawaii),
-            "ID" => Some(USState::Idaho),
-            "IL" => Some(USState::Illinois),
-            "IN" => Some(USState::Indiana),
-            "IA" => Some(USState::Iowa),
-            "KS" => Some(USState::Kansas),
-            "KY" => Some(USState::Kentucky),
-            "LA" => Some(USState::Louisiana),
-            "ME" => Some(USState::Maine),
-            "MD" => Some(USState::Maryland),
-            "MA" => Some(USState::Massachusetts),
-            "MI" => Some(USState::Michigan),
-            "MN" => Some(USState::Minnesota),
-            "MS" => Some(USState::Mississippi),
-            "MO" => Some(USState::Missouri),
-            "MT" => Some(USState::Montana),
-            "NE" => Some(USState::Nebraska),
-            "NV" => Some(USState::Nevada),
-            "NH" => Some(USState::NewHampshire),
-            "NJ" => Some(USState::NewJersey),
-            "NM" => Some(USState::NewMexico),
-            "NY" => Some(USState::NewYork),
-            "NC" => Some(USState::NorthCarolina),
-            "ND" => Some(USState::NorthDakota),
-            "OH" => Some(USState::Ohio),
-            "OK" => Some(USState::Oklahoma),
-            "OR" => Some(USState::Oregon),
-            "PA" => Some(USState::Pennsylvania),
-            "RI" => Some(USState::RhodeIsland),
-            "SC" => Some(USState::SouthCarolina),
-            "SD" => Some(USState::SouthDakota),
-            "TN" => Some(USState::Tennessee),
-            "TX" => Some(USState::Texas),
-            "UT" => Some(USState::Utah),
-            "VT" => Some(USState::Vermont),
-            "VA" => Some(USState::Virginia),
-            "WA" => Some(USState::Washington),
-            "DC" => Some(USState::WashingtonDC),
-            "WV" => Some(USState::WestVirginia),
-            "WI" => Some(USState::Wisconsin),
-            "WY" => Some(USState::Wyoming),
-            _ => None,
+            "AL" => Ok(USState::Alabama),
+            "AK" => Ok(USState::Alaska),
+            "AZ" => Ok(USState::Arizona),
+            "AR" => Ok(USState::Arkansas),
+            "CA" => Ok(USState::California),
+            "CO" => Ok(USState::Colorado),
+            "CT" => Ok(USState::Connecticut),
+            "DE" => Ok(USState::Delaware),
+            "FL" => Ok(USState::Florida),
+            "GA" => Ok(USState::Georgia),
+            "HI" => Ok(USState::Hawaii),
+            "ID" => Ok(USState::Idaho),
+            "IL" => Ok(USState::Illinois),
+            "IN" => Ok(USState::Indiana),
+            "IA" => Ok(USState::Iowa),
+            "KS" => Ok(USState::Kansas),
+            "KY" => Ok(USState::Kentucky),
+            "LA" => Ok(USState::Louisiana),
+            "ME" => Ok(USState::Maine),
+            "MD" => Ok(USState::Maryland),
+            "MA" => Ok(USState::Massachusetts),
+            "MI" => Ok(USState::Michigan),
+            "MN" => Ok(USState::Minnesota),
+            "MS" => Ok(USState::Mississippi),
+            "MO" => Ok(USState::Missouri),
+            "MT" => Ok(USState::Montana),
+            "NE" => Ok(USState::Nebraska),
+            "NV" => Ok(USState::Nevada),
+            "NH" => Ok(USState::NewHampshire),
+            "NJ" => Ok(USState::NewJersey),
+            "NM" => Ok(USState::NewMexico),
+            "NY" => Ok(USState::NewYork),
+            "NC" => Ok(USState::NorthCarolina),
+            "ND" => Ok(USState::NorthDakota),
+            "OH" => Ok(USState::Ohio),
+            "OK" => Ok(USState::Oklahoma),
+            "OR" => Ok(USState::Oregon),
+            "PA" => Ok(USState::Pennsylvania),
+            "RI" => Ok(USState::RhodeIsland),
+            "SC" => Ok(USState::SouthCarolina),
+            "SD" => Ok(USState::SouthDakota),
+            "TN" => Ok(USState::Tennessee),
+            "TX" => Ok(USState::Texas),
+            "UT" => Ok(USState::Utah),
+            "VT" => Ok(USState::Vermont),
+            "VA" => Ok(USState::Virginia),
+            "WA" => Ok(USState::Washington),
+            "DC" => Ok(USState::WashingtonDC),
+            "WV" => Ok(USState::WestVirginia),
+            "WI" => Ok(USState::Wisconsin),
+            "WY" => Ok(USState::Wyoming),
+            "PR" => Ok(USState::PuertoRico),
+            "GU" => Ok(USState::Guam),
+            "VI" => Ok(USState::VirginIslands),
+            "NONE" | "FOREIGN" => Ok(USState::NoState),
+            _ => Err(ParseEnumError::new("state code", code)),
         }
     }
 }
@@ -367,6 +437,7 @@ mod tests {
         assert!(USState::Nevada.has_no_income_tax());
         assert!(!USState::California.has_no_income_tax());
         assert!(!USState::NewYork.has_no_income_tax());
+        assert!(USState::NoState.has_no_income_tax());
     }
 
     #[test]
@@ -389,10 +460,31 @@ mod tests {
         assert_eq!(USState::from_code("ca"), Some(USState::California));
         assert_eq!(USState::from_code("TX"), Some(USState::Texas));
         assert_eq!(USState::from_code("XX"), None);
+        assert_eq!(USState::from_code("pr"), Some(USState::PuertoRico));
+        assert_eq!(USState::from_code("foreign"), Some(USState::NoState));
+    }
+
+    #[test]
+    fn test_display_matches_code() {
+        for state in USState::all().iter().chain(USState::territories()) {
+            assert_eq!(state.to_string(), state.code());
+            assert_eq!(state.to_string().parse::<USState>(), Ok(*state));
+        }
+        assert_eq!(
+            "XX".parse::<USState>().unwrap_err().to_string(),
+            "invalid state code: XX"
+        );
     }
 
     #[test]
     fn test_all_states_count() {
         assert_eq!(USState::all().len(), 51); // 50 states + DC
     }
+
+    #[test]
+    fn test_territories() {
+        assert_eq!(USState::territories().len(), 3);
+        assert!(USState::territories().iter().all(|t| t.is_territory()));
+        assert!(!USState::all().iter().any(|s| s.is_territory()));
+    }
 }
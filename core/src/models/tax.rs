@@ -2,6 +2,11 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::rate::Rate;
+use super::ParseEnumError;
 
 /// IRS filing status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -46,13 +51,69 @@ impl FilingStatus {
     }
 }
 
+impl fmt::Display for FilingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FilingStatus {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "single" => Ok(FilingStatus::Single),
+            "married_filing_jointly" => Ok(FilingStatus::MarriedFilingJointly),
+            "married_filing_separately" => Ok(FilingStatus::MarriedFilingSeparately),
+            "head_of_household" => Ok(FilingStatus::HeadOfHousehold),
+            "qualifying_widower" => Ok(FilingStatus::QualifyingWidower),
+            _ => Err(ParseEnumError::new("filing status", s)),
+        }
+    }
+}
+
+/// State disability insurance coverage for the employee's paycheck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SdiCoverage {
+    /// Withheld at the state program's rate
+    #[default]
+    State,
+    /// Not withheld (e.g. the employer self-insures the benefit)
+    Excluded,
+    /// Employer voluntary plan withholding at its own rate, up to the state wage base
+    VoluntaryPlan { rate: Decimal },
+}
+
+/// State payroll program withheld from employee wages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PayrollProgramKind {
+    Disability,
+    PaidFamilyLeave,
+    /// Unemployment and workforce development contributions
+    Unemployment,
+    LongTermCare,
+}
+
+/// One payroll program's employee contribution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayrollContribution {
+    pub kind: PayrollProgramKind,
+    /// Program name as it appears on a pay stub (e.g. "FLI", "PFML")
+    pub name: String,
+    pub amount: Decimal,
+}
+
 /// Tax bracket definition
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TaxBracket {
     pub floor: Decimal,
     pub ceiling: Option<Decimal>,
-    pub rate: Decimal,
+    pub rate: Rate,
     pub base_tax: Decimal,
+    /// Tax added at the floor beyond the lower brackets, included in
+    /// `base_tax` (Ohio's $360.69 at the end of its zero bracket)
+    #[serde(default)]
+    pub step: Decimal,
 }
 
 impl TaxBracket {
@@ -60,11 +121,17 @@ impl TaxBracket {
         Self {
             floor,
             ceiling,
-            rate,
+            rate: Rate::from_fraction(rate),
             base_tax,
+            step: Decimal::ZERO,
         }
     }
 
+    pub fn with_step(mut self, step: Decimal) -> Self {
+        self.step = step;
+        self
+    }
+
     /// Calculate tax using the base tax formula
     /// Tax = BaseTax + (Income - Floor) × Rate
     pub fn calculate(&self, taxable_income: Decimal) -> Decimal {
@@ -86,7 +153,7 @@ impl TaxBracket {
 pub struct BracketAmount {
     pub floor: Decimal,
     pub ceiling: Option<Decimal>,
-    pub rate: Decimal,
+    pub rate: Rate,
     pub taxable_in_bracket: Decimal,
     pub tax_paid: Decimal,
 }
@@ -99,6 +166,36 @@ pub struct FederalTaxResult {
     pub marginal_rate: Decimal,
     pub effective_rate: Decimal,
     pub bracket_breakdown: Vec<BracketAmount>,
+    /// Nonrefundable credits already subtracted from `tax`
+    #[serde(default)]
+    pub credits: Decimal,
+    /// Standard or itemized deduction subtracted from AGI, whichever is larger
+    #[serde(default)]
+    pub deduction: Decimal,
+    /// Whether `deduction` is the itemized total
+    #[serde(default)]
+    pub itemized: bool,
+    /// Qualified business income deduction on self-employment income
+    #[serde(default)]
+    pub qbi_deduction: Decimal,
+    /// Tax on ordinary income at the bracket rates, before credits
+    #[serde(default)]
+    pub ordinary_tax: Decimal,
+    /// Tax on long-term gains and qualified dividends at 0/15/20%, before credits
+    #[serde(default)]
+    pub preferential_tax: Decimal,
+}
+
+impl FederalTaxResult {
+    /// Subtract nonrefundable credits from the tax
+    pub fn apply_credits(&mut self, credits: Decimal) {
+        let credits = credits.min(self.tax).max(Decimal::ZERO);
+        self.tax -= credits;
+        self.credits += credits;
+        if self.taxable_income > Decimal::ZERO {
+            self.effective_rate = self.tax / self.taxable_income;
+        }
+    }
 }
 
 impl Default for FederalTaxResult {
@@ -109,10 +206,40 @@ impl Default for FederalTaxResult {
             marginal_rate: Decimal::ZERO,
             effective_rate: Decimal::ZERO,
             bracket_breakdown: vec![],
+            credits: Decimal::ZERO,
+            deduction: Decimal::ZERO,
+            itemized: false,
+            qbi_deduction: Decimal::ZERO,
+            ordinary_tax: Decimal::ZERO,
+            preferential_tax: Decimal::ZERO,
         }
     }
 }
 
+/// Federal credits, split by whether they can exceed the tax
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaxCredits {
+    /// Child Tax Credit used against income tax
+    pub child_tax_credit: Decimal,
+    /// Credit for Other Dependents used against income tax
+    pub other_dependents_credit: Decimal,
+    /// Energy, clean vehicle, and adoption credits used against income tax
+    pub other_nonrefundable: Decimal,
+    /// Refundable part of the Child Tax Credit
+    pub additional_child_tax_credit: Decimal,
+    pub earned_income_credit: Decimal,
+    /// Credits limited to income tax, already subtracted from the federal tax
+    pub nonrefundable: Decimal,
+    /// Credits paid out beyond income tax, subtracted from total taxes
+    pub refundable: Decimal,
+}
+
+impl TaxCredits {
+    pub fn total(&self) -> Decimal {
+        self.nonrefundable + self.refundable
+    }
+}
+
 /// FICA calculation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FicaResult {
@@ -123,6 +250,19 @@ pub struct FicaResult {
     pub total: Decimal,
 }
 
+/// Self-employment (SECA) tax on a year's net profit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SelfEmploymentTax {
+    /// Net profit × 92.35%
+    pub net_earnings: Decimal,
+    pub social_security: Decimal,
+    pub medicare: Decimal,
+    pub additional_medicare: Decimal,
+    pub total: Decimal,
+    /// Employer-equivalent half of Social Security and Medicare, deducted from AGI
+    pub deductible_half: Decimal,
+}
+
 impl Default for FicaResult {
     fn default() -> Self {
         Self {
@@ -142,10 +282,25 @@ pub struct StateTaxResult {
     pub taxable_income: Decimal,
     pub income_tax: Decimal,
     pub local_tax: Decimal,
+    #[serde(default)]
+    pub school_district_tax: Decimal,
+    /// All employee payroll-program contributions, the sum of `payroll_programs`
     pub sdi: Decimal,
     pub total_tax: Decimal,
     pub effective_rate: Decimal,
     pub bracket_breakdown: Option<Vec<BracketAmount>>,
+    /// Whether `local_tax` used a caller-supplied rate instead of an estimate
+    #[serde(default)]
+    pub local_tax_overridden: bool,
+    /// Whether `local_tax` is the state's average rate rather than a known locality's
+    #[serde(default)]
+    pub local_tax_estimated: bool,
+    /// Whether the state had no schedule for the filing status and single was used
+    #[serde(default)]
+    pub single_brackets_used: bool,
+    /// Disability, family leave, and other payroll programs making up `sdi`
+    #[serde(default)]
+    pub payroll_programs: Vec<PayrollContribution>,
 }
 
 impl Default for StateTaxResult {
@@ -155,22 +310,117 @@ impl Default for StateTaxResult {
             taxable_income: Decimal::ZERO,
             income_tax: Decimal::ZERO,
             local_tax: Decimal::ZERO,
+            school_district_tax: Decimal::ZERO,
             sdi: Decimal::ZERO,
             total_tax: Decimal::ZERO,
             effective_rate: Decimal::ZERO,
             bracket_breakdown: None,
+            local_tax_overridden: false,
+            local_tax_estimated: false,
+            single_brackets_used: false,
+            payroll_programs: vec![],
         }
     }
 }
 
+/// How reliable a calculated amount is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Confidence {
+    /// Computed from published schedules for the taxpayer's situation
+    #[default]
+    Exact,
+    /// Approximated (average rates, fallback schedules, sourcing estimates)
+    Estimated,
+}
+
+/// Confidence for each line of a `TaxBreakdown`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakdownConfidence {
+    pub federal: Confidence,
+    pub state_income_tax: Confidence,
+    pub local_tax: Confidence,
+    pub school_district_tax: Confidence,
+    pub sdi: Confidence,
+    pub work_state: Confidence,
+    pub fica: Confidence,
+}
+
+impl BreakdownConfidence {
+    /// Whether any line is an approximation
+    pub fn has_estimates(&self) -> bool {
+        [
+            self.federal,
+            self.state_income_tax,
+            self.local_tax,
+            self.school_district_tax,
+            self.sdi,
+            self.work_state,
+            self.fica,
+        ]
+        .contains(&Confidence::Estimated)
+    }
+}
+
+/// Plain-language description of how each `TaxBreakdown` line was computed
+///
+/// Lines that don't apply to the taxpayer are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakdownExplanations {
+    pub federal: Option<String>,
+    pub state_income_tax: Option<String>,
+    pub local_tax: Option<String>,
+    pub school_district_tax: Option<String>,
+    pub sdi: Option<String>,
+    pub work_state: Option<String>,
+    pub social_security: Option<String>,
+    pub medicare: Option<String>,
+    pub additional_medicare: Option<String>,
+    #[serde(default)]
+    pub self_employment: Option<String>,
+}
+
+impl BreakdownExplanations {
+    /// Explanations that apply, in breakdown display order
+    pub fn lines(&self) -> Vec<&str> {
+        [
+            &self.federal,
+            &self.state_income_tax,
+            &self.local_tax,
+            &self.school_district_tax,
+            &self.sdi,
+            &self.work_state,
+            &self.social_security,
+            &self.medicare,
+            &self.additional_medicare,
+            &self.self_employment,
+        ]
+        .into_iter()
+        .filter_map(|line| line.as_deref())
+        .collect()
+    }
+}
+
 /// Complete tax breakdown
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaxBreakdown {
     pub federal: FederalTaxResult,
     pub state: StateTaxResult,
+    /// Nonresident tax owed to a separate work state
+    #[serde(default)]
+    pub work_state: Option<StateTaxResult>,
     pub fica: FicaResult,
+    /// SECA tax on self-employment income
+    #[serde(default)]
+    pub self_employment: SelfEmploymentTax,
+    #[serde(default)]
+    pub credits: TaxCredits,
+    /// Federal, state, FICA, and self-employment taxes less refundable credits
     pub total_taxes: Decimal,
     pub effective_rate: Decimal,
+    #[serde(default)]
+    pub confidence: BreakdownConfidence,
+    #[serde(default)]
+    pub explanations: BreakdownExplanations,
 }
 
 impl Default for TaxBreakdown {
@@ -178,49 +428,25 @@ impl Default for TaxBreakdown {
         Self {
             federal: FederalTaxResult::default(),
             state: StateTaxResult::default(),
+            work_state: None,
             fica: FicaResult::default(),
+            self_employment: SelfEmploymentTax::default(),
+            credits: TaxCredits::default(),
             total_taxes: Decimal::ZERO,
             effective_rate: Decimal::ZERO,
+            confidence: BreakdownConfidence::default(),
+            explanations: BreakdownExplanations::default(),
         }
     }
 }
 
 /// Effective rates summary
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EffectiveRates {
-    pub federal: Decimal,
-    pub state: Decimal,
-    pub fica: Decimal,
-    pub total: Decimal,
-}
-
-impl EffectiveRates {
-    pub fn federal_percent(&self) -> Decimal {
-        self.federal * Decimal::from(100)
-    }
-
-    pub fn state_percent(&self) -> Decimal {
-        self.state * Decimal::from(100)
-    }
-
-    pub fn fica_percent(&self) -> Decimal {
-        self.fica * Decimal::from(100)
-    }
-
-    pub fn total_percent(&self) -> Decimal {
-        self.total * Decimal::from(100)
-    }
-}
-
-impl Default for EffectiveRates {
-    fn default() -> Self {
-        Self {
-            federal: Decimal::ZERO,
-            state: Decimal::ZERO,
-            fica: Decimal::ZERO,
-            total: Decimal::ZERO,
-        }
-    }
+    pub federal: Rate,
+    pub state: Rate,
+    pub fica: Rate,
+    pub total: Rate,
 }
 
 #[cfg(test)]
@@ -239,6 +465,20 @@ mod tests {
         assert_eq!(tax, dec!(5426) + (dec!(80000) - dec!(47150)) * dec!(0.22));
     }
 
+    #[test]
+    fn test_filing_status_round_trip() {
+        for status in [
+            FilingStatus::Single,
+            FilingStatus::MarriedFilingJointly,
+            FilingStatus::MarriedFilingSeparately,
+            FilingStatus::HeadOfHousehold,
+            FilingStatus::QualifyingWidower,
+        ] {
+            assert_eq!(status.to_string().parse::<FilingStatus>(), Ok(status));
+        }
+        assert!("married".parse::<FilingStatus>().is_err());
+    }
+
     #[test]
     fn test_bracket_contains() {
         let bracket = TaxBracket::new(dec!(47150), Some(dec!(100525)), dec!(0.22), dec!(5426));
@@ -0,0 +1,223 @@
+//! Versioned JSON for saved inputs and results
+//!
+//! Saved documents carry a top-level `schema_version` alongside the struct's
+//! own fields. Reading upgrades an older document one version at a time
+//! before deserializing it. A new field only needs a migration step when
+//! `#[serde(default)]` can't reconstruct it from what older documents held.
+//!
+//! Versions:
+//! - 1: documents written without `schema_version`
+//! - 2: adds `schema_version`. Results list the disability contribution in
+//!   `payroll_programs`, which version 1 only reported as the `sdi` total.
+
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::calculators::state::disability_program_name;
+use crate::engine::{ScenarioComparison, TaxCalculationInput, TaxCalculationResult};
+use crate::models::state::USState;
+use crate::models::tax::PayrollProgramKind;
+
+/// Version written by `to_json`
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Field holding the version in a saved document
+const VERSION_FIELD: &str = "schema_version";
+
+/// Version assumed for documents without a version field
+const UNVERSIONED: u32 = 1;
+
+/// Error reading or writing a versioned document
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaError {
+    #[error("schema version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+    #[error("schema_version must be a whole number")]
+    InvalidVersion,
+    #[error("document is not a JSON object")]
+    NotAnObject,
+    #[error("invalid document: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A type persisted as a versioned document
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Upgrade a document from `from_version` to the next version in place
+    fn migrate(_document: &mut Map<String, Value>, _from_version: u32) {}
+}
+
+impl Versioned for TaxCalculationInput {}
+
+impl Versioned for TaxCalculationResult {
+    fn migrate(document: &mut Map<String, Value>, from_version: u32) {
+        let breakdown = document
+            .get_mut("tax_breakdown")
+            .and_then(Value::as_object_mut);
+        if let (1, Some(breakdown)) = (from_version, breakdown) {
+            for key in ["state", "work_state"] {
+                if let Some(state) = breakdown.get_mut(key).and_then(Value::as_object_mut) {
+                    itemize_disability(state);
+                }
+            }
+        }
+    }
+}
+
+impl Versioned for ScenarioComparison {
+    fn migrate(document: &mut Map<String, Value>, from_version: u32) {
+        for key in ["base", "scenario"] {
+            if let Some(result) = document.get_mut(key).and_then(Value::as_object_mut) {
+                TaxCalculationResult::migrate(result, from_version);
+            }
+        }
+    }
+}
+
+/// Version 1 state results only had the `sdi` total, which was all disability
+fn itemize_disability(state: &mut Map<String, Value>) {
+    if state.contains_key("payroll_programs") {
+        return;
+    }
+    let sdi = state.get("sdi").cloned().unwrap_or(Value::Null);
+    let is_zero = sdi
+        .as_str()
+        .and_then(|s| s.parse::<Decimal>().ok())
+        .is_none_or(|amount| amount.is_zero());
+    let programs = if is_zero {
+        Vec::new()
+    } else {
+        let name = state
+            .get("state_code")
+            .and_then(Value::as_str)
+            .and_then(|code| code.parse::<USState>().ok())
+            .map(disability_program_name)
+            .unwrap_or("SDI");
+        vec![serde_json::json!({
+            "kind": PayrollProgramKind::Disability,
+            "name": name,
+            "amount": sdi,
+        })]
+    };
+    state.insert("payroll_programs".to_string(), Value::Array(programs));
+}
+
+/// Serialize with the current schema version
+pub fn to_json<T: Versioned>(document: &T) -> Result<String, SchemaError> {
+    Ok(serde_json::to_string(&to_value(document)?)?)
+}
+
+/// Deserialize a document written by this or any earlier schema version
+pub fn from_json<T: Versioned>(json: &str) -> Result<T, SchemaError> {
+    from_value(serde_json::from_str(json)?)
+}
+
+/// Serialize to a JSON value with the current schema version
+pub fn to_value<T: Versioned>(document: &T) -> Result<Value, SchemaError> {
+    let mut value = serde_json::to_value(document)?;
+    let object = value.as_object_mut().ok_or(SchemaError::NotAnObject)?;
+    object.insert(VERSION_FIELD.to_string(), Value::from(SCHEMA_VERSION));
+    Ok(value)
+}
+
+/// Deserialize an already-parsed document of any supported version
+pub fn from_value<T: Versioned>(mut value: Value) -> Result<T, SchemaError> {
+    let object = value.as_object_mut().ok_or(SchemaError::NotAnObject)?;
+
+    let found = match object.remove(VERSION_FIELD) {
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or(SchemaError::InvalidVersion)?,
+        None => UNVERSIONED,
+    };
+    if found > SCHEMA_VERSION {
+        return Err(SchemaError::UnsupportedVersion {
+            found,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    for version in found..SCHEMA_VERSION {
+        T::migrate(object, version);
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::engine::TaxCalculationEngine;
+    use crate::models::tax::FilingStatus;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn california_result(data: &EmbeddedTaxData) -> TaxCalculationResult {
+        TaxCalculationEngine::new(data, 2024).calculate(&TaxCalculationInput {
+            gross_income: dec!(100000),
+            filing_status: FilingStatus::Single,
+            state: USState::California,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_round_trip_writes_version() {
+        let data = setup();
+        let result = california_result(&data);
+
+        let json = to_json(&result).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+
+        let read: TaxCalculationResult = from_json(&json).unwrap();
+        assert_eq!(read.income.net, result.income.net);
+        assert_eq!(
+            read.tax_breakdown.state.payroll_programs,
+            result.tax_breakdown.state.payroll_programs
+        );
+
+        let input = TaxCalculationInput::default();
+        let read: TaxCalculationInput = from_json(&to_json(&input).unwrap()).unwrap();
+        assert_eq!(read.gross_income, input.gross_income);
+    }
+
+    #[test]
+    fn test_migrates_unversioned_result() {
+        let data = setup();
+        let result = california_result(&data);
+
+        // A version 1 document: no version and no itemized payroll programs
+        let mut value = serde_json::to_value(&result).unwrap();
+        value["tax_breakdown"]["state"]
+            .as_object_mut()
+            .unwrap()
+            .remove("payroll_programs");
+
+        let read: TaxCalculationResult = from_json(&value.to_string()).unwrap();
+        let programs = &read.tax_breakdown.state.payroll_programs;
+        assert_eq!(programs.len(), 1);
+        assert_eq!(programs[0].kind, PayrollProgramKind::Disability);
+        assert_eq!(programs[0].name, "SDI");
+        assert_eq!(programs[0].amount, result.tax_breakdown.state.sdi);
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        let json = r#"{"schema_version": 99, "gross_income": "1"}"#;
+
+        assert!(matches!(
+            from_json::<TaxCalculationInput>(json),
+            Err(SchemaError::UnsupportedVersion { found: 99, .. })
+        ));
+        assert!(matches!(
+            from_json::<TaxCalculationInput>("[1, 2]"),
+            Err(SchemaError::NotAnObject)
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+//! HTTP microservice over the calculation engine
+//!
+//! - `POST /calculate`: a `TaxCalculationInput` document, answered with a
+//!   `TaxCalculationResult`
+//! - `POST /compare`: `{"base": input, "scenario": input}`, answered with a
+//!   `ScenarioComparison`
+//! - `GET /states`: every state with its code and whether it taxes wages
+//!
+//! Bodies use the versioned schema, so scenarios saved by the apps can be
+//! posted unchanged and responses carry `schema_version`.
+
+use std::io;
+use std::net::SocketAddr;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+
+use crate::data::embedded::get_embedded_data;
+use crate::data::TaxDataProvider;
+use crate::engine::{TaxCalculationEngine, TaxCalculationInput};
+use crate::models::state::USState;
+use crate::schema::{self, SchemaError};
+
+/// Error returned to the client as `{"error": message}`
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl ToString) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.to_string(),
+        }
+    }
+
+    fn unprocessable(message: impl ToString) -> Self {
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<SchemaError> for ApiError {
+    fn from(e: SchemaError) -> Self {
+        Self::bad_request(e)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Tax data and year every request is calculated against
+#[derive(Clone, Copy)]
+struct ServiceState {
+    data: &'static dyn TaxDataProvider,
+    year: u32,
+}
+
+impl ServiceState {
+    fn engine(&self) -> TaxCalculationEngine<'static> {
+        TaxCalculationEngine::new(self.data, self.year)
+    }
+
+    /// Decode, validate, and check data coverage for one input document
+    fn input(&self, document: Value) -> Result<TaxCalculationInput, ApiError> {
+        let input: TaxCalculationInput = schema::from_value(document)?;
+        input.validate().map_err(ApiError::unprocessable)?;
+        self.engine()
+            .validate_data(&input)
+            .map_err(ApiError::unprocessable)?;
+        Ok(input)
+    }
+
+    fn calculate(&self, document: Value) -> Result<Value, ApiError> {
+        let input = self.input(document)?;
+        Ok(schema::to_value(&self.engine().calculate(&input))?)
+    }
+
+    fn compare(&self, mut request: Value) -> Result<Value, ApiError> {
+        let mut take = |key: &str| {
+            request
+                .get_mut(key)
+                .map(Value::take)
+                .ok_or_else(|| ApiError::bad_request(format!("missing \"{key}\"")))
+        };
+        let base = self.input(take("base")?)?;
+        let scenario = self.input(take("scenario")?)?;
+        Ok(schema::to_value(
+            &self.engine().compare_scenarios(&base, &scenario),
+        )?)
+    }
+}
+
+fn states() -> Value {
+    USState::all()
+        .iter()
+        .map(|state| {
+            json!({
+                "code": state.code(),
+                "name": state.name(),
+                "has_income_tax": !state.has_no_income_tax(),
+            })
+        })
+        .collect()
+}
+
+/// Routes served over the embedded data for a tax year
+pub fn router(year: u32) -> Router {
+    let state = ServiceState {
+        data: get_embedded_data(),
+        year,
+    };
+    Router::new()
+        .route(
+            "/calculate",
+            post(
+                |State(s): State<ServiceState>, Json(body): Json<Value>| async move {
+                    s.calculate(body).map(Json)
+                },
+            ),
+        )
+        .route(
+            "/compare",
+            post(
+                |State(s): State<ServiceState>, Json(body): Json<Value>| async move {
+                    s.compare(body).map(Json)
+                },
+            ),
+        )
+        .route("/states", get(|| async { Json(states()) }))
+        .with_state(state)
+}
+
+/// Serve the routes on `addr` until the process exits
+pub async fn serve(addr: SocketAddr, year: u32) -> io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(year)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tax::FilingStatus;
+
+    fn service() -> ServiceState {
+        ServiceState {
+            data: get_embedded_data(),
+            year: 2024,
+        }
+    }
+
+    #[test]
+    fn test_calculate_returns_versioned_result() {
+        let response = service()
+            .calculate(json!({
+                "gross_income": "100000",
+                "filing_status": "Single",
+                "state": "Texas",
+                "pre_tax_deductions": "0",
+                "post_tax_deductions": "0",
+                "traditional_401k": "0",
+                "roth_401k": "0",
+            }))
+            .unwrap();
+
+        assert_eq!(response["schema_version"], schema::SCHEMA_VERSION);
+        assert_eq!(response["income"]["gross"], "100000");
+    }
+
+    #[test]
+    fn test_compare_and_errors() {
+        let input = |gross: &str, state: &str| {
+            schema::to_value(&TaxCalculationInput {
+                gross_income: gross.parse().unwrap(),
+                state: state.parse().unwrap(),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        let response = service()
+            .compare(json!({
+                "base": input("100000", "CA"),
+                "scenario": input("100000", "TX"),
+            }))
+            .unwrap();
+        assert_eq!(response["schema_version"], schema::SCHEMA_VERSION);
+        assert!(response["net_difference"]
+            .as_str()
+            .unwrap()
+            .parse::<f64>()
+            .is_ok_and(|d| d > 0.0));
+
+        let missing = service().compare(json!({ "base": input("1", "CA") }));
+        assert_eq!(missing.unwrap_err().status, StatusCode::BAD_REQUEST);
+
+        let negative = service().calculate(input("-5", "CA"));
+        assert_eq!(
+            negative.unwrap_err().status,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn test_state_brackets_fallback_is_a_warning() {
+        let response = service()
+            .calculate(
+                schema::to_value(&TaxCalculationInput {
+                    gross_income: "100000".parse().unwrap(),
+                    filing_status: FilingStatus::MarriedFilingJointly,
+                    state: USState::NewYork,
+                    ..Default::default()
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        assert!(response["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|w| w.get("StateBracketsFallback").is_some()));
+    }
+
+    #[test]
+    fn test_states_listing() {
+        let listing = states();
+        let states = listing.as_array().unwrap();
+
+        assert_eq!(states.len(), USState::all().len());
+        let texas = states.iter().find(|s| s["code"] == "TX").unwrap();
+        assert_eq!(texas["has_income_tax"], false);
+    }
+}
@@ -0,0 +1,257 @@
+//! Named scenario persistence
+//!
+//! Scenarios live in a single JSON file written through the versioned
+//! schema, so a store saved by an older release migrates on open. Every
+//! mutation rewrites the file via a temporary sibling and a rename, so a
+//! crash mid-write leaves the previous contents intact.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::engine::{TaxCalculationInput, TaxCalculationResult};
+use crate::schema::{self, SchemaError, Versioned};
+
+/// Error reading or writing a scenario store
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("scenario store I/O failed: {0}")]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    #[error("scenario name cannot be empty")]
+    EmptyName,
+    #[error("no scenario named {0:?}")]
+    NotFound(String),
+}
+
+/// A named input with the result last calculated for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedScenario {
+    pub name: String,
+    pub input: TaxCalculationInput,
+    /// Result calculated when the scenario was saved, if the caller cached one
+    #[serde(default)]
+    pub result: Option<TaxCalculationResult>,
+}
+
+/// On-disk layout of a store
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreDocument {
+    scenarios: Vec<SavedScenario>,
+}
+
+impl Versioned for StoreDocument {
+    fn migrate(document: &mut Map<String, Value>, from_version: u32) {
+        let cached_results = document
+            .get_mut("scenarios")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+            .filter_map(|scenario| scenario.get_mut("result"))
+            .filter_map(Value::as_object_mut);
+        for result in cached_results {
+            TaxCalculationResult::migrate(result, from_version);
+        }
+    }
+}
+
+/// JSON-file store of named scenarios, ordered by name
+#[derive(Debug)]
+pub struct ScenarioStore {
+    path: PathBuf,
+    scenarios: BTreeMap<String, SavedScenario>,
+}
+
+impl ScenarioStore {
+    /// Open the store at `path`, starting empty if the file doesn't exist yet
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let path = path.into();
+        let document: StoreDocument = match fs::read_to_string(&path) {
+            Ok(json) => schema::from_json(&json)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => StoreDocument::default(),
+            Err(e) => return Err(e.into()),
+        };
+        let scenarios = document
+            .scenarios
+            .into_iter()
+            .map(|scenario| (scenario.name.clone(), scenario))
+            .collect();
+        Ok(Self { path, scenarios })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Scenario names in order
+    pub fn names(&self) -> Vec<String> {
+        self.scenarios.keys().cloned().collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SavedScenario> {
+        self.scenarios.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.scenarios.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenarios.is_empty()
+    }
+
+    /// Create or replace a scenario and write the store
+    pub fn save(
+        &mut self,
+        name: &str,
+        input: TaxCalculationInput,
+        result: Option<TaxCalculationResult>,
+    ) -> Result<(), StorageError> {
+        if name.trim().is_empty() {
+            return Err(StorageError::EmptyName);
+        }
+        self.scenarios.insert(
+            name.to_string(),
+            SavedScenario {
+                name: name.to_string(),
+                input,
+                result,
+            },
+        );
+        self.flush()
+    }
+
+    /// Rename a scenario, replacing any scenario already using the new name
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), StorageError> {
+        if to.trim().is_empty() {
+            return Err(StorageError::EmptyName);
+        }
+        let mut scenario = self
+            .scenarios
+            .remove(from)
+            .ok_or_else(|| StorageError::NotFound(from.to_string()))?;
+        scenario.name = to.to_string();
+        self.scenarios.insert(to.to_string(), scenario);
+        self.flush()
+    }
+
+    /// Delete a scenario, returning whether it existed
+    pub fn delete(&mut self, name: &str) -> Result<bool, StorageError> {
+        if self.scenarios.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.flush()?;
+        Ok(true)
+    }
+
+    fn flush(&self) -> Result<(), StorageError> {
+        let document = StoreDocument {
+            scenarios: self.scenarios.values().cloned().collect(),
+        };
+        let json = schema::to_json(&document)?;
+
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        fs::write(&temp, json)?;
+        fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::embedded::EmbeddedTaxData;
+    use crate::engine::TaxCalculationEngine;
+    use crate::models::state::USState;
+    use rust_decimal_macros::dec;
+
+    fn setup() -> EmbeddedTaxData {
+        EmbeddedTaxData::new()
+    }
+
+    fn store_path(test: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("takehome-{}", std::process::id()))
+            .join(format!("{test}.json"));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn input(gross_income: rust_decimal::Decimal, state: USState) -> TaxCalculationInput {
+        TaxCalculationInput {
+            gross_income,
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_crud_persists_across_opens() {
+        let path = store_path("crud");
+        let mut store = ScenarioStore::open(&path).unwrap();
+        assert!(store.is_empty());
+
+        store
+            .save("Current job", input(dec!(95000), USState::Ohio), None)
+            .unwrap();
+        store
+            .save("Offer", input(dec!(120000), USState::Texas), None)
+            .unwrap();
+        store.rename("Offer", "Austin offer").unwrap();
+        assert!(store.delete("Current job").unwrap());
+        assert!(!store.delete("Current job").unwrap());
+
+        let reopened = ScenarioStore::open(&path).unwrap();
+        assert_eq!(reopened.names(), vec!["Austin offer"]);
+        let saved = reopened.get("Austin offer").unwrap();
+        assert_eq!(saved.name, "Austin offer");
+        assert_eq!(saved.input.gross_income, dec!(120000));
+        assert_eq!(saved.input.state, USState::Texas);
+
+        assert!(matches!(
+            store.save(" ", TaxCalculationInput::default(), None),
+            Err(StorageError::EmptyName)
+        ));
+        assert!(matches!(
+            store.rename("Missing", "Other"),
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_cached_result_round_trips() {
+        let data = setup();
+        let engine = TaxCalculationEngine::new(&data, 2024);
+        let path = store_path("cached");
+
+        let scenario = input(dec!(100000), USState::California);
+        let result = engine.calculate(&scenario);
+        let mut store = ScenarioStore::open(&path).unwrap();
+        store.save("CA", scenario, Some(result.clone())).unwrap();
+
+        let reopened = ScenarioStore::open(&path).unwrap();
+        let cached = reopened.get("CA").unwrap().result.as_ref().unwrap();
+        assert_eq!(cached.income.net, result.income.net);
+    }
+
+    #[test]
+    fn test_open_rejects_corrupt_file() {
+        let path = store_path("corrupt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "not json").unwrap();
+
+        assert!(matches!(
+            ScenarioStore::open(&path),
+            Err(StorageError::Schema(_))
+        ));
+    }
+}